@@ -0,0 +1,74 @@
+//! Compares the header parsing used by the server with a naive line parser, which
+//! validates every line as UTF-8 before splitting it. Run with `cargo bench`.
+use http::parser::{self, ParsingMode};
+use std::{
+    hint::black_box,
+    io::Cursor,
+    time::{Duration, Instant},
+};
+
+const ITERATIONS: u32 = 100_000;
+
+const REQUEST: &str = "GET /user/123?name=Alice HTTP/1.1\r\n\
+Host: localhost:8080\r\n\
+User-Agent: Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0\r\n\
+Accept: text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8\r\n\
+Accept-Language: en-US,en;q=0.5\r\n\
+Accept-Encoding: gzip, deflate, br\r\n\
+Connection: keep-alive\r\n\
+Cookie: session=0123456789abcdef; theme=dark\r\n\
+Cache-Control: max-age=0\r\n\
+X-Request-Id: 7f3c2a1e-5b8d-4f6a-9c0e-1d2b3a4c5e6f\r\n\
+\r\n";
+
+/// [`measure`] will run `parse` [`ITERATIONS`] times and return how long it took
+/// on average.
+fn measure(parse: impl Fn(&[u8]) -> usize) -> Duration {
+    // warm up caches and the worker's arena
+    for _ in 0..ITERATIONS / 10 {
+        black_box(parse(black_box(REQUEST.as_bytes())));
+    }
+
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(parse(black_box(REQUEST.as_bytes())));
+    }
+    started.elapsed() / ITERATIONS
+}
+
+/// [`split_lines_naively`] will validate every header line as UTF-8 and split it
+/// with [`str::split_once`], which is how headers were parsed before the scanner.
+fn split_lines_naively(request: &[u8]) -> usize {
+    request
+        .split(|byte| *byte == b'\n')
+        .skip(1)
+        .filter_map(|line| std::str::from_utf8(line).ok())
+        .filter_map(|line| line.trim_end_matches('\r').split_once(':'))
+        .map(|(name, value)| name.trim().len() + value.trim().len())
+        .sum()
+}
+
+/// [`split_lines_with_scanner`] will split every header line with
+/// [`parser::split_header_line`], without validating any line as UTF-8.
+fn split_lines_with_scanner(request: &[u8]) -> usize {
+    request
+        .split(|byte| *byte == b'\n')
+        .skip(1)
+        .filter_map(|line| parser::split_header_line(line.strip_suffix(b"\r").unwrap_or(line)))
+        .map(|(name, value)| name.len() + value.len())
+        .sum()
+}
+
+fn main() {
+    let naive = measure(split_lines_naively);
+    let scanner = measure(split_lines_with_scanner);
+    let full = measure(|request| {
+        parser::read_request_head(&mut Cursor::new(request), ParsingMode::Strict)
+            .map(|head| head.headers.len())
+            .unwrap_or_default()
+    });
+
+    println!("split header lines (naive):   {naive:?} per request");
+    println!("split header lines (scanner): {scanner:?} per request");
+    println!("read_request_head (strict):   {full:?} per request");
+}
@@ -0,0 +1,291 @@
+use std::{
+    cell::RefCell,
+    io::{self, BufRead, Read},
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// [`REQUEST_ARENA_CAPACITY`] is the starting capacity of every worker's
+/// [`Arena`], which fits the request line and headers of most requests.
+pub const REQUEST_ARENA_CAPACITY: usize = 8 * 1024;
+
+/// [`REQUEST_ARENA_MAX_CAPACITY`] is the most capacity a worker's [`Arena`] keeps
+/// between requests, where one that grew past it for a large request is shrunk
+/// back to [`REQUEST_ARENA_CAPACITY`], so it doesn't pin that memory forever.
+pub const REQUEST_ARENA_MAX_CAPACITY: usize = 64 * 1024;
+
+/// [`REQUESTS`] counts every request that used an [`Arena`] through
+/// [`with_request_arena`].
+static REQUESTS: AtomicU64 = AtomicU64::new(0);
+
+/// [`BYTES_ALLOCATED`] counts every byte allocated in an [`Arena`] through
+/// [`with_request_arena`].
+static BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+
+/// [`PEAK_REQUEST_BYTES`] is the most bytes a single request allocated in an
+/// [`Arena`] through [`with_request_arena`].
+static PEAK_REQUEST_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// [`GROWTHS`] counts every time an [`Arena`] used through [`with_request_arena`]
+/// had to grow past its capacity.
+static GROWTHS: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// [`REQUEST_ARENA`] is the [`Arena`] each worker thread reuses for every
+    /// request it handles.
+    static REQUEST_ARENA: RefCell<Arena> = RefCell::new(Arena::with_capacity(REQUEST_ARENA_CAPACITY));
+}
+
+/// [`Arena`] is a bump allocator for short-lived strings, which stores every
+/// allocation back-to-back in one buffer and frees them all at once with
+/// [`Arena::reset`], keeping the buffer for the next use. Lines read with
+/// [`Arena::alloc_line`] are kept as raw bytes, and are only checked for UTF-8
+/// when they are read with [`Arena::get`].
+#[derive(Clone, Debug, Default)]
+pub struct Arena {
+    /// [`Arena::buffer`] holds every string allocated in the [`Arena`].
+    buffer: Vec<u8>,
+    /// [`Arena::growths`] counts every time [`Arena::buffer`] had to grow since
+    /// the last [`Arena::reset`].
+    growths: u64,
+}
+
+/// [`ArenaStr`] is a handle to a string allocated in an [`Arena`], which is
+/// resolved with [`Arena::get`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArenaStr(Range<usize>);
+
+/// [`ArenaStats`] represents the allocation statistics of every [`Arena`] used
+/// through [`with_request_arena`] since the server started.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ArenaStats {
+    /// [`ArenaStats::requests`] is how many requests used an [`Arena`].
+    pub requests: u64,
+    /// [`ArenaStats::bytes_allocated`] is how many bytes were allocated in total.
+    pub bytes_allocated: u64,
+    /// [`ArenaStats::peak_request_bytes`] is the most bytes a single request
+    /// allocated.
+    pub peak_request_bytes: u64,
+    /// [`ArenaStats::growths`] is how many times an [`Arena`] had to grow past its
+    /// capacity, which happens less as the workers' arenas warm up.
+    pub growths: u64,
+}
+
+impl ArenaStr {
+    /// [`ArenaStr::shorten`] will return an [`ArenaStr`] without the last `bytes`
+    /// bytes, such as a line ending.
+    pub fn shorten(&self, bytes: usize) -> ArenaStr {
+        ArenaStr(self.0.start..self.0.end.saturating_sub(bytes).max(self.0.start))
+    }
+}
+
+impl Arena {
+    /// [`Arena::with_capacity`] creates an empty [`Arena`] that can hold `capacity`
+    /// bytes before it has to grow.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena {
+            buffer: Vec::with_capacity(capacity),
+            growths: 0,
+        }
+    }
+
+    /// [`Arena::alloc`] will copy `value` into the [`Arena`] and return an
+    /// [`ArenaStr`] for it.
+    /// # Example
+    /// [`Arena::alloc`] can be used to keep a path segment around while parsing:
+    /// ```rust
+    /// use http::arena::Arena;
+    ///
+    /// fn first_segment(arena: &mut Arena, path: &str) -> String {
+    ///     let segment = arena.alloc(path.trim_start_matches('/').split('/').next().unwrap_or(""));
+    ///     arena.get(&segment).to_string()
+    /// }
+    /// ```
+    pub fn alloc(&mut self, value: &str) -> ArenaStr {
+        let start = self.buffer.len();
+        self.reserve(value.len());
+        self.buffer.extend_from_slice(value.as_bytes());
+        ArenaStr(start..self.buffer.len())
+    }
+
+    /// [`Arena::alloc_line`] will read a single line from `reader` straight into
+    /// the [`Arena`] as raw bytes, including its line ending, and return an
+    /// [`ArenaStr`] for it. The [`ArenaStr`] is empty when there is nothing left
+    /// to read.
+    /// # Example
+    /// [`Arena::alloc_line`] can be used to read a request line without allocating
+    /// a [`String`] for it:
+    /// ```rust
+    /// use http::arena::Arena;
+    /// use std::io::BufRead;
+    ///
+    /// fn read_method(arena: &mut Arena, reader: &mut impl BufRead) -> std::io::Result<String> {
+    ///     let line = arena.alloc_line(reader)?;
+    ///     Ok(arena.get(&line).split(' ').next().unwrap_or("").to_string())
+    /// }
+    /// ```
+    pub fn alloc_line(&mut self, reader: &mut impl BufRead) -> io::Result<ArenaStr> {
+        self.alloc_line_within(reader, usize::MAX)
+    }
+
+    /// [`Arena::alloc_line_within`] will read a single line from `reader` into the
+    /// [`Arena`] like [`Arena::alloc_line`], but read no more than `max_bytes` of
+    /// it, so the [`ArenaStr`] doesn't end with a line ending when the line is
+    /// longer than that.
+    /// # Example
+    /// [`Arena::alloc_line_within`] can be used to refuse a line that is too long:
+    /// ```rust
+    /// use http::arena::Arena;
+    /// use std::io::BufRead;
+    ///
+    /// fn is_short_line(arena: &mut Arena, reader: &mut impl BufRead) -> bool {
+    ///     arena
+    ///         .alloc_line_within(reader, 1024)
+    ///         .is_ok_and(|line| arena.get_bytes(&line).ends_with(b"\n"))
+    /// }
+    /// ```
+    pub fn alloc_line_within(
+        &mut self,
+        reader: &mut impl BufRead,
+        max_bytes: usize,
+    ) -> io::Result<ArenaStr> {
+        let start = self.buffer.len();
+        let capacity = self.buffer.capacity();
+        reader
+            .take(max_bytes as u64)
+            .read_until(b'\n', &mut self.buffer)?;
+        if self.buffer.capacity() > capacity {
+            self.growths += 1;
+        }
+        Ok(ArenaStr(start..self.buffer.len()))
+    }
+
+    /// [`Arena::join`] will allocate `first`, followed by `separator`, followed by
+    /// `second`, and return an [`ArenaStr`] for the result.
+    pub fn join(&mut self, first: &ArenaStr, separator: &str, second: &ArenaStr) -> ArenaStr {
+        let start = self.buffer.len();
+        self.reserve(first.0.len() + separator.len() + second.0.len());
+        self.buffer.extend_from_within(first.0.clone());
+        self.buffer.extend_from_slice(separator.as_bytes());
+        self.buffer.extend_from_within(second.0.clone());
+        ArenaStr(start..self.buffer.len())
+    }
+
+    /// [`Arena::trim`] will return an [`ArenaStr`] for `value` without its leading
+    /// and trailing ASCII whitespace, without allocating anything.
+    pub fn trim(&self, value: &ArenaStr) -> ArenaStr {
+        let bytes = self.get_bytes(value);
+        let start = value.0.start + (bytes.len() - bytes.trim_ascii_start().len());
+        let end = value.0.end - (bytes.len() - bytes.trim_ascii_end().len());
+        ArenaStr(start..end.max(start))
+    }
+
+    /// [`Arena::get`] will return the string an [`ArenaStr`] points to, which is
+    /// empty if a line read with [`Arena::alloc_line`] is not valid UTF-8.
+    pub fn get(&self, value: &ArenaStr) -> &str {
+        std::str::from_utf8(self.get_bytes(value)).unwrap_or_default()
+    }
+
+    /// [`Arena::get_bytes`] will return the raw bytes an [`ArenaStr`] points to,
+    /// without checking that they are valid UTF-8.
+    pub fn get_bytes(&self, value: &ArenaStr) -> &[u8] {
+        &self.buffer[value.0.clone()]
+    }
+
+    /// [`Arena::len`] will return how many bytes are allocated in the [`Arena`].
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// [`Arena::is_empty`] will return `true` if nothing is allocated in the
+    /// [`Arena`], and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// [`Arena::capacity`] will return how many bytes the [`Arena`] can hold
+    /// before it has to grow.
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// [`Arena::reset`] will free every allocation in the [`Arena`] at once, while
+    /// keeping its capacity for the next use. Any [`ArenaStr`] from before the
+    /// reset must not be used afterwards.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.growths = 0;
+    }
+
+    /// [`Arena::shrink_to`] will lower the capacity of the [`Arena`] to `capacity`,
+    /// or to how many bytes are allocated in it if that is more.
+    pub fn shrink_to(&mut self, capacity: usize) {
+        self.buffer.shrink_to(capacity);
+    }
+
+    /// [`Arena::reserve`] will make room for `additional` bytes, counting it as a
+    /// growth if [`Arena::buffer`] has to reallocate.
+    fn reserve(&mut self, additional: usize) {
+        if self.buffer.capacity() - self.buffer.len() < additional {
+            self.growths += 1;
+            self.buffer.reserve(additional);
+        }
+    }
+}
+
+/// [`with_request_arena`] will run `scope` with the current worker's [`Arena`], and
+/// reset the [`Arena`] afterwards so every request starts with an empty one, where
+/// an [`Arena`] that grew past [`REQUEST_ARENA_MAX_CAPACITY`] is shrunk as well. The
+/// allocations made in `scope` are recorded in [`stats`].
+/// # Example
+/// [`with_request_arena`] can be used to parse a request with scratch space that
+/// is reused across requests:
+/// ```rust
+/// use http::arena;
+/// use std::io::BufRead;
+///
+/// fn read_request_line(reader: &mut impl BufRead) -> std::io::Result<String> {
+///     arena::with_request_arena(|arena| {
+///         let line = arena.alloc_line(reader)?;
+///         Ok(arena.get(&line).trim_end().to_string())
+///     })
+/// }
+/// ```
+pub fn with_request_arena<T>(scope: impl FnOnce(&mut Arena) -> T) -> T {
+    REQUEST_ARENA.with_borrow_mut(|arena| {
+        arena.reset();
+        let result = scope(arena);
+
+        let bytes = arena.len() as u64;
+        REQUESTS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(bytes, Ordering::Relaxed);
+        PEAK_REQUEST_BYTES.fetch_max(bytes, Ordering::Relaxed);
+        GROWTHS.fetch_add(arena.growths, Ordering::Relaxed);
+        arena.reset();
+        if arena.capacity() > REQUEST_ARENA_MAX_CAPACITY {
+            arena.shrink_to(REQUEST_ARENA_CAPACITY);
+        }
+        result
+    })
+}
+
+/// [`stats`] will return the [`ArenaStats`] of every request that used
+/// [`with_request_arena`] since the server started.
+/// # Example
+/// [`stats`] can be used to report how much parsing scratch space requests use:
+/// ```rust
+/// use http::arena;
+///
+/// fn average_request_bytes() -> u64 {
+///     let stats = arena::stats();
+///     stats.bytes_allocated / stats.requests.max(1)
+/// }
+/// ```
+pub fn stats() -> ArenaStats {
+    ArenaStats {
+        requests: REQUESTS.load(Ordering::Relaxed),
+        bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed),
+        peak_request_bytes: PEAK_REQUEST_BYTES.load(Ordering::Relaxed),
+        growths: GROWTHS.load(Ordering::Relaxed),
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{
+    json::Json,
+    methods::{DELETE, Method, POST, PUT},
+    request::Request,
+    sha256,
+    status::Status,
+};
+use logger::log_warning;
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// [`DEFAULT_AUDIT_MAX_BYTES`] is how large an audit log file grows before it is
+/// rotated, unless another [`AuditRotation`] is used.
+pub const DEFAULT_AUDIT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// [`DEFAULT_AUDIT_KEEP`] is how many rotated audit log files are kept, unless
+/// another [`AuditRotation`] is used.
+pub const DEFAULT_AUDIT_KEEP: usize = 5;
+
+/// [`PrincipalFn`] is a dynamic function that names who made a request, such as the
+/// user of its credentials, [`None`] for an anonymous request.
+pub type PrincipalFn = Box<dyn Fn(&Request) -> Option<String> + Send + Sync + 'static>;
+
+/// [`AuditRecord`] is a single entry of the audit trail, which records who made a
+/// mutating request, what it was, and when.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditRecord {
+    /// [`AuditRecord::time`] is when the request was answered, in seconds since the
+    /// Unix epoch.
+    pub time: u64,
+    /// [`AuditRecord::principal`] is who made the request, [`None`] if it was
+    /// anonymous.
+    pub principal: Option<String>,
+    /// [`AuditRecord::method`] is the HTTP method of the request.
+    pub method: Method,
+    /// [`AuditRecord::path`] is the path of the request, along with its query string.
+    pub path: String,
+    /// [`AuditRecord::body_digest`] is the hex SHA-256 digest of the request body, so
+    /// the trail can prove what was sent without keeping it, [`None`] without a body.
+    pub body_digest: Option<String>,
+    /// [`AuditRecord::status`] is the status the request was answered with.
+    pub status: Status,
+}
+
+impl AuditRecord {
+    /// [`AuditRecord::new`] creates an [`AuditRecord`] of `request` made by
+    /// `principal`, which was answered with `status` just now.
+    pub fn new(request: &Request, principal: Option<String>, status: Status) -> Self {
+        AuditRecord {
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_secs()),
+            principal,
+            method: request.method,
+            path: request.path.clone(),
+            body_digest: request
+                .body_content
+                .as_deref()
+                .map(|body| to_hex(&sha256::hash(body.as_bytes()))),
+            status,
+        }
+    }
+
+    /// [`AuditRecord::to_json`] will get the [`AuditRecord`] as a single line of
+    /// JSON, such as
+    /// `{"time":1700000000,"principal":"evan","method":"POST","path":"/users","body_sha256":"9f86...","status":201}`.
+    pub fn to_json(&self) -> String {
+        let optional = |value: &Option<String>| value.clone().map_or(Json::Null, Json::String);
+        Json::Object(vec![
+            ("time".to_string(), Json::Number(self.time as f64)),
+            ("principal".to_string(), optional(&self.principal)),
+            ("method".to_string(), Json::String(self.method.to_string())),
+            ("path".to_string(), Json::String(self.path.clone())),
+            ("body_sha256".to_string(), optional(&self.body_digest)),
+            (
+                "status".to_string(),
+                Json::Number(f64::from(self.status.code())),
+            ),
+        ])
+        .to_string()
+    }
+}
+
+/// [`AuditRotation`] is when an [`AuditSink`] starts a new file, and how many of the
+/// old ones it keeps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AuditRotation {
+    /// [`AuditRotation::max_bytes`] is how large a file grows before it is rotated.
+    pub max_bytes: u64,
+    /// [`AuditRotation::keep`] is how many rotated files are kept, named after the
+    /// file with a `.1` for the newest, `.2` for the one before it, and so on.
+    pub keep: usize,
+}
+
+/// Implement [`Default`] for [`AuditRotation`].
+impl Default for AuditRotation {
+    fn default() -> Self {
+        AuditRotation {
+            max_bytes: DEFAULT_AUDIT_MAX_BYTES,
+            keep: DEFAULT_AUDIT_KEEP,
+        }
+    }
+}
+
+/// Implement [`fmt::Display`] for [`AuditRotation`].
+impl fmt::Display for AuditRotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rotated at {} bytes, keeping {}",
+            self.max_bytes, self.keep
+        )
+    }
+}
+
+/// [`AuditSink`] is an append-only file every [`AuditRecord`] is written to as a
+/// line of JSON, apart from the application logs, which is rotated by its
+/// [`AuditRotation`].
+#[derive(Debug)]
+pub struct AuditSink {
+    /// [`AuditSink::path`] is the file records are appended to.
+    path: PathBuf,
+    /// [`AuditSink::rotation`] is when the file is rotated.
+    rotation: AuditRotation,
+    /// [`AuditSink::file`] is the open file, which is only ever appended to.
+    file: Mutex<File>,
+}
+
+impl AuditSink {
+    /// [`AuditSink::open`] will open the file at `path` to append records to,
+    /// creating it and its directory if they don't exist, and rotate it by
+    /// `rotation`.
+    /// # Example
+    /// [`AuditSink::open`] can be used to keep the audit trail in its own directory:
+    /// ```rust
+    /// use http::audit::{AuditRotation, AuditSink};
+    ///
+    /// fn open_audit_sink() -> std::io::Result<AuditSink> {
+    ///     AuditSink::open("logs/audit.log", AuditRotation::default())
+    /// }
+    /// ```
+    pub fn open(path: impl AsRef<Path>, rotation: AuditRotation) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(directory) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(directory)?;
+        }
+        let file = open_for_append(&path)?;
+        Ok(AuditSink {
+            path,
+            rotation,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// [`AuditSink::path`] will get the file records are appended to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// [`AuditSink::rotation`] will get when the file is rotated.
+    pub fn rotation(&self) -> AuditRotation {
+        self.rotation
+    }
+
+    /// [`AuditSink::append`] will append `line` to the file, rotating it first if
+    /// `line` would grow it past [`AuditRotation::max_bytes`].
+    pub fn append(&self, line: &str) -> io::Result<()> {
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let size = file.metadata()?.len();
+        // a line larger than a whole file still gets a file of its own
+        if size > 0 && size + line.len() as u64 + 1 > self.rotation.max_bytes {
+            self.rotate()?;
+            *file = open_for_append(&self.path)?;
+        }
+        file.write_all(format!("{line}\n").as_bytes())?;
+        file.flush()
+    }
+
+    /// [`AuditSink::rotated_path`] will get the path of the rotated file `index`,
+    /// such as `audit.log.1` for the newest one.
+    pub fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        PathBuf::from(path)
+    }
+
+    /// [`AuditSink::rotate`] will move the file to [`AuditSink::rotated_path`] `1`,
+    /// shifting every older one up, and removing the oldest past
+    /// [`AuditRotation::keep`].
+    fn rotate(&self) -> io::Result<()> {
+        if self.rotation.keep == 0 {
+            return fs::remove_file(&self.path);
+        }
+        let oldest = self.rotated_path(self.rotation.keep);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for index in (1..self.rotation.keep).rev() {
+            let rotated = self.rotated_path(index);
+            if rotated.exists() {
+                fs::rename(rotated, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))
+    }
+}
+
+/// [`AuditLog`] decides which requests are audited, who made them, and writes an
+/// [`AuditRecord`] of each to its [`AuditSink`]. Only `POST`, `PUT`, and `DELETE`
+/// requests are audited, since they are the ones that change anything.
+#[derive(Default)]
+pub struct AuditLog {
+    /// [`AuditLog::principal`] is the [`PrincipalFn`] that names who made a request.
+    principal: Option<PrincipalFn>,
+    /// [`AuditLog::sink`] is the [`AuditSink`] records are written to, where
+    /// nothing is audited without one.
+    sink: Option<AuditSink>,
+}
+
+impl AuditLog {
+    /// [`AuditLog::new`] creates an [`AuditLog`] without a [`PrincipalFn`] or an
+    /// [`AuditSink`], which audits nothing until it has a sink.
+    pub fn new() -> Self {
+        AuditLog::default()
+    }
+
+    /// [`AuditLog::with_principal`] will set the [`PrincipalFn`] that names who made
+    /// every audited request, and return the [`AuditLog`].
+    /// # Example
+    /// [`AuditLog::with_principal`] can be used to record the user a gateway
+    /// authenticated:
+    /// ```rust
+    /// use http::audit::AuditLog;
+    ///
+    /// fn create_audit_log() -> AuditLog {
+    ///     AuditLog::new().with_principal(Box::new(|request| {
+    ///         request.header("X-Authenticated-User").map(str::to_string)
+    ///     }))
+    /// }
+    /// ```
+    pub fn with_principal(mut self, principal: PrincipalFn) -> Self {
+        self.principal = Some(principal);
+        self
+    }
+
+    /// [`AuditLog::with_sink`] will set the [`AuditSink`] records are written to,
+    /// and return the [`AuditLog`].
+    pub fn with_sink(mut self, sink: AuditSink) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// [`AuditLog::sink`] will get the [`AuditSink`] records are written to,
+    /// [`None`] if nothing is audited.
+    pub fn sink(&self) -> Option<&AuditSink> {
+        self.sink.as_ref()
+    }
+
+    /// [`AuditLog::audits`] will return `true` if a request with `method` is
+    /// audited, which is a `POST`, `PUT`, or `DELETE` while there is a sink.
+    pub fn audits(&self, method: Method) -> bool {
+        self.sink.is_some() && [POST, PUT, DELETE].contains(&method)
+    }
+
+    /// [`AuditLog::record`] will write an [`AuditRecord`] of `request`, which was
+    /// answered with `status`, if [`AuditLog::audits`] its method. A record that
+    /// can't be written is logged as a warning, rather than failing the request.
+    /// # Example
+    /// [`AuditLog::record`] can be used to audit a request once it is answered:
+    /// ```rust
+    /// use http::{audit::AuditLog, request::Request, response::Response};
+    ///
+    /// fn audit(audit_log: &AuditLog, request: &Request, response: &Response) {
+    ///     audit_log.record(request, response.status.clone());
+    /// }
+    /// ```
+    pub fn record(&self, request: &Request, status: Status) {
+        let Some(sink) = self.sink.as_ref().filter(|_| self.audits(request.method)) else {
+            return;
+        };
+        let principal = self
+            .principal
+            .as_ref()
+            .and_then(|principal| principal(request));
+        let record = AuditRecord::new(request, principal, status);
+        if let Err(error) = sink.append(&record.to_json()) {
+            log_warning!(
+                "can't write to the audit log {}: {error}",
+                sink.path.display()
+            );
+        }
+    }
+}
+
+/// [`open_for_append`] will open the file at `path` so it can only be appended to,
+/// creating it if it doesn't exist.
+fn open_for_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// [`to_hex`] will get `bytes` as lowercase hex.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,184 @@
+use crate::{json::Json, methods::Method, response::Response, status::Status};
+use std::thread;
+
+/// [`BATCH_PATH`] is the path that answers an array of sub-requests with an array
+/// of their responses, when batching is turned on.
+pub const BATCH_PATH: &str = "/__batch";
+
+/// [`MAX_BATCH_REQUESTS`] is how many sub-requests a single batch can have, so one
+/// request can't hold a worker for long.
+pub const MAX_BATCH_REQUESTS: usize = 100;
+
+/// [`BatchRequest`] is one sub-request of a batch, which is routed as if it was
+/// sent on its own.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchRequest {
+    /// [`BatchRequest::method`] is the HTTP method of the sub-request.
+    pub method: Method,
+    /// [`BatchRequest::path`] is the path of the sub-request, along with any query
+    /// string, such as `/users/1?fields=name`.
+    pub path: String,
+    /// [`BatchRequest::body`] is the body of the sub-request, [`None`] if it has
+    /// none.
+    pub body: Option<String>,
+}
+
+impl BatchRequest {
+    /// [`BatchRequest::parse_all`] will parse `body`, a JSON array of objects with a
+    /// `method`, a `path`, and an optional `body`, into every [`BatchRequest`] in
+    /// order. A `body` that isn't a string is sent as its JSON. Returns an [`Err`] if
+    /// `body` isn't such an array, or has more than [`MAX_BATCH_REQUESTS`].
+    /// # Example
+    /// [`BatchRequest::parse_all`] can be used to count the sub-requests of a batch:
+    /// ```rust
+    /// use http::batch::BatchRequest;
+    ///
+    /// fn count_sub_requests(body: &str) -> usize {
+    ///     BatchRequest::parse_all(body).map_or(0, |requests| requests.len())
+    /// }
+    /// ```
+    pub fn parse_all(body: &str) -> Result<Vec<BatchRequest>, String> {
+        let json =
+            Json::parse(body).map_err(|error| format!("batch is not valid JSON: {error}"))?;
+        let requests = json
+            .as_array()
+            .ok_or("batch must be a JSON array of requests")?;
+        if requests.len() > MAX_BATCH_REQUESTS {
+            return Err(format!(
+                "batch has {} requests, but can have at most {MAX_BATCH_REQUESTS}",
+                requests.len()
+            ));
+        }
+
+        requests
+            .iter()
+            .enumerate()
+            .map(|(index, request)| {
+                let method = request
+                    .get("method")
+                    .and_then(Json::as_str)
+                    .ok_or_else(|| format!("request {index} has no method"))?
+                    .parse::<Method>()
+                    .map_err(|error| format!("request {index}: {error}"))?;
+                let path = request
+                    .get("path")
+                    .and_then(Json::as_str)
+                    .filter(|path| path.starts_with('/'))
+                    .ok_or_else(|| format!("request {index} has no path starting with '/'"))?;
+                let body = match request.get("body") {
+                    None | Some(Json::Null) => None,
+                    Some(Json::String(body)) => Some(body.clone()),
+                    Some(body) => Some(body.to_string()),
+                };
+                Ok(BatchRequest {
+                    method,
+                    path: path.to_string(),
+                    body,
+                })
+            })
+            .collect()
+    }
+}
+
+/// [`Batch`] runs the sub-requests of a batch, one after another or a few at a
+/// time, and answers with every [`Response`] in the order of their sub-requests.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch {
+    /// [`Batch::concurrency`] is how many sub-requests run at the same time, where
+    /// `1` runs them in order.
+    pub concurrency: usize,
+}
+
+impl Batch {
+    /// [`Batch::new`] creates a [`Batch`] that runs up to `concurrency`
+    /// sub-requests at the same time, and at least one.
+    pub fn new(concurrency: usize) -> Self {
+        Batch {
+            concurrency: concurrency.max(1),
+        }
+    }
+
+    /// [`Batch::run`] will get the [`Response`] of every one of `requests` from
+    /// `dispatch`, in the order of `requests`, running up to [`Batch::concurrency`]
+    /// of them at the same time.
+    /// # Example
+    /// [`Batch::run`] can be used to answer every sub-request with `404 NOT FOUND`:
+    /// ```rust
+    /// use http::{batch::{Batch, BatchRequest}, response::Response};
+    ///
+    /// fn answer_all(requests: &[BatchRequest]) -> Vec<Response> {
+    ///     Batch::new(1).run(requests, |_| Response::not_found())
+    /// }
+    /// ```
+    pub fn run<F>(&self, requests: &[BatchRequest], dispatch: F) -> Vec<Response>
+    where
+        F: Fn(&BatchRequest) -> Response + Sync,
+    {
+        if self.concurrency == 1 {
+            return requests.iter().map(&dispatch).collect();
+        }
+
+        let dispatch = &dispatch;
+        let mut responses = Vec::with_capacity(requests.len());
+        for chunk in requests.chunks(self.concurrency) {
+            thread::scope(|scope| {
+                let handles: Vec<_> = chunk
+                    .iter()
+                    .map(|request| scope.spawn(move || dispatch(request)))
+                    .collect();
+                // a sub-request that panics fails on its own, not the whole batch
+                responses.extend(
+                    handles
+                        .into_iter()
+                        .map(|handle| handle.join().unwrap_or_else(|_| Response::server_error())),
+                );
+            });
+        }
+        responses
+    }
+
+    /// [`Batch::handle`] will parse a batch `body` and answer it with a JSON array of
+    /// the `status`, `headers`, and `body` of every sub-request from `dispatch`, or
+    /// with `400 BAD REQUEST` if `body` isn't a valid batch.
+    /// # Example
+    /// [`Batch::handle`] can be used to answer a batch from a route:
+    /// ```rust
+    /// use http::{batch::Batch, request::Request, response::Response};
+    ///
+    /// fn answer_batch(request: &Request) -> Response {
+    ///     let body = request.body_content.as_deref().unwrap_or_default();
+    ///     Batch::new(4).handle(body, |_| Response::not_found())
+    /// }
+    /// ```
+    pub fn handle<F>(&self, body: &str, dispatch: F) -> Response
+    where
+        F: Fn(&BatchRequest) -> Response + Sync,
+    {
+        let requests = match BatchRequest::parse_all(body) {
+            Ok(requests) => requests,
+            Err(reason) => return Response::with_status(Status::BadRequest, &reason, false),
+        };
+        let responses = self.run(&requests, dispatch).iter().map(to_json).collect();
+        Response::with_status(Status::Ok, &Json::Array(responses).to_string(), true)
+    }
+}
+
+/// [`to_json`] will get the `status`, `headers`, and `body` of `response` as a JSON
+/// object, where a body that is JSON is kept as such, rather than as a string.
+fn to_json(response: &Response) -> Json {
+    let headers = response
+        .headers
+        .iter()
+        .map(|(name, value)| (name.clone(), Json::String(value.clone())))
+        .collect();
+    let body =
+        Json::parse(&response.content).unwrap_or_else(|_| Json::String(response.content.clone()));
+    Json::Object(vec![
+        (
+            "status".to_string(),
+            Json::Number(f64::from(response.status.code())),
+        ),
+        ("headers".to_string(), Json::Object(headers)),
+        ("body".to_string(), body),
+    ])
+}
@@ -0,0 +1,113 @@
+use std::io::{self, Cursor, Read};
+
+/// [`BodyReader`] reads the body of a [`Request`](crate::request::Request) straight
+/// from the connection, so a handler can process a large upload a piece at a time
+/// instead of holding all of it in memory. It never reads past the end of the body.
+pub struct BodyReader {
+    /// [`BodyReader::len`] is the length of the whole body in bytes, from the
+    /// `Content-Length` header.
+    len: usize,
+    /// [`BodyReader::reader`] is what the body is read from, which ends with the
+    /// body.
+    reader: io::Take<Box<dyn Read + Send>>,
+}
+
+impl BodyReader {
+    /// [`BodyReader::new`] creates a [`BodyReader`] that reads the `len` bytes of a
+    /// body from `reader`.
+    /// # Example
+    /// [`BodyReader::new`] can be used to read a body from a connection:
+    /// ```rust
+    /// use http::body::BodyReader;
+    /// use std::net::TcpStream;
+    ///
+    /// fn read_body_from(stream: TcpStream, content_length: usize) -> BodyReader {
+    ///     BodyReader::new(Box::new(stream), content_length)
+    /// }
+    /// ```
+    pub fn new(reader: Box<dyn Read + Send>, len: usize) -> Self {
+        BodyReader {
+            len,
+            reader: reader.take(len as u64),
+        }
+    }
+
+    /// [`BodyReader::from_bytes`] creates a [`BodyReader`] that reads `bytes`, such
+    /// as a body that was already read.
+    /// # Example
+    /// [`BodyReader::from_bytes`] can be used to test a handler that reads its body:
+    /// ```rust
+    /// use http::body::BodyReader;
+    ///
+    /// fn create_upload() -> BodyReader {
+    ///     BodyReader::from_bytes(b"one line\nanother line\n".to_vec())
+    /// }
+    /// ```
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        BodyReader::new(Box::new(Cursor::new(bytes)), len)
+    }
+
+    /// [`BodyReader::len`] will get the length of the whole body in bytes, no
+    /// matter how much of it was read already.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// [`BodyReader::is_empty`] will check if the body has no bytes at all.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Implement [`Read`] for [`BodyReader`].
+impl Read for BodyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+/// Implement [`PartialEq`] for [`BodyReader`], where the bytes can only be read
+/// once, so two [`BodyReader`] values are equal when their bodies are the same
+/// length.
+impl PartialEq for BodyReader {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+    }
+}
+
+/// [`parse_size`] will parse a human readable size, such as `"512"`, `"64KB"`, or
+/// `"1MB"`, into a number of bytes, where every unit is a power of 1024.
+/// # Example
+/// [`parse_size`] can be used to read a body size limit from a config value:
+/// ```rust
+/// use http::body;
+///
+/// fn get_upload_limit() -> usize {
+///     body::parse_size("1MB").unwrap_or(1024 * 1024)
+/// }
+/// ```
+pub fn parse_size(size: &str) -> Result<usize, String> {
+    let size = size.trim();
+    let unit_start = size
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (amount, unit) = size.split_at(unit_start);
+    let amount: usize = amount
+        .parse()
+        .map_err(|_| format!("size '{size}' has to start with a number of bytes"))?;
+    let multiplier: usize = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "KB" => 1024,
+        "MB" => 1024 * 1024,
+        "GB" => 1024 * 1024 * 1024,
+        unit => {
+            return Err(format!(
+                "unknown size unit '{unit}', expected B, KB, MB, or GB"
+            ));
+        }
+    };
+    amount
+        .checked_mul(multiplier)
+        .ok_or(format!("size '{size}' is too large"))
+}
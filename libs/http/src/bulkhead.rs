@@ -0,0 +1,119 @@
+use std::{
+    sync::{
+        Condvar, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// [`Bulkhead`] limits how many calls of something expensive, such as the handler
+/// of a report [`Route`](crate::route::Route), run at the same time, so one heavy
+/// endpoint can't take every worker from the rest of the API. A call over the limit
+/// waits for a running one to finish for up to its queue timeout, and is rejected
+/// once that passes.
+#[derive(Debug)]
+pub struct Bulkhead {
+    /// [`Bulkhead::max_concurrent`] is how many calls can run at the same time.
+    pub max_concurrent: usize,
+    /// [`Bulkhead::queue_timeout`] is how long a call over the limit waits for a
+    /// running one to finish, where [`Duration::ZERO`] rejects it right away.
+    pub queue_timeout: Duration,
+    /// [`Bulkhead::running`] is how many calls are running.
+    running: Mutex<usize>,
+    /// [`Bulkhead::released`] wakes a waiting call when a running one finishes.
+    released: Condvar,
+    /// [`Bulkhead::rejected`] is how many calls were rejected.
+    rejected: AtomicU64,
+}
+
+impl Bulkhead {
+    /// [`Bulkhead::new`] creates a [`Bulkhead`] that runs up to `max_concurrent`
+    /// calls at the same time, and rejects any other call right away.
+    pub fn new(max_concurrent: usize) -> Self {
+        Bulkhead {
+            max_concurrent,
+            queue_timeout: Duration::ZERO,
+            running: Mutex::new(0),
+            released: Condvar::new(),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    /// [`Bulkhead::with_queue_timeout`] will let a call over the limit wait up to
+    /// `queue_timeout` for a running one to finish before it is rejected, and
+    /// return the [`Bulkhead`].
+    pub fn with_queue_timeout(mut self, queue_timeout: Duration) -> Self {
+        self.queue_timeout = queue_timeout;
+        self
+    }
+
+    /// [`Bulkhead::try_enter`] will get a [`BulkheadPermit`] that lets a call run
+    /// until it is dropped, waiting up to [`Bulkhead::queue_timeout`] for one, or
+    /// [`None`] if the call is rejected.
+    /// # Example
+    /// [`Bulkhead::try_enter`] can be used to run at most two reports at once:
+    /// ```rust
+    /// use http::bulkhead::Bulkhead;
+    ///
+    /// fn build_report(bulkhead: &Bulkhead) -> Option<String> {
+    ///     let _permit = bulkhead.try_enter()?;
+    ///     Some("report".to_string())
+    /// }
+    /// ```
+    pub fn try_enter(&self) -> Option<BulkheadPermit<'_>> {
+        let deadline = Instant::now() + self.queue_timeout;
+        let mut running = self
+            .running
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        while *running >= self.max_concurrent {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            running = self
+                .released
+                .wait_timeout(running, remaining)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+        *running += 1;
+        Some(BulkheadPermit { bulkhead: self })
+    }
+
+    /// [`Bulkhead::running`] will get how many calls are running.
+    pub fn running(&self) -> usize {
+        *self
+            .running
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// [`Bulkhead::rejected`] will get how many calls were rejected.
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+}
+
+/// [`BulkheadPermit`] lets a call run in a [`Bulkhead`], and makes room for the next
+/// one when it is dropped.
+#[must_use = "a call only holds its place in the Bulkhead until its permit is dropped"]
+#[derive(Debug)]
+pub struct BulkheadPermit<'a> {
+    /// [`BulkheadPermit::bulkhead`] is the [`Bulkhead`] the call runs in.
+    bulkhead: &'a Bulkhead,
+}
+
+/// Implement [`Drop`] for [`BulkheadPermit`], waking a call that waits for room.
+impl Drop for BulkheadPermit<'_> {
+    fn drop(&mut self) {
+        let mut running = self
+            .bulkhead
+            .running
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *running = running.saturating_sub(1);
+        self.bulkhead.released.notify_one();
+    }
+}
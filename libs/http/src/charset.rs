@@ -0,0 +1,66 @@
+/// [`Charset`] represents the character encoding of a request body, named by the
+/// `charset` parameter of its `Content-Type` header.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Charset {
+    /// [`Charset::Utf8`] represents `utf-8`, which is assumed when a body doesn't
+    /// name its charset.
+    Utf8,
+    /// [`Charset::Latin1`] represents `iso-8859-1`, where every byte is the
+    /// character with the same code point.
+    Latin1,
+    /// [`Charset::Ascii`] represents `us-ascii`, which only has the first 128
+    /// characters.
+    Ascii,
+}
+
+impl Charset {
+    /// [`Charset::from_content_type`] will get the [`Charset`] named by the
+    /// `charset` parameter of `content_type`, defaults to [`Charset::Utf8`] if it
+    /// doesn't name one, and returns an [`Err`] for a charset that can't be decoded.
+    /// # Example
+    /// [`Charset::from_content_type`] can be used to tell if a body can be read:
+    /// ```rust
+    /// use http::charset::Charset;
+    ///
+    /// fn is_readable(content_type: Option<&str>) -> bool {
+    ///     Charset::from_content_type(content_type).is_ok()
+    /// }
+    /// ```
+    pub fn from_content_type(content_type: Option<&str>) -> Result<Self, String> {
+        let charset = content_type
+            .into_iter()
+            .flat_map(|content_type| content_type.split(';').skip(1))
+            .filter_map(|parameter| parameter.split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+            .map(|(_, value)| value.trim().trim_matches('"').to_ascii_lowercase());
+        match charset.as_deref() {
+            None | Some("utf-8" | "utf8") => Ok(Charset::Utf8),
+            Some("iso-8859-1" | "iso8859-1" | "latin1" | "latin-1" | "l1") => Ok(Charset::Latin1),
+            Some("us-ascii" | "ascii") => Ok(Charset::Ascii),
+            Some(other) => Err(format!("unsupported charset '{other}'")),
+        }
+    }
+
+    /// [`Charset::decode`] will decode `bytes` into a [`String`], and return an
+    /// [`Err`] if they aren't valid in the [`Charset`].
+    /// # Example
+    /// [`Charset::decode`] can be used to read a latin-1 body:
+    /// ```rust
+    /// use http::charset::Charset;
+    ///
+    /// fn read_latin1(bytes: &[u8]) -> String {
+    ///     Charset::Latin1.decode(bytes).unwrap_or_default()
+    /// }
+    /// ```
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        match self {
+            Charset::Utf8 => String::from_utf8(bytes.to_vec())
+                .map_err(|error| format!("body is not valid utf-8: {error}")),
+            Charset::Latin1 => Ok(bytes.iter().map(|&byte| char::from(byte)).collect()),
+            Charset::Ascii => match bytes.iter().position(|byte| !byte.is_ascii()) {
+                Some(index) => Err(format!("body is not valid us-ascii at byte {index}")),
+                None => Ok(bytes.iter().map(|&byte| char::from(byte)).collect()),
+            },
+        }
+    }
+}
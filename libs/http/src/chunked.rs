@@ -0,0 +1,244 @@
+use crate::{
+    constants::{
+        CONTENT_CSV, CONTENT_JSON, CONTENT_NDJSON, CONTENT_TYPE, HTTP_VERSION, TRAILER,
+        TRANSFER_ENCODING,
+    },
+    export::{csv_row, ndjson_line},
+    header,
+    respond::Respond,
+    status::Status,
+};
+use std::io::{Error, ErrorKind, Result, Write};
+
+/// [`ChunkedWriter`] streams a response body to a [`Write`] with
+/// `Transfer-Encoding: chunked`, so the body can be sent as it is produced,
+/// followed by any trailers that were advertised in the `Trailer` header.
+pub struct ChunkedWriter<W: Write> {
+    /// [`ChunkedWriter::writer`] is the [`Write`] the response is sent to.
+    writer: W,
+    /// [`ChunkedWriter::declared_trailers`] is a collection of trailer names that
+    /// were advertised in the `Trailer` header.
+    declared_trailers: Vec<String>,
+    /// [`ChunkedWriter::is_framed`] is whether the body is sent as chunks, which
+    /// it is unless the whole body is collected with [`ChunkedWriter::unframed`].
+    is_framed: bool,
+}
+
+impl<W: Write> ChunkedWriter<W> {
+    /// [`ChunkedWriter::new`] will write the status line and headers of a chunked
+    /// response to `writer`, advertising every name in `trailers` with a `Trailer`
+    /// header, and return a [`ChunkedWriter`] to send the body with.
+    /// # Example
+    /// [`ChunkedWriter::new`] can be used to start a chunked response that ends
+    /// with a checksum trailer:
+    /// ```rust
+    /// use http::{chunked::ChunkedWriter, status::Status};
+    /// use std::net::TcpStream;
+    ///
+    /// fn start_response(stream: &mut TcpStream) -> std::io::Result<ChunkedWriter<&mut TcpStream>> {
+    ///     ChunkedWriter::new(stream, Status::Ok, &["X-Checksum"])
+    /// }
+    /// ```
+    pub fn new(writer: W, status: Status, trailers: &[&str]) -> Result<Self> {
+        ChunkedWriter::with_content_type(writer, status, CONTENT_JSON, trailers)
+    }
+
+    /// [`ChunkedWriter::csv`] will write the status line and headers of a chunked
+    /// CSV response to `writer`, and return a [`ChunkedWriter`] to send its rows
+    /// with [`ChunkedWriter::write_csv`].
+    pub fn csv(writer: W, status: Status) -> Result<Self> {
+        ChunkedWriter::with_content_type(writer, status, CONTENT_CSV, &[])
+    }
+
+    /// [`ChunkedWriter::ndjson`] will write the status line and headers of a chunked
+    /// NDJSON response to `writer`, and return a [`ChunkedWriter`] to send its
+    /// items with [`ChunkedWriter::write_ndjson`].
+    pub fn ndjson(writer: W, status: Status) -> Result<Self> {
+        ChunkedWriter::with_content_type(writer, status, CONTENT_NDJSON, &[])
+    }
+
+    /// [`ChunkedWriter::with_content_type`] will write the status line and headers
+    /// of a chunked response with `content_type` to `writer`, advertising every
+    /// name in `trailers` with a `Trailer` header, and return a [`ChunkedWriter`]
+    /// to send the body with.
+    pub fn with_content_type(
+        mut writer: W,
+        status: Status,
+        content_type: &str,
+        trailers: &[&str],
+    ) -> Result<Self> {
+        header::check_header(CONTENT_TYPE, content_type)
+            .map_err(|reason| Error::new(ErrorKind::InvalidInput, reason))?;
+        if let Some(name) = trailers.iter().find(|name| !header::is_header_name(name)) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{name:?} is not a valid trailer name"),
+            ));
+        }
+        let trailer_header = if trailers.is_empty() {
+            String::new()
+        } else {
+            format!("{TRAILER}: {}\r\n", trailers.join(", "))
+        };
+        write!(
+            writer,
+            "{HTTP_VERSION} {status}\r\n{TRANSFER_ENCODING}: chunked\r\n{CONTENT_TYPE}: {content_type}\r\n{trailer_header}\r\n"
+        )?;
+
+        Ok(ChunkedWriter {
+            writer,
+            declared_trailers: trailers.iter().map(|name| name.to_lowercase()).collect(),
+            is_framed: true,
+        })
+    }
+
+    /// [`ChunkedWriter::without_head`] will return a [`ChunkedWriter`] that sends
+    /// the body of a response whose status line and headers were already written to
+    /// `writer`, such as a [`Response::stream`](crate::response::Response::stream).
+    pub(crate) fn without_head(writer: W) -> Self {
+        ChunkedWriter {
+            writer,
+            declared_trailers: Vec::new(),
+            is_framed: true,
+        }
+    }
+
+    /// [`ChunkedWriter::unframed`] will return a [`ChunkedWriter`] that writes the
+    /// body to `writer` as-is, without any chunks, to collect the whole body of a
+    /// [`Response::stream`](crate::response::Response::stream).
+    pub(crate) fn unframed(writer: W) -> Self {
+        ChunkedWriter {
+            writer,
+            declared_trailers: Vec::new(),
+            is_framed: false,
+        }
+    }
+
+    /// [`ChunkedWriter::write_chunk`] will send `data` as a single chunk. Empty
+    /// `data` is skipped, as an empty chunk would end the body.
+    /// # Example
+    /// [`ChunkedWriter::write_chunk`] can be used to send each line of a report
+    /// as soon as it is ready:
+    /// ```rust
+    /// use http::chunked::ChunkedWriter;
+    /// use std::io::Write;
+    ///
+    /// fn send_lines(response: &mut ChunkedWriter<impl Write>, lines: &[String]) -> std::io::Result<()> {
+    ///     for line in lines {
+    ///         response.write_chunk(line.as_bytes())?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_chunk(&mut self, data: &[u8]) -> Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        if !self.is_framed {
+            return self.writer.write_all(data);
+        }
+        write!(self.writer, "{:X}\r\n", data.len())?;
+        self.writer.write_all(data)?;
+        self.writer.write_all(b"\r\n")?;
+        self.writer.flush()
+    }
+
+    /// [`ChunkedWriter::write_csv`] will send every row of `rows` as a line of CSV,
+    /// one chunk per row, so a large export never has to be held in memory.
+    /// # Example
+    /// [`ChunkedWriter::write_csv`] can be used to stream rows read from a
+    /// database cursor:
+    /// ```rust
+    /// use http::{chunked::ChunkedWriter, status::Status};
+    /// use std::net::TcpStream;
+    ///
+    /// fn stream_orders(
+    ///     stream: &mut TcpStream,
+    ///     orders: impl Iterator<Item = [String; 2]>,
+    /// ) -> std::io::Result<()> {
+    ///     let mut response = ChunkedWriter::csv(stream, Status::Ok)?;
+    ///     response.write_csv([["id", "total"]])?;
+    ///     response.write_csv(orders)?;
+    ///     response.finish(&[])?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_csv<R, T>(&mut self, rows: impl IntoIterator<Item = R>) -> Result<()>
+    where
+        R: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        for row in rows {
+            self.write_chunk(csv_row(row).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// [`ChunkedWriter::write_ndjson`] will send every item of `items` as a line of
+    /// JSON, one chunk per item, so a large export never has to be held in memory.
+    /// # Example
+    /// [`ChunkedWriter::write_ndjson`] can be used to stream events as they are
+    /// read:
+    /// ```rust
+    /// use http::{chunked::ChunkedWriter, status::Status};
+    /// use std::net::TcpStream;
+    ///
+    /// fn stream_events(
+    ///     stream: &mut TcpStream,
+    ///     events: impl Iterator<Item = String>,
+    /// ) -> std::io::Result<()> {
+    ///     let mut response = ChunkedWriter::ndjson(stream, Status::Ok)?;
+    ///     response.write_ndjson(events)?;
+    ///     response.finish(&[])?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_ndjson<T: Respond>(&mut self, items: impl IntoIterator<Item = T>) -> Result<()> {
+        for item in items {
+            self.write_chunk(ndjson_line(&item).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// [`ChunkedWriter::finish`] will send the final chunk followed by `trailers`,
+    /// and return the [`Write`] the response was sent to. Returns an [`Err`] without
+    /// writing anything if a trailer was not advertised in [`ChunkedWriter::new`].
+    /// # Example
+    /// [`ChunkedWriter::finish`] can be used to end a response with the time it
+    /// took to produce:
+    /// ```rust
+    /// use http::chunked::ChunkedWriter;
+    /// use std::io::Write;
+    ///
+    /// fn end_response(response: ChunkedWriter<impl Write>, elapsed_ms: u128) -> std::io::Result<()> {
+    ///     response.finish(&[("X-Elapsed-Ms", &elapsed_ms.to_string())])?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn finish(mut self, trailers: &[(&str, &str)]) -> Result<W> {
+        if let Some((name, _)) = trailers
+            .iter()
+            .find(|(name, _)| !self.declared_trailers.contains(&name.to_lowercase()))
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("trailer '{name}' was not advertised in the Trailer header"),
+            ));
+        }
+        for (name, value) in trailers {
+            header::check_header(name, value)
+                .map_err(|reason| Error::new(ErrorKind::InvalidInput, reason))?;
+        }
+
+        if !self.is_framed {
+            return Ok(self.writer);
+        }
+        self.writer.write_all(b"0\r\n")?;
+        for (name, value) in trailers {
+            write!(self.writer, "{name}: {value}\r\n")?;
+        }
+        self.writer.write_all(b"\r\n")?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
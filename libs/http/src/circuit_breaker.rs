@@ -0,0 +1,296 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// [`DEFAULT_FAILURE_RATE`] is the share of failed calls in the window that opens a
+/// [`CircuitBreaker`], unless [`CircuitBreaker::with_failure_rate`] is used.
+pub const DEFAULT_FAILURE_RATE: f64 = 0.5;
+
+/// [`DEFAULT_MINIMUM_CALLS`] is how many calls a [`CircuitBreaker`] has to see
+/// before its failure rate is trusted, unless [`CircuitBreaker::with_minimum_calls`]
+/// is used.
+pub const DEFAULT_MINIMUM_CALLS: usize = 10;
+
+/// [`DEFAULT_WINDOW_SIZE`] is how many of the latest calls the failure rate of a
+/// [`CircuitBreaker`] is measured over, unless [`CircuitBreaker::with_window_size`]
+/// is used.
+pub const DEFAULT_WINDOW_SIZE: usize = 20;
+
+/// [`DEFAULT_OPEN_DURATION`] is how long an open [`CircuitBreaker`] fails calls
+/// before it lets a trial call through, unless [`CircuitBreaker::with_open_duration`]
+/// is used.
+pub const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// [`CircuitState`] represents the state of a [`CircuitBreaker`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CircuitState {
+    /// [`CircuitState::Closed`] lets every call through, while counting failures.
+    Closed,
+    /// [`CircuitState::Open`] fails every call right away, without reaching the
+    /// upstream.
+    Open,
+    /// [`CircuitState::HalfOpen`] lets a single trial call through, which closes
+    /// the [`CircuitBreaker`] if it succeeds, and opens it again if it fails.
+    HalfOpen,
+}
+
+/// Implement [`fmt::Display`] for [`CircuitState`].
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half-open",
+        };
+        write!(f, "{state}")
+    }
+}
+
+/// [`CircuitBreaker`] stops calls to an upstream that keeps failing, so callers
+/// fail fast instead of waiting on it. It opens once the failure rate of the latest
+/// calls reaches a threshold, and closes again after a trial call succeeds.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    /// [`CircuitBreaker::failure_rate`] is the share of failed calls, from `0.0` to
+    /// `1.0`, that opens the [`CircuitBreaker`].
+    failure_rate: f64,
+    /// [`CircuitBreaker::minimum_calls`] is how many calls have to be in the window
+    /// before the [`CircuitBreaker`] can open.
+    minimum_calls: usize,
+    /// [`CircuitBreaker::window_size`] is how many of the latest calls are counted.
+    window_size: usize,
+    /// [`CircuitBreaker::open_duration`] is how long the [`CircuitBreaker`] stays
+    /// open before a trial call.
+    open_duration: Duration,
+    /// [`CircuitBreaker::state`] is the mutable state of the [`CircuitBreaker`].
+    state: Mutex<BreakerState>,
+}
+
+/// [`BreakerState`] is the mutable state of a [`CircuitBreaker`].
+#[derive(Debug)]
+struct BreakerState {
+    /// [`BreakerState::state`] is the current [`CircuitState`].
+    state: CircuitState,
+    /// [`BreakerState::outcomes`] is whether each of the latest calls succeeded.
+    outcomes: VecDeque<bool>,
+    /// [`BreakerState::opened_at`] is when the [`CircuitBreaker`] last opened.
+    opened_at: Option<Instant>,
+    /// [`BreakerState::is_trial_running`] is whether the trial call of a half-open
+    /// [`CircuitBreaker`] is in flight.
+    is_trial_running: bool,
+}
+
+impl CircuitBreaker {
+    /// [`CircuitBreaker::new`] creates a closed [`CircuitBreaker`] with the default
+    /// thresholds.
+    /// # Example
+    /// [`CircuitBreaker::new`] can be used to protect calls to a single upstream:
+    /// ```rust
+    /// use http::circuit_breaker::CircuitBreaker;
+    /// use std::time::Duration;
+    ///
+    /// fn create_circuit_breaker() -> CircuitBreaker {
+    ///     CircuitBreaker::new()
+    ///         .with_failure_rate(0.25)
+    ///         .with_open_duration(Duration::from_secs(5))
+    /// }
+    /// ```
+    pub fn new() -> Self {
+        CircuitBreaker {
+            failure_rate: DEFAULT_FAILURE_RATE,
+            minimum_calls: DEFAULT_MINIMUM_CALLS,
+            window_size: DEFAULT_WINDOW_SIZE,
+            open_duration: DEFAULT_OPEN_DURATION,
+            state: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+                is_trial_running: false,
+            }),
+        }
+    }
+
+    /// [`CircuitBreaker::with_failure_rate`] will set the share of failed calls,
+    /// from `0.0` to `1.0`, that opens the [`CircuitBreaker`], and return the
+    /// [`CircuitBreaker`].
+    pub fn with_failure_rate(mut self, failure_rate: f64) -> Self {
+        self.failure_rate = failure_rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// [`CircuitBreaker::with_minimum_calls`] will set how many calls have to be
+    /// counted before the [`CircuitBreaker`] can open, and return the
+    /// [`CircuitBreaker`].
+    pub fn with_minimum_calls(mut self, minimum_calls: usize) -> Self {
+        self.minimum_calls = minimum_calls.max(1);
+        self
+    }
+
+    /// [`CircuitBreaker::with_window_size`] will set how many of the latest calls
+    /// are counted, and return the [`CircuitBreaker`].
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size.max(1);
+        self
+    }
+
+    /// [`CircuitBreaker::with_open_duration`] will set how long the
+    /// [`CircuitBreaker`] stays open before a trial call, and return the
+    /// [`CircuitBreaker`].
+    pub fn with_open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+
+    /// [`CircuitBreaker::state`] will get the current [`CircuitState`], which
+    /// reports [`CircuitState::HalfOpen`] once an open [`CircuitBreaker`] is ready
+    /// for a trial call.
+    pub fn state(&self) -> CircuitState {
+        let state = self.lock();
+        match state.opened_at {
+            Some(opened_at)
+                if state.state == CircuitState::Open
+                    && opened_at.elapsed() >= self.open_duration =>
+            {
+                CircuitState::HalfOpen
+            }
+            _ => state.state,
+        }
+    }
+
+    /// [`CircuitBreaker::allow_request`] will return `true` if a call may go to the
+    /// upstream, and `false` if it should fail fast. Every allowed call has to be
+    /// followed by [`CircuitBreaker::record_success`] or
+    /// [`CircuitBreaker::record_failure`].
+    pub fn allow_request(&self) -> bool {
+        let mut state = self.lock();
+        match state.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                let is_ready_for_trial = state
+                    .opened_at
+                    .is_some_and(|opened_at| opened_at.elapsed() >= self.open_duration);
+                if is_ready_for_trial {
+                    state.state = CircuitState::HalfOpen;
+                    state.is_trial_running = true;
+                }
+                is_ready_for_trial
+            }
+            CircuitState::HalfOpen if state.is_trial_running => false,
+            CircuitState::HalfOpen => {
+                state.is_trial_running = true;
+                true
+            }
+        }
+    }
+
+    /// [`CircuitBreaker::record_success`] will count a successful call, closing a
+    /// half-open [`CircuitBreaker`].
+    pub fn record_success(&self) {
+        let mut state = self.lock();
+        match state.state {
+            CircuitState::HalfOpen => {
+                state.state = CircuitState::Closed;
+                state.outcomes.clear();
+                state.opened_at = None;
+                state.is_trial_running = false;
+            }
+            _ => self.push_outcome(&mut state, true),
+        }
+    }
+
+    /// [`CircuitBreaker::record_failure`] will count a failed call, opening the
+    /// [`CircuitBreaker`] if it was half-open or its failure rate reached the
+    /// threshold.
+    pub fn record_failure(&self) {
+        let mut state = self.lock();
+        match state.state {
+            CircuitState::HalfOpen => self.open(&mut state),
+            _ => {
+                self.push_outcome(&mut state, false);
+                let failures = state.outcomes.iter().filter(|is_ok| !**is_ok).count();
+                let calls = state.outcomes.len();
+                if calls >= self.minimum_calls
+                    && failures as f64 / calls as f64 >= self.failure_rate
+                {
+                    self.open(&mut state);
+                }
+            }
+        }
+    }
+
+    /// [`CircuitBreaker::call`] will run `call` if the [`CircuitBreaker`] allows it
+    /// and record its result, or return an [`Err`] right away if it is open.
+    /// # Example
+    /// [`CircuitBreaker::call`] can be used to fail fast when an upstream is down:
+    /// ```rust
+    /// use http::circuit_breaker::CircuitBreaker;
+    /// use std::net::TcpStream;
+    ///
+    /// fn ping_upstream(circuit_breaker: &CircuitBreaker) -> Result<(), String> {
+    ///     circuit_breaker.call(|| {
+    ///         TcpStream::connect("127.0.0.1:9000")
+    ///             .map(|_| ())
+    ///             .map_err(|error| error.to_string())
+    ///     })
+    /// }
+    /// ```
+    pub fn call<T>(&self, call: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+        if !self.allow_request() {
+            return Err("circuit breaker is open, failing fast".to_string());
+        }
+        let result = call();
+        match result {
+            Ok(_) => self.record_success(),
+            Err(_) => self.record_failure(),
+        }
+        result
+    }
+
+    /// [`CircuitBreaker::retry_after`] will get how long until an open
+    /// [`CircuitBreaker`] allows a trial call, defaults to [`Duration::ZERO`] if it
+    /// isn't open.
+    pub fn retry_after(&self) -> Duration {
+        let state = self.lock();
+        match (state.state, state.opened_at) {
+            (CircuitState::Open, Some(opened_at)) => {
+                self.open_duration.saturating_sub(opened_at.elapsed())
+            }
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// [`CircuitBreaker::open`] will open the [`CircuitBreaker`] from now.
+    fn open(&self, state: &mut BreakerState) {
+        state.state = CircuitState::Open;
+        state.opened_at = Some(Instant::now());
+        state.outcomes.clear();
+        state.is_trial_running = false;
+    }
+
+    /// [`CircuitBreaker::push_outcome`] will count a call outcome, dropping the
+    /// oldest one once the window is full.
+    fn push_outcome(&self, state: &mut BreakerState, is_ok: bool) {
+        if state.outcomes.len() == self.window_size {
+            state.outcomes.pop_front();
+        }
+        state.outcomes.push_back(is_ok);
+    }
+
+    /// [`CircuitBreaker::lock`] will lock the [`BreakerState`], recovering it if a
+    /// caller panicked while holding the lock.
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Implement [`Default`] for [`CircuitBreaker`].
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        CircuitBreaker::new()
+    }
+}
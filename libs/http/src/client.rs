@@ -0,0 +1,546 @@
+use crate::{
+    circuit_breaker::{CircuitBreaker, CircuitState},
+    constants::{CONTENT_LENGTH, HTTP_VERSION, TRANSFER_ENCODING},
+    correlation::Correlation,
+    header,
+    methods::HEAD,
+    retry::RetryPolicy,
+    signing::RequestSigner,
+};
+use logger::{log_debug, log_warning};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+/// [`DEFAULT_CLIENT_TIMEOUT`] is how long a [`Client`] waits to connect, and for
+/// each read or write, unless [`Client::with_timeout`] is used.
+pub const DEFAULT_CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// [`DEFAULT_MAX_RESPONSE_BYTES`] is the largest response body a [`Client`] reads,
+/// unless [`Client::with_max_response_bytes`] is used.
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+/// [`Client`] sends HTTP/1.1 requests to `http://` upstreams over a new connection
+/// for each request, optionally guarded by a [`CircuitBreaker`] and retried with a
+/// [`RetryPolicy`], and optionally signed by a [`RequestSigner`].
+#[derive(Clone, Debug)]
+pub struct Client {
+    /// [`Client::timeout`] is how long the [`Client`] waits to connect, and for
+    /// each read or write.
+    pub timeout: Duration,
+    /// [`Client::circuit_breaker`] is the [`CircuitBreaker`] every request goes
+    /// through, if any. Connection errors and `5xx` responses count as failures.
+    pub circuit_breaker: Option<Arc<CircuitBreaker>>,
+    /// [`Client::retry_policy`] is the [`RetryPolicy`] failed idempotent requests
+    /// are retried with, if any.
+    pub retry_policy: Option<RetryPolicy>,
+    /// [`Client::signer`] is the [`RequestSigner`] every attempt is signed with
+    /// right before it is sent, if any.
+    pub signer: Option<Arc<dyn RequestSigner>>,
+    /// [`Client::max_response_bytes`] is the largest response body the [`Client`]
+    /// reads, where a larger one is an [`Err`] instead of being buffered.
+    pub max_response_bytes: usize,
+}
+
+/// [`ClientRequest`] represents a request sent by a [`Client`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientRequest {
+    /// [`ClientRequest::method`] is the HTTP method of the request, such as `"GET"`.
+    pub method: String,
+    /// [`ClientRequest::url`] is the absolute `http://` URL of the request.
+    pub url: String,
+    /// [`ClientRequest::headers`] is every HTTP header sent with the request,
+    /// besides `Host`, `Connection`, and `Content-Length`, which are always set by
+    /// the [`Client`].
+    pub headers: Vec<(String, String)>,
+    /// [`ClientRequest::body`] is the body sent with the request.
+    pub body: Vec<u8>,
+}
+
+/// [`ClientResponse`] represents a response received by a [`Client`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientResponse {
+    /// [`ClientResponse::status_code`] is the numeric status code, such as `200`.
+    pub status_code: u16,
+    /// [`ClientResponse::reason`] is the reason phrase of the status, such as `"OK"`.
+    pub reason: String,
+    /// [`ClientResponse::headers`] is every HTTP header of the response, in order.
+    pub headers: Vec<(String, String)>,
+    /// [`ClientResponse::body`] is the body of the response, with any chunked
+    /// transfer coding removed.
+    pub body: Vec<u8>,
+}
+
+impl Client {
+    /// [`Client::new`] creates a [`Client`] with [`DEFAULT_CLIENT_TIMEOUT`] and
+    /// [`DEFAULT_MAX_RESPONSE_BYTES`], no [`CircuitBreaker`], [`RetryPolicy`], or
+    /// [`RequestSigner`].
+    /// # Example
+    /// [`Client::new`] can be used to fetch a resource from an upstream:
+    /// ```rust
+    /// use http::client::{Client, ClientRequest};
+    ///
+    /// fn fetch_users() -> Result<String, String> {
+    ///     let response = Client::new().send(&ClientRequest::new("GET", "http://127.0.0.1:9000/users"))?;
+    ///     Ok(response.body_as_string())
+    /// }
+    /// ```
+    pub fn new() -> Self {
+        Client {
+            timeout: DEFAULT_CLIENT_TIMEOUT,
+            circuit_breaker: None,
+            retry_policy: None,
+            signer: None,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        }
+    }
+
+    /// [`Client::with_timeout`] will set [`Client::timeout`], and return the
+    /// [`Client`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// [`Client::with_max_response_bytes`] will set [`Client::max_response_bytes`],
+    /// and return the [`Client`].
+    /// # Example
+    /// [`Client::with_max_response_bytes`] can be used to refuse an upstream that
+    /// answers with more than a megabyte:
+    /// ```rust
+    /// use http::client::Client;
+    ///
+    /// fn create_small_response_client() -> Client {
+    ///     Client::new().with_max_response_bytes(1024 * 1024)
+    /// }
+    /// ```
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// [`Client::with_circuit_breaker`] will send every request through the
+    /// provided [`CircuitBreaker`], and return the [`Client`]. The
+    /// [`CircuitBreaker`] can be shared by clients that call the same upstream.
+    /// # Example
+    /// [`Client::with_circuit_breaker`] can be used to fail fast while an upstream
+    /// is down:
+    /// ```rust
+    /// use http::{circuit_breaker::CircuitBreaker, client::Client};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_guarded_client() -> Client {
+    ///     Client::new().with_circuit_breaker(Arc::new(CircuitBreaker::new()))
+    /// }
+    /// ```
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// [`Client::with_retry_policy`] will retry failed idempotent requests with the
+    /// provided [`RetryPolicy`], and return the [`Client`].
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// [`Client::with_signer`] will sign every request with the provided
+    /// [`RequestSigner`], and return the [`Client`].
+    /// # Example
+    /// [`Client::with_signer`] can be used to authenticate proxied calls:
+    /// ```rust
+    /// use http::{client::Client, proxy::Proxy, signing::HmacSigner};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_signed_proxy(secret: &[u8]) -> Proxy {
+    ///     let client = Client::new().with_signer(Arc::new(HmacSigner::new("gateway", secret)));
+    ///     Proxy::new("/api", "http://127.0.0.1:9000").with_client(client)
+    /// }
+    /// ```
+    pub fn with_signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// [`Client::send`] will send `request` and wait for the whole response, and
+    /// return an [`Err`] describing the problem if the upstream can't be reached, the
+    /// response is malformed or has a body larger than [`Client::max_response_bytes`],
+    /// or the [`Client::circuit_breaker`] is open. Idempotent
+    /// requests are retried by the [`Client::retry_policy`], until an attempt
+    /// succeeds, the attempts or the retry budget run out, or the
+    /// [`Client::circuit_breaker`] opens.
+    pub fn send(&self, request: &ClientRequest) -> Result<ClientResponse, String> {
+        let Some(retry_policy) = &self.retry_policy else {
+            return self.send_guarded(request);
+        };
+        retry_policy.record_request();
+
+        let attempts = retry_policy.attempts_for(&request.method);
+        let mut attempt = 1;
+        loop {
+            let result = self.send_guarded(request);
+            if attempt >= attempts || !retry_policy.should_retry(&result) || self.is_circuit_open()
+            {
+                return result;
+            }
+            let outcome = match &result {
+                Ok(response) => format!("{} {}", response.status_code, response.reason),
+                Err(error) => error.clone(),
+            };
+            if !retry_policy.take_retry() {
+                log_warning!(
+                    "{} {} attempt {attempt}/{attempts} failed ({outcome}), not retrying since the retry budget is spent",
+                    request.method,
+                    request.url
+                );
+                return result;
+            }
+
+            let delay = retry_policy.delay_for(attempt);
+            log_warning!(
+                "{} {} attempt {attempt}/{attempts} failed ({outcome}), retrying in {delay:?}",
+                request.method,
+                request.url
+            );
+            thread::sleep(delay);
+            attempt += 1;
+            log_debug!(
+                "{} {} attempt {attempt}/{attempts}",
+                request.method,
+                request.url
+            );
+        }
+    }
+
+    /// [`Client::send_guarded`] will send `request` once through the
+    /// [`Client::circuit_breaker`], if there is one.
+    fn send_guarded(&self, request: &ClientRequest) -> Result<ClientResponse, String> {
+        let Some(circuit_breaker) = &self.circuit_breaker else {
+            return self.send_once(request);
+        };
+        if !circuit_breaker.allow_request() {
+            return Err(format!(
+                "circuit breaker is open for '{}', failing fast",
+                request.url
+            ));
+        }
+
+        let result = self.send_once(request);
+        match &result {
+            Ok(response) if response.status_code < 500 => circuit_breaker.record_success(),
+            _ => circuit_breaker.record_failure(),
+        }
+        result
+    }
+
+    /// [`Client::is_circuit_open`] will return `true` if the
+    /// [`Client::circuit_breaker`] is failing requests fast, and `false` otherwise.
+    fn is_circuit_open(&self) -> bool {
+        self.circuit_breaker
+            .as_ref()
+            .is_some_and(|circuit_breaker| circuit_breaker.state() == CircuitState::Open)
+    }
+
+    /// [`Client::send_once`] will add every header of the current [`Correlation`]
+    /// that `request` doesn't set itself, sign it with the [`Client::signer`], if
+    /// there is one, and send it over a new connection, without going through the
+    /// [`Client::circuit_breaker`].
+    fn send_once(&self, request: &ClientRequest) -> Result<ClientResponse, String> {
+        let prepared_request;
+        let request = match (&self.signer, Correlation::current()) {
+            (None, None) => request,
+            (signer, correlation) => {
+                let mut request = request.clone();
+                for (name, value) in correlation
+                    .map(|correlation| correlation.headers)
+                    .unwrap_or_default()
+                {
+                    if !request
+                        .headers
+                        .iter()
+                        .any(|(header_name, _)| header_name.eq_ignore_ascii_case(&name))
+                    {
+                        request.headers.push((name, value));
+                    }
+                }
+                if let Some(signer) = signer {
+                    signer.sign(&mut request)?;
+                }
+                prepared_request = request;
+                &prepared_request
+            }
+        };
+        let (authority, target) = split_url(&request.url)?;
+        // a header that can't be sent could smuggle another request to the upstream
+        for (name, value) in &request.headers {
+            header::check_header(name, value)?;
+        }
+        let mut stream = self.connect(authority)?;
+
+        let mut head = format!(
+            "{} {target} {HTTP_VERSION}\r\nHost: {authority}\r\nConnection: close\r\n{CONTENT_LENGTH}: {}\r\n",
+            request.method,
+            request.body.len()
+        );
+        for (name, value) in &request.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("\r\n");
+        stream
+            .write_all(head.as_bytes())
+            .and_then(|_| stream.write_all(&request.body))
+            .and_then(|_| stream.flush())
+            .map_err(|error| format!("can't send request to '{authority}': {error}"))?;
+
+        read_response(
+            &mut BufReader::new(stream),
+            request.method == HEAD.as_str(),
+            self.max_response_bytes,
+        )
+    }
+
+    /// [`Client::connect`] will connect to the first address `authority` resolves
+    /// to that accepts a connection within [`Client::timeout`], using port `80`
+    /// when `authority` has no port.
+    fn connect(&self, authority: &str) -> Result<TcpStream, String> {
+        let addresses = if authority.contains(':') {
+            authority.to_socket_addrs()
+        } else {
+            (authority, 80).to_socket_addrs()
+        }
+        .map_err(|error| format!("can't resolve '{authority}': {error}"))?;
+
+        let mut last_error = format!("'{authority}' did not resolve to any address");
+        for address in addresses {
+            match TcpStream::connect_timeout(&address, self.timeout) {
+                Ok(stream) => {
+                    stream
+                        .set_read_timeout(Some(self.timeout))
+                        .and_then(|_| stream.set_write_timeout(Some(self.timeout)))
+                        .map_err(|error| error.to_string())?;
+                    return Ok(stream);
+                }
+                Err(error) => last_error = format!("can't connect to '{authority}': {error}"),
+            }
+        }
+        Err(last_error)
+    }
+}
+
+/// Implement [`Default`] for [`Client`].
+impl Default for Client {
+    fn default() -> Self {
+        Client::new()
+    }
+}
+
+impl ClientRequest {
+    /// [`ClientRequest::new`] creates a [`ClientRequest`] for `method` and `url`,
+    /// without any headers or body.
+    pub fn new(method: &str, url: &str) -> Self {
+        ClientRequest {
+            method: method.to_string(),
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// [`ClientRequest::with_header`] will add an HTTP header to the
+    /// [`ClientRequest`], and return the [`ClientRequest`].
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// [`ClientRequest::with_body`] will set [`ClientRequest::body`], and return the
+    /// [`ClientRequest`].
+    /// # Example
+    /// [`ClientRequest::with_body`] can be used to post JSON to an upstream:
+    /// ```rust
+    /// use http::client::ClientRequest;
+    ///
+    /// fn create_post(url: &str, json: &str) -> ClientRequest {
+    ///     ClientRequest::new("POST", url)
+    ///         .with_header("Content-Type", "application/json")
+    ///         .with_body(json.as_bytes())
+    /// }
+    /// ```
+    pub fn with_body(mut self, body: &[u8]) -> Self {
+        self.body = body.to_vec();
+        self
+    }
+}
+
+impl ClientResponse {
+    /// [`ClientResponse::header`] will get the value of a header by `name`, ignoring
+    /// case, defaults to [`None`] if the header is not found.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// [`ClientResponse::body_as_string`] will get [`ClientResponse::body`] as text,
+    /// replacing any invalid UTF-8.
+    pub fn body_as_string(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+}
+
+/// [`split_url`] will split an `http://` `url` into its authority, such as
+/// `host:port`, and its request target, which is `/` if the URL has no path. A
+/// `url` with whitespace or a control character, such as a line break, is refused.
+pub(crate) fn split_url(url: &str) -> Result<(&str, &str), String> {
+    if url
+        .contains(|character: char| character.is_ascii_whitespace() || character.is_ascii_control())
+    {
+        return Err(format!(
+            "{url:?} can't contain whitespace or control characters"
+        ));
+    }
+    let Some(rest) = url.strip_prefix("http://") else {
+        return Err(format!("'{url}' is not an http:// URL"));
+    };
+    let target_start = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, target) = rest.split_at(target_start);
+    if authority.is_empty() {
+        return Err(format!("'{url}' has no host"));
+    }
+    Ok((authority, if target.is_empty() { "/" } else { target }))
+}
+
+/// [`read_response`] will read a whole response from `reader`, which has no body
+/// when it answers a `HEAD` request, and return an [`Err`] for a body larger than
+/// `max_body_bytes`. The body is only ever buffered as it arrives, so a
+/// `Content-Length` or chunk size the upstream lies about can't allocate more.
+fn read_response(
+    reader: &mut impl BufRead,
+    is_head: bool,
+    max_body_bytes: usize,
+) -> Result<ClientResponse, String> {
+    let status_line = read_line(reader)?;
+    let mut status_parts = status_line.splitn(3, ' ');
+    let status_code = status_parts
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| format!("malformed status line '{status_line}'"))?;
+    let reason = status_parts.next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("malformed header line '{line}'"))?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+
+    let has_body = !is_head && status_code >= 200 && status_code != 204 && status_code != 304;
+    let mut body = Vec::new();
+    if !has_body {
+        // a response to HEAD, or with a 1xx, 204, or 304 status, never has a body
+    } else if header(TRANSFER_ENCODING).is_some_and(|coding| coding.eq_ignore_ascii_case("chunked"))
+    {
+        read_chunked_body(reader, &mut body, max_body_bytes)?;
+    } else if let Some(length) = header(CONTENT_LENGTH) {
+        let length = length
+            .parse::<usize>()
+            .map_err(|_| format!("malformed Content-Length '{length}'"))?;
+        if length > max_body_bytes {
+            return Err(format!(
+                "response body of {length} bytes is over the {max_body_bytes} byte limit"
+            ));
+        }
+        read_body(reader, &mut body, length)?;
+    } else {
+        reader
+            .by_ref()
+            .take(max_body_bytes as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|error| format!("can't read response body: {error}"))?;
+        if body.len() > max_body_bytes {
+            return Err(format!(
+                "response body is over the {max_body_bytes} byte limit"
+            ));
+        }
+    }
+
+    Ok(ClientResponse {
+        status_code,
+        reason,
+        headers,
+        body,
+    })
+}
+
+/// [`read_chunked_body`] will read a body sent with the chunked transfer coding onto
+/// `body`, skipping any chunk extensions and trailers, and return an [`Err`] once
+/// the chunks add up to more than `max_body_bytes`.
+fn read_chunked_body(
+    reader: &mut impl BufRead,
+    body: &mut Vec<u8>,
+    max_body_bytes: usize,
+) -> Result<(), String> {
+    loop {
+        let size_line = read_line(reader)?;
+        let size_digits = size_line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_digits, 16)
+            .map_err(|_| format!("malformed chunk size '{size_line}'"))?;
+        if size == 0 {
+            while !read_line(reader)?.is_empty() {}
+            return Ok(());
+        }
+        if size > max_body_bytes - body.len() {
+            return Err(format!(
+                "chunked response body is over the {max_body_bytes} byte limit"
+            ));
+        }
+
+        read_body(reader, body, size)?;
+        read_line(reader)?;
+    }
+}
+
+/// [`read_body`] will read exactly `length` more bytes from `reader` onto `body`,
+/// growing `body` only as the bytes arrive, and return an [`Err`] if the connection
+/// closed first.
+fn read_body(reader: &mut impl BufRead, body: &mut Vec<u8>, length: usize) -> Result<(), String> {
+    let read = reader
+        .by_ref()
+        .take(length as u64)
+        .read_to_end(body)
+        .map_err(|error| format!("can't read response body: {error}"))?;
+    if read < length {
+        return Err(format!(
+            "connection closed after {read} of {length} response body bytes"
+        ));
+    }
+    Ok(())
+}
+
+/// [`read_line`] will read a single line from `reader` without its line ending,
+/// and return an [`Err`] if the connection closed first.
+fn read_line(reader: &mut impl BufRead) -> Result<String, String> {
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(0) => Err("connection closed before the response was complete".to_string()),
+        Ok(_) => Ok(line.trim_end_matches(['\r', '\n']).to_string()),
+        Err(error) => Err(format!("can't read response: {error}")),
+    }
+}
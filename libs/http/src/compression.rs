@@ -0,0 +1,250 @@
+/// [`Encoding`] represents a content coding the server can send a response body with.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Encoding {
+    /// [`Encoding::Identity`] sends the response body as-is.
+    Identity,
+    /// [`Encoding::Brotli`] compresses the response body with brotli (`br`), which
+    /// is only available when the `brotli` feature is enabled.
+    Brotli,
+    /// [`Encoding::Gzip`] compresses the response body with gzip (`gzip`), which is
+    /// only available when the `gzip` feature is enabled.
+    Gzip,
+}
+
+impl Encoding {
+    /// [`Encoding::as_str`] returns the content coding name of an [`Encoding`], as
+    /// it is written in `Accept-Encoding` and `Content-Encoding` headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Identity => "identity",
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+        }
+    }
+
+    /// [`Encoding::is_supported`] will return `true` if the server was built with
+    /// support for the [`Encoding`], and `false` otherwise.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Encoding::Identity => true,
+            Encoding::Brotli => cfg!(feature = "brotli"),
+            Encoding::Gzip => cfg!(feature = "gzip"),
+        }
+    }
+
+    /// [`Encoding::compress`] will encode `body` with the [`Encoding`]. Returns
+    /// `body` unchanged for [`Encoding::Identity`] or an unsupported [`Encoding`].
+    /// # Example
+    /// [`Encoding::compress`] can be used to compress a response body:
+    /// ```rust
+    /// use http::compression::Encoding;
+    ///
+    /// fn compress_body(body: &str) -> Vec<u8> {
+    ///     Encoding::Brotli.compress(body.as_bytes())
+    /// }
+    /// ```
+    pub fn compress(&self, body: &[u8]) -> Vec<u8> {
+        match self {
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => {
+                use std::io::Write;
+
+                let mut compressor = brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+                compressor
+                    .write_all(body)
+                    .expect("writing to an in-memory buffer can't fail");
+                compressor.into_inner()
+            }
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => {
+                use std::io::Write;
+
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(body)
+                    .expect("writing to an in-memory buffer can't fail");
+                encoder
+                    .finish()
+                    .expect("writing to an in-memory buffer can't fail")
+            }
+            _ => body.to_vec(),
+        }
+    }
+}
+
+/// [`ENCODINGS`] is every [`Encoding`] besides [`Encoding::Identity`], in the order
+/// the server prefers them when a client accepts several equally.
+const ENCODINGS: [Encoding; 2] = [Encoding::Brotli, Encoding::Gzip];
+
+/// [`negotiate`] will pick the best supported [`Encoding`] for the provided value
+/// of an `Accept-Encoding` header, based on the q-value of every coding the client
+/// accepts. Defaults to [`Encoding::Identity`] when nothing better is accepted.
+/// # Example
+/// [`negotiate`] can be used to pick an [`Encoding`] for a request:
+/// ```rust
+/// use http::{compression::{self, Encoding}, request::Request};
+///
+/// fn pick_encoding(request: &Request) -> Encoding {
+///     compression::negotiate(request.header("accept-encoding"))
+/// }
+/// ```
+pub fn negotiate(accept_encoding: Option<&str>) -> Encoding {
+    let Some(accept_encoding) = accept_encoding else {
+        return Encoding::Identity;
+    };
+    let accepted: Vec<(String, f32)> = accept_encoding
+        .split(',')
+        .filter_map(parse_coding)
+        .collect();
+
+    let mut best = (Encoding::Identity, 0.0);
+    for encoding in ENCODINGS.iter().filter(|encoding| encoding.is_supported()) {
+        let quality = accepted
+            .iter()
+            .find(|(coding, _)| coding == encoding.as_str())
+            .or_else(|| accepted.iter().find(|(coding, _)| coding == "*"))
+            .map(|(_, quality)| *quality)
+            .unwrap_or(0.0);
+        if quality > best.1 {
+            best = (encoding.clone(), quality);
+        }
+    }
+    best.0
+}
+
+/// [`parse_coding`] will parse a single coding of an `Accept-Encoding` header,
+/// such as `br;q=0.8`, into its lowercase name and q-value.
+fn parse_coding(coding: &str) -> Option<(String, f32)> {
+    let mut parts = coding.split(';');
+    let name = parts.next()?.trim().to_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+
+    let quality = parts
+        .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+        .filter_map(|quality| quality.trim().parse::<f32>().ok())
+        .next()
+        .unwrap_or(1.0);
+    Some((name, quality.clamp(0.0, 1.0)))
+}
+
+/// [`CompressionPolicy`] decides which responses are worth compressing, skipping
+/// bodies that are too small to gain anything, content that is already compressed
+/// such as images, and paths that stream their responses such as server-sent events.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompressionPolicy {
+    /// [`CompressionPolicy::min_bytes`] is how big a body has to be to be
+    /// compressed.
+    pub min_bytes: usize,
+    /// [`CompressionPolicy::excluded_content_types`] is every content type that is
+    /// never compressed, such as `image/png`, or `video/*` for every video.
+    pub excluded_content_types: Vec<String>,
+    /// [`CompressionPolicy::excluded_paths`] is every path that is never
+    /// compressed, along with every path below it.
+    pub excluded_paths: Vec<String>,
+}
+
+impl CompressionPolicy {
+    /// [`CompressionPolicy::new`] creates a [`CompressionPolicy`] that compresses
+    /// every response.
+    pub fn new() -> Self {
+        CompressionPolicy::default()
+    }
+
+    /// [`CompressionPolicy::with_min_bytes`] will only compress bodies of at least
+    /// `min_bytes`, and return the [`CompressionPolicy`].
+    pub fn with_min_bytes(mut self, min_bytes: usize) -> Self {
+        self.min_bytes = min_bytes;
+        self
+    }
+
+    /// [`CompressionPolicy::with_excluded_content_types`] will never compress a
+    /// body of any of `content_types`, where `video/*` stands for every video, and
+    /// return the [`CompressionPolicy`].
+    pub fn with_excluded_content_types(mut self, content_types: &[&str]) -> Self {
+        self.excluded_content_types.extend(
+            content_types
+                .iter()
+                .map(|content_type| content_type.trim().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    /// [`CompressionPolicy::with_excluded_paths`] will never compress a response
+    /// for any of `paths`, or any path below them, and return the
+    /// [`CompressionPolicy`].
+    pub fn with_excluded_paths(mut self, paths: &[&str]) -> Self {
+        self.excluded_paths.extend(
+            paths
+                .iter()
+                .map(|path| path.trim().trim_end_matches('/').to_string()),
+        );
+        self
+    }
+
+    /// [`CompressionPolicy::apply`] will get the [`Encoding`] to send a response
+    /// for `path` with, which is the `negotiated` one unless the body of
+    /// `body_bytes` with the `content_type` is excluded, where it is
+    /// [`Encoding::Identity`].
+    /// # Example
+    /// [`CompressionPolicy::apply`] can be used to leave images uncompressed:
+    /// ```rust
+    /// use http::compression::{CompressionPolicy, Encoding};
+    ///
+    /// fn get_encoding(path: &str, content_type: Option<&str>, body: &str) -> Encoding {
+    ///     CompressionPolicy::new()
+    ///         .with_min_bytes(1024)
+    ///         .with_excluded_content_types(&["image/*"])
+    ///         .apply(Encoding::Brotli, path, content_type, body.len())
+    /// }
+    /// ```
+    pub fn apply(
+        &self,
+        negotiated: Encoding,
+        path: &str,
+        content_type: Option<&str>,
+        body_bytes: usize,
+    ) -> Encoding {
+        if negotiated == Encoding::Identity
+            || body_bytes < self.min_bytes
+            || self.excludes_content_type(content_type)
+            || self.excludes_path(path)
+        {
+            return Encoding::Identity;
+        }
+        negotiated
+    }
+
+    /// [`CompressionPolicy::excludes_content_type`] will return `true` if
+    /// `content_type`, without any of its parameters, is excluded.
+    fn excludes_content_type(&self, content_type: Option<&str>) -> bool {
+        let Some(content_type) = content_type else {
+            return false;
+        };
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or(content_type)
+            .trim()
+            .to_ascii_lowercase();
+        self.excluded_content_types
+            .iter()
+            .any(|excluded| match excluded.strip_suffix("/*") {
+                Some(top_level) => media_type
+                    .split_once('/')
+                    .is_some_and(|(media_top_level, _)| media_top_level == top_level),
+                None => *excluded == media_type,
+            })
+    }
+
+    /// [`CompressionPolicy::excludes_path`] will return `true` if `path` is an
+    /// excluded path, or below one.
+    fn excludes_path(&self, path: &str) -> bool {
+        self.excluded_paths.iter().any(|excluded| {
+            path.strip_prefix(excluded.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
@@ -8,4 +8,49 @@ pub const CONTENT_LENGTH: &str = "Content-Length";
 pub const CONTENT_TYPE: &str = "Content-Type";
 
 /// [`CONTENT_JSON`] is a `const` [`str`] that represents the HTTP header for denoting JSON content.
-pub const CONTENT_JSON: &str = "application/json";
+pub const CONTENT_JSON: &str = "application/json; charset=utf-8";
+
+/// [`ALLOW`] is a `const` [`str`] that represents the HTTP header for listing allowed methods.
+pub const ALLOW: &str = "Allow";
+
+/// [`VARY`] is a `const` [`str`] that represents the HTTP header for listing request headers that change a response.
+pub const VARY: &str = "Vary";
+
+/// [`TRANSFER_ENCODING`] is a `const` [`str`] that represents the HTTP header for transfer encoding.
+pub const TRANSFER_ENCODING: &str = "Transfer-Encoding";
+
+/// [`TRAILER`] is a `const` [`str`] that represents the HTTP header for advertising trailers.
+pub const TRAILER: &str = "Trailer";
+
+/// [`CONTENT_ENCODING`] is a `const` [`str`] that represents the HTTP header for content encoding.
+pub const CONTENT_ENCODING: &str = "Content-Encoding";
+
+/// [`CACHE_CONTROL`] is a `const` [`str`] that represents the HTTP header for caching directives.
+pub const CACHE_CONTROL: &str = "Cache-Control";
+
+/// [`LAST_MODIFIED`] is a `const` [`str`] that represents the HTTP header for when a resource last changed.
+pub const LAST_MODIFIED: &str = "Last-Modified";
+
+/// [`LOCATION`] is a `const` [`str`] that represents the HTTP header for where a response redirects to.
+pub const LOCATION: &str = "Location";
+
+/// [`SET_COOKIE`] is a `const` [`str`] that represents the HTTP header for storing a cookie on the client.
+pub const SET_COOKIE: &str = "Set-Cookie";
+
+/// [`CONTENT_XML`] is a `const` [`str`] that represents the HTTP header for denoting XML content.
+pub const CONTENT_XML: &str = "application/xml; charset=utf-8";
+
+/// [`CONTENT_CSV`] is a `const` [`str`] that represents the HTTP header for denoting CSV content.
+pub const CONTENT_CSV: &str = "text/csv; charset=utf-8";
+
+/// [`CONTENT_NDJSON`] is a `const` [`str`] that represents the HTTP header for denoting newline-delimited JSON content.
+pub const CONTENT_NDJSON: &str = "application/x-ndjson; charset=utf-8";
+
+/// [`CONTENT_EVENT_STREAM`] is a `const` [`str`] that represents the HTTP header for denoting server-sent events.
+pub const CONTENT_EVENT_STREAM: &str = "text/event-stream";
+
+/// [`CONTENT_FORM`] is a `const` [`str`] that represents the HTTP header for denoting form-urlencoded content.
+pub const CONTENT_FORM: &str = "application/x-www-form-urlencoded";
+
+/// [`CONTENT_MULTIPART`] is a `const` [`str`] that represents the HTTP header for denoting multipart form content, such as file uploads.
+pub const CONTENT_MULTIPART: &str = "multipart/form-data";
@@ -0,0 +1,200 @@
+use crate::{
+    extensions::TypeMap,
+    methods::GET,
+    request::{ClientAddress, Request},
+    session::Session,
+    tenant::TenantId,
+};
+use logger::log_severity::LogSeverity;
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// [`Deadline`] is when the server stops waiting on a [`Request`], which the server
+/// adds to [`Request::extensions`] when it has a request timeout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Deadline(pub Instant);
+
+/// [`SharedState`] is the state every handler shares, from
+/// [`Router::with_state`](crate::router::Router::with_state), which the server adds
+/// to [`Request::extensions`] of every [`Request`] it routes.
+#[derive(Clone)]
+pub struct SharedState(pub Arc<TypeMap>);
+
+/// [`Context`] bundles a [`Request`] with everything else a handler can use while
+/// serving it, such as its deadline, the state every handler shares, and a logger
+/// scoped to its route. A handler gets it with a `ctx: &Context` argument, so new
+/// per-request facilities don't change the signature of existing handlers.
+pub struct Context {
+    /// [`Context::request`] is the [`Request`] being served.
+    pub request: Request,
+    /// [`Context::scope`] is what every message logged through the [`Context`] is
+    /// prefixed with, which is the method and pattern of the route, such as
+    /// `GET /user/{id}`.
+    pub scope: String,
+}
+
+impl Context {
+    /// [`Context::new`] creates a [`Context`] for `request`, which logs messages
+    /// prefixed with `scope`.
+    /// # Example
+    /// [`Context::new`] can be used to call a handler that takes a [`Context`] by
+    /// hand:
+    /// ```rust
+    /// use http::{context::Context, request::Request};
+    ///
+    /// fn greet(ctx: &Context) -> String {
+    ///     format!("hello {}", ctx.query_param("name").unwrap_or("stranger"))
+    /// }
+    ///
+    /// fn serve_greeting(request: Request) -> String {
+    ///     greet(&Context::new(request, "GET /greet"))
+    /// }
+    /// ```
+    pub fn new(request: Request, scope: &str) -> Self {
+        Context {
+            request,
+            scope: scope.to_string(),
+        }
+    }
+
+    /// [`Context::path_param`] will get the value of the path parameter `name`,
+    /// or [`None`] if the route has no such parameter.
+    pub fn path_param(&self, name: &str) -> Option<&str> {
+        self.request.path_params.get(name).map(String::as_str)
+    }
+
+    /// [`Context::query_param`] will get the value of the query parameter `name`,
+    /// as [`Request::query_param`] does.
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.request.query_param(name)
+    }
+
+    /// [`Context::extension`] will get the value of type `T` from
+    /// [`Request::extensions`], as [`Request::extension`] does.
+    pub fn extension<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.request.extension::<T>()
+    }
+
+    /// [`Context::state`] will get the value of type `T` from the [`SharedState`]
+    /// of the server, or [`None`] if the server has no state of that type.
+    /// # Example
+    /// [`Context::state`] can be used to read configuration every handler shares:
+    /// ```rust
+    /// use http::context::Context;
+    ///
+    /// struct Greeting(String);
+    ///
+    /// fn greet(ctx: &Context) -> String {
+    ///     ctx.state::<Greeting>()
+    ///         .map(|greeting| greeting.0.clone())
+    ///         .unwrap_or_else(|| "hello".to_string())
+    /// }
+    /// ```
+    pub fn state<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.extension::<SharedState>()
+            .and_then(|SharedState(state)| state.get::<T>())
+    }
+
+    /// [`Context::client_address`] will get the [`ClientAddress`] the [`Request`]
+    /// came from, which the server always adds.
+    pub fn client_address(&self) -> Option<ClientAddress> {
+        self.extension::<ClientAddress>().copied()
+    }
+
+    /// [`Context::session`] will get the [`Session`] of the client, which the server
+    /// adds to every [`Request`] it routes, and saves once the handler returns.
+    /// # Example
+    /// [`Context::session`] can be used to count the visits of a client:
+    /// ```rust
+    /// use http::context::Context;
+    ///
+    /// fn count_visit(ctx: &Context) -> String {
+    ///     let Some(session) = ctx.session() else {
+    ///         return "0".to_string();
+    ///     };
+    ///     let visits = session.get("visits").and_then(|visits| visits.parse::<u32>().ok());
+    ///     let visits = visits.unwrap_or(0) + 1;
+    ///     session.insert("visits", &visits.to_string());
+    ///     visits.to_string()
+    /// }
+    /// ```
+    pub fn session(&self) -> Option<&Session> {
+        self.extension::<Session>()
+    }
+
+    /// [`Context::tenant`] will get the [`TenantId`] the [`Request`] was made for,
+    /// which the server adds to every [`Request`] it routes when tenants are
+    /// resolved.
+    /// # Example
+    /// [`Context::tenant`] can be used to keep the values of every tenant apart:
+    /// ```rust
+    /// use http::context::Context;
+    ///
+    /// fn get_cache_key(ctx: &Context, key: &str) -> String {
+    ///     ctx.tenant()
+    ///         .map(|tenant| tenant.scope(key))
+    ///         .unwrap_or_else(|| key.to_string())
+    /// }
+    /// ```
+    pub fn tenant(&self) -> Option<&TenantId> {
+        self.extension::<TenantId>()
+    }
+
+    /// [`Context::deadline`] will get the [`Instant`] the server stops waiting on
+    /// the [`Request`], or [`None`] if it has no request timeout.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.extension::<Deadline>()
+            .map(|Deadline(deadline)| *deadline)
+    }
+
+    /// [`Context::time_remaining`] will get how long is left until
+    /// [`Context::deadline`], which is [`Duration::ZERO`] once it has passed, or
+    /// [`None`] if there is no deadline.
+    /// # Example
+    /// [`Context::time_remaining`] can be used to skip slow work that can't finish
+    /// in time:
+    /// ```rust
+    /// use http::context::Context;
+    /// use std::time::Duration;
+    ///
+    /// fn build_report(ctx: &Context) -> String {
+    ///     match ctx.time_remaining() {
+    ///         Some(remaining) if remaining < Duration::from_secs(1) => "\"partial\"".to_string(),
+    ///         _ => "\"full\"".to_string(),
+    ///     }
+    /// }
+    /// ```
+    pub fn time_remaining(&self) -> Option<Duration> {
+        self.deadline()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// [`Context::log`] will log `message` at `severity`, prefixed with
+    /// [`Context::scope`].
+    /// # Example
+    /// [`Context::log`] can be used to log from a handler with its route:
+    /// ```rust
+    /// use http::context::Context;
+    /// use logger::log_severity::LogSeverity;
+    ///
+    /// fn delete_user(ctx: &Context) -> String {
+    ///     ctx.log(LogSeverity::Info, "deleting a user");
+    ///     "\"deleted\"".to_string()
+    /// }
+    /// ```
+    pub fn log(&self, severity: LogSeverity, message: &str) {
+        logger::log(format!("{}: {message}", self.scope), &severity);
+    }
+}
+
+/// Implement [`Default`] for [`Context`], which is a `GET /` [`Request`] without any
+/// parameters, extensions, or scope.
+impl Default for Context {
+    fn default() -> Self {
+        Context::new(Request::new("/", GET, None, HashMap::new()), "")
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{request::Request, session::new_random_id};
+use std::cell::RefCell;
+
+/// [`REQUEST_ID_HEADER`] is the header that carries the id of a request, which a
+/// [`Correlation`] creates when an inbound request doesn't have one.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// [`DEFAULT_PROPAGATED_HEADERS`] is every header a [`Correlation`] propagates
+/// unless it is given its own allowlist, which are the request id and the W3C trace
+/// context headers.
+pub const DEFAULT_PROPAGATED_HEADERS: [&str; 3] = [REQUEST_ID_HEADER, "traceparent", "tracestate"];
+
+thread_local! {
+    /// [`CURRENT`] is the [`Correlation`] of the request the current thread is
+    /// serving, if any.
+    static CURRENT: RefCell<Option<Correlation>> = const { RefCell::new(None) };
+}
+
+/// [`Correlation`] is the request id and trace headers of an inbound request, which
+/// every request a [`Client`](crate::client::Client) sends while the inbound request
+/// is served carries, so upstream logs can be matched with it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Correlation {
+    /// [`Correlation::headers`] is every propagated header with its value.
+    pub headers: Vec<(String, String)>,
+}
+
+/// [`CorrelationGuard`] keeps a [`Correlation`] current on its thread until it is
+/// dropped, which restores the [`Correlation`] that was current before it.
+#[must_use = "the correlation is only current until its CorrelationGuard is dropped"]
+pub struct CorrelationGuard {
+    /// [`CorrelationGuard::previous`] is the [`Correlation`] that was current
+    /// before, if any.
+    previous: Option<Correlation>,
+}
+
+impl Correlation {
+    /// [`Correlation::from_request`] creates a [`Correlation`] with every header of
+    /// `allowlist` that `request` has, ignoring case, and a new
+    /// [`REQUEST_ID_HEADER`] when the allowlist has it but `request` doesn't.
+    /// # Example
+    /// [`Correlation::from_request`] can be used to propagate the request id and a
+    /// tenant header:
+    /// ```rust
+    /// use http::{correlation::Correlation, request::Request};
+    ///
+    /// fn correlate(request: &Request) -> Correlation {
+    ///     Correlation::from_request(request, &["X-Request-Id", "X-Tenant"])
+    /// }
+    /// ```
+    pub fn from_request(request: &Request, allowlist: &[&str]) -> Self {
+        let headers = allowlist
+            .iter()
+            .filter_map(|name| match request.header(name) {
+                Some(value) => Some((name.to_string(), value.to_string())),
+                None if name.eq_ignore_ascii_case(REQUEST_ID_HEADER) => {
+                    Some((name.to_string(), new_random_id()))
+                }
+                None => None,
+            })
+            .collect();
+        Correlation { headers }
+    }
+
+    /// [`Correlation::request_id`] will get the value of [`REQUEST_ID_HEADER`], or
+    /// [`None`] if it isn't propagated.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(REQUEST_ID_HEADER))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// [`Correlation::enter`] will make the [`Correlation`] current on this thread,
+    /// until the returned [`CorrelationGuard`] is dropped.
+    /// # Example
+    /// [`Correlation::enter`] can be used to keep the headers of a request on the
+    /// upstream calls made while serving it:
+    /// ```rust
+    /// use http::{correlation::{Correlation, DEFAULT_PROPAGATED_HEADERS}, request::Request, response::Response};
+    ///
+    /// fn serve(request: Request, handler: impl Fn(Request) -> Response) -> Response {
+    ///     let _correlation =
+    ///         Correlation::from_request(&request, &DEFAULT_PROPAGATED_HEADERS).enter();
+    ///     handler(request)
+    /// }
+    /// ```
+    pub fn enter(self) -> CorrelationGuard {
+        CorrelationGuard {
+            previous: CURRENT.with(|current| current.replace(Some(self))),
+        }
+    }
+
+    /// [`Correlation::current`] will get a copy of the [`Correlation`] that is
+    /// current on this thread, or [`None`] outside of a request.
+    pub fn current() -> Option<Correlation> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+}
+
+/// Implement [`Drop`] for [`CorrelationGuard`], restoring the previous
+/// [`Correlation`].
+impl Drop for CorrelationGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = self.previous.take());
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{json::Json, response::Response, status::Status};
+use std::fmt;
+
+/// [`HttpError`] is a ready-made error for handlers, holding the [`Status`] to
+/// answer with and a message for the body, so a handler that returns
+/// `Result<String, HttpError>` can fail without defining an error type of its own.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpError {
+    /// [`HttpError::status`] is the [`Status`] of the [`Response`] for the error.
+    pub status: Status,
+    /// [`HttpError::message`] is the message in the body of the [`Response`] for
+    /// the error.
+    pub message: String,
+}
+
+/// [`ErrorMapper`] maps an error type of an application, such as its domain error
+/// enum, to the [`Status`] and body of the [`Response`] it becomes. It is
+/// implemented once for the error type, so every handler that returns
+/// `Result<String, E>` has its errors answered the same way.
+pub trait ErrorMapper {
+    /// [`ErrorMapper::status`] gets the [`Status`] of the [`Response`] for the
+    /// error.
+    /// # Example
+    /// [`ErrorMapper::status`] can be used to answer a missing user with a
+    /// `404 NOT FOUND`:
+    /// ```rust
+    /// use http::{error::ErrorMapper, status::Status};
+    ///
+    /// enum AppError {
+    ///     UserNotFound,
+    ///     DatabaseDown,
+    /// }
+    ///
+    /// impl ErrorMapper for AppError {
+    ///     fn status(&self) -> Status {
+    ///         match self {
+    ///             AppError::UserNotFound => Status::NotFound,
+    ///             AppError::DatabaseDown => Status::ServiceUnavailable,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn status(&self) -> Status;
+
+    /// [`ErrorMapper::body`] gets the JSON content of the [`Response`] for the
+    /// error, which defaults to the [`ErrorMapper::status`] as a JSON string.
+    fn body(&self) -> String {
+        Json::String(self.status().to_string()).to_string()
+    }
+
+    /// [`ErrorMapper::to_response`] will get the [`Response`] for the error, with
+    /// its content sent as-is when `raw_response` is `true`.
+    fn to_response(&self, raw_response: bool) -> Response {
+        Response::with_status(self.status(), &self.body(), raw_response)
+    }
+}
+
+/// [`IntoResponse`] turns what a handler returns into the [`Response`] that is sent
+/// for it, which the `#[http_*]` attributes use for every handler.
+pub trait IntoResponse {
+    /// [`IntoResponse::into_response`] will turn the value into a [`Response`],
+    /// with its content sent as-is when `raw_response` is `true`.
+    /// # Example
+    /// [`IntoResponse::into_response`] can be used to answer with a handler result:
+    /// ```rust
+    /// use http::{error::IntoResponse, response::Response};
+    ///
+    /// fn respond(result: Result<String, Response>) -> Response {
+    ///     result.into_response(false)
+    /// }
+    /// ```
+    fn into_response(self, raw_response: bool) -> Response;
+
+    /// [`IntoResponse::into_response_with_status`] will turn the value into a
+    /// [`Response`] like [`IntoResponse::into_response`] does, but answer with
+    /// `status` instead of `200 OK` when it succeeds, which the `status` option of
+    /// the `#[http_*]` attributes uses. A [`Response`] and an error keep their own
+    /// [`Status`].
+    /// # Example
+    /// [`IntoResponse::into_response_with_status`] can be used to answer with a
+    /// `201 CREATED`:
+    /// ```rust
+    /// use http::{error::IntoResponse, response::Response, status::Status};
+    ///
+    /// fn respond_created(id: u32) -> Response {
+    ///     format!("{id}").into_response_with_status(Status::Created, false)
+    /// }
+    /// ```
+    fn into_response_with_status(self, _status: Status, raw_response: bool) -> Response
+    where
+        Self: Sized,
+    {
+        self.into_response(raw_response)
+    }
+}
+
+impl HttpError {
+    /// [`HttpError::new`] creates a new [`HttpError`] that is answered with
+    /// `status` and `message`.
+    /// # Example
+    /// [`HttpError::new`] can be used to fail a handler with any [`Status`]:
+    /// ```rust
+    /// use http::{error::HttpError, status::Status};
+    ///
+    /// fn get_report(id: u32) -> Result<String, HttpError> {
+    ///     Err(HttpError::new(Status::BadGateway, &format!("report {id} is not ready")))
+    /// }
+    /// ```
+    pub fn new(status: Status, message: &str) -> Self {
+        HttpError {
+            status,
+            message: message.to_string(),
+        }
+    }
+
+    /// [`HttpError::bad_request`] creates a [`Status::BadRequest`] [`HttpError`]
+    /// with `message`.
+    pub fn bad_request(message: &str) -> Self {
+        HttpError::new(Status::BadRequest, message)
+    }
+
+    /// [`HttpError::forbidden`] creates a [`Status::Forbidden`] [`HttpError`] with
+    /// `message`.
+    pub fn forbidden(message: &str) -> Self {
+        HttpError::new(Status::Forbidden, message)
+    }
+
+    /// [`HttpError::not_found`] creates a [`Status::NotFound`] [`HttpError`] with
+    /// `message`.
+    /// # Example
+    /// [`HttpError::not_found`] can be used to answer a missing user with a
+    /// `404 NOT FOUND`:
+    /// ```rust
+    /// use http::error::HttpError;
+    ///
+    /// fn get_user(id: u32) -> Result<String, HttpError> {
+    ///     if id == 1 {
+    ///         Ok("\"evan\"".to_string())
+    ///     } else {
+    ///         Err(HttpError::not_found(&format!("no user {id}")))
+    ///     }
+    /// }
+    /// ```
+    pub fn not_found(message: &str) -> Self {
+        HttpError::new(Status::NotFound, message)
+    }
+
+    /// [`HttpError::unprocessable_entity`] creates a
+    /// [`Status::UnprocessableEntity`] [`HttpError`] with `message`.
+    pub fn unprocessable_entity(message: &str) -> Self {
+        HttpError::new(Status::UnprocessableEntity, message)
+    }
+
+    /// [`HttpError::server_error`] creates a [`Status::ServerError`] [`HttpError`]
+    /// with `message`.
+    pub fn server_error(message: &str) -> Self {
+        HttpError::new(Status::ServerError, message)
+    }
+
+    /// [`HttpError::service_unavailable`] creates a
+    /// [`Status::ServiceUnavailable`] [`HttpError`] with `message`.
+    pub fn service_unavailable(message: &str) -> Self {
+        HttpError::new(Status::ServiceUnavailable, message)
+    }
+}
+
+/// Implement [`ErrorMapper`] for [`HttpError`], answering with its status and its
+/// message as a JSON string.
+impl ErrorMapper for HttpError {
+    fn status(&self) -> Status {
+        self.status.clone()
+    }
+
+    fn body(&self) -> String {
+        Json::String(self.message.clone()).to_string()
+    }
+}
+
+/// Implement [`fmt::Display`] for [`HttpError`].
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.status, self.message)
+    }
+}
+
+/// Implement [`std::error::Error`] for [`HttpError`].
+impl std::error::Error for HttpError {}
+
+/// Implement [`IntoResponse`] for [`String`], which is a `200 OK` [`Response`].
+impl IntoResponse for String {
+    fn into_response(self, raw_response: bool) -> Response {
+        Response::ok(&self, raw_response)
+    }
+
+    fn into_response_with_status(self, status: Status, raw_response: bool) -> Response {
+        Response::with_status(status, &self, raw_response)
+    }
+}
+
+/// Implement [`IntoResponse`] for [`Response`], which is sent as-is.
+impl IntoResponse for Response {
+    fn into_response(self, _raw_response: bool) -> Response {
+        self
+    }
+}
+
+/// Implement [`IntoResponse`] for [`Result`], where an [`Err`] is mapped by its
+/// [`ErrorMapper`].
+impl<T: IntoResponse, E: ErrorMapper> IntoResponse for Result<T, E> {
+    fn into_response(self, raw_response: bool) -> Response {
+        match self {
+            Ok(value) => value.into_response(raw_response),
+            Err(error) => error.to_response(raw_response),
+        }
+    }
+
+    fn into_response_with_status(self, status: Status, raw_response: bool) -> Response {
+        match self {
+            Ok(value) => value.into_response_with_status(status, raw_response),
+            Err(error) => error.to_response(raw_response),
+        }
+    }
+}
+
+/// Implement [`ErrorMapper`] for [`Response`], so a handler can return any
+/// [`Response`] as its error.
+impl ErrorMapper for Response {
+    fn status(&self) -> Status {
+        self.status.clone()
+    }
+
+    fn to_response(&self, _raw_response: bool) -> Response {
+        self.clone()
+    }
+}
@@ -0,0 +1,52 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+/// [`ThreadWaker`] wakes a future that [`block_on`] is driving by unparking the
+/// thread that is waiting on it.
+struct ThreadWaker(Thread);
+
+/// Implement [`Wake`] for [`ThreadWaker`].
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// [`block_on`] will drive `future` to completion on the current thread, parking
+/// the thread whenever the future is waiting to be woken, and return its output.
+/// It is the bridge the `#[http_*]` attributes use to run an `async fn` handler on
+/// a worker thread, so handlers can await non-blocking IO.
+/// # Example
+/// [`block_on`] can be used to call an `async fn` from a synchronous one:
+/// ```rust
+/// use http::executor;
+///
+/// async fn get_greeting() -> String {
+///     "hello".to_string()
+/// }
+///
+/// fn get_greeting_blocking() -> String {
+///     executor::block_on(get_greeting())
+/// }
+/// ```
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            // a spurious unpark only polls the future one more time
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::respond::Respond;
+
+/// [`csv_row`] will join `fields` into a single CSV line ending with `\r\n`,
+/// quoting any field that holds a comma, quote, or line break, and doubling the
+/// quotes inside it.
+/// # Example
+/// [`csv_row`] can be used to write the header line of a CSV export:
+/// ```rust
+/// use http::export::csv_row;
+///
+/// fn create_header_line() -> String {
+///     csv_row(["id", "name", "notes"])
+/// }
+/// ```
+pub fn csv_row<T: AsRef<str>>(fields: impl IntoIterator<Item = T>) -> String {
+    let mut line = fields
+        .into_iter()
+        .map(|field| csv_field(field.as_ref()))
+        .collect::<Vec<String>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// [`ndjson_line`] will get the JSON representation of `item` on a single line
+/// ending with `\n`, escaping any line break [`Respond::get_json`] left in it.
+/// # Example
+/// [`ndjson_line`] can be used to write one event of an NDJSON export:
+/// ```rust
+/// use http::export::ndjson_line;
+///
+/// fn create_event_line(event: &str) -> String {
+///     ndjson_line(&event)
+/// }
+/// ```
+pub fn ndjson_line<T: Respond + ?Sized>(item: &T) -> String {
+    let mut line = item.get_json().replace('\r', "\\r").replace('\n', "\\n");
+    line.push('\n');
+    line
+}
+
+/// [`sse_event`] will get the JSON representation of `item` as a single server-sent
+/// event, a `data:` line followed by the blank line that ends the event.
+/// # Example
+/// [`sse_event`] can be used to push one event to an `EventSource`:
+/// ```rust
+/// use http::export::sse_event;
+///
+/// fn create_event(event: &str) -> String {
+///     sse_event(&event)
+/// }
+/// ```
+pub fn sse_event<T: Respond + ?Sized>(item: &T) -> String {
+    format!("data: {}\n", ndjson_line(item))
+}
+
+/// [`csv_field`] will quote `field` if it holds a comma, quote, or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+/// [`TypeMap`] holds at most one value of every type, keyed by the type itself, so
+/// whatever runs before a handler, such as authentication, A/B bucketing, or
+/// tracing, can pass typed values to it without turning them into headers.
+#[derive(Default)]
+pub struct TypeMap {
+    /// [`TypeMap::values`] is every value in the [`TypeMap`], keyed by the
+    /// [`TypeId`] of its type.
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl TypeMap {
+    /// [`TypeMap::new`] creates an empty [`TypeMap`].
+    /// # Example
+    /// [`TypeMap::new`] can be used to hold the user a request was authenticated as:
+    /// ```rust
+    /// use http::extensions::TypeMap;
+    ///
+    /// struct AuthUser(String);
+    ///
+    /// fn create_extensions(user: &str) -> TypeMap {
+    ///     let mut extensions = TypeMap::new();
+    ///     extensions.insert(AuthUser(user.to_string()));
+    ///     extensions
+    /// }
+    /// ```
+    pub fn new() -> Self {
+        TypeMap::default()
+    }
+
+    /// [`TypeMap::insert`] will add `value` to the [`TypeMap`], and return the value
+    /// of the same type it replaces, if there was one.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|replaced| replaced.downcast().ok())
+            .map(|replaced| *replaced)
+    }
+
+    /// [`TypeMap::get`] will get the value of type `T`, defaults to [`None`] if the
+    /// [`TypeMap`] doesn't have one.
+    /// # Example
+    /// [`TypeMap::get`] can be used to read the bucket of an A/B test:
+    /// ```rust
+    /// use http::extensions::TypeMap;
+    ///
+    /// struct Bucket(u8);
+    ///
+    /// fn get_bucket(extensions: &TypeMap) -> u8 {
+    ///     extensions.get::<Bucket>().map_or(0, |bucket| bucket.0)
+    /// }
+    /// ```
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// [`TypeMap::get_mut`] will get a mutable reference to the value of type `T`,
+    /// defaults to [`None`] if the [`TypeMap`] doesn't have one.
+    pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        self.values
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// [`TypeMap::remove`] will take the value of type `T` out of the [`TypeMap`],
+    /// defaults to [`None`] if the [`TypeMap`] doesn't have one.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.values
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    /// [`TypeMap::contains`] will check if the [`TypeMap`] has a value of type `T`.
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// [`TypeMap::len`] will get how many values are in the [`TypeMap`].
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// [`TypeMap::is_empty`] will check if the [`TypeMap`] has no values at all.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Implement [`PartialEq`] for [`TypeMap`], where values of any type can't be
+/// compared, so two [`TypeMap`] values are equal when they hold the same types.
+impl PartialEq for TypeMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.values.len() == other.values.len()
+            && self.values.keys().all(|key| other.values.contains_key(key))
+    }
+}
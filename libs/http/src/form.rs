@@ -0,0 +1,56 @@
+use crate::{constants::CONTENT_FORM, percent};
+
+/// [`is_form`] will return `true` if `content_type` is
+/// `application/x-www-form-urlencoded`, with or without a `charset`.
+/// # Example
+/// [`is_form`] can be used to tell if a request body holds form fields:
+/// ```rust
+/// use http::{form, request::Request};
+///
+/// fn has_form_body(request: &Request) -> bool {
+///     form::is_form(request.header("content-type"))
+/// }
+/// ```
+pub fn is_form(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(CONTENT_FORM))
+}
+
+/// [`decode_component`] will decode a name or value of a form-urlencoded body,
+/// where a `+` stands for a space and every `%XX` escape for the byte it encodes,
+/// and return an [`Err`] if it doesn't decode to UTF-8.
+/// # Example
+/// [`decode_component`] can be used to read a single form value:
+/// ```rust
+/// use http::form;
+///
+/// fn get_search(value: &str) -> String {
+///     form::decode_component(value).unwrap_or_default()
+/// }
+/// ```
+pub fn decode_component(component: &str) -> Result<String, String> {
+    percent::decode(&component.replace('+', " "))
+}
+
+/// [`parse`] will parse a form-urlencoded `body`, such as `name=Evan+G&age=30`,
+/// into every `(name, value)` pair in order, skipping any pair that doesn't
+/// decode. A field without a `=` has an empty value.
+/// # Example
+/// [`parse`] can be used to read every field of a form:
+/// ```rust
+/// use http::form;
+///
+/// fn get_field_names(body: &str) -> Vec<String> {
+///     form::parse(body).into_iter().map(|(name, _)| name).collect()
+/// }
+/// ```
+pub fn parse(body: &str) -> Vec<(String, String)> {
+    body.split('&')
+        .filter(|field| !field.is_empty())
+        .filter_map(|field| {
+            let (name, value) = field.split_once('=').unwrap_or((field, ""));
+            Some((decode_component(name).ok()?, decode_component(value).ok()?))
+        })
+        .collect()
+}
@@ -0,0 +1,51 @@
+/// [`is_header_name`] will return `true` if `name` can be sent as the name of an
+/// HTTP header, which is a non-empty token of letters, digits, and
+/// ``!#$%&'*+-.^_`|~``, so it can't hold a line break.
+/// # Example
+/// [`is_header_name`] can be used to check a header name read from configuration:
+/// ```rust
+/// use http::header;
+///
+/// fn can_send(name: &str) -> bool {
+///     header::is_header_name(name)
+/// }
+/// ```
+pub fn is_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|character| {
+            character.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(character)
+        })
+}
+
+/// [`is_header_value`] will return `true` if `value` can be sent as the value of an
+/// HTTP header, which is any value without a CR, LF, or NUL, so it can't end the
+/// header early and start another header or response.
+pub fn is_header_value(value: &str) -> bool {
+    !value.contains(['\r', '\n', '\0'])
+}
+
+/// [`check_header`] will return an [`Err`] describing the problem if `name` or
+/// `value` can't be sent as an HTTP header, as decided by [`is_header_name`] and
+/// [`is_header_value`].
+/// # Example
+/// [`check_header`] can be used to refuse a header built from a request, such as
+/// an echoed request id:
+/// ```rust
+/// use http::header;
+///
+/// fn echo_request_id(request_id: &str) -> Result<(String, String), String> {
+///     header::check_header("X-Request-Id", request_id)?;
+///     Ok(("X-Request-Id".to_string(), request_id.to_string()))
+/// }
+/// ```
+pub fn check_header(name: &str, value: &str) -> Result<(), String> {
+    if !is_header_name(name) {
+        return Err(format!("{name:?} is not a valid header name"));
+    }
+    if !is_header_value(value) {
+        return Err(format!(
+            "the value of header {name} can't contain a CR, LF, or NUL"
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::methods::Method;
+use logger::log_debug;
+use std::time::{Duration, Instant};
+
+/// [`REDACTED`] is what the value of a redacted handler argument is logged as.
+pub const REDACTED: &str = "[redacted]";
+
+/// [`HandlerSpan`] logs when a handler is entered with its arguments, and when it
+/// exits with how long it took, at debug level. It is created by the `#[http_*]`
+/// attributes with the `instrument` option, and logs the exit when it is dropped.
+#[must_use = "the exit of a handler is logged when its HandlerSpan is dropped"]
+pub struct HandlerSpan {
+    /// [`HandlerSpan::name`] is the method and pattern of the route the handler
+    /// serves, such as `GET /user/{id}`.
+    name: String,
+    /// [`HandlerSpan::started`] is when the handler was entered.
+    started: Instant,
+}
+
+impl HandlerSpan {
+    /// [`HandlerSpan::enter`] will log that the handler for `method` and `pattern`
+    /// was entered with `arguments`, and return the [`HandlerSpan`] that logs its
+    /// exit.
+    /// # Example
+    /// [`HandlerSpan::enter`] can be used to instrument a handler by hand:
+    /// ```rust
+    /// use http::{instrument::HandlerSpan, methods::GET};
+    ///
+    /// fn get_user(id: u32) -> String {
+    ///     let _span = HandlerSpan::enter(GET, "/user/{id}", &format!("id={id:?}"));
+    ///     format!("user {id}")
+    /// }
+    /// ```
+    pub fn enter(method: Method, pattern: &str, arguments: &str) -> Self {
+        let name = format!("{method} {pattern}");
+        log_debug!("entering {name} handler with ({arguments})");
+        HandlerSpan {
+            name,
+            started: Instant::now(),
+        }
+    }
+
+    /// [`HandlerSpan::elapsed`] will get how long it has been since the handler was
+    /// entered.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// Implement [`Drop`] for [`HandlerSpan`], logging the exit of the handler.
+impl Drop for HandlerSpan {
+    fn drop(&mut self) {
+        log_debug!("exiting {} handler after {:?}", self.name, self.elapsed());
+    }
+}
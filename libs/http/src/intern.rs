@@ -0,0 +1,239 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{OnceLock, RwLock},
+};
+
+/// [`Symbol`] is the integer ID of an interned string, which compares and hashes
+/// as cheaply as the integer itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+/// [`Interner`] holds every interned string along with its [`Symbol`].
+#[derive(Default)]
+struct Interner {
+    /// [`Interner::symbols`] maps every interned string to its [`Symbol`].
+    symbols: HashMap<&'static str, Symbol>,
+    /// [`Interner::names`] holds every interned string, indexed by its [`Symbol`].
+    names: Vec<&'static str>,
+}
+
+impl Interner {
+    /// [`Interner::insert`] will intern `value` if it isn't already, and return its
+    /// [`Symbol`]. Every interned string is kept for the life of the server.
+    fn insert(&mut self, value: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(value) {
+            return *symbol;
+        }
+        let name: &'static str = Box::leak(value.to_string().into_boxed_str());
+        let symbol = Symbol(self.names.len() as u32);
+        self.names.push(name);
+        self.symbols.insert(name, symbol);
+        symbol
+    }
+}
+
+/// [`INTERNER`] is the [`Interner`] shared by every thread, which is seeded with
+/// every name in [`headers::KNOWN`] so their [`Symbol`] values are known up front.
+static INTERNER: OnceLock<RwLock<Interner>> = OnceLock::new();
+fn get_interner() -> &'static RwLock<Interner> {
+    INTERNER.get_or_init(|| {
+        let mut interner = Interner::default();
+        for name in headers::KNOWN {
+            interner.insert(name);
+        }
+        RwLock::new(interner)
+    })
+}
+
+/// [`KNOWN_HEADERS`] maps every lowercase name in [`headers`] to its [`Symbol`],
+/// and never changes after it is initialized, so it can be read without a lock.
+static KNOWN_HEADERS: OnceLock<HashMap<&'static str, Symbol>> = OnceLock::new();
+fn get_known_headers() -> &'static HashMap<&'static str, Symbol> {
+    KNOWN_HEADERS.get_or_init(|| {
+        headers::KNOWN
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (*name, Symbol(index as u32)))
+            .collect()
+    })
+}
+
+/// [`MAX_STACK_LOWERCASE`] is the longest header name that is lowercased on the
+/// stack when it is looked up, instead of allocating a [`String`].
+const MAX_STACK_LOWERCASE: usize = 64;
+
+/// [`known_headers`] defines a [`HeaderName`] constant for every header name, along
+/// with the [`headers::KNOWN`] list of every name in the order of their [`Symbol`].
+macro_rules! known_headers {
+    ($($constant:ident => $name:literal,)*) => {
+        /// [`headers`] is a collection of [`HeaderName`](super::HeaderName) constants
+        /// for common header names, which are always interned.
+        pub mod headers {
+            use super::{HeaderName, Symbol};
+
+            /// [`KNOWN`] is every lowercase header name with a constant, in the order
+            /// of their [`Symbol`] values.
+            pub const KNOWN: &[&str] = &[$($name,)*];
+
+            known_headers!(@symbols 0u32, $($constant => $name,)*);
+        }
+    };
+    (@symbols $index:expr, $constant:ident => $name:literal, $($rest:tt)*) => {
+        #[doc = concat!("[`", stringify!($constant), "`] is the [`HeaderName`] of the `", $name, "` header.")]
+        pub const $constant: HeaderName = HeaderName::Known(Symbol($index));
+        known_headers!(@symbols $index + 1u32, $($rest)*);
+    };
+    (@symbols $index:expr,) => {};
+}
+
+known_headers! {
+    ACCEPT => "accept",
+    ACCEPT_ENCODING => "accept-encoding",
+    ACCEPT_LANGUAGE => "accept-language",
+    AUTHORIZATION => "authorization",
+    CACHE_CONTROL => "cache-control",
+    CONNECTION => "connection",
+    CONTENT_ENCODING => "content-encoding",
+    CONTENT_LENGTH => "content-length",
+    CONTENT_TYPE => "content-type",
+    COOKIE => "cookie",
+    EXPECT => "expect",
+    FORWARDED => "forwarded",
+    HOST => "host",
+    IF_MATCH => "if-match",
+    IF_MODIFIED_SINCE => "if-modified-since",
+    IF_NONE_MATCH => "if-none-match",
+    ORIGIN => "origin",
+    PRAGMA => "pragma",
+    RANGE => "range",
+    REFERER => "referer",
+    TE => "te",
+    TRAILER => "trailer",
+    TRANSFER_ENCODING => "transfer-encoding",
+    UPGRADE => "upgrade",
+    USER_AGENT => "user-agent",
+    X_FORWARDED_FOR => "x-forwarded-for",
+    X_REQUEST_ID => "x-request-id",
+}
+
+impl Symbol {
+    /// [`Symbol::as_str`] will return the string a [`Symbol`] was interned from.
+    pub fn as_str(&self) -> &'static str {
+        get_interner()
+            .read()
+            .expect("interner lock should never be poisoned")
+            .names[self.0 as usize]
+    }
+}
+
+/// Implement [`fmt::Display`] for [`Symbol`].
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// [`intern`] will intern `value` if it isn't already, and return its [`Symbol`].
+/// Interned strings are never freed, so only bounded sets of strings, such as
+/// route patterns, should be interned.
+/// # Example
+/// [`intern`] can be used to compare a path segment by ID:
+/// ```rust
+/// use http::intern;
+///
+/// fn is_users_segment(segment: &str) -> bool {
+///     intern::lookup(segment) == Some(intern::intern("users"))
+/// }
+/// ```
+pub fn intern(value: &str) -> Symbol {
+    if let Some(symbol) = lookup(value) {
+        return symbol;
+    }
+    get_interner()
+        .write()
+        .expect("interner lock should never be poisoned")
+        .insert(value)
+}
+
+/// [`lookup`] will return the [`Symbol`] of `value` without interning it, defaults
+/// to [`None`] if `value` was never interned.
+pub fn lookup(value: &str) -> Option<Symbol> {
+    get_interner()
+        .read()
+        .expect("interner lock should never be poisoned")
+        .symbols
+        .get(value)
+        .copied()
+}
+
+/// [`HeaderName`] represents the case-insensitive name of an HTTP header, which is
+/// a [`Symbol`] for every name in [`headers`] and a lowercase [`String`] otherwise.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HeaderName {
+    /// [`HeaderName::Known`] is a header name from [`headers`].
+    Known(Symbol),
+    /// [`HeaderName::Custom`] is any other header name, in lowercase.
+    Custom(String),
+}
+
+impl HeaderName {
+    /// [`HeaderName::new`] will create a [`HeaderName`] from `name` in any case,
+    /// which only allocates for header names that aren't in [`headers`].
+    /// # Example
+    /// [`HeaderName::new`] can be used to find a header in a map of headers:
+    /// ```rust
+    /// use http::intern::HeaderName;
+    /// use std::collections::HashMap;
+    ///
+    /// fn get_host(headers: &HashMap<HeaderName, String>) -> Option<&String> {
+    ///     headers.get(&HeaderName::new("Host"))
+    /// }
+    /// ```
+    pub fn new(name: &str) -> Self {
+        let known_headers = get_known_headers();
+        if let Some(symbol) = known_headers.get(name) {
+            return HeaderName::Known(*symbol);
+        }
+
+        if name.len() <= MAX_STACK_LOWERCASE {
+            let mut buffer = [0; MAX_STACK_LOWERCASE];
+            let lowercase = &mut buffer[..name.len()];
+            lowercase.copy_from_slice(name.as_bytes());
+            lowercase.make_ascii_lowercase();
+            // lowercasing ASCII bytes keeps the name valid UTF-8
+            let lowercase = std::str::from_utf8(lowercase).unwrap_or_default();
+            match known_headers.get(lowercase) {
+                Some(symbol) => HeaderName::Known(*symbol),
+                None => HeaderName::Custom(lowercase.to_string()),
+            }
+        } else {
+            HeaderName::Custom(name.to_ascii_lowercase())
+        }
+    }
+
+    /// [`HeaderName::as_str`] will return the lowercase name of a [`HeaderName`].
+    pub fn as_str(&self) -> &str {
+        match self {
+            HeaderName::Known(symbol) => headers::KNOWN
+                .get(symbol.0 as usize)
+                .copied()
+                .unwrap_or_else(|| symbol.as_str()),
+            HeaderName::Custom(name) => name,
+        }
+    }
+}
+
+/// Implement [`From`] for [`HeaderName`], so a header name can be written as a `&str`.
+impl From<&str> for HeaderName {
+    fn from(name: &str) -> Self {
+        HeaderName::new(name)
+    }
+}
+
+/// Implement [`fmt::Display`] for [`HeaderName`].
+impl fmt::Display for HeaderName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
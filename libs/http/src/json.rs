@@ -0,0 +1,433 @@
+use crate::respond::Respond;
+
+/// [`MAX_JSON_DEPTH`] is how deeply arrays and objects can be nested in a document
+/// [`Json::parse`] accepts, so a body of nothing but `[` can't overflow the stack of
+/// the worker parsing it.
+pub const MAX_JSON_DEPTH: usize = 128;
+
+/// [`Json`] represents a parsed JSON value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    /// [`Json::Null`] represents a JSON `null`.
+    Null,
+    /// [`Json::Bool`] represents a JSON `true` or `false`.
+    Bool(bool),
+    /// [`Json::Number`] represents any JSON number.
+    Number(f64),
+    /// [`Json::String`] represents a JSON string, with all escapes resolved.
+    String(String),
+    /// [`Json::Array`] represents a JSON array.
+    Array(Vec<Json>),
+    /// [`Json::Object`] represents a JSON object, with keys kept in the order
+    /// they were parsed or inserted.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// [`Json::parse`] will parse the provided `input` into a [`Json`] value, and
+    /// return an [`Err`] describing the problem if `input` is not valid JSON, or
+    /// nests arrays and objects deeper than [`MAX_JSON_DEPTH`].
+    /// # Example
+    /// [`Json::parse`] can be used to read a JSON request body:
+    /// ```rust
+    /// use http::json::Json;
+    ///
+    /// fn parse_body(body: &str) -> Option<Json> {
+    ///     Json::parse(body).ok()
+    /// }
+    /// ```
+    pub fn parse(input: &str) -> Result<Json, String> {
+        let mut parser = JsonParser {
+            bytes: input.as_bytes(),
+            position: 0,
+            depth: 0,
+        };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.position < parser.bytes.len() {
+            return Err(format!(
+                "unexpected trailing characters at position {}",
+                parser.position
+            ));
+        }
+        Ok(value)
+    }
+
+    /// [`Json::get`] will get the value of an object member by `key`, defaults
+    /// to [`None`] if the key is missing or the value is not a [`Json::Object`].
+    /// # Example
+    /// [`Json::get`] can be used to look up a member of a JSON object:
+    /// ```rust
+    /// use http::json::Json;
+    ///
+    /// fn get_name(value: &Json) -> Option<&str> {
+    ///     value.get("name").and_then(|name| name.as_str())
+    /// }
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members
+                .iter()
+                .find(|(member_key, _)| member_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// [`Json::as_str`] will return the [`str`] of a [`Json::String`], and [`None`]
+    /// for any other value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// [`Json::as_f64`] will return the number of a [`Json::Number`], and [`None`]
+    /// for any other value.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// [`Json::as_bool`] will return the value of a [`Json::Bool`], and [`None`]
+    /// for any other value.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// [`Json::as_array`] will return the items of a [`Json::Array`], and [`None`]
+    /// for any other value.
+    pub fn as_array(&self) -> Option<&Vec<Json>> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// [`Json::is_null`] will return `true` if the value is [`Json::Null`], and
+    /// `false` otherwise.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Json::Null)
+    }
+}
+
+/// Implement [`std::fmt::Display`] for [`Json`], which writes compact JSON.
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Number(value) => write_number(f, *value),
+            Json::String(value) => write_string(f, value),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(members) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// Implement [`Respond`] for [`Json`]
+impl Respond for Json {
+    fn get_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// [`write_number`] writes a JSON number, without a fractional part when the
+/// number is whole, and as `null` when the number can't be represented in JSON.
+fn write_number(f: &mut std::fmt::Formatter<'_>, value: f64) -> std::fmt::Result {
+    if !value.is_finite() {
+        write!(f, "null")
+    } else if value.fract() == 0.0 && value.abs() < 1e15 {
+        write!(f, "{}", value as i64)
+    } else {
+        write!(f, "{value}")
+    }
+}
+
+/// [`write_string`] writes a quoted JSON string, escaping any characters that
+/// are not allowed to appear literally.
+fn write_string(f: &mut std::fmt::Formatter<'_>, value: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for character in value.chars() {
+        match character {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// [`JsonParser`] is a recursive descent parser over the bytes of a JSON document.
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    /// [`JsonParser::depth`] is how many arrays and objects the current position is
+    /// nested in.
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    /// [`JsonParser::parse_value`] parses any JSON value at the current position.
+    fn parse_value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'n') => self.parse_literal("null", Json::Null),
+            Some(b't') => self.parse_literal("true", Json::Bool(true)),
+            Some(b'f') => self.parse_literal("false", Json::Bool(false)),
+            Some(b'"') => self.parse_string().map(Json::String),
+            Some(b'[' | b'{') if self.depth == MAX_JSON_DEPTH => Err(format!(
+                "nested deeper than {MAX_JSON_DEPTH} at position {}",
+                self.position
+            )),
+            Some(b'[') => self.nested(Self::parse_array),
+            Some(b'{') => self.nested(Self::parse_object),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(format!(
+                "unexpected character at position {}",
+                self.position
+            )),
+            None => Err("unexpected end of input".to_string()),
+        }
+    }
+
+    /// [`JsonParser::nested`] runs `parse` one level deeper into arrays and objects.
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    /// [`JsonParser::parse_literal`] parses one of `null`, `true`, or `false`.
+    fn parse_literal(&mut self, literal: &str, value: Json) -> Result<Json, String> {
+        if self.bytes[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(format!("invalid literal at position {}", self.position))
+        }
+    }
+
+    /// [`JsonParser::parse_number`] parses a JSON number.
+    fn parse_number(&mut self) -> Result<Json, String> {
+        let start = self.position;
+        let invalid_number = || format!("invalid number at position {start}");
+        // a number is an optional `-`, an integer without leading zeros, and an
+        // optional fraction and exponent, each with at least one digit
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.position += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(invalid_number()),
+        }
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(invalid_number());
+            }
+            self.skip_digits();
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.position += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.position += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(invalid_number());
+            }
+            self.skip_digits();
+        }
+        std::str::from_utf8(&self.bytes[start..self.position])
+            .ok()
+            .and_then(|number| number.parse::<f64>().ok())
+            .map(Json::Number)
+            .ok_or_else(invalid_number)
+    }
+
+    /// [`JsonParser::skip_digits`] moves past any ASCII digits.
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    /// [`JsonParser::parse_string`] parses a quoted JSON string, resolving escapes.
+    fn parse_string(&mut self) -> Result<String, String> {
+        // skip the opening quote
+        self.position += 1;
+        let mut value = Vec::new();
+        loop {
+            match self.next() {
+                Some(b'"') => break,
+                Some(b'\\') => match self.next() {
+                    Some(b'"') => value.push(b'"'),
+                    Some(b'\\') => value.push(b'\\'),
+                    Some(b'/') => value.push(b'/'),
+                    Some(b'b') => value.push(0x08),
+                    Some(b'f') => value.push(0x0c),
+                    Some(b'n') => value.push(b'\n'),
+                    Some(b'r') => value.push(b'\r'),
+                    Some(b't') => value.push(b'\t'),
+                    Some(b'u') => {
+                        let character = self.parse_unicode_escape()?;
+                        let mut buffer = [0; 4];
+                        value.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                    }
+                    _ => return Err(format!("invalid escape at position {}", self.position)),
+                },
+                Some(byte) if byte < 0x20 => {
+                    return Err(format!(
+                        "unescaped control character at position {}",
+                        self.position
+                    ));
+                }
+                Some(byte) => value.push(byte),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+        String::from_utf8(value).map_err(|_| "invalid UTF-8 in string".to_string())
+    }
+
+    /// [`JsonParser::parse_unicode_escape`] parses the hex digits of a `\u` escape,
+    /// including a following low surrogate when the first escape is a high surrogate.
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.parse_hex_digits()?;
+        let code_point = if (0xD800..0xDC00).contains(&high) {
+            if self.next() != Some(b'\\') || self.next() != Some(b'u') {
+                return Err(format!("unpaired surrogate at position {}", self.position));
+            }
+            let low = self.parse_hex_digits()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(format!("invalid surrogate at position {}", self.position));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code_point)
+            .ok_or_else(|| format!("invalid unicode escape at position {}", self.position))
+    }
+
+    /// [`JsonParser::parse_hex_digits`] parses the four hex digits of a `\u` escape.
+    fn parse_hex_digits(&mut self) -> Result<u32, String> {
+        let digits = self
+            .bytes
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| format!("invalid unicode escape at position {}", self.position))?;
+        self.position += 4;
+        Ok(digits)
+    }
+
+    /// [`JsonParser::parse_array`] parses a JSON array.
+    fn parse_array(&mut self) -> Result<Json, String> {
+        // skip the opening bracket
+        self.position += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b']') => return Ok(Json::Array(items)),
+                _ => return Err(format!("expected ',' or ']' at position {}", self.position)),
+            }
+        }
+    }
+
+    /// [`JsonParser::parse_object`] parses a JSON object.
+    fn parse_object(&mut self) -> Result<Json, String> {
+        // skip the opening brace
+        self.position += 1;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(format!("expected object key at position {}", self.position));
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            if self.next() != Some(b':') {
+                return Err(format!("expected ':' at position {}", self.position));
+            }
+            members.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b'}') => return Ok(Json::Object(members)),
+                _ => {
+                    return Err(format!(
+                        "expected ',' or '}}' at position {}",
+                        self.position
+                    ));
+                }
+            }
+        }
+    }
+
+    /// [`JsonParser::skip_whitespace`] moves past any insignificant whitespace.
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    /// [`JsonParser::peek`] returns the byte at the current position without
+    /// consuming it.
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    /// [`JsonParser::next`] returns the byte at the current position and moves
+    /// past it.
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek();
+        if byte.is_some() {
+            self.position += 1;
+        }
+        byte
+    }
+}
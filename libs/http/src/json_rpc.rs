@@ -0,0 +1,221 @@
+use crate::json::Json;
+use std::collections::HashMap;
+
+/// [`JSON_RPC_VERSION`] is a `const` [`str`] that represents the only supported
+/// JSON-RPC protocol version.
+pub const JSON_RPC_VERSION: &str = "2.0";
+
+/// [`JsonRpcMethod`] is a dynamic handler function for a named JSON-RPC method,
+/// which takes the `params` of a call and gives back a result or a [`JsonRpcError`].
+pub type JsonRpcMethod =
+    Box<dyn Fn(Option<Json>) -> Result<Json, JsonRpcError> + Send + Sync + 'static>;
+
+/// [`JsonRpcError`] represents a JSON-RPC error object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonRpcError {
+    /// [`JsonRpcError::code`] is the numeric error code, such as `-32601`.
+    pub code: i64,
+    /// [`JsonRpcError::message`] is a short description of the error.
+    pub message: String,
+    /// [`JsonRpcError::data`] is optional additional information about the error.
+    pub data: Option<Json>,
+}
+
+impl JsonRpcError {
+    /// [`JsonRpcError::new`] creates a [`JsonRpcError`] with the provided `code`
+    /// and `message`, and no [`JsonRpcError::data`].
+    /// # Example
+    /// [`JsonRpcError::new`] can be used to return an application-defined error
+    /// from a [`JsonRpcMethod`]:
+    /// ```rust
+    /// use http::{json::Json, json_rpc::JsonRpcError};
+    ///
+    /// fn divide(params: Option<Json>) -> Result<Json, JsonRpcError> {
+    ///     Err(JsonRpcError::new(1, "division by zero"))
+    /// }
+    /// ```
+    pub fn new(code: i64, message: &str) -> Self {
+        JsonRpcError {
+            code,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+
+    /// [`JsonRpcError::parse_error`] represents a `-32700` error, for invalid JSON.
+    pub fn parse_error() -> Self {
+        JsonRpcError::new(-32700, "Parse error")
+    }
+
+    /// [`JsonRpcError::invalid_request`] represents a `-32600` error, for JSON that
+    /// is not a valid request object.
+    pub fn invalid_request() -> Self {
+        JsonRpcError::new(-32600, "Invalid Request")
+    }
+
+    /// [`JsonRpcError::method_not_found`] represents a `-32601` error, for a method
+    /// that has not been registered.
+    pub fn method_not_found() -> Self {
+        JsonRpcError::new(-32601, "Method not found")
+    }
+
+    /// [`JsonRpcError::invalid_params`] represents a `-32602` error, for method
+    /// parameters that are missing or of the wrong shape.
+    pub fn invalid_params() -> Self {
+        JsonRpcError::new(-32602, "Invalid params")
+    }
+
+    /// [`JsonRpcError::internal_error`] represents a `-32603` error, for failures
+    /// inside of a method.
+    pub fn internal_error() -> Self {
+        JsonRpcError::new(-32603, "Internal error")
+    }
+
+    /// [`JsonRpcError::to_json`] returns the JSON-RPC error object for a
+    /// [`JsonRpcError`].
+    pub fn to_json(&self) -> Json {
+        let mut members = vec![
+            ("code".to_string(), Json::Number(self.code as f64)),
+            ("message".to_string(), Json::String(self.message.clone())),
+        ];
+        if let Some(data) = &self.data {
+            members.push(("data".to_string(), data.clone()));
+        }
+        Json::Object(members)
+    }
+}
+
+/// [`JsonRpcRegistry`] is a collection of named [`JsonRpcMethod`] that a
+/// JSON-RPC [`Route`](crate::route::Route) can dispatch calls to.
+#[derive(Default)]
+pub struct JsonRpcRegistry {
+    /// [`JsonRpcRegistry::methods`] maps method names to their [`JsonRpcMethod`].
+    methods: HashMap<String, JsonRpcMethod>,
+}
+
+impl JsonRpcRegistry {
+    /// [`JsonRpcRegistry::new`] creates an empty [`JsonRpcRegistry`].
+    pub fn new() -> Self {
+        JsonRpcRegistry {
+            methods: HashMap::new(),
+        }
+    }
+
+    /// [`JsonRpcRegistry::register`] adds a [`JsonRpcMethod`] to the registry
+    /// under the provided `name`, replacing any method with the same name.
+    /// # Example
+    /// [`JsonRpcRegistry::register`] can be used to add an `echo` method:
+    /// ```rust
+    /// use http::json_rpc::{JsonRpcError, JsonRpcRegistry};
+    /// use http::json::Json;
+    ///
+    /// fn create_registry() -> JsonRpcRegistry {
+    ///     let mut registry = JsonRpcRegistry::new();
+    ///     registry.register(
+    ///         "echo",
+    ///         Box::new(|params: Option<Json>| Ok(params.unwrap_or(Json::Null))),
+    ///     );
+    ///     registry
+    /// }
+    /// ```
+    pub fn register(&mut self, name: &str, method: JsonRpcMethod) {
+        self.methods.insert(name.to_string(), method);
+    }
+
+    /// [`JsonRpcRegistry::handle`] will parse a JSON-RPC request `body`, which can
+    /// be a single call or a batch, and dispatch every call to its registered
+    /// method. Returns [`None`] when there is nothing to respond with, which is the
+    /// case when the body only contains notifications.
+    /// # Example
+    /// [`JsonRpcRegistry::handle`] can be used to answer a JSON-RPC request body:
+    /// ```rust
+    /// use http::json_rpc::JsonRpcRegistry;
+    ///
+    /// fn answer(registry: &JsonRpcRegistry, body: &str) -> String {
+    ///     registry.handle(body).unwrap_or_default()
+    /// }
+    /// ```
+    pub fn handle(&self, body: &str) -> Option<String> {
+        let request = match Json::parse(body) {
+            Ok(request) => request,
+            Err(_) => {
+                return Some(error_response(Json::Null, JsonRpcError::parse_error()).to_string());
+            }
+        };
+
+        match request {
+            Json::Array(calls) if calls.is_empty() => {
+                Some(error_response(Json::Null, JsonRpcError::invalid_request()).to_string())
+            }
+            Json::Array(calls) => {
+                let responses: Vec<Json> = calls
+                    .iter()
+                    .filter_map(|call| self.handle_call(call))
+                    .collect();
+                if responses.is_empty() {
+                    None
+                } else {
+                    Some(Json::Array(responses).to_string())
+                }
+            }
+            call => self.handle_call(&call).map(|response| response.to_string()),
+        }
+    }
+
+    /// [`JsonRpcRegistry::handle_call`] validates and dispatches a single call,
+    /// returning [`None`] for notifications.
+    fn handle_call(&self, call: &Json) -> Option<Json> {
+        let id = call.get("id");
+        let response_id = match id {
+            Some(id @ (Json::Null | Json::Number(_) | Json::String(_))) => id.clone(),
+            _ => Json::Null,
+        };
+
+        let has_valid_id = !matches!(id, Some(Json::Bool(_) | Json::Array(_) | Json::Object(_)));
+        let method = call.get("method").and_then(Json::as_str);
+        let params = call.get("params");
+        let is_valid_call = matches!(call, Json::Object(_))
+            && call.get("jsonrpc").and_then(Json::as_str) == Some(JSON_RPC_VERSION)
+            && method.is_some()
+            && has_valid_id;
+        if !is_valid_call {
+            return Some(error_response(response_id, JsonRpcError::invalid_request()));
+        }
+
+        let result = match params {
+            Some(Json::Array(_) | Json::Object(_)) | None => self
+                .methods
+                .get(method.unwrap())
+                .ok_or_else(JsonRpcError::method_not_found)
+                .and_then(|handler| handler(params.cloned())),
+            Some(_) => Err(JsonRpcError::invalid_params()),
+        };
+
+        // notifications never get a response, even when they fail
+        id?;
+
+        Some(match result {
+            Ok(value) => Json::Object(vec![
+                (
+                    "jsonrpc".to_string(),
+                    Json::String(JSON_RPC_VERSION.to_string()),
+                ),
+                ("result".to_string(), value),
+                ("id".to_string(), response_id),
+            ]),
+            Err(error) => error_response(response_id, error),
+        })
+    }
+}
+
+/// [`error_response`] builds a JSON-RPC response object for an `error`.
+fn error_response(id: Json, error: JsonRpcError) -> Json {
+    Json::Object(vec![
+        (
+            "jsonrpc".to_string(),
+            Json::String(JSON_RPC_VERSION.to_string()),
+        ),
+        ("error".to_string(), error.to_json()),
+        ("id".to_string(), id),
+    ])
+}
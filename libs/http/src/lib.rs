@@ -3,12 +3,127 @@
 //! [`Response`](response::Response), and [`Route`](route::Route) to facilitate HTTP
 //! communication between a client and a server.
 
+/// [`arena`] holds a bump allocator that workers reuse as scratch space for
+/// every request they parse.
+pub mod arena;
+
+/// [`audit`] holds the opt-in audit trail of who made every mutating request, what
+/// it was, and when, which is kept apart from the application logs.
+pub mod audit;
+
+/// [`batch`] runs an array of sub-requests sent as one request, to save chatty
+/// clients round trips.
+pub mod batch;
+
+/// [`body`] holds all functionality for reading a request body straight from the
+/// connection.
+pub mod body;
+
+/// [`bulkhead`] holds a bulkhead that limits how many calls of an expensive route
+/// run at the same time.
+pub mod bulkhead;
+
+/// [`charset`] holds all functionality for decoding a request body by the charset
+/// named in its `Content-Type` header.
+pub mod charset;
+
+/// [`chunked`] holds all functionality for streaming a response body in chunks.
+pub mod chunked;
+
+/// [`circuit_breaker`] holds a circuit breaker that makes calls to a failing
+/// upstream fail fast.
+pub mod circuit_breaker;
+
+/// [`client`] holds a minimal HTTP/1.1 client for calling upstream servers.
+pub mod client;
+
+/// [`compression`] holds all functionality for negotiating and applying a
+/// content coding to response bodies.
+pub mod compression;
+
 /// [`constants`] is a collection of constant values that represent common HTTP header values.
 pub mod constants;
 
+/// [`context`] holds the per-request context a handler can take to reach its
+/// request, deadline, shared state, and scoped logger.
+pub mod context;
+
+/// [`correlation`] holds all functionality for propagating the request id and
+/// trace headers of a request to the upstream calls made while serving it.
+pub mod correlation;
+
+/// [`error`] holds all functionality for turning what handlers return, including
+/// their errors, into responses.
+pub mod error;
+
+/// [`executor`] holds a minimal executor that drives `async` handlers on worker
+/// threads.
+pub mod executor;
+
+/// [`export`] holds all functionality for serializing rows as CSV and NDJSON for
+/// export-style endpoints.
+pub mod export;
+
+/// [`extensions`] holds a map of typed values that are passed along with a
+/// request to its handler.
+pub mod extensions;
+
+/// [`form`] holds all functionality for reading the fields of a
+/// form-urlencoded request body.
+pub mod form;
+
+/// [`header`] holds the checks every response and upstream request header goes
+/// through, so a value with a line break can't inject another header or response.
+pub mod header;
+
+/// [`instrument`] holds all functionality for logging when handlers are entered and
+/// exited.
+pub mod instrument;
+
+/// [`intern`] holds a shared table of interned strings, used for header names
+/// and route pattern segments.
+pub mod intern;
+
+/// [`json`] contains a minimal JSON value type, along with a parser and serializer for it.
+pub mod json;
+
+/// [`json_rpc`] holds all functionality for serving JSON-RPC 2.0 requests.
+pub mod json_rpc;
+
+/// [`load_balancer`] holds all functionality for spreading requests across several
+/// upstream servers, and ejecting the ones that keep failing.
+pub mod load_balancer;
+
 /// [`methods`] is a collection of constant values that represent HTTP methods.
 pub mod methods;
 
+/// [`multipart`] parses `multipart/form-data` bodies into the files and fields
+/// they upload.
+pub mod multipart;
+
+/// [`params`] holds traits for binding request parameters into structs, and types
+/// that path parameters can be parsed into.
+pub mod params;
+
+/// [`parser`] holds all functionality for reading the request line and headers of
+/// an HTTP request.
+pub mod parser;
+
+/// [`percent`] holds all functionality for decoding percent-encoded text, such as
+/// path segments.
+pub mod percent;
+
+/// [`postman`] holds all functionality for exporting routes as a Postman
+/// collection.
+pub mod postman;
+
+/// [`proxy`] holds all functionality for forwarding requests to an upstream server.
+pub mod proxy;
+
+/// [`registry`] holds the registry every `#[http_*]` attribute adds its route to,
+/// so routes can be collected without listing them by hand.
+pub mod registry;
+
 /// [`request`] holds all functionality related to HTTP requests.
 pub mod request;
 
@@ -18,8 +133,50 @@ pub mod respond;
 /// [`response`] holds all functionality related to HTTP responses.
 pub mod response;
 
+/// [`retry`] holds the retry policy and retry budget used by the HTTP client.
+pub mod retry;
+
 /// [`route`] holds all functionality that will serve a response based on a request.
 pub mod route;
 
+/// [`router`] holds all functionality for resolving a request to the [`Route`](route::Route)
+/// that serves it.
+pub mod router;
+
+/// [`sdk`] holds all functionality for generating a typed Rust client for the
+/// routes of a server.
+pub mod sdk;
+
+/// [`session`] holds the sessions a server keeps for its clients between requests,
+/// and the stores they are kept in.
+pub mod session;
+
+/// [`sha256`] holds a dependency-free SHA-256 and HMAC-SHA256, for signing requests
+/// and fingerprinting audited bodies.
+pub mod sha256;
+
+/// [`signing`] holds all functionality for signing outbound requests, so upstreams
+/// can authenticate them.
+pub mod signing;
+
+/// [`static_files`] holds all functionality for serving fingerprinted static assets
+/// from a directory.
+pub mod static_files;
+
 /// [`status`] is a collection of HTTP statuses.
 pub mod status;
+
+/// [`tenant`] holds all functionality for resolving the tenant a request was made
+/// for, and keeping the state of every tenant apart.
+pub mod tenant;
+
+/// [`testing`] holds dependency-free helpers for testing, such as generators for
+/// fuzzing a route table, and golden-file snapshots of responses.
+pub mod testing;
+
+/// [`validation`] holds all functionality for checking a request against the
+/// parameters a route expects.
+pub mod validation;
+
+/// [`xml`] holds all functionality for answering clients that prefer XML to JSON.
+pub mod xml;
@@ -0,0 +1,293 @@
+use crate::{
+    client::{Client, ClientRequest},
+    methods::GET,
+    request::Request,
+};
+use logger::{log_info, log_warning};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// [`DEFAULT_MAX_FAILURES`] is how many failures in a row eject a [`Backend`],
+/// unless [`LoadBalancer::with_ejection`] is used.
+pub const DEFAULT_MAX_FAILURES: u32 = 3;
+
+/// [`DEFAULT_EJECTION_DURATION`] is how long an ejected [`Backend`] gets no
+/// requests, unless [`LoadBalancer::with_ejection`] is used.
+pub const DEFAULT_EJECTION_DURATION: Duration = Duration::from_secs(30);
+
+/// [`BalanceStrategy`] decides which [`Backend`] of a [`LoadBalancer`] gets a
+/// request.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BalanceStrategy {
+    /// [`BalanceStrategy::RoundRobin`] sends requests to each available backend in
+    /// turn.
+    RoundRobin,
+    /// [`BalanceStrategy::LeastConnections`] sends requests to the available
+    /// backend with the fewest requests in flight.
+    LeastConnections,
+    /// [`BalanceStrategy::HashByHeader`] sends every request with the same value of
+    /// the named header to the same backend, for as long as it is available.
+    /// Requests without the header are sent round-robin.
+    HashByHeader(String),
+}
+
+/// [`Backend`] is a single upstream of a [`LoadBalancer`], along with its health.
+#[derive(Debug)]
+pub struct Backend {
+    /// [`Backend::url`] is the `http://` URL requests are forwarded to.
+    pub url: String,
+    /// [`Backend::active`] is how many requests are in flight to the backend.
+    active: AtomicUsize,
+    /// [`Backend::failures`] is how many requests in a row failed.
+    failures: AtomicU32,
+    /// [`Backend::ejected_until`] is when an ejected backend gets requests again.
+    ejected_until: Mutex<Option<Instant>>,
+}
+
+/// [`BackendGuard`] is a [`Backend`] picked by a [`LoadBalancer`], which counts as
+/// a request in flight to it until the guard is dropped.
+#[derive(Debug)]
+pub struct BackendGuard<'a> {
+    /// [`BackendGuard::backend`] is the picked [`Backend`].
+    pub backend: &'a Backend,
+}
+
+/// [`LoadBalancer`] spreads requests across several upstreams with a
+/// [`BalanceStrategy`], ejecting backends that keep failing until they recover.
+#[derive(Debug)]
+pub struct LoadBalancer {
+    /// [`LoadBalancer::backends`] are the upstreams requests are spread across.
+    pub backends: Vec<Backend>,
+    /// [`LoadBalancer::strategy`] decides which backend gets each request.
+    pub strategy: BalanceStrategy,
+    /// [`LoadBalancer::max_failures`] is how many failures in a row eject a
+    /// backend.
+    pub max_failures: u32,
+    /// [`LoadBalancer::ejection_duration`] is how long an ejected backend gets no
+    /// requests.
+    pub ejection_duration: Duration,
+    /// [`LoadBalancer::health_check`] is the path and interval backends are
+    /// health checked with, if any.
+    pub health_check: Option<(String, Duration)>,
+    /// [`LoadBalancer::next`] is the round-robin position.
+    next: AtomicUsize,
+}
+
+impl Backend {
+    /// [`Backend::new`] creates a healthy [`Backend`] for `url`.
+    pub fn new(url: &str) -> Self {
+        Backend {
+            url: url.trim_end_matches('/').to_string(),
+            active: AtomicUsize::new(0),
+            failures: AtomicU32::new(0),
+            ejected_until: Mutex::new(None),
+        }
+    }
+
+    /// [`Backend::active_requests`] will get how many requests are in flight to the
+    /// [`Backend`].
+    pub fn active_requests(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// [`Backend::is_available`] will return `true` if the [`Backend`] isn't
+    /// ejected, and `false` otherwise.
+    pub fn is_available(&self) -> bool {
+        self.lock_ejected_until()
+            .is_none_or(|ejected_until| Instant::now() >= ejected_until)
+    }
+
+    /// [`Backend::lock_ejected_until`] will lock [`Backend::ejected_until`],
+    /// recovering it if a caller panicked while holding the lock.
+    fn lock_ejected_until(&self) -> std::sync::MutexGuard<'_, Option<Instant>> {
+        self.ejected_until
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Implement [`Drop`] for [`BackendGuard`], so the request stops counting as in
+/// flight.
+impl Drop for BackendGuard<'_> {
+    fn drop(&mut self) {
+        self.backend.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl LoadBalancer {
+    /// [`LoadBalancer::new`] creates a [`LoadBalancer`] that spreads requests across
+    /// `upstreams` with `strategy`, ejecting backends after
+    /// [`DEFAULT_MAX_FAILURES`] failures in a row for [`DEFAULT_EJECTION_DURATION`].
+    /// # Example
+    /// [`LoadBalancer::new`] can be used to keep each user on the same upstream:
+    /// ```rust
+    /// use http::load_balancer::{BalanceStrategy, LoadBalancer};
+    /// use std::time::Duration;
+    ///
+    /// fn create_sticky_balancer() -> LoadBalancer {
+    ///     LoadBalancer::new(
+    ///         &["http://10.0.0.1:9000", "http://10.0.0.2:9000"],
+    ///         BalanceStrategy::HashByHeader("X-User-Id".to_string()),
+    ///     )
+    ///     .with_health_check("/health", Duration::from_secs(5))
+    /// }
+    /// ```
+    pub fn new(upstreams: &[&str], strategy: BalanceStrategy) -> Self {
+        LoadBalancer {
+            backends: upstreams.iter().map(|url| Backend::new(url)).collect(),
+            strategy,
+            max_failures: DEFAULT_MAX_FAILURES,
+            ejection_duration: DEFAULT_EJECTION_DURATION,
+            health_check: None,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// [`LoadBalancer::with_ejection`] will eject backends after `max_failures`
+    /// failures in a row for `ejection_duration`, and return the [`LoadBalancer`].
+    pub fn with_ejection(mut self, max_failures: u32, ejection_duration: Duration) -> Self {
+        self.max_failures = max_failures.max(1);
+        self.ejection_duration = ejection_duration;
+        self
+    }
+
+    /// [`LoadBalancer::with_health_check`] will send a `GET` request for `path` to
+    /// every backend each `interval` once [`LoadBalancer::spawn_health_checks`] is
+    /// called, counting anything but a `2xx` response as a failure, and return the
+    /// [`LoadBalancer`].
+    pub fn with_health_check(mut self, path: &str, interval: Duration) -> Self {
+        self.health_check = Some((path.to_string(), interval));
+        self
+    }
+
+    /// [`LoadBalancer::pick`] will pick the [`Backend`] that gets `request`, from the
+    /// backends that aren't ejected, or from every backend when all of them are
+    /// ejected. Returns [`None`] if there are no backends.
+    pub fn pick(&self, request: &Request) -> Option<BackendGuard<'_>> {
+        let available: Vec<&Backend> = self
+            .backends
+            .iter()
+            .filter(|backend| backend.is_available())
+            .collect();
+        let candidates = if available.is_empty() {
+            self.backends.iter().collect()
+        } else {
+            available
+        };
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let backend = match &self.strategy {
+            BalanceStrategy::LeastConnections => candidates
+                .iter()
+                .copied()
+                .min_by_key(|backend| backend.active_requests()),
+            BalanceStrategy::HashByHeader(header) => match request.header(header) {
+                Some(key) => candidates
+                    .iter()
+                    .copied()
+                    .max_by_key(|backend| rendezvous_weight(key, &backend.url)),
+                None => self.next_round_robin(&candidates),
+            },
+            BalanceStrategy::RoundRobin => self.next_round_robin(&candidates),
+        }?;
+        backend.active.fetch_add(1, Ordering::Relaxed);
+        Some(BackendGuard { backend })
+    }
+
+    /// [`LoadBalancer::record_success`] will count a successful request to
+    /// `backend`, bringing it back if it was ejected.
+    pub fn record_success(&self, backend: &Backend) {
+        let failures = backend.failures.swap(0, Ordering::Relaxed);
+        let mut ejected_until = backend.lock_ejected_until();
+        if ejected_until.take().is_some() || failures >= self.max_failures {
+            log_info!(
+                "upstream '{}' recovered, sending it requests again",
+                backend.url
+            );
+        }
+    }
+
+    /// [`LoadBalancer::record_failure`] will count a failed request to `backend`,
+    /// ejecting it for [`LoadBalancer::ejection_duration`] once it failed
+    /// [`LoadBalancer::max_failures`] times in a row.
+    pub fn record_failure(&self, backend: &Backend) {
+        let failures = backend.failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures < self.max_failures {
+            return;
+        }
+        let mut ejected_until = backend.lock_ejected_until();
+        if ejected_until.is_none_or(|ejected_until| Instant::now() >= ejected_until) {
+            log_warning!(
+                "upstream '{}' failed {failures} times in a row, ejecting it for {:?}",
+                backend.url,
+                self.ejection_duration
+            );
+            *ejected_until = Some(Instant::now() + self.ejection_duration);
+        }
+    }
+
+    /// [`LoadBalancer::check_health`] will health check every backend once with
+    /// `client`, if [`LoadBalancer::health_check`] is set.
+    pub fn check_health(&self, client: &Client) {
+        let Some((path, _)) = &self.health_check else {
+            return;
+        };
+        for backend in &self.backends {
+            let request = ClientRequest::new(GET.as_str(), &format!("{}{path}", backend.url));
+            match client.send(&request) {
+                Ok(response) if (200..300).contains(&response.status_code) => {
+                    self.record_success(backend)
+                }
+                _ => self.record_failure(backend),
+            }
+        }
+    }
+
+    /// [`LoadBalancer::spawn_health_checks`] will health check every backend on a
+    /// background thread each interval of [`LoadBalancer::health_check`], until the
+    /// [`LoadBalancer`] is dropped. Does nothing if no health check is set.
+    pub fn spawn_health_checks(self: &Arc<Self>) -> io::Result<()> {
+        let Some((_, interval)) = self.health_check else {
+            return Ok(());
+        };
+        let load_balancer: Weak<LoadBalancer> = Arc::downgrade(self);
+        let client = Client::new().with_timeout(interval);
+        thread::Builder::new()
+            .name("health-checks".to_string())
+            .spawn(move || {
+                while let Some(load_balancer) = load_balancer.upgrade() {
+                    load_balancer.check_health(&client);
+                    drop(load_balancer);
+                    thread::sleep(interval);
+                }
+            })
+            .map(|_| ())
+    }
+
+    /// [`LoadBalancer::next_round_robin`] will pick the next of `candidates` in
+    /// turn.
+    fn next_round_robin<'a>(&self, candidates: &[&'a Backend]) -> Option<&'a Backend> {
+        let position = self.next.fetch_add(1, Ordering::Relaxed);
+        candidates.get(position % candidates.len()).copied()
+    }
+}
+
+/// [`rendezvous_weight`] will get the weight of the backend at `url` for `key`,
+/// where the highest weight wins. Unlike taking the hash modulo the number of
+/// backends, only the keys of an ejected backend move when it is ejected.
+fn rendezvous_weight(key: &str, url: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    url.hash(&mut hasher);
+    hasher.finish()
+}
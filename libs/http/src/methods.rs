@@ -1,11 +1,111 @@
-/// [`GET`] is a `const` [`str`] representation of an HTTP `GET` method.
-pub const GET: &str = "GET";
+use std::{fmt, str::FromStr};
 
-/// [`POST`] is a `const` [`str`] representation of an HTTP `POST` method.
-pub const POST: &str = "POST";
+/// [`Method`] represents an HTTP method the server can route, which is parsed from
+/// the request line of every request.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Method {
+    /// [`Method::Get`] represents an HTTP `GET` method.
+    Get,
+    /// [`Method::Head`] represents an HTTP `HEAD` method.
+    Head,
+    /// [`Method::Post`] represents an HTTP `POST` method.
+    Post,
+    /// [`Method::Put`] represents an HTTP `PUT` method.
+    Put,
+    /// [`Method::Patch`] represents an HTTP `PATCH` method.
+    Patch,
+    /// [`Method::Delete`] represents an HTTP `DELETE` method.
+    Delete,
+    /// [`Method::Options`] represents an HTTP `OPTIONS` method.
+    Options,
+}
 
-/// [`PUT`] is a `const` [`str`] representation of an HTTP `PUT` method.
-pub const PUT: &str = "PUT";
+/// [`GET`] is a `const` [`Method`] for an HTTP `GET` method.
+pub const GET: Method = Method::Get;
 
-/// [`DELETE`] is a `const` [`str`] representation of an HTTP `DELETE` method.
-pub const DELETE: &str = "DELETE";
+/// [`POST`] is a `const` [`Method`] for an HTTP `POST` method.
+pub const POST: Method = Method::Post;
+
+/// [`PUT`] is a `const` [`Method`] for an HTTP `PUT` method.
+pub const PUT: Method = Method::Put;
+
+/// [`PATCH`] is a `const` [`Method`] for an HTTP `PATCH` method.
+pub const PATCH: Method = Method::Patch;
+
+/// [`DELETE`] is a `const` [`Method`] for an HTTP `DELETE` method.
+pub const DELETE: Method = Method::Delete;
+
+/// [`OPTIONS`] is a `const` [`Method`] for an HTTP `OPTIONS` method.
+pub const OPTIONS: Method = Method::Options;
+
+/// [`HEAD`] is a `const` [`Method`] for an HTTP `HEAD` method.
+pub const HEAD: Method = Method::Head;
+
+impl Method {
+    /// [`Method::as_str`] will get the name of the [`Method`] as it is sent in a
+    /// request line, such as `"GET"`.
+    /// # Example
+    /// [`Method::as_str`] can be used to build a request line:
+    /// ```rust
+    /// use http::methods::Method;
+    ///
+    /// fn get_request_line(method: Method, path: &str) -> String {
+    ///     format!("{} {path} HTTP/1.1", method.as_str())
+    /// }
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Head => "HEAD",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+            Method::Options => "OPTIONS",
+        }
+    }
+}
+
+/// [`join`] will join the names of `methods` with `, `, as they are listed in an
+/// `Allow` header.
+/// # Example
+/// [`join`] can be used to build an `Allow` header:
+/// ```rust
+/// use http::methods::{self, GET, OPTIONS};
+///
+/// fn get_allow_header() -> String {
+///     format!("Allow: {}", methods::join(&[GET, OPTIONS]))
+/// }
+/// ```
+pub fn join(methods: &[Method]) -> String {
+    methods
+        .iter()
+        .map(Method::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Implement [`FromStr`] for [`Method`], where method names are case-sensitive.
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "GET" => Ok(Method::Get),
+            "HEAD" => Ok(Method::Head),
+            "POST" => Ok(Method::Post),
+            "PUT" => Ok(Method::Put),
+            "PATCH" => Ok(Method::Patch),
+            "DELETE" => Ok(Method::Delete),
+            "OPTIONS" => Ok(Method::Options),
+            _ => Err(format!("unknown HTTP method '{value}'")),
+        }
+    }
+}
+
+/// Implement [`fmt::Display`] for [`Method`].
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
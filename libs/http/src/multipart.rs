@@ -0,0 +1,185 @@
+use crate::constants::CONTENT_MULTIPART;
+
+/// [`Part`] is one field of a `multipart/form-data` body, such as an uploaded file,
+/// along with its filename and content type when the client sent them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Part {
+    /// [`Part::name`] is the name of the form field the [`Part`] was sent as.
+    pub name: String,
+    /// [`Part::filename`] is the name of the uploaded file, [`None`] for a text field.
+    pub filename: Option<String>,
+    /// [`Part::content_type`] is the `Content-Type` of the [`Part`], [`None`] if the
+    /// client didn't send one.
+    pub content_type: Option<String>,
+    /// [`Part::bytes`] is the content of the [`Part`], exactly as it was sent.
+    pub bytes: Vec<u8>,
+}
+
+impl Part {
+    /// [`Part::text`] will get [`Part::bytes`] as a [`String`], defaults to [`None`]
+    /// if they aren't UTF-8.
+    /// # Example
+    /// [`Part::text`] can be used to read an uploaded text file:
+    /// ```rust
+    /// use http::multipart::Part;
+    ///
+    /// fn count_lines(file: &Part) -> usize {
+    ///     file.text().map_or(0, |text| text.lines().count())
+    /// }
+    /// ```
+    pub fn text(&self) -> Option<String> {
+        String::from_utf8(self.bytes.clone()).ok()
+    }
+
+    /// [`Part::is_file`] will return `true` if the [`Part`] is an uploaded file,
+    /// rather than a text field.
+    pub fn is_file(&self) -> bool {
+        self.filename.is_some()
+    }
+}
+
+/// [`Multipart`] is every [`Part`] of a `multipart/form-data` body, in the order
+/// they were sent. The server adds it to a [`Request`](crate::request::Request)
+/// with a multipart body in place of [`Request::body_content`](crate::request::Request::body_content).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Multipart {
+    parts: Vec<Part>,
+}
+
+impl Multipart {
+    /// [`Multipart::parse`] will parse `body` by the boundary named in
+    /// `content_type`, and return an [`Err`] if `content_type` isn't
+    /// `multipart/form-data` with a boundary, or `body` isn't made of parts
+    /// separated by it.
+    /// # Example
+    /// [`Multipart::parse`] can be used to count the files of an upload:
+    /// ```rust
+    /// use http::multipart::Multipart;
+    ///
+    /// fn count_files(body: &[u8], content_type: &str) -> usize {
+    ///     Multipart::parse(body, content_type)
+    ///         .map_or(0, |multipart| multipart.parts().iter().filter(|part| part.is_file()).count())
+    /// }
+    /// ```
+    pub fn parse(body: &[u8], content_type: &str) -> Result<Self, String> {
+        let boundary = get_boundary(content_type).ok_or_else(|| {
+            format!("'{content_type}' is not multipart/form-data with a boundary")
+        })?;
+        let delimiter = format!("--{boundary}").into_bytes();
+
+        let mut rest = body;
+        let start = find(rest, &delimiter).ok_or("body has no multipart boundary")?;
+        rest = &rest[start + delimiter.len()..];
+        let mut parts = Vec::new();
+        loop {
+            // the last boundary is followed by `--`
+            if rest.starts_with(b"--") {
+                return Ok(Multipart { parts });
+            }
+            rest = rest
+                .strip_prefix(b"\r\n")
+                .ok_or("multipart boundary is not followed by a line break")?;
+            let end = find(rest, &[b"\r\n", delimiter.as_slice()].concat())
+                .ok_or("multipart body ends before its closing boundary")?;
+            parts.push(parse_part(&rest[..end])?);
+            rest = &rest[end + 2 + delimiter.len()..];
+        }
+    }
+
+    /// [`Multipart::part`] will get the first [`Part`] named `name`, defaults to
+    /// [`None`] if no part has that name.
+    /// # Example
+    /// [`Multipart::part`] can be used to get an uploaded avatar:
+    /// ```rust
+    /// use http::multipart::{Multipart, Part};
+    ///
+    /// fn get_avatar(multipart: &Multipart) -> Option<&Part> {
+    ///     multipart.part("avatar")
+    /// }
+    /// ```
+    pub fn part(&self, name: &str) -> Option<&Part> {
+        self.parts.iter().find(|part| part.name == name)
+    }
+
+    /// [`Multipart::parts`] will get every [`Part`], in the order they were sent.
+    pub fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+}
+
+/// [`is_multipart`] will return `true` if `content_type` is `multipart/form-data`.
+/// # Example
+/// [`is_multipart`] can be used to tell if a request body holds uploaded files:
+/// ```rust
+/// use http::{multipart, request::Request};
+///
+/// fn has_uploads(request: &Request) -> bool {
+///     multipart::is_multipart(request.header("content-type"))
+/// }
+/// ```
+pub fn is_multipart(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(CONTENT_MULTIPART))
+}
+
+/// [`get_boundary`] will get the `boundary` parameter of a `multipart/form-data`
+/// `content_type`, defaults to [`None`] for any other content type.
+fn get_boundary(content_type: &str) -> Option<&str> {
+    if !is_multipart(Some(content_type)) {
+        return None;
+    }
+    content_type
+        .split(';')
+        .skip(1)
+        .filter_map(|parameter| parameter.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"'))
+        .filter(|boundary| !boundary.is_empty())
+}
+
+/// [`parse_part`] will parse the headers and content of a single part, which are
+/// separated by an empty line.
+fn parse_part(part: &[u8]) -> Result<Part, String> {
+    let header_end = find(part, b"\r\n\r\n").ok_or("multipart part has no headers")?;
+    let headers = std::str::from_utf8(&part[..header_end])
+        .map_err(|_| "multipart part headers are not valid utf-8".to_string())?;
+
+    let mut parsed = Part {
+        bytes: part[header_end + 4..].to_vec(),
+        ..Part::default()
+    };
+    let mut has_disposition = false;
+    for header in headers.split("\r\n") {
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        if name.trim().eq_ignore_ascii_case("content-type") {
+            parsed.content_type = Some(value.trim().to_string());
+        } else if name.trim().eq_ignore_ascii_case("content-disposition") {
+            has_disposition = true;
+            for parameter in value.split(';').skip(1) {
+                let Some((key, value)) = parameter.split_once('=') else {
+                    continue;
+                };
+                let value = value.trim().trim_matches('"').to_string();
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "name" => parsed.name = value,
+                    "filename" => parsed.filename = Some(value),
+                    _ => {}
+                }
+            }
+        }
+    }
+    if !has_disposition || parsed.name.is_empty() {
+        return Err("multipart part has no content-disposition name".to_string());
+    }
+    Ok(parsed)
+}
+
+/// [`find`] will get the index of the first `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
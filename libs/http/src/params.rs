@@ -0,0 +1,101 @@
+use crate::{request::Request, validation::RouteParam};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// [`PathParams`] is a struct that every path parameter of a route binds into, such
+/// as `OrgRepo { org: String, repo: String }` for `/orgs/{org}/repos/{repo}`, so a
+/// handler takes a single argument for a deeply nested path. It is implemented with
+/// `#[derive(PathParams)]`, and the handler argument is marked with `#[path]`.
+pub trait PathParams: Sized {
+    /// [`PathParams::from_path_params`] will parse every field from the path
+    /// parameter of the same name in `path_params`, and return an [`Err`] naming
+    /// the first one that is missing or can't be parsed into its type.
+    /// # Example
+    /// [`PathParams::from_path_params`] can be used to bind the path parameters of
+    /// a [`Request`](crate::request::Request):
+    /// ```rust
+    /// use http::{params::PathParams, request::Request};
+    ///
+    /// fn bind_path_params<T: PathParams>(request: &Request) -> Result<T, String> {
+    ///     T::from_path_params(&request.path_params)
+    /// }
+    /// ```
+    fn from_path_params(path_params: &HashMap<String, String>) -> Result<Self, String>;
+
+    /// [`PathParams::route_params`] will get a path [`RouteParam`] for every field,
+    /// with the type of the field.
+    fn route_params() -> Vec<RouteParam>;
+}
+
+/// [`FromQuery`] is a struct that the query parameters of a request bind into, such
+/// as `SearchParams { term: String, page: u32 }` for `/search?term=rust&page=2`, so a
+/// handler takes a single argument for many query parameters. It is implemented
+/// with `#[derive(FromQuery)]`, and the handler argument is marked with `#[query]`.
+pub trait FromQuery: Sized {
+    /// [`FromQuery::from_query`] will parse every field from the query parameter of
+    /// the same name in `request`, and return an [`Err`] naming the first one that is
+    /// missing or can't be parsed into its type.
+    /// # Example
+    /// [`FromQuery::from_query`] can be used to bind the query parameters of a
+    /// [`Request`]:
+    /// ```rust
+    /// use http::{params::FromQuery, request::Request};
+    ///
+    /// fn bind_query<T: FromQuery>(request: &Request) -> Result<T, String> {
+    ///     T::from_query(request)
+    /// }
+    /// ```
+    fn from_query(request: &Request) -> Result<Self, String>;
+
+    /// [`FromQuery::route_params`] will get a query [`RouteParam`] for every field,
+    /// with the type of the field.
+    fn route_params() -> Vec<RouteParam>;
+}
+
+/// [`Uuid`] is a UUID, such as `67e55044-10b1-426f-9247-bb680e5fe0c8`, which a path
+/// parameter can be parsed into, so a handler only ever sees well-formed ids and a
+/// path with any other segment is answered with `404 NOT FOUND`. Its [`Default`] is
+/// the nil UUID of all zeros.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Uuid(pub [u8; 16]);
+
+/// [`UUID_GROUPS`] is how many hex digits each group of a hyphenated [`Uuid`] has.
+const UUID_GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+
+/// Implement [`FromStr`] for [`Uuid`], which parses the hyphenated form of a UUID
+/// in either case.
+impl FromStr for Uuid {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let groups: Vec<&str> = value.split('-').collect();
+        let is_hyphenated = groups.len() == UUID_GROUPS.len()
+            && groups
+                .iter()
+                .zip(UUID_GROUPS)
+                .all(|(group, digits)| group.len() == digits);
+        if !is_hyphenated || !groups.concat().chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("'{value}' is not a UUID"));
+        }
+        let digits = groups.concat();
+        let mut bytes = [0; 16];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&digits[index * 2..index * 2 + 2], 16)
+                .map_err(|_| format!("'{value}' is not a UUID"))?;
+        }
+        Ok(Uuid(bytes))
+    }
+}
+
+/// Implement [`fmt::Display`] for [`Uuid`], which writes its lowercase hyphenated
+/// form.
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, byte) in self.0.iter().enumerate() {
+            if matches!(index, 4 | 6 | 8 | 10) {
+                write!(f, "-")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
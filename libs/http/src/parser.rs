@@ -0,0 +1,269 @@
+use crate::{
+    arena::{self, Arena, ArenaStr},
+    intern::{HeaderName, headers::CONTENT_LENGTH, headers::TRANSFER_ENCODING},
+};
+use std::{collections::HashMap, io::BufRead, str::FromStr};
+
+/// [`MAX_REQUEST_HEAD_BYTES`] is the most bytes the request line and headers of a
+/// request can take up together, where a larger request head is refused before it
+/// is read any further.
+pub const MAX_REQUEST_HEAD_BYTES: usize = 64 * 1024;
+
+/// [`MAX_REQUEST_HEADERS`] is the most header lines a request can have.
+pub const MAX_REQUEST_HEADERS: usize = 100;
+
+/// [`ParsingMode`] represents how strictly the server parses incoming requests.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParsingMode {
+    /// [`ParsingMode::Strict`] rejects obsolete line folding, duplicate
+    /// `Content-Length` headers, and bare `LF` line endings.
+    Strict,
+    /// [`ParsingMode::Lenient`] accepts obsolete line folding, duplicate
+    /// `Content-Length` headers with the same value, and bare `LF` line endings
+    /// for compatibility with older clients.
+    Lenient,
+}
+
+/// Implement [`FromStr`] for [`ParsingMode`].
+impl FromStr for ParsingMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "strict" => Ok(ParsingMode::Strict),
+            "lenient" => Ok(ParsingMode::Lenient),
+            _ => Err(format!("unknown parsing mode '{value}'")),
+        }
+    }
+}
+
+/// [`RequestHead`] represents the request line and headers of a request, before
+/// any body content has been read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestHead {
+    /// [`RequestHead::method`] is the HTTP method, such as `"GET"`.
+    pub method: String,
+    /// [`RequestHead::target`] is the **entire** request path, including any query.
+    pub target: String,
+    /// [`RequestHead::version`] is the HTTP version, such as `"HTTP/1.1"`.
+    pub version: String,
+    /// [`RequestHead::headers`] is a collection of HTTP headers, keyed by their
+    /// case-insensitive [`HeaderName`], with the values of repeated headers joined
+    /// with commas.
+    pub headers: HashMap<HeaderName, String>,
+    /// [`RequestHead::content_length`] is the length of the body content, which
+    /// defaults to `0` when no `Content-Length` header was sent.
+    pub content_length: usize,
+}
+
+/// [`read_request_head`] will read the request line and headers from `reader`,
+/// following the rules of the provided [`ParsingMode`]. Returns an [`Err`]
+/// describing the problem if the request is malformed, or if its headers could
+/// be used to smuggle a request, such as conflicting `Content-Length` headers or
+/// both `Content-Length` and `Transfer-Encoding`, regardless of [`ParsingMode`], or
+/// if it is larger than [`MAX_REQUEST_HEAD_BYTES`] or has more than
+/// [`MAX_REQUEST_HEADERS`].
+/// # Example
+/// [`read_request_head`] can be used to parse a request from any [`BufRead`]:
+/// ```rust
+/// use http::parser::{self, ParsingMode, RequestHead};
+/// use std::io::BufRead;
+///
+/// fn parse_request(reader: &mut impl BufRead) -> Option<RequestHead> {
+///     parser::read_request_head(reader, ParsingMode::Strict).ok()
+/// }
+/// ```
+pub fn read_request_head(
+    reader: &mut impl BufRead,
+    mode: ParsingMode,
+) -> Result<RequestHead, String> {
+    // every line is read into the worker's arena, so only the final values allocate
+    arena::with_request_arena(|arena| read_request_head_in(arena, reader, mode))
+}
+
+/// [`read_request_head_in`] will read the request line and headers from `reader`
+/// like [`read_request_head`], using `arena` as scratch space for every line.
+fn read_request_head_in(
+    arena: &mut Arena,
+    reader: &mut impl BufRead,
+    mode: ParsingMode,
+) -> Result<RequestHead, String> {
+    // every line counts against the size of the whole request head
+    let mut remaining = MAX_REQUEST_HEAD_BYTES;
+    // read the first line of the request (e.g., "GET /get/person/123?name=Alice HTTP/1.1")
+    let request_line = read_line(arena, reader, mode, &mut remaining)?.ok_or("request is empty")?;
+    let request_line = std::str::from_utf8(arena.get_bytes(&request_line))
+        .map_err(|_| "request line is not valid UTF-8".to_string())?;
+    let parts: Vec<&str> = request_line.splitn(3, ' ').collect();
+    if parts.len() != 3 || parts.iter().any(|part| part.is_empty()) {
+        return Err("request line is malformed".to_string());
+    }
+    let (method, target, version) = (
+        parts[0].to_string(),
+        parts[1].to_string(),
+        parts[2].to_string(),
+    );
+
+    let mut header_lines: Vec<ArenaStr> = Vec::new();
+    loop {
+        let line = match read_line(arena, reader, mode, &mut remaining)? {
+            Some(line) if arena.get_bytes(&line).is_empty() => break,
+            Some(line) => line,
+            None if mode == ParsingMode::Lenient => break,
+            None => return Err("request ended before headers ended".to_string()),
+        };
+
+        // obsolete line folding continues the previous header on a new line
+        if arena.get_bytes(&line).starts_with(b" ") || arena.get_bytes(&line).starts_with(b"\t") {
+            match (mode, header_lines.last_mut()) {
+                (ParsingMode::Lenient, Some(previous)) => {
+                    let continuation = arena.trim(&line);
+                    *previous = arena.join(previous, " ", &continuation);
+                    continue;
+                }
+                _ => return Err("obsolete line folding is not allowed".to_string()),
+            }
+        }
+        if header_lines.len() == MAX_REQUEST_HEADERS {
+            return Err(format!(
+                "request has more than {MAX_REQUEST_HEADERS} headers"
+            ));
+        }
+        header_lines.push(line);
+    }
+
+    let mut headers = HashMap::new();
+    let mut content_length: Option<usize> = None;
+    for line in header_lines {
+        let line = arena.get_bytes(&line);
+        let Some((name, value)) = split_header_line(line) else {
+            return Err(format!(
+                "header '{}' is malformed",
+                String::from_utf8_lossy(line)
+            ));
+        };
+        let name =
+            std::str::from_utf8(name).map_err(|_| "header name is not valid UTF-8".to_string())?;
+        let value = std::str::from_utf8(value)
+            .map_err(|_| format!("header '{name}' is not valid UTF-8"))?;
+        let name = HeaderName::new(name);
+
+        if name == CONTENT_LENGTH {
+            let length = value
+                .parse()
+                .map_err(|_| format!("Content-Length '{value}' is not a number"))?;
+            match content_length {
+                Some(_) if mode == ParsingMode::Strict => {
+                    return Err("duplicate Content-Length headers are not allowed".to_string());
+                }
+                Some(previous) if previous != length => {
+                    return Err("conflicting Content-Length headers are not allowed".to_string());
+                }
+                Some(_) => continue,
+                None => content_length = Some(length),
+            }
+        }
+
+        // repeated headers are combined into one comma-separated value
+        headers
+            .entry(name)
+            .and_modify(|existing: &mut String| {
+                existing.push_str(", ");
+                existing.push_str(value);
+            })
+            .or_insert_with(|| value.to_string());
+    }
+
+    if content_length.is_some() && headers.contains_key(&TRANSFER_ENCODING) {
+        return Err("Content-Length and Transfer-Encoding are not allowed together".to_string());
+    }
+
+    Ok(RequestHead {
+        method,
+        target,
+        version,
+        headers,
+        content_length: content_length.unwrap_or(0),
+    })
+}
+
+/// [`read_line`] will read a single line from `reader` into `arena` without its
+/// line ending, returning [`None`] when there is nothing left to read. Bare `LF`
+/// line endings are only accepted in [`ParsingMode::Lenient`]. The line is taken
+/// out of the `remaining` bytes of the request head, and is an [`Err`] if it
+/// doesn't fit in them.
+fn read_line(
+    arena: &mut Arena,
+    reader: &mut impl BufRead,
+    mode: ParsingMode,
+    remaining: &mut usize,
+) -> Result<Option<ArenaStr>, String> {
+    let too_large = || format!("request head is larger than {MAX_REQUEST_HEAD_BYTES} bytes");
+    if *remaining == 0 {
+        return Err(too_large());
+    }
+    let line = arena
+        .alloc_line_within(reader, *remaining)
+        .map_err(|error| format!("can't read request: {error}"))?;
+    let bytes = arena.get_bytes(&line);
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    *remaining -= bytes.len();
+    if *remaining == 0 && !bytes.ends_with(b"\n") {
+        return Err(too_large());
+    }
+
+    if bytes.ends_with(b"\r\n") {
+        Ok(Some(line.shorten(2)))
+    } else if mode == ParsingMode::Lenient {
+        Ok(Some(line.shorten(usize::from(bytes.ends_with(b"\n")))))
+    } else {
+        Err("line endings must be CRLF".to_string())
+    }
+}
+
+/// [`split_header_line`] will split a header line without its line ending into
+/// its name and value, both without surrounding whitespace, defaults to [`None`]
+/// if the line has no `:`. The line is scanned as raw bytes, so nothing has to be
+/// valid UTF-8 until the name and value are used.
+/// # Example
+/// [`split_header_line`] can be used to read a header straight from a buffer:
+/// ```rust
+/// use http::parser;
+///
+/// fn header_name(line: &[u8]) -> Option<&[u8]> {
+///     parser::split_header_line(line).map(|(name, _)| name)
+/// }
+/// ```
+pub fn split_header_line(line: &[u8]) -> Option<(&[u8], &[u8])> {
+    let colon = find_byte(line, b':')?;
+    Some((line[..colon].trim_ascii(), line[colon + 1..].trim_ascii()))
+}
+
+/// [`find_byte`] will return the index of the first `needle` in `haystack`,
+/// checking eight bytes at a time, defaults to [`None`] if there is no `needle`.
+fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    const LOW_BITS: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HIGH_BITS: u64 = u64::from_ne_bytes([0x80; 8]);
+    let needles = LOW_BITS * u64::from(needle);
+
+    let mut words = haystack.chunks_exact(8);
+    let mut offset = 0;
+    for word in &mut words {
+        // every byte that equals `needle` becomes zero, and the lowest zero byte
+        // is the only one guaranteed to have its high bit set below
+        let word = u64::from_le_bytes(word.try_into().ok()?) ^ needles;
+        let zero_bytes = word.wrapping_sub(LOW_BITS) & !word & HIGH_BITS;
+        if zero_bytes != 0 {
+            return Some(offset + zero_bytes.trailing_zeros() as usize / 8);
+        }
+        offset += 8;
+    }
+
+    words
+        .remainder()
+        .iter()
+        .position(|byte| *byte == needle)
+        .map(|index| offset + index)
+}
@@ -0,0 +1,36 @@
+/// [`decode`] will decode every `%XX` escape of `value` into the byte it stands
+/// for, and return an [`Err`] if an escape isn't two hex digits, or the decoded
+/// bytes aren't UTF-8.
+/// # Example
+/// [`decode`] can be used to get the text of a path segment:
+/// ```rust
+/// use http::percent;
+///
+/// fn get_name(segment: &str) -> String {
+///     percent::decode(segment).unwrap_or_else(|_| segment.to_string())
+/// }
+/// ```
+pub fn decode(value: &str) -> Result<String, String> {
+    if !value.contains('%') {
+        return Ok(value.to_string());
+    }
+
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, after)) = rest.split_first() {
+        if byte != b'%' {
+            bytes.push(byte);
+            rest = after;
+            continue;
+        }
+        let escaped = after
+            .get(..2)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .filter(|digits| digits.chars().all(|digit| digit.is_ascii_hexdigit()))
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| format!("'{value}' has an invalid percent escape"))?;
+        bytes.push(escaped);
+        rest = &after[2..];
+    }
+    String::from_utf8(bytes).map_err(|_| format!("'{value}' does not decode to UTF-8"))
+}
@@ -0,0 +1,184 @@
+use crate::{
+    json::Json,
+    route::{RouteSegment, RouteSpec},
+    validation::{ParamLocation, RouteParam},
+};
+
+/// [`POSTMAN_SCHEMA`] is the schema of the Postman collections that
+/// [`export_collection`] creates, which Insomnia can import as well.
+pub const POSTMAN_SCHEMA: &str =
+    "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// [`BASE_URL_VARIABLE`] is the collection variable every request URL starts with,
+/// so the server a collection calls can be changed in one place.
+pub const BASE_URL_VARIABLE: &str = "baseUrl";
+
+/// [`export_collection`] will export `routes` as a Postman collection named `name`,
+/// with a request for every [`RouteSpec`] of a route on the server at `base_url`. Path
+/// parameters become Postman path variables, such as `:id`, and query parameters and
+/// the body from [`RouteSpec::params`] are left empty to be filled in, with their type
+/// and description as their description. Every request is described by
+/// [`RouteSpec::description`].
+/// # Example
+/// [`export_collection`] can be used to write a collection file:
+/// ```rust
+/// use http::{postman, response::Response, route::Route};
+///
+/// fn write_collection() -> std::io::Result<()> {
+///     let routes = vec![Route::get("/user/{id}", Box::new(|_| Response::ok("\"user\"", false))).spec];
+///     let collection = postman::export_collection(&routes, "Users", "http://localhost:8080");
+///     std::fs::write("users.postman_collection.json", collection.to_string())
+/// }
+/// ```
+pub fn export_collection(routes: &[RouteSpec], name: &str, base_url: &str) -> Json {
+    Json::Object(vec![
+        (
+            "info".to_string(),
+            Json::Object(vec![
+                ("name".to_string(), Json::String(name.to_string())),
+                (
+                    "schema".to_string(),
+                    Json::String(POSTMAN_SCHEMA.to_string()),
+                ),
+            ]),
+        ),
+        (
+            "variable".to_string(),
+            Json::Array(vec![Json::Object(vec![
+                (
+                    "key".to_string(),
+                    Json::String(BASE_URL_VARIABLE.to_string()),
+                ),
+                (
+                    "value".to_string(),
+                    Json::String(base_url.trim_end_matches('/').to_string()),
+                ),
+            ])]),
+        ),
+        (
+            "item".to_string(),
+            Json::Array(routes.iter().map(export_request).collect()),
+        ),
+    ])
+}
+
+/// [`export_request`] will export `route` as a Postman request item.
+fn export_request(route: &RouteSpec) -> Json {
+    let path: Vec<String> = route
+        .segments
+        .iter()
+        .skip(1)
+        .map(|segment| match segment {
+            RouteSegment::Static(symbol) => symbol.as_str().to_string(),
+            RouteSegment::Param(name) | RouteSegment::Rest(name) => format!(":{name}"),
+        })
+        .collect();
+    let path_variables: Vec<Json> = route
+        .segments
+        .iter()
+        .filter_map(|segment| match segment {
+            RouteSegment::Param(name) | RouteSegment::Rest(name) => Some(name),
+            RouteSegment::Static(_) => None,
+        })
+        .map(
+            |name| match route.params.iter().find(|param| &param.name == name) {
+                Some(param) => param_key_value(param),
+                None => key_value(name, "String"),
+            },
+        )
+        .collect();
+    let query: Vec<Json> = route
+        .params
+        .iter()
+        .filter(|param| param.location == ParamLocation::Query)
+        .map(param_key_value)
+        .collect();
+    let headers: Vec<Json> = route
+        .params
+        .iter()
+        .filter(|param| param.location == ParamLocation::Header)
+        .map(param_key_value)
+        .collect();
+    let raw_query = route
+        .params
+        .iter()
+        .filter(|param| param.location == ParamLocation::Query)
+        .map(|param| format!("{}=", param.name))
+        .collect::<Vec<_>>()
+        .join("&");
+    let raw_url = format!(
+        "{{{{{BASE_URL_VARIABLE}}}}}/{}{}{raw_query}",
+        path.join("/"),
+        if raw_query.is_empty() { "" } else { "?" }
+    );
+
+    let mut request = vec![
+        ("method".to_string(), Json::String(route.method.to_string())),
+        (
+            "description".to_string(),
+            Json::String(route.description.clone().unwrap_or_default()),
+        ),
+        ("header".to_string(), Json::Array(headers)),
+        (
+            "url".to_string(),
+            Json::Object(vec![
+                ("raw".to_string(), Json::String(raw_url)),
+                (
+                    "host".to_string(),
+                    Json::Array(vec![Json::String(format!("{{{{{BASE_URL_VARIABLE}}}}}"))]),
+                ),
+                (
+                    "path".to_string(),
+                    Json::Array(path.into_iter().map(Json::String).collect()),
+                ),
+                ("query".to_string(), Json::Array(query)),
+                ("variable".to_string(), Json::Array(path_variables)),
+            ]),
+        ),
+    ];
+    if route
+        .params
+        .iter()
+        .any(|param| param.location == ParamLocation::Body)
+    {
+        request.push((
+            "body".to_string(),
+            Json::Object(vec![
+                ("mode".to_string(), Json::String("raw".to_string())),
+                ("raw".to_string(), Json::String(String::new())),
+            ]),
+        ));
+    }
+
+    Json::Object(vec![
+        (
+            "name".to_string(),
+            Json::String(format!("{} {}", route.method, route.request_pattern)),
+        ),
+        ("request".to_string(), Json::Object(request)),
+    ])
+}
+
+/// [`param_key_value`] will create an empty Postman key and value pair for `param`,
+/// described by its type, followed by its description if it has one.
+fn param_key_value(param: &RouteParam) -> Json {
+    match &param.description {
+        Some(description) => {
+            key_value(&param.name, &format!("{} - {description}", param.type_name))
+        }
+        None => key_value(&param.name, &param.type_name),
+    }
+}
+
+/// [`key_value`] will create an empty Postman key and value pair for the parameter
+/// `name`, described by its `type_name`.
+fn key_value(name: &str, type_name: &str) -> Json {
+    Json::Object(vec![
+        ("key".to_string(), Json::String(name.to_string())),
+        ("value".to_string(), Json::String(String::new())),
+        (
+            "description".to_string(),
+            Json::String(type_name.to_string()),
+        ),
+    ])
+}
@@ -0,0 +1,366 @@
+use crate::{
+    circuit_breaker::CircuitState,
+    client::{Client, ClientRequest, ClientResponse},
+    constants::{CONTENT_LENGTH, LOCATION},
+    load_balancer::LoadBalancer,
+    methods::{DELETE, GET, POST, PUT},
+    request::Request,
+    response::Response,
+    route::Route,
+    status::Status,
+};
+use std::{fmt, sync::Arc};
+
+/// [`PROXY_PATH_PARAM`] is the name of the catch-all path parameter a [`Proxy`]
+/// route forwards, which is the rest of the path after [`Proxy::prefix`].
+pub const PROXY_PATH_PARAM: &str = "proxy_path";
+
+/// [`HOP_BY_HOP_HEADERS`] are the headers that only apply to a single connection,
+/// so a [`Proxy`] never forwards them in either direction.
+pub const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// [`ResponseTransformFn`] rewrites an upstream response before a [`Proxy`] passes
+/// it back, given the [`Request`] that was forwarded.
+pub type ResponseTransformFn = Arc<dyn Fn(&Request, &mut ClientResponse) + Send + Sync>;
+
+/// [`Proxy`] forwards every request under a path prefix to an upstream server, and
+/// passes the upstream response back, after running it through every response
+/// transform. Requests can be spread across several upstreams with a
+/// [`LoadBalancer`]. Request and response bodies are forwarded as text.
+#[derive(Clone)]
+pub struct Proxy {
+    /// [`Proxy::prefix`] is the request path prefix that is forwarded, such as
+    /// `/api`.
+    pub prefix: String,
+    /// [`Proxy::upstream`] is the `http://` URL requests are forwarded to, which
+    /// the rest of the path after [`Proxy::prefix`] is appended to. Unused when
+    /// there is a [`Proxy::load_balancer`].
+    pub upstream: String,
+    /// [`Proxy::load_balancer`] picks the upstream each request is forwarded to,
+    /// if any.
+    pub load_balancer: Option<Arc<LoadBalancer>>,
+    /// [`Proxy::client`] is the [`Client`] requests are forwarded with.
+    pub client: Client,
+    /// [`Proxy::response_transforms`] rewrite every upstream response, in the order
+    /// they were added.
+    pub response_transforms: Vec<ResponseTransformFn>,
+}
+
+impl Proxy {
+    /// [`Proxy::new`] creates a [`Proxy`] that forwards every request under `prefix`
+    /// to `upstream` with a default [`Client`].
+    /// # Example
+    /// [`Proxy::new`] can be used to forward `/api/users` to
+    /// `http://127.0.0.1:9000/users`:
+    /// ```rust
+    /// use http::{proxy::Proxy, route::Route};
+    ///
+    /// fn get_proxy_routes() -> Vec<Route> {
+    ///     Proxy::new("/api", "http://127.0.0.1:9000").routes()
+    /// }
+    /// ```
+    pub fn new(prefix: &str, upstream: &str) -> Self {
+        Proxy {
+            prefix: prefix.trim_end_matches('/').to_string(),
+            upstream: upstream.trim_end_matches('/').to_string(),
+            load_balancer: None,
+            client: Client::new(),
+            response_transforms: Vec::new(),
+        }
+    }
+
+    /// [`Proxy::balanced`] creates a [`Proxy`] that forwards every request under
+    /// `prefix` to the upstream `load_balancer` picks, with a default [`Client`].
+    /// Failed requests and `5xx` responses count against the picked upstream.
+    /// # Example
+    /// [`Proxy::balanced`] can be used to spread requests across healthy upstreams:
+    /// ```rust
+    /// use http::{
+    ///     load_balancer::{BalanceStrategy, LoadBalancer},
+    ///     proxy::Proxy,
+    ///     route::Route,
+    /// };
+    /// use std::{io, sync::Arc, time::Duration};
+    ///
+    /// fn get_balanced_routes() -> io::Result<Vec<Route>> {
+    ///     let load_balancer = Arc::new(
+    ///         LoadBalancer::new(
+    ///             &["http://10.0.0.1:9000", "http://10.0.0.2:9000"],
+    ///             BalanceStrategy::LeastConnections,
+    ///         )
+    ///         .with_health_check("/health", Duration::from_secs(5)),
+    ///     );
+    ///     load_balancer.spawn_health_checks()?;
+    ///     Ok(Proxy::balanced("/api", load_balancer).routes())
+    /// }
+    /// ```
+    pub fn balanced(prefix: &str, load_balancer: Arc<LoadBalancer>) -> Self {
+        let upstream = load_balancer
+            .backends
+            .first()
+            .map(|backend| backend.url.as_str())
+            .unwrap_or_default();
+        Proxy {
+            load_balancer: Some(Arc::clone(&load_balancer)),
+            ..Proxy::new(prefix, upstream)
+        }
+    }
+
+    /// [`Proxy::with_client`] will forward requests with the provided [`Client`],
+    /// and return the [`Proxy`].
+    /// # Example
+    /// [`Proxy::with_client`] can be used to fail fast while the upstream is down:
+    /// ```rust
+    /// use http::{circuit_breaker::CircuitBreaker, client::Client, proxy::Proxy};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_guarded_proxy() -> Proxy {
+    ///     let client = Client::new().with_circuit_breaker(Arc::new(CircuitBreaker::new()));
+    ///     Proxy::new("/api", "http://127.0.0.1:9000").with_client(client)
+    /// }
+    /// ```
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// [`Proxy::with_response_transform`] will add `transform` to
+    /// [`Proxy::response_transforms`], and return the [`Proxy`].
+    /// # Example
+    /// [`Proxy::with_response_transform`] can be used to rewrite upstream bodies:
+    /// ```rust
+    /// use http::proxy::Proxy;
+    ///
+    /// fn create_renaming_proxy() -> Proxy {
+    ///     Proxy::new("/api", "http://127.0.0.1:9000").with_response_transform(|_, response| {
+    ///         let body = response.body_as_string().replace("legacy_id", "id");
+    ///         response.body = body.into_bytes();
+    ///     })
+    /// }
+    /// ```
+    pub fn with_response_transform(
+        mut self,
+        transform: impl Fn(&Request, &mut ClientResponse) + Send + Sync + 'static,
+    ) -> Self {
+        self.response_transforms.push(Arc::new(transform));
+        self
+    }
+
+    /// [`Proxy::with_stripped_headers`] will remove every header in `headers` from
+    /// upstream responses, ignoring case, and return the [`Proxy`].
+    /// # Example
+    /// [`Proxy::with_stripped_headers`] can be used to hide internal headers:
+    /// ```rust
+    /// use http::proxy::Proxy;
+    ///
+    /// fn create_gateway() -> Proxy {
+    ///     Proxy::new("/api", "http://127.0.0.1:9000")
+    ///         .with_stripped_headers(&["Server", "X-Internal-Trace"])
+    /// }
+    /// ```
+    pub fn with_stripped_headers(self, headers: &[&str]) -> Self {
+        let headers: Vec<String> = headers.iter().map(|name| name.to_string()).collect();
+        self.with_response_transform(move |_, response| {
+            response.headers.retain(|(name, _)| {
+                !headers
+                    .iter()
+                    .any(|header| header.eq_ignore_ascii_case(name))
+            });
+        })
+    }
+
+    /// [`Proxy::with_location_rewrite`] will rewrite `Location` headers that point
+    /// at [`Proxy::upstream`], or any upstream of [`Proxy::load_balancer`], to point
+    /// at the same resource under
+    /// [`Proxy::prefix`], on the host the client asked for, and return the
+    /// [`Proxy`]. The rewritten `Location` is a path when the forwarded request had
+    /// no `Host` header.
+    pub fn with_location_rewrite(self) -> Self {
+        let upstreams: Vec<String> = match &self.load_balancer {
+            Some(load_balancer) => load_balancer
+                .backends
+                .iter()
+                .map(|backend| backend.url.clone())
+                .collect(),
+            None => vec![self.upstream.clone()],
+        };
+        let prefix = self.prefix.clone();
+        self.with_response_transform(move |request, response| {
+            for (name, value) in response.headers.iter_mut() {
+                if !name.eq_ignore_ascii_case(LOCATION) {
+                    continue;
+                }
+                let Some(rest) = upstreams
+                    .iter()
+                    .find_map(|upstream| value.strip_prefix(upstream.as_str()))
+                else {
+                    continue;
+                };
+                if !rest.is_empty() && !rest.starts_with(['/', '?']) {
+                    continue;
+                }
+                *value = match request.header("Host") {
+                    Some(host) => format!("http://{host}{prefix}{rest}"),
+                    None => format!("{prefix}{rest}"),
+                };
+            }
+        })
+    }
+
+    /// [`Proxy::routes`] will create a [`Route`] for every supported HTTP method
+    /// that forwards requests under [`Proxy::prefix`] with [`Proxy::forward`].
+    pub fn routes(self) -> Vec<Route> {
+        let proxy = Arc::new(self);
+        let pattern = format!("{}/{{*{PROXY_PATH_PARAM}}}", proxy.prefix);
+        [GET, POST, PUT, DELETE]
+            .into_iter()
+            .map(|method| {
+                let proxy = Arc::clone(&proxy);
+                let handler = Box::new(move |request: Request| proxy.forward(request));
+                match method {
+                    GET => Route::get(&pattern, handler),
+                    POST => Route::post(&pattern, handler),
+                    PUT => Route::put(&pattern, handler),
+                    _ => Route::delete(&pattern, handler),
+                }
+            })
+            .collect()
+    }
+
+    /// [`Proxy::forward`] will send `request` to [`Proxy::upstream`], or the
+    /// upstream [`Proxy::load_balancer`] picks, and return the upstream response as
+    /// a raw [`Response`]. When the upstream can't be reached, a
+    /// [`Status::BadGateway`] [`Response`] is returned, or a
+    /// [`Status::ServiceUnavailable`] [`Response`] with a `Retry-After` header when
+    /// the circuit breaker of [`Proxy::client`] is open.
+    pub fn forward(&self, request: Request) -> Response {
+        let Some(load_balancer) = &self.load_balancer else {
+            let result = self
+                .client
+                .send(&self.to_upstream_request(&request, &self.upstream));
+            return self.to_response(&request, result);
+        };
+        let Some(picked) = load_balancer.pick(&request) else {
+            return self.to_response(&request, Err("no upstreams to forward to".to_string()));
+        };
+
+        let result = self
+            .client
+            .send(&self.to_upstream_request(&request, &picked.backend.url));
+        match &result {
+            Ok(response) if response.status_code < 500 => {
+                load_balancer.record_success(picked.backend)
+            }
+            _ => load_balancer.record_failure(picked.backend),
+        }
+        self.to_response(&request, result)
+    }
+
+    /// [`Proxy::to_response`] will turn the `result` of forwarding `request` into
+    /// the [`Response`] passed back, running it through every response transform.
+    fn to_response(&self, request: &Request, result: Result<ClientResponse, String>) -> Response {
+        match result {
+            Ok(mut upstream_response) => {
+                for transform in &self.response_transforms {
+                    transform(request, &mut upstream_response);
+                }
+                upstream_response
+                    .headers
+                    .iter()
+                    .filter(|(name, _)| {
+                        !is_hop_by_hop(name) && !name.eq_ignore_ascii_case(CONTENT_LENGTH)
+                    })
+                    .fold(
+                        Response::with_status(
+                            Status::from_code(
+                                upstream_response.status_code,
+                                &upstream_response.reason,
+                            ),
+                            &upstream_response.body_as_string(),
+                            true,
+                        ),
+                        |response, (name, value)| response.with_header(name, value),
+                    )
+            }
+            Err(_) => match &self.client.circuit_breaker {
+                Some(circuit_breaker) if circuit_breaker.state() == CircuitState::Open => {
+                    Response::service_unavailable().with_header(
+                        "Retry-After",
+                        &circuit_breaker.retry_after().as_secs().max(1).to_string(),
+                    )
+                }
+                _ => Response::with_status(
+                    Status::BadGateway,
+                    "\"The server upstream didn't answer\"",
+                    false,
+                ),
+            },
+        }
+    }
+
+    /// [`Proxy::to_upstream_request`] will create the [`ClientRequest`] that forwards
+    /// `request` to `upstream`, keeping its query string and every header besides
+    /// `Host`, `Content-Length`, and [`HOP_BY_HOP_HEADERS`].
+    fn to_upstream_request(&self, request: &Request, upstream: &str) -> ClientRequest {
+        let path = request
+            .path_params
+            .get(PROXY_PATH_PARAM)
+            .map(|path| path.as_str())
+            .unwrap_or_default();
+        let query = request
+            .path
+            .split_once('?')
+            .map(|(_, query)| format!("?{query}"))
+            .unwrap_or_default();
+        let url = format!("{upstream}/{path}{query}");
+
+        let upstream_request = request
+            .headers
+            .iter()
+            .filter(|(name, _)| {
+                let name = name.as_str();
+                !is_hop_by_hop(name) && name != "host" && !name.eq_ignore_ascii_case(CONTENT_LENGTH)
+            })
+            .fold(
+                ClientRequest::new(request.method.as_str(), &url),
+                |upstream_request, (name, value)| {
+                    upstream_request.with_header(name.as_str(), value)
+                },
+            );
+        match &request.body_content {
+            Some(body) => upstream_request.with_body(body.as_bytes()),
+            None => upstream_request,
+        }
+    }
+}
+
+/// Implement [`fmt::Debug`] for [`Proxy`], counting the response transforms since
+/// closures can't be printed.
+impl fmt::Debug for Proxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Proxy")
+            .field("prefix", &self.prefix)
+            .field("upstream", &self.upstream)
+            .field("load_balancer", &self.load_balancer)
+            .field("client", &self.client)
+            .field("response_transforms", &self.response_transforms.len())
+            .finish()
+    }
+}
+
+/// [`is_hop_by_hop`] will return `true` if the header `name` is one of the
+/// [`HOP_BY_HOP_HEADERS`], ignoring case, and `false` otherwise.
+fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP_HEADERS
+        .iter()
+        .any(|header| header.eq_ignore_ascii_case(name))
+}
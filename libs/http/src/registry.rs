@@ -0,0 +1,70 @@
+use crate::route::Route;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// [`RouteRegistration`] is the function that creates one or more [`Route`]
+/// values, which every `#[http_*]` attribute submits to the registry so
+/// [`collect_routes`] can find them without them being listed by hand.
+pub struct RouteRegistration {
+    /// [`RouteRegistration::create_routes`] creates every [`Route`] that was
+    /// registered, such as one for each method of a `#[http_route]`.
+    pub create_routes: fn() -> Vec<Route>,
+}
+
+impl RouteRegistration {
+    /// [`RouteRegistration::new`] creates a new [`RouteRegistration`] for every
+    /// [`Route`] that `create_routes` creates.
+    pub const fn new(create_routes: fn() -> Vec<Route>) -> Self {
+        RouteRegistration { create_routes }
+    }
+}
+
+inventory::collect!(RouteRegistration);
+
+/// [`collect_routes`] will create every [`Route`] that was registered by a `#[http_*]`
+/// attribute in any module or crate linked into the binary, ordered by
+/// [`RouteSpec::request_pattern`] and then [`RouteSpec::method`] so the order doesn't
+/// depend on the linker.
+/// # Example
+/// [`collect_routes`] can be used to serve every attributed handler:
+/// ```rust
+/// use http::{registry, router::Router};
+///
+/// fn get_router() -> Router {
+///     Router::new(registry::collect_routes())
+/// }
+/// ```
+pub fn collect_routes() -> Vec<Route> {
+    let mut routes: Vec<Route> = inventory::iter::<RouteRegistration>
+        .into_iter()
+        .flat_map(|registration| (registration.create_routes)())
+        .collect();
+    routes.sort_by(|a, b| {
+        a.spec
+            .request_pattern
+            .cmp(&b.spec.request_pattern)
+            .then_with(|| a.spec.method.as_str().cmp(b.spec.method.as_str()))
+    });
+    routes
+}
+
+/// [`collect_routes!`](crate::collect_routes!) will gather every [`Route`] that was
+/// registered by a `#[http_*]` attribute into a [`Vec`], as
+/// [`registry::collect_routes`](collect_routes) does.
+/// # Example
+/// [`collect_routes!`](crate::collect_routes!) can be used in place of listing every
+/// route by hand:
+/// ```rust
+/// use http::route::Route;
+///
+/// fn get_endpoints() -> Vec<Route> {
+///     http::collect_routes!()
+/// }
+/// ```
+#[macro_export]
+macro_rules! collect_routes {
+    () => {
+        $crate::registry::collect_routes()
+    };
+}
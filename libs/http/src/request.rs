@@ -1,8 +1,18 @@
 use crate::{
+    body::BodyReader,
     constants::HTTP_VERSION,
-    methods::{DELETE, GET, POST, PUT},
+    extensions::TypeMap,
+    form,
+    intern::HeaderName,
+    methods::{DELETE, GET, Method, POST, PUT},
+    multipart::{self, Multipart},
 };
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap, net::IpAddr};
+
+/// [`ClientAddress`] is the address of the client that sent a [`Request`], which
+/// the server adds to [`Request::extensions`] of every [`Request`] it routes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClientAddress(pub IpAddr);
 
 /// [`Request`] represents a web request.
 #[derive(PartialEq)]
@@ -14,17 +24,28 @@ pub struct Request {
     /// - `/some/path?querykey=queryvalue`
     /// - `/some/path#SomeAnchor`
     pub path: String,
-    /// [`Request::method`] is a [`String`] representation of the HTTP method for a [`Request`].
+    /// [`Request::method`] is the HTTP [`Method`] of a [`Request`].
     /// # Example values
-    /// - `"GET"`
-    /// - `"POST"`
-    /// - `"PUT"`
-    /// - `"DELETE"`
-    pub method: String,
+    /// - [`Method::Get`]
+    /// - [`Method::Post`]
+    /// - [`Method::Put`]
+    /// - [`Method::Delete`]
+    pub method: Method,
     /// [`Request::body_content`] is an optional [`String`] representation of any body content sent as part of a [`Request`].
     pub body_content: Option<String>,
     /// [`Request::path_params`] is a collection of parameters that are in [`Request::path`].
     pub path_params: HashMap<String, String>,
+    /// [`Request::headers`] is a collection of HTTP headers sent with a [`Request`],
+    /// keyed by their case-insensitive [`HeaderName`].
+    pub headers: HashMap<HeaderName, String>,
+    /// [`Request::body_reader`] is an optional [`BodyReader`] over a body that was
+    /// left on the connection instead of being read into [`Request::body_content`],
+    /// for a [`Route`](crate::route::Route) that streams its body.
+    pub body_reader: Option<BodyReader>,
+    /// [`Request::extensions`] is a [`TypeMap`] of typed values that were attached
+    /// to the [`Request`] before its handler runs, such as the user it was
+    /// authenticated as.
+    pub extensions: TypeMap,
 }
 
 impl Request {
@@ -32,26 +53,109 @@ impl Request {
     /// # Example
     /// [`Request::new`] can be used to create a new `GET` [`Request`] for any path:
     /// ```rust
-    /// use http::request::Request;
+    /// use http::{methods::GET, request::Request};
+    /// use std::collections::HashMap;
     ///
     /// fn create_get_request(path: &str) -> Request {
-    ///     http::request::Request::new(path, "GET", None, HashMap::new())
+    ///     http::request::Request::new(path, GET, None, HashMap::new())
     /// }
     /// ```
     pub fn new(
         path: &str,
-        method: &str,
+        method: Method,
         body_content: Option<String>,
         path_params: HashMap<String, String>,
     ) -> Self {
         Self {
             path: path.to_string(),
-            method: method.to_string(),
+            method,
             body_content,
             path_params,
+            headers: HashMap::new(),
+            body_reader: None,
+            extensions: TypeMap::new(),
         }
     }
 
+    /// [`Request::with_headers`] will set [`Request::headers`] on a [`Request`],
+    /// lowercasing every header name, and return the [`Request`].
+    /// # Example
+    /// [`Request::with_headers`] can be used to create a [`Request`] with an
+    /// `If-Match` header:
+    /// ```rust
+    /// use http::{methods::PUT, request::Request};
+    /// use std::collections::HashMap;
+    ///
+    /// fn create_conditional_request(path: &str, etag: &str) -> Request {
+    ///     Request::new(path, PUT, None, HashMap::new())
+    ///         .with_headers(HashMap::from([("If-Match".to_string(), etag.to_string())]))
+    /// }
+    /// ```
+    pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers = headers
+            .into_iter()
+            .map(|(name, value)| (HeaderName::new(&name), value))
+            .collect();
+        self
+    }
+
+    /// [`Request::with_extension`] will add `value` to [`Request::extensions`],
+    /// replacing any value of the same type, and return the [`Request`].
+    /// # Example
+    /// [`Request::with_extension`] can be used to attach the user a [`Request`] was
+    /// authenticated as:
+    /// ```rust
+    /// use http::request::Request;
+    ///
+    /// struct AuthUser(String);
+    ///
+    /// fn authenticate(request: Request, user: &str) -> Request {
+    ///     request.with_extension(AuthUser(user.to_string()))
+    /// }
+    /// ```
+    pub fn with_extension<T: Any + Send + Sync>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
+    /// [`Request::extension`] will get the value of type `T` from
+    /// [`Request::extensions`], defaults to [`None`] if nothing attached one.
+    /// # Example
+    /// [`Request::extension`] can be used to greet the user a [`Request`] was
+    /// authenticated as:
+    /// ```rust
+    /// use http::request::Request;
+    ///
+    /// struct AuthUser(String);
+    ///
+    /// fn greet(request: &Request) -> String {
+    ///     match request.extension::<AuthUser>() {
+    ///         Some(user) => format!("hello, {}", user.0),
+    ///         None => "hello, stranger".to_string(),
+    ///     }
+    /// }
+    /// ```
+    pub fn extension<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.extensions.get()
+    }
+
+    /// [`Request::header`] will get a header value by `name`, ignoring case,
+    /// defaults to [`None`] if the header is not found.
+    /// # Example
+    /// [`Request::header`] can be used to look up a header on a [`Request`]:
+    /// ```rust
+    /// use http::request::Request;
+    ///
+    /// fn get_content_type(request: &Request) -> Option<&str> {
+    ///     request.header("Content-Type")
+    /// }
+    /// ```
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&HeaderName::new(name))
+            .map(|value| value.as_str())
+    }
+
     /// [`Request::get`] creates an HTTP `GET` request header using the provided `path`.
     /// # Example
     /// [`Request::get`] can be used to create an HTTP `GET` request header for any path:
@@ -107,6 +211,47 @@ impl Request {
     pub fn delete(path: &str) -> String {
         format!("{DELETE} {path} {HTTP_VERSION}")
     }
+    /// [`Request::cookie`] will get the value of the cookie `name` from the `Cookie`
+    /// header, without the quotes around a quoted value, defaults to [`None`] if
+    /// the cookie is not found.
+    /// # Example
+    /// [`Request::cookie`] can be used to read the theme a client picked:
+    /// ```rust
+    /// use http::request::Request;
+    ///
+    /// fn get_theme(request: &Request) -> &str {
+    ///     request.cookie("theme").unwrap_or("light")
+    /// }
+    /// ```
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.header("cookie")
+            .and_then(|header| cookie_value(header, name))
+    }
+
+    /// [`Request::header_all`] will get every value of a header by `name`, ignoring
+    /// case, from a header that was repeated or sent as a comma-separated list.
+    /// Defaults to an empty [`Vec`] if the header is not found.
+    /// # Example
+    /// [`Request::header_all`] can be used to read every value of an `Accept`
+    /// header:
+    /// ```rust
+    /// use http::request::Request;
+    ///
+    /// fn get_accepted_types(request: &Request) -> Vec<&str> {
+    ///     request.header_all("Accept")
+    /// }
+    /// ```
+    pub fn header_all(&self, name: &str) -> Vec<&str> {
+        self.header(name)
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|value| value.trim())
+                    .filter(|value| !value.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 
     /// [`Request::query_param`] will get a query parameter value by `name`,
     /// defaults to `None` if the query parameter is not found.
@@ -147,6 +292,69 @@ impl Request {
         None
     }
 
+    /// [`Request::is_form`] will return `true` if the `Content-Type` of the
+    /// [`Request`] is `application/x-www-form-urlencoded` or `multipart/form-data`.
+    /// # Example
+    /// [`Request::is_form`] can be used to read a body as form fields or JSON:
+    /// ```rust
+    /// use http::request::Request;
+    ///
+    /// fn describe_body(request: &Request) -> &'static str {
+    ///     if request.is_form() { "form" } else { "json" }
+    /// }
+    /// ```
+    pub fn is_form(&self) -> bool {
+        let content_type = self.header("content-type");
+        form::is_form(content_type) || multipart::is_multipart(content_type)
+    }
+
+    /// [`Request::form_param`] will get the decoded value of the first form field
+    /// named `name` in a form-urlencoded body, or the text of the first part named
+    /// `name` in a multipart body, defaults to [`None`] if the field is not found or
+    /// the body isn't a form.
+    /// # Example
+    /// [`Request::form_param`] can be used to read a field of a login form:
+    /// ```rust
+    /// use http::request::Request;
+    ///
+    /// fn get_username(request: &Request) -> String {
+    ///     request.form_param("username").unwrap_or_default()
+    /// }
+    /// ```
+    pub fn form_param(&self, name: &str) -> Option<String> {
+        if !self.is_form() {
+            return None;
+        }
+        if let Some(multipart) = self.multipart() {
+            return multipart.part(name)?.text();
+        }
+        form::parse(self.body_content.as_deref()?)
+            .into_iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+
+    /// [`Request::multipart`] will get the files and fields of a `multipart/form-data`
+    /// body, which the server reads in place of [`Request::body_content`], defaults to
+    /// [`None`] for any other body.
+    /// # Example
+    /// [`Request::multipart`] can be used to get the names of every uploaded file:
+    /// ```rust
+    /// use http::request::Request;
+    ///
+    /// fn get_filenames(request: &Request) -> Vec<String> {
+    ///     request
+    ///         .multipart()
+    ///         .map(|multipart| {
+    ///             multipart.parts().iter().filter_map(|part| part.filename.clone()).collect()
+    ///         })
+    ///         .unwrap_or_default()
+    /// }
+    /// ```
+    pub fn multipart(&self) -> Option<&Multipart> {
+        self.extension::<Multipart>()
+    }
+
     /// [`Request::body_as_string`] will return a [`String`] representation
     /// of [`Request::body_content`].
     /// # Example
@@ -167,4 +375,40 @@ impl Request {
             .clone()
             .ok_or_else(|| "Request body not available".to_string())
     }
+
+    /// [`Request::take_body`] will take [`Request::body_reader`] out of the
+    /// [`Request`], or a [`BodyReader`] over [`Request::body_content`] when the body
+    /// was already read, so a handler can read either the same way.
+    /// # Example
+    /// [`Request::take_body`] can be used to count the lines of an upload without
+    /// holding all of it in memory:
+    /// ```rust
+    /// use http::request::Request;
+    /// use std::io::{BufRead, BufReader};
+    ///
+    /// fn count_uploaded_lines(mut request: Request) -> usize {
+    ///     BufReader::new(request.take_body()).lines().count()
+    /// }
+    /// ```
+    pub fn take_body(&mut self) -> BodyReader {
+        self.body_reader.take().unwrap_or_else(|| {
+            BodyReader::from_bytes(self.body_content.take().unwrap_or_default().into_bytes())
+        })
+    }
+}
+
+/// [`cookie_value`] will get the value of the cookie `name` from a `Cookie` header,
+/// such as `theme=dark; session_id=abc`, without the quotes around a quoted value.
+pub(crate) fn cookie_value<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    header
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(cookie_name, _)| cookie_name.trim() == name)
+        .map(|(_, value)| {
+            let value = value.trim();
+            value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value)
+        })
 }
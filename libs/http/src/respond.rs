@@ -1,3 +1,4 @@
+use crate::{json::Json, xml};
 use std::collections::HashMap;
 
 /// [`Respond`] is a trait for formatting types to repond to web server requests easily.
@@ -14,6 +15,24 @@ pub trait Respond {
     /// }
     /// ```
     fn get_json(&self) -> String;
+
+    /// [`Respond::get_xml`] gets an XML document representation of the type that
+    /// calls it, which defaults to converting [`Respond::get_json`] with
+    /// [`xml::from_json`].
+    /// # Example
+    /// [`Respond::get_xml`] can be used to return an XML response for any
+    /// implementations:
+    /// ```rust
+    /// use http::respond::Respond;
+    ///
+    /// fn return_xml_response(message: impl Respond) -> String {
+    ///     message.get_xml()
+    /// }
+    /// ```
+    fn get_xml(&self) -> String {
+        let json = self.get_json();
+        xml::from_json(&Json::parse(&json).unwrap_or(Json::String(json)))
+    }
 }
 
 /// Implement [`Respond`] for [`String`]
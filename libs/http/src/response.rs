@@ -1,18 +1,92 @@
 use crate::{
-    constants::{CONTENT_JSON, CONTENT_LENGTH, CONTENT_TYPE, HTTP_VERSION},
+    chunked::ChunkedWriter,
+    compression::Encoding,
+    constants::{
+        ALLOW, CONTENT_CSV, CONTENT_ENCODING, CONTENT_JSON, CONTENT_LENGTH, CONTENT_NDJSON,
+        CONTENT_TYPE, CONTENT_XML, HTTP_VERSION, TRANSFER_ENCODING, VARY,
+    },
+    export::{csv_row, ndjson_line},
+    header,
+    methods::{self, Method},
+    request::Request,
+    respond::Respond,
     status::Status,
+    xml,
+};
+use logger::log_warning;
+use std::{
+    borrow::Cow,
+    fmt,
+    io::{self, Write},
+    net::TcpStream,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
 };
-use std::{io::Write, net::TcpStream};
 use time::date::Date;
 
+/// [`LONG_POLL_INTERVAL`] is how long a pending [`Response::long_poll`] waits
+/// between checks, so whatever waits on it sleeps instead of spinning.
+pub const LONG_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// [`PollFn`] is the dynamic function a [`Response::long_poll`] checks, which gives
+/// back [`Some`] content once it is available.
+pub type PollFn = Box<dyn FnMut() -> Option<String> + Send + 'static>;
+
+/// [`LongPoll`] is the wait of a [`Response::long_poll`] that didn't have its
+/// content yet, which is checked again with [`Response::poll`] until its deadline.
+#[derive(Clone)]
+pub struct LongPoll {
+    /// [`LongPoll::deadline`] is when the wait gives up and is answered with
+    /// [`Status::NoContent`].
+    pub deadline: Instant,
+    /// [`LongPoll::poll_fn`] is the [`PollFn`] that is checked for the content.
+    poll_fn: Arc<Mutex<PollFn>>,
+}
+
+/// Implement [`fmt::Debug`] for [`LongPoll`]
+impl fmt::Debug for LongPoll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LongPoll")
+            .field("deadline", &self.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Implement [`PartialEq`] for [`LongPoll`]
+impl PartialEq for LongPoll {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline && Arc::ptr_eq(&self.poll_fn, &other.poll_fn)
+    }
+}
+
+/// [`StreamFn`] is the dynamic function that writes the body of a
+/// [`Response::stream`] to a [`ChunkedWriter`] as it's produced.
+pub type StreamFn =
+    Box<dyn FnOnce(&mut ChunkedWriter<&mut dyn Write>) -> io::Result<()> + Send + 'static>;
+
+/// [`BodyStream`] is the [`StreamFn`] of a [`Response::stream`], which can only be
+/// written once, by whichever clone of the [`Response`] is sent first.
+#[derive(Clone)]
+pub struct BodyStream(Arc<Mutex<Option<StreamFn>>>);
+
+/// Implement [`fmt::Debug`] for [`BodyStream`]
+impl fmt::Debug for BodyStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BodyStream")
+    }
+}
+
+/// Implement [`PartialEq`] for [`BodyStream`]
+impl PartialEq for BodyStream {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 /// [`Response`] represents a response to a web request.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Response {
-    /// [`Response::header`] is the HTTP header of a response.
-    /// # Example values
-    /// - `GET / HTTP/1.1`
-    /// - `POST /data HTTP/1.1`
-    pub header: String,
     /// [`Response::content`] is the content returned by a response.
     pub content: String,
     /// [`Response::status`] is the HTTP status of a response.
@@ -22,6 +96,24 @@ pub struct Response {
     pub status: Status,
     /// [`Response::time`] is a timestamp of when a response is served.
     pub time: Date,
+    /// [`Response::headers`] is a collection of additional HTTP headers sent
+    /// with a response, besides the content length and content type.
+    pub headers: Vec<(String, String)>,
+    /// [`Response::raw_response`] is whether [`Response::content`] is sent as-is,
+    /// instead of wrapped with the status and time.
+    pub raw_response: bool,
+    /// [`Response::omits_body`] is whether only the status line and headers are
+    /// sent, with the `Content-Length` the body would have, such as for a `HEAD`
+    /// request.
+    pub omits_body: bool,
+    /// [`Response::pending`] is the [`LongPoll`] of a [`Response::long_poll`] that
+    /// is still waiting for its content, which is [`None`] for any other
+    /// [`Response`].
+    pub pending: Option<LongPoll>,
+    /// [`Response::body_stream`] is the [`BodyStream`] of a [`Response::stream`],
+    /// which is sent with `Transfer-Encoding: chunked` instead of
+    /// [`Response::content`], and is [`None`] for any other [`Response`].
+    pub body_stream: Option<BodyStream>,
 }
 
 impl Response {
@@ -40,6 +132,380 @@ impl Response {
         Response::new(Status::Ok, contents, raw_response)
     }
 
+    /// [`Response::no_content`] represents a [`Status::NoContent`] [`Response`],
+    /// which never has a body.
+    /// # Example
+    /// [`Response::no_content`] can be used to create a [`Response`] that
+    /// has no content with [`Status::NoContent`]:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_simple_no_content_response() -> Response {
+    ///     Response::no_content()
+    /// }
+    /// ```
+    pub fn no_content() -> Self {
+        Response::new(Status::NoContent, "", true)
+    }
+
+    /// [`Response::options`] represents a [`Status::NoContent`] [`Response`] to an
+    /// `OPTIONS` request, with an `Allow` header listing the `allowed_methods`.
+    /// # Example
+    /// [`Response::options`] can be used to answer an `OPTIONS` request for a
+    /// resource that supports `GET` and `PUT`:
+    /// ```rust
+    /// use http::{
+    ///     methods::{GET, OPTIONS, PUT},
+    ///     response::Response,
+    /// };
+    ///
+    /// fn create_options_response() -> Response {
+    ///     Response::options(&[GET, PUT, OPTIONS])
+    /// }
+    /// ```
+    pub fn options(allowed_methods: &[Method]) -> Self {
+        Response::no_content().with_header(ALLOW, &methods::join(allowed_methods))
+    }
+
+    /// [`Response::csv`] represents a [`Status::Ok`] [`Response`] with every row of
+    /// `rows` as a line of CSV, sent as-is with a `text/csv` content type. Large
+    /// exports can be streamed instead with [`Response::csv_stream`].
+    /// # Example
+    /// [`Response::csv`] can be used to export users as a spreadsheet:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn export_users(users: &[(u32, String)]) -> Response {
+    ///     let rows = users.iter().map(|(id, name)| vec![id.to_string(), name.clone()]);
+    ///     Response::csv(std::iter::once(vec!["id".to_string(), "name".to_string()]).chain(rows))
+    /// }
+    /// ```
+    pub fn csv<R, T>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let content: String = rows.into_iter().map(csv_row).collect();
+        Response::ok(&content, true).with_header(CONTENT_TYPE, CONTENT_CSV)
+    }
+
+    /// [`Response::ndjson`] represents a [`Status::Ok`] [`Response`] with every
+    /// item of `items` as a line of JSON, sent as-is with an
+    /// `application/x-ndjson` content type. Large exports can be streamed instead
+    /// with [`Response::ndjson_stream`].
+    /// # Example
+    /// [`Response::ndjson`] can be used to export an audit trail one event per line:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn export_events(events: Vec<String>) -> Response {
+    ///     Response::ndjson(events)
+    /// }
+    /// ```
+    pub fn ndjson<T: Respond>(items: impl IntoIterator<Item = T>) -> Self {
+        let content: String = items.into_iter().map(|item| ndjson_line(&item)).collect();
+        Response::ok(&content, true).with_header(CONTENT_TYPE, CONTENT_NDJSON)
+    }
+
+    /// [`Response::stream`] represents a [`Response`] with `status` and
+    /// `content_type`, whose body is written by `write_body` with
+    /// `Transfer-Encoding: chunked` once the status line and headers are sent, so a
+    /// body too large to hold in memory is sent as it's produced. An [`Err`] from
+    /// `write_body` ends the connection without the final chunk, so the client can
+    /// tell the body is incomplete.
+    /// # Example
+    /// [`Response::stream`] can be used to send a report one line at a time:
+    /// ```rust
+    /// use http::{response::Response, status::Status};
+    ///
+    /// fn stream_report(lines: Vec<String>) -> Response {
+    ///     Response::stream(Status::Ok, "text/plain; charset=utf-8", move |writer| {
+    ///         for line in lines {
+    ///             writer.write_chunk(format!("{line}\n").as_bytes())?;
+    ///         }
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn stream<F>(status: Status, content_type: &str, write_body: F) -> Self
+    where
+        F: FnOnce(&mut ChunkedWriter<&mut dyn Write>) -> io::Result<()> + Send + 'static,
+    {
+        let mut response = Response::new(status, "", true);
+        response.body_stream = Some(BodyStream(Arc::new(Mutex::new(Some(Box::new(write_body))))));
+        response.with_header(CONTENT_TYPE, content_type)
+    }
+
+    /// [`Response::csv_stream`] represents a [`Status::Ok`] [`Response::stream`]
+    /// with every row of `rows` as a line of CSV, one chunk per row, with a
+    /// `text/csv` content type, so a large export is never held in memory.
+    /// # Example
+    /// [`Response::csv_stream`] can be used to stream rows read from a database
+    /// cursor:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn stream_orders(orders: impl Iterator<Item = [String; 2]> + Send + 'static) -> Response {
+    ///     let header = ["id".to_string(), "total".to_string()];
+    ///     Response::csv_stream(std::iter::once(header).chain(orders))
+    /// }
+    /// ```
+    pub fn csv_stream<I, R, T>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = R> + Send + 'static,
+        R: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        Response::stream(Status::Ok, CONTENT_CSV, move |writer| {
+            writer.write_csv(rows)
+        })
+    }
+
+    /// [`Response::ndjson_stream`] represents a [`Status::Ok`] [`Response::stream`]
+    /// with every item of `items` as a line of JSON, one chunk per item, with an
+    /// `application/x-ndjson` content type, so a large export is never held in
+    /// memory.
+    /// # Example
+    /// [`Response::ndjson_stream`] can be used to stream events as they are read:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn stream_events(events: impl Iterator<Item = String> + Send + 'static) -> Response {
+    ///     Response::ndjson_stream(events)
+    /// }
+    /// ```
+    pub fn ndjson_stream<I, T>(items: I) -> Self
+    where
+        I: IntoIterator<Item = T> + Send + 'static,
+        T: Respond,
+    {
+        Response::stream(Status::Ok, CONTENT_NDJSON, move |writer| {
+            writer.write_ndjson(items)
+        })
+    }
+
+    /// [`Response::is_streamed`] will return `true` if a [`Response`] is a
+    /// [`Response::stream`], and `false` otherwise.
+    pub fn is_streamed(&self) -> bool {
+        self.body_stream.is_some()
+    }
+
+    /// [`Response::write_body_stream`] will write the body of a
+    /// [`Response::stream`] to `writer` as chunks, once the status line and headers
+    /// from [`Response::to_bytes`] were written, followed by the final chunk. A
+    /// [`Response`] that isn't streamed, or omits its body, writes nothing, and so
+    /// does a [`Response::stream`] whose body was already written.
+    /// # Example
+    /// [`Response::write_body_stream`] can be used to send any [`Response`]:
+    /// ```rust
+    /// use http::{compression::Encoding, response::Response};
+    /// use std::{io::Write, net::TcpStream};
+    ///
+    /// fn send(response: &Response, stream: &mut TcpStream) -> std::io::Result<()> {
+    ///     stream.write_all(&response.to_bytes(&Encoding::Identity))?;
+    ///     response.write_body_stream(stream)
+    /// }
+    /// ```
+    pub fn write_body_stream(&self, writer: &mut dyn Write) -> io::Result<()> {
+        let Some(body_stream) = self.body_stream.as_ref().filter(|_| self.sends_body()) else {
+            return Ok(());
+        };
+        let Some(write_body) = body_stream.0.lock().unwrap().take() else {
+            return Ok(());
+        };
+        let mut chunked_writer = ChunkedWriter::without_head(writer);
+        write_body(&mut chunked_writer)?;
+        chunked_writer.finish(&[]).map(|_| ())
+    }
+
+    /// [`Response::buffered`] will write the body of a [`Response::stream`] into
+    /// [`Response::content`], and return a [`Response`] that isn't streamed, for
+    /// whatever needs the whole body at once, such as a batch. A [`Response`] that
+    /// isn't streamed is returned as-is.
+    pub fn buffered(mut self) -> Response {
+        let Some(body_stream) = self.body_stream.take() else {
+            return self;
+        };
+        let mut body = Vec::new();
+        if let Some(write_body) = body_stream.0.lock().unwrap().take()
+            && let Err(error) =
+                write_body(&mut ChunkedWriter::unframed(&mut body as &mut dyn Write))
+        {
+            log_warning!(
+                "can't buffer a streamed response: {error}, returning 500 INTERNAL SERVER ERROR."
+            );
+            return Response::server_error();
+        }
+        self.content = String::from_utf8_lossy(&body).to_string();
+        self
+    }
+
+    /// [`Response::xml`] represents a [`Status::Ok`] [`Response`] with the XML
+    /// document `contents`, sent as-is with an `application/xml` content type.
+    /// # Example
+    /// [`Response::xml`] can be used to answer a legacy client with any type that
+    /// implements [`Respond`]:
+    /// ```rust
+    /// use http::{respond::Respond, response::Response};
+    ///
+    /// fn create_xml_response(user: impl Respond) -> Response {
+    ///     Response::xml(&user.get_xml())
+    /// }
+    /// ```
+    pub fn xml(contents: &str) -> Self {
+        Response::ok(contents, true).with_header(CONTENT_TYPE, CONTENT_XML)
+    }
+
+    /// [`Response::negotiate`] represents a [`Status::Ok`] [`Response`] with
+    /// `value` as [`Response::xml`] when the `Accept` header of `request` prefers
+    /// XML, as [`xml::prefers_xml`] decides, or as JSON otherwise, which is
+    /// wrapped with the status and time unless `raw_response` is set. The
+    /// [`Response`] varies on `Accept`, so caches keep both.
+    /// # Example
+    /// [`Response::negotiate`] can be used to serve JSON and XML clients from one
+    /// handler:
+    /// ```rust
+    /// use http::{json::Json, request::Request, response::Response};
+    ///
+    /// fn get_user(request: &Request) -> Response {
+    ///     let user = Json::Object(vec![("name".to_string(), Json::String("evan".to_string()))]);
+    ///     Response::negotiate(&user, request, false)
+    /// }
+    /// ```
+    pub fn negotiate<T: Respond + ?Sized>(
+        value: &T,
+        request: &Request,
+        raw_response: bool,
+    ) -> Self {
+        let response = if xml::prefers_xml(request.header("accept")) {
+            Response::xml(&value.get_xml())
+        } else {
+            Response::ok(&value.get_json(), raw_response)
+        };
+        response.vary_on("Accept")
+    }
+
+    /// [`Response::long_poll`] will call `poll_fn` once, and respond with its content
+    /// as a [`Status::Ok`] [`Response`] if it's already available. Otherwise a
+    /// pending [`Response::no_content`] is returned, which keeps checking `poll_fn`
+    /// through [`Response::poll`] until it returns [`Some`] content or `timeout`
+    /// elapses.
+    ///
+    /// Nothing waits inside [`Response::long_poll`]: the server hands a pending
+    /// [`Response`] to a dedicated thread that checks every waiting request each
+    /// [`LONG_POLL_INTERVAL`], so the worker is free for other requests in the
+    /// meantime. Use [`Response::wait`] to block on it outside of the server.
+    /// # Example
+    /// [`Response::long_poll`] can be used to wait up to 30 seconds for a message:
+    /// ```rust
+    /// use http::response::Response;
+    /// use std::{
+    ///     sync::{Arc, Mutex, mpsc::Receiver},
+    ///     time::Duration,
+    /// };
+    ///
+    /// fn wait_for_message(messages: Arc<Mutex<Receiver<String>>>) -> Response {
+    ///     Response::long_poll(Duration::from_secs(30), move || {
+    ///         messages.lock().unwrap().try_recv().ok()
+    ///     })
+    /// }
+    /// ```
+    pub fn long_poll<F>(timeout: Duration, mut poll_fn: F) -> Self
+    where
+        F: FnMut() -> Option<String> + Send + 'static,
+    {
+        if let Some(content) = poll_fn() {
+            return Response::ok(&content, false);
+        }
+
+        let mut response = Response::no_content();
+        if !timeout.is_zero() {
+            response.pending = Some(LongPoll {
+                deadline: Instant::now() + timeout,
+                poll_fn: Arc::new(Mutex::new(Box::new(poll_fn))),
+            });
+        }
+        response
+    }
+
+    /// [`Response::is_pending`] will return `true` if a [`Response`] is a
+    /// [`Response::long_poll`] that is still waiting for its content, and `false`
+    /// otherwise.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// [`Response::poll`] will check a pending [`Response::long_poll`] once, and
+    /// return the [`Response`] it is answered with, which keeps every header of this
+    /// [`Response`]: a [`Status::Ok`] with the content once it's available, or a
+    /// [`Status::NoContent`] once its deadline passed. [`None`] is returned while it
+    /// is still waiting, and a [`Response`] that isn't pending is returned as-is.
+    /// # Example
+    /// [`Response::poll`] can be used to answer a long poll once it's ready:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn try_answer(response: &Response) -> Option<String> {
+    ///     response.poll().map(|answer| answer.to_string())
+    /// }
+    /// ```
+    pub fn poll(&self) -> Option<Response> {
+        let Some(pending) = &self.pending else {
+            return Some(self.clone());
+        };
+
+        let content = (pending.poll_fn.lock().unwrap())();
+        let mut answer = match content {
+            Some(content) => Response::ok(&content, false),
+            None if Instant::now() >= pending.deadline => Response::no_content(),
+            None => return None,
+        };
+        answer.headers.extend(self.headers.iter().cloned());
+        answer.omits_body = self.omits_body;
+        Some(answer)
+    }
+
+    /// [`Response::wait`] will block until a pending [`Response::long_poll`] is
+    /// answered, checking it each [`LONG_POLL_INTERVAL`], and return the answer. A
+    /// [`Response`] that isn't pending is returned right away.
+    /// # Example
+    /// [`Response::wait`] can be used to get the answer of a long poll outside of
+    /// the server, such as in a test:
+    /// ```rust
+    /// use http::response::Response;
+    /// use std::time::Duration;
+    ///
+    /// fn wait_for_nothing() -> Response {
+    ///     Response::long_poll(Duration::from_millis(50), || None).wait()
+    /// }
+    /// ```
+    pub fn wait(self) -> Response {
+        loop {
+            if let Some(answer) = self.poll() {
+                return answer;
+            }
+            if let Some(pending) = &self.pending {
+                let remaining = pending.deadline.saturating_duration_since(Instant::now());
+                thread::sleep(LONG_POLL_INTERVAL.min(remaining));
+            }
+        }
+    }
+
+    /// [`Response::forbidden`] represents a [`Status::Forbidden`] [`Response`].
+    /// # Example
+    /// [`Response::forbidden`] can be used to create a [`Response`] that returns a
+    /// simple [`String`] with [`Status::Forbidden`]:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_simple_forbidden_response() -> Response {
+    ///     Response::forbidden()
+    /// }
+    /// ```
+    pub fn forbidden() -> Self {
+        Response::new(Status::Forbidden, "\"You're not on the list\"", false)
+    }
+
     /// [`Response::not_found`] represents a [`Status::NotFound`] [`Response`].
     /// # Example
     /// [`Response::not_found`] can be used to create a [`Response`] that
@@ -74,6 +540,62 @@ impl Response {
         Response::new(Status::BadRequest, "\"Are you sure about that?\"", false)
     }
 
+    /// [`Response::precondition_failed`] represents a [`Status::PreconditionFailed`]
+    /// [`Response`].
+    /// # Example
+    /// [`Response::precondition_failed`] can be used to create a [`Response`] that
+    /// returns a simple [`String`] with [`Status::PreconditionFailed`]:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_simple_precondition_failed_response() -> Response {
+    ///     Response::precondition_failed()
+    /// }
+    /// ```
+    pub fn precondition_failed() -> Self {
+        Response::new(
+            Status::PreconditionFailed,
+            "\"Somebody got there before you\"",
+            false,
+        )
+    }
+
+    /// [`Response::payload_too_large`] represents a [`Status::PayloadTooLarge`]
+    /// [`Response`].
+    /// # Example
+    /// [`Response::payload_too_large`] can be used to create a [`Response`] that
+    /// returns a simple [`String`] with [`Status::PayloadTooLarge`]:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_simple_payload_too_large_response() -> Response {
+    ///     Response::payload_too_large()
+    /// }
+    /// ```
+    pub fn payload_too_large() -> Self {
+        Response::new(
+            Status::PayloadTooLarge,
+            "\"That's more than I can carry\"",
+            false,
+        )
+    }
+
+    /// [`Response::unsupported_media_type`] represents a
+    /// [`Status::UnsupportedMediaType`] [`Response`].
+    /// # Example
+    /// [`Response::unsupported_media_type`] can be used to create a [`Response`]
+    /// that returns a simple [`String`] with [`Status::UnsupportedMediaType`]:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_simple_unsupported_media_type_response() -> Response {
+    ///     Response::unsupported_media_type()
+    /// }
+    /// ```
+    pub fn unsupported_media_type() -> Self {
+        Response::new(Status::UnsupportedMediaType, "\"I can't read that\"", false)
+    }
+
     /// [`Response::unprocessable_entity`] represents a [`Status::UnprocessableEntity`]
     /// [`Response`].
     /// # Example
@@ -113,43 +635,269 @@ impl Response {
         )
     }
 
-    /// [`Response::server_error`] creates a [`Response`] with the provided
+    /// [`Response::service_unavailable`] represents a [`Status::ServiceUnavailable`]
+    /// [`Response`].
+    /// # Example
+    /// [`Response::service_unavailable`] can be used to create a [`Response`] that
+    /// returns a simple [`String`] with [`Status::ServiceUnavailable`]:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_simple_service_unavailable_response() -> Response {
+    ///     Response::service_unavailable()
+    /// }
+    /// ```
+    pub fn service_unavailable() -> Self {
+        Response::new(
+            Status::ServiceUnavailable,
+            "\"We're closed for maintenance, try again soon\"",
+            false,
+        )
+    }
+
+    /// [`Response::with_status`] creates a [`Response`] with the provided
+    /// [`Status`] and `contents`.
+    /// # Example
+    /// [`Response::with_status`] can be used to create a [`Status::BadRequest`]
+    /// [`Response`] with a custom message:
+    /// ```rust
+    /// use http::{response::Response, status::Status};
+    ///
+    /// fn create_custom_bad_request_response() -> Response {
+    ///     Response::with_status(Status::BadRequest, "\"missing id\"", false)
+    /// }
+    /// ```
+    pub fn with_status(status: Status, contents: &str, raw_response: bool) -> Self {
+        Response::new(status, contents, raw_response)
+    }
+
+    /// [`Response::new`] creates a [`Response`] with the provided
     /// [`Status`].
     fn new(status: Status, contents: &str, raw_response: bool) -> Self {
         Response {
             content: contents.to_string(),
             status,
             time: Date::new(),
-            header: String::new(),
+            headers: Vec::new(),
+            raw_response,
+            omits_body: false,
+            pending: None,
+            body_stream: None,
         }
-        .add_http_headers(raw_response)
     }
 
-    /// [`Response::add_http_headers`] adds [`Response::header`] information
-    /// to a [`Response`].
-    fn add_http_headers(mut self, raw_response: bool) -> Self {
-        self.header = format!(
-            "{HTTP_VERSION} {}\r\n{CONTENT_LENGTH}: {}\r\n{CONTENT_TYPE}: {CONTENT_JSON}\r\n\r\n{}",
-            self.status,
-            self.len(raw_response),
-            self.render_body(raw_response)
-        );
+    /// [`Response::with_header`] will add an HTTP header to a [`Response`], and
+    /// return the [`Response`].
+    /// # Example
+    /// [`Response::with_header`] can be used to add an `Allow` header to a
+    /// [`Response`]:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_allow_response() -> Response {
+    ///     Response::no_content().with_header("Allow", "GET, OPTIONS")
+    /// }
+    /// ```
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.push_header(name, value);
         self
     }
 
-    /// [`Response::render_body`] returns a JSON [`String`] representation of
-    /// [`Response::content`].
-    fn render_body(&self, raw_response: bool) -> String {
+    /// [`Response::with_headers`] will add every `(name, value)` pair of `headers`
+    /// to a [`Response`] as HTTP headers, in order, and return the [`Response`].
+    /// # Example
+    /// [`Response::with_headers`] can be used to keep a [`Response`] out of every
+    /// cache:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_uncached_response() -> Response {
+    ///     Response::ok("\"fresh\"", false)
+    ///         .with_headers(&[("Cache-Control", "no-store"), ("Pragma", "no-cache")])
+    /// }
+    /// ```
+    pub fn with_headers(mut self, headers: &[(&str, &str)]) -> Self {
+        for (name, value) in headers {
+            self.push_header(name, value);
+        }
+        self
+    }
+
+    /// [`Response::without_body`] will make a [`Response`] send only its status
+    /// line and headers, keeping the `Content-Length` of the body it would have
+    /// sent, as a response to a `HEAD` request does, and return the [`Response`].
+    /// # Example
+    /// [`Response::without_body`] can be used to answer a `HEAD` request with the
+    /// headers of a `GET` response:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn create_head_response() -> Response {
+    ///     Response::ok("\"hello\"", false).without_body()
+    /// }
+    /// ```
+    pub fn without_body(mut self) -> Self {
+        self.omits_body = true;
+        self
+    }
+
+    /// [`Response::vary_on`] will add `request_header` to the `Vary` header of a
+    /// [`Response`], so caches know the [`Response`] depends on it, and return the
+    /// [`Response`]. Every call is merged into a single `Vary` header without
+    /// duplicates, and `*` replaces any other value.
+    /// # Example
+    /// [`Response::vary_on`] can be used to mark a [`Response`] that was chosen
+    /// based on the `Accept` and `Accept-Encoding` request headers:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn mark_negotiated(response: Response) -> Response {
+    ///     response.vary_on("Accept").vary_on("Accept-Encoding")
+    /// }
+    /// ```
+    pub fn vary_on(self, request_header: &str) -> Self {
+        match merge_vary(self.get_header(VARY), request_header.trim()) {
+            Some(vary) => self.replace_header(VARY, &vary),
+            None => self,
+        }
+    }
+
+    /// [`Response::get_header`] will get the value of a header added with
+    /// [`Response::with_header`] by `name`, ignoring case, defaults to [`None`] if
+    /// the header is not found.
+    /// # Example
+    /// [`Response::get_header`] can be used to read the `Allow` header of a
+    /// [`Response`]:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn get_allowed_methods(response: &Response) -> Option<&str> {
+    ///     response.get_header("allow")
+    /// }
+    /// ```
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// [`Response::replace_header`] will replace the value of an existing header,
+    /// or add the header if it doesn't exist yet.
+    fn replace_header(mut self, name: &str, value: &str) -> Self {
+        if let Err(reason) = header::check_header(name, value) {
+            log_warning!("dropping response header: {reason}.");
+            return self;
+        }
+        match self
+            .headers
+            .iter_mut()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+        {
+            Some(header) => header.1 = value.to_string(),
+            None => self.headers.push((name.to_string(), value.to_string())),
+        }
+        self
+    }
+
+    /// [`Response::push_header`] will add an HTTP header to [`Response::headers`],
+    /// unless it can't be sent as one, such as a value with a line break that would
+    /// inject another header, which is dropped and logged instead.
+    fn push_header(&mut self, name: &str, value: &str) {
+        match header::check_header(name, value) {
+            Ok(()) => self.headers.push((name.to_string(), value.to_string())),
+            Err(reason) => log_warning!("dropping response header: {reason}."),
+        }
+    }
+
+    /// [`Response::sends_body`] returns whether the body of a [`Response`] is sent,
+    /// which it isn't when [`Response::omits_body`] is set or its [`Status`] never
+    /// has one.
+    fn sends_body(&self) -> bool {
+        !self.omits_body && self.status.allows_body()
+    }
+
+    /// [`Response::render_head`] returns the status line and all HTTP headers of
+    /// a [`Response`], including the blank line that separates them from the body.
+    /// The `Content-Type` is JSON unless a `Content-Type` header was added, and a
+    /// [`Response::stream`] is sent with `Transfer-Encoding: chunked` instead of a
+    /// `Content-Length`. A [`Status`] that never has a body, such as
+    /// [`Status::NoContent`], is sent without a `Content-Length` or `Content-Type`.
+    /// A body compressed with `encoding` adds `Content-Encoding` and
+    /// `Vary: Accept-Encoding` headers. A header pushed onto [`Response::headers`]
+    /// directly that can't be sent is left out.
+    fn render_head(&self, body_length: usize, encoding: Option<&Encoding>) -> String {
+        let allows_body = self.status.allows_body();
+        let current_vary = self.get_header(VARY);
+        let vary = encoding.and_then(|_| merge_vary(current_vary, "Accept-Encoding"));
+        let mut extra_headers: String = self
+            .headers
+            .iter()
+            .filter(|(name, value)| header::check_header(name, value).is_ok())
+            .filter(|(name, _)| {
+                allows_body
+                    || !(name.eq_ignore_ascii_case(CONTENT_TYPE)
+                        || name.eq_ignore_ascii_case(CONTENT_LENGTH))
+            })
+            .map(|(name, value)| match &vary {
+                Some(vary) if name.eq_ignore_ascii_case(VARY) => format!("{name}: {vary}\r\n"),
+                _ => format!("{name}: {value}\r\n"),
+            })
+            .collect();
+        if let Some(encoding) = encoding {
+            extra_headers.push_str(&format!("{CONTENT_ENCODING}: {}\r\n", encoding.as_str()));
+            if let Some(vary) = vary.filter(|_| current_vary.is_none()) {
+                extra_headers.push_str(&format!("{VARY}: {vary}\r\n"));
+            }
+        }
+        if !allows_body {
+            return format!("{HTTP_VERSION} {}\r\n{extra_headers}\r\n", self.status);
+        }
+        let content_type = match self.get_header(CONTENT_TYPE) {
+            Some(_) => String::new(),
+            None => format!("{CONTENT_TYPE}: {CONTENT_JSON}\r\n"),
+        };
+        // a streamed body isn't known up front, so it is sent in chunks instead
+        let body_framing = if self.is_streamed() {
+            format!("{TRANSFER_ENCODING}: chunked\r\n")
+        } else {
+            format!("{CONTENT_LENGTH}: {body_length}\r\n")
+        };
+        format!(
+            "{HTTP_VERSION} {}\r\n{body_framing}{content_type}{extra_headers}\r\n",
+            self.status
+        )
+    }
+
+    /// [`Response::render_body`] returns the body for [`Response::content`] when
+    /// it's sent, which borrows [`Response::content`] for a raw response, so a
+    /// changed [`Response::content`] or [`Response::status`] is always what is sent.
+    fn render_body(&self, raw_response: bool) -> Cow<'_, str> {
         if raw_response {
-            self.content.clone()
+            Cow::Borrowed(&self.content)
         } else {
-            format!(
+            Cow::Owned(format!(
                 r#"{{"content":{},"status":"{}","time":"{}"}}"#,
                 self.content, self.status, self.time.formatted
-            )
+            ))
         }
     }
 
+    /// [`Response::header`] will return the status line, HTTP headers, and body of
+    /// a [`Response`] as they are sent without compression.
+    /// # Example
+    /// [`Response::header`] can be used to log exactly what a client is sent:
+    /// ```rust
+    /// use http::response::Response;
+    ///
+    /// fn describe_response(response: &Response) -> String {
+    ///     response.header()
+    /// }
+    /// ```
+    pub fn header(&self) -> String {
+        self.to_string()
+    }
+
     /// [`Response::len`] will return the length of the JSON [`String`]
     /// representation of [`Response::content`].
     /// # Example
@@ -179,11 +927,12 @@ impl Response {
     /// }
     /// ```
     pub fn is_empty(&self, raw_response: bool) -> bool {
-        self.render_body(raw_response).len() == 0
+        self.render_body(raw_response).is_empty()
     }
 
     /// [`Response::send`] will create a well-formed HTTP result, and write that
-    /// result to the provided [`TcpStream`], then return an [`Ok`].
+    /// result to the provided [`TcpStream`], then return an [`Ok`]. The body is
+    /// left out when [`Response::omits_body`] is set, or its [`Status`] never has one.
     /// # Example
     /// [`Response::send`] can be used to send an HTTP response back to a
     /// [`TcpStream`]:
@@ -203,23 +952,90 @@ impl Response {
         let body_length = body.len();
 
         // create the status line and headers
-        let response_start = format!(
-            "{HTTP_VERSION} {}\r\n{CONTENT_LENGTH}: {}\r\n{CONTENT_TYPE}: {CONTENT_JSON}\r\n\r\n",
-            self.status, body_length
-        );
+        let response_start = self.render_head(body_length, None);
 
         // write headers and body separately
         stream.write_all(response_start.as_bytes())?;
-        stream.write_all(body.as_bytes())?;
+        if self.is_streamed() {
+            self.write_body_stream(stream)?;
+        } else if self.sends_body() {
+            stream.write_all(body.as_bytes())?;
+        }
         stream.flush()?;
 
         Ok(())
     }
+
+    /// [`Response::to_bytes`] will create a well-formed HTTP result with the body
+    /// encoded using the provided [`Encoding`], adding `Content-Encoding` and
+    /// `Vary: Accept-Encoding` headers when the body is compressed. Empty bodies,
+    /// unsupported encodings, and responses that omit their body are always sent
+    /// as-is. A [`Response::stream`] is only its status line and headers, where its
+    /// body is sent with [`Response::write_body_stream`].
+    /// # Example
+    /// [`Response::to_bytes`] can be used to send a [`Response`] compressed with the
+    /// best encoding a client accepts:
+    /// ```rust
+    /// use http::{compression, request::Request, response::Response};
+    ///
+    /// fn get_response_bytes(response: &Response, request: &Request) -> Vec<u8> {
+    ///     response.to_bytes(&compression::negotiate(request.header("accept-encoding")))
+    /// }
+    /// ```
+    pub fn to_bytes(&self, encoding: &Encoding) -> Vec<u8> {
+        let body = self.render_body(self.raw_response);
+        if !self.sends_body()
+            || *encoding == Encoding::Identity
+            || !encoding.is_supported()
+            || body.is_empty()
+        {
+            let mut bytes = self.render_head(body.len(), None).into_bytes();
+            if self.sends_body() {
+                bytes.extend_from_slice(body.as_bytes());
+            }
+            return bytes;
+        }
+
+        let encoded_body = encoding.compress(body.as_bytes());
+        let mut bytes = self
+            .render_head(encoded_body.len(), Some(encoding))
+            .into_bytes();
+        bytes.extend_from_slice(&encoded_body);
+        bytes
+    }
+}
+
+/// [`merge_vary`] returns the `Vary` header value `current` with `request_header`
+/// added, without duplicates, where `*` replaces any other value, and [`None`] if
+/// `current` already covers `request_header`.
+fn merge_vary(current: Option<&str>, request_header: &str) -> Option<String> {
+    let varied_headers: Vec<&str> = current
+        .map(|vary| vary.split(',').map(|name| name.trim()).collect())
+        .unwrap_or_default();
+
+    if varied_headers.contains(&"*")
+        || varied_headers
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(request_header))
+    {
+        return None;
+    }
+
+    if request_header == "*" || varied_headers.is_empty() {
+        Some(request_header.to_string())
+    } else {
+        Some(format!("{}, {request_header}", varied_headers.join(", ")))
+    }
 }
 
 /// Implement [`std::fmt::Display`] for [`Response`]
 impl std::fmt::Display for Response {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.header)
+        let body = self.render_body(self.raw_response);
+        f.write_str(&self.render_head(body.len(), None))?;
+        if self.sends_body() {
+            f.write_str(&body)?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,262 @@
+use crate::{
+    client::ClientResponse,
+    methods::{DELETE, GET, HEAD, Method, OPTIONS, PUT},
+};
+use std::{
+    hash::{BuildHasher, Hasher, RandomState},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// [`DEFAULT_MAX_ATTEMPTS`] is how many times a [`RetryPolicy`] sends a request in
+/// total, including the first attempt, unless [`RetryPolicy::with_max_attempts`] is
+/// used.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// [`DEFAULT_BASE_DELAY`] is how long a [`RetryPolicy`] waits before the first
+/// retry, which doubles for every retry after it, unless [`RetryPolicy::with_backoff`]
+/// is used.
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// [`DEFAULT_MAX_DELAY`] is the longest a [`RetryPolicy`] waits before any retry,
+/// unless [`RetryPolicy::with_backoff`] is used.
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// [`DEFAULT_RETRY_STATUSES`] are the response status codes a [`RetryPolicy`]
+/// retries, since they mean the upstream may succeed if asked again.
+pub const DEFAULT_RETRY_STATUSES: &[u16] = &[429, 502, 503, 504];
+
+/// [`IDEMPOTENT_METHODS`] are the HTTP methods that are safe to send more than
+/// once, which are the only methods a [`RetryPolicy`] retries.
+pub const IDEMPOTENT_METHODS: &[Method] = &[GET, HEAD, PUT, DELETE, OPTIONS];
+
+/// [`RetryPolicy`] decides whether a failed outbound request is sent again, and
+/// how long to wait before each retry, using exponential backoff with jitter.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// [`RetryPolicy::max_attempts`] is how many times a request is sent in total,
+    /// including the first attempt.
+    pub max_attempts: u32,
+    /// [`RetryPolicy::base_delay`] is how long to wait before the first retry,
+    /// which doubles for every retry after it.
+    pub base_delay: Duration,
+    /// [`RetryPolicy::max_delay`] is the longest to wait before any retry.
+    pub max_delay: Duration,
+    /// [`RetryPolicy::jitter`] is the share of each delay, from `0.0` to `1.0`, that
+    /// is randomized, so clients that failed together don't retry together.
+    pub jitter: f64,
+    /// [`RetryPolicy::retry_statuses`] are the response status codes that are
+    /// retried, besides requests that never got a response.
+    pub retry_statuses: Vec<u16>,
+    /// [`RetryPolicy::budget`] is the [`RetryBudget`] every retry is taken from, if
+    /// any.
+    pub budget: Option<Arc<RetryBudget>>,
+}
+
+/// [`RetryBudget`] caps retries to a share of the requests that were sent, so an
+/// upstream outage doesn't multiply the load on it. Every request adds a fraction
+/// of a token to the budget, and every retry takes a whole token out of it.
+#[derive(Debug)]
+pub struct RetryBudget {
+    /// [`RetryBudget::ratio`] is how many retries each request adds to the budget.
+    ratio: f64,
+    /// [`RetryBudget::max_tokens`] is the most retries the budget can hold.
+    max_tokens: f64,
+    /// [`RetryBudget::tokens`] is how many retries the budget holds right now.
+    tokens: Mutex<f64>,
+}
+
+impl RetryPolicy {
+    /// [`RetryPolicy::new`] creates a [`RetryPolicy`] with the default attempts,
+    /// backoff, full jitter, and retried statuses, without a [`RetryBudget`].
+    /// # Example
+    /// [`RetryPolicy::new`] can be used to retry a flaky upstream a few more times:
+    /// ```rust
+    /// use http::{client::Client, retry::RetryPolicy};
+    /// use std::time::Duration;
+    ///
+    /// fn create_retrying_client() -> Client {
+    ///     Client::new().with_retry_policy(
+    ///         RetryPolicy::new()
+    ///             .with_max_attempts(5)
+    ///             .with_backoff(Duration::from_millis(50), Duration::from_secs(1)),
+    ///     )
+    /// }
+    /// ```
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            jitter: 1.0,
+            retry_statuses: DEFAULT_RETRY_STATUSES.to_vec(),
+            budget: None,
+        }
+    }
+
+    /// [`RetryPolicy::with_max_attempts`] will set [`RetryPolicy::max_attempts`],
+    /// and return the [`RetryPolicy`].
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// [`RetryPolicy::with_backoff`] will set [`RetryPolicy::base_delay`] and
+    /// [`RetryPolicy::max_delay`], and return the [`RetryPolicy`].
+    pub fn with_backoff(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay.max(base_delay);
+        self
+    }
+
+    /// [`RetryPolicy::with_jitter`] will set [`RetryPolicy::jitter`], and return the
+    /// [`RetryPolicy`]. `0.0` always waits the full delay.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// [`RetryPolicy::with_retry_statuses`] will set [`RetryPolicy::retry_statuses`],
+    /// and return the [`RetryPolicy`].
+    pub fn with_retry_statuses(mut self, retry_statuses: &[u16]) -> Self {
+        self.retry_statuses = retry_statuses.to_vec();
+        self
+    }
+
+    /// [`RetryPolicy::with_budget`] will take every retry from the provided
+    /// [`RetryBudget`], and return the [`RetryPolicy`]. The [`RetryBudget`] can be
+    /// shared by every client that calls the same upstream.
+    /// # Example
+    /// [`RetryPolicy::with_budget`] can be used to retry at most one in ten requests:
+    /// ```rust
+    /// use http::retry::{RetryBudget, RetryPolicy};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_budgeted_policy() -> RetryPolicy {
+    ///     RetryPolicy::new().with_budget(Arc::new(RetryBudget::new(0.1, 10)))
+    /// }
+    /// ```
+    pub fn with_budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// [`RetryPolicy::attempts_for`] will get how many times a request with the
+    /// provided `method` may be sent, which is once for methods that are not in
+    /// [`IDEMPOTENT_METHODS`].
+    pub fn attempts_for(&self, method: &str) -> u32 {
+        if IDEMPOTENT_METHODS
+            .iter()
+            .any(|idempotent| idempotent.as_str().eq_ignore_ascii_case(method))
+        {
+            self.max_attempts
+        } else {
+            1
+        }
+    }
+
+    /// [`RetryPolicy::should_retry`] will return `true` if the `result` of an
+    /// attempt failed in a way that is worth retrying, and `false` otherwise.
+    pub fn should_retry(&self, result: &Result<ClientResponse, String>) -> bool {
+        match result {
+            Ok(response) => self.retry_statuses.contains(&response.status_code),
+            Err(_) => true,
+        }
+    }
+
+    /// [`RetryPolicy::delay_for`] will get how long to wait before the `retry`th
+    /// retry, counting from `1`, which is [`RetryPolicy::base_delay`] doubled for
+    /// every earlier retry, capped at [`RetryPolicy::max_delay`], with
+    /// [`RetryPolicy::jitter`] of it randomized.
+    /// # Example
+    /// [`RetryPolicy::delay_for`] can be used to log how long a retry will wait:
+    /// ```rust
+    /// use http::retry::RetryPolicy;
+    ///
+    /// fn describe_first_retry(policy: &RetryPolicy) -> String {
+    ///     format!("retrying in {:?}", policy.delay_for(1))
+    /// }
+    /// ```
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        delay.mul_f64(1.0 - self.jitter * random_fraction())
+    }
+
+    /// [`RetryPolicy::take_retry`] will take a retry from [`RetryPolicy::budget`],
+    /// and return `true` if one was available or there is no budget, and `false`
+    /// otherwise.
+    pub fn take_retry(&self) -> bool {
+        self.budget
+            .as_ref()
+            .is_none_or(|budget| budget.try_withdraw())
+    }
+
+    /// [`RetryPolicy::record_request`] will add the share of a retry that a request
+    /// earns to [`RetryPolicy::budget`], if there is one.
+    pub fn record_request(&self) {
+        if let Some(budget) = &self.budget {
+            budget.deposit();
+        }
+    }
+}
+
+/// Implement [`Default`] for [`RetryPolicy`].
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+impl RetryBudget {
+    /// [`RetryBudget::new`] creates a [`RetryBudget`] that earns `ratio` retries for
+    /// every request, holding at most `max_tokens` retries, and starting full so
+    /// the first failures can be retried.
+    pub fn new(ratio: f64, max_tokens: u32) -> Self {
+        RetryBudget {
+            ratio: ratio.max(0.0),
+            max_tokens: f64::from(max_tokens),
+            tokens: Mutex::new(f64::from(max_tokens)),
+        }
+    }
+
+    /// [`RetryBudget::deposit`] will add [`RetryBudget::ratio`] retries to the
+    /// budget, up to [`RetryBudget::max_tokens`].
+    pub fn deposit(&self) {
+        let mut tokens = self.lock();
+        *tokens = (*tokens + self.ratio).min(self.max_tokens);
+    }
+
+    /// [`RetryBudget::try_withdraw`] will take a single retry from the budget, and
+    /// return `true` if one was available, and `false` otherwise.
+    pub fn try_withdraw(&self) -> bool {
+        let mut tokens = self.lock();
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// [`RetryBudget::remaining`] will get how many whole retries are left in the
+    /// budget.
+    pub fn remaining(&self) -> u32 {
+        *self.lock() as u32
+    }
+
+    /// [`RetryBudget::lock`] will lock [`RetryBudget::tokens`], recovering them if a
+    /// caller panicked while holding the lock.
+    fn lock(&self) -> std::sync::MutexGuard<'_, f64> {
+        self.tokens
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// [`random_fraction`] will return a random number from `0.0` up to `1.0`, using
+/// the randomly seeded [`RandomState`] of the standard library.
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1_u64 << 53) as f64
+}
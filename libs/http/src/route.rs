@@ -1,27 +1,294 @@
 use crate::{
-    methods::{DELETE, GET, POST, PUT},
+    bulkhead::Bulkhead,
+    constants::VARY,
+    intern::{self, Symbol},
+    json::Json,
+    json_rpc::JsonRpcRegistry,
+    methods::{DELETE, GET, HEAD, Method, OPTIONS, POST, PUT},
+    percent,
     request::Request,
+    respond::Respond,
     response::Response,
+    validation::{ParamLocation, RouteParam, Violation},
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, time::Duration};
 
 /// [`RouteHandler`] is a dynamic handler function for a [`Route`],
 /// which takes a [`Request`] and gives back a [`Response`].
 pub type RouteHandler = Box<dyn Fn(Request) -> Response + Send + Sync + 'static>;
 
-/// [`Route`] represents routing information and functionality for a server.
-pub struct Route {
-    /// [`Route::method`] is a [`String`] representation of an HTTP method.
-    pub method: String,
-    /// [`Route::request_pattern`] is a [`String`] representation of a [`Request`]
-    /// path.
+/// [`VersionHandler`] is a dynamic function for a [`Route`], which takes a
+/// [`Request`] and gives back the current ETag of the resource it targets, or
+/// [`None`] if the resource doesn't exist.
+pub type VersionHandler = Box<dyn Fn(&Request) -> Option<String> + Send + Sync + 'static>;
+
+/// [`RouteSegment`] represents a single `/`-separated segment of a
+/// [`RouteSpec::request_pattern`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum RouteSegment {
+    /// [`RouteSegment::Static`] is a segment that has to match exactly, which is
+    /// interned so matching compares [`Symbol`] values.
+    Static(Symbol),
+    /// [`RouteSegment::Param`] is a dynamic segment, such as `{id}`, holding the
+    /// name of its path parameter.
+    Param(String),
+    /// [`RouteSegment::Rest`] is a catch-all final segment, such as `{*path}`,
+    /// holding the name of the path parameter that gets the rest of the path.
+    Rest(String),
+}
+
+/// [`PatternSegment`] represents a single `/`-separated segment of a route pattern
+/// that was split ahead of time, such as by the `#[http_*]` attributes, so it can
+/// be embedded in a `const` array.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PatternSegment {
+    /// [`PatternSegment::Static`] is a segment that has to match exactly.
+    Static(&'static str),
+    /// [`PatternSegment::Param`] is the name of a dynamic segment, such as `id`
+    /// for `{id}`.
+    Param(&'static str),
+    /// [`PatternSegment::Rest`] is the name of a catch-all final segment, such as
+    /// `path` for `{*path}`.
+    Rest(&'static str),
+}
+
+/// Implement [`From`] for [`RouteSegment`], interning a [`PatternSegment::Static`].
+impl From<&PatternSegment> for RouteSegment {
+    fn from(segment: &PatternSegment) -> Self {
+        match segment {
+            PatternSegment::Static(segment) => RouteSegment::Static(intern::intern(segment)),
+            PatternSegment::Param(param_name) => RouteSegment::Param(param_name.to_string()),
+            PatternSegment::Rest(param_name) => RouteSegment::Rest(param_name.to_string()),
+        }
+    }
+}
+
+/// [`RouteSpec`] is everything about a [`Route`] besides its handlers, such as its
+/// method, pattern, and parameters. It can be cloned and serialized, so tooling such
+/// as docs and client generators can work with routes without their handlers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteSpec {
+    /// [`RouteSpec::method`] is the HTTP [`Method`] the [`Route`] serves.
+    pub method: Method,
+    /// [`RouteSpec::request_pattern`] is a [`String`] representation of a
+    /// [`Request`] path.
     pub request_pattern: String,
+    /// [`RouteSpec::segments`] is every [`RouteSegment`] of
+    /// [`RouteSpec::request_pattern`], which is split once when the [`Route`] is
+    /// created.
+    pub segments: Vec<RouteSegment>,
+    /// [`RouteSpec::params`] is a collection of [`RouteParam`] that describe what
+    /// the [`Route::handler`] expects from a [`Request`].
+    pub params: Vec<RouteParam>,
+    /// [`RouteSpec::requires`] is the name of every upstream dependency, such as a
+    /// database, that has to be available for the [`Route`] to be served.
+    pub requires: Vec<String>,
+    /// [`RouteSpec::description`] is an optional description of what the [`Route`]
+    /// does, such as the doc comment of its handler.
+    pub description: Option<String>,
+    /// [`RouteSpec::body_type`] is the Rust type the body of a [`Request`] is parsed
+    /// into, such as `"User"`, [`None`] if the [`Route`] doesn't read a body.
+    pub body_type: Option<String>,
+    /// [`RouteSpec::response_type`] is the Rust type a successful [`Response`] is
+    /// the JSON of, such as `"Vec<User>"`, [`None`] if it isn't known.
+    pub response_type: Option<String>,
+    /// [`RouteSpec::response_status`] is the status code of a successful
+    /// [`Response`], such as `201`, [`None`] if it isn't known.
+    pub response_status: Option<u16>,
+    /// [`RouteSpec::body_limit`] is the largest body in bytes a [`Request`] to the
+    /// [`Route`] can send, which replaces the limit of the server when set.
+    pub body_limit: Option<usize>,
+    /// [`RouteSpec::streams_body`] is whether the body of a [`Request`] is left on
+    /// the connection for the [`Route::handler`] to read from
+    /// [`Request::body_reader`], instead of being read into
+    /// [`Request::body_content`] first.
+    pub streams_body: bool,
+    /// [`RouteSpec::allows_encoded_slashes`] is whether a path parameter can hold
+    /// a `/` that was sent percent-encoded as `%2F`, which is rejected by default
+    /// so a single segment can't smuggle in more path.
+    pub allows_encoded_slashes: bool,
+}
+
+/// [`Route`] represents routing information and functionality for a server, which
+/// binds the handlers of a [`Route`] to its [`RouteSpec`].
+pub struct Route {
+    /// [`Route::spec`] is the [`RouteSpec`] of the [`Route`], describing what it
+    /// serves.
+    pub spec: RouteSpec,
     /// [`Route::fallback_responses`] is a collection of [`Response`] for a [`Route`]
     /// to serve in various error cases.
     pub fallback_responses: Vec<Response>,
     /// [`Route::handler`] is a [`RouteHandler`] that returns the intended [`Response`]
     /// for a [`Request`].
     pub handler: RouteHandler,
+    /// [`Route::version_handler`] is an optional [`VersionHandler`] that, when set,
+    /// requires every [`Request`] to send an `If-Match` header matching the
+    /// current ETag of the resource.
+    pub version_handler: Option<VersionHandler>,
+    /// [`Route::default_headers`] is a collection of HTTP headers that are added to
+    /// every [`Response`] from the [`Route`], unless the [`Response`] already has
+    /// a header with the same name. A default `Vary` header is merged with the
+    /// `Vary` header of the [`Response`] instead.
+    pub default_headers: Vec<(String, String)>,
+    /// [`Route::bulkhead`] is an optional [`Bulkhead`] that limits how many
+    /// [`Request`] the [`Route::handler`] serves at the same time, where any other
+    /// [`Request`] is answered with a `503 SERVICE UNAVAILABLE`.
+    pub bulkhead: Option<Bulkhead>,
+}
+
+impl RouteSpec {
+    /// [`RouteSpec::matches_path`] checks if the `request_path` matches the pattern of
+    /// the [`Route`] and extracts path parameters, and if so, returns [`Some`]
+    /// [`HashMap`]. Defaults to [`None`]. A catch-all final segment, such as `{*path}`,
+    /// gets the rest of `request_path`, which can be empty. Every path parameter is
+    /// percent-decoded before it is extracted, and one with an encoded `/` doesn't
+    /// match unless [`RouteSpec::allows_encoded_slashes`] is set. A static segment
+    /// has to match as it was sent, so `/%61dmin` never matches `/admin` and slips
+    /// past anything that checks the path by its prefix.
+    /// # Example
+    /// [`RouteSpec::matches_path`] can be used to determine if a request path contains
+    /// any matches for a [`RouteSpec::request_pattern`]:
+    /// ```rust
+    /// use http::route::RouteSpec;
+    ///
+    /// fn check_endpoint_for_route_match(spec: &RouteSpec, endpoint: &str) -> bool {
+    ///     spec.matches_path(endpoint).is_some()
+    /// }
+    /// ```
+    pub fn matches_path(&self, request_path: &str) -> Option<HashMap<String, String>> {
+        let rest_param = match self.segments.last() {
+            Some(RouteSegment::Rest(param_name)) => Some(param_name),
+            _ => None,
+        };
+        let fixed_segments = self.segments.len() - usize::from(rest_param.is_some());
+
+        // must have the same number of path segments, besides a catch-all segment
+        let request_segments = request_path.split('/').count();
+        if request_segments != self.segments.len()
+            && (rest_param.is_none() || request_segments < fixed_segments)
+        {
+            return None;
+        }
+
+        let mut path_params = HashMap::new();
+        let mut request_segments = request_path.splitn(fixed_segments + 1, '/');
+
+        // iterate through segments, comparing static parts and extracting dynamic ones
+        for segment in &self.segments[..fixed_segments] {
+            let request_segment = request_segments.next()?;
+            match segment {
+                // this is a path parameter (e.g., "{id}"), which is a single segment
+                // unless the route allows encoded slashes
+                RouteSegment::Param(param_name) => {
+                    let value = percent::decode(request_segment).ok()?;
+                    if value.contains('/') && !self.allows_encoded_slashes {
+                        return None;
+                    }
+                    path_params.insert(param_name.clone(), value);
+                }
+                // a request segment that was never interned can't match any route
+                RouteSegment::Static(symbol) => {
+                    if intern::lookup(request_segment) != Some(*symbol) {
+                        return None;
+                    }
+                }
+                // a catch-all segment is only ever the last segment
+                RouteSegment::Rest(_) => return None,
+            }
+        }
+        if let Some(param_name) = rest_param {
+            let rest = request_segments.next().unwrap_or_default();
+            path_params.insert(param_name.clone(), percent::decode(rest).ok()?);
+        }
+
+        Some(path_params)
+    }
+
+    /// [`RouteSpec::example_curl`] will get a ready-to-run `curl` command for the
+    /// [`Route`] on the server at `base_url`, such as `http://localhost:8080`, with a
+    /// `<name>` placeholder for every path parameter, query parameter, and body in
+    /// [`RouteSpec::params`].
+    /// # Example
+    /// [`RouteSpec::example_curl`] can be used to show API consumers how to call a
+    /// [`Route`]:
+    /// ```rust
+    /// use http::{
+    ///     response::Response,
+    ///     route::Route,
+    ///     validation::{ParamLocation, RouteParam},
+    /// };
+    ///
+    /// fn get_user_curl() -> String {
+    ///     Route::get("/user/{id}", Box::new(|_| Response::ok("\"user\"", false)))
+    ///         .with_params(vec![RouteParam::new("id", ParamLocation::Path, "String")])
+    ///         .spec
+    ///         .example_curl("http://localhost:8080")
+    /// }
+    /// ```
+    pub fn example_curl(&self, base_url: &str) -> String {
+        let path = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                RouteSegment::Static(symbol) => symbol.as_str().to_string(),
+                RouteSegment::Param(name) | RouteSegment::Rest(name) => format!("<{name}>"),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let query = self
+            .params
+            .iter()
+            .filter(|param| param.location == ParamLocation::Query)
+            .map(|param| format!("{0}=<{0}>", param.name))
+            .collect::<Vec<_>>()
+            .join("&");
+        let mut curl = String::from("curl");
+        if self.method != GET {
+            curl.push_str(&format!(" -X {}", self.method));
+        }
+        curl.push_str(&format!(" '{}{path}", base_url.trim_end_matches('/')));
+        if !query.is_empty() {
+            curl.push('?');
+            curl.push_str(&query);
+        }
+        curl.push('\'');
+        if let Some(body) = self
+            .params
+            .iter()
+            .find(|param| param.location == ParamLocation::Body)
+        {
+            curl.push_str(&format!(" -d '<{}>'", body.name));
+        }
+        for file in self
+            .params
+            .iter()
+            .filter(|param| param.location == ParamLocation::File)
+        {
+            curl.push_str(&format!(" -F '{0}=@<{0}>'", file.name));
+        }
+        curl
+    }
+
+    /// [`RouteSpec::validate`] will check the provided [`Request`] against every
+    /// [`RouteParam`] in [`RouteSpec::params`], and return a [`Violation`] for each
+    /// parameter that is missing or can't be parsed into its expected type. The body
+    /// of a [`Route`] that streams its body is never checked, as it isn't read yet.
+    /// # Example
+    /// [`RouteSpec::validate`] can be used to check a [`Request`] before handling it:
+    /// ```rust
+    /// use http::{request::Request, route::RouteSpec};
+    ///
+    /// fn is_valid_request(spec: &RouteSpec, request: &Request) -> bool {
+    ///     spec.validate(request).is_empty()
+    /// }
+    /// ```
+    pub fn validate(&self, request: &Request) -> Vec<Violation> {
+        self.params
+            .iter()
+            .filter(|param| !(self.streams_body && param.location == ParamLocation::Body))
+            .filter_map(|param| param.validate(request))
+            .collect()
+    }
 }
 
 impl Route {
@@ -129,46 +396,376 @@ impl Route {
         Route::new(DELETE, path, handler)
     }
 
-    /// [`Route::matches_path`] checks if the `request_path` matches this route's pattern and
-    /// extracts path parameters, and if so, returns [`Some`] [`HashMap`]. Defaults to [`None`].
+    /// [`Route::head`] creates a [`Route`] that represents an HTTP `HEAD` [`Request`],
+    /// and it's coupled [`Response`], which is sent without its body but with the
+    /// `Content-Length` of it.
     /// # Example
-    /// [`Route::matches_path`] can be used to determine if a request path contains any matches
-    /// for a [`Route::request_pattern`]:
+    /// [`Route::head`] can be used to create a [`Response`] for an HTTP `HEAD` [`Request`]:
+    /// ```rust
+    /// use http::{
+    ///     response::Response,
+    ///     request::Request,
+    ///     route::Route,
+    /// };
+    ///
+    /// fn get_route_handler(_request: Request) -> Response {
+    ///     Response::ok("\"exists!\"", false)
+    /// }
+    ///
+    /// fn create_head_route(path: &str) -> Route {
+    ///     Route::head(
+    ///         path,
+    ///         (Box::new(get_route_handler) as http::route::RouteHandler),
+    ///     )
+    /// }
+    /// ```
+    pub fn head(path: &str, handler: RouteHandler) -> Self {
+        Route::new(
+            HEAD,
+            path,
+            Box::new(move |request| handler(request).without_body()),
+        )
+    }
+
+    /// [`Route::json_rpc`] creates a `POST` [`Route`] that answers JSON-RPC 2.0
+    /// requests by dispatching every call to a method in the provided `registry`.
+    /// The JSON-RPC envelope is always returned as the raw [`Response`] body, and a
+    /// body of only notifications is answered with [`Response::no_content`].
+    /// # Example
+    /// [`Route::json_rpc`] can be used to serve a registry of JSON-RPC methods:
+    /// ```rust
+    /// use http::{
+    ///     json::Json,
+    ///     json_rpc::JsonRpcRegistry,
+    ///     route::Route,
+    /// };
+    ///
+    /// fn create_json_rpc_route(path: &str) -> Route {
+    ///     let mut registry = JsonRpcRegistry::new();
+    ///     registry.register("ping", Box::new(|_| Ok(Json::String("pong".to_string()))));
+    ///     Route::json_rpc(path, registry)
+    /// }
+    /// ```
+    pub fn json_rpc(path: &str, registry: JsonRpcRegistry) -> Self {
+        Route::new(
+            POST,
+            path,
+            Box::new(move |request: Request| {
+                let body = request.body_content.unwrap_or_default();
+                match registry.handle(&body) {
+                    Some(content) => Response::ok(&content, true),
+                    None => Response::no_content(),
+                }
+            }),
+        )
+    }
+
+    /// [`Route::with_params`] will set [`RouteSpec::params`] on a [`Route`], and
+    /// return the [`Route`].
+    /// # Example
+    /// [`Route::with_params`] can be used to describe an `i32` query parameter:
+    /// ```rust
+    /// use http::{
+    ///     route::Route,
+    ///     validation::{ParamLocation, RouteParam},
+    /// };
+    ///
+    /// fn describe_route(route: Route) -> Route {
+    ///     route.with_params(vec![RouteParam::new("number", ParamLocation::Query, "i32")])
+    /// }
+    /// ```
+    pub fn with_params(mut self, params: Vec<RouteParam>) -> Self {
+        self.spec.params = params;
+        self
+    }
+
+    /// [`Route::with_requirements`] will add every name in `dependencies` to
+    /// [`RouteSpec::requires`], and return the [`Route`].
+    /// # Example
+    /// [`Route::with_requirements`] can be used to stop serving a [`Route`] while
+    /// its database is unreachable:
     /// ```rust
     /// use http::route::Route;
-    /// use std::collections::HashMap;
     ///
-    /// fn check_endpoint_for_route_match(route: Route, endpoint: &str) -> bool {
-    ///     route.matches_path(endpoint).is_some()
+    /// fn require_database(route: Route) -> Route {
+    ///     route.with_requirements(&["db"])
     /// }
     /// ```
-    pub fn matches_path(&self, request_path: &str) -> Option<HashMap<String, String>> {
-        let pattern_segments: Vec<&str> = self.request_pattern.split('/').collect();
-        let request_segments: Vec<&str> = request_path.split('/').collect();
+    pub fn with_requirements(mut self, dependencies: &[&str]) -> Self {
+        self.spec
+            .requires
+            .extend(dependencies.iter().map(|dependency| dependency.to_string()));
+        self
+    }
 
-        // must have the same number of path segments
-        if pattern_segments.len() != request_segments.len() {
-            return None;
+    /// [`Route::with_description`] will set [`RouteSpec::description`] on a [`Route`],
+    /// and return the [`Route`].
+    /// # Example
+    /// [`Route::with_description`] can be used to document a [`Route`] that wasn't
+    /// created by the http attributes:
+    /// ```rust
+    /// use http::route::Route;
+    ///
+    /// fn document_route(route: Route) -> Route {
+    ///     route.with_description("Gets a user by their id.")
+    /// }
+    /// ```
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.spec.description = Some(description.to_string());
+        self
+    }
+
+    /// [`Route::with_body_type`] will set [`RouteSpec::body_type`] on a [`Route`], and
+    /// return the [`Route`].
+    /// # Example
+    /// [`Route::with_body_type`] can be used to describe the body of a [`Route`] that
+    /// wasn't created by the http attributes:
+    /// ```rust
+    /// use http::route::Route;
+    ///
+    /// fn describe_user_body(route: Route) -> Route {
+    ///     route.with_body_type("User")
+    /// }
+    /// ```
+    pub fn with_body_type(mut self, type_name: &str) -> Self {
+        self.spec.body_type = Some(type_name.to_string());
+        self
+    }
+
+    /// [`Route::with_response`] will set [`RouteSpec::response_status`] to
+    /// `status_code` and [`RouteSpec::response_type`] to `type_name` on a [`Route`],
+    /// and return the [`Route`].
+    /// # Example
+    /// [`Route::with_response`] can be used to describe what a [`Route`] that wasn't
+    /// created by the http attributes answers with:
+    /// ```rust
+    /// use http::route::Route;
+    ///
+    /// fn describe_created_user(route: Route) -> Route {
+    ///     route.with_response(201, "User")
+    /// }
+    /// ```
+    pub fn with_response(mut self, status_code: u16, type_name: &str) -> Self {
+        self.spec.response_status = Some(status_code);
+        self.spec.response_type = Some(type_name.to_string());
+        self
+    }
+
+    /// [`Route::with_body_limit`] will set [`RouteSpec::body_limit`] on a [`Route`] to
+    /// `limit` bytes, and return the [`Route`].
+    /// # Example
+    /// [`Route::with_body_limit`] can be used to accept larger bodies on an upload
+    /// [`Route`] than anywhere else:
+    /// ```rust
+    /// use http::route::Route;
+    ///
+    /// fn allow_large_uploads(route: Route) -> Route {
+    ///     route.with_body_limit(64 * 1024 * 1024)
+    /// }
+    /// ```
+    pub fn with_body_limit(mut self, limit: usize) -> Self {
+        self.spec.body_limit = Some(limit);
+        self
+    }
+
+    /// [`Route::with_streamed_body`] will set [`RouteSpec::streams_body`] on a
+    /// [`Route`], so its handler reads the body from [`Request::take_body`] within
+    /// bounded memory, and return the [`Route`].
+    /// # Example
+    /// [`Route::with_streamed_body`] can be used to process an upload without
+    /// buffering it:
+    /// ```rust
+    /// use http::{request::Request, response::Response, route::Route};
+    ///
+    /// fn count_uploaded_bytes(mut request: Request) -> Response {
+    ///     let count = std::io::copy(&mut request.take_body(), &mut std::io::sink()).unwrap_or(0);
+    ///     Response::ok(&count.to_string(), false)
+    /// }
+    ///
+    /// fn create_upload_route() -> Route {
+    ///     Route::post("/upload", Box::new(count_uploaded_bytes)).with_streamed_body()
+    /// }
+    /// ```
+    pub fn with_streamed_body(mut self) -> Self {
+        self.spec.streams_body = true;
+        self
+    }
+
+    /// [`Route::with_concurrency_limit`] will set [`Route::bulkhead`] on a [`Route`],
+    /// so at most `max_concurrent` [`Request`] are served at the same time, and any
+    /// other [`Request`] waits up to `queue_timeout` for one of them to finish
+    /// before it is answered with a `503 SERVICE UNAVAILABLE`, and return the
+    /// [`Route`].
+    /// # Example
+    /// [`Route::with_concurrency_limit`] can be used to keep an expensive report
+    /// from taking every worker:
+    /// ```rust
+    /// use http::route::Route;
+    /// use std::time::Duration;
+    ///
+    /// fn limit_reports(route: Route) -> Route {
+    ///     route.with_concurrency_limit(2, Duration::from_millis(250))
+    /// }
+    /// ```
+    pub fn with_concurrency_limit(
+        mut self,
+        max_concurrent: usize,
+        queue_timeout: Duration,
+    ) -> Self {
+        self.bulkhead = Some(Bulkhead::new(max_concurrent).with_queue_timeout(queue_timeout));
+        self
+    }
+
+    /// [`Route::with_encoded_slashes`] will set [`RouteSpec::allows_encoded_slashes`]
+    /// on a [`Route`], so a path parameter can hold a `/` sent as `%2F`, and return
+    /// the [`Route`].
+    /// # Example
+    /// [`Route::with_encoded_slashes`] can be used for a path parameter that names
+    /// a file in a folder:
+    /// ```rust
+    /// use http::{request::Request, response::Response, route::Route};
+    ///
+    /// fn get_file(request: Request) -> Response {
+    ///     let name = request.path_params.get("name").cloned().unwrap_or_default();
+    ///     Response::ok(&format!("\"{name}\""), false)
+    /// }
+    ///
+    /// fn create_file_route() -> Route {
+    ///     Route::get("/files/{name}", Box::new(get_file)).with_encoded_slashes()
+    /// }
+    /// ```
+    pub fn with_encoded_slashes(mut self) -> Self {
+        self.spec.allows_encoded_slashes = true;
+        self
+    }
+
+    /// [`Route::with_header`] will add a default HTTP header to every [`Response`]
+    /// from the [`Route`], and return the [`Route`].
+    /// # Example
+    /// [`Route::with_header`] can be used to tag every [`Response`] with the name
+    /// of the service that served it:
+    /// ```rust
+    /// use http::route::Route;
+    ///
+    /// fn tag_route(route: Route) -> Route {
+    ///     route.with_header("X-Service", "users")
+    /// }
+    /// ```
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.default_headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// [`Route::with_headers`] will add many default HTTP headers to every
+    /// [`Response`] from the [`Route`], and return the [`Route`].
+    /// # Example
+    /// [`Route::with_headers`] can be used to tag a group of [`Route`] with the
+    /// same headers:
+    /// ```rust
+    /// use http::route::Route;
+    ///
+    /// fn tag_routes(routes: Vec<Route>) -> Vec<Route> {
+    ///     let headers = [("X-Service", "users"), ("X-Team", "identity")];
+    ///     routes
+    ///         .into_iter()
+    ///         .map(|route| route.with_headers(&headers))
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn with_headers(mut self, headers: &[(&str, &str)]) -> Self {
+        for (name, value) in headers {
+            self = self.with_header(name, value);
         }
+        self
+    }
 
-        let mut path_params = HashMap::new();
+    /// [`Route::with_if_match`] will require every [`Request`] to the [`Route`] to
+    /// send an `If-Match` header that matches the ETag given back by
+    /// `version_handler`, and return the [`Route`].
+    /// # Example
+    /// [`Route::with_if_match`] can be used to protect a `PUT` [`Route`] from lost
+    /// updates:
+    /// ```rust
+    /// use http::{request::Request, route::Route};
+    ///
+    /// fn get_current_version(_request: &Request) -> Option<String> {
+    ///     Some("\"v2\"".to_string())
+    /// }
+    ///
+    /// fn protect_route(route: Route) -> Route {
+    ///     route.with_if_match(Box::new(get_current_version))
+    /// }
+    /// ```
+    pub fn with_if_match(mut self, version_handler: VersionHandler) -> Self {
+        self.version_handler = Some(version_handler);
+        self
+    }
 
-        // iterate through segments, comparing static parts and extracting dynamic ones
-        for i in 0..pattern_segments.len() {
-            let pattern_segment = pattern_segments[i];
-            let request_segment = request_segments[i];
-
-            if pattern_segment.starts_with('{') && pattern_segment.ends_with('}') {
-                // this is a path parameter (e.g., "{id}")
-                let param_name = &pattern_segment[1..pattern_segment.len() - 1]; // Extract "id"
-                path_params.insert(param_name.to_string(), request_segment.to_string());
-            } else if pattern_segment != request_segment {
-                // static segment mismatch (e.g., "/get/" vs "/post/")
-                return None;
+    /// [`Route::check_precondition`] will return `true` if the [`Request`] satisfies
+    /// the `If-Match` precondition of the [`Route`], and `false` otherwise. A
+    /// [`Route`] without a [`Route::version_handler`] always returns `true`, and one
+    /// with a [`Route::version_handler`] returns `false` when `If-Match` is missing.
+    /// # Example
+    /// [`Route::check_precondition`] can be used to decide whether to answer with
+    /// a [`Response::precondition_failed`]:
+    /// ```rust
+    /// use http::{request::Request, response::Response, route::Route};
+    ///
+    /// fn respond(route: &Route, request: Request) -> Response {
+    ///     if route.check_precondition(&request) {
+    ///         route.get_response(request)
+    ///     } else {
+    ///         Response::precondition_failed()
+    ///     }
+    /// }
+    /// ```
+    pub fn check_precondition(&self, request: &Request) -> bool {
+        let Some(version_handler) = &self.version_handler else {
+            return true;
+        };
+        let Some(if_match) = request.header("if-match") else {
+            return false;
+        };
+        let Some(current_etag) = version_handler(request) else {
+            return false;
+        };
+
+        // weak tags never match, as If-Match requires strong comparison
+        if_match
+            .split(',')
+            .map(|etag| etag.trim())
+            .any(|etag| etag == "*" || (!etag.starts_with("W/") && etag == current_etag))
+    }
+
+    /// [`Route::allowed_methods`] will return every HTTP method implemented by the
+    /// `routes` whose [`RouteSpec::request_pattern`] matches `request_path`, in the
+    /// order they were registered. `OPTIONS` is always included when any method
+    /// matches, as the server answers it automatically.
+    /// # Example
+    /// [`Route::allowed_methods`] can be used to build an `Allow` header:
+    /// ```rust
+    /// use http::{methods, route::Route};
+    ///
+    /// fn get_allow_header(routes: &[Route], path: &str) -> String {
+    ///     methods::join(&Route::allowed_methods(routes, path))
+    /// }
+    /// ```
+    pub fn allowed_methods(routes: &[Route], request_path: &str) -> Vec<Method> {
+        let mut allowed_methods: Vec<Method> = Vec::new();
+        for route in routes
+            .iter()
+            .filter(|route| route.spec.matches_path(request_path).is_some())
+        {
+            if !allowed_methods.contains(&route.spec.method) {
+                allowed_methods.push(route.spec.method);
             }
         }
 
-        Some(path_params)
+        if !allowed_methods.is_empty() && !allowed_methods.contains(&OPTIONS) {
+            allowed_methods.push(OPTIONS);
+        }
+        allowed_methods
     }
 
     /// [`Route::get_response`] will get a [`Response`] based on the provided [`Request`].
@@ -186,16 +783,108 @@ impl Route {
     /// }
     /// ```
     pub fn get_response(&self, request: Request) -> Response {
-        (self.handler)(request)
+        // a request over the concurrency limit never reaches the handler
+        let permit = match &self.bulkhead {
+            Some(bulkhead) => match bulkhead.try_enter() {
+                Some(permit) => Some(permit),
+                None => {
+                    return Response::service_unavailable().with_header("Retry-After", "1");
+                }
+            },
+            None => None,
+        };
+        let response = (self.handler)(request);
+        drop(permit);
+        self.default_headers
+            .iter()
+            .fold(response, |response, (name, value)| {
+                if name.eq_ignore_ascii_case(VARY) {
+                    value
+                        .split(',')
+                        .fold(response, |response, varied| response.vary_on(varied))
+                } else if response.get_header(name).is_some() {
+                    response
+                } else {
+                    response.with_header(name, value)
+                }
+            })
+    }
+
+    /// [`Route::from_segments`] creates a [`Route`] for any `http_method` from a
+    /// `path` that was already split into `segments`, so the `path` is never split
+    /// again. Every [`PatternSegment`] has to come from splitting `path` on `/`.
+    /// # Example
+    /// [`Route::from_segments`] can be used to create a [`Route`] from a `const`
+    /// array of segments:
+    /// ```rust
+    /// use http::{
+    ///     methods::GET,
+    ///     response::Response,
+    ///     route::{PatternSegment, Route},
+    /// };
+    ///
+    /// const USER_SEGMENTS: &[PatternSegment] = &[
+    ///     PatternSegment::Static(""),
+    ///     PatternSegment::Static("user"),
+    ///     PatternSegment::Param("id"),
+    /// ];
+    ///
+    /// fn create_user_route() -> Route {
+    ///     Route::from_segments(GET, "/user/{id}", USER_SEGMENTS, Box::new(|_| Response::ok("\"user\"", false)))
+    /// }
+    /// ```
+    pub fn from_segments(
+        http_method: Method,
+        path: &str,
+        segments: &[PatternSegment],
+        handler: RouteHandler,
+    ) -> Self {
+        let segments = segments.iter().map(RouteSegment::from).collect();
+        Route::with_segments(http_method, path, segments, handler)
     }
 
     /// [`Route::new`] creates a new [`Route`] for any `http_method`, which uses the
     /// `path` and `handler` to determine what happens when the [`Route`] is matched
     /// by the server.
-    fn new(http_method: &str, path: &str, handler: RouteHandler) -> Self {
+    fn new(http_method: Method, path: &str, handler: RouteHandler) -> Self {
+        let segments = path
+            .split('/')
+            .map(
+                |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                    Some(param_name) => match param_name.strip_prefix('*') {
+                        Some(param_name) => RouteSegment::Rest(param_name.to_string()),
+                        None => RouteSegment::Param(param_name.to_string()),
+                    },
+                    None => RouteSegment::Static(intern::intern(segment)),
+                },
+            )
+            .collect();
+        Route::with_segments(http_method, path, segments, handler)
+    }
+
+    /// [`Route::with_segments`] creates a new [`Route`] for any `http_method`, with
+    /// `path` already split into `segments`.
+    fn with_segments(
+        http_method: Method,
+        path: &str,
+        segments: Vec<RouteSegment>,
+        handler: RouteHandler,
+    ) -> Self {
         Route {
-            request_pattern: path.to_string(),
-            method: http_method.to_string(),
+            spec: RouteSpec {
+                method: http_method,
+                request_pattern: path.to_string(),
+                segments,
+                params: Vec::new(),
+                requires: Vec::new(),
+                description: None,
+                body_type: None,
+                response_type: None,
+                response_status: None,
+                body_limit: None,
+                streams_body: false,
+                allows_encoded_slashes: false,
+            },
             handler,
             fallback_responses: vec![
                 Response::not_found(),
@@ -203,6 +892,85 @@ impl Route {
                 Response::unprocessable_entity(),
                 Response::server_error(),
             ],
+            version_handler: None,
+            default_headers: Vec::new(),
+            bulkhead: None,
         }
     }
 }
+
+/// Implement [`Respond`] for [`RouteSpec`], as a JSON object of its method, pattern,
+/// parameters, and the rest of its metadata.
+impl Respond for RouteSpec {
+    fn get_json(&self) -> String {
+        let optional_string = |value: &Option<String>| {
+            value
+                .as_ref()
+                .map_or(Json::Null, |value| Json::String(value.clone()))
+        };
+        let params = self
+            .params
+            .iter()
+            .map(|param| {
+                Json::Object(vec![
+                    ("name".to_string(), Json::String(param.name.clone())),
+                    (
+                        "location".to_string(),
+                        Json::String(param.location.to_string()),
+                    ),
+                    ("type".to_string(), Json::String(param.type_name.clone())),
+                    (
+                        "description".to_string(),
+                        optional_string(&param.description),
+                    ),
+                ])
+            })
+            .collect();
+        Json::Object(vec![
+            ("method".to_string(), Json::String(self.method.to_string())),
+            (
+                "pattern".to_string(),
+                Json::String(self.request_pattern.clone()),
+            ),
+            ("params".to_string(), Json::Array(params)),
+            (
+                "requires".to_string(),
+                Json::Array(self.requires.iter().cloned().map(Json::String).collect()),
+            ),
+            (
+                "description".to_string(),
+                optional_string(&self.description),
+            ),
+            ("body_type".to_string(), optional_string(&self.body_type)),
+            (
+                "response".to_string(),
+                Json::Object(vec![
+                    (
+                        "status".to_string(),
+                        self.response_status
+                            .map_or(Json::Null, |status| Json::Number(f64::from(status))),
+                    ),
+                    ("type".to_string(), optional_string(&self.response_type)),
+                ]),
+            ),
+            (
+                "body_limit".to_string(),
+                self.body_limit
+                    .map_or(Json::Null, |limit| Json::Number(limit as f64)),
+            ),
+            ("streams_body".to_string(), Json::Bool(self.streams_body)),
+        ])
+        .to_string()
+    }
+}
+
+/// Implement [`fmt::Debug`] for [`Route`], showing its method and pattern, with
+/// [`Route::handler`] and the rest of its fields elided.
+impl fmt::Debug for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Route")
+            .field("method", &self.spec.method)
+            .field("request_pattern", &self.spec.request_pattern)
+            .finish_non_exhaustive()
+    }
+}
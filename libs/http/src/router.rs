@@ -0,0 +1,502 @@
+use crate::{
+    extensions::TypeMap,
+    methods::Method,
+    request::Request,
+    response::Response,
+    route::{Route, RouteHandler, RouteSegment, RouteSpec},
+};
+use std::{any::Any, collections::HashMap, fmt, sync::Arc};
+
+/// [`ResponseFilter`] is a dynamic function that a [`Router`] passes every
+/// [`Response`] from a [`Route`] handler through before it is sent, such as to wrap
+/// it in an envelope or redact fields.
+pub type ResponseFilter = Box<dyn Fn(Response) -> Response + Send + Sync + 'static>;
+
+/// [`RequestPredicate`] is a dynamic function that decides if a [`ResponseFilter`]
+/// applies to a [`Request`], such as by its path or headers.
+pub type RequestPredicate = Box<dyn Fn(&Request) -> bool + Send + Sync + 'static>;
+
+/// [`FilterScope`] represents which requests a [`ResponseFilter`] of a [`Router`]
+/// applies to.
+pub enum FilterScope {
+    /// [`FilterScope::Prefix`] applies to every [`Route`] whose pattern is the
+    /// prefix or under it, where an empty prefix applies to every [`Route`].
+    Prefix(String),
+    /// [`FilterScope::Predicate`] applies to every [`Request`] the
+    /// [`RequestPredicate`] returns `true` for.
+    Predicate(RequestPredicate),
+}
+
+impl FilterScope {
+    /// [`FilterScope::applies`] will check if a [`ResponseFilter`] with this scope
+    /// applies to `request` for `route`.
+    pub fn applies(&self, route: &Route, request: &Request) -> bool {
+        match self {
+            FilterScope::Prefix(prefix) => is_under_prefix(&route.spec.request_pattern, prefix),
+            FilterScope::Predicate(predicate) => predicate(request),
+        }
+    }
+}
+
+/// [`Router`] holds every [`Route`] of a server, and resolves a request method and
+/// path to the [`Route`] that serves it. Resolving only compares the method and
+/// path, so it can be called without a connection, such as from tests that fuzz a
+/// route table with the generators in [`testing`](crate::testing).
+pub struct Router {
+    /// [`Router::routes`] is every [`Route`], in the order they are matched in,
+    /// where static segments beat parameters, which beat catch-all segments.
+    pub routes: Vec<Route>,
+    /// [`Router::filters`] is every [`ResponseFilter`] with the [`FilterScope`] of
+    /// the requests it applies to, in the order they are applied.
+    pub filters: Vec<(FilterScope, ResponseFilter)>,
+    /// [`Router::fallbacks`] is every fallback [`RouteHandler`] with the path prefix
+    /// of the requests it answers when no [`Route`] matches them.
+    pub fallbacks: Vec<(String, RouteHandler)>,
+    /// [`Router::state`] is the state every handler shares, which a handler reads
+    /// with [`Context::state`](crate::context::Context::state).
+    pub state: Arc<TypeMap>,
+}
+
+/// [`RouteConflict`] is a pair of [`Route`] values with the same method whose
+/// patterns both match some request path, such as `/user/new` and `/user/{id}`,
+/// where the more specific one serves every path they share.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteConflict {
+    /// [`RouteConflict::method`] is the method of both [`Route`] values.
+    pub method: Method,
+    /// [`RouteConflict::first`] is the pattern of the [`Route`] that is matched
+    /// first, and serves every path the patterns share.
+    pub first: String,
+    /// [`RouteConflict::second`] is the pattern of the [`Route`] that is matched
+    /// second.
+    pub second: String,
+    /// [`RouteConflict::shadowed`] is whether [`RouteConflict::first`] matches
+    /// every path [`RouteConflict::second`] does, so the second [`Route`] is never
+    /// served.
+    pub shadowed: bool,
+}
+
+impl Router {
+    /// [`Router::new`] creates a [`Router`] for `routes`, where the most specific
+    /// [`Route`] that matches a request serves it. Patterns are compared segment by
+    /// segment, where a static segment beats a parameter, which beats a catch-all,
+    /// so `/user/new` wins over `/user/{id}` regardless of the order `routes` are
+    /// in. Equally specific [`Route`] values keep the order they were registered.
+    /// # Example
+    /// [`Router::new`] can be used to find the [`Route`] for a request:
+    /// ```rust
+    /// use http::{methods::GET, response::Response, route::Route, router::Router};
+    ///
+    /// fn get_user_pattern() -> Option<String> {
+    ///     let router = Router::new(vec![Route::get(
+    ///         "/user/{id}",
+    ///         Box::new(|_| Response::ok("\"user\"", false)),
+    ///     )]);
+    ///     router
+    ///         .resolve(GET, "/user/1")
+    ///         .map(|(route, _)| route.spec.request_pattern.clone())
+    /// }
+    /// ```
+    pub fn new(mut routes: Vec<Route>) -> Self {
+        routes.sort_by_cached_key(|route| specificity(&route.spec.segments));
+        Router {
+            routes,
+            filters: Vec::new(),
+            fallbacks: Vec::new(),
+            state: Arc::new(TypeMap::new()),
+        }
+    }
+
+    /// [`Router::with_state`] will add `value` to the state every handler shares,
+    /// replacing any value of the same type, and return the [`Router`].
+    /// # Example
+    /// [`Router::with_state`] can be used to share a connection string:
+    /// ```rust
+    /// use http::router::Router;
+    ///
+    /// struct DatabaseUrl(String);
+    ///
+    /// fn with_database(router: Router) -> Router {
+    ///     router.with_state(DatabaseUrl("postgres://localhost/users".to_string()))
+    /// }
+    /// ```
+    pub fn with_state<T: Any + Send + Sync>(mut self, value: T) -> Self {
+        Arc::get_mut(&mut self.state)
+            .expect("the state of a router can't be changed once it is shared")
+            .insert(value);
+        self
+    }
+
+    /// [`Router::with_filter`] will pass the [`Response`] of every [`Route`] through
+    /// `filter`, after the filters that were added before it, and return the
+    /// [`Router`].
+    /// # Example
+    /// [`Router::with_filter`] can be used to tag every [`Response`]:
+    /// ```rust
+    /// use http::router::Router;
+    ///
+    /// fn tag_responses(router: Router) -> Router {
+    ///     router.with_filter(Box::new(|response| response.with_header("X-Service", "users")))
+    /// }
+    /// ```
+    pub fn with_filter(self, filter: ResponseFilter) -> Self {
+        self.with_filter_for("", filter)
+    }
+
+    /// [`Router::with_filter_for`] will pass the [`Response`] of every [`Route`]
+    /// whose pattern is `prefix` or under it through `filter`, after the filters
+    /// that were added before it, and return the [`Router`].
+    /// # Example
+    /// [`Router::with_filter_for`] can be used to stop caching only the `/admin`
+    /// routes:
+    /// ```rust
+    /// use http::router::Router;
+    ///
+    /// fn stop_caching_admin_responses(router: Router) -> Router {
+    ///     router.with_filter_for(
+    ///         "/admin",
+    ///         Box::new(|response| response.with_header("Cache-Control", "no-store")),
+    ///     )
+    /// }
+    /// ```
+    pub fn with_filter_for(mut self, prefix: &str, filter: ResponseFilter) -> Self {
+        self.filters.push((
+            FilterScope::Prefix(prefix.trim_end_matches('/').to_string()),
+            filter,
+        ));
+        self
+    }
+
+    /// [`Router::with_filter_only_for`] will pass the [`Response`] of every
+    /// [`Request`] that `predicate` returns `true` for through `filter`, after the
+    /// filters that were added before it, and return the [`Router`]. `predicate` is
+    /// checked before the handler runs, so it sees the [`Request`] as it was sent.
+    /// The handler still runs when `predicate` returns `true`, since `filter` only
+    /// changes the [`Response`] afterwards, so it can't keep a [`Request`] from being
+    /// handled, such as one that isn't authorized.
+    /// # Example
+    /// [`Router::with_filter_only_for`] can be used to stop caching the responses
+    /// of signed in users:
+    /// ```rust
+    /// use http::router::Router;
+    ///
+    /// fn stop_caching_private_responses(router: Router) -> Router {
+    ///     router.with_filter_only_for(
+    ///         Box::new(|request| request.header("Authorization").is_some()),
+    ///         Box::new(|response| response.with_header("Cache-Control", "private")),
+    ///     )
+    /// }
+    /// ```
+    pub fn with_filter_only_for(
+        mut self,
+        predicate: RequestPredicate,
+        filter: ResponseFilter,
+    ) -> Self {
+        self.filters
+            .push((FilterScope::Predicate(predicate), filter));
+        self
+    }
+
+    /// [`Router::with_fallback`] will answer every request whose path is `prefix` or
+    /// under it with `handler` when no [`Route`] matches it, instead of
+    /// [`Response::not_found`], and return the [`Router`]. The fallback with the
+    /// longest matching prefix answers a request, so `/api` can answer with JSON
+    /// while `/` answers everything else with an HTML page.
+    /// # Example
+    /// [`Router::with_fallback`] can be used to answer unknown pages with HTML:
+    /// ```rust
+    /// use http::{response::Response, router::Router, status::Status};
+    ///
+    /// fn with_not_found_pages(router: Router) -> Router {
+    ///     router
+    ///         .with_fallback("/api", Box::new(|_| Response::not_found()))
+    ///         .with_fallback(
+    ///             "/",
+    ///             Box::new(|_| {
+    ///                 Response::with_status(Status::NotFound, "<h1>Page not found</h1>", true)
+    ///                     .with_header("Content-Type", "text/html; charset=utf-8")
+    ///             }),
+    ///         )
+    /// }
+    /// ```
+    pub fn with_fallback(mut self, prefix: &str, handler: RouteHandler) -> Self {
+        self.fallbacks
+            .push((prefix.trim_end_matches('/').to_string(), handler));
+        self
+    }
+
+    /// [`Router::resolve_fallback`] will get the fallback [`RouteHandler`] with the
+    /// longest prefix that `request_path` is under, or [`None`] if there is none.
+    pub fn resolve_fallback(&self, request_path: &str) -> Option<&RouteHandler> {
+        let request_path = request_path.split(['?', '#']).next().unwrap_or_default();
+        self.fallbacks
+            .iter()
+            .filter(|(prefix, _)| is_under_prefix(request_path, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, handler)| handler)
+    }
+
+    /// [`Router::respond`] will get the [`Response`] of `route` to `request`, passed
+    /// through every [`ResponseFilter`] that applies to them, in the order they were
+    /// added.
+    /// # Example
+    /// [`Router::respond`] can be used to answer a request without a connection:
+    /// ```rust
+    /// use http::{methods::GET, request::Request, response::Response, router::Router};
+    /// use std::collections::HashMap;
+    ///
+    /// fn answer(router: &Router, path: &str) -> Response {
+    ///     match router.resolve(GET, path) {
+    ///         Some((route, path_params)) => {
+    ///             router.respond(route, Request::new(path, GET, None, path_params))
+    ///         }
+    ///         None => Response::not_found(),
+    ///     }
+    /// }
+    /// ```
+    pub fn respond(&self, route: &Route, request: Request) -> Response {
+        // a predicate sees the request before the handler takes it
+        let applied: Vec<&ResponseFilter> = self
+            .filters
+            .iter()
+            .filter(|(scope, _)| scope.applies(route, &request))
+            .map(|(_, filter)| filter)
+            .collect();
+        applied
+            .into_iter()
+            .fold(route.get_response(request), |response, filter| {
+                filter(response)
+            })
+    }
+
+    /// [`Router::resolve`] will get the most specific [`Route`] for `method` whose
+    /// pattern matches `request_path`, along with the path parameters it extracted, or
+    /// [`None`] if no [`Route`] matches.
+    pub fn resolve(
+        &self,
+        method: Method,
+        request_path: &str,
+    ) -> Option<(&Route, HashMap<String, String>)> {
+        self.routes
+            .iter()
+            .filter(|route| route.spec.method == method)
+            .find_map(|route| {
+                route
+                    .spec
+                    .matches_path(request_path)
+                    .map(|path_params| (route, path_params))
+            })
+    }
+
+    /// [`Router::conflicts`] will get a [`RouteConflict`] for every pair of
+    /// [`Route`] values with the same method whose patterns both match some request
+    /// path, in the order they are matched in. The server logs them when it starts,
+    /// as warnings when a [`Route`] is shadowed and never served.
+    /// # Example
+    /// [`Router::conflicts`] can be used to check a route table for ambiguity:
+    /// ```rust
+    /// use http::{response::Response, route::Route, router::Router};
+    ///
+    /// fn has_ambiguous_routes() -> bool {
+    ///     let router = Router::new(vec![
+    ///         Route::get("/user/{id}", Box::new(|_| Response::ok("\"user\"", false))),
+    ///         Route::get("/user/new", Box::new(|_| Response::ok("\"new\"", false))),
+    ///     ]);
+    ///     !router.conflicts().is_empty()
+    /// }
+    /// ```
+    pub fn conflicts(&self) -> Vec<RouteConflict> {
+        let mut conflicts = Vec::new();
+        for (index, first) in self.routes.iter().enumerate() {
+            for second in self.routes[index + 1..]
+                .iter()
+                .filter(|route| route.spec.method == first.spec.method)
+            {
+                if patterns_overlap(&first.spec.segments, &second.spec.segments) {
+                    conflicts.push(RouteConflict {
+                        method: first.spec.method,
+                        first: first.spec.request_pattern.clone(),
+                        second: second.spec.request_pattern.clone(),
+                        shadowed: pattern_covers(&first.spec.segments, &second.spec.segments),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// [`Router::allowed_methods`] will get every method with a [`Route`] that
+    /// matches `request_path`, as [`Route::allowed_methods`] does.
+    pub fn allowed_methods(&self, request_path: &str) -> Vec<Method> {
+        Route::allowed_methods(&self.routes, request_path)
+    }
+
+    /// [`Router::specs`] will get a clone of the [`RouteSpec`] of every [`Route`],
+    /// in the order they are matched in, for tooling that doesn't need handlers.
+    /// # Example
+    /// [`Router::specs`] can be used to export a Postman collection of a server:
+    /// ```rust
+    /// use http::{json::Json, postman, router::Router};
+    ///
+    /// fn export_server(router: &Router) -> Json {
+    ///     postman::export_collection(&router.specs(), "Server", "http://localhost:8080")
+    /// }
+    /// ```
+    pub fn specs(&self) -> Vec<RouteSpec> {
+        self.routes.iter().map(|route| route.spec.clone()).collect()
+    }
+}
+
+/// Implement [`fmt::Debug`] for [`Router`], showing every [`Route`] and the scope
+/// of every [`ResponseFilter`].
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let filter_prefixes: Vec<&str> = self
+            .filters
+            .iter()
+            .map(|(scope, _)| match scope {
+                FilterScope::Prefix(prefix) => prefix.as_str(),
+                FilterScope::Predicate(_) => "<predicate>",
+            })
+            .collect();
+        f.debug_struct("Router")
+            .field("routes", &self.routes)
+            .field("filters", &filter_prefixes)
+            .field(
+                "fallbacks",
+                &self
+                    .fallbacks
+                    .iter()
+                    .map(|(prefix, _)| prefix.as_str())
+                    .collect::<Vec<&str>>(),
+            )
+            .field("state", &self.state.len())
+            .finish()
+    }
+}
+
+/// Implement [`fmt::Display`] for [`Router`], as a table of every [`Route`] in the
+/// order they are matched in, with the description of each when it has one.
+impl fmt::Display for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pattern_width = self
+            .routes
+            .iter()
+            .map(|route| route.spec.request_pattern.len())
+            .chain(["PATTERN".len()])
+            .max()
+            .unwrap_or_default();
+        write!(
+            f,
+            "{:<7}  {:<pattern_width$}  DESCRIPTION",
+            "METHOD", "PATTERN"
+        )?;
+        for route in &self.routes {
+            write!(
+                f,
+                "\n{:<7}  {:<pattern_width$}  {}",
+                route.spec.method.as_str(),
+                route.spec.request_pattern,
+                route
+                    .spec
+                    .description
+                    .as_deref()
+                    .and_then(|description| description.lines().next())
+                    .unwrap_or("-")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Implement [`fmt::Display`] for [`RouteConflict`].
+impl fmt::Display for RouteConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.shadowed {
+            write!(
+                f,
+                "{} {} is shadowed by {} {}, so it is never served",
+                self.method, self.second, self.method, self.first
+            )
+        } else {
+            write!(
+                f,
+                "{} {} and {} {} both match some paths, which are served by {}",
+                self.method, self.first, self.method, self.second, self.first
+            )
+        }
+    }
+}
+
+/// [`specificity`] will rank every segment of a pattern, where a lower rank is
+/// more specific, so sorting patterns by it puts static segments before parameters
+/// and parameters before catch-all segments, from the first segment on.
+fn specificity(segments: &[RouteSegment]) -> Vec<u8> {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            RouteSegment::Static(_) => 0,
+            RouteSegment::Param(_) => 1,
+            RouteSegment::Rest(_) => 2,
+        })
+        .collect()
+}
+
+/// [`split_rest`] will split `segments` into the segments that match exactly one
+/// path segment, and whether they end with a [`RouteSegment::Rest`].
+fn split_rest(segments: &[RouteSegment]) -> (&[RouteSegment], bool) {
+    match segments.split_last() {
+        Some((RouteSegment::Rest(_), fixed)) => (fixed, true),
+        _ => (segments, false),
+    }
+}
+
+/// [`patterns_overlap`] will return `true` if some request path matches both the
+/// `first` and `second` segments of a pattern.
+fn patterns_overlap(first: &[RouteSegment], second: &[RouteSegment]) -> bool {
+    let (first, first_rest) = split_rest(first);
+    let (second, second_rest) = split_rest(second);
+    let lengths_overlap = match (first_rest, second_rest) {
+        (false, false) => first.len() == second.len(),
+        (true, false) => second.len() >= first.len(),
+        (false, true) => first.len() >= second.len(),
+        (true, true) => true,
+    };
+    // only two different static segments can't match the same path segment
+    lengths_overlap
+        && first
+            .iter()
+            .zip(second)
+            .all(|segment_pair| match segment_pair {
+                (RouteSegment::Static(first), RouteSegment::Static(second)) => first == second,
+                _ => true,
+            })
+}
+
+/// [`pattern_covers`] will return `true` if every request path that matches the
+/// `second` segments of a pattern also matches the `first` segments.
+fn pattern_covers(first: &[RouteSegment], second: &[RouteSegment]) -> bool {
+    let (first, first_rest) = split_rest(first);
+    let (second, second_rest) = split_rest(second);
+    let lengths_covered = match (first_rest, second_rest) {
+        (false, false) => first.len() == second.len(),
+        (false, true) => false,
+        (true, _) => second.len() >= first.len(),
+    };
+    lengths_covered
+        && first
+            .iter()
+            .zip(second)
+            .all(|segment_pair| match segment_pair {
+                (RouteSegment::Static(first), RouteSegment::Static(second)) => first == second,
+                (RouteSegment::Static(_), _) => false,
+                _ => true,
+            })
+}
+
+/// [`is_under_prefix`] will check if `path` is `prefix` or under it, one whole
+/// segment at a time, so `/apis` is not under `/api`.
+fn is_under_prefix(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
@@ -0,0 +1,242 @@
+use crate::{
+    route::{RouteSegment, RouteSpec},
+    validation::{ParamLocation, RouteParam},
+};
+use std::fmt::Write;
+
+/// [`generate_client`] will generate the source of a Rust module with a `client_name`
+/// struct that has a function for every [`RouteSpec`] in `routes`, which calls it with
+/// the [`Client`](crate::client::Client) of this crate, so callers don't have to build
+/// paths by hand. Functions take path parameters, query parameters, and the body from
+/// [`RouteSpec::params`], in that order, with `&str` for `String` parameters, `&[u8]`
+/// for a streamed body, and the recorded type for every other parameter.
+/// # Example
+/// [`generate_client`] can be used to write a client module for a service:
+/// ```rust
+/// use http::{response::Response, route::Route, sdk};
+///
+/// fn write_user_client() -> std::io::Result<()> {
+///     let routes = vec![Route::get("/user/{id}", Box::new(|_| Response::ok("\"user\"", false))).spec];
+///     std::fs::write("user_client.rs", sdk::generate_client(&routes, "UserClient"))
+/// }
+/// ```
+pub fn generate_client(routes: &[RouteSpec], client_name: &str) -> String {
+    let mut source = format!(
+        "// Generated by `http::sdk::generate_client`, edits will be overwritten.
+use http::client::{{Client, ClientRequest, ClientResponse}};
+
+/// [`{client_name}`] calls every route of the server at [`{client_name}::base_url`].
+pub struct {client_name} {{
+    /// [`{client_name}::base_url`] is where the server is, such as `http://localhost:8080`.
+    pub base_url: String,
+    /// [`{client_name}::client`] is the [`Client`] every call is sent with.
+    pub client: Client,
+}}
+
+impl {client_name} {{
+    /// [`{client_name}::new`] creates a new [`{client_name}`] for the server at `base_url`.
+    pub fn new(base_url: &str, client: Client) -> Self {{
+        {client_name} {{
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }}
+    }}
+"
+    );
+    let mut function_names: Vec<String> = Vec::new();
+    for route in routes {
+        let mut function_name = function_name(route);
+        if function_names.contains(&function_name) {
+            function_name = format!("{function_name}_{}", function_names.len());
+        }
+        write_function(&mut source, route, &function_name);
+        function_names.push(function_name);
+    }
+    source.push_str(
+        "}
+
+/// [`encode`] will percent-encode every character of `value` that isn't allowed in a
+/// path segment or query value as-is.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!(\"%{byte:02X}\"),
+        })
+        .collect()
+}
+",
+    );
+    source
+}
+
+/// [`write_function`] will write the function named `function_name` that calls
+/// `route` to `source`.
+fn write_function(source: &mut String, route: &RouteSpec, function_name: &str) {
+    // a catch-all parameter keeps its slashes, so each of its segments is encoded
+    let path_params: Vec<(RouteParam, bool)> = route
+        .segments
+        .iter()
+        .filter_map(|segment| match segment {
+            RouteSegment::Param(name) => Some((path_param(route, name), false)),
+            RouteSegment::Rest(name) => Some((path_param(route, name), true)),
+            RouteSegment::Static(_) => None,
+        })
+        .collect();
+    let query_params: Vec<&RouteParam> = route
+        .params
+        .iter()
+        .filter(|param| param.location == ParamLocation::Query)
+        .collect();
+    let body_param = route
+        .params
+        .iter()
+        .find(|param| param.location == ParamLocation::Body);
+
+    let arguments: String = path_params
+        .iter()
+        .map(|(param, _)| param)
+        .chain(query_params.iter().copied())
+        .map(|param| format!(", {}: {}", param.name, argument_type(param)))
+        .chain(body_param.map(|param| {
+            // a streamed body can be anything, so it is sent as bytes
+            if route.streams_body {
+                format!(", {}: &[u8]", param.name)
+            } else {
+                format!(", {}: {}", param.name, argument_type(param))
+            }
+        }))
+        .collect();
+    let path: String = route
+        .segments
+        .iter()
+        .map(|segment| match segment {
+            RouteSegment::Static(symbol) => symbol.as_str().to_string(),
+            RouteSegment::Param(_) | RouteSegment::Rest(_) => "{}".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    let query: String = query_params
+        .iter()
+        .enumerate()
+        .map(|(index, param)| format!("{}{}={{}}", if index == 0 { "?" } else { "&" }, param.name))
+        .collect();
+    let format_arguments: String = path_params
+        .iter()
+        .map(|(param, is_rest)| {
+            if *is_rest {
+                format!(
+                    ", {}.split('/').map(encode).collect::<Vec<_>>().join(\"/\")",
+                    value_expression(param)
+                )
+            } else {
+                format!(", encode({})", borrowed_value_expression(param))
+            }
+        })
+        .chain(
+            query_params
+                .iter()
+                .map(|param| format!(", encode({})", borrowed_value_expression(param))),
+        )
+        .collect();
+    let body = body_param
+        .map(|param| {
+            if route.streams_body {
+                format!(".with_body({})", param.name)
+            } else {
+                format!(".with_body({}.as_bytes())", value_expression(param))
+            }
+        })
+        .unwrap_or_default();
+
+    let _ = write!(
+        source,
+        "
+    /// [`Self::{function_name}`] calls `{} {}`.
+    pub fn {function_name}(&self{arguments}) -> Result<ClientResponse, String> {{
+        let url = format!(\"{{}}{path}{query}\", self.base_url{format_arguments});
+        self.client.send(&ClientRequest::new(\"{}\", &url){body})
+    }}
+",
+        route.method, route.request_pattern, route.method
+    );
+}
+
+/// [`path_param`] will get the [`RouteParam`] of `route` for the path parameter
+/// `name`, or a `String` one if the route doesn't describe it.
+fn path_param(route: &RouteSpec, name: &str) -> RouteParam {
+    route
+        .params
+        .iter()
+        .find(|param| param.name == name)
+        .cloned()
+        .unwrap_or_else(|| RouteParam::new(name, ParamLocation::Path, "String"))
+}
+
+/// [`function_name`] will get the name of the function for `route`, from its method
+/// and pattern, such as `get_user_by_id` for `GET /user/{id}`.
+fn function_name(route: &RouteSpec) -> String {
+    let mut name = route.method.as_str().to_lowercase();
+    for segment in &route.segments {
+        match segment {
+            RouteSegment::Static(symbol) if !symbol.as_str().is_empty() => {
+                name.push('_');
+                name.push_str(&to_identifier(symbol.as_str()));
+            }
+            RouteSegment::Param(param_name) | RouteSegment::Rest(param_name) => {
+                name.push_str("_by_");
+                name.push_str(&to_identifier(param_name));
+            }
+            RouteSegment::Static(_) => {}
+        }
+    }
+    if !name.contains('_') {
+        name.push_str("_root");
+    }
+    name
+}
+
+/// [`to_identifier`] will turn `segment` into a lowercase Rust identifier, such as
+/// `user_settings` for `user-settings`.
+fn to_identifier(segment: &str) -> String {
+    segment
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// [`argument_type`] will get the type of the function argument for `param`, which
+/// is `&str` for a `String`.
+fn argument_type(param: &RouteParam) -> &str {
+    match param.type_name.as_str() {
+        "String" | "&str" => "&str",
+        type_name => type_name,
+    }
+}
+
+/// [`value_expression`] will get the expression that turns the function argument
+/// for `param` into a `&str`.
+fn value_expression(param: &RouteParam) -> String {
+    match argument_type(param) {
+        "&str" => param.name.clone(),
+        _ => format!("{}.to_string()", param.name),
+    }
+}
+
+/// [`borrowed_value_expression`] will get the expression that borrows the function
+/// argument for `param` as a `&str`.
+fn borrowed_value_expression(param: &RouteParam) -> String {
+    match argument_type(param) {
+        "&str" => param.name.clone(),
+        _ => format!("&{}.to_string()", param.name),
+    }
+}
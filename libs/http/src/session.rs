@@ -0,0 +1,950 @@
+use crate::{
+    constants::SET_COOKIE, json::Json, request::cookie_value, response::Response, tenant::TenantId,
+};
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// [`SESSION_COOKIE`] is the name of the cookie that holds the id of a [`Session`].
+pub const SESSION_COOKIE: &str = "session_id";
+
+/// [`TCP_STORE_TIMEOUT`] is how long a [`TcpStore`] waits to connect, and for each
+/// read or write.
+pub const TCP_STORE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// [`SESSION_CLEANUP_INTERVAL`] is how often expired sessions are removed from a
+/// [`SessionStore`] that doesn't expire them itself.
+pub const SESSION_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// [`SessionData`] is every value stored in a [`Session`], by its key.
+pub type SessionData = HashMap<String, String>;
+
+/// [`StoredSession`] is what a [`SessionStore`] keeps for a [`Session`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredSession {
+    /// [`StoredSession::data`] is every value of the [`Session`].
+    pub data: SessionData,
+    /// [`StoredSession::expires_at`] is when the [`Session`] expires, or [`None`]
+    /// if it never does.
+    pub expires_at: Option<SystemTime>,
+    /// [`StoredSession::flash`] is every value flashed with [`Session::flash`],
+    /// which the next request can read once.
+    pub flash: SessionData,
+}
+
+/// [`SessionStore`] is where the [`SessionData`] of every [`Session`] is kept
+/// between requests, by the id of the [`Session`].
+pub trait SessionStore: Send + Sync {
+    /// [`SessionStore::load`] will get the [`StoredSession`] for `id`, or [`None`]
+    /// if nothing is stored for it or it has expired.
+    fn load(&self, id: &str) -> Result<Option<StoredSession>, String>;
+
+    /// [`SessionStore::save`] will store `session` for `id`, replacing anything
+    /// that was stored for it.
+    fn save(&self, id: &str, session: &StoredSession) -> Result<(), String>;
+
+    /// [`SessionStore::remove`] will remove anything stored for `id`.
+    fn remove(&self, id: &str) -> Result<(), String>;
+
+    /// [`SessionStore::remove_expired`] will remove every expired session, and
+    /// return how many were removed. A store that expires sessions itself, such
+    /// as a [`TcpStore`], has nothing to remove.
+    fn remove_expired(&self) -> Result<usize, String> {
+        Ok(0)
+    }
+}
+
+/// [`SessionBackend`] is which [`SessionStore`] the server keeps sessions in, which
+/// is parsed from `"memory"`, `"file:<directory>"`, or `"tcp:<host>:<port>"`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SessionBackend {
+    /// [`SessionBackend::Memory`] keeps sessions in a [`MemoryStore`], so they are
+    /// lost when the server restarts.
+    Memory,
+    /// [`SessionBackend::File`] keeps sessions in a [`FileStore`] in the
+    /// directory.
+    File(PathBuf),
+    /// [`SessionBackend::Tcp`] keeps sessions in a [`TcpStore`] at the address,
+    /// such as a memcached server.
+    Tcp(String),
+}
+
+/// [`MemoryStore`] is a [`SessionStore`] that keeps sessions in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    /// [`MemoryStore::sessions`] is the [`StoredSession`] of every session, by its
+    /// id.
+    sessions: Mutex<HashMap<String, StoredSession>>,
+}
+
+/// [`FileStore`] is a [`SessionStore`] that keeps each session in a file of its own,
+/// named after its id, so sessions survive a restart of the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileStore {
+    /// [`FileStore::directory`] is the directory every session file is in.
+    pub directory: PathBuf,
+}
+
+/// [`TcpStore`] is a [`SessionStore`] that keeps sessions in a key-value server
+/// that speaks the memcached text protocol, so sessions survive a restart of the
+/// server and are shared between every instance of it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TcpStore {
+    /// [`TcpStore::address`] is the address of the key-value server, such as
+    /// `127.0.0.1:11211`.
+    pub address: String,
+}
+
+/// [`Session`] is the values a server keeps for one client between requests,
+/// which the server adds to [`Request::extensions`](crate::request::Request::extensions)
+/// of every request, and saves after the handler when they changed. Clones of a
+/// [`Session`] share its values.
+#[derive(Clone, Debug)]
+pub struct Session {
+    /// [`Session::id`] is the id of the [`Session`], which is sent to the client in
+    /// the [`SESSION_COOKIE`] cookie.
+    id: String,
+    /// [`Session::is_new`] is whether the client didn't send an id of a stored
+    /// [`Session`].
+    is_new: bool,
+    /// [`Session::expires_at`] is when the stored [`Session`] expires, or [`None`]
+    /// if it never does or was never stored.
+    expires_at: Option<SystemTime>,
+    /// [`Session::state`] is the values of the [`Session`], and whether they
+    /// changed.
+    state: Arc<Mutex<SessionState>>,
+}
+
+/// [`SessionState`] is the values of a [`Session`], and whether they changed since
+/// it was loaded.
+#[derive(Debug, Default)]
+struct SessionState {
+    /// [`SessionState::data`] is every value of the [`Session`], by its key.
+    data: SessionData,
+    /// [`SessionState::modified`] is whether [`SessionState::data`] or a flashed
+    /// value changed since the [`Session`] was loaded.
+    modified: bool,
+    /// [`SessionState::flashed`] is every value flashed by the previous request,
+    /// which are removed as they are read.
+    flashed: SessionData,
+    /// [`SessionState::flash`] is every value flashed by this request, for the
+    /// next one.
+    flash: SessionData,
+}
+
+/// [`Sessions`] loads the [`Session`] of every request from a [`SessionStore`] by
+/// its cookie, and saves it once the request has been served.
+pub struct Sessions {
+    /// [`Sessions::store`] is the [`SessionStore`] every [`Session`] is kept in.
+    store: Box<dyn SessionStore>,
+    /// [`Sessions::ttl`] is how long a [`Session`] lives once it is created, or
+    /// renewed with [`Sessions::sliding`], or [`None`] if it never expires.
+    pub ttl: Option<Duration>,
+    /// [`Sessions::sliding`] is whether every request with a [`Session`] renews it
+    /// for another [`Sessions::ttl`].
+    pub sliding: bool,
+    /// [`Sessions::secure`] is whether the session cookie has the `Secure`
+    /// attribute, so clients only send it over HTTPS.
+    pub secure: bool,
+}
+
+impl SessionBackend {
+    /// [`SessionBackend::open`] will create the [`SessionStore`] for the
+    /// [`SessionBackend`], creating the directory of a [`SessionBackend::File`]
+    /// when it doesn't exist.
+    /// # Example
+    /// [`SessionBackend::open`] can be used to keep sessions in a directory:
+    /// ```rust
+    /// use http::session::{SessionBackend, SessionStore};
+    ///
+    /// fn open_session_files() -> Result<Box<dyn SessionStore>, String> {
+    ///     "file:sessions".parse::<SessionBackend>()?.open()
+    /// }
+    /// ```
+    pub fn open(&self) -> Result<Box<dyn SessionStore>, String> {
+        Ok(match self {
+            SessionBackend::Memory => Box::new(MemoryStore::new()),
+            SessionBackend::File(directory) => Box::new(FileStore::new(directory.clone())?),
+            SessionBackend::Tcp(address) => Box::new(TcpStore::new(address)),
+        })
+    }
+}
+
+impl MemoryStore {
+    /// [`MemoryStore::new`] creates an empty [`MemoryStore`].
+    pub fn new() -> Self {
+        MemoryStore::default()
+    }
+}
+
+impl FileStore {
+    /// [`FileStore::new`] creates a [`FileStore`] that keeps sessions in
+    /// `directory`, creating it when it doesn't exist.
+    pub fn new(directory: PathBuf) -> Result<Self, String> {
+        fs::create_dir_all(&directory).map_err(|error| {
+            format!(
+                "can't create session directory {}: {error}",
+                directory.display()
+            )
+        })?;
+        Ok(FileStore { directory })
+    }
+
+    /// [`FileStore::path`] will get the path of the file for the session `id`.
+    fn path(&self, id: &str) -> PathBuf {
+        self.directory.join(format!("{id}.json"))
+    }
+}
+
+impl TcpStore {
+    /// [`TcpStore::new`] creates a [`TcpStore`] for the key-value server at
+    /// `address`, which is connected to for every call.
+    pub fn new(address: &str) -> Self {
+        TcpStore {
+            address: address.to_string(),
+        }
+    }
+
+    /// [`TcpStore::call`] will send `command` to the key-value server and return a
+    /// reader over the reply.
+    fn call(&self, command: &[u8]) -> Result<BufReader<TcpStream>, String> {
+        // the address can be a host name, such as the name of a container
+        let address = self
+            .address
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| format!("can't resolve session store address {}", self.address))?;
+        let mut stream = TcpStream::connect_timeout(&address, TCP_STORE_TIMEOUT)
+            .map_err(|error| format!("can't connect to session store {}: {error}", self.address))?;
+        stream
+            .set_read_timeout(Some(TCP_STORE_TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(TCP_STORE_TIMEOUT)))
+            .and_then(|_| stream.write_all(command))
+            .map_err(|error| format!("can't write to session store {}: {error}", self.address))?;
+        Ok(BufReader::new(stream))
+    }
+}
+
+impl StoredSession {
+    /// [`StoredSession::is_expired`] will check if the [`StoredSession`] has
+    /// expired at `now`.
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+impl Session {
+    /// [`Session::new`] creates an empty [`Session`] with a new random id.
+    pub fn new() -> Self {
+        Session {
+            id: new_random_id(),
+            is_new: true,
+            expires_at: None,
+            state: Arc::new(Mutex::new(SessionState::default())),
+        }
+    }
+
+    /// [`Session::with_data`] creates a [`Session`] for `id` that was loaded with
+    /// `data`.
+    pub fn with_data(id: &str, data: SessionData) -> Self {
+        Session {
+            id: id.to_string(),
+            is_new: false,
+            expires_at: None,
+            state: Arc::new(Mutex::new(SessionState {
+                data,
+                ..SessionState::default()
+            })),
+        }
+    }
+
+    /// [`Session::with_flashed`] will set the values the previous request flashed,
+    /// which this request can read once with [`Session::take_flash`], and return
+    /// the [`Session`]. The [`Session`] is modified when there are any, since they
+    /// are gone once this request has been served.
+    pub fn with_flashed(self, flashed: SessionData) -> Self {
+        {
+            let mut state = self.lock();
+            state.modified |= !flashed.is_empty();
+            state.flashed = flashed;
+        }
+        self
+    }
+
+    /// [`Session::with_expiry`] will set when the stored [`Session`] expires, and
+    /// return the [`Session`].
+    pub fn with_expiry(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// [`Session::expires_at`] will get when the stored [`Session`] expires, or
+    /// [`None`] if it never does or was never stored.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// [`Session::id`] will get the id of the [`Session`].
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// [`Session::is_new`] will check if the [`Session`] was created for this
+    /// request, rather than loaded.
+    pub fn is_new(&self) -> bool {
+        self.is_new
+    }
+
+    /// [`Session::get`] will get the value for `key`, or [`None`] if the
+    /// [`Session`] has no value for it.
+    /// # Example
+    /// [`Session::get`] can be used to read who is signed in:
+    /// ```rust
+    /// use http::session::Session;
+    ///
+    /// fn get_signed_in_user(session: &Session) -> String {
+    ///     session.get("user").unwrap_or_else(|| "guest".to_string())
+    /// }
+    /// ```
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.lock().data.get(key).cloned()
+    }
+
+    /// [`Session::insert`] will set the value for `key` to `value`, returning the
+    /// value it replaced.
+    /// # Example
+    /// [`Session::insert`] can be used to remember who signed in:
+    /// ```rust
+    /// use http::session::Session;
+    ///
+    /// fn sign_in(session: &Session, user: &str) {
+    ///     session.insert("user", user);
+    /// }
+    /// ```
+    pub fn insert(&self, key: &str, value: &str) -> Option<String> {
+        let mut state = self.lock();
+        state.modified = true;
+        state.data.insert(key.to_string(), value.to_string())
+    }
+
+    /// [`Session::remove`] will remove the value for `key`, returning it.
+    pub fn remove(&self, key: &str) -> Option<String> {
+        let mut state = self.lock();
+        let removed = state.data.remove(key);
+        state.modified |= removed.is_some();
+        removed
+    }
+
+    /// [`Session::clear`] will remove every value of the [`Session`], such as when
+    /// signing out.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.modified |= !state.data.is_empty();
+        state.data.clear();
+    }
+
+    /// [`Session::flash`] will set `key` to `value` for the next request only,
+    /// which can read it once with [`Session::take_flash`], such as a message to
+    /// show after redirecting from a form.
+    /// # Example
+    /// [`Session::flash`] can be used to confirm a form after redirecting from it:
+    /// ```rust
+    /// use http::{response::Response, session::Session};
+    ///
+    /// fn save_profile(session: &Session) -> Response {
+    ///     session.flash("notice", "profile saved");
+    ///     Response::no_content().with_header("Location", "/profile")
+    /// }
+    /// ```
+    pub fn flash(&self, key: &str, value: &str) {
+        let mut state = self.lock();
+        state.modified = true;
+        state.flash.insert(key.to_string(), value.to_string());
+    }
+
+    /// [`Session::take_flash`] will get the value the previous request flashed for
+    /// `key` with [`Session::flash`], which can only be read once.
+    /// # Example
+    /// [`Session::take_flash`] can be used to show a message once after a redirect:
+    /// ```rust
+    /// use http::session::Session;
+    ///
+    /// fn show_profile(session: &Session) -> String {
+    ///     match session.take_flash("notice") {
+    ///         Some(notice) => format!("<p>{notice}</p>"),
+    ///         None => String::new(),
+    ///     }
+    /// }
+    /// ```
+    pub fn take_flash(&self, key: &str) -> Option<String> {
+        self.lock().flashed.remove(key)
+    }
+
+    /// [`Session::is_modified`] will check if any value of the [`Session`] changed
+    /// since it was loaded.
+    pub fn is_modified(&self) -> bool {
+        self.lock().modified
+    }
+
+    /// [`Session::data`] will get a copy of every value of the [`Session`].
+    pub fn data(&self) -> SessionData {
+        self.lock().data.clone()
+    }
+
+    /// [`Session::flashed_for_next_request`] will get a copy of every value this
+    /// request flashed.
+    fn flashed_for_next_request(&self) -> SessionData {
+        self.lock().flash.clone()
+    }
+
+    /// [`Session::lock`] will lock the [`SessionState`] of the [`Session`].
+    fn lock(&self) -> std::sync::MutexGuard<'_, SessionState> {
+        self.state
+            .lock()
+            .expect("session lock should never be poisoned")
+    }
+}
+
+impl Sessions {
+    /// [`Sessions::new`] creates [`Sessions`] that are kept in `store`.
+    /// # Example
+    /// [`Sessions::new`] can be used to keep sessions in memory:
+    /// ```rust
+    /// use http::session::{MemoryStore, Sessions};
+    ///
+    /// fn create_sessions() -> Sessions {
+    ///     Sessions::new(Box::new(MemoryStore::new()))
+    /// }
+    /// ```
+    pub fn new(store: Box<dyn SessionStore>) -> Self {
+        Sessions {
+            store,
+            ttl: None,
+            sliding: false,
+            secure: false,
+        }
+    }
+
+    /// [`Sessions::with_ttl`] will make every [`Session`] expire `ttl` after it is
+    /// created, and return the [`Sessions`].
+    /// # Example
+    /// [`Sessions::with_ttl`] can be used to sign clients out after a day:
+    /// ```rust
+    /// use http::session::{MemoryStore, Sessions};
+    /// use std::time::Duration;
+    ///
+    /// fn create_daily_sessions() -> Sessions {
+    ///     Sessions::new(Box::new(MemoryStore::new())).with_ttl(Duration::from_secs(86_400))
+    /// }
+    /// ```
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// [`Sessions::with_sliding_renewal`] will make every request with a
+    /// [`Session`] renew it for another [`Sessions::ttl`], so only idle sessions
+    /// expire, and return the [`Sessions`].
+    /// # Example
+    /// [`Sessions::with_sliding_renewal`] can be used to sign clients out after
+    /// half an hour without a request:
+    /// ```rust
+    /// use http::session::{MemoryStore, Sessions};
+    /// use std::time::Duration;
+    ///
+    /// fn create_idle_sessions() -> Sessions {
+    ///     Sessions::new(Box::new(MemoryStore::new()))
+    ///         .with_ttl(Duration::from_secs(1_800))
+    ///         .with_sliding_renewal()
+    /// }
+    /// ```
+    pub fn with_sliding_renewal(mut self) -> Self {
+        self.sliding = true;
+        self
+    }
+
+    /// [`Sessions::with_secure_cookie`] will add the `Secure` attribute to the
+    /// session cookie, so clients only send it over HTTPS, and return the
+    /// [`Sessions`].
+    /// # Example
+    /// [`Sessions::with_secure_cookie`] can be used to keep session ids off of
+    /// plain HTTP when the server is behind a TLS proxy:
+    /// ```rust
+    /// use http::session::{MemoryStore, Sessions};
+    ///
+    /// fn create_secure_sessions() -> Sessions {
+    ///     Sessions::new(Box::new(MemoryStore::new())).with_secure_cookie()
+    /// }
+    /// ```
+    pub fn with_secure_cookie(mut self) -> Self {
+        self.secure = true;
+        self
+    }
+
+    /// [`Sessions::remove_expired`] will remove every expired [`Session`] from the
+    /// store, and return how many were removed.
+    pub fn remove_expired(&self) -> Result<usize, String> {
+        self.store.remove_expired()
+    }
+
+    /// [`Sessions::load`] will load the [`Session`] whose id is in the
+    /// [`SESSION_COOKIE`] cookie of `cookie_header`, or create a new [`Session`]
+    /// when there is no such cookie, nothing is stored for its id, or the store
+    /// fails.
+    pub fn load(&self, cookie_header: Option<&str>) -> Result<Session, String> {
+        self.load_for_tenant(cookie_header, None)
+    }
+
+    /// [`Sessions::load_for_tenant`] will load the [`Session`] of `tenant` as
+    /// [`Sessions::load`] does, where the sessions of every tenant are kept apart,
+    /// so a session id of one tenant is a new [`Session`] for any other.
+    pub fn load_for_tenant(
+        &self,
+        cookie_header: Option<&str>,
+        tenant: Option<&TenantId>,
+    ) -> Result<Session, String> {
+        let Some(id) = cookie_header
+            .and_then(|header| cookie_value(header, SESSION_COOKIE))
+            .filter(|id| is_session_id(id))
+        else {
+            return Ok(Session::new());
+        };
+        Ok(match self.store.load(&store_key(id, tenant))? {
+            Some(StoredSession {
+                data,
+                expires_at,
+                flash,
+            }) => {
+                let session = Session::with_data(id, data).with_flashed(flash);
+                match expires_at {
+                    Some(expires_at) => session.with_expiry(expires_at),
+                    None => session,
+                }
+            }
+            None => Session::new(),
+        })
+    }
+
+    /// [`Sessions::persist`] will save `session` when it was modified or is
+    /// renewed, removing it from the store once it has no values, and return
+    /// `response` with a `Set-Cookie` header whenever the cookie has to change,
+    /// whose `Max-Age` matches when the [`Session`] expires.
+    pub fn persist(&self, session: &Session, response: Response) -> Result<Response, String> {
+        self.persist_for_tenant(session, None, response)
+    }
+
+    /// [`Sessions::persist_for_tenant`] will save `session` of `tenant` as
+    /// [`Sessions::persist`] does, apart from the sessions of every other tenant.
+    pub fn persist_for_tenant(
+        &self,
+        session: &Session,
+        tenant: Option<&TenantId>,
+        response: Response,
+    ) -> Result<Response, String> {
+        let key = store_key(session.id(), tenant);
+        let renewed = self.sliding && self.ttl.is_some() && !session.is_new();
+        if !session.is_modified() && !renewed {
+            return Ok(response);
+        }
+        let data = session.data();
+        let flash = session.flashed_for_next_request();
+        if data.is_empty() && flash.is_empty() {
+            if session.is_new() {
+                return Ok(response);
+            }
+            // the client forgets a session that no longer exists
+            self.store.remove(&key)?;
+            return Ok(response.with_header(
+                SET_COOKIE,
+                &session_cookie(session.id(), Some(Duration::ZERO), self.secure),
+            ));
+        }
+        let (expires_at, cookie) = if session.is_new() || renewed {
+            (
+                self.ttl.map(|ttl| SystemTime::now() + ttl),
+                Some(session_cookie(session.id(), self.ttl, self.secure)),
+            )
+        } else {
+            (session.expires_at(), None)
+        };
+        self.store.save(
+            &key,
+            &StoredSession {
+                data,
+                expires_at,
+                flash,
+            },
+        )?;
+        Ok(match cookie {
+            Some(cookie) => response.with_header(SET_COOKIE, &cookie),
+            None => response,
+        })
+    }
+}
+
+/// Implement [`Default`] for [`Session`], which is an empty new [`Session`].
+impl Default for Session {
+    fn default() -> Self {
+        Session::new()
+    }
+}
+
+/// Implement [`SessionStore`] for [`MemoryStore`].
+impl SessionStore for MemoryStore {
+    fn load(&self, id: &str) -> Result<Option<StoredSession>, String> {
+        Ok(self
+            .sessions
+            .lock()
+            .expect("session store lock should never be poisoned")
+            .get(id)
+            .filter(|session| !session.is_expired(SystemTime::now()))
+            .cloned())
+    }
+
+    fn save(&self, id: &str, session: &StoredSession) -> Result<(), String> {
+        self.sessions
+            .lock()
+            .expect("session store lock should never be poisoned")
+            .insert(id.to_string(), session.clone());
+        Ok(())
+    }
+
+    fn remove(&self, id: &str) -> Result<(), String> {
+        self.sessions
+            .lock()
+            .expect("session store lock should never be poisoned")
+            .remove(id);
+        Ok(())
+    }
+
+    fn remove_expired(&self) -> Result<usize, String> {
+        let now = SystemTime::now();
+        let mut sessions = self
+            .sessions
+            .lock()
+            .expect("session store lock should never be poisoned");
+        let count = sessions.len();
+        sessions.retain(|_, session| !session.is_expired(now));
+        Ok(count - sessions.len())
+    }
+}
+
+/// Implement [`SessionStore`] for [`FileStore`], where every session is a JSON
+/// object in a file of its own.
+impl SessionStore for FileStore {
+    fn load(&self, id: &str) -> Result<Option<StoredSession>, String> {
+        let session = match fs::read_to_string(self.path(id)) {
+            Ok(content) => decode_stored_session(&content)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(format!("can't read session {id}: {error}")),
+        };
+        if session.is_expired(SystemTime::now()) {
+            self.remove(id)?;
+            return Ok(None);
+        }
+        Ok(Some(session))
+    }
+
+    fn save(&self, id: &str, session: &StoredSession) -> Result<(), String> {
+        // write a temporary file first, so a session is never read half-written
+        let temporary_path = self.directory.join(format!("{id}.json.tmp"));
+        fs::write(&temporary_path, encode_stored_session(session))
+            .and_then(|_| fs::rename(&temporary_path, self.path(id)))
+            .map_err(|error| format!("can't write session {id}: {error}"))
+    }
+
+    fn remove(&self, id: &str) -> Result<(), String> {
+        match fs::remove_file(self.path(id)) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => {
+                Err(format!("can't remove session {id}: {error}"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn remove_expired(&self) -> Result<usize, String> {
+        let entries = fs::read_dir(&self.directory).map_err(|error| {
+            format!(
+                "can't read session directory {}: {error}",
+                self.directory.display()
+            )
+        })?;
+        let now = SystemTime::now();
+        let mut removed = 0;
+        for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            let Some(id) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+                .filter(|key| is_store_key(key))
+            else {
+                continue;
+            };
+            // a session that can't be read is left for load to report
+            let expired = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| decode_stored_session(&content).ok())
+                .is_some_and(|session| session.is_expired(now));
+            if expired {
+                self.remove(id)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Implement [`SessionStore`] for [`TcpStore`], with the `get`, `set`, and
+/// `delete` commands of the memcached text protocol, where the key-value server
+/// expires sessions itself.
+impl SessionStore for TcpStore {
+    fn load(&self, id: &str) -> Result<Option<StoredSession>, String> {
+        let mut reader = self.call(format!("get session:{id}\r\n").as_bytes())?;
+        let header = read_reply_line(&mut reader)?;
+        if header == "END" {
+            return Ok(None);
+        }
+        // e.g., "VALUE session:<id> 0 42"
+        let length = header
+            .strip_prefix("VALUE ")
+            .and_then(|value| value.split(' ').nth(2))
+            .and_then(|length| length.parse::<usize>().ok())
+            .ok_or_else(|| format!("unexpected session store reply: {header}"))?;
+        let mut value = vec![0; length + 2];
+        reader
+            .read_exact(&mut value)
+            .map_err(|error| format!("can't read session {id}: {error}"))?;
+        value.truncate(length);
+        let content =
+            String::from_utf8(value).map_err(|_| format!("session {id} is not valid UTF-8"))?;
+        let session = decode_stored_session(&content)?;
+        Ok((!session.is_expired(SystemTime::now())).then_some(session))
+    }
+
+    fn save(&self, id: &str, session: &StoredSession) -> Result<(), String> {
+        let content = encode_stored_session(session);
+        // an expiry time is a unix timestamp, and 0 never expires
+        let expiry = session.expires_at.map_or(0, unix_seconds);
+        let mut reader = self.call(
+            format!(
+                "set session:{id} 0 {expiry} {}\r\n{content}\r\n",
+                content.len()
+            )
+            .as_bytes(),
+        )?;
+        match read_reply_line(&mut reader)?.as_str() {
+            "STORED" => Ok(()),
+            reply => Err(format!("session store didn't store session {id}: {reply}")),
+        }
+    }
+
+    fn remove(&self, id: &str) -> Result<(), String> {
+        let mut reader = self.call(format!("delete session:{id}\r\n").as_bytes())?;
+        match read_reply_line(&mut reader)?.as_str() {
+            "DELETED" | "NOT_FOUND" => Ok(()),
+            reply => Err(format!("session store didn't remove session {id}: {reply}")),
+        }
+    }
+}
+
+/// Implement [`FromStr`] for [`SessionBackend`].
+impl FromStr for SessionBackend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            None if value == "memory" => Ok(SessionBackend::Memory),
+            Some(("file", directory)) if !directory.is_empty() => {
+                Ok(SessionBackend::File(PathBuf::from(directory)))
+            }
+            Some(("tcp", address)) if !address.is_empty() => {
+                Ok(SessionBackend::Tcp(address.to_string()))
+            }
+            _ => Err(format!(
+                "unknown session store '{value}', expected \"memory\", \"file:<directory>\", or \"tcp:<host>:<port>\""
+            )),
+        }
+    }
+}
+
+/// Implement [`fmt::Display`] for [`SessionBackend`], in the form it is parsed from.
+impl fmt::Display for SessionBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionBackend::Memory => write!(f, "memory"),
+            SessionBackend::File(directory) => write!(f, "file:{}", directory.display()),
+            SessionBackend::Tcp(address) => write!(f, "tcp:{address}"),
+        }
+    }
+}
+
+/// [`store_key`] will get the key the session `id` is stored by, partitioned for
+/// `tenant` when there is one.
+fn store_key(id: &str, tenant: Option<&TenantId>) -> String {
+    match tenant {
+        Some(tenant) => tenant.scope(id),
+        None => id.to_string(),
+    }
+}
+
+/// [`new_random_id`] will create a random id of 32 hex digits, such as a session
+/// id, from the random number generator of the operating system, so the ids can't
+/// be guessed from one another.
+pub(crate) fn new_random_id() -> String {
+    let mut bytes = [0; 16];
+    fill_random(&mut bytes)
+        .expect("the random number generator of the operating system should be readable");
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// [`fill_random`] will fill `bytes` from `/dev/urandom`.
+#[cfg(unix)]
+fn fill_random(bytes: &mut [u8]) -> io::Result<()> {
+    fs::File::open("/dev/urandom")?.read_exact(bytes)
+}
+
+/// [`fill_random`] will fill `bytes` from the system preferred random number
+/// generator of `BCryptGenRandom`.
+#[cfg(windows)]
+fn fill_random(bytes: &mut [u8]) -> io::Result<()> {
+    /// [`BCRYPT_USE_SYSTEM_PREFERRED_RNG`] lets `BCryptGenRandom` pick the random
+    /// number generator without an algorithm handle.
+    const BCRYPT_USE_SYSTEM_PREFERRED_RNG: u32 = 0x2;
+
+    #[link(name = "bcrypt")]
+    unsafe extern "system" {
+        fn BCryptGenRandom(
+            algorithm: *mut std::ffi::c_void,
+            buffer: *mut u8,
+            length: u32,
+            flags: u32,
+        ) -> i32;
+    }
+
+    for chunk in bytes.chunks_mut(u32::MAX as usize) {
+        // SAFETY: `chunk` is valid for writes of its whole length
+        let status = unsafe {
+            BCryptGenRandom(
+                std::ptr::null_mut(),
+                chunk.as_mut_ptr(),
+                chunk.len() as u32,
+                BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+            )
+        };
+        if status < 0 {
+            return Err(io::Error::other(format!(
+                "BCryptGenRandom failed with status {status:#x}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// [`is_session_id`] will check if `id` could have been created by
+/// [`new_random_id`], so an id from a client never names a file outside of a
+/// [`FileStore`], or breaks a command of a [`TcpStore`].
+fn is_session_id(id: &str) -> bool {
+    id.len() == 32 && id.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// [`is_store_key`] will check if `key` is a session id, partitioned for a tenant
+/// or not, as [`store_key`] creates it.
+fn is_store_key(key: &str) -> bool {
+    match key.split_once('.') {
+        Some((tenant, id)) => TenantId::new(tenant).is_some() && is_session_id(id),
+        None => is_session_id(key),
+    }
+}
+
+/// [`session_cookie`] will get the `Set-Cookie` value for the session `id`, which
+/// the client keeps for `max_age`, or until it closes when it is [`None`], and
+/// only sends over HTTPS when `secure` is `true`.
+fn session_cookie(id: &str, max_age: Option<Duration>, secure: bool) -> String {
+    let max_age = max_age
+        .map(|max_age| format!("; Max-Age={}", max_age.as_secs()))
+        .unwrap_or_default();
+    let secure = if secure { "; Secure" } else { "" };
+    format!("{SESSION_COOKIE}={id}; Path=/{max_age}; HttpOnly; SameSite=Lax{secure}")
+}
+
+/// [`unix_seconds`] will get the seconds from the unix epoch to `time`.
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// [`encode_stored_session`] will encode `session` as a JSON object, such as
+/// `{"expires_at":1700000000,"data":{"user":"evan"},"flash":{}}`.
+fn encode_stored_session(session: &StoredSession) -> String {
+    let expires_at = session.expires_at.map_or(Json::Null, |expires_at| {
+        Json::Number(unix_seconds(expires_at) as f64)
+    });
+    Json::Object(vec![
+        ("expires_at".to_string(), expires_at),
+        ("data".to_string(), encode_session_data(&session.data)),
+        ("flash".to_string(), encode_session_data(&session.flash)),
+    ])
+    .to_string()
+}
+
+/// [`encode_session_data`] will encode `data` as a JSON object of strings.
+fn encode_session_data(data: &SessionData) -> Json {
+    Json::Object(
+        data.iter()
+            .map(|(key, value)| (key.clone(), Json::String(value.clone())))
+            .collect(),
+    )
+}
+
+/// [`decode_stored_session`] will decode the JSON object `content` into a
+/// [`StoredSession`], where a missing `flash` object has no flashed values.
+fn decode_stored_session(content: &str) -> Result<StoredSession, String> {
+    let json = Json::parse(content)?;
+    let data = decode_session_data(json.get("data"))?;
+    let flash = match json.get("flash") {
+        None => SessionData::new(),
+        flash => decode_session_data(flash)?,
+    };
+    let expires_at = json
+        .get("expires_at")
+        .and_then(Json::as_f64)
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds as u64));
+    Ok(StoredSession {
+        data,
+        expires_at,
+        flash,
+    })
+}
+
+/// [`decode_session_data`] will decode a JSON object of strings into
+/// [`SessionData`].
+fn decode_session_data(json: Option<&Json>) -> Result<SessionData, String> {
+    match json {
+        Some(Json::Object(members)) => members
+            .iter()
+            .map(|(key, value)| match value {
+                Json::String(value) => Ok((key.clone(), value.clone())),
+                _ => Err(format!("session value for '{key}' is not a string")),
+            })
+            .collect(),
+        _ => Err("session data is not a JSON object".to_string()),
+    }
+}
+
+/// [`read_reply_line`] will read one line of a reply from a [`TcpStore`], without
+/// its line ending.
+fn read_reply_line(reader: &mut impl BufRead) -> Result<String, String> {
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .map_err(|error| format!("can't read from session store: {error}"))?;
+    Ok(line.trim_end().to_string())
+}
@@ -0,0 +1,138 @@
+/// [`BLOCK_BYTES`] is how many bytes SHA-256 compresses at a time, which is also
+/// the key size of HMAC-SHA256.
+const BLOCK_BYTES: usize = 64;
+
+/// [`INITIAL_STATE`] is the starting state of every SHA-256 hash, from FIPS 180-4.
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// [`ROUND_CONSTANTS`] are the constants of the 64 rounds of SHA-256, from
+/// FIPS 180-4.
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// [`hash`] will get the SHA-256 digest of `data`.
+/// # Example
+/// [`hash`] can be used to fingerprint a request body:
+/// ```rust
+/// use http::sha256;
+///
+/// fn fingerprint(body: &str) -> [u8; 32] {
+///     sha256::hash(body.as_bytes())
+/// }
+/// ```
+pub fn hash(data: &[u8]) -> [u8; 32] {
+    hash_parts(&[data])
+}
+
+/// [`hmac`] will get the HMAC-SHA256 of `data` with `key`, as defined by RFC 2104.
+/// # Example
+/// [`hmac`] can be used to sign a message with a shared secret:
+/// ```rust
+/// use http::sha256;
+///
+/// fn sign(message: &str, secret: &[u8]) -> [u8; 32] {
+///     sha256::hmac(message.as_bytes(), secret)
+/// }
+/// ```
+pub fn hmac(data: &[u8], key: &[u8]) -> [u8; 32] {
+    // a key longer than a block is hashed first, and a shorter one is padded
+    let mut block_key = [0; BLOCK_BYTES];
+    if key.len() > BLOCK_BYTES {
+        block_key[..32].copy_from_slice(&hash(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+    let inner_key = block_key.map(|byte| byte ^ 0x36);
+    let outer_key = block_key.map(|byte| byte ^ 0x5c);
+    let inner_hash = hash_parts(&[&inner_key, data]);
+    hash_parts(&[&outer_key, &inner_hash])
+}
+
+/// [`hash_parts`] will get the SHA-256 digest of every part of `parts` joined
+/// together, without copying them into one buffer.
+fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+    let mut state = INITIAL_STATE;
+    let mut block = [0; BLOCK_BYTES];
+    let mut block_len = 0;
+    let mut total_len: u64 = 0;
+    for part in parts {
+        total_len += part.len() as u64;
+        for &byte in *part {
+            block[block_len] = byte;
+            block_len += 1;
+            if block_len == BLOCK_BYTES {
+                compress(&mut state, &block);
+                block_len = 0;
+            }
+        }
+    }
+
+    // the message ends with a single set bit, zeros, and its length in bits
+    block[block_len] = 0x80;
+    block[block_len + 1..].fill(0);
+    if block_len + 1 > BLOCK_BYTES - 8 {
+        compress(&mut state, &block);
+        block.fill(0);
+    }
+    block[BLOCK_BYTES - 8..].copy_from_slice(&(total_len * 8).to_be_bytes());
+    compress(&mut state, &block);
+
+    let mut digest = [0; 32];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// [`compress`] will mix a single `block` into `state`.
+fn compress(state: &mut [u32; 8], block: &[u8; BLOCK_BYTES]) {
+    let mut schedule = [0u32; 64];
+    for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for index in 16..64 {
+        let previous = schedule[index - 2];
+        let earlier = schedule[index - 15];
+        let sigma_0 = earlier.rotate_right(7) ^ earlier.rotate_right(18) ^ (earlier >> 3);
+        let sigma_1 = previous.rotate_right(17) ^ previous.rotate_right(19) ^ (previous >> 10);
+        schedule[index] = schedule[index - 16]
+            .wrapping_add(sigma_0)
+            .wrapping_add(schedule[index - 7])
+            .wrapping_add(sigma_1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for (constant, word) in ROUND_CONSTANTS.iter().zip(schedule) {
+        let sum_1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let temp_1 = h
+            .wrapping_add(sum_1)
+            .wrapping_add(choice)
+            .wrapping_add(*constant)
+            .wrapping_add(word);
+        let sum_0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let temp_2 = sum_0.wrapping_add(majority);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp_1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp_1.wrapping_add(temp_2);
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
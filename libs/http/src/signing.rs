@@ -0,0 +1,200 @@
+use crate::{
+    client::{ClientRequest, split_url},
+    sha256,
+};
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// [`HMAC_SHA256_ALGORITHM`] is the name of the algorithm an [`HmacSigner`] signs
+/// with, which starts both the string to sign and the signature header.
+pub const HMAC_SHA256_ALGORITHM: &str = "HMAC-SHA256";
+
+/// [`SIGNATURE_TIMESTAMP_HEADER`] is the header an [`HmacSigner`] puts the time of
+/// signing in, as seconds since the Unix epoch, so upstreams can reject replays.
+pub const SIGNATURE_TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+
+/// [`CONTENT_SHA256_HEADER`] is the header an [`HmacSigner`] puts the hex SHA-256
+/// digest of the request body in.
+pub const CONTENT_SHA256_HEADER: &str = "X-Content-SHA256";
+
+/// [`DEFAULT_SIGNATURE_HEADER`] is the header an [`HmacSigner`] puts the signature
+/// in, unless [`HmacSigner::with_signature_header`] is used.
+pub const DEFAULT_SIGNATURE_HEADER: &str = "Authorization";
+
+/// [`RequestSigner`] signs every request a [`Client`](crate::client::Client) sends,
+/// so the upstream can tell it came from a trusted caller. Signers run right before
+/// each attempt is sent, after every other header was added.
+pub trait RequestSigner: fmt::Debug + Send + Sync {
+    /// [`RequestSigner::sign`] will add the headers that authenticate `request`,
+    /// or return an [`Err`] describing why it can't be signed.
+    fn sign(&self, request: &mut ClientRequest) -> Result<(), String>;
+}
+
+/// [`HmacSigner`] signs requests with a shared secret, in the style of AWS
+/// Signature Version 4. A canonical form of the method, path, query, signed
+/// headers, and body digest is hashed, and the hash is signed with HMAC-SHA256
+/// along with the time of signing.
+///
+/// The signature header looks like
+/// `HMAC-SHA256 KeyId=<key id>, SignedHeaders=host;x-content-sha256;x-signature-timestamp, Signature=<hex>`.
+#[derive(Clone)]
+pub struct HmacSigner {
+    /// [`HmacSigner::key_id`] tells the upstream which secret the request was
+    /// signed with.
+    pub key_id: String,
+    /// [`HmacSigner::secret`] is the shared secret requests are signed with.
+    secret: Vec<u8>,
+    /// [`HmacSigner::signature_header`] is the header the signature is put in.
+    pub signature_header: String,
+    /// [`HmacSigner::signed_headers`] are the lowercase names of every header that
+    /// is signed, besides `host`, [`CONTENT_SHA256_HEADER`], and
+    /// [`SIGNATURE_TIMESTAMP_HEADER`], which are always signed.
+    pub signed_headers: Vec<String>,
+}
+
+impl HmacSigner {
+    /// [`HmacSigner::new`] creates an [`HmacSigner`] that signs with `secret`,
+    /// identified to the upstream as `key_id`.
+    /// # Example
+    /// [`HmacSigner::new`] can be used to authenticate webhook deliveries:
+    /// ```rust
+    /// use http::{client::Client, signing::HmacSigner};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_webhook_client(secret: &[u8]) -> Client {
+    ///     Client::new().with_signer(Arc::new(
+    ///         HmacSigner::new("webhooks", secret).with_signed_headers(&["Content-Type"]),
+    ///     ))
+    /// }
+    /// ```
+    pub fn new(key_id: &str, secret: &[u8]) -> Self {
+        HmacSigner {
+            key_id: key_id.to_string(),
+            secret: secret.to_vec(),
+            signature_header: DEFAULT_SIGNATURE_HEADER.to_string(),
+            signed_headers: Vec::new(),
+        }
+    }
+
+    /// [`HmacSigner::with_signature_header`] will set
+    /// [`HmacSigner::signature_header`], and return the [`HmacSigner`].
+    pub fn with_signature_header(mut self, signature_header: &str) -> Self {
+        self.signature_header = signature_header.to_string();
+        self
+    }
+
+    /// [`HmacSigner::with_signed_headers`] will add every header in
+    /// `signed_headers` to [`HmacSigner::signed_headers`], and return the
+    /// [`HmacSigner`].
+    pub fn with_signed_headers(mut self, signed_headers: &[&str]) -> Self {
+        self.signed_headers
+            .extend(signed_headers.iter().map(|name| name.to_ascii_lowercase()));
+        self
+    }
+
+    /// [`HmacSigner::sign_at`] will sign `request` as if it was sent at `timestamp`,
+    /// in seconds since the Unix epoch, replacing any signature it already had.
+    pub fn sign_at(&self, request: &mut ClientRequest, timestamp: u64) -> Result<(), String> {
+        request.headers.retain(|(name, _)| {
+            ![
+                SIGNATURE_TIMESTAMP_HEADER,
+                CONTENT_SHA256_HEADER,
+                &self.signature_header,
+            ]
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case(name))
+        });
+        request.headers.push((
+            SIGNATURE_TIMESTAMP_HEADER.to_string(),
+            timestamp.to_string(),
+        ));
+        request.headers.push((
+            CONTENT_SHA256_HEADER.to_string(),
+            to_hex(&sha256::hash(&request.body)),
+        ));
+
+        let (canonical_request, signed_headers) = self.canonical_request(request)?;
+        let string_to_sign = format!(
+            "{HMAC_SHA256_ALGORITHM}\n{timestamp}\n{}",
+            to_hex(&sha256::hash(canonical_request.as_bytes()))
+        );
+        let signature = to_hex(&sha256::hmac(string_to_sign.as_bytes(), &self.secret));
+        request.headers.push((
+            self.signature_header.clone(),
+            format!(
+                "{HMAC_SHA256_ALGORITHM} KeyId={}, SignedHeaders={signed_headers}, Signature={signature}",
+                self.key_id
+            ),
+        ));
+        Ok(())
+    }
+
+    /// [`HmacSigner::canonical_request`] will get the canonical form of `request`
+    /// that is signed, along with the `;` separated names of its signed headers.
+    /// Signed headers that `request` doesn't have are left out.
+    fn canonical_request(&self, request: &ClientRequest) -> Result<(String, String), String> {
+        let (authority, target) = split_url(&request.url)?;
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let mut query_pairs: Vec<&str> = query.split('&').filter(|pair| !pair.is_empty()).collect();
+        query_pairs.sort_unstable();
+
+        let mut headers = vec![("host".to_string(), authority.trim().to_string())];
+        for (name, value) in &request.headers {
+            let name = name.to_ascii_lowercase();
+            if name == SIGNATURE_TIMESTAMP_HEADER.to_ascii_lowercase()
+                || name == CONTENT_SHA256_HEADER.to_ascii_lowercase()
+                || self.signed_headers.contains(&name)
+            {
+                headers.push((name, value.trim().to_string()));
+            }
+        }
+        headers.sort();
+
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{name}:{value}\n"))
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{canonical_headers}\n{signed_headers}\n{}",
+            request.method.to_ascii_uppercase(),
+            if path.is_empty() { "/" } else { path },
+            query_pairs.join("&"),
+            to_hex(&sha256::hash(&request.body))
+        );
+        Ok((canonical_request, signed_headers))
+    }
+}
+
+/// Implement [`RequestSigner`] for [`HmacSigner`].
+impl RequestSigner for HmacSigner {
+    fn sign(&self, request: &mut ClientRequest) -> Result<(), String> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|error| error.to_string())?
+            .as_secs();
+        self.sign_at(request, timestamp)
+    }
+}
+
+/// Implement [`fmt::Debug`] for [`HmacSigner`], leaving out the secret.
+impl fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("key_id", &self.key_id)
+            .field("signature_header", &self.signature_header)
+            .field("signed_headers", &self.signed_headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// [`to_hex`] will encode `bytes` as lowercase hex.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,294 @@
+use crate::{
+    constants::{CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, HTTP_VERSION, LAST_MODIFIED},
+    status::Status,
+};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+use time::date::Date;
+
+/// [`IMMUTABLE_CACHE_CONTROL`] is the `Cache-Control` value for fingerprinted
+/// assets, which can be cached forever since their path changes with their content.
+pub const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// [`REVALIDATE_CACHE_CONTROL`] is the `Cache-Control` value for assets requested
+/// by their plain path, which caches have to revalidate before reusing.
+pub const REVALIDATE_CACHE_CONTROL: &str = "no-cache";
+
+/// [`StaticFiles`] serves every file in a directory under a path prefix, both by
+/// their plain path (`/assets/app.js`) and by a content-hash fingerprinted path
+/// (`/assets/app.3f9ab2c1.js`).
+#[derive(Clone, Debug, Default)]
+pub struct StaticFiles {
+    /// [`StaticFiles::prefix`] is the request path prefix assets are served under,
+    /// such as `/assets`.
+    pub prefix: String,
+    /// [`StaticFiles::directory`] is the directory assets are read from.
+    pub directory: PathBuf,
+    /// [`StaticFiles::manifest`] maps the plain path of every asset, relative to
+    /// [`StaticFiles::directory`], to its fingerprinted path.
+    pub manifest: HashMap<String, String>,
+}
+
+/// [`StaticAsset`] represents a single file that a [`StaticFiles`] resolved a
+/// request path to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StaticAsset {
+    /// [`StaticAsset::file_path`] is the location of the file on disk.
+    pub file_path: PathBuf,
+    /// [`StaticAsset::content_type`] is the media type of the file, based on its
+    /// extension.
+    pub content_type: &'static str,
+    /// [`StaticAsset::is_fingerprinted`] is whether the asset was requested by its
+    /// fingerprinted path.
+    pub is_fingerprinted: bool,
+}
+
+impl StaticFiles {
+    /// [`StaticFiles::new`] creates a [`StaticFiles`] that serves every file in
+    /// `directory` under the request path `prefix`, fingerprinting each file by its
+    /// content. A missing `directory` serves nothing.
+    /// # Example
+    /// [`StaticFiles::new`] can be used to serve the `static` directory under the
+    /// `/assets` path:
+    /// ```rust
+    /// use http::static_files::StaticFiles;
+    ///
+    /// fn create_static_files() -> StaticFiles {
+    ///     StaticFiles::new("/assets", "static")
+    /// }
+    /// ```
+    pub fn new(prefix: &str, directory: impl AsRef<Path>) -> Self {
+        let directory = directory.as_ref().to_path_buf();
+        let mut manifest = HashMap::new();
+        for relative_path in list_files(&directory, "") {
+            if let Ok(contents) = fs::read(directory.join(&relative_path)) {
+                let fingerprinted_path = fingerprint(&relative_path, &contents);
+                manifest.insert(relative_path, fingerprinted_path);
+            }
+        }
+
+        StaticFiles {
+            prefix: prefix.trim_end_matches('/').to_string(),
+            directory,
+            manifest,
+        }
+    }
+
+    /// [`StaticFiles::asset_path`] will get the fingerprinted request path for the
+    /// asset at the plain `relative_path`, defaults to [`None`] if there is no such
+    /// asset.
+    /// # Example
+    /// [`StaticFiles::asset_path`] can be used to link to an asset from a template,
+    /// so browsers can cache it forever:
+    /// ```rust
+    /// use http::static_files::StaticFiles;
+    ///
+    /// fn render_script_tag(static_files: &StaticFiles) -> String {
+    ///     let src = static_files.asset_path("app.js").unwrap_or_default();
+    ///     format!("<script src=\"{src}\"></script>")
+    /// }
+    /// ```
+    pub fn asset_path(&self, relative_path: &str) -> Option<String> {
+        self.manifest
+            .get(relative_path.trim_start_matches('/'))
+            .map(|fingerprinted_path| format!("{}/{fingerprinted_path}", self.prefix))
+    }
+
+    /// [`StaticFiles::resolve`] will find the [`StaticAsset`] for a `request_path`,
+    /// by either its plain or fingerprinted path, defaults to [`None`] if the path
+    /// is not an asset. Only files found when the [`StaticFiles`] was created are
+    /// ever resolved.
+    /// # Example
+    /// [`StaticFiles::resolve`] can be used to check if a request is for an asset:
+    /// ```rust
+    /// use http::static_files::StaticFiles;
+    ///
+    /// fn is_asset(static_files: &StaticFiles, path: &str) -> bool {
+    ///     static_files.resolve(path).is_some()
+    /// }
+    /// ```
+    pub fn resolve(&self, request_path: &str) -> Option<StaticAsset> {
+        let requested = request_path.strip_prefix(&self.prefix)?.strip_prefix('/')?;
+
+        let (relative_path, is_fingerprinted) = if self.manifest.contains_key(requested) {
+            (requested, false)
+        } else {
+            let (relative_path, _) = self
+                .manifest
+                .iter()
+                .find(|(_, fingerprinted_path)| fingerprinted_path.as_str() == requested)?;
+            (relative_path.as_str(), true)
+        };
+
+        Some(StaticAsset {
+            file_path: self.directory.join(relative_path),
+            content_type: content_type_for(relative_path),
+            is_fingerprinted,
+        })
+    }
+}
+
+impl StaticAsset {
+    /// [`StaticAsset::send`] will write a [`Status::Ok`] response for the asset to
+    /// `stream` with a `Last-Modified` header from the file metadata, sending the
+    /// file without reading it into memory (see [`copy_file`]). When the provided
+    /// `if_modified_since` value shows the client already has the current file, a
+    /// [`Status::NotModified`] response without a body is written instead.
+    /// # Example
+    /// [`StaticAsset::send`] can be used to serve an asset to a [`std::net::TcpStream`]:
+    /// ```rust
+    /// use http::{request::Request, static_files::StaticAsset};
+    /// use std::net::TcpStream;
+    ///
+    /// fn serve_asset(asset: &StaticAsset, request: &Request, stream: &mut TcpStream) -> std::io::Result<()> {
+    ///     asset.send(stream, request.header("if-modified-since"))
+    /// }
+    /// ```
+    pub fn send(&self, stream: &mut impl Write, if_modified_since: Option<&str>) -> io::Result<()> {
+        let mut file = File::open(&self.file_path)?;
+        let metadata = file.metadata()?;
+        let cache_control = if self.is_fingerprinted {
+            IMMUTABLE_CACHE_CONTROL
+        } else {
+            REVALIDATE_CACHE_CONTROL
+        };
+
+        // HTTP dates only have whole seconds, so compare the modified time at that precision
+        let modified = metadata.modified()?;
+        let last_modified = Date::format_http_date(modified);
+        let is_not_modified = if_modified_since
+            .and_then(Date::parse_http_date)
+            .is_some_and(|since| Date::parse_http_date(&last_modified) <= Some(since));
+        if is_not_modified {
+            write!(
+                stream,
+                "{HTTP_VERSION} {}\r\n{LAST_MODIFIED}: {last_modified}\r\n{CACHE_CONTROL}: {cache_control}\r\n\r\n",
+                Status::NotModified
+            )?;
+            return stream.flush();
+        }
+
+        write!(
+            stream,
+            "{HTTP_VERSION} {}\r\n{CONTENT_LENGTH}: {}\r\n{CONTENT_TYPE}: {}\r\n{LAST_MODIFIED}: {last_modified}\r\n{CACHE_CONTROL}: {cache_control}\r\n\r\n",
+            Status::Ok,
+            metadata.len(),
+            self.content_type
+        )?;
+        copy_file(&mut file, stream)?;
+        stream.flush()
+    }
+}
+
+/// [`COPY_BUFFER_SIZE`] is the size of the buffer each worker reuses to copy static
+/// files on platforms without zero-copy file transmission.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// [`copy_file`] will write the rest of `file` to `stream`. On Linux, when `stream`
+/// is a [`std::net::TcpStream`], [`io::copy`] sends the file with `sendfile(2)` or
+/// `splice(2)`, so its contents never pass through user space.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn copy_file(file: &mut File, stream: &mut impl Write) -> io::Result<u64> {
+    io::copy(file, stream)
+}
+
+/// [`copy_file`] will write the rest of `file` to `stream` through a buffer that is
+/// allocated once per worker thread and reused for every static response.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn copy_file(file: &mut File, stream: &mut impl Write) -> io::Result<u64> {
+    use std::{cell::RefCell, io::Read};
+
+    thread_local! {
+        static COPY_BUFFER: RefCell<Vec<u8>> = RefCell::new(vec![0; COPY_BUFFER_SIZE]);
+    }
+
+    COPY_BUFFER.with_borrow_mut(|buffer| {
+        let mut copied = 0;
+        loop {
+            let bytes_read = match file.read(buffer) {
+                Ok(0) => return Ok(copied),
+                Ok(bytes_read) => bytes_read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            stream.write_all(&buffer[..bytes_read])?;
+            copied += bytes_read as u64;
+        }
+    })
+}
+
+/// [`list_files`] will return the path of every file in `directory` and its
+/// subdirectories, relative to the top-level directory and separated by `/`.
+fn list_files(directory: &Path, relative_directory: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative_path = if relative_directory.is_empty() {
+            name
+        } else {
+            format!("{relative_directory}/{name}")
+        };
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => {
+                files.extend(list_files(&entry.path(), &relative_path))
+            }
+            Ok(file_type) if file_type.is_file() => files.push(relative_path),
+            _ => {}
+        }
+    }
+    files
+}
+
+/// [`fingerprint`] will insert a hash of `contents` before the extension of
+/// `relative_path`, such as `app.js` becoming `app.3f9ab2c1.js`.
+fn fingerprint(relative_path: &str, contents: &[u8]) -> String {
+    // FNV-1a, which is plenty to tell versions of the same file apart
+    let hash = contents.iter().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    let hash = format!("{:08x}", hash >> 32);
+
+    let file_name_start = relative_path.rfind('/').map_or(0, |index| index + 1);
+    match relative_path[file_name_start..].rfind('.') {
+        Some(0) | None => format!("{relative_path}.{hash}"),
+        Some(extension_start) => {
+            let (stem, extension) = relative_path.split_at(file_name_start + extension_start);
+            format!("{stem}.{hash}{extension}")
+        }
+    }
+}
+
+/// [`content_type_for`] will return the media type of a file based on its extension,
+/// defaulting to `application/octet-stream`.
+fn content_type_for(relative_path: &str) -> &'static str {
+    let extension = relative_path
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" => "application/json; charset=utf-8",
+        "txt" => "text/plain; charset=utf-8",
+        "svg" => "image/svg+xml; charset=utf-8",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "wasm" => "application/wasm",
+        _ => "application/octet-stream",
+    }
+}
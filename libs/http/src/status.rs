@@ -3,30 +3,176 @@
 pub enum Status {
     /// [`Status::Ok`] represents a `200 OK` HTTP response status code.
     Ok,
+    /// [`Status::Created`] represents a `201 CREATED` HTTP response status code.
+    Created,
+    /// [`Status::Accepted`] represents a `202 ACCEPTED` HTTP response status code.
+    Accepted,
+    /// [`Status::NoContent`] represents a `204 NO CONTENT` HTTP response
+    /// status code.
+    NoContent,
+    /// [`Status::NotModified`] represents a `304 NOT MODIFIED` HTTP response
+    /// status code.
+    NotModified,
     /// [`Status::BadRequest`] represents a `400 BAD REQUEST` HTTP response
     /// status code.
     BadRequest,
+    /// [`Status::Forbidden`] represents a `403 FORBIDDEN` HTTP response status
+    /// code.
+    Forbidden,
     /// [`Status::NotFound`] represents a `404 NOT FOUND` HTTP response
     /// status code.
     NotFound,
+    /// [`Status::PreconditionFailed`] represents a `412 PRECONDITION FAILED`
+    /// HTTP response status code.
+    PreconditionFailed,
+    /// [`Status::PayloadTooLarge`] represents a `413 PAYLOAD TOO LARGE` HTTP
+    /// response status code.
+    PayloadTooLarge,
+    /// [`Status::UnsupportedMediaType`] represents a `415 UNSUPPORTED MEDIA TYPE`
+    /// HTTP response status code.
+    UnsupportedMediaType,
     /// [`Status::UnprocessableEntity`] represents a `422 UNPROCESSABLE ENTITY`
     /// HTTP response status code.
     UnprocessableEntity,
     /// [`Status::ServerError`] represents a `500 INTERNAL SERVER ERROR` HTTP
     /// response status code.
     ServerError,
+    /// [`Status::NotImplemented`] represents a `501 NOT IMPLEMENTED` HTTP response
+    /// status code.
+    NotImplemented,
+    /// [`Status::BadGateway`] represents a `502 BAD GATEWAY` HTTP response status
+    /// code.
+    BadGateway,
+    /// [`Status::ServiceUnavailable`] represents a `503 SERVICE UNAVAILABLE` HTTP
+    /// response status code.
+    ServiceUnavailable,
+    /// [`Status::Other`] represents any other HTTP response status code along with
+    /// its reason phrase, such as a status passed through from an upstream.
+    Other(u16, String),
+}
+
+impl Status {
+    /// [`Status::from_code`] will get the [`Status`] for a numeric status `code`,
+    /// defaults to [`Status::Other`] with the provided `reason` for codes without
+    /// their own [`Status`].
+    /// # Example
+    /// [`Status::from_code`] can be used to pass an upstream status through:
+    /// ```rust
+    /// use http::status::Status;
+    ///
+    /// fn get_teapot_status() -> Status {
+    ///     Status::from_code(418, "I'm a teapot")
+    /// }
+    /// ```
+    pub fn from_code(code: u16, reason: &str) -> Self {
+        match code {
+            200 => Status::Ok,
+            201 => Status::Created,
+            202 => Status::Accepted,
+            204 => Status::NoContent,
+            304 => Status::NotModified,
+            400 => Status::BadRequest,
+            403 => Status::Forbidden,
+            404 => Status::NotFound,
+            412 => Status::PreconditionFailed,
+            413 => Status::PayloadTooLarge,
+            415 => Status::UnsupportedMediaType,
+            422 => Status::UnprocessableEntity,
+            500 => Status::ServerError,
+            501 => Status::NotImplemented,
+            502 => Status::BadGateway,
+            503 => Status::ServiceUnavailable,
+            code => Status::Other(code, reason.to_string()),
+        }
+    }
+
+    /// [`Status::code`] will get the numeric status code of a [`Status`], such as
+    /// `404` for [`Status::NotFound`].
+    /// # Example
+    /// [`Status::code`] can be used to check for a client error:
+    /// ```rust
+    /// use http::status::Status;
+    ///
+    /// fn is_client_error(status: &Status) -> bool {
+    ///     (400..500).contains(&status.code())
+    /// }
+    /// ```
+    pub fn code(&self) -> u16 {
+        match self {
+            Status::Ok => 200,
+            Status::Created => 201,
+            Status::Accepted => 202,
+            Status::NoContent => 204,
+            Status::NotModified => 304,
+            Status::BadRequest => 400,
+            Status::Forbidden => 403,
+            Status::NotFound => 404,
+            Status::PreconditionFailed => 412,
+            Status::PayloadTooLarge => 413,
+            Status::UnsupportedMediaType => 415,
+            Status::UnprocessableEntity => 422,
+            Status::ServerError => 500,
+            Status::NotImplemented => 501,
+            Status::BadGateway => 502,
+            Status::ServiceUnavailable => 503,
+            Status::Other(code, _) => *code,
+        }
+    }
+
+    /// [`Status::reason`] will get the reason phrase of a [`Status`], such as
+    /// `"NOT FOUND"` for [`Status::NotFound`].
+    /// # Example
+    /// [`Status::reason`] can be used to describe a [`Status`] without its code:
+    /// ```rust
+    /// use http::status::Status;
+    ///
+    /// fn describe_status(status: &Status) -> String {
+    ///     status.reason().to_lowercase()
+    /// }
+    /// ```
+    pub fn reason(&self) -> &str {
+        match self {
+            Status::Ok => "OK",
+            Status::Created => "CREATED",
+            Status::Accepted => "ACCEPTED",
+            Status::NoContent => "NO CONTENT",
+            Status::NotModified => "NOT MODIFIED",
+            Status::BadRequest => "BAD REQUEST",
+            Status::Forbidden => "FORBIDDEN",
+            Status::NotFound => "NOT FOUND",
+            Status::PreconditionFailed => "PRECONDITION FAILED",
+            Status::PayloadTooLarge => "PAYLOAD TOO LARGE",
+            Status::UnsupportedMediaType => "UNSUPPORTED MEDIA TYPE",
+            Status::UnprocessableEntity => "UNPROCESSABLE ENTITY",
+            Status::ServerError => "INTERNAL SERVER ERROR",
+            Status::NotImplemented => "NOT IMPLEMENTED",
+            Status::BadGateway => "BAD GATEWAY",
+            Status::ServiceUnavailable => "SERVICE UNAVAILABLE",
+            Status::Other(_, reason) => reason,
+        }
+    }
+
+    /// [`Status::allows_body`] will return `false` for a `1xx`, `204 NO CONTENT`,
+    /// or `304 NOT MODIFIED` [`Status`], which is never sent with a body or the
+    /// `Content-Length` and `Content-Type` headers that describe one, and `true`
+    /// otherwise.
+    /// # Example
+    /// [`Status::allows_body`] can be used to leave the body out of a response:
+    /// ```rust
+    /// use http::status::Status;
+    ///
+    /// fn get_body<'a>(status: &Status, body: &'a str) -> &'a str {
+    ///     if status.allows_body() { body } else { "" }
+    /// }
+    /// ```
+    pub fn allows_body(&self) -> bool {
+        !matches!(self.code(), 100..=199 | 204 | 304)
+    }
 }
 
 /// Implement [`std::fmt::Display`] for [`Status`].
 impl std::fmt::Display for Status {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let status = match self {
-            Status::Ok => "200 OK",
-            Status::BadRequest => "400 BAD REQUEST",
-            Status::NotFound => "404 NOT FOUND",
-            Status::UnprocessableEntity => "422 UNPROCESSABLE ENTITY",
-            Status::ServerError => "500 INTERNAL SERVER ERROR",
-        };
-        write!(f, "{status}")
+        write!(f, "{} {}", self.code(), self.reason())
     }
 }
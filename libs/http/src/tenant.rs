@@ -0,0 +1,148 @@
+use crate::intern::{HeaderName, headers::HOST};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// [`MAX_TENANT_ID_LENGTH`] is the longest a [`TenantId`] can be, which is the
+/// longest a DNS label, such as a subdomain, can be.
+pub const MAX_TENANT_ID_LENGTH: usize = 63;
+
+/// [`TenantId`] is the tenant a request was made for, when one deployment serves
+/// many customers. The server adds it to every [`Request`](crate::request::Request)
+/// it routes when tenants are resolved.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// [`TenantId::new`] will create a [`TenantId`] from `id`, which has to be up to
+    /// [`MAX_TENANT_ID_LENGTH`] ASCII letters, digits, `-`, or `_`, so it is safe to
+    /// put in a key. Returns [`None`] for any other `id`.
+    pub fn new(id: &str) -> Option<Self> {
+        let is_valid = !id.is_empty()
+            && id.len() <= MAX_TENANT_ID_LENGTH
+            && id.chars().all(|character| {
+                character.is_ascii_alphanumeric() || matches!(character, '-' | '_')
+            });
+        is_valid.then(|| TenantId(id.to_ascii_lowercase()))
+    }
+
+    /// [`TenantId::as_str`] will get the id of the tenant.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// [`TenantId::scope`] will get `key` partitioned for the tenant, so the same key
+    /// of two tenants never names the same stored value.
+    /// # Example
+    /// [`TenantId::scope`] can be used to keep a counter of each tenant apart:
+    /// ```rust
+    /// use http::tenant::TenantId;
+    ///
+    /// fn get_counter_key(tenant: &TenantId) -> String {
+    ///     tenant.scope("visits")
+    /// }
+    /// ```
+    pub fn scope(&self, key: &str) -> String {
+        format!("{}.{key}", self.0)
+    }
+}
+
+/// Implement [`fmt::Display`] for [`TenantId`].
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// [`TenantResolver`] represents where the [`TenantId`] of a request is found.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TenantResolver {
+    /// [`TenantResolver::Subdomain`] reads the subdomain of the `Host` header below
+    /// the base domain, such as `acme` from `acme.example.com` for `example.com`.
+    Subdomain(String),
+    /// [`TenantResolver::Header`] reads the value of a request header, such as
+    /// `X-Tenant`.
+    Header(String),
+    /// [`TenantResolver::PathPrefix`] reads the first segment of the path, such as
+    /// `acme` from `/acme/users`, and routes the request by the rest of it.
+    PathPrefix,
+}
+
+impl TenantResolver {
+    /// [`TenantResolver::resolve`] will get the [`TenantId`] of a request with
+    /// `headers` for `path`, along with the path to route it by, which only differs
+    /// from `path` for [`TenantResolver::PathPrefix`]. Returns [`None`] if the request
+    /// doesn't name a valid tenant.
+    /// # Example
+    /// [`TenantResolver::resolve`] can be used to read the tenant from a header:
+    /// ```rust
+    /// use http::{intern::HeaderName, tenant::{TenantId, TenantResolver}};
+    /// use std::collections::HashMap;
+    ///
+    /// fn get_tenant(headers: &HashMap<HeaderName, String>) -> Option<TenantId> {
+    ///     let resolver = TenantResolver::Header("X-Tenant".to_string());
+    ///     resolver.resolve(headers, "/users").map(|(tenant, _)| tenant)
+    /// }
+    /// ```
+    pub fn resolve(
+        &self,
+        headers: &HashMap<HeaderName, String>,
+        path: &str,
+    ) -> Option<(TenantId, String)> {
+        match self {
+            TenantResolver::Subdomain(base_domain) => {
+                let host = headers.get(&HOST)?;
+                // the port is not part of the domain
+                let host = host
+                    .rsplit_once(':')
+                    .map_or(host.as_str(), |(host, _)| host);
+                let subdomain = host
+                    .to_ascii_lowercase()
+                    .strip_suffix(&base_domain.to_ascii_lowercase())?
+                    .strip_suffix('.')?
+                    .to_string();
+                // only the label right below the base domain names the tenant
+                let tenant = subdomain.rsplit('.').next()?;
+                Some((TenantId::new(tenant)?, path.to_string()))
+            }
+            TenantResolver::Header(name) => {
+                let tenant = headers.get(&HeaderName::new(name))?;
+                Some((TenantId::new(tenant.trim())?, path.to_string()))
+            }
+            TenantResolver::PathPrefix => {
+                let rest = path.strip_prefix('/')?;
+                let (tenant, rest) = rest.split_once('/').unwrap_or((rest, ""));
+                Some((TenantId::new(tenant)?, format!("/{rest}")))
+            }
+        }
+    }
+}
+
+/// Implement [`FromStr`] for [`TenantResolver`].
+impl FromStr for TenantResolver {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            None if value == "path" => Ok(TenantResolver::PathPrefix),
+            Some(("subdomain", base_domain)) if !base_domain.is_empty() => {
+                Ok(TenantResolver::Subdomain(base_domain.to_string()))
+            }
+            Some(("header", name)) if !name.is_empty() => {
+                Ok(TenantResolver::Header(name.to_string()))
+            }
+            _ => Err(format!(
+                "unknown tenant resolver '{value}', expected \"subdomain:<base domain>\", \"header:<name>\", or \"path\""
+            )),
+        }
+    }
+}
+
+/// Implement [`fmt::Display`] for [`TenantResolver`], in the form it is parsed from.
+impl fmt::Display for TenantResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TenantResolver::Subdomain(base_domain) => write!(f, "subdomain:{base_domain}"),
+            TenantResolver::Header(name) => write!(f, "header:{name}"),
+            TenantResolver::PathPrefix => write!(f, "path"),
+        }
+    }
+}
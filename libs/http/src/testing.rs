@@ -0,0 +1,318 @@
+use crate::{
+    constants::CONTENT_LENGTH,
+    methods::Method,
+    response::Response,
+    route::{RouteSegment, RouteSpec},
+    router::Router,
+};
+use std::{env, fmt, fs, path::Path};
+
+/// [`DEFAULT_CASES`] is how many cases a property is checked with, unless another
+/// amount is asked for.
+pub const DEFAULT_CASES: usize = 256;
+
+/// [`UPDATE_SNAPSHOTS`] is the environment variable that makes [`snapshot`] write
+/// golden files instead of comparing against them, such as
+/// `UPDATE_SNAPSHOTS=1 cargo test`.
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+/// [`TIME_PLACEHOLDER`] is what the time of a [`Response`] is replaced with by
+/// [`render_snapshot`], so snapshots don't change every second.
+pub const TIME_PLACEHOLDER: &str = "[time]";
+
+/// [`SEGMENT_CHARACTERS`] are the characters [`Gen::segment`] builds path segments
+/// from, which are every character that doesn't have to be percent-encoded.
+const SEGMENT_CHARACTERS: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-._~";
+
+/// [`Gen`] is a seeded generator of random values, such as path segments and
+/// request paths, so a failing case can be reproduced from its seed. It has no
+/// dependencies, so it can be used from any test.
+#[derive(Clone, Debug)]
+pub struct Gen {
+    /// [`Gen::seed`] is the seed the [`Gen`] was created with.
+    seed: u64,
+    /// [`Gen::state`] is the state of the xorshift generator.
+    state: u64,
+}
+
+/// [`Ambiguity`] is a request path that more than one [`Route`](crate::route::Route)
+/// with the same method matches, where only the first of them ever serves it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ambiguity {
+    /// [`Ambiguity::method`] is the method of the [`Route`](crate::route::Route)
+    /// values.
+    pub method: Method,
+    /// [`Ambiguity::path`] is a request path they all match.
+    pub path: String,
+    /// [`Ambiguity::patterns`] is the pattern of every [`Route`](crate::route::Route)
+    /// that matches [`Ambiguity::path`], in the order they are matched, so the first
+    /// one serves it.
+    pub patterns: Vec<String>,
+}
+
+impl Gen {
+    /// [`Gen::new`] creates a [`Gen`] from `seed`, which always generates the same
+    /// values for the same `seed`.
+    /// # Example
+    /// [`Gen::new`] can be used to generate a request path for a
+    /// [`Route`](crate::route::Route):
+    /// ```rust
+    /// use http::{response::Response, route::Route, testing::Gen};
+    ///
+    /// fn generate_user_path() -> String {
+    ///     let route = Route::get("/user/{id}", Box::new(|_| Response::ok("\"user\"", false)));
+    ///     Gen::new(42).path_for(&route.spec)
+    /// }
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        Gen {
+            seed,
+            // xorshift gets stuck on 0, so mix the seed first
+            state: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+        }
+    }
+
+    /// [`Gen::seed`] will get the seed the [`Gen`] was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// [`Gen::next_u64`] will get the next random [`u64`].
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// [`Gen::below`] will get a random number from `0` up to `bound`, or `0` if
+    /// `bound` is `0`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            0
+        } else {
+            (self.next_u64() % bound as u64) as usize
+        }
+    }
+
+    /// [`Gen::segment`] will get a random path segment of 1 to 12 characters that
+    /// don't have to be percent-encoded.
+    pub fn segment(&mut self) -> String {
+        let length = 1 + self.below(12);
+        (0..length)
+            .map(|_| char::from(SEGMENT_CHARACTERS[self.below(SEGMENT_CHARACTERS.len())]))
+            .collect()
+    }
+
+    /// [`Gen::path`] will get a random request path of up to `max_segments`
+    /// segments, such as `/aB3/x-y`.
+    pub fn path(&mut self, max_segments: usize) -> String {
+        let segments = self.below(max_segments + 1);
+        if segments == 0 {
+            return "/".to_string();
+        }
+        (0..segments).fold(String::new(), |mut path, _| {
+            path.push('/');
+            path.push_str(&self.segment());
+            path
+        })
+    }
+
+    /// [`Gen::path_for`] will get a random request path that the route of `spec`
+    /// matches, with a random value for every path parameter, and up to 3 random
+    /// segments for a catch-all segment.
+    pub fn path_for(&mut self, spec: &RouteSpec) -> String {
+        spec.segments
+            .iter()
+            .map(|segment| match segment {
+                RouteSegment::Static(symbol) => symbol.as_str().to_string(),
+                RouteSegment::Param(_) => self.segment(),
+                RouteSegment::Rest(_) => {
+                    let segments = self.below(4);
+                    (0..segments)
+                        .map(|_| self.segment())
+                        .collect::<Vec<_>>()
+                        .join("/")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Implement [`fmt::Display`] for [`Ambiguity`].
+impl fmt::Display for Ambiguity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} is matched by {}",
+            self.method,
+            self.path,
+            self.patterns.join(", then ")
+        )
+    }
+}
+
+/// [`check`] will check `property` against `cases` cases, each with a [`Gen`] seeded
+/// from `seed` and the case number, and return an [`Err`] with the seed of the first
+/// case that fails, so it can be reproduced with [`Gen::new`].
+/// # Example
+/// [`check`] can be used to check that every `/user/{id}` path is routed:
+/// ```rust
+/// use http::{methods::GET, response::Response, route::Route, router::Router, testing};
+///
+/// fn check_user_route() -> Result<(), String> {
+///     let router = Router::new(vec![Route::get(
+///         "/user/{id}",
+///         Box::new(|_| Response::ok("\"user\"", false)),
+///     )]);
+///     testing::check(testing::DEFAULT_CASES, 7, |generator| {
+///         let path = generator.path_for(&router.routes[0].spec);
+///         router
+///             .resolve(GET, &path)
+///             .map(|_| ())
+///             .ok_or(format!("{path} was not routed"))
+///     })
+/// }
+/// ```
+pub fn check<F>(cases: usize, seed: u64, mut property: F) -> Result<(), String>
+where
+    F: FnMut(&mut Gen) -> Result<(), String>,
+{
+    for case in 0..cases {
+        let mut generator = Gen::new(seed.wrapping_add(case as u64));
+        property(&mut generator).map_err(|reason| {
+            format!(
+                "case {case} of {cases} failed with seed {}: {reason}",
+                generator.seed()
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// [`find_ambiguities`] will generate `cases` request paths for every
+/// [`Route`](crate::route::Route) of `router` from `seed`, and get every path that more
+/// than one [`Route`](crate::route::Route) with the same method matches, once for each
+/// set of patterns that match.
+/// # Example
+/// [`find_ambiguities`] can be used to find the paths that more than one
+/// [`Route`](crate::route::Route) matches, where the most specific one serves it:
+/// ```rust
+/// use http::{response::Response, route::Route, router::Router, testing};
+///
+/// fn find_overlapping_routes() -> Vec<testing::Ambiguity> {
+///     let router = Router::new(vec![
+///         Route::get("/user/{id}", Box::new(|_| Response::ok("\"user\"", false))),
+///         Route::get("/user/me", Box::new(|_| Response::ok("\"me\"", false))),
+///     ]);
+///     testing::find_ambiguities(&router, testing::DEFAULT_CASES, 0)
+/// }
+/// ```
+pub fn find_ambiguities(router: &Router, cases: usize, seed: u64) -> Vec<Ambiguity> {
+    let mut generator = Gen::new(seed);
+    let mut ambiguities: Vec<Ambiguity> = Vec::new();
+    for route in &router.routes {
+        for _ in 0..cases {
+            let path = generator.path_for(&route.spec);
+            let patterns: Vec<String> = router
+                .routes
+                .iter()
+                .filter(|other| other.spec.method == route.spec.method)
+                .filter(|other| other.spec.matches_path(&path).is_some())
+                .map(|other| other.spec.request_pattern.clone())
+                .collect();
+            if patterns.len() > 1
+                && !ambiguities.iter().any(|ambiguity| {
+                    ambiguity.method == route.spec.method && ambiguity.patterns == patterns
+                })
+            {
+                ambiguities.push(Ambiguity {
+                    method: route.spec.method,
+                    path,
+                    patterns,
+                });
+            }
+        }
+    }
+    ambiguities
+}
+
+/// [`render_snapshot`] will render `response` in a canonical text form for
+/// [`snapshot`], with the status on the first line, then every header besides
+/// `Content-Length` sorted by name, then a blank line and the body. The time of
+/// `response` is replaced with [`TIME_PLACEHOLDER`], and lines end with `\n`.
+pub fn render_snapshot(response: &Response) -> String {
+    let rendered = response
+        .to_string()
+        .replace(&response.time.formatted, TIME_PLACEHOLDER);
+    let (head, body) = rendered.split_once("\r\n\r\n").unwrap_or((&rendered, ""));
+    let mut headers: Vec<&str> = head
+        .split("\r\n")
+        .skip(1)
+        .filter(|line| {
+            !line
+                .split(':')
+                .next()
+                .is_some_and(|name| name.trim().eq_ignore_ascii_case(CONTENT_LENGTH))
+        })
+        .collect();
+    headers.sort_by_key(|line| line.to_ascii_lowercase());
+    let mut snapshot = format!("{}\n", response.status);
+    for header in headers {
+        snapshot.push_str(header);
+        snapshot.push('\n');
+    }
+    snapshot.push('\n');
+    snapshot.push_str(body);
+    if !body.ends_with('\n') {
+        snapshot.push('\n');
+    }
+    snapshot
+}
+
+/// [`snapshot`] will compare `response`, rendered by [`render_snapshot`], with the
+/// golden file at `path`, and return an [`Err`] showing the first line that
+/// differs. The golden file is written instead when it doesn't exist yet, or when
+/// the [`UPDATE_SNAPSHOTS`] environment variable is set.
+/// # Example
+/// [`snapshot`] can be used to catch any change to a [`Response`]:
+/// ```rust
+/// use http::{response::Response, testing};
+///
+/// fn check_not_found_response() -> Result<(), String> {
+///     testing::snapshot(&Response::not_found(), "tests/snapshots/not_found.txt")
+/// }
+/// ```
+pub fn snapshot(response: &Response, path: impl AsRef<Path>) -> Result<(), String> {
+    let path = path.as_ref();
+    let actual = render_snapshot(response);
+    if env::var_os(UPDATE_SNAPSHOTS).is_some() || !path.exists() {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)
+                .map_err(|error| format!("can't create {}: {error}", directory.display()))?;
+        }
+        return fs::write(path, &actual)
+            .map_err(|error| format!("can't write snapshot {}: {error}", path.display()));
+    }
+
+    let expected = fs::read_to_string(path)
+        .map_err(|error| format!("can't read snapshot {}: {error}", path.display()))?
+        .replace("\r\n", "\n");
+    if expected == actual {
+        return Ok(());
+    }
+    let line = expected
+        .lines()
+        .zip(actual.lines())
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+    Err(format!(
+        "response does not match snapshot {} at line {}, run with {UPDATE_SNAPSHOTS}=1 to update it\n  expected: {}\n    actual: {}",
+        path.display(),
+        line + 1,
+        expected.lines().nth(line).unwrap_or("<end of snapshot>"),
+        actual.lines().nth(line).unwrap_or("<end of response>"),
+    ))
+}
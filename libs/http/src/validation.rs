@@ -0,0 +1,257 @@
+use crate::{json::Json, request::Request, respond::Respond};
+
+/// [`ParamLocation`] represents where in a [`Request`] a route parameter is read from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamLocation {
+    /// [`ParamLocation::Path`] is a dynamic path segment, such as `{id}`.
+    Path,
+    /// [`ParamLocation::Query`] is a query string parameter, such as `?id=1`.
+    Query,
+    /// [`ParamLocation::Body`] is the body content of a [`Request`].
+    Body,
+    /// [`ParamLocation::Header`] is a request header, such as `X-Api-Key`.
+    Header,
+    /// [`ParamLocation::Cookie`] is a cookie of the `Cookie` header, such as
+    /// `session_id`.
+    Cookie,
+    /// [`ParamLocation::File`] is a part of a `multipart/form-data` body, such as an
+    /// uploaded file.
+    File,
+}
+
+/// Implement [`std::fmt::Display`] for [`ParamLocation`].
+impl std::fmt::Display for ParamLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let location = match self {
+            ParamLocation::Path => "path",
+            ParamLocation::Query => "query",
+            ParamLocation::Body => "body",
+            ParamLocation::Header => "header",
+            ParamLocation::Cookie => "cookie",
+            ParamLocation::File => "file",
+        };
+        write!(f, "{location}")
+    }
+}
+
+/// [`TypeCheck`] is a function that returns whether a value can be parsed into the
+/// type of a [`RouteParam`], the same way its handler parses it.
+pub type TypeCheck = fn(&str) -> bool;
+
+/// [`RouteParam`] describes a single parameter a [`Route`](crate::route::Route)
+/// handler expects, as recorded by the http attributes.
+#[derive(Clone, Debug)]
+pub struct RouteParam {
+    /// [`RouteParam::name`] is the name of the handler argument.
+    pub name: String,
+    /// [`RouteParam::location`] is where the parameter is read from.
+    pub location: ParamLocation,
+    /// [`RouteParam::type_name`] is the Rust type of the handler argument,
+    /// such as `"i32"` or `"String"`.
+    pub type_name: String,
+    /// [`RouteParam::description`] is an optional description of the parameter,
+    /// such as the doc comment of the handler argument.
+    pub description: Option<String>,
+    /// [`RouteParam::type_check`] is the [`TypeCheck`] a value is validated with,
+    /// which the http attributes record for every argument they parse with
+    /// [`FromStr`](std::str::FromStr). Without one, only a primitive
+    /// [`RouteParam::type_name`], such as `"u32"`, is checked.
+    pub type_check: Option<TypeCheck>,
+}
+
+/// Implement [`PartialEq`] for [`RouteParam`], where the [`RouteParam::type_check`]
+/// is left out, since it checks the [`RouteParam::type_name`] that is compared.
+impl PartialEq for RouteParam {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.location == other.location
+            && self.type_name == other.type_name
+            && self.description == other.description
+    }
+}
+
+impl RouteParam {
+    /// [`RouteParam::new`] creates a new [`RouteParam`].
+    /// # Example
+    /// [`RouteParam::new`] can be used to describe an `i32` query parameter:
+    /// ```rust
+    /// use http::validation::{ParamLocation, RouteParam};
+    ///
+    /// fn describe_number_param() -> RouteParam {
+    ///     RouteParam::new("number", ParamLocation::Query, "i32")
+    /// }
+    /// ```
+    pub fn new(name: &str, location: ParamLocation, type_name: &str) -> Self {
+        RouteParam {
+            name: name.to_string(),
+            location,
+            type_name: type_name.to_string(),
+            description: None,
+            type_check: None,
+        }
+    }
+
+    /// [`RouteParam::with_description`] will set [`RouteParam::description`] on a
+    /// [`RouteParam`], and return the [`RouteParam`].
+    /// # Example
+    /// [`RouteParam::with_description`] can be used to explain what a parameter is
+    /// for:
+    /// ```rust
+    /// use http::validation::{ParamLocation, RouteParam};
+    ///
+    /// fn describe_id_param() -> RouteParam {
+    ///     RouteParam::new("id", ParamLocation::Path, "u32")
+    ///         .with_description("The id of the user to get.")
+    /// }
+    /// ```
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// [`RouteParam::with_type_check`] will set [`RouteParam::type_check`] on a
+    /// [`RouteParam`], and return the [`RouteParam`].
+    /// # Example
+    /// [`RouteParam::with_type_check`] can be used to validate a body against a type
+    /// that isn't a primitive:
+    /// ```rust
+    /// use http::{
+    ///     params::Uuid,
+    ///     validation::{ParamLocation, RouteParam},
+    /// };
+    ///
+    /// fn describe_uuid_body() -> RouteParam {
+    ///     RouteParam::new("id", ParamLocation::Body, "Uuid")
+    ///         .with_type_check(|value| value.parse::<Uuid>().is_ok())
+    /// }
+    /// ```
+    pub fn with_type_check(mut self, type_check: TypeCheck) -> Self {
+        self.type_check = Some(type_check);
+        self
+    }
+
+    /// [`RouteParam::validate`] will check that the parameter is present in the
+    /// `request`, and that it can be parsed into [`RouteParam::type_name`] with the
+    /// [`RouteParam::type_check`], or as a primitive type without one. Returns
+    /// [`Some`] [`Violation`] if it can't, and [`None`] otherwise.
+    pub fn validate(&self, request: &Request) -> Option<Violation> {
+        // an uploaded file is kept as bytes, so there is nothing to parse
+        if self.location == ParamLocation::File {
+            let has_part = request
+                .multipart()
+                .is_some_and(|multipart| multipart.part(&self.name).is_some());
+            return (!has_part).then(|| self.violation("is required"));
+        }
+        // a form binds its fields to the arguments of the same name
+        let form_value = match self.location {
+            ParamLocation::Query | ParamLocation::Body => request.form_param(&self.name),
+            _ => None,
+        };
+        let value = match self.location {
+            ParamLocation::Path => request.path_params.get(&self.name).map(|v| v.as_str()),
+            ParamLocation::Query => form_value
+                .as_deref()
+                .or_else(|| request.query_param(&self.name)),
+            ParamLocation::Body if request.is_form() => form_value.as_deref(),
+            ParamLocation::Body => request.body_content.as_deref(),
+            ParamLocation::Header => request.header(&self.name),
+            ParamLocation::Cookie => request.cookie(&self.name),
+            ParamLocation::File => None,
+        };
+
+        match value {
+            None => Some(self.violation("is required")),
+            Some(value) if !self.is_parseable(value) => {
+                Some(self.violation(&format!("must be of type {}", self.type_name)))
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// [`RouteParam::is_parseable`] will return `true` if `value` can be parsed into
+    /// [`RouteParam::type_name`], and `false` otherwise.
+    fn is_parseable(&self, value: &str) -> bool {
+        match self.type_check {
+            Some(type_check) => type_check(value),
+            None => is_parseable_as(value, &self.type_name),
+        }
+    }
+
+    /// [`RouteParam::violation`] creates a [`Violation`] for this parameter.
+    fn violation(&self, message: &str) -> Violation {
+        Violation {
+            parameter: self.name.clone(),
+            location: self.location.clone(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// [`Violation`] represents a single reason a [`Request`] failed validation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    /// [`Violation::parameter`] is the name of the offending parameter.
+    pub parameter: String,
+    /// [`Violation::location`] is where the offending parameter is read from.
+    pub location: ParamLocation,
+    /// [`Violation::message`] is a human-readable description of the problem.
+    pub message: String,
+}
+
+/// Implement [`Respond`] for [`Violation`]
+impl Respond for Violation {
+    fn get_json(&self) -> String {
+        Json::Object(vec![
+            (
+                "parameter".to_string(),
+                Json::String(self.parameter.clone()),
+            ),
+            (
+                "location".to_string(),
+                Json::String(self.location.to_string()),
+            ),
+            ("message".to_string(), Json::String(self.message.clone())),
+        ])
+        .to_string()
+    }
+}
+
+/// Implement [`Respond`] for [`Vec<Violation>`]
+impl Respond for Vec<Violation> {
+    fn get_json(&self) -> String {
+        format!(
+            r#"{{"violations":[{}]}}"#,
+            self.iter()
+                .map(|violation| violation.get_json())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+/// [`is_parseable_as`] will return `true` if `value` can be parsed into the
+/// primitive type named by `type_name`. It is only used for a [`RouteParam`] without
+/// a [`RouteParam::type_check`], such as one made by hand, where only the name of
+/// its type is known, so any other type is always considered parseable and left for
+/// the handler to check.
+fn is_parseable_as(value: &str, type_name: &str) -> bool {
+    match type_name {
+        "i8" => value.parse::<i8>().is_ok(),
+        "i16" => value.parse::<i16>().is_ok(),
+        "i32" => value.parse::<i32>().is_ok(),
+        "i64" => value.parse::<i64>().is_ok(),
+        "i128" => value.parse::<i128>().is_ok(),
+        "isize" => value.parse::<isize>().is_ok(),
+        "u8" => value.parse::<u8>().is_ok(),
+        "u16" => value.parse::<u16>().is_ok(),
+        "u32" => value.parse::<u32>().is_ok(),
+        "u64" => value.parse::<u64>().is_ok(),
+        "u128" => value.parse::<u128>().is_ok(),
+        "usize" => value.parse::<usize>().is_ok(),
+        "f32" => value.parse::<f32>().is_ok(),
+        "f64" => value.parse::<f64>().is_ok(),
+        "bool" => value.parse::<bool>().is_ok(),
+        "char" => value.parse::<char>().is_ok(),
+        _ => true,
+    }
+}
@@ -0,0 +1,152 @@
+use crate::json::Json;
+
+/// [`ROOT_ELEMENT`] is the name of the element every XML document built by
+/// [`from_json`] is wrapped in.
+pub const ROOT_ELEMENT: &str = "response";
+
+/// [`ITEM_ELEMENT`] is the name of the element every item of a JSON array is
+/// written as.
+pub const ITEM_ELEMENT: &str = "item";
+
+/// [`from_json`] will convert `json` into an XML document wrapped in a
+/// [`ROOT_ELEMENT`], where every member of an object is an element named after its
+/// key, every item of an array is an [`ITEM_ELEMENT`], and `null` is an empty
+/// element.
+/// # Example
+/// [`from_json`] can be used to answer a legacy client with the XML form of a
+/// JSON value:
+/// ```rust
+/// use http::{json::Json, xml};
+///
+/// fn create_user_xml() -> String {
+///     // <response><name>evan</name><admin>true</admin></response>
+///     xml::from_json(&Json::Object(vec![
+///         ("name".to_string(), Json::String("evan".to_string())),
+///         ("admin".to_string(), Json::Bool(true)),
+///     ]))
+/// }
+/// ```
+pub fn from_json(json: &Json) -> String {
+    let mut document = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    write_element(&mut document, ROOT_ELEMENT, json);
+    document
+}
+
+/// [`escape`] will escape every character of `text` that has a meaning in XML.
+/// # Example
+/// [`escape`] can be used to put any text inside an element:
+/// ```rust
+/// use http::xml;
+///
+/// fn create_note_element(note: &str) -> String {
+///     format!("<note>{}</note>", xml::escape(note))
+/// }
+/// ```
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// [`prefers_xml`] will check if the provided value of an `Accept` header ranks
+/// XML above JSON, based on the q-value of every media range the client accepts.
+/// JSON wins ties, and is the default when there is no `Accept` header.
+/// # Example
+/// [`prefers_xml`] can be used to check what a request should be answered with:
+/// ```rust
+/// use http::{request::Request, xml};
+///
+/// fn wants_xml(request: &Request) -> bool {
+///     xml::prefers_xml(request.header("accept"))
+/// }
+/// ```
+pub fn prefers_xml(accept: Option<&str>) -> bool {
+    let Some(accept) = accept else {
+        return false;
+    };
+    let accepted: Vec<(String, f32)> = accept.split(',').filter_map(parse_media_range).collect();
+    let quality = |media_types: &[&str]| {
+        accepted
+            .iter()
+            .filter(|(media_type, _)| media_types.contains(&media_type.as_str()))
+            .map(|(_, quality)| *quality)
+            .fold(0.0, f32::max)
+    };
+
+    let xml = quality(&["application/xml", "text/xml"]);
+    let json = quality(&["application/json", "application/*", "*/*"]);
+    xml > json
+}
+
+/// [`parse_media_range`] will parse a single media range of an `Accept` header,
+/// such as `text/xml;q=0.8`, into its lowercase media type and q-value.
+fn parse_media_range(media_range: &str) -> Option<(String, f32)> {
+    let mut parts = media_range.split(';');
+    let media_type = parts.next()?.trim().to_lowercase();
+    if media_type.is_empty() {
+        return None;
+    }
+
+    let quality = parts
+        .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+        .filter_map(|quality| quality.trim().parse::<f32>().ok())
+        .next()
+        .unwrap_or(1.0);
+    Some((media_type, quality.clamp(0.0, 1.0)))
+}
+
+/// [`write_element`] will write `json` to `document` as an element called `name`.
+fn write_element(document: &mut String, name: &str, json: &Json) {
+    let name = element_name(name);
+    match json {
+        Json::Null => {
+            document.push_str(&format!("<{name}/>"));
+            return;
+        }
+        _ => document.push_str(&format!("<{name}>")),
+    }
+    match json {
+        Json::String(text) => document.push_str(&escape(text)),
+        Json::Array(items) => {
+            for item in items {
+                write_element(document, ITEM_ELEMENT, item);
+            }
+        }
+        Json::Object(members) => {
+            for (key, value) in members {
+                write_element(document, key, value);
+            }
+        }
+        value => document.push_str(&value.to_string()),
+    }
+    document.push_str(&format!("</{name}>"));
+}
+
+/// [`element_name`] will turn `key` into a valid XML element name, replacing every
+/// character an element name can't hold with `_`, and prefixing it with `_` when
+/// it doesn't start with a letter or `_`.
+fn element_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|character| {
+            if character.is_alphanumeric() || matches!(character, '_' | '-' | '.') {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match name.chars().next() {
+        Some(first) if first.is_alphabetic() || first == '_' => name,
+        _ => format!("_{name}"),
+    }
+}
@@ -0,0 +1,79 @@
+use syn::{
+    Attribute, Ident, LitStr, Token,
+    parse::{Parse, ParseStream},
+};
+
+/// [`BODY_ATTRIBUTE`] is the name of the attribute that marks the handler argument
+/// the body is passed to, such as `#[body(limit = "1MB", stream)]`.
+pub(crate) const BODY_ATTRIBUTE: &str = "body";
+
+/// [`BodyArgs`] represents the options of a `#[body(...)]` attribute on a handler
+/// argument, which are a `limit = "size"` option and a `stream` flag.
+#[derive(Clone, Default)]
+pub(crate) struct BodyArgs {
+    /// [`BodyArgs::limit`] is the largest body in bytes the route accepts, from a
+    /// `limit` option such as `"1MB"`.
+    pub(crate) limit: Option<usize>,
+    /// [`BodyArgs::stream`] is whether the handler argument is a
+    /// [`BodyReader`](http::body::BodyReader) over the body, instead of the body
+    /// read into memory.
+    pub(crate) stream: bool,
+}
+
+impl BodyArgs {
+    /// [`BodyArgs::from_attributes`] will parse the `#[body(...)]` attribute out of
+    /// `attrs`, if there is one, and return [`None`] if there isn't.
+    pub(crate) fn from_attributes(attrs: &[Attribute]) -> syn::Result<Option<Self>> {
+        let mut body_attrs = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(BODY_ATTRIBUTE));
+        let Some(attr) = body_attrs.next() else {
+            return Ok(None);
+        };
+        if let Some(duplicate) = body_attrs.next() {
+            return Err(syn::Error::new_spanned(
+                duplicate,
+                "a handler argument can only have one `#[body]` attribute",
+            ));
+        }
+
+        // a bare `#[body]` only marks the argument the body is passed to
+        match &attr.meta {
+            syn::Meta::Path(_) => Ok(Some(BodyArgs::default())),
+            _ => attr.parse_args().map(Some),
+        }
+    }
+}
+
+/// Implement [`Parse`] for [`BodyArgs`].
+impl Parse for BodyArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut body_args = BodyArgs::default();
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "limit" => {
+                    input.parse::<Token![=]>()?;
+                    let limit: LitStr = input.parse()?;
+                    body_args.limit = Some(
+                        http::body::parse_size(&limit.value())
+                            .map_err(|reason| syn::Error::new_spanned(&limit, reason))?,
+                    );
+                }
+                "stream" => body_args.stream = true,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!("unknown body option `{key}`, expected `limit` or `stream`"),
+                    ));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(body_args)
+    }
+}
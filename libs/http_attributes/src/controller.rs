@@ -0,0 +1,226 @@
+use crate::route_args::RouteArgs;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::{Attribute, ImplItem, ItemFn, ItemImpl, LitStr, Type};
+
+/// [`impl_to_controller`] takes the `prefix` of a `#[controller]` attribute and the
+/// `impl` block it is on, then moves every method with an `#[http_*]` attribute out
+/// of the block with `prefix` in front of its path. Each method is replaced with one
+/// of the same name that returns its [`Route`](http::route::Route), and the block
+/// gets a `routes` function that returns all of them.
+pub(crate) fn impl_to_controller(prefix: LitStr, mut item_impl: ItemImpl) -> TokenStream {
+    let prefix_value = prefix.value();
+    if item_impl.trait_.is_some() || !item_impl.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &item_impl.self_ty,
+            "`#[controller]` can only be used on an `impl` block of a type without generics",
+        )
+        .to_compile_error();
+    }
+    let self_ty = item_impl.self_ty.clone();
+    let Type::Path(type_path) = &*self_ty else {
+        return syn::Error::new_spanned(&self_ty, "`#[controller]` needs a named type")
+            .to_compile_error();
+    };
+    let type_name = type_path
+        .path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string().to_lowercase())
+        .unwrap_or_default();
+
+    let mut handlers = Vec::new();
+    let mut collected_routes = Vec::new();
+    for item in item_impl.items.iter_mut() {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let Some(route_attr_index) = method.attrs.iter().position(is_route_attribute) else {
+            continue;
+        };
+        let route_attr = &method.attrs[route_attr_index];
+        let route_args: RouteArgs = match route_attr.parse_args() {
+            Ok(route_args) => route_args,
+            Err(error) => return error.to_compile_error(),
+        };
+        let prefixed_path = LitStr::new(
+            &join_paths(&prefix_value, &route_args.path.value()),
+            route_args.path.span(),
+        );
+        let mut handler_attrs = method.attrs.clone();
+        handler_attrs[route_attr_index] =
+            match prefix_route_attribute(route_attr, &route_args.path, &prefixed_path) {
+                Ok(prefixed_attr) => prefixed_attr,
+                Err(error) => return error.to_compile_error(),
+            };
+
+        // the handler is moved next to the `impl` block, where the http attributes
+        // can turn it into a route like any other function
+        let method_ident = method.sig.ident.clone();
+        let handler_ident = Ident::new(
+            &format!("__controller_{type_name}_{method_ident}"),
+            Span::call_site(),
+        );
+        let mut handler_sig = method.sig.clone();
+        handler_sig.ident = handler_ident.clone();
+        let handler = ItemFn {
+            attrs: handler_attrs,
+            vis: method.vis.clone(),
+            sig: handler_sig,
+            block: Box::new(method.block.clone()),
+        };
+        handlers.push(replace_self(
+            quote! { #[doc(hidden)] #handler },
+            &self_ty.to_token_stream(),
+        ));
+
+        // a route behind a cargo feature is left out of the controller when it is
+        // disabled, along with any route behind a `#[cfg]` of its own
+        let cfg_attrs: Vec<TokenStream> = method
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .map(ToTokens::to_token_stream)
+            .chain(
+                route_args
+                    .cfg
+                    .iter()
+                    .map(|feature| quote! { #[cfg(feature = #feature)] }),
+            )
+            .collect();
+        let doc_attrs = method
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"));
+        let method_vis = &method.vis;
+        let returns_many = is_multi_method_attribute(route_attr);
+        let (output, collect) = if returns_many {
+            (
+                quote! { Vec<http::route::Route> },
+                quote! { routes.extend(Self::#method_ident()); },
+            )
+        } else {
+            (
+                quote! { http::route::Route },
+                quote! { routes.push(Self::#method_ident()); },
+            )
+        };
+        *item = syn::parse_quote! {
+            #(#cfg_attrs)*
+            #(#doc_attrs)*
+            #method_vis fn #method_ident() -> #output {
+                #handler_ident()
+            }
+        };
+        collected_routes.push(quote! {
+            #(#cfg_attrs)*
+            #collect
+        });
+    }
+
+    if collected_routes.is_empty() {
+        return syn::Error::new_spanned(
+            &self_ty,
+            "a `#[controller]` needs at least one method with an `#[http_*]` attribute, such as `#[http_get]`",
+        )
+        .to_compile_error();
+    }
+    item_impl.items.push(syn::parse_quote! {
+        /// `routes` will get every [`Route`](http::route::Route) of this controller.
+        pub fn routes() -> Vec<http::route::Route> {
+            let mut routes = Vec::new();
+            #(#collected_routes)*
+            routes
+        }
+    });
+
+    quote! {
+        #(#handlers)*
+
+        #item_impl
+    }
+}
+
+/// [`is_route_attribute`] will check if `attr` is one of the `#[http_*]` attributes,
+/// such as `#[http_get]` or `#[http_attributes::http_raw_post]`.
+fn is_route_attribute(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident.to_string().starts_with("http_"))
+}
+
+/// [`is_multi_method_attribute`] will check if `attr` is `#[http_route]` or
+/// `#[http_raw_route]`, which create a [`Route`](http::route::Route) for each of
+/// their methods.
+fn is_multi_method_attribute(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "http_route" || segment.ident == "http_raw_route")
+}
+
+/// [`join_paths`] will put `prefix` in front of `path` with a single `/` between
+/// them, so `"/users"` and `"/{id}"` give `"/users/{id}"`, and `"/users"` and `"/"`
+/// give `"/users"`.
+fn join_paths(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    if prefix.is_empty() {
+        return path.to_string();
+    }
+    match path.trim_start_matches('/') {
+        "" => prefix.to_string(),
+        path => format!("{prefix}/{path}"),
+    }
+}
+
+/// [`prefix_route_attribute`] will get a copy of the http attribute `attr` with its
+/// `path` replaced by `prefixed_path`, whether it was the first argument or a
+/// `path` option.
+fn prefix_route_attribute(
+    attr: &Attribute,
+    path: &LitStr,
+    prefixed_path: &LitStr,
+) -> syn::Result<Attribute> {
+    let list = attr.meta.require_list()?;
+    let path_literal = path.to_token_stream().to_string();
+    let mut tokens: Vec<TokenTree> = list.tokens.clone().into_iter().collect();
+    let path_index = tokens.iter().enumerate().position(|(index, token)| {
+        let is_path =
+            matches!(token, TokenTree::Literal(literal) if literal.to_string() == path_literal);
+        let follows_path_option = index >= 2
+            && matches!(&tokens[index - 1], TokenTree::Punct(punct) if punct.as_char() == '=')
+            && matches!(&tokens[index - 2], TokenTree::Ident(ident) if ident == "path");
+        is_path && (index == 0 || follows_path_option)
+    });
+    let Some(path_index) = path_index else {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "can't find the path of this route",
+        ));
+    };
+    tokens[path_index] = TokenTree::Literal(prefixed_path.token());
+    let attr_path = &list.path;
+    let tokens: TokenStream = tokens.into_iter().collect();
+    Ok(syn::parse_quote! { #[#attr_path(#tokens)] })
+}
+
+/// [`replace_self`] will replace every `Self` in `tokens` with `self_ty`, since a
+/// handler moved out of its `impl` block can't refer to `Self` anymore.
+fn replace_self(tokens: TokenStream, self_ty: &TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => self_ty.clone(),
+            TokenTree::Group(group) => {
+                let mut replaced = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_self(group.stream(), self_ty),
+                );
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
+            }
+            token => token.into(),
+        })
+        .collect()
+}
@@ -0,0 +1,82 @@
+use crate::transform::doc_comment;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+/// [`QUERY_ATTRIBUTE`] is the name of the attribute that marks the handler argument
+/// the query parameters bind into, such as `#[query] params: SearchParams`.
+pub(crate) const QUERY_ATTRIBUTE: &str = "query";
+
+/// [`derive_from_query`] will implement [`FromQuery`](http::params::FromQuery) for
+/// the struct in `input`, parsing every named field from the query parameter of the
+/// same name, and describing each with its doc comment.
+pub(crate) fn derive_from_query(input: DeriveInput) -> TokenStream {
+    let struct_ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input.ident,
+                    "FromQuery can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "FromQuery can only be derived for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let field_bindings = fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        let field_type = &field.ty;
+        quote! {
+            #field_ident: request
+                .query_param(stringify!(#field_ident))
+                .ok_or_else(|| format!("missing query parameter {}", stringify!(#field_ident)))?
+                .parse::<#field_type>()
+                .map_err(|_| format!(
+                    "invalid query parameter {}: expected {}",
+                    stringify!(#field_ident),
+                    stringify!(#field_type)
+                ))?
+        }
+    });
+    let route_params = fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        let field_type = &field.ty;
+        let field_description =
+            doc_comment(&field.attrs).map(|description| quote! { .with_description(#description) });
+        quote! {
+            http::validation::RouteParam::new(
+                stringify!(#field_ident),
+                http::validation::ParamLocation::Query,
+                stringify!(#field_type),
+            )#field_description
+            .with_type_check(|value: &str| value.parse::<#field_type>().is_ok())
+        }
+    });
+
+    quote! {
+        impl #impl_generics http::params::FromQuery for #struct_ident #type_generics #where_clause {
+            fn from_query(request: &http::request::Request) -> Result<Self, String> {
+                Ok(#struct_ident {
+                    #(#field_bindings),*
+                })
+            }
+
+            fn route_params() -> Vec<http::validation::RouteParam> {
+                vec![#(#route_params),*]
+            }
+        }
+    }
+    .into()
+}
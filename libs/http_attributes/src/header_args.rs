@@ -0,0 +1,110 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, LitStr};
+
+/// [`HEADER_ATTRIBUTE`] is the name of the attribute that marks a handler argument
+/// that is read from a request header, such as `#[header("X-Api-Key")] key: String`.
+pub(crate) const HEADER_ATTRIBUTE: &str = "header";
+
+/// [`COOKIE_ATTRIBUTE`] is the name of the attribute that marks a handler argument
+/// that is read from a cookie of the `Cookie` header, such as
+/// `#[cookie("session_id")] id: String`.
+pub(crate) const COOKIE_ATTRIBUTE: &str = "cookie";
+
+/// [`HeaderArg`] represents a handler argument that is read from the request
+/// headers, with a `#[header("...")]` or a `#[cookie("...")]` attribute.
+#[derive(Clone)]
+pub(crate) enum HeaderArg {
+    /// [`HeaderArg::Header`] is the name of the request header the argument is
+    /// read from.
+    Header(LitStr),
+    /// [`HeaderArg::Cookie`] is the name of the cookie the argument is read from.
+    Cookie(LitStr),
+}
+
+impl HeaderArg {
+    /// [`HeaderArg::from_attributes`] will parse the `#[header("...")]` or
+    /// `#[cookie("...")]` attribute out of `attrs`, if there is one, and return
+    /// [`None`] if there isn't.
+    pub(crate) fn from_attributes(attrs: &[Attribute]) -> syn::Result<Option<Self>> {
+        let mut header_attrs = attrs.iter().filter(|attr| {
+            attr.path().is_ident(HEADER_ATTRIBUTE) || attr.path().is_ident(COOKIE_ATTRIBUTE)
+        });
+        let Some(attr) = header_attrs.next() else {
+            return Ok(None);
+        };
+        if let Some(duplicate) = header_attrs.next() {
+            return Err(syn::Error::new_spanned(
+                duplicate,
+                "a handler argument can only have one `#[header]` or `#[cookie]` attribute",
+            ));
+        }
+
+        let is_header = attr.path().is_ident(HEADER_ATTRIBUTE);
+        let kind = if is_header { "header" } else { "cookie" };
+        let name: LitStr = attr.parse_args().map_err(|_| {
+            syn::Error::new_spanned(
+                attr,
+                if is_header {
+                    "a `#[header]` attribute needs the name of the header, such as `#[header(\"X-Api-Key\")]`"
+                } else {
+                    "a `#[cookie]` attribute needs the name of the cookie, such as `#[cookie(\"session_id\")]`"
+                },
+            )
+        })?;
+        if !is_header_name(&name.value()) {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!("`{}` is not a valid {kind} name", name.value()),
+            ));
+        }
+        Ok(Some(if is_header {
+            HeaderArg::Header(name)
+        } else {
+            HeaderArg::Cookie(name)
+        }))
+    }
+
+    /// [`HeaderArg::name`] gets the name of the header or cookie.
+    pub(crate) fn name(&self) -> &LitStr {
+        match self {
+            HeaderArg::Header(name) | HeaderArg::Cookie(name) => name,
+        }
+    }
+
+    /// [`HeaderArg::kind`] gets what the argument is read from, which is `header`
+    /// or `cookie`.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            HeaderArg::Header(_) => "header",
+            HeaderArg::Cookie(_) => "cookie",
+        }
+    }
+
+    /// [`HeaderArg::location`] gets the
+    /// [`ParamLocation`](http::validation::ParamLocation) of the argument.
+    pub(crate) fn location(&self) -> TokenStream {
+        match self {
+            HeaderArg::Header(_) => quote! { http::validation::ParamLocation::Header },
+            HeaderArg::Cookie(_) => quote! { http::validation::ParamLocation::Cookie },
+        }
+    }
+
+    /// [`HeaderArg::lookup`] gets the expression that reads the value of the
+    /// argument from the request `req`, as an [`Option`] of a [`str`].
+    pub(crate) fn lookup(&self) -> TokenStream {
+        match self {
+            HeaderArg::Header(name) => quote! { req.header(#name) },
+            HeaderArg::Cookie(name) => quote! { req.cookie(#name) },
+        }
+    }
+}
+
+/// [`is_header_name`] will check if `name` is a valid HTTP header or cookie name,
+/// which is a token of letters, digits, and a few symbols.
+pub(crate) fn is_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|character| {
+            character.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(character)
+        })
+}
@@ -9,3 +9,13 @@ pub(crate) const PUT: &str = "PUT";
 
 /// [`DELETE`] is a [`str`] representation of the `DELETE` HTTP method.
 pub(crate) const DELETE: &str = "DELETE";
+
+/// [`HEAD`] is a [`str`] representation of the `HEAD` HTTP method.
+pub(crate) const HEAD: &str = "HEAD";
+
+/// [`OPTIONS`] is a [`str`] representation of the `OPTIONS` HTTP method.
+pub(crate) const OPTIONS: &str = "OPTIONS";
+
+/// [`ROUTE_METHODS`] is every HTTP method a `#[http_route]` can list in its
+/// `methods` option.
+pub(crate) const ROUTE_METHODS: &[&str] = &[GET, HEAD, POST, PUT, DELETE, OPTIONS];
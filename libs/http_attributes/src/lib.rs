@@ -5,27 +5,81 @@
 //! It provides helpful `#[proc_macro_attribute]` macro attributes to enable functions
 //! to return a [`Route`](http::route::Route) for various request types:
 //! - [`http_get`](macro@http_get)
+//! - [`http_head`](macro@http_head)
 //! - [`http_post`](macro@http_post)
 //! - [`http_put`](macro@http_put)
 //! - [`http_delete`](macro@http_delete)
+//! - [`http_options`](macro@http_options)
+//! - [`http_route`](macro@http_route), for several methods at once
 //!
 //! It also provides versions of all the previous macro attributes that allow full
 //! control of the response shape:
 //! - [`http_raw_get`](macro@http_raw_get)
+//! - [`http_raw_head`](macro@http_raw_head)
 //! - [`http_raw_post`](macro@http_raw_post)
 //! - [`http_raw_put`](macro@http_raw_put)
 //! - [`http_raw_delete`](macro@http_raw_delete)
+//! - [`http_raw_options`](macro@http_raw_options)
+//! - [`http_raw_route`](macro@http_raw_route)
+//!
+//! It also provides the [`require_header`](macro@require_header) attribute, to guard
+//! any of those routes with a request header.
+//!
+//! It also provides the [`controller`](macro@controller) attribute, to group the
+//! routes of an `impl` block under a shared path prefix.
+//!
+//! It also provides the [`PathParams`](derive@PathParams) derive macro, to bind every
+//! path parameter of a route into a single handler argument.
+//!
+//! It also provides the [`FromQuery`](derive@FromQuery) derive macro, to bind the
+//! query parameters of a route into a single handler argument.
+
+/// [`body_args`] contains the options of the `#[body]` attribute on a handler argument.
+pub(crate) mod body_args;
+
+/// [`controller`] contains the `#[controller]` attribute that groups the routes of
+/// an `impl` block under a shared prefix.
+pub(crate) mod controller;
+
+/// [`from_query`] contains the derive macro for binding query parameters into a
+/// struct.
+pub(crate) mod from_query;
+
+/// [`header_args`] contains the `#[header]` and `#[cookie]` attributes on a handler
+/// argument.
+pub(crate) mod header_args;
 
 /// [`http_methods`] contains [`str`] representations of all supported HTTP methods.
 pub(crate) mod http_methods;
 
+/// [`path_params`] contains the derive macro for binding every path parameter into
+/// a struct.
+pub(crate) mod path_params;
+
+/// [`route_args`] contains the arguments every http attribute accepts.
+pub(crate) mod route_args;
+
+/// [`route_guards`] contains the `#[require_header]` attribute that guards a route.
+pub(crate) mod route_guards;
+
+/// [`route_path`] contains the checks every route path of an http attribute has to
+/// pass at compile time.
+pub(crate) mod route_path;
+
 /// [`transform`] contains all functionality related to modifying [`proc_macro::TokenStream`] input.
 pub(crate) mod transform;
 
+/// [`validate_args`] contains the `#[validate]` attribute that checks a handler
+/// argument before the handler runs.
+pub(crate) mod validate_args;
+
 #[doc = r#"
 # http_get
 The [`http_get`](macro@http_get) attribute modifies the function that uses it inline
-to return a `GET` [`Route`](http::route::Route), as long as the function returns a [`String`].
+to return a `GET` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
 
 This attribute will ensure the [`Response`](http::response::Response) returns the
 matched [`Route`](http::route::Route) with the following shape:
@@ -70,21 +124,276 @@ fn get_squared_query_parameter(number: i32) -> String {
     squared_result.to_string()
 }
 ```
+
+## `GET` route that can fail
+[`http_get`](macro@http_get) can also be used on a function that returns a [`Result`],
+where an [`Err`] is answered with the [`Status`](http::status::Status) and body its
+[`ErrorMapper`](http::error::ErrorMapper) maps it to:
+```rust
+use http::{error::ErrorMapper, status::Status};
+use http_attributes::http_get;
+
+enum AppError {
+    UserNotFound,
+}
+
+impl ErrorMapper for AppError {
+    fn status(&self) -> Status {
+        match self {
+            AppError::UserNotFound => Status::NotFound,
+        }
+    }
+}
+
+// this returns a 404 NOT FOUND for every user besides "1".
+#[http_get("/user/{id}")]
+fn get_user(id: String) -> Result<String, AppError> {
+    if id == "1" {
+        Ok("\"evan\"".to_string())
+    } else {
+        Err(AppError::UserNotFound)
+    }
+}
+```
+
+## `GET` route that returns any `Respond`
+[`http_get`](macro@http_get) can also return any type that implements
+[`Respond`](http::respond::Respond), which is sent as its JSON:
+```rust
+use http::respond::Respond;
+use http_attributes::http_get;
+
+// this route returns "\"hello\"", a JSON string.
+#[http_get("/greeting")]
+fn get_greeting() -> impl Respond {
+    "hello"
+}
+```
+
+## `GET` route with a context
+[`http_get`](macro@http_get) can also pass a `&Context` argument, with the
+[`Request`](http::request::Request), its deadline, and the state every handler
+shares:
+```rust
+use http::context::Context;
+use http_attributes::http_get;
+
+// this route answers "GET /user/{id}" for the user, or "unknown" past its deadline.
+#[http_get("/user/{id}")]
+fn get_user(ctx: &Context) -> String {
+    match ctx.time_remaining() {
+        Some(remaining) if remaining.is_zero() => "\"unknown\"".to_string(),
+        _ => format!("\"{}: {}\"", ctx.scope, ctx.path_param("id").unwrap_or_default()),
+    }
+}
+```
+## `GET` route with header arguments
+[`http_get`](macro@http_get) can also read an argument from a request header with a
+`#[header]` attribute, answering with `400 BAD REQUEST` when the header is missing
+or can't be parsed:
+```rust
+use http_attributes::http_get;
+
+// this route listens for a GET request on the "/reports" path, and needs an
+// "X-Api-Key" header.
+#[http_get("/reports")]
+fn get_reports(#[header("X-Api-Key")] key: String) -> String {
+    format!("\"reports for {key}\"")
+}
+```
+
+## `GET` route with cookie arguments
+[`http_get`](macro@http_get) can also read an argument from a cookie with a
+`#[cookie]` attribute, answering with `400 BAD REQUEST` when the cookie is missing
+or can't be parsed:
+```rust
+use http_attributes::http_get;
+
+// this route listens for a GET request on the "/theme" path, and needs a "theme"
+// cookie.
+#[http_get("/theme")]
+fn get_theme(#[cookie("theme")] theme: String) -> String {
+    format!("\"{theme}\"")
+}
+```
+
+## `GET` route with validated arguments
+[`http_get`](macro@http_get) can also check arguments before the handler runs with a
+`#[validate]` attribute, which takes a `range` of values or a `length`, each with a
+`min`, a `max`, or both, answering with `422 UNPROCESSABLE ENTITY` and every
+violation when any argument breaks its rules:
+```rust
+use http_attributes::http_get;
+
+// this route listens for a GET request on a path such as "/users?page=2&name=evan",
+// where "page" has to be between 1 and 100, and "name" can't be empty.
+#[http_get("/users")]
+fn get_users(
+    #[validate(range(min = 1, max = 100))] page: u32,
+    #[validate(length(min = 1))] name: String,
+) -> String {
+    format!("\"{name} on page {page}\"")
+}
+```
+
+## `GET` route with static headers
+[`http_get`](macro@http_get) can also add the same headers to every response of a
+route with the `headers` option:
+```rust
+use http_attributes::http_get;
+
+// this route answers a GET request on the "/health" path, which is never cached.
+#[http_get("/health", headers(("Cache-Control", "no-store")))]
+fn health() -> String {
+    "\"healthy\"".to_string()
+}
+```
+
+## `GET` route with a concurrency limit
+[`http_get`](macro@http_get) can also limit how many requests a route serves at the
+same time with the `concurrency` option, where a request over the limit waits up to
+`queue_ms` for room before it is answered with `503 SERVICE UNAVAILABLE`:
+```rust
+use http_attributes::http_get;
+
+// this route builds at most two reports at the same time.
+#[http_get("/reports", concurrency = 2, queue_ms = 250)]
+fn get_reports() -> String {
+    "\"reports\"".to_string()
+}
+```
+
+## Invalid route paths
+A route path that could never match a request fails to compile, such as a path
+without its leading `/`:
+```compile_fail
+use http_attributes::http_get;
+
+// requests always start with "/", so this route would never be served.
+#[http_get("users/{id}")]
+fn get_user(id: u64) -> String {
+    format!("\"user {id}\"")
+}
+```
+
+A path with an empty segment fails to compile:
+```compile_fail
+use http_attributes::http_get;
+
+// the extra "/" leaves an empty segment no request path would have.
+#[http_get("/users//{id}")]
+fn get_user(id: u64) -> String {
+    format!("\"user {id}\"")
+}
+```
+
+A path parameter with an unbalanced brace fails to compile:
+```compile_fail
+use http_attributes::http_get;
+
+// "{id" is missing its closing "}".
+#[http_get("/users/{id")]
+fn get_user(id: u64) -> String {
+    format!("\"user {id}\"")
+}
+```
+
+A catch-all path parameter that isn't the last segment fails to compile:
+```compile_fail
+use http_attributes::http_get;
+
+// "{*path}" takes the rest of the path, so nothing is left for "raw".
+#[http_get("/files/{*path}/raw")]
+fn get_file(path: String) -> String {
+    format!("\"{path}\"")
+}
+```
+
+A path parameter name used more than once fails to compile:
+```compile_fail
+use http_attributes::http_get;
+
+// both path parameters would be bound to the same "id" argument.
+#[http_get("/users/{id}/friends/{id}")]
+fn get_friend(id: u64) -> String {
+    format!("\"friend {id}\"")
+}
+```
+
+A static segment with a character that has to be percent-encoded fails to compile:
+```compile_fail
+use http_attributes::http_get;
+
+// "<" and ">" are never sent unencoded, so this segment would never match.
+#[http_get("/users/<id>")]
+fn get_user(id: u64) -> String {
+    format!("\"user {id}\"")
+}
+```
+
+## Mismatched path parameters
+Every path parameter needs an argument of the same name, or a `#[path]` struct, to
+be bound to, so a path parameter without one fails to compile:
+```compile_fail
+use http_attributes::http_get;
+
+// "name" is a query parameter, so nothing gets the "id" path parameter.
+#[http_get("/users/{id}")]
+fn get_user(name: String) -> String {
+    format!("\"{name}\"")
+}
+```
+
+An argument with a `#[body]`, `#[header]`, or `#[cookie]` attribute reads its value
+from somewhere else, so it doesn't get the path parameter of its name either:
+```compile_fail
+use http_attributes::http_get;
+
+// "id" is read from the "X-User-Id" header, so nothing gets the "id" path parameter.
+#[http_get("/users/{id}")]
+fn get_user(#[header("X-User-Id")] id: u64) -> String {
+    format!("\"user {id}\"")
+}
+```
+
+A `#[path]` struct on a route without path parameters could never be bound, so it
+fails to compile as well:
+```compile_fail
+use http_attributes::{PathParams, http_get};
+
+#[derive(PathParams)]
+struct OrgRepo {
+    org: String,
+    repo: String,
+}
+
+// "/repos" has no path parameters to fill "org" and "repo" with.
+#[http_get("/repos")]
+fn get_repo(#[path] params: OrgRepo) -> String {
+    format!("\"{}/{}\"", params.org, params.repo)
+}
+```
+
+An argument without a path parameter of its name is a query parameter rather than a
+mistake, as in the `GET` route with query parameters above.
 "#]
 #[proc_macro_attribute]
 pub fn http_get(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let path_lit = syn::parse_macro_input!(attr as syn::LitStr);
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
     let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
-    transform::function_to_route(path_lit, input_fn, http_methods::GET, false)
+    transform::function_to_route(route_args, input_fn, http_methods::GET, false)
 }
 
 #[doc = r#"
 # http_raw_get
 The [`http_raw_get`](macro@http_raw_get) attribute modifies the function that uses it inline
-to return a `GET` [`Route`](http::route::Route), as long as the function returns a [`String`].
+to return a `GET` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
 
 This attribute will always return a [`Response`](http::response::Response) with the shape of
 the [`Route`](http::route::Route) result.
@@ -121,21 +430,38 @@ fn get_squared_query_parameter(number: i32) -> String {
     squared_result.to_string()
 }
 ```
+
+## `GET` route with a content type
+[`http_raw_get`](macro@http_raw_get) can also send a successful response as another
+content type than JSON, such as an HTML page, with the `content_type` option, while
+errors and responses that set their own `Content-Type` are sent as they are:
+```rust
+use http_attributes::http_raw_get;
+
+// this route answers a GET request on the "/page" path with an HTML page.
+#[http_raw_get("/page", content_type = "text/html; charset=utf-8")]
+fn get_page() -> String {
+    "<h1>Hello!</h1>".to_string()
+}
+```
 "#]
 #[proc_macro_attribute]
 pub fn http_raw_get(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let path_lit = syn::parse_macro_input!(attr as syn::LitStr);
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
     let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
-    transform::function_to_route(path_lit, input_fn, http_methods::GET, true)
+    transform::function_to_route(route_args, input_fn, http_methods::GET, true)
 }
 
 #[doc = r#"
 # http_post
 The [`http_post`](macro@http_post) attribute modifies the function that uses it inline
-to return a `POST` [`Route`](http::route::Route), as long as the function returns a [`String`].
+to return a `POST` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
 
 This attribute will ensure the [`Response`](http::response::Response) returns the
 matched [`Route`](http::route::Route) with the following shape:
@@ -165,21 +491,91 @@ fn some_request(content: String) -> String {
     format!("received {content} from POST!")
 }
 ```
+
+## `POST` route with form fields
+[`http_post`](macro@http_post) can also bind every argument to the form field of
+its name when the request is `application/x-www-form-urlencoded`, instead of giving
+the whole body to the first argument, answering with `400 BAD REQUEST` when a field
+is missing or can't be parsed:
+```rust
+use http_attributes::http_post;
+
+// this route reads "name=Evan&age=30" from a form body as two arguments.
+#[http_post("/signup")]
+fn signup(name: String, age: u32) -> String {
+    format!("\"{name} is {age}\"")
+}
+```
+
+## `POST` route with an uploaded file
+[`http_post`](macro@http_post) can also bind an argument of type
+[`Part`](http::multipart::Part) to the part of its name in a `multipart/form-data`
+body, with the filename, content type, and bytes of the uploaded file, answering
+with `400 BAD REQUEST` when the part is missing. The rest of the arguments are bound
+to the text parts of their names:
+```rust
+use http::multipart::Part;
+use http_attributes::http_post;
+
+// this route reads the "avatar" file and "user" field of a multipart upload.
+#[http_post("/avatar")]
+fn upload_avatar(user: String, avatar: Part) -> String {
+    format!("\"{user} uploaded {} bytes\"", avatar.bytes.len())
+}
+```
+
+## `POST` route with a success status
+[`http_post`](macro@http_post) can also answer with a success status besides
+`200 OK`, such as `201 CREATED`, with the `status` option, while errors keep their
+own status:
+```rust
+use http_attributes::http_post;
+
+// this route answers a POST request on the "/items" path with 201 CREATED.
+#[http_post("/items", status = 201)]
+fn create_item(item: String) -> String {
+    format!("\"created {item}\"")
+}
+```
+
+## `POST` route with the raw request
+[`http_post`](macro@http_post) can also pass the incoming
+[`Request`](http::request::Request) to a handler as-is, with an argument of that
+type, which is never extracted from the query or body:
+```rust
+use http::request::Request;
+use http_attributes::http_post;
+
+// this route answers a POST request on the "/echo" path with its method, path,
+// and body.
+#[http_post("/echo")]
+fn echo(request: Request) -> String {
+    format!(
+        "\"{} {} {}\"",
+        request.method,
+        request.path,
+        request.body_content.unwrap_or_default()
+    )
+}
+```
 "#]
 #[proc_macro_attribute]
 pub fn http_post(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let path_lit = syn::parse_macro_input!(attr as syn::LitStr);
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
     let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
-    transform::function_to_route(path_lit, input_fn, http_methods::POST, false)
+    transform::function_to_route(route_args, input_fn, http_methods::POST, false)
 }
 
 #[doc = r#"
 # http_raw_post
 The [`http_raw_post`](macro@http_raw_post) attribute modifies the function that uses it inline
-to return a `POST` [`Route`](http::route::Route), as long as the function returns a [`String`].
+to return a `POST` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
 
 This attribute will always return a [`Response`](http::response::Response) with the shape of
 the [`Route`](http::route::Route) result.
@@ -207,15 +603,18 @@ pub fn http_raw_post(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let path_lit = syn::parse_macro_input!(attr as syn::LitStr);
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
     let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
-    transform::function_to_route(path_lit, input_fn, http_methods::POST, true)
+    transform::function_to_route(route_args, input_fn, http_methods::POST, true)
 }
 
 #[doc = r#"
 # http_put
 The [`http_put`](macro@http_put) attribute modifies the function that uses it inline
-to return a `PUT` [`Route`](http::route::Route), as long as the function returns a [`String`].
+to return a `PUT` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
 
 This attribute will ensure the [`Response`](http::response::Response) returns the
 matched [`Route`](http::route::Route) with the following shape:
@@ -251,15 +650,18 @@ pub fn http_put(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let path_lit = syn::parse_macro_input!(attr as syn::LitStr);
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
     let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
-    transform::function_to_route(path_lit, input_fn, http_methods::PUT, false)
+    transform::function_to_route(route_args, input_fn, http_methods::PUT, false)
 }
 
 #[doc = r#"
 # http_raw_put
 The [`http_raw_put`](macro@http_raw_post) attribute modifies the function that uses it inline
-to return a `PUT` [`Route`](http::route::Route), as long as the function returns a [`String`].
+to return a `PUT` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
 
 This attribute will always return a [`Response`](http::response::Response) with the shape of
 the [`Route`](http::route::Route) result.
@@ -287,15 +689,18 @@ pub fn http_raw_put(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let path_lit = syn::parse_macro_input!(attr as syn::LitStr);
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
     let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
-    transform::function_to_route(path_lit, input_fn, http_methods::PUT, true)
+    transform::function_to_route(route_args, input_fn, http_methods::PUT, true)
 }
 
 #[doc = r#"
 # http_delete
 The [`http_delete`](macro@http_delete) attribute modifies the function that uses it inline
-to return a `DELETE` [`Route`](http::route::Route), as long as the function returns a [`String`].
+to return a `DELETE` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
 
 This attribute will ensure the [`Response`](http::response::Response) returns the
 matched [`Route`](http::route::Route) with the following shape:
@@ -331,15 +736,18 @@ pub fn http_delete(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let path_lit = syn::parse_macro_input!(attr as syn::LitStr);
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
     let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
-    transform::function_to_route(path_lit, input_fn, http_methods::DELETE, false)
+    transform::function_to_route(route_args, input_fn, http_methods::DELETE, false)
 }
 
 #[doc = r#"
 # http_raw_delete
 The [`http_raw_delete`](macro@http_raw_delete) attribute modifies the function that uses it inline
-to return a `DELETE` [`Route`](http::route::Route), as long as the function returns a [`String`].
+to return a `DELETE` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
 
 This attribute will always return a [`Response`](http::response::Response) with the shape of
 the [`Route`](http::route::Route) result.
@@ -367,7 +775,419 @@ pub fn http_raw_delete(
     attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let path_lit = syn::parse_macro_input!(attr as syn::LitStr);
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
+    let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
+    transform::function_to_route(route_args, input_fn, http_methods::DELETE, true)
+}
+
+#[doc = r#"
+# http_head
+The [`http_head`](macro@http_head) attribute modifies the function that uses it inline
+to return a `HEAD` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
+
+The [`Response`](http::response::Response) is built the same way as it is for
+[`http_get`](macro@http_get), but only its status line and headers are sent, with the
+`Content-Length` of the body it would have sent.
+
+To get a `HEAD` response with the `Content-Length` of only what would be returned in
+"content", use the [`http_raw_head`](macro@http_raw_head) attribute instead.
+
+# Examples
+## Basic `HEAD` route
+[`http_head`](macro@http_head) can be used to create a basic `HEAD` route which lets a
+client check that a user exists without downloading it:
+```rust
+use http_attributes::http_head;
+
+// this route listens for a HEAD request on the "/user/{id}" path, and
+// returns only the headers of the user.
+#[http_head("/user/{id}")]
+fn some_request(id: String) -> String {
+    format!("\"user {id}\"")
+}
+```
+"#]
+#[proc_macro_attribute]
+pub fn http_head(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
+    let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
+    transform::function_to_route(route_args, input_fn, http_methods::HEAD, false)
+}
+
+#[doc = r#"
+# http_raw_head
+The [`http_raw_head`](macro@http_raw_head) attribute modifies the function that uses it inline
+to return a `HEAD` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
+
+The [`Response`](http::response::Response) is built the same way as it is for
+[`http_raw_get`](macro@http_raw_get), but only its status line and headers are sent,
+with the `Content-Length` of the body it would have sent.
+
+To get a `HEAD` response with the `Content-Length` of a body that has more information,
+like "status" and "time", use the [`http_head`](macro@http_head) attribute instead.
+
+# Examples
+## Basic `HEAD` route
+[`http_raw_head`](macro@http_raw_head) can be used to create a basic `HEAD` route which
+lets a client check that a user exists without downloading it:
+```rust
+use http_attributes::http_raw_head;
+
+// this route listens for a HEAD request on the "/user/{id}" path, and
+// returns only the headers of the user.
+#[http_raw_head("/user/{id}")]
+fn some_request(id: String) -> String {
+    format!("\"user {id}\"")
+}
+```
+"#]
+#[proc_macro_attribute]
+pub fn http_raw_head(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
+    let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
+    transform::function_to_route(route_args, input_fn, http_methods::HEAD, true)
+}
+
+#[doc = r#"
+# http_options
+The [`http_options`](macro@http_options) attribute modifies the function that uses it inline
+to return an `OPTIONS` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
+
+The server answers `OPTIONS` requests for every known path on its own, so this
+attribute is only needed to send content with the answer, such as what a resource
+accepts. Unless the handler sets an `Allow` header itself, the server adds one
+listing every method registered on the requested path.
+
+This attribute will ensure the [`Response`](http::response::Response) returns the
+matched [`Route`](http::route::Route) with the following shape:
+```json
+{
+    "content": "...",
+    "status": "...",
+    "time": "...",
+    "header": "...",
+}
+```
+
+To get an `OPTIONS` response that is only what would be returned in "content", use the
+[`http_raw_options`](macro@http_raw_options) attribute instead.
+
+# Examples
+## Basic `OPTIONS` route
+[`http_options`](macro@http_options) can be used to describe what a resource accepts:
+```rust
+use http_attributes::http_options;
+
+// this route listens for an OPTIONS request on the "/upload" path, and
+// returns the content types it accepts.
+#[http_options("/upload")]
+fn some_request() -> String {
+    "[\"text/csv\",\"application/json\"]".to_string()
+}
+```
+"#]
+#[proc_macro_attribute]
+pub fn http_options(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
+    let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
+    transform::function_to_route(route_args, input_fn, http_methods::OPTIONS, false)
+}
+
+#[doc = r#"
+# http_raw_options
+The [`http_raw_options`](macro@http_raw_options) attribute modifies the function that uses it inline
+to return an `OPTIONS` [`Route`](http::route::Route), as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
+
+This attribute will always return a [`Response`](http::response::Response) with the shape of
+the [`Route`](http::route::Route) result, and the server adds an `Allow` header to it
+the same way it does for [`http_options`](macro@http_options).
+
+To get an `OPTIONS` response that has more information, like "status" and "time", use the
+[`http_options`](macro@http_options) attribute instead.
+
+# Examples
+## Basic `OPTIONS` route
+[`http_raw_options`](macro@http_raw_options) can be used to describe what a resource
+accepts:
+```rust
+use http_attributes::http_raw_options;
+
+// this route listens for an OPTIONS request on the "/upload" path, and
+// returns the content types it accepts.
+#[http_raw_options("/upload")]
+fn some_request() -> String {
+    "[\"text/csv\",\"application/json\"]".to_string()
+}
+```
+"#]
+#[proc_macro_attribute]
+pub fn http_raw_options(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
     let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
-    transform::function_to_route(path_lit, input_fn, http_methods::DELETE, true)
+    transform::function_to_route(route_args, input_fn, http_methods::OPTIONS, true)
+}
+
+#[doc = r#"
+# http_route
+The [`http_route`](macro@http_route) attribute modifies the function that uses it inline
+to return a [`Vec`] with a [`Route`](http::route::Route) for every method in its
+`methods` option, all served by the same function, as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
+
+The path is given as a `path` option, and every other option of the single-method
+attributes, such as `requires` or `instrument`, applies to every method. Arguments are
+bound for each method the same way the single-method attribute binds them, so the
+first argument of a `POST` that isn't a path parameter is the body, while it is a
+query parameter for a `GET`.
+
+This attribute will ensure the [`Response`](http::response::Response) returns the
+matched [`Route`](http::route::Route) with the following shape:
+```json
+{
+    "content": "...",
+    "status": "...",
+    "time": "...",
+    "header": "...",
+}
+```
+
+To get responses that are only what would be returned in "content", use the
+[`http_raw_route`](macro@http_raw_route) attribute instead.
+
+# Examples
+## `GET` and `POST` route
+[`http_route`](macro@http_route) can be used to serve a `GET` and a `POST` with one
+function:
+```rust
+use http_attributes::http_route;
+
+// this route listens for a GET request with a "name" query parameter, and
+// a POST request with a "name" body, on the "/greet" path.
+#[http_route(path = "/greet", methods = ["GET", "POST"])]
+fn greet(name: String) -> String {
+    format!("\"hello {name}\"")
+}
+```
+"#]
+#[proc_macro_attribute]
+pub fn http_route(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
+    let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
+    transform::function_to_method_routes(route_args, input_fn, false)
+}
+
+#[doc = r#"
+# http_raw_route
+The [`http_raw_route`](macro@http_raw_route) attribute modifies the function that uses it inline
+to return a [`Vec`] with a [`Route`](http::route::Route) for every method in its
+`methods` option, all served by the same function, as long as the function returns a [`String`] of JSON, any other type that implements
+[`Respond`](http::respond::Respond), which is sent as its
+[`Respond::get_json`](http::respond::Respond::get_json), or a [`Result`] of either
+and an error that implements [`ErrorMapper`](http::error::ErrorMapper).
+
+This attribute will always return a [`Response`](http::response::Response) with the shape of
+the [`Route`](http::route::Route) result.
+
+To get responses that have more information, like "status" and "time", use the
+[`http_route`](macro@http_route) attribute instead.
+
+# Examples
+## `PUT` and `DELETE` route
+[`http_raw_route`](macro@http_raw_route) can be used to serve a `PUT` and a `DELETE`
+with one function:
+```rust
+use http_attributes::http_raw_route;
+
+// this route listens for a PUT or a DELETE request on the "/items/{id}" path.
+#[http_raw_route(path = "/items/{id}", methods = ["PUT", "DELETE"])]
+fn change_item(id: String) -> String {
+    format!("\"changed {id}\"")
+}
+```
+"#]
+#[proc_macro_attribute]
+pub fn http_raw_route(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let route_args = syn::parse_macro_input!(attr as route_args::RouteArgs);
+    let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
+    transform::function_to_method_routes(route_args, input_fn, true)
+}
+
+#[doc = r#"
+# require_header
+The [`require_header`](macro@require_header) attribute guards a route created by any of
+the `#[http_*]` attributes, which answers every request without the header with
+`403 FORBIDDEN` before the handler is called. When a value is given, a request with a
+different value for the header is answered with `403 FORBIDDEN` as well. It can be
+written above or below the `#[http_*]` attribute, and more than one can be stacked.
+
+# Example
+[`require_header`](macro@require_header) can be used to keep a route to internal
+callers:
+```rust
+use http_attributes::{http_get, require_header};
+
+// this route listens for a GET request on the "/internal/stats" path, which has to
+// have an "X-Internal: true" header.
+#[require_header("X-Internal", "true")]
+#[http_get("/internal/stats")]
+fn get_stats() -> String {
+    "\"ok\"".to_string()
+}
+```
+"#]
+#[proc_macro_attribute]
+pub fn require_header(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input_fn = syn::parse_macro_input!(item as syn::ItemFn);
+    route_guards::stack_require_header(attr.into(), input_fn).into()
+}
+
+#[doc = r#"
+# controller
+The [`controller`](macro@controller) attribute groups the methods of an `impl` block
+that have an `#[http_*]` attribute, such as [`http_get`](macro@http_get) or
+[`http_post`](macro@http_post), under a shared path prefix. Every such method
+returns its [`Route`](http::route::Route) with the prefix in front of its path, and
+the block gets a `routes` function that returns all of them.
+
+The handlers are moved out of the `impl` block to become routes, so they can't take
+`self`, and `Self` in them stands for the type of the block.
+
+# Example
+[`controller`](macro@controller) can be used to serve every user route under
+"/users":
+```rust
+use http_attributes::{controller, http_get, http_post};
+
+struct Users;
+
+#[controller("/users")]
+impl Users {
+    // this route listens for a GET request on the "/users/{id}" path.
+    #[http_get("/{id}")]
+    fn get_user(id: u32) -> String {
+        format!("\"user {id}\"")
+    }
+
+    // this route listens for a POST request on the "/users" path.
+    #[http_post("/")]
+    fn create_user(name: String) -> String {
+        format!("\"created {name}\"")
+    }
+}
+
+fn get_endpoints() -> Vec<http::route::Route> {
+    Users::routes()
+}
+```
+"#]
+#[proc_macro_attribute]
+pub fn controller(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let prefix = syn::parse_macro_input!(attr as syn::LitStr);
+    let item_impl = syn::parse_macro_input!(item as syn::ItemImpl);
+    controller::impl_to_controller(prefix, item_impl).into()
+}
+
+#[doc = r#"
+# PathParams
+The [`PathParams`](derive@PathParams) derive macro implements
+[`PathParams`](http::params::PathParams) for a struct with named fields, so every
+path parameter of a route can bind into a single handler argument marked with
+`#[path]`. Every field is parsed from the path parameter of the same name.
+
+# Example
+[`PathParams`](derive@PathParams) can be used to keep the signature of a handler for
+a deeply nested path tidy:
+```rust
+use http_attributes::{PathParams, http_get};
+
+#[derive(PathParams)]
+struct OrgRepo {
+    org: String,
+    repo: String,
+}
+
+// this route listens for a GET request on a path such as "/orgs/rust/repos/cargo",
+// and returns "rust/cargo".
+#[http_get("/orgs/{org}/repos/{repo}")]
+fn get_repo(#[path] params: OrgRepo) -> String {
+    format!("{}/{}", params.org, params.repo)
+}
+```
+"#]
+#[proc_macro_derive(PathParams)]
+pub fn derive_path_params(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    path_params::derive_path_params(input)
+}
+
+#[doc = r#"
+# FromQuery
+The [`FromQuery`](derive@FromQuery) derive macro implements
+[`FromQuery`](http::params::FromQuery) for a struct with named fields, so the query
+parameters of a route can bind into a single handler argument marked with
+`#[query]`. Every field is parsed from the query parameter of the same name, and a
+request with a missing or invalid one is answered with `400 BAD REQUEST`.
+
+# Example
+[`FromQuery`](derive@FromQuery) can be used to keep the signature of a search
+handler tidy:
+```rust
+use http_attributes::{FromQuery, http_get};
+
+#[derive(FromQuery)]
+struct SearchParams {
+    term: String,
+    page: u32,
+}
+
+// this route listens for a GET request on a path such as "/search?term=rust&page=2",
+// and returns "rust on page 2".
+#[http_get("/search")]
+fn search(#[query] params: SearchParams) -> String {
+    format!("{} on page {}", params.term, params.page)
+}
+```
+"#]
+#[proc_macro_derive(FromQuery)]
+pub fn derive_from_query(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    from_query::derive_from_query(input)
 }
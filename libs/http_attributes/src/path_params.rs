@@ -0,0 +1,84 @@
+use crate::transform::doc_comment;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+/// [`PATH_ATTRIBUTE`] is the name of the attribute that marks the handler argument
+/// every path parameter binds into, such as `#[path] params: OrgRepo`.
+pub(crate) const PATH_ATTRIBUTE: &str = "path";
+
+/// [`derive_path_params`] will implement [`PathParams`](http::params::PathParams)
+/// for the struct in `input`, parsing every named field from the path parameter of
+/// the same name, and describing each with its doc comment.
+pub(crate) fn derive_path_params(input: DeriveInput) -> TokenStream {
+    let struct_ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input.ident,
+                    "PathParams can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "PathParams can only be derived for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let field_bindings = fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        let field_type = &field.ty;
+        quote! {
+            #field_ident: path_params
+                .get(stringify!(#field_ident))
+                .ok_or_else(|| format!("missing path parameter {}", stringify!(#field_ident)))?
+                .parse::<#field_type>()
+                .map_err(|_| format!(
+                    "path parameter {} must be of type {}",
+                    stringify!(#field_ident),
+                    stringify!(#field_type)
+                ))?
+        }
+    });
+    let route_params = fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        let field_type = &field.ty;
+        let field_description =
+            doc_comment(&field.attrs).map(|description| quote! { .with_description(#description) });
+        quote! {
+            http::validation::RouteParam::new(
+                stringify!(#field_ident),
+                http::validation::ParamLocation::Path,
+                stringify!(#field_type),
+            )#field_description
+            .with_type_check(|value: &str| value.parse::<#field_type>().is_ok())
+        }
+    });
+
+    quote! {
+        impl #impl_generics http::params::PathParams for #struct_ident #type_generics #where_clause {
+            fn from_path_params(
+                path_params: &std::collections::HashMap<String, String>,
+            ) -> Result<Self, String> {
+                Ok(#struct_ident {
+                    #(#field_bindings),*
+                })
+            }
+
+            fn route_params() -> Vec<http::validation::RouteParam> {
+                vec![#(#route_params),*]
+            }
+        }
+    }
+    .into()
+}
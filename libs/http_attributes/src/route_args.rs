@@ -0,0 +1,283 @@
+use crate::header_args::is_header_name;
+use proc_macro2::Span;
+use syn::{
+    Ident, LitInt, LitStr, Token,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+
+/// [`RouteArgs`] represents the arguments of an http attribute, which are the route
+/// path followed by any `key = "value"` options and flags, such as
+/// `#[http_get("/users", requires = "db")]`,
+/// `#[http_get("/debug/state", cfg = "debug-endpoints")]`,
+/// `#[http_post("/login", instrument, redact = "password")]`,
+/// `#[http_post("/items", status = 201)]`,
+/// `#[http_get("/health", headers(("Cache-Control", "no-store")))]`,
+/// `#[http_get("/reports", concurrency = 2, queue_ms = 250)]`,
+/// `#[http_get("/files/{name}", encoded_slashes)]`,
+/// `#[http_raw_get("/page", content_type = "text/html")]`, or
+/// `#[http_get("/version", generate_tests)]`. The path can also be given as a
+/// `path` option, which `#[http_route(path = "/thing", methods = ["GET", "POST"])]`
+/// does.
+pub(crate) struct RouteArgs {
+    /// [`RouteArgs::path`] is the path the route listens on.
+    pub(crate) path: LitStr,
+    /// [`RouteArgs::methods`] is every HTTP method from a `methods` option, which
+    /// only `#[http_route]` accepts.
+    pub(crate) methods: Vec<LitStr>,
+    /// [`RouteArgs::requires`] is the name of every dependency from a `requires`
+    /// option, which has to be available for the route to be served.
+    pub(crate) requires: Vec<LitStr>,
+    /// [`RouteArgs::cfg`] is the optional cargo feature from a `cfg` option, which
+    /// has to be enabled for the route to be compiled at all.
+    pub(crate) cfg: Option<LitStr>,
+    /// [`RouteArgs::instrument`] is whether the `instrument` flag was set, which
+    /// logs the arguments and elapsed time of every call to the handler.
+    pub(crate) instrument: bool,
+    /// [`RouteArgs::redact`] is the name of every handler argument from a `redact`
+    /// option, which is logged as redacted when the handler is instrumented.
+    pub(crate) redact: Vec<LitStr>,
+    /// [`RouteArgs::generate_tests`] is whether the `generate_tests` flag was set,
+    /// which emits a `#[cfg(test)]` module that checks the route and calls its
+    /// handler with the default of every argument, unless one is a `#[path]` or
+    /// `#[query]` struct or a streamed body, which have no default.
+    pub(crate) generate_tests: bool,
+    /// [`RouteArgs::status`] is the status code from a `status` option, which the
+    /// handler answers with instead of `200 OK` when it succeeds.
+    pub(crate) status: Option<u16>,
+    /// [`RouteArgs::headers`] is every `(name, value)` pair from a `headers`
+    /// option, which are added to every response of the route.
+    pub(crate) headers: Vec<(LitStr, LitStr)>,
+    /// [`RouteArgs::concurrency`] is the limit from a `concurrency` option, which is
+    /// how many requests the handler serves at the same time.
+    pub(crate) concurrency: Option<LitInt>,
+    /// [`RouteArgs::queue_ms`] is the milliseconds from a `queue_ms` option, which
+    /// is how long a request over the `concurrency` limit waits before it is
+    /// answered with `503 SERVICE UNAVAILABLE`.
+    pub(crate) queue_ms: Option<LitInt>,
+    /// [`RouteArgs::encoded_slashes`] is whether the `encoded_slashes` flag was set,
+    /// which lets a path parameter hold a `/` sent as `%2F`.
+    pub(crate) encoded_slashes: bool,
+    /// [`RouteArgs::content_type`] is the media type from a `content_type` option,
+    /// which successful responses of a raw route are sent as instead of JSON.
+    pub(crate) content_type: Option<LitStr>,
+}
+
+/// [`SUCCESS_STATUS_CODES`] is every status code the `status` option accepts,
+/// which are the success codes with a [`Status`](http::status::Status) of their
+/// own.
+const SUCCESS_STATUS_CODES: [u16; 4] = [200, 201, 202, 204];
+
+/// Implement [`Parse`] for [`RouteArgs`].
+impl Parse for RouteArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut path: Option<LitStr> = if input.peek(LitStr) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let mut methods = Vec::new();
+        let mut requires = Vec::new();
+        let mut cfg = None;
+        let mut instrument = false;
+        let mut redact = Vec::new();
+        let mut generate_tests = false;
+        let mut status = None;
+        let mut headers = Vec::new();
+        let mut concurrency = None;
+        let mut queue_ms = None;
+        let mut encoded_slashes = false;
+        let mut content_type: Option<LitStr> = None;
+        let mut needs_comma = path.is_some();
+        while !input.is_empty() {
+            if needs_comma {
+                input.parse::<Token![,]>()?;
+                if input.is_empty() {
+                    break;
+                }
+            }
+            needs_comma = true;
+
+            let key: Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "instrument" => instrument = true,
+                "generate_tests" => generate_tests = true,
+                "encoded_slashes" => encoded_slashes = true,
+                "methods" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    methods.extend(Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?);
+                }
+                "path" | "cfg"
+                    if (key == "path" && path.is_some()) || (key == "cfg" && cfg.is_some()) =>
+                {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!("the `{key}` option can only be set once"),
+                    ));
+                }
+                "path" => {
+                    input.parse::<Token![=]>()?;
+                    path = Some(input.parse()?);
+                }
+                "cfg" => {
+                    input.parse::<Token![=]>()?;
+                    cfg = Some(input.parse()?);
+                }
+                "requires" => {
+                    input.parse::<Token![=]>()?;
+                    requires.push(input.parse()?);
+                }
+                "redact" => {
+                    input.parse::<Token![=]>()?;
+                    redact.push(input.parse()?);
+                }
+                "status" if status.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        "the `status` option can only be set once",
+                    ));
+                }
+                "status" => {
+                    input.parse::<Token![=]>()?;
+                    let code: LitInt = input.parse()?;
+                    match code.base10_parse::<u16>() {
+                        Ok(code) if SUCCESS_STATUS_CODES.contains(&code) => status = Some(code),
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &code,
+                                "the `status` option must be a success status code, which is 200, 201, 202, or 204",
+                            ));
+                        }
+                    }
+                }
+                "headers" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    while !content.is_empty() {
+                        headers.push(parse_header(&content)?);
+                        if content.is_empty() {
+                            break;
+                        }
+                        content.parse::<Token![,]>()?;
+                    }
+                }
+                "concurrency" | "queue_ms"
+                    if (key == "concurrency" && concurrency.is_some())
+                        || (key == "queue_ms" && queue_ms.is_some()) =>
+                {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!("the `{key}` option can only be set once"),
+                    ));
+                }
+                "concurrency" => {
+                    input.parse::<Token![=]>()?;
+                    let limit: LitInt = input.parse()?;
+                    if !matches!(limit.base10_parse::<usize>(), Ok(limit) if limit > 0) {
+                        return Err(syn::Error::new_spanned(
+                            &limit,
+                            "the `concurrency` option must be a number of requests above 0",
+                        ));
+                    }
+                    concurrency = Some(limit);
+                }
+                "content_type" if content_type.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        "the `content_type` option can only be set once",
+                    ));
+                }
+                "content_type" => {
+                    input.parse::<Token![=]>()?;
+                    let media_type: LitStr = input.parse()?;
+                    let value = media_type.value();
+                    if !value.contains('/') || value.contains(['\r', '\n']) {
+                        return Err(syn::Error::new_spanned(
+                            &media_type,
+                            "the `content_type` option must be a media type on one line, such as \"text/html; charset=utf-8\"",
+                        ));
+                    }
+                    content_type = Some(media_type);
+                }
+                "queue_ms" => {
+                    input.parse::<Token![=]>()?;
+                    let milliseconds: LitInt = input.parse()?;
+                    milliseconds.base10_parse::<u64>()?;
+                    queue_ms = Some(milliseconds);
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!(
+                            "unknown http attribute option `{key}`, expected `path`, `methods`, `requires`, `cfg`, `instrument`, `redact`, `generate_tests`, `status`, `headers`, `concurrency`, `queue_ms`, `encoded_slashes`, or `content_type`"
+                        ),
+                    ));
+                }
+            }
+        }
+        let Some(path) = path else {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "an http attribute needs the path of the route, such as `#[http_get(\"/users\")]`",
+            ));
+        };
+        if let Some(redacted) = redact.first().filter(|_| !instrument) {
+            return Err(syn::Error::new_spanned(
+                redacted,
+                "the `redact` option only applies to routes with the `instrument` flag",
+            ));
+        }
+        if let Some(queue_ms) = queue_ms.as_ref().filter(|_| concurrency.is_none()) {
+            return Err(syn::Error::new_spanned(
+                queue_ms,
+                "the `queue_ms` option only applies to routes with a `concurrency` option",
+            ));
+        }
+
+        Ok(RouteArgs {
+            path,
+            methods,
+            requires,
+            cfg,
+            instrument,
+            redact,
+            generate_tests,
+            status,
+            headers,
+            concurrency,
+            queue_ms,
+            encoded_slashes,
+            content_type,
+        })
+    }
+}
+
+/// [`parse_header`] will parse a single `("Name", "value")` pair of a `headers`
+/// option, which fails to compile if the name is not a valid header name, or the
+/// value holds a line break.
+fn parse_header(input: ParseStream) -> syn::Result<(LitStr, LitStr)> {
+    let pair;
+    syn::parenthesized!(pair in input);
+    let name: LitStr = pair.parse()?;
+    pair.parse::<Token![,]>()?;
+    let value: LitStr = pair.parse()?;
+    if !pair.is_empty() {
+        return Err(pair.error("a header is a `(\"Name\", \"value\")` pair"));
+    }
+
+    if !is_header_name(&name.value()) {
+        return Err(syn::Error::new_spanned(
+            &name,
+            format!("`{}` is not a valid header name", name.value()),
+        ));
+    }
+    if value.value().contains(['\r', '\n']) {
+        return Err(syn::Error::new_spanned(
+            &value,
+            "a header value cannot contain a line break",
+        ));
+    }
+    Ok((name, value))
+}
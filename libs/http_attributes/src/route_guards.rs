@@ -0,0 +1,123 @@
+use crate::{header_args::is_header_name, transform::reject_request};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, ItemFn, LitStr, Token, parse::Parse, parse::ParseStream};
+
+/// [`REQUIRE_HEADER_ATTRIBUTE`] is the name of the attribute that guards a route
+/// with a request header, such as `#[require_header("X-Internal", "true")]`.
+pub(crate) const REQUIRE_HEADER_ATTRIBUTE: &str = "require_header";
+
+/// [`RequiredHeader`] represents a `#[require_header]` attribute, which answers
+/// every request without the header, or with a different value, with
+/// `403 FORBIDDEN` before the handler is called.
+pub(crate) struct RequiredHeader {
+    /// [`RequiredHeader::name`] is the name of the request header.
+    name: LitStr,
+    /// [`RequiredHeader::value`] is the value the request header has to have, or
+    /// [`None`] if any value will do.
+    value: Option<LitStr>,
+}
+
+/// Implement [`Parse`] for [`RequiredHeader`].
+impl Parse for RequiredHeader {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: LitStr = input.parse()?;
+        if !is_header_name(&name.value()) {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!("`{}` is not a valid header name", name.value()),
+            ));
+        }
+        let value = if input.is_empty() {
+            None
+        } else {
+            input.parse::<Token![,]>()?;
+            let value: LitStr = input.parse()?;
+            if value.value().contains(['\r', '\n']) {
+                return Err(syn::Error::new_spanned(
+                    &value,
+                    "a header value cannot contain a line break",
+                ));
+            }
+            Some(value)
+        };
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        if !input.is_empty() {
+            return Err(input.error(
+                "a `#[require_header]` attribute only takes the name of a header and its value",
+            ));
+        }
+        Ok(RequiredHeader { name, value })
+    }
+}
+
+impl RequiredHeader {
+    /// [`RequiredHeader::from_attributes`] will parse every `#[require_header]`
+    /// attribute out of `attrs`.
+    pub(crate) fn from_attributes(attrs: &[Attribute]) -> syn::Result<Vec<Self>> {
+        attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(REQUIRE_HEADER_ATTRIBUTE))
+            .map(|attr| {
+                attr.parse_args().map_err(|error| {
+                    syn::Error::new_spanned(
+                        attr,
+                        format!("{error}, such as `#[require_header(\"X-Internal\", \"true\")]`"),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// [`RequiredHeader::guard`] gets the statement that returns a `403 FORBIDDEN`
+    /// [`Response`](http::response::Response), finished with `finish_response`,
+    /// when the request `req` doesn't have the header.
+    pub(crate) fn guard(&self, is_raw: bool, finish_response: &TokenStream) -> TokenStream {
+        let name = &self.name;
+        let forbidden =
+            |message: TokenStream| reject_request("forbidden", message, is_raw, finish_response);
+        let missing = forbidden(quote! { concat!("missing header ", #name) });
+        match &self.value {
+            Some(value) => {
+                let mismatched = forbidden(quote! { concat!("mismatched header ", #name) });
+                quote! {
+                    match req.header(#name) {
+                        Some(#value) => {}
+                        Some(_) => #mismatched,
+                        None => #missing,
+                    }
+                }
+            }
+            None => quote! {
+                if req.header(#name).is_none() {
+                    #missing;
+                }
+            },
+        }
+    }
+}
+
+/// [`stack_require_header`] moves a `#[require_header]` attribute that was written
+/// above an `#[http_*]` attribute below it, so the `#[http_*]` attribute reads it no
+/// matter the order they were written in.
+pub(crate) fn stack_require_header(attr: TokenStream, mut item_fn: ItemFn) -> TokenStream {
+    let is_route = item_fn.attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident.to_string().starts_with("http_"))
+    });
+    if !is_route {
+        return syn::Error::new_spanned(
+            &item_fn.sig.ident,
+            "`#[require_header]` has to be used with an `#[http_*]` attribute, such as `#[http_get]`",
+        )
+        .to_compile_error();
+    }
+    item_fn
+        .attrs
+        .push(syn::parse_quote! { #[require_header(#attr)] });
+    quote! { #item_fn }
+}
@@ -0,0 +1,85 @@
+use syn::{Ident, LitStr};
+
+/// [`STATIC_SEGMENT_SYMBOLS`] is every character besides ASCII letters and digits a
+/// static segment of a route path can have, which are the characters a path
+/// segment can have without being percent-encoded.
+const STATIC_SEGMENT_SYMBOLS: &str = "-._~!$&'()*+,;=:@";
+
+/// [`validate_route_path`] will check the route path of an http attribute, `path`,
+/// so a route that could never match a request is a compile error, spanned on the
+/// path, instead. A path has to start with `/`, and can't have an empty segment,
+/// other than the one before its leading `/` or the path `/` itself, every `{` and
+/// `}` has to wrap a whole segment as `{name}`, or a final `{*name}`, where `name` is
+/// an identifier used only once, and a static segment can only have letters,
+/// digits, and [`STATIC_SEGMENT_SYMBOLS`].
+pub(crate) fn validate_route_path(path: &LitStr) -> syn::Result<()> {
+    let path_value = path.value();
+    let error = |message: String| Err(syn::Error::new_spanned(path, message));
+    if path_value.is_empty() {
+        return error("a route path can't be empty, use `/` for the root".to_string());
+    }
+    if !path_value.starts_with('/') {
+        return error(format!(
+            "route path `{path_value}` has to start with `/`, such as `/{path_value}`"
+        ));
+    }
+    if path_value == "/" {
+        return Ok(());
+    }
+
+    let segments: Vec<&str> = path_value.split('/').collect();
+    let mut param_names: Vec<&str> = Vec::new();
+    for (index, segment) in segments.iter().enumerate() {
+        // the only empty segment a route can match is the one before a leading `/`
+        if segment.is_empty() {
+            if index == 0 {
+                continue;
+            }
+            return error(format!(
+                "route path `{path_value}` has an empty segment, remove the extra `/`"
+            ));
+        }
+
+        if !segment.contains(['{', '}']) {
+            if let Some(invalid) = segment.chars().find(|character| {
+                !character.is_ascii_alphanumeric() && !STATIC_SEGMENT_SYMBOLS.contains(*character)
+            }) {
+                return error(format!(
+                    "route path `{path_value}` has an invalid character {invalid:?}, a segment can only have letters, digits, and `{STATIC_SEGMENT_SYMBOLS}`"
+                ));
+            }
+            continue;
+        }
+
+        let Some(param) = segment
+            .strip_prefix('{')
+            .and_then(|segment| segment.strip_suffix('}'))
+            .filter(|param| !param.contains(['{', '}']))
+        else {
+            return error(format!(
+                "route path `{path_value}` has a malformed path parameter `{segment}`, expected a whole segment of `{{name}}` or `{{*name}}`"
+            ));
+        };
+        let param_name = match param.strip_prefix('*') {
+            Some(_) if index != segments.len() - 1 => {
+                return error(format!(
+                    "route path `{path_value}` has the catch-all `{segment}` before its last segment"
+                ));
+            }
+            Some(param_name) => param_name,
+            None => param,
+        };
+        if syn::parse_str::<Ident>(param_name).is_err() {
+            return error(format!(
+                "route path `{path_value}` has a path parameter `{segment}` whose name isn't an identifier"
+            ));
+        }
+        if param_names.contains(&param_name) {
+            return error(format!(
+                "route path `{path_value}` has the path parameter `{param_name}` more than once"
+            ));
+        }
+        param_names.push(param_name);
+    }
+    Ok(())
+}
@@ -1,148 +1,1094 @@
-use http::methods::{POST, PUT};
+use crate::{
+    body_args::{BODY_ATTRIBUTE, BodyArgs},
+    from_query::QUERY_ATTRIBUTE,
+    header_args::{COOKIE_ATTRIBUTE, HEADER_ATTRIBUTE, HeaderArg},
+    http_methods::{HEAD, POST, PUT, ROUTE_METHODS},
+    path_params::PATH_ATTRIBUTE,
+    route_args::RouteArgs,
+    route_guards::{REQUIRE_HEADER_ATTRIBUTE, RequiredHeader},
+    route_path::validate_route_path,
+    validate_args::{VALIDATE_ATTRIBUTE, ValidateArgs},
+};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::Pat;
-use syn::{FnArg, Ident, ItemFn, PatType, ReturnType, Type};
+use syn::{
+    Attribute, Expr, ExprLit, FnArg, Ident, ItemFn, Lit, LitStr, Meta, MetaNameValue, PatType,
+    ReturnType, Token, Type, punctuated::Punctuated, spanned::Spanned,
+};
 
-/// [`function_to_route`] takes the parsed attribute, `path_lit`, and the original
+/// [`function_to_route`] takes the parsed attribute, `route_args`, and the original
 /// function, then reconstructs a new function in it's place that returns a
 /// [`Route`](http::route::Route).
 pub(crate) fn function_to_route(
-    path_lit: syn::LitStr,
+    route_args: RouteArgs,
     item_fn: ItemFn,
     method_str: &str,
     is_raw: bool,
 ) -> TokenStream {
+    if let Some(methods) = route_args.methods.first() {
+        return syn::Error::new_spanned(
+            methods,
+            "the `methods` option only applies to `#[http_route]`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    function_to_routes(
+        route_args,
+        item_fn,
+        &[method_str.to_string()],
+        is_raw,
+        false,
+    )
+}
+
+/// [`function_to_method_routes`] takes the parsed attribute of `#[http_route]`,
+/// `route_args`, and the original function, then reconstructs a new function in
+/// it's place that returns a [`Route`](http::route::Route) for every method in
+/// the `methods` option, all served by the original function.
+pub(crate) fn function_to_method_routes(
+    route_args: RouteArgs,
+    item_fn: ItemFn,
+    is_raw: bool,
+) -> TokenStream {
+    if route_args.methods.is_empty() {
+        return syn::Error::new_spanned(
+            &route_args.path,
+            "`#[http_route]` needs at least one method, such as `methods = [\"GET\", \"POST\"]`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let mut methods: Vec<String> = Vec::new();
+    for method in &route_args.methods {
+        let method_str = method.value();
+        if !ROUTE_METHODS.contains(&method_str.as_str()) {
+            return syn::Error::new_spanned(
+                method,
+                format!(
+                    "unknown HTTP method `{method_str}`, expected one of {}",
+                    ROUTE_METHODS.join(", ")
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+        if methods.contains(&method_str) {
+            return syn::Error::new_spanned(
+                method,
+                format!("the `{method_str}` method is listed more than once"),
+            )
+            .to_compile_error()
+            .into();
+        }
+        methods.push(method_str);
+    }
+    function_to_routes(route_args, item_fn, &methods, is_raw, true)
+}
+
+/// [`function_to_routes`] reconstructs the original function as a function that
+/// returns a [`Route`](http::route::Route) for every method in `methods`, or a
+/// [`Vec`] of them when `returns_many` is `true`.
+fn function_to_routes(
+    route_args: RouteArgs,
+    item_fn: ItemFn,
+    methods: &[String],
+    is_raw: bool,
+    returns_many: bool,
+) -> TokenStream {
+    // the name of a multi-method route stands for all of its methods
+    let attribute_method = if returns_many { "route" } else { &methods[0] };
+
     // validate the return type
-    if let Err(e) = validate_return_type(&item_fn, method_str) {
+    if let Err(e) = validate_return_type(&item_fn, attribute_method) {
         return e;
     }
 
+    // a route whose path could never match a request is a mistake, not a route
+    if let Err(error) = validate_route_path(&route_args.path) {
+        return error.to_compile_error().into();
+    }
+
     // get information from the original function to create the route handler
-    let mut had_body_arg = false;
-    let fn_attrs = item_fn.attrs;
+    let mut fn_attrs = item_fn.attrs;
     let fn_vis = item_fn.vis;
     let mut fn_sig = item_fn.sig;
     let fn_block = item_fn.block;
     let original_fn_ident = fn_sig.ident.clone();
-    let path_str_value = path_lit.value();
+    let path_str_value = route_args.path.value();
+    let requires = route_args.requires;
+    // a route behind a cargo feature is compiled out entirely when it is disabled
+    let cfg_attr = route_args
+        .cfg
+        .map(|feature| quote! { #[cfg(feature = #feature)] });
     let request_type_ident: Type = syn::parse_str("http::request::Request").unwrap();
-    let internal_fn_ident = generate_unique_internal_fn_name(method_str, &original_fn_ident);
+    let internal_fn_ident = generate_unique_internal_fn_name(attribute_method, &original_fn_ident);
     let path_param_names = extract_path_parameters(&path_str_value, &original_fn_ident);
 
     // extract original inputs, output, and generics for the internal function, these
     // are cloned these because `fn_sig` will be mutated for the public function.
-    let original_inputs = fn_sig.inputs.clone();
+    let mut original_inputs = fn_sig.inputs.clone();
     let original_output = fn_sig.output.clone();
+    let original_asyncness = fn_sig.asyncness;
     let original_generics = &fn_sig.generics;
+    let respond_conversion = respond_with_json(&original_output);
 
-    // iterate over the original function's arguments to generate extraction logic
-    let mut original_fn_call_args = Vec::new();
-    let mut extracted_arg_prelude = Vec::new();
+    // a `#[require_header]` attribute is only read by this macro, so it is removed
+    let required_headers = match RequiredHeader::from_attributes(&fn_attrs) {
+        Ok(required_headers) => required_headers,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    fn_attrs.retain(|attr| !attr.path().is_ident(REQUIRE_HEADER_ATTRIBUTE));
+
+    let redacted_names: Vec<String> = route_args.redact.iter().map(LitStr::value).collect();
+
+    // an argument with a `#[body]` attribute gets the body, instead of the first
+    // argument of a `POST` or `PUT` that isn't a path parameter
+    let mut body_args = Vec::new();
+    // an argument with a `#[header]` or a `#[cookie]` attribute gets the value of
+    // that request header or cookie
+    let mut header_args = Vec::new();
+    // an argument with a `#[validate]` attribute is checked before the handler runs
+    let mut validate_args = Vec::new();
     for arg in &original_inputs {
-        if let FnArg::Typed(PatType { pat, ty, .. }) = arg {
-            let param_ident = if let Pat::Ident(pat_ident) = &**pat {
-                pat_ident.ident.clone()
+        let attrs = match arg {
+            FnArg::Typed(PatType { attrs, .. }) => attrs.as_slice(),
+            FnArg::Receiver(_) => &[],
+        };
+        match BodyArgs::from_attributes(attrs) {
+            Ok(args) => body_args.push(args),
+            Err(error) => return error.to_compile_error().into(),
+        }
+        match HeaderArg::from_attributes(attrs) {
+            Ok(header_arg) => header_args.push(header_arg),
+            Err(error) => return error.to_compile_error().into(),
+        }
+        match ValidateArgs::from_attributes(attrs) {
+            Ok(args) => validate_args.push(args),
+            Err(error) => return error.to_compile_error().into(),
+        }
+    }
+    if let Some(second_body_arg) = original_inputs
+        .iter()
+        .zip(&body_args)
+        .filter(|(_, args)| args.is_some())
+        .nth(1)
+    {
+        return syn::Error::new_spanned(
+            second_body_arg.0,
+            "only one argument of an HTTP handler can have a `#[body]` attribute",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let has_body_attribute = body_args.iter().any(Option::is_some);
+
+    // a path parameter without an argument to bind it to, or a `#[path]` struct
+    // without path parameters to bind, is a mistake rather than a route
+    if let Err(error) = validate_path_arguments(
+        &route_args.path,
+        &path_param_names,
+        &original_inputs,
+        &body_args,
+        &header_args,
+    ) {
+        return error.to_compile_error().into();
+    }
+
+    // prepare everything the public function needs that doesn't depend on the method
+    let pattern_segments = split_path_pattern(&path_str_value);
+    let route_description =
+        doc_comment(&fn_attrs).map(|description| quote! { .with_description(#description) });
+    // a successful response is the JSON of the `Ok` type of a `Result`, so that is
+    // the shape a spec generator describes
+    let route_response = match &original_output {
+        ReturnType::Type(_, ty) => {
+            let response_type = ok_type(ty).unwrap_or(ty);
+            let status_code = route_args.status.unwrap_or(200);
+            Some(quote! { .with_response(#status_code, stringify!(#response_type)) })
+        }
+        ReturnType::Default => None,
+    };
+    // every method of a multi-method route gets a concurrency limit of its own
+    let concurrency_limit = route_args.concurrency.as_ref().map(|limit| {
+        let queue_ms = route_args
+            .queue_ms
+            .as_ref()
+            .map_or_else(|| quote! { 0 }, |queue_ms| quote! { #queue_ms });
+        quote! { .with_concurrency_limit(#limit, std::time::Duration::from_millis(#queue_ms)) }
+    });
+    let encoded_slashes = route_args
+        .encoded_slashes
+        .then(|| quote! { .with_encoded_slashes() });
+    // a wrapped response is always JSON, so only a raw route can be sent as anything
+    // else
+    if let Some(content_type) = route_args.content_type.as_ref().filter(|_| !is_raw) {
+        return syn::Error::new_spanned(
+            content_type,
+            "the `content_type` option only applies to raw routes, such as `#[http_raw_get]`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    // an error, or a response that names its own content type, is sent as it is
+    let with_content_type = route_args.content_type.as_ref().map(|content_type| {
+        quote! {
+            let response = if response.status.code() < 300
+                && response.get_header("Content-Type").is_none()
+            {
+                response.with_header("Content-Type", #content_type)
             } else {
-                return syn::Error::new_spanned(
+                response
+            };
+        }
+    });
+    let mut original_fn_call_args = Vec::new();
+    let mut default_call_args = Vec::new();
+    let mut has_default_args = true;
+    let mut route_exprs = Vec::new();
+
+    for method_str in methods.iter().map(String::as_str) {
+        // iterate over the original function's arguments to generate extraction logic
+        original_fn_call_args.clear();
+        default_call_args.clear();
+        has_default_args = true;
+        let mut extracted_arg_prelude = Vec::new();
+        let mut route_params = Vec::new();
+        let mut body_limit = None;
+        let mut streams_body = false;
+        let mut body_type = None;
+        let mut logged_arguments = Vec::new();
+        let mut validation_checks = Vec::new();
+        let method_ident = Ident::new(method_str, Span::call_site());
+        let with_headers = (!route_args.headers.is_empty()).then(|| {
+            let header_pairs = route_args
+                .headers
+                .iter()
+                .map(|(name, value)| quote! { (#name, #value) });
+            quote! { .with_headers(&[#(#header_pairs),*]) }
+        });
+        // a HEAD response keeps the Content-Length of its body, but never sends it
+        let omit_body = (method_str == HEAD).then(|| quote! { .without_body() });
+        let finish_response = quote! { #with_headers #omit_body };
+        // a request without a required header is turned away before any argument
+        // is extracted from it
+        extracted_arg_prelude.extend(
+            required_headers
+                .iter()
+                .map(|required_header| required_header.guard(is_raw, &finish_response)),
+        );
+        let mut had_body_arg = has_body_attribute;
+        let takes_context = original_inputs
+            .iter()
+            .any(|arg| matches!(arg, FnArg::Typed(PatType { ty, .. }) if is_context(ty)));
+
+        for (((arg, body_args), header_arg), validate_arg) in original_inputs
+            .iter()
+            .zip(body_args.iter().cloned())
+            .zip(&header_args)
+            .zip(&validate_args)
+        {
+            if let FnArg::Typed(PatType { attrs, pat, ty, .. }) = arg {
+                let param_ident = if let Pat::Ident(pat_ident) = &**pat {
+                    pat_ident.ident.clone()
+                } else {
+                    return syn::Error::new_spanned(
                     pat,
                     "Only identifier patterns are supported for function arguments in HTTP handlers",
                 ).to_compile_error().into();
-            };
+                };
+
+                // a `&Context` argument gets the request itself, so it is neither a
+                // parameter nor logged
+                if is_context(ty) {
+                    if body_args.is_some()
+                        || header_arg.is_some()
+                        || validate_arg.is_some()
+                        || attrs.iter().any(|attr| {
+                            attr.path().is_ident(PATH_ATTRIBUTE)
+                                || attr.path().is_ident(QUERY_ATTRIBUTE)
+                        })
+                    {
+                        return syn::Error::new_spanned(
+                            arg,
+                            "a `&Context` argument can't have a `#[path]`, `#[query]`, `#[body]`, `#[header]`, `#[cookie]`, or `#[validate]` attribute",
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                    original_fn_call_args.push(quote! { &__context });
+                    default_call_args.push(quote! { &Default::default() });
+                    continue;
+                }
 
-            if path_param_names.contains(&param_ident) {
-                extracted_arg_prelude.push(quote!{
-                    let #pat: #ty = req.path_params
-                        .get(stringify!(#param_ident))
-                        .expect(&format!("Missing path parameter: {}", stringify!(#param_ident)))
-                        .parse()
-                        .expect(&format!("Invalid path parameter type for {}: expected {}", stringify!(#param_ident), stringify!(#ty)));
+                // a `Request` argument gets the incoming request as-is, so it is
+                // neither a parameter nor logged
+                if let Some(by_reference) = request_type(ty) {
+                    if body_args.is_some()
+                        || header_arg.is_some()
+                        || validate_arg.is_some()
+                        || attrs.iter().any(|attr| {
+                            attr.path().is_ident(PATH_ATTRIBUTE)
+                                || attr.path().is_ident(QUERY_ATTRIBUTE)
+                        })
+                    {
+                        return syn::Error::new_spanned(
+                            arg,
+                            "a `Request` argument can't have a `#[path]`, `#[query]`, `#[body]`, `#[header]`, `#[cookie]`, or `#[validate]` attribute",
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                    if takes_context && !by_reference {
+                        return syn::Error::new_spanned(
+                            arg,
+                            "a handler that takes a `&Context` can only take the request as `&Request`, since the `Context` owns it",
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                    let default_request = quote! {
+                        http::request::Request::new(
+                            #path_str_value,
+                            http::methods::#method_ident,
+                            None,
+                            Default::default(),
+                        )
+                    };
+                    original_fn_call_args.push(match (by_reference, takes_context) {
+                        (true, true) => quote! { &__context.request },
+                        (true, false) => quote! { &req },
+                        (false, _) => quote! { req },
+                    });
+                    default_call_args.push(if by_reference {
+                        quote! { &#default_request }
+                    } else {
+                        default_request
+                    });
+                    continue;
+                }
+
+                // an argument with a `#[path]` attribute gets every path parameter
+                let is_path_struct = attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident(PATH_ATTRIBUTE));
+                // an argument with a `#[query]` attribute gets the query parameters
+                let is_query_struct = attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident(QUERY_ATTRIBUTE));
+                if [
+                    is_path_struct,
+                    is_query_struct,
+                    body_args.is_some(),
+                    header_arg.is_some(),
+                ]
+                .iter()
+                .filter(|has_attribute| **has_attribute)
+                .count()
+                    > 1
+                {
+                    return syn::Error::new_spanned(
+                        arg,
+                        "an argument can only have one of a `#[path]`, `#[query]`, `#[body]`, `#[header]`, or `#[cookie]` attribute",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                // an uploaded file is read from a multipart body, never the whole body
+                let is_file_arg = body_args.is_none()
+                    && header_arg.is_none()
+                    && !is_path_struct
+                    && !is_query_struct
+                    && is_part(ty);
+                // only a single parsed value has anything to check
+                if validate_arg.is_some()
+                    && (is_path_struct
+                        || is_query_struct
+                        || is_file_arg
+                        || body_args.as_ref().is_some_and(|body_args| body_args.stream))
+                {
+                    return syn::Error::new_spanned(
+                        arg,
+                        "a `#[validate]` attribute only applies to an argument parsed from a single value, not a `#[path]` or `#[query]` struct, a file part, or a streamed body",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                let is_body_arg = body_args.is_some()
+                    || ((method_str == POST || method_str == PUT)
+                        && !had_body_arg
+                        && !is_path_struct
+                        && !is_query_struct
+                        && !is_file_arg
+                        && header_arg.is_none()
+                        && !path_param_names.contains(&param_ident));
+                if is_body_arg {
+                    body_type = Some(quote! { .with_body_type(stringify!(#ty)) });
+                }
+                let param_location = if let Some(header_arg) = header_arg {
+                    header_arg.location()
+                } else if is_file_arg {
+                    quote! { http::validation::ParamLocation::File }
+                } else if path_param_names.contains(&param_ident) && !is_body_arg {
+                    quote! { http::validation::ParamLocation::Path }
+                } else if is_body_arg {
+                    quote! { http::validation::ParamLocation::Body }
+                } else {
+                    quote! { http::validation::ParamLocation::Query }
+                };
+                // a value the handler parses is validated by parsing it the same way,
+                // while a file part or a streamed body has nothing to parse
+                let type_check = (!is_file_arg
+                    && !body_args.as_ref().is_some_and(|body_args| body_args.stream))
+                .then(|| {
+                    quote! { .with_type_check(|value: &str| value.parse::<#ty>().is_ok()) }
                 });
-            } else if (method_str == POST || method_str == PUT) && !had_body_arg {
-                extracted_arg_prelude.push(quote! {
-                    let #pat: #ty = req.body_as_string()
-                        .expect("Failed to get request body as string")
-                        .parse()
-                        .expect("Failed to parse request body into expected type.");
+                route_params.push(if is_path_struct {
+                    quote! { <#ty as http::params::PathParams>::route_params() }
+                } else if is_query_struct {
+                    quote! { <#ty as http::params::FromQuery>::route_params() }
+                } else if let Some(header_arg) = header_arg {
+                    let header_name = header_arg.name();
+                    let param_description = doc_comment(attrs)
+                        .map(|description| quote! { .with_description(#description) });
+                    quote! {
+                        vec![http::validation::RouteParam::new(
+                            #header_name,
+                            #param_location,
+                            stringify!(#ty),
+                        )#param_description #type_check]
+                    }
+                } else {
+                    let param_description = doc_comment(attrs)
+                        .map(|description| quote! { .with_description(#description) });
+                    quote! {
+                        vec![http::validation::RouteParam::new(
+                            stringify!(#param_ident),
+                            #param_location,
+                            stringify!(#ty),
+                        )#param_description #type_check]
+                    }
                 });
-                had_body_arg = true;
-            } else {
-                // TODO: fix wrong query string parameter type (i.e.: /squared?number=AAA will crash the server)
-                extracted_arg_prelude.push(quote!{
-                    let #pat: #ty = req.query_param(stringify!(#param_ident))
-                        .and_then(|s| s.parse().ok())
-                        .expect(&format!("Missing or invalid query parameter: {}", stringify!(#param_ident)));
+
+                if let Some(header_arg) = header_arg {
+                    // a missing or unparseable header or cookie is answered with a 400
+                    let header_name = header_arg.name();
+                    let lookup = header_arg.lookup();
+                    let kind = header_arg.kind();
+                    let invalid = reject_request(
+                        "bad_request",
+                        quote! { concat!("invalid ", #kind, " ", #header_name, ": expected ", stringify!(#ty)) },
+                        is_raw,
+                        &finish_response,
+                    );
+                    let missing = reject_request(
+                        "bad_request",
+                        quote! { concat!("missing ", #kind, " ", #header_name) },
+                        is_raw,
+                        &finish_response,
+                    );
+                    extracted_arg_prelude.push(quote! {
+                        let #pat: #ty = match #lookup.map(str::parse) {
+                            Some(Ok(value)) => value,
+                            Some(Err(_)) => #invalid,
+                            None => #missing,
+                        };
+                    });
+                } else if is_file_arg {
+                    let missing = reject_request(
+                        "bad_request",
+                        quote! { concat!("missing file part ", stringify!(#param_ident)) },
+                        is_raw,
+                        &finish_response,
+                    );
+                    extracted_arg_prelude.push(quote! {
+                        let #pat: #ty = match req.multipart().and_then(|multipart| multipart.part(stringify!(#param_ident))) {
+                            Some(part) => part.clone(),
+                            None => #missing,
+                        };
+                    });
+                } else if is_path_struct {
+                    // a segment that doesn't parse doesn't name anything the route serves
+                    let invalid =
+                        reject_request("not_found", quote! { &message }, is_raw, &finish_response);
+                    extracted_arg_prelude.push(quote! {
+                        let #pat: #ty = match <#ty as http::params::PathParams>::from_path_params(&req.path_params) {
+                            Ok(value) => value,
+                            Err(message) => #invalid,
+                        };
+                    });
+                } else if is_query_struct {
+                    let invalid = reject_request(
+                        "bad_request",
+                        quote! { &message },
+                        is_raw,
+                        &finish_response,
+                    );
+                    extracted_arg_prelude.push(quote! {
+                        let #pat: #ty = match <#ty as http::params::FromQuery>::from_query(&req) {
+                            Ok(value) => value,
+                            Err(message) => #invalid,
+                        };
+                    });
+                } else if let Some(body_args) = body_args {
+                    body_limit = body_args.limit;
+                    streams_body = body_args.stream;
+                    extracted_arg_prelude.push(if body_args.stream {
+                        quote! {
+                            let #pat: #ty = req.take_body();
+                        }
+                    } else {
+                        parse_body(pat, ty, is_raw, &finish_response)
+                    });
+                } else if path_param_names.contains(&param_ident) {
+                    let invalid = reject_request(
+                        "not_found",
+                        quote! { concat!("invalid path parameter ", stringify!(#param_ident), ": expected ", stringify!(#ty)) },
+                        is_raw,
+                        &finish_response,
+                    );
+                    let missing = reject_request(
+                        "bad_request",
+                        quote! { concat!("missing path parameter ", stringify!(#param_ident)) },
+                        is_raw,
+                        &finish_response,
+                    );
+                    extracted_arg_prelude.push(quote! {
+                        let #pat: #ty = match req.path_params.get(stringify!(#param_ident)).map(|value| value.parse()) {
+                            Some(Ok(value)) => value,
+                            Some(Err(_)) => #invalid,
+                            None => #missing,
+                        };
+                    });
+                } else if (method_str == POST || method_str == PUT) && !had_body_arg {
+                    // a form binds the argument to the field of its name, rather than
+                    // the whole body
+                    let form_field = parse_form_field(&param_ident, ty, is_raw, &finish_response);
+                    let body = body_expr(ty, is_raw, &finish_response);
+                    extracted_arg_prelude.push(quote! {
+                        let #pat: #ty = if req.is_form() { #form_field } else { #body };
+                    });
+                    had_body_arg = true;
+                } else if method_str == POST || method_str == PUT {
+                    // a form field is preferred over a query parameter of the same name
+                    let form_field = parse_form_field(&param_ident, ty, is_raw, &finish_response);
+                    let query_param = parse_query_param(&param_ident, ty, is_raw, &finish_response);
+                    extracted_arg_prelude.push(quote! {
+                        let #pat: #ty = if req.form_param(stringify!(#param_ident)).is_some() {
+                            #form_field
+                        } else {
+                            #query_param
+                        };
+                    });
+                } else {
+                    let query_param = parse_query_param(&param_ident, ty, is_raw, &finish_response);
+                    extracted_arg_prelude.push(quote! {
+                        let #pat: #ty = #query_param;
+                    });
+                }
+                if let Some(validate_arg) = validate_arg {
+                    let name = match header_arg {
+                        Some(header_arg) => {
+                            let header_name = header_arg.name();
+                            quote! { #header_name }
+                        }
+                        None => quote! { stringify!(#param_ident) },
+                    };
+                    validation_checks.push(validate_arg.checks(
+                        &param_ident,
+                        &name,
+                        &param_location,
+                    ));
+                }
+                // a streamed body can only be read once, so it is never logged
+                let param_name = param_ident.to_string();
+                logged_arguments.push(if redacted_names.contains(&param_name) {
+                    quote! { format!("{}={}", stringify!(#param_ident), http::instrument::REDACTED) }
+                } else if streams_body && is_body_arg {
+                    quote! { format!("{}=<stream>", stringify!(#param_ident)) }
+                } else {
+                    quote! { format!("{}={:?}", stringify!(#param_ident), &#param_ident) }
                 });
+                original_fn_call_args.push(quote! { #param_ident });
+                // a `#[path]` or `#[query]` struct or a streamed body has no default,
+                // while any other type without one is an error spanned on its argument
+                if is_path_struct || is_query_struct || (streams_body && is_body_arg) {
+                    has_default_args = false;
+                } else {
+                    default_call_args.push(quote_spanned! { ty.span()=> Default::default() });
+                }
+            } else {
+                return syn::Error::new_spanned(
+                    arg,
+                    "Receiver arguments (like &self) are not supported in HTTP handlers.",
+                )
+                .to_compile_error()
+                .into();
             }
-            original_fn_call_args.push(quote! { #param_ident });
+        }
+
+        let request_binding = if streams_body {
+            quote! { mut req }
         } else {
-            return syn::Error::new_spanned(
-                arg,
-                "Receiver arguments (like &self) are not supported in HTTP handlers.",
-            )
-            .to_compile_error()
-            .into();
+            quote! { req }
+        };
+        let body_options = match (body_limit, streams_body) {
+            (Some(limit), true) => quote! { .with_body_limit(#limit).with_streamed_body() },
+            (Some(limit), false) => quote! { .with_body_limit(#limit) },
+            (None, true) => quote! { .with_streamed_body() },
+            (None, false) => quote! {},
+        };
+        // every broken rule is answered at once, before the handler runs
+        let validate_arguments = (!validation_checks.is_empty()).then(|| {
+            quote! {
+                let mut __violations: Vec<http::validation::Violation> = Vec::new();
+                #(#validation_checks)*
+                if !__violations.is_empty() {
+                    return http::response::Response::with_status(
+                        http::status::Status::UnprocessableEntity,
+                        &http::respond::Respond::get_json(&__violations),
+                        #is_raw,
+                    ) #finish_response;
+                }
+            }
+        });
+        // the context owns the request, so it is created once every other argument
+        // has been extracted from it
+        let context_binding = takes_context.then(|| {
+            quote! {
+                let __context = http::context::Context::new(
+                    req,
+                    concat!(#method_str, " ", #path_str_value),
+                );
+            }
+        });
+        let handler_span = route_args.instrument.then(|| {
+            quote! {
+                let _handler_span = http::instrument::HandlerSpan::enter(
+                    http::methods::#method_ident,
+                    #path_str_value,
+                    &<[String]>::join(&[#(#logged_arguments),*], ", "),
+                );
+            }
+        });
+        let call_handler = if original_asyncness.is_some() {
+            quote! { http::executor::block_on(#internal_fn_ident(#(#original_fn_call_args),*)) }
+        } else {
+            quote! { #internal_fn_ident(#(#original_fn_call_args),*) }
+        };
+        let into_response = match route_args.status {
+            Some(code) => quote! {
+                http::error::IntoResponse::into_response_with_status(
+                    content,
+                    http::status::Status::from_code(#code, ""),
+                    #is_raw,
+                )
+            },
+            None => quote! { http::error::IntoResponse::into_response(content, #is_raw) },
+        };
+        let handler_closure = quote! {
+            Box::new(
+                |#request_binding: #request_type_ident| -> http::response::Response {
+                    #(#extracted_arg_prelude)*
+                    #validate_arguments
+                    #context_binding
+                    #handler_span
+                    let content = #call_handler;
+                    #respond_conversion
+                    let response = #into_response;
+                    #with_content_type
+                    response #finish_response
+                }
+            ) as http::route::RouteHandler
+        };
+        route_exprs.push(quote! {
+            http::route::Route::from_segments(http::methods::#method_ident, #path_str_value, PATTERN_SEGMENTS, #handler_closure)
+                .with_params(<[Vec<http::validation::RouteParam>]>::concat(&[#(#route_params),*]))
+                .with_requirements(&[#(#requires),*])
+                #body_options
+                #concurrency_limit
+                #encoded_slashes
+                #route_description
+                #body_type
+                #route_response
+        });
+    }
+
+    // a redacted argument that doesn't exist is most likely a typo
+    if let Some(unknown) = route_args.redact.iter().find(|redacted| {
+        !original_fn_call_args
+            .iter()
+            .any(|arg| arg.to_string() == redacted.value())
+    }) {
+        return syn::Error::new_spanned(
+            unknown,
+            format!("`{}` is not an argument of this handler", unknown.value()),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // the `#[body]`, `#[path]`, `#[query]`, `#[header]`, `#[cookie]`, and `#[validate]` attributes and the doc comments of arguments are
+    // only read by this macro, so they are removed
+    for arg in original_inputs.iter_mut() {
+        if let FnArg::Typed(PatType { attrs, .. }) = arg {
+            attrs.retain(|attr| {
+                !attr.path().is_ident(BODY_ATTRIBUTE)
+                    && !attr.path().is_ident(PATH_ATTRIBUTE)
+                    && !attr.path().is_ident(QUERY_ATTRIBUTE)
+                    && !attr.path().is_ident(VALIDATE_ATTRIBUTE)
+                    && !attr.path().is_ident(HEADER_ATTRIBUTE)
+                    && !attr.path().is_ident(COOKIE_ATTRIBUTE)
+                    && !attr.path().is_ident("doc")
+            });
         }
     }
 
     // reconstruct the original function as an internal helper
     let original_fn_impl = quote! {
+        #cfg_attr
         #(#fn_attrs)*
-        #fn_vis fn #internal_fn_ident #original_generics (#original_inputs) #original_output #fn_block
+        #fn_vis #original_asyncness fn #internal_fn_ident #original_generics (#original_inputs) #original_output #fn_block
     };
 
     // prepare the public function's signature, note `fn_sig` is *mutated* to become the public signature.
+    // an `async fn` handler is driven on the worker, so the public function is never `async`
+    fn_sig.asyncness = None;
     fn_sig.inputs = syn::parse_quote! {};
-    fn_sig.output = syn::parse_quote! { -> http::route::Route };
-    let method_ident = syn::Ident::new(&method_str.to_lowercase(), Span::call_site());
-    let handler_closure = quote! {
-        Box::new(
-            |req: #request_type_ident| -> http::response::Response {
-                #(#extracted_arg_prelude)*
-                let content = #internal_fn_ident(#(#original_fn_call_args),*);
-                http::response::Response::ok(&content, #is_raw)
-            }
-        ) as http::route::RouteHandler
+    fn_sig.output = if returns_many {
+        syn::parse_quote! { -> Vec<http::route::Route> }
+    } else {
+        syn::parse_quote! { -> http::route::Route }
     };
+    // every route is created the same way, whether it is returned alone or not
+    let routes_expr = if returns_many {
+        quote! { vec![#(#route_exprs),*] }
+    } else {
+        quote! { #(#route_exprs)* }
+    };
+    let registered_routes = if returns_many {
+        quote! { #original_fn_ident }
+    } else {
+        quote! { || vec![#original_fn_ident()] }
+    };
+    let generated_tests = route_args.generate_tests.then(|| {
+        let tests_mod_ident = Ident::new(
+            &format!(
+                "__http_{}_tests_{}",
+                attribute_method.to_lowercase(),
+                original_fn_ident
+            ),
+            Span::call_site(),
+        );
+        let tested_routes = if returns_many {
+            quote! { super::#original_fn_ident() }
+        } else {
+            quote! { vec![super::#original_fn_ident()] }
+        };
+        let call_with_defaults = if original_asyncness.is_some() {
+            quote! { http::executor::block_on(super::#internal_fn_ident(#(#default_call_args),*)) }
+        } else {
+            quote! { super::#internal_fn_ident(#(#default_call_args),*) }
+        };
+        // a handler is only smoke tested when every argument has a default
+        let smoke_test = has_default_args.then(|| {
+            quote! {
+                #[test]
+                fn handler_should_respond_to_default_parameters() {
+                    let content = #call_with_defaults;
+
+                    let response = http::error::IntoResponse::into_response(content, #is_raw);
+
+                    assert!(!response.to_string().is_empty());
+                }
+            }
+        });
+        quote! {
+            #cfg_attr
+            #[cfg(test)]
+            mod #tests_mod_ident {
+                #[test]
+                fn route_should_have_pattern_and_method() {
+                    let routes: Vec<http::route::Route> = #tested_routes;
+
+                    for route in &routes {
+                        assert_eq!(#path_str_value, route.spec.request_pattern);
+                    }
+                    assert_eq!(
+                        vec![#(#methods),*],
+                        routes.iter().map(|route| route.spec.method.as_str()).collect::<Vec<_>>()
+                    );
+                }
+
+                #smoke_test
+            }
+        }
+    });
     let expanded = quote! {
         #original_fn_impl
 
+        #cfg_attr
         #(#fn_attrs)*
         #fn_vis #fn_sig {
-            http::route::Route::#method_ident(#path_str_value, #handler_closure)
+            const PATTERN_SEGMENTS: &[http::route::PatternSegment] = &[#(#pattern_segments),*];
+            #routes_expr
+        }
+
+        #cfg_attr
+        http::registry::inventory::submit! {
+            http::registry::RouteRegistration::new(#registered_routes)
         }
+
+        #generated_tests
     };
 
     // return the syntax tree as a TokenStream
     expanded.into()
 }
 
-/// [`validate_return_type`] generates a custom error message for the http attributes.
-fn validate_return_type(item_fn: &ItemFn, method: &str) -> Result<(), TokenStream> {
-    let original_return_type = match &item_fn.sig.output {
-        ReturnType::Type(_, ty) => ty,
-        _ => {
-            return Err(syn::Error::new_spanned(
-                &item_fn.sig.output,
-                format!("Functions marked with http_{} must have an explicit return type (e.g., `-> String`).", method),
-            )
-            .to_compile_error()
-            .into());
+/// [`reject_request`] gets the expression that returns the
+/// [`HttpError`](http::error::HttpError) created by its `constructor` with `message`
+/// from a handler, finished with `finish_response`, instead of calling the handler.
+pub(crate) fn reject_request(
+    constructor: &str,
+    message: proc_macro2::TokenStream,
+    is_raw: bool,
+    finish_response: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let constructor = Ident::new(constructor, Span::call_site());
+    quote! {
+        return http::error::ErrorMapper::to_response(
+            &http::error::HttpError::#constructor(#message),
+            #is_raw,
+        ) #finish_response
+    }
+}
+
+/// [`parse_body`] gets the statement that parses the body of the request `req` into
+/// the argument `pat` of type `ty`, where a missing body is answered with
+/// `400 BAD REQUEST` and a body that can't be parsed with `422 UNPROCESSABLE ENTITY`.
+fn parse_body(
+    pat: &Pat,
+    ty: &Type,
+    is_raw: bool,
+    finish_response: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let body = body_expr(ty, is_raw, finish_response);
+    quote! {
+        let #pat: #ty = #body;
+    }
+}
+
+/// [`body_expr`] gets the expression that parses the body of the request `req` into
+/// `ty`, as [`parse_body`] does.
+fn body_expr(
+    ty: &Type,
+    is_raw: bool,
+    finish_response: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let invalid = reject_request(
+        "unprocessable_entity",
+        quote! { concat!("invalid request body: expected ", stringify!(#ty)) },
+        is_raw,
+        finish_response,
+    );
+    let missing = reject_request(
+        "bad_request",
+        quote! { "missing request body" },
+        is_raw,
+        finish_response,
+    );
+    quote! {
+        match req.body_as_string().map(|body| body.parse()) {
+            Ok(Ok(value)) => value,
+            Ok(Err(_)) => #invalid,
+            Err(_) => #missing,
         }
-    };
+    }
+}
+
+/// [`parse_form_field`] gets the expression that parses the form field named
+/// `param_ident` of the request `req` into `ty`, where a missing or unparseable
+/// field is answered with `400 BAD REQUEST`.
+fn parse_form_field(
+    param_ident: &Ident,
+    ty: &Type,
+    is_raw: bool,
+    finish_response: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let invalid = reject_request(
+        "bad_request",
+        quote! { concat!("invalid form field ", stringify!(#param_ident), ": expected ", stringify!(#ty)) },
+        is_raw,
+        finish_response,
+    );
+    let missing = reject_request(
+        "bad_request",
+        quote! { concat!("missing form field ", stringify!(#param_ident)) },
+        is_raw,
+        finish_response,
+    );
+    quote! {
+        match req.form_param(stringify!(#param_ident)).map(|value| value.parse()) {
+            Some(Ok(value)) => value,
+            Some(Err(_)) => #invalid,
+            None => #missing,
+        }
+    }
+}
 
-    let expected_return_type: Type = syn::parse_quote! { String };
+/// [`parse_query_param`] gets the expression that parses the query parameter named
+/// `param_ident` of the request `req` into `ty`, where a missing or unparseable
+/// parameter is answered with `400 BAD REQUEST`.
+fn parse_query_param(
+    param_ident: &Ident,
+    ty: &Type,
+    is_raw: bool,
+    finish_response: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let invalid = reject_request(
+        "bad_request",
+        quote! { concat!("invalid query parameter ", stringify!(#param_ident), ": expected ", stringify!(#ty)) },
+        is_raw,
+        finish_response,
+    );
+    let missing = reject_request(
+        "bad_request",
+        quote! { concat!("missing query parameter ", stringify!(#param_ident)) },
+        is_raw,
+        finish_response,
+    );
+    quote! {
+        match req.query_param(stringify!(#param_ident)).map(str::parse) {
+            Some(Ok(value)) => value,
+            Some(Err(_)) => #invalid,
+            None => #missing,
+        }
+    }
+}
 
-    // Compare the concrete types (ignoring potential path/query params which aren't part of the type)
-    if quote! {#original_return_type}.to_string() != quote! {#expected_return_type}.to_string() {
-        return Err(syn::Error::new_spanned(
-            original_return_type,
+/// [`doc_comment`] will get the text of every doc comment in `attrs`, joined into
+/// lines, or [`None`] if there are no doc comments.
+pub(crate) fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }),
+                ..
+            }) => Some(line.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    let description = lines.join("\n").trim().to_string();
+    (!description.is_empty()).then_some(description)
+}
+
+/// [`split_path_pattern`] splits `path` on `/` into a
+/// [`PatternSegment`](http::route::PatternSegment) expression for each segment, so
+/// the generated [`Route`](http::route::Route) never splits its pattern at runtime.
+fn split_path_pattern(path: &str) -> Vec<proc_macro2::TokenStream> {
+    path.split('/')
+        .map(
+            |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                Some(param_name) => match param_name.strip_prefix('*') {
+                    Some(param_name) => quote! { http::route::PatternSegment::Rest(#param_name) },
+                    None => quote! { http::route::PatternSegment::Param(#param_name) },
+                },
+                None => quote! { http::route::PatternSegment::Static(#segment) },
+            },
+        )
+        .collect()
+}
+
+/// [`validate_path_arguments`] will check that every path parameter of `path`, in
+/// `path_param_names`, has an argument of `inputs` of the same name to be bound to,
+/// unless an argument gets them all as a `#[path]` struct, a `Request`, or a
+/// `&Context`. An argument with a `#[body]`, `#[header]`, or `#[cookie]` attribute
+/// doesn't get a path parameter of its name, so it doesn't count. A `#[path]`
+/// struct on a `path` without path parameters is an error as well, since it could
+/// never be bound. An argument without a path parameter of its name isn't checked,
+/// since it is bound to the query parameter of its name instead.
+fn validate_path_arguments(
+    path: &LitStr,
+    path_param_names: &[Ident],
+    inputs: &Punctuated<FnArg, Token![,]>,
+    body_args: &[Option<BodyArgs>],
+    header_args: &[Option<HeaderArg>],
+) -> syn::Result<()> {
+    let mut binds_every_param = false;
+    let mut bound_names = Vec::new();
+    for ((arg, body_args), header_arg) in inputs.iter().zip(body_args).zip(header_args) {
+        let FnArg::Typed(PatType { attrs, pat, ty, .. }) = arg else {
+            continue;
+        };
+        if attrs
+            .iter()
+            .any(|attr| attr.path().is_ident(PATH_ATTRIBUTE))
+        {
+            if path_param_names.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    format!(
+                        "a `#[path]` argument needs path parameters, but `{}` has none",
+                        path.value()
+                    ),
+                ));
+            }
+            binds_every_param = true;
+        } else if is_context(ty) || request_type(ty).is_some() {
+            binds_every_param = true;
+        } else if let Pat::Ident(pat_ident) = &**pat
+            && body_args.is_none()
+            && header_arg.is_none()
+            && !attrs
+                .iter()
+                .any(|attr| attr.path().is_ident(QUERY_ATTRIBUTE))
+        {
+            bound_names.push(pat_ident.ident.clone());
+        }
+    }
+    if binds_every_param {
+        return Ok(());
+    }
+
+    match path_param_names
+        .iter()
+        .find(|param_name| !bound_names.contains(param_name))
+    {
+        Some(param_name) => Err(syn::Error::new_spanned(
+            path,
             format!(
-                "http_{} functions must return `String`, but found `{}`",
-                method,
-                quote! {#original_return_type}
+                "the path parameter `{param_name}` of `{}` has no argument to bind it to, add an argument named `{param_name}`, or a `#[path]` struct",
+                path.value()
             ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// [`validate_return_type`] generates a custom error message for the http attributes,
+/// which accept functions with an explicit return type. Whether that type implements
+/// [`Respond`](http::respond::Respond) is left to the compiler, which points at the
+/// type itself when it doesn't.
+fn validate_return_type(item_fn: &ItemFn, method: &str) -> Result<(), TokenStream> {
+    if let ReturnType::Default = &item_fn.sig.output {
+        return Err(syn::Error::new_spanned(
+            &item_fn.sig.output,
+            format!("Functions marked with http_{} must have an explicit return type (e.g., `-> String`).", method),
         )
         .to_compile_error()
         .into());
@@ -150,6 +1096,103 @@ fn validate_return_type(item_fn: &ItemFn, method: &str) -> Result<(), TokenStrea
     Ok(())
 }
 
+/// [`respond_with_json`] will get the statement that turns the `content` a handler
+/// returned into its JSON with [`Respond::get_json`](http::respond::Respond::get_json),
+/// or the [`Ok`] value of it when the handler returns a [`Result`]. A [`String`] is
+/// already the JSON of the [`Response`](http::response::Response), so it is sent as-is.
+fn respond_with_json(return_type: &ReturnType) -> Option<proc_macro2::TokenStream> {
+    let ReturnType::Type(_, ty) = return_type else {
+        return None;
+    };
+    match ok_type(ty) {
+        Some(ok_type) if is_string(ok_type) => None,
+        Some(_) => Some(quote! {
+            let content = content.map(|value| http::respond::Respond::get_json(&value));
+        }),
+        None if is_string(ty) => None,
+        None => Some(quote! {
+            let content = http::respond::Respond::get_json(&content);
+        }),
+    }
+}
+
+/// [`is_string`] will check if `ty` is [`String`].
+fn is_string(ty: &Type) -> bool {
+    let expected_type: Type = syn::parse_quote! { String };
+    quote! {#ty}.to_string() == quote! {#expected_type}.to_string()
+}
+
+/// [`is_context`] will check if `ty` is a reference to a
+/// [`Context`](http::context::Context), such as `&Context` or
+/// `&http::context::Context`.
+fn is_context(ty: &Type) -> bool {
+    let Type::Reference(reference) = ty else {
+        return false;
+    };
+    let Type::Path(type_path) = &*reference.elem else {
+        return false;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Context")
+}
+
+/// [`is_part`] will check if `ty` is a [`Part`](http::multipart::Part), such as
+/// `Part` or `http::multipart::Part`.
+fn is_part(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Part" && segment.arguments.is_empty())
+}
+
+/// [`request_type`] will check if `ty` is a [`Request`](http::request::Request),
+/// such as `Request` or `&http::request::Request`, and get whether it is a
+/// reference, or [`None`] if it isn't a [`Request`](http::request::Request).
+fn request_type(ty: &Type) -> Option<bool> {
+    let (by_reference, ty) = match ty {
+        Type::Reference(reference) if reference.mutability.is_none() => (true, &*reference.elem),
+        ty => (false, ty),
+    };
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Request" && segment.arguments.is_empty())
+        .then_some(by_reference)
+}
+
+/// [`ok_type`] will get the [`Ok`] type of `ty` if it is a [`Result`] with an [`Ok`]
+/// and an [`Err`] type, and [`None`] otherwise.
+fn ok_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    if arguments.args.len() != 2 {
+        return None;
+    }
+    match arguments.args.first()? {
+        syn::GenericArgument::Type(ok_type) => Some(ok_type),
+        _ => None,
+    }
+}
+
 /// [`generate_unique_internal_fn_name`] creates a unique name for the generated internal
 /// function that will be maintained as a result of using any of the http attributes.
 fn generate_unique_internal_fn_name(method_str: &str, original_fn_ident: &Ident) -> Ident {
@@ -169,6 +1212,9 @@ fn extract_path_parameters(path_str_value: &String, original_fn_ident: &Ident) -
     path_str_value
         .split('/')
         .filter(|segment| segment.starts_with('{') && segment.ends_with('}'))
-        .map(|segment| Ident::new(&segment[1..segment.len() - 1], original_fn_ident.span()))
+        .map(|segment| {
+            let param_name = segment[1..segment.len() - 1].trim_start_matches('*');
+            Ident::new(param_name, original_fn_ident.span())
+        })
         .collect()
 }
@@ -0,0 +1,197 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Expr, Ident, Token, parenthesized,
+    parse::{Parse, ParseStream},
+};
+
+/// [`VALIDATE_ATTRIBUTE`] is the name of the attribute that checks the value of a
+/// handler argument before the handler runs, such as
+/// `#[validate(range(min = 1, max = 100))]`.
+pub(crate) const VALIDATE_ATTRIBUTE: &str = "validate";
+
+/// [`ValidationRule`] represents a single rule of a `#[validate(...)]` attribute.
+#[derive(Clone)]
+pub(crate) enum ValidationRule {
+    /// [`ValidationRule::Range`] checks the argument is between an inclusive `min`
+    /// and `max`, from a `range(min = 1, max = 100)` rule.
+    Range {
+        min: Option<Expr>,
+        max: Option<Expr>,
+    },
+    /// [`ValidationRule::Length`] checks the `len()` of the argument is between an
+    /// inclusive `min` and `max`, from a `length(min = 1, max = 64)` rule.
+    Length {
+        min: Option<Expr>,
+        max: Option<Expr>,
+    },
+}
+
+impl ValidationRule {
+    /// [`ValidationRule::check`] gets the statement that pushes a
+    /// [`Violation`](http::validation::Violation) for the parameter `name` read from
+    /// `location` onto `__violations` when the argument `param_ident` breaks the rule.
+    fn check(
+        &self,
+        param_ident: &Ident,
+        name: &TokenStream,
+        location: &TokenStream,
+    ) -> TokenStream {
+        let (value, min, max, messages) = match self {
+            ValidationRule::Range { min, max } => (
+                quote! { #param_ident },
+                min,
+                max,
+                [
+                    "must be between {} and {}",
+                    "must be at least {}",
+                    "must be at most {}",
+                ],
+            ),
+            ValidationRule::Length { min, max } => (
+                quote! { #param_ident.len() },
+                min,
+                max,
+                [
+                    "must have a length between {} and {}",
+                    "must have a length of at least {}",
+                    "must have a length of at most {}",
+                ],
+            ),
+        };
+        let [between, at_least, at_most] = messages;
+        // a rule always has at least one bound, which is checked when it is parsed
+        let (is_valid, message) = match (min, max) {
+            (Some(min), Some(max)) => (
+                quote! { (#min..=#max).contains(&#value) },
+                quote! { format!(#between, #min, #max) },
+            ),
+            (Some(min), None) => (
+                quote! { #value >= #min },
+                quote! { format!(#at_least, #min) },
+            ),
+            (None, max) => (
+                quote! { #value <= #max },
+                quote! { format!(#at_most, #max) },
+            ),
+        };
+        quote! {
+            if !(#is_valid) {
+                __violations.push(http::validation::Violation {
+                    parameter: #name.to_string(),
+                    location: #location,
+                    message: #message,
+                });
+            }
+        }
+    }
+}
+
+/// [`ValidateArgs`] represents every rule of the `#[validate(...)]` attributes on a
+/// handler argument.
+#[derive(Clone, Default)]
+pub(crate) struct ValidateArgs {
+    /// [`ValidateArgs::rules`] is every [`ValidationRule`], in the order they were
+    /// written.
+    pub(crate) rules: Vec<ValidationRule>,
+}
+
+impl ValidateArgs {
+    /// [`ValidateArgs::from_attributes`] will parse the rules of every
+    /// `#[validate(...)]` attribute out of `attrs`, and return [`None`] if there are
+    /// none.
+    pub(crate) fn from_attributes(attrs: &[Attribute]) -> syn::Result<Option<Self>> {
+        let mut validate_args = ValidateArgs::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(VALIDATE_ATTRIBUTE))
+        {
+            let parsed: ValidateArgs = attr.parse_args().map_err(|error| {
+                syn::Error::new(
+                    error.span(),
+                    format!(
+                        "{error}, such as `#[validate(range(min = 1, max = 100))]` or `#[validate(length(max = 64))]`"
+                    ),
+                )
+            })?;
+            validate_args.rules.extend(parsed.rules);
+        }
+        Ok((!validate_args.rules.is_empty()).then_some(validate_args))
+    }
+
+    /// [`ValidateArgs::checks`] gets the statements that push a
+    /// [`Violation`](http::validation::Violation) for every rule the argument
+    /// `param_ident` breaks, named `name` and read from `location`.
+    pub(crate) fn checks(
+        &self,
+        param_ident: &Ident,
+        name: &TokenStream,
+        location: &TokenStream,
+    ) -> TokenStream {
+        let checks = self
+            .rules
+            .iter()
+            .map(|rule| rule.check(param_ident, name, location));
+        quote! { #(#checks)* }
+    }
+}
+
+/// Implement [`Parse`] for [`ValidateArgs`].
+impl Parse for ValidateArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut rules = Vec::new();
+        while !input.is_empty() {
+            let rule: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let (mut min, mut max) = (None, None);
+            while !content.is_empty() {
+                let bound: Ident = content.parse()?;
+                content.parse::<Token![=]>()?;
+                let value: Expr = content.parse()?;
+                match bound.to_string().as_str() {
+                    "min" if min.is_none() => min = Some(value),
+                    "max" if max.is_none() => max = Some(value),
+                    "min" | "max" => {
+                        return Err(syn::Error::new_spanned(
+                            &bound,
+                            format!("`{bound}` can only be set once"),
+                        ));
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &bound,
+                            format!("unknown bound `{bound}`, expected `min` or `max`"),
+                        ));
+                    }
+                }
+                if content.is_empty() {
+                    break;
+                }
+                content.parse::<Token![,]>()?;
+            }
+            if min.is_none() && max.is_none() {
+                return Err(syn::Error::new_spanned(
+                    &rule,
+                    format!("`{rule}` needs a `min`, a `max`, or both"),
+                ));
+            }
+            rules.push(match rule.to_string().as_str() {
+                "range" => ValidationRule::Range { min, max },
+                "length" => ValidationRule::Length { min, max },
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &rule,
+                        format!("unknown validation rule `{rule}`, expected `range` or `length`"),
+                    ));
+                }
+            });
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(ValidateArgs { rules })
+    }
+}
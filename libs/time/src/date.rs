@@ -1,4 +1,13 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// [`WEEKDAYS`] is the abbreviated name of every day of the week, as written in
+/// an HTTP date.
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// [`MONTHS`] is the abbreviated name of every month, as written in an HTTP date.
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
 
 /// [`Date`] represents a human-readable interpretation of [`std::time::SystemTime`].
 #[derive(Clone, Debug, PartialEq)]
@@ -19,6 +28,116 @@ impl Date {
         }
     }
 
+    /// [`Date::format_http_date`] will format the provided [`SystemTime`] as an
+    /// HTTP date, such as `Sun, 06 Nov 1994 08:49:37 GMT`, dropping any fraction
+    /// of a second. Times before [`UNIX_EPOCH`] are formatted as [`UNIX_EPOCH`].
+    /// # Example
+    /// [`Date::format_http_date`] can be used to create a `Last-Modified` header value:
+    /// ```rust
+    /// use std::time::SystemTime;
+    /// use time::date::Date;
+    ///
+    /// fn last_modified(modified: SystemTime) -> String {
+    ///     format!("Last-Modified: {}", Date::format_http_date(modified))
+    /// }
+    /// ```
+    pub fn format_http_date(system_time: SystemTime) -> String {
+        let seconds = system_time
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let days_since_epoch = (seconds / 86400) as i64;
+        let remaining_seconds = seconds % 86400;
+        let (year, month, day) = Self::civil_from_days(days_since_epoch);
+
+        // 1/1/1970 was a thursday
+        let weekday = WEEKDAYS[((days_since_epoch + 3) % 7) as usize];
+        format!(
+            "{weekday}, {day:02} {} {year:04} {:02}:{:02}:{:02} GMT",
+            MONTHS[month as usize - 1],
+            remaining_seconds / 3600,
+            (remaining_seconds % 3600) / 60,
+            remaining_seconds % 60
+        )
+    }
+
+    /// [`Date::parse_http_date`] will parse an HTTP date in the preferred
+    /// `Sun, 06 Nov 1994 08:49:37 GMT` format into a [`SystemTime`], defaults to
+    /// [`None`] if the date is malformed.
+    /// # Example
+    /// [`Date::parse_http_date`] can be used to read an `If-Modified-Since` header value:
+    /// ```rust
+    /// use std::time::SystemTime;
+    /// use time::date::Date;
+    ///
+    /// fn modified_since(if_modified_since: &str) -> Option<SystemTime> {
+    ///     Date::parse_http_date(if_modified_since)
+    /// }
+    /// ```
+    pub fn parse_http_date(http_date: &str) -> Option<SystemTime> {
+        let (_, date) = http_date.trim().split_once(", ")?;
+        let parts: Vec<&str> = date.split(' ').collect();
+        if parts.len() != 5 || parts[4] != "GMT" {
+            return None;
+        }
+
+        let day: u32 = parts[0].parse().ok()?;
+        let month = MONTHS.iter().position(|month| *month == parts[1])? as u32 + 1;
+        let year: i32 = parts[2].parse().ok()?;
+        let time: Vec<u64> = parts[3]
+            .split(':')
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()?;
+        if day == 0
+            || day > Self::days_in_month_utc(year, month)
+            || time.len() != 3
+            || time[0] > 23
+            || time[1] > 59
+            || time[2] > 60
+        {
+            return None;
+        }
+
+        let days_since_epoch = u64::try_from(Self::days_from_civil(year, month, day)).ok()?;
+        let seconds = days_since_epoch * 86400 + time[0] * 3600 + time[1] * 60 + time[2];
+        Some(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+
+    /// [`Date::civil_from_days`] will return the year, month, and day of the month
+    /// that are `days_since_epoch` days after [`UNIX_EPOCH`].
+    fn civil_from_days(days_since_epoch: i64) -> (i32, u32, u32) {
+        let mut year = 1970;
+        let mut days = days_since_epoch;
+        while days >= Self::days_in_year(year) {
+            days -= Self::days_in_year(year);
+            year += 1;
+        }
+
+        let mut month = 1;
+        while days >= Self::days_in_month_utc(year, month).into() {
+            days -= i64::from(Self::days_in_month_utc(year, month));
+            month += 1;
+        }
+
+        (year, month, days as u32 + 1)
+    }
+
+    /// [`Date::days_from_civil`] will return how many days after [`UNIX_EPOCH`] the
+    /// provided `year`, `month`, and `day` of the month is, which is negative for
+    /// dates before [`UNIX_EPOCH`].
+    fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+        let days_in_years: i64 = if year >= 1970 {
+            (1970..year).map(Self::days_in_year).sum()
+        } else {
+            -(year..1970).map(Self::days_in_year).sum::<i64>()
+        };
+        let days_in_months: i64 = (1..month)
+            .map(|month| i64::from(Self::days_in_month_utc(year, month)))
+            .sum();
+
+        days_in_years + days_in_months + i64::from(day) - 1
+    }
+
     /// [`Date::get_seconds_elapsed_from_unix_epoch`] will return how many
     /// seconds have elapsed since [`SystemTime::UNIX_EPOCH`].
     fn get_seconds_elapsed_from_unix_epoch(time: SystemTime) -> u64 {
@@ -0,0 +1,174 @@
+use std::{fmt, fs, path::Path, thread};
+
+/// [`CGROUP_ROOT`] is where the cgroup filesystem is mounted in Linux containers.
+pub const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// [`WORKERS_PER_CPU`] is how many workers are created for every available CPU when
+/// the worker count is detected, since workers spend much of their time waiting on
+/// their connection.
+pub const WORKERS_PER_CPU: usize = 2;
+
+/// [`DEFAULT_MAX_BODY_BYTES`] is the largest request body accepted when the body
+/// size limit is detected and there is no memory limit.
+pub const DEFAULT_MAX_BODY_BYTES: usize = 8 * 1024 * 1024;
+
+/// [`MIN_MAX_BODY_BYTES`] is the smallest body size limit that is ever detected,
+/// no matter how little memory is available.
+pub const MIN_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// [`UNLIMITED_MEMORY_BYTES`] is the threshold above which a cgroup v1 memory limit
+/// is treated as no limit, since v1 reports "unlimited" as a huge page-aligned value.
+const UNLIMITED_MEMORY_BYTES: u64 = 1 << 60;
+
+/// [`ContainerLimits`] represents the CPU and memory limits a container runtime,
+/// such as Docker or Kubernetes, placed on the server through cgroups.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ContainerLimits {
+    /// [`ContainerLimits::cpus`] is how many CPUs the server may use, which can be
+    /// fractional, or [`None`] if there is no CPU limit.
+    pub cpus: Option<f64>,
+    /// [`ContainerLimits::memory_bytes`] is how many bytes of memory the server may
+    /// use, or [`None`] if there is no memory limit.
+    pub memory_bytes: Option<u64>,
+}
+
+impl ContainerLimits {
+    /// [`ContainerLimits::detect`] will read the [`ContainerLimits`] of the current
+    /// process from [`CGROUP_ROOT`], defaults to no limits outside of a container
+    /// or on platforms without cgroups.
+    /// # Example
+    /// [`ContainerLimits::detect`] can be used to check if the server is limited:
+    /// ```rust
+    /// use minimal_api::environment::container::ContainerLimits;
+    ///
+    /// fn is_limited() -> bool {
+    ///     ContainerLimits::detect() != ContainerLimits::default()
+    /// }
+    /// ```
+    pub fn detect() -> Self {
+        ContainerLimits::from_cgroup_root(CGROUP_ROOT)
+    }
+
+    /// [`ContainerLimits::from_cgroup_root`] will read the [`ContainerLimits`] from a
+    /// cgroup filesystem mounted at `root`, preferring the unified cgroup v2 files
+    /// (`cpu.max` and `memory.max`) over the cgroup v1 controllers.
+    /// # Example
+    /// [`ContainerLimits::from_cgroup_root`] can be used to read limits from a
+    /// non-standard mount:
+    /// ```rust
+    /// use minimal_api::environment::container::ContainerLimits;
+    ///
+    /// fn read_host_limits() -> ContainerLimits {
+    ///     ContainerLimits::from_cgroup_root("/host/sys/fs/cgroup")
+    /// }
+    /// ```
+    pub fn from_cgroup_root(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        let read = |file: &str| fs::read_to_string(root.join(file)).ok();
+
+        let cpus = match read("cpu.max") {
+            Some(cpu_max) => parse_cpu_max(&cpu_max),
+            None => read("cpu/cpu.cfs_quota_us")
+                .zip(read("cpu/cpu.cfs_period_us"))
+                .and_then(|(quota, period)| parse_cpu_quota(&quota, &period)),
+        };
+        let memory_bytes = match read("memory.max") {
+            Some(memory_max) => parse_memory_limit(&memory_max),
+            None => read("memory/memory.limit_in_bytes")
+                .as_deref()
+                .and_then(parse_memory_limit),
+        };
+
+        ContainerLimits { cpus, memory_bytes }
+    }
+
+    /// [`ContainerLimits::available_cpus`] will get the number of whole CPUs the
+    /// server can use, rounding a fractional [`ContainerLimits::cpus`] up, and never
+    /// more than the host has.
+    pub fn available_cpus(&self) -> usize {
+        let host_cpus = thread::available_parallelism().map_or(1, |cpus| cpus.get());
+        match self.cpus {
+            Some(cpus) => (cpus.ceil() as usize).clamp(1, host_cpus),
+            None => host_cpus,
+        }
+    }
+
+    /// [`ContainerLimits::default_workers`] will get the number of workers that fits
+    /// the available CPUs, which is [`WORKERS_PER_CPU`] for each of them.
+    /// # Example
+    /// [`ContainerLimits::default_workers`] can be used to size a thread pool so it
+    /// doesn't oversubscribe a container:
+    /// ```rust
+    /// use minimal_api::{environment::container::ContainerLimits, server::thread_pool::ThreadPool};
+    ///
+    /// fn create_sized_pool() -> ThreadPool {
+    ///     ThreadPool::new(ContainerLimits::detect().default_workers())
+    /// }
+    /// ```
+    pub fn default_workers(&self) -> usize {
+        self.available_cpus() * WORKERS_PER_CPU
+    }
+
+    /// [`ContainerLimits::default_max_body_bytes`] will get the largest request body
+    /// that `workers` can each hold at once while using at most a quarter of
+    /// [`ContainerLimits::memory_bytes`], between [`MIN_MAX_BODY_BYTES`] and
+    /// [`DEFAULT_MAX_BODY_BYTES`].
+    pub fn default_max_body_bytes(&self, workers: usize) -> usize {
+        match self.memory_bytes {
+            Some(memory_bytes) => {
+                let per_worker = memory_bytes / 4 / workers.max(1) as u64;
+                usize::try_from(per_worker)
+                    .unwrap_or(usize::MAX)
+                    .clamp(MIN_MAX_BODY_BYTES, DEFAULT_MAX_BODY_BYTES)
+            }
+            None => DEFAULT_MAX_BODY_BYTES,
+        }
+    }
+}
+
+/// Implement [`fmt::Display`] for [`ContainerLimits`], which summarizes every limit.
+impl fmt::Display for ContainerLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cpus {
+            Some(cpus) => write!(f, "{cpus} CPU(s) (cgroup limit)")?,
+            None => write!(f, "{} CPU(s) (host)", self.available_cpus())?,
+        }
+        match self.memory_bytes {
+            Some(memory_bytes) => write!(f, ", {} MiB memory (cgroup limit)", memory_bytes >> 20),
+            None => write!(f, ", no memory limit"),
+        }
+    }
+}
+
+/// [`parse_cpu_max`] will parse a cgroup v2 `cpu.max` value, such as `"150000 100000"`
+/// for 1.5 CPUs, defaults to [`None`] for `"max"`.
+fn parse_cpu_max(cpu_max: &str) -> Option<f64> {
+    let (quota, period) = cpu_max.trim().split_once(' ')?;
+    parse_cpu_quota(quota, period)
+}
+
+/// [`parse_cpu_quota`] will divide a CPU `quota` by its `period`, defaults to
+/// [`None`] when the quota is unlimited (`"max"` or `"-1"`).
+fn parse_cpu_quota(quota: &str, period: &str) -> Option<f64> {
+    let quota = quota
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|quota| *quota > 0.0)?;
+    let period = period
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|period| *period > 0.0)?;
+    Some(quota / period)
+}
+
+/// [`parse_memory_limit`] will parse a cgroup memory limit in bytes, defaults to
+/// [`None`] when the limit is `"max"` or too large to be a real limit.
+fn parse_memory_limit(limit: &str) -> Option<u64> {
+    limit
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|bytes| *bytes < UNLIMITED_MEMORY_BYTES)
+}
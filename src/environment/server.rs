@@ -1,4 +1,15 @@
-use std::env;
+use crate::{
+    environment::container::ContainerLimits,
+    server::{
+        acceptor::SocketOptions, access_control::AccessList, honeypot::HoneypotMode,
+        thread_pool::WorkerStart,
+    },
+};
+use http::{
+    audit::AuditRotation, compression::CompressionPolicy, parser::ParsingMode,
+    session::SessionBackend, tenant::TenantResolver,
+};
+use std::{env, path::PathBuf, time::Duration};
 
 /// [`ServerConfig`] represents a set of environmental server configurations.
 pub struct ServerConfig {
@@ -13,8 +24,148 @@ pub struct ServerConfig {
     /// [`ServerConfig::workers`] is the number of workers that the server has to
     /// handle requests (essentially individual threads dedicated to the server),
     /// which is set by the `WORKERS` value in the config files in the `.cargo`
-    /// directory.
+    /// directory. `"auto"` sizes it to the CPUs in [`ServerConfig::container_limits`].
     pub workers: usize,
+    /// [`ServerConfig::validate_requests`] is whether the server checks every request
+    /// against the parameters its route expects before running the route handler,
+    /// which is set by the `VALIDATE_REQUESTS` value in the config files in the
+    /// `.cargo` directory.
+    pub validate_requests: bool,
+    /// [`ServerConfig::parsing_mode`] is how strictly the server parses the request
+    /// line and headers of every request, which is set by the `PARSING_MODE` value
+    /// (`"strict"` or `"lenient"`) in the config files in the `.cargo` directory.
+    pub parsing_mode: ParsingMode,
+    /// [`ServerConfig::tcp_nodelay`] is whether `TCP_NODELAY` is set on every
+    /// connection, which is set by the `TCP_NODELAY` value in the config files in
+    /// the `.cargo` directory.
+    pub tcp_nodelay: bool,
+    /// [`ServerConfig::socket_linger`] is how long closing a connection waits for
+    /// unsent data, which is set by the `SOCKET_LINGER` value (a number of seconds,
+    /// or `"off"`) in the config files in the `.cargo` directory, and only applies
+    /// with the `socket-extras` cargo feature.
+    pub socket_linger: Option<Duration>,
+    /// [`ServerConfig::request_timeout`] is how long a handler has to serve a
+    /// request, which handlers read as the deadline of their
+    /// [`Context`](http::context::Context), and is set by the `REQUEST_TIMEOUT`
+    /// value (a number of seconds, or `"off"`) in the config files in the `.cargo`
+    /// directory.
+    pub request_timeout: Option<Duration>,
+    /// [`ServerConfig::acceptors`] is the number of listeners bound to the server
+    /// address with `SO_REUSEPORT`, each with its own accept thread and an equal
+    /// share of [`ServerConfig::workers`], which is set by the `ACCEPTORS` value in
+    /// the config files in the `.cargo` directory. `1` binds a single listener, and
+    /// more than `1` needs the `socket-extras` cargo feature.
+    pub acceptors: usize,
+    /// [`ServerConfig::priority_workers`] is the number of workers each acceptor
+    /// keeps for health and admin requests, which answer them while every other
+    /// worker is busy, and is set by the `PRIORITY_WORKERS` value in the config
+    /// files in the `.cargo` directory. `0` turns the priority lane off.
+    pub priority_workers: usize,
+    /// [`ServerConfig::worker_start`] is when the workers are started, which is set
+    /// by the `WORKER_START` value (`"eager"` or `"lazy"`) in the config files in the
+    /// `.cargo` directory. `"eager"` starts them all with the server and runs the
+    /// warm-up from [`get_warm_up`](crate::routes::index::get_warm_up) on each, and
+    /// `"lazy"` only starts one once every started worker is busy.
+    pub worker_start: WorkerStart,
+    /// [`ServerConfig::max_body_bytes`] is the largest request body the server will
+    /// read, which is set by the `MAX_BODY_BYTES` value in the config files in the
+    /// `.cargo` directory. `"auto"` sizes it to the memory in
+    /// [`ServerConfig::container_limits`].
+    pub max_body_bytes: usize,
+    /// [`ServerConfig::container_limits`] is the CPU and memory limits detected from
+    /// the container the server runs in, which `"auto"` values are based on.
+    pub container_limits: ContainerLimits,
+    /// [`ServerConfig::access_list`] is the [`AccessList`] every request is checked
+    /// against before it is routed, which is set by the comma separated CIDR ranges
+    /// of the `ALLOW_CIDRS` and `DENY_CIDRS` values in the config files in the
+    /// `.cargo` directory. Empty values allow every address.
+    pub access_list: AccessList,
+    /// [`ServerConfig::honeypot_mode`] is how requests for scanner paths are
+    /// answered, which is set by the `HONEYPOT` value (`"off"`, `"tarpit"`, or
+    /// `"junk"`) in the config files in the `.cargo` directory.
+    pub honeypot_mode: HoneypotMode,
+    /// [`ServerConfig::profiler_enabled`] is if the sampling profiler can be run
+    /// from its admin path, which is set by the `PROFILER` value in the config files
+    /// in the `.cargo` directory.
+    pub profiler_enabled: bool,
+    /// [`ServerConfig::metrics_enabled`] is if metrics, such as memory usage, are
+    /// served from their admin path, which is set by the `METRICS` value in the
+    /// config files in the `.cargo` directory.
+    pub metrics_enabled: bool,
+    /// [`ServerConfig::batch_concurrency`] is how many sub-requests of a batch sent to
+    /// the [`BATCH_PATH`](http::batch::BATCH_PATH) run at the same time, which is set
+    /// by the `BATCH` value (a number of sub-requests, or `"off"`) in the config
+    /// files in the `.cargo` directory. `"1"` runs them in order, and `"off"` leaves
+    /// batching off.
+    pub batch_concurrency: Option<usize>,
+    /// [`ServerConfig::chaos_enabled`] is if the faults from
+    /// [`get_chaos`](crate::routes::index::get_chaos) are injected into requests,
+    /// which is set by the `CHAOS` value in the config files in the `.cargo`
+    /// directory, and should only be `"true"` in staging.
+    pub chaos_enabled: bool,
+    /// [`ServerConfig::audit_log`] is the file every `POST`, `PUT`, and `DELETE`
+    /// request is audited to, apart from the application logs, which is set by the
+    /// `AUDIT_LOG` value (a path, or `"off"`) in the config files in the `.cargo`
+    /// directory.
+    pub audit_log: Option<PathBuf>,
+    /// [`ServerConfig::audit_rotation`] is when [`ServerConfig::audit_log`] is
+    /// rotated, which is set by the `AUDIT_LOG_MAX_BYTES` and `AUDIT_LOG_KEEP`
+    /// values in the config files in the `.cargo` directory.
+    pub audit_rotation: AuditRotation,
+    /// [`ServerConfig::session_backend`] is where sessions are kept between
+    /// requests, which is set by the `SESSION_STORE` value (`"memory"`,
+    /// `"file:<directory>"`, or `"tcp:<host>:<port>"`) in the config files in the
+    /// `.cargo` directory. Only the file and TCP stores keep sessions across a
+    /// restart.
+    pub session_backend: SessionBackend,
+    /// [`ServerConfig::session_ttl`] is how long a session lives, which is set by
+    /// the `SESSION_TTL` value (a number of seconds, or `"off"`) in the config
+    /// files in the `.cargo` directory. `"off"` keeps sessions until the client
+    /// closes.
+    pub session_ttl: Option<Duration>,
+    /// [`ServerConfig::session_sliding`] is whether every request renews its
+    /// session for another [`ServerConfig::session_ttl`], so only idle sessions
+    /// expire, which is set by the `SESSION_SLIDING` value in the config files in
+    /// the `.cargo` directory.
+    pub session_sliding: bool,
+    /// [`ServerConfig::session_secure`] is whether the session cookie has the
+    /// `Secure` attribute, so clients only send it over HTTPS, which is set by the
+    /// `SESSION_SECURE` value in the config files in the `.cargo` directory.
+    pub session_secure: bool,
+    /// [`ServerConfig::tenant_resolver`] is where the tenant of every request is
+    /// found, which is set by the `TENANTS` value (`"subdomain:<base domain>"`,
+    /// `"header:<name>"`, `"path"`, or `"off"`) in the config files in the `.cargo`
+    /// directory.
+    pub tenant_resolver: Option<TenantResolver>,
+    /// [`ServerConfig::propagated_headers`] is every header of a request that the
+    /// [`Client`](http::client::Client) calls made while serving it carry, such as
+    /// its request id and trace context, which is set by the comma separated
+    /// `PROPAGATE_HEADERS` value in the config files in the `.cargo` directory.
+    pub propagated_headers: Vec<String>,
+    /// [`ServerConfig::slow_request_threshold`] is how long a request can take
+    /// before a warning is logged for it, which is set by the `SLOW_REQUEST_MS`
+    /// value (a number of milliseconds, or `"off"`) in the config files in the
+    /// `.cargo` directory.
+    pub slow_request_threshold: Option<Duration>,
+    /// [`ServerConfig::large_response_threshold`] is how many bytes a response can
+    /// have before a warning is logged for it, which is set by the
+    /// `LARGE_RESPONSE_BYTES` value (a number of bytes, or `"off"`) in the config
+    /// files in the `.cargo` directory.
+    pub large_response_threshold: Option<usize>,
+    /// [`ServerConfig::compression_min_bytes`] is how big a response body has to be
+    /// to be compressed, which is set by the `COMPRESSION_MIN_BYTES` value in the
+    /// config files in the `.cargo` directory.
+    pub compression_min_bytes: usize,
+    /// [`ServerConfig::compression_excluded_types`] is every content type that is
+    /// never compressed, such as images that already are, which is set by the comma
+    /// separated `COMPRESSION_EXCLUDED_TYPES` value in the config files in the
+    /// `.cargo` directory.
+    pub compression_excluded_types: Vec<String>,
+    /// [`ServerConfig::compression_excluded_paths`] is every path that is never
+    /// compressed, along with every path below it, which is set by the comma
+    /// separated `COMPRESSION_EXCLUDED_PATHS` value in the config files in the
+    /// `.cargo` directory.
+    pub compression_excluded_paths: Vec<String>,
 }
 
 impl ServerConfig {
@@ -33,9 +184,147 @@ impl ServerConfig {
     pub fn new() -> Self {
         let ip_address = env!("IP_ADDR").to_string();
         let port = env!("PORT").to_string();
-        let workers = env!("WORKERS")
-            .parse::<usize>()
-            .expect("cannot parse WORKERS defined in .cargo/config.toml, please check the value.");
+        let container_limits = ContainerLimits::detect();
+        let workers = match env!("WORKERS") {
+            "auto" => container_limits.default_workers(),
+            workers => workers.parse::<usize>().expect(
+                "cannot parse WORKERS defined in .cargo/config.toml, please check the value.",
+            ),
+        };
+        let validate_requests = env!("VALIDATE_REQUESTS").parse::<bool>().expect(
+            "cannot parse VALIDATE_REQUESTS defined in .cargo/config.toml, please check the value.",
+        );
+        let parsing_mode = env!("PARSING_MODE").parse::<ParsingMode>().expect(
+            "cannot parse PARSING_MODE defined in .cargo/config.toml, please check the value.",
+        );
+
+        let tcp_nodelay = env!("TCP_NODELAY").parse::<bool>().expect(
+            "cannot parse TCP_NODELAY defined in .cargo/config.toml, please check the value.",
+        );
+        let socket_linger = match env!("SOCKET_LINGER") {
+            "off" => None,
+            seconds => Some(Duration::from_secs(seconds.parse::<u64>().expect(
+                "cannot parse SOCKET_LINGER defined in .cargo/config.toml, please check the value.",
+            ))),
+        };
+
+        let request_timeout = match env!("REQUEST_TIMEOUT") {
+            "off" => None,
+            seconds => Some(Duration::from_secs(seconds.parse::<u64>().expect(
+                "cannot parse REQUEST_TIMEOUT defined in .cargo/config.toml, please check the value.",
+            ))),
+        };
+
+        let acceptors = env!("ACCEPTORS").parse::<usize>().expect(
+            "cannot parse ACCEPTORS defined in .cargo/config.toml, please check the value.",
+        );
+
+        let priority_workers = env!("PRIORITY_WORKERS").parse::<usize>().expect(
+            "cannot parse PRIORITY_WORKERS defined in .cargo/config.toml, please check the value.",
+        );
+
+        let worker_start = env!("WORKER_START").parse::<WorkerStart>().expect(
+            "cannot parse WORKER_START defined in .cargo/config.toml, please check the value.",
+        );
+
+        let max_body_bytes = match env!("MAX_BODY_BYTES") {
+            "auto" => container_limits.default_max_body_bytes(workers),
+            bytes => bytes.parse::<usize>().expect(
+                "cannot parse MAX_BODY_BYTES defined in .cargo/config.toml, please check the value.",
+            ),
+        };
+
+        let access_list = AccessList::parse(env!("ALLOW_CIDRS"), env!("DENY_CIDRS"))
+            .unwrap_or_else(|error| {
+                panic!(
+                    "cannot parse ALLOW_CIDRS or DENY_CIDRS defined in .cargo/config.toml: {error}."
+                )
+            });
+
+        let honeypot_mode = env!("HONEYPOT")
+            .parse::<HoneypotMode>()
+            .expect("cannot parse HONEYPOT defined in .cargo/config.toml, please check the value.");
+
+        let profiler_enabled = env!("PROFILER")
+            .parse::<bool>()
+            .expect("cannot parse PROFILER defined in .cargo/config.toml, please check the value.");
+
+        let metrics_enabled = env!("METRICS")
+            .parse::<bool>()
+            .expect("cannot parse METRICS defined in .cargo/config.toml, please check the value.");
+
+        let batch_concurrency = match env!("BATCH") {
+            "off" => None,
+            concurrency => Some(concurrency.parse::<usize>().expect(
+                "cannot parse BATCH defined in .cargo/config.toml, please check the value.",
+            )),
+        };
+
+        let chaos_enabled = env!("CHAOS")
+            .parse::<bool>()
+            .expect("cannot parse CHAOS defined in .cargo/config.toml, please check the value.");
+
+        let audit_log = match env!("AUDIT_LOG") {
+            "off" => None,
+            path => Some(PathBuf::from(path)),
+        };
+
+        let audit_rotation = AuditRotation {
+            max_bytes: env!("AUDIT_LOG_MAX_BYTES").parse::<u64>().expect(
+                "cannot parse AUDIT_LOG_MAX_BYTES defined in .cargo/config.toml, please check the value.",
+            ),
+            keep: env!("AUDIT_LOG_KEEP").parse::<usize>().expect(
+                "cannot parse AUDIT_LOG_KEEP defined in .cargo/config.toml, please check the value.",
+            ),
+        };
+
+        let session_backend = env!("SESSION_STORE").parse::<SessionBackend>().expect(
+            "cannot parse SESSION_STORE defined in .cargo/config.toml, please check the value.",
+        );
+
+        let session_ttl = match env!("SESSION_TTL") {
+            "off" => None,
+            seconds => Some(Duration::from_secs(seconds.parse::<u64>().expect(
+                "cannot parse SESSION_TTL defined in .cargo/config.toml, please check the value.",
+            ))),
+        };
+        let session_sliding = env!("SESSION_SLIDING").parse::<bool>().expect(
+            "cannot parse SESSION_SLIDING defined in .cargo/config.toml, please check the value.",
+        );
+        let session_secure = env!("SESSION_SECURE").parse::<bool>().expect(
+            "cannot parse SESSION_SECURE defined in .cargo/config.toml, please check the value.",
+        );
+
+        let tenant_resolver = match env!("TENANTS") {
+            "off" => None,
+            resolver => Some(resolver.parse::<TenantResolver>().expect(
+                "cannot parse TENANTS defined in .cargo/config.toml, please check the value.",
+            )),
+        };
+
+        let propagated_headers = split_list(env!("PROPAGATE_HEADERS"));
+
+        let slow_request_threshold = match env!("SLOW_REQUEST_MS") {
+            "off" => None,
+            milliseconds => Some(Duration::from_millis(milliseconds.parse::<u64>().expect(
+                "cannot parse SLOW_REQUEST_MS defined in .cargo/config.toml, please check the value.",
+            ))),
+        };
+
+        let large_response_threshold = match env!("LARGE_RESPONSE_BYTES") {
+            "off" => None,
+            bytes => Some(bytes.parse::<usize>().expect(
+                "cannot parse LARGE_RESPONSE_BYTES defined in .cargo/config.toml, please check the value.",
+            )),
+        };
+
+        let compression_min_bytes = env!("COMPRESSION_MIN_BYTES").parse::<usize>().expect(
+            "cannot parse COMPRESSION_MIN_BYTES defined in .cargo/config.toml, please check the value.",
+        );
+
+        let compression_excluded_types = split_list(env!("COMPRESSION_EXCLUDED_TYPES"));
+
+        let compression_excluded_paths = split_list(env!("COMPRESSION_EXCLUDED_PATHS"));
 
         if ip_address.is_empty() {
             panic!("IP_ADDR not defined in .cargo/config.toml.");
@@ -43,15 +332,260 @@ impl ServerConfig {
             panic!("PORT not defined in .cargo/config.toml.");
         } else if workers == 0 {
             panic!("WORKERS not defined in .cargo/config.toml.");
+        } else if acceptors == 0 {
+            panic!("ACCEPTORS not defined in .cargo/config.toml.");
         }
 
         ServerConfig {
             ip_address,
             port,
             workers,
+            validate_requests,
+            parsing_mode,
+            tcp_nodelay,
+            socket_linger,
+            request_timeout,
+            acceptors,
+            priority_workers,
+            worker_start,
+            max_body_bytes,
+            container_limits,
+            access_list,
+            honeypot_mode,
+            profiler_enabled,
+            metrics_enabled,
+            batch_concurrency,
+            chaos_enabled,
+            audit_log,
+            audit_rotation,
+            session_backend,
+            session_ttl,
+            session_sliding,
+            session_secure,
+            tenant_resolver,
+            propagated_headers,
+            slow_request_threshold,
+            large_response_threshold,
+            compression_min_bytes,
+            compression_excluded_types,
+            compression_excluded_paths,
+        }
+    }
+
+    /// [`ServerConfig::get_compression_policy`] will get the [`CompressionPolicy`]
+    /// every response is compressed by, based on
+    /// [`ServerConfig::compression_min_bytes`],
+    /// [`ServerConfig::compression_excluded_types`], and
+    /// [`ServerConfig::compression_excluded_paths`].
+    ///
+    /// # Example
+    /// [`ServerConfig::get_compression_policy`] can be used to leave small bodies
+    /// uncompressed:
+    /// ```rust
+    /// use http::compression::Encoding;
+    /// use minimal_api::environment::server::ServerConfig;
+    ///
+    /// fn get_encoding(server_config: &ServerConfig, path: &str, body: &str) -> Encoding {
+    ///     server_config
+    ///         .get_compression_policy()
+    ///         .apply(Encoding::Brotli, path, None, body.len())
+    /// }
+    /// ```
+    pub fn get_compression_policy(&self) -> CompressionPolicy {
+        let excluded_types: Vec<&str> = self
+            .compression_excluded_types
+            .iter()
+            .map(String::as_str)
+            .collect();
+        let excluded_paths: Vec<&str> = self
+            .compression_excluded_paths
+            .iter()
+            .map(String::as_str)
+            .collect();
+        CompressionPolicy::new()
+            .with_min_bytes(self.compression_min_bytes)
+            .with_excluded_content_types(&excluded_types)
+            .with_excluded_paths(&excluded_paths)
+    }
+
+    /// [`ServerConfig::get_socket_options`] will get the [`SocketOptions`] to set on
+    /// every connection, based on [`ServerConfig::tcp_nodelay`] and
+    /// [`ServerConfig::socket_linger`].
+    ///
+    /// # Example
+    /// [`ServerConfig::get_socket_options`] can be used to tune accepted connections:
+    /// ```rust
+    /// use minimal_api::environment::server::ServerConfig;
+    /// use std::net::TcpStream;
+    ///
+    /// fn tune_stream(server_config: &ServerConfig, stream: &TcpStream) -> std::io::Result<()> {
+    ///     server_config.get_socket_options().apply(stream)
+    /// }
+    /// ```
+    pub fn get_socket_options(&self) -> SocketOptions {
+        SocketOptions {
+            nodelay: self.tcp_nodelay,
+            linger: self.socket_linger,
         }
     }
 
+    /// [`ServerConfig::get_limits_summary`] will get a summary of the effective
+    /// worker count and request body limit, along with the
+    /// [`ServerConfig::container_limits`] they are based on.
+    ///
+    /// # Example
+    /// [`ServerConfig::get_limits_summary`] can be used to log the limits at startup:
+    /// ```rust
+    /// use minimal_api::environment::server::ServerConfig;
+    ///
+    /// fn log_limits(server_config: &ServerConfig) {
+    ///     println!("{}", server_config.get_limits_summary());
+    /// }
+    /// ```
+    pub fn get_limits_summary(&self) -> String {
+        format!(
+            "effective limits: {}, {} worker(s), {} byte request bodies",
+            self.container_limits, self.workers, self.max_body_bytes
+        )
+    }
+
+    /// [`ServerConfig::get_summary`] will get every effective setting of the
+    /// [`ServerConfig`] as a `(name, value)` pair, from its address and workers to
+    /// its timeouts, limits, and enabled features, where the credentials of an
+    /// address, such as `user:secret@host:port`, are redacted.
+    ///
+    /// # Example
+    /// [`ServerConfig::get_summary`] can be used to log the configuration at startup:
+    /// ```rust
+    /// use minimal_api::environment::server::ServerConfig;
+    ///
+    /// fn log_configuration(server_config: &ServerConfig) {
+    ///     for (name, value) in server_config.get_summary() {
+    ///         println!("{name}: {value}");
+    ///     }
+    /// }
+    /// ```
+    pub fn get_summary(&self) -> Vec<(&'static str, String)> {
+        let off_or = |value: Option<String>| value.unwrap_or_else(|| "off".to_string());
+        let list_or_none = |values: &[String]| {
+            if values.is_empty() {
+                "none".to_string()
+            } else {
+                values.join(", ")
+            }
+        };
+        let session_backend = match &self.session_backend {
+            SessionBackend::Tcp(address) => format!("tcp:{}", redact_credentials(address)),
+            backend => backend.to_string(),
+        };
+        let features: Vec<String> = [
+            ("brotli", cfg!(feature = "brotli")),
+            ("gzip", cfg!(feature = "gzip")),
+            ("alloc-metrics", cfg!(feature = "alloc-metrics")),
+            ("debug-endpoints", cfg!(feature = "debug-endpoints")),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(feature, _)| feature.to_string())
+        .collect();
+
+        vec![
+            ("address", self.get_server_address()),
+            ("acceptors", self.acceptors.to_string()),
+            ("workers", self.workers.to_string()),
+            ("priority workers", self.priority_workers.to_string()),
+            (
+                "worker start",
+                format!("{:?}", self.worker_start).to_lowercase(),
+            ),
+            (
+                "request timeout",
+                off_or(self.request_timeout.map(|timeout| format!("{timeout:?}"))),
+            ),
+            (
+                "socket linger",
+                off_or(self.socket_linger.map(|linger| format!("{linger:?}"))),
+            ),
+            ("tcp nodelay", self.tcp_nodelay.to_string()),
+            ("max body bytes", self.max_body_bytes.to_string()),
+            ("container limits", self.container_limits.to_string()),
+            (
+                "parsing mode",
+                format!("{:?}", self.parsing_mode).to_lowercase(),
+            ),
+            ("validate requests", self.validate_requests.to_string()),
+            (
+                "access list",
+                format!(
+                    "{} allowed and {} denied range(s)",
+                    self.access_list.allow.len(),
+                    self.access_list.deny.len()
+                ),
+            ),
+            (
+                "honeypot",
+                format!("{:?}", self.honeypot_mode).to_lowercase(),
+            ),
+            ("sessions", session_backend),
+            (
+                "session ttl",
+                off_or(self.session_ttl.map(|ttl| format!("{ttl:?}"))),
+            ),
+            ("session sliding", self.session_sliding.to_string()),
+            ("session secure", self.session_secure.to_string()),
+            (
+                "tenants",
+                off_or(self.tenant_resolver.as_ref().map(ToString::to_string)),
+            ),
+            ("propagated headers", list_or_none(&self.propagated_headers)),
+            (
+                "slow request threshold",
+                off_or(
+                    self.slow_request_threshold
+                        .map(|threshold| format!("{threshold:?}")),
+                ),
+            ),
+            (
+                "large response threshold",
+                off_or(
+                    self.large_response_threshold
+                        .map(|bytes| format!("{bytes} bytes")),
+                ),
+            ),
+            (
+                "compression min bytes",
+                self.compression_min_bytes.to_string(),
+            ),
+            (
+                "compression excluded types",
+                list_or_none(&self.compression_excluded_types),
+            ),
+            (
+                "compression excluded paths",
+                list_or_none(&self.compression_excluded_paths),
+            ),
+            ("profiler", self.profiler_enabled.to_string()),
+            ("metrics", self.metrics_enabled.to_string()),
+            (
+                "batch concurrency",
+                off_or(
+                    self.batch_concurrency
+                        .map(|concurrency| concurrency.to_string()),
+                ),
+            ),
+            ("chaos", self.chaos_enabled.to_string()),
+            (
+                "audit log",
+                off_or(
+                    self.audit_log
+                        .as_ref()
+                        .map(|path| format!("{}, {}", path.display(), self.audit_rotation)),
+                ),
+            ),
+            ("features", list_or_none(&features)),
+        ]
+    }
+
     /// [`ServerConfig::get_server_address`] will get [`ServerConfig::ip_address`] and
     /// [`ServerConfig::port`], formatted with a colon between them.
     ///
@@ -75,3 +609,24 @@ impl Default for ServerConfig {
         ServerConfig::new()
     }
 }
+
+/// [`redact_credentials`] will replace the credentials of `address`, everything
+/// before its last `@` such as `user:secret` in `user:secret@host:port`, so it can
+/// be logged.
+pub fn redact_credentials(address: &str) -> String {
+    match address.rsplit_once('@') {
+        Some((_, host)) => format!("<redacted>@{host}"),
+        None => address.to_string(),
+    }
+}
+
+/// [`split_list`] will split a comma separated config `value` into its trimmed,
+/// non-empty entries.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
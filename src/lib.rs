@@ -3,8 +3,10 @@
 //! hosting a thread-safe web API capable of receiving `GET`, `POST`, `PUT`,
 //! and `DELETE` HTTP requests, and serving back content based on a route.
 //!
-//! It contains no dependencies besides a couple libraries that are included
-//! with it:
+//! It contains no dependencies besides `signal-hook` and `windows-service`, for
+//! stopping gracefully when run as a service, `socket2` behind the optional
+//! `socket-extras` cargo feature, for the socket options the standard library
+//! doesn't expose, and a couple libraries that are included with it:
 //! - [`http`] for basic HTTP communication abstractions
 //! - [`http_attributes`] for helpful attribute macros, such as
 //!   [`http_get`](macro@http_attributes::http_get) and
@@ -84,11 +86,49 @@
 /// [`server`] contains all functionality related to handling client requests
 /// and serving responses safely.
 pub mod server {
+    /// [`acceptor`] contains the dedicated thread that accepts connections and
+    /// tunes their sockets before handing them to a worker.
+    pub mod acceptor;
+    /// [`access_control`] contains the IP allow and deny lists that are checked
+    /// before a request is routed.
+    pub mod access_control;
+    /// [`chaos`] contains the opt-in fault injection for testing how clients
+    /// handle latency, errors, and dropped connections.
+    pub mod chaos;
+    /// [`docs`] contains the page that documents every route, with a `curl`
+    /// command to try each of them.
+    pub mod docs;
+    /// [`events`] contains a typed publish/subscribe channel for sharing events
+    /// between route handlers and background jobs.
+    pub mod events;
+    /// [`honeypot`] contains the trap for vulnerability scanners, which answers
+    /// them slowly or with junk, and bans their address.
+    pub mod honeypot;
     /// [`job`] contains type definitions for the server.
     pub mod job;
     /// [`listener`] contains all functionality for how the server listens
     /// for requests.
     pub mod listener;
+    /// [`long_poll`] contains the thread that answers long polls, so a waiting
+    /// client never holds a worker.
+    pub mod long_poll;
+    /// [`metrics`] contains the metrics endpoint, with memory usage and the
+    /// optional allocator that counts allocations.
+    pub mod metrics;
+    /// [`priority`] contains the priority lane that keeps health and admin requests
+    /// answered while every worker is busy.
+    pub mod priority;
+    /// [`profiler`] contains the sampling profiler that reports what worker threads
+    /// spend their time on.
+    pub mod profiler;
+    /// [`readiness`] contains the upstream dependency checks that decide if the
+    /// server is ready, and which routes can be served.
+    pub mod readiness;
+    /// [`service`] contains the integrations that let launchd, systemd, Docker,
+    /// and the Windows service control manager run and stop the server.
+    pub mod service;
+    /// [`shutdown`] contains the signal used to stop the server gracefully.
+    pub mod shutdown;
     /// [`thread_pool`] contains a basic thread pool implementation to allow
     /// the server to be multi-threaded.
     pub mod thread_pool;
@@ -103,6 +143,9 @@ pub mod environment {
     /// [`app`] contains all environment variables that are application-centric,
     /// like version number and application name.
     pub mod app;
+    /// [`container`] contains detection of the CPU and memory limits placed on
+    /// the server by a container runtime, like Docker or Kubernetes.
+    pub mod container;
     /// [`server`] contains all environment variables that are specifically for
     /// the web server.
     pub mod server;
@@ -120,7 +163,7 @@ pub mod routes {
         pub mod session;
     }
     /// [`index`] holds a very important function,
-    /// [`get_endpoints`](crate::routes::index::get_endpoints), which contains
+    /// [`get_endpoints`](crate::routes::index::get_endpoints), which collects
     /// all endpoints that the server will know about.
     pub mod index;
 }
@@ -1,11 +1,74 @@
 use logger::log_severity::LogSeverity;
-use minimal_api::server::listener;
+use minimal_api::{
+    environment::{app::CRATE_NAME, server::ServerConfig},
+    routes,
+    server::{listener, service},
+};
+
+/// [`ALLOCATOR`] counts every allocation for the metrics endpoint, when the
+/// `alloc-metrics` cargo feature is enabled.
+#[cfg(feature = "alloc-metrics")]
+#[global_allocator]
+static ALLOCATOR: minimal_api::server::metrics::CountingAllocator =
+    minimal_api::server::metrics::CountingAllocator;
 
 /// [`main`] is the entry point of [`minimal_api`], which invokes the
 /// [`listen`] function to listen for any [`Route`](http::route::Route)
 /// that is returned by the
 /// [`get_endpoints`](minimal_api::routes::index::get_endpoints) function.
+///
+/// Passing `--launchd-plist` prints a launchd property list for running the
+/// server as a macOS service instead, passing `--generate-client` prints a typed
+/// Rust client module for every route, passing `--postman-collection` prints a
+/// Postman collection of every route, and passing `--windows-service` runs the
+/// server under the Windows service control manager.
 fn main() {
     logger::set_logging_severity(LogSeverity::Debug);
+
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    if arguments
+        .iter()
+        .any(|argument| argument == "--launchd-plist")
+    {
+        let program = std::env::current_exe().expect("cannot find the server executable.");
+        print!("{}", service::launchd_plist(CRATE_NAME, &program));
+        return;
+    }
+    if arguments
+        .iter()
+        .any(|argument| argument == "--generate-client")
+    {
+        print!(
+            "{}",
+            http::sdk::generate_client(&routes::index::get_router().specs(), "ApiClient")
+        );
+        return;
+    }
+    if arguments
+        .iter()
+        .any(|argument| argument == "--postman-collection")
+    {
+        let base_url = format!("http://{}", ServerConfig::new().get_server_address());
+        print!(
+            "{}",
+            http::postman::export_collection(
+                &routes::index::get_router().specs(),
+                CRATE_NAME,
+                &base_url
+            )
+        );
+        return;
+    }
+    #[cfg(windows)]
+    if arguments
+        .iter()
+        .any(|argument| argument == "--windows-service")
+    {
+        if let Err(error) = service::run_as_windows_service() {
+            logger::log_error!("can't run as a Windows service: {error}");
+        }
+        return;
+    }
+
     listener::listen();
 }
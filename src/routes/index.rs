@@ -1,68 +1,125 @@
 use crate::{
     environment::app::{CRATE_NAME, CRATE_VERSION},
-    routes,
+    server::{
+        access_control::AccessControl, chaos::Chaos, readiness::DependencyCheck,
+        thread_pool::WarmUp,
+    },
+};
+use http::{
+    audit::AuditLog, respond::Respond, route::Route, router::Router, static_files::StaticFiles,
 };
-use http::{respond::Respond, route::Route};
 use http_attributes::{http_delete, http_get, http_post, http_put};
 
 #[http_get("/")]
-pub fn show_welcome_message() -> String {
-    format!("Hello from {CRATE_NAME} v{CRATE_VERSION}!").get_json()
+pub fn show_welcome_message() -> impl Respond {
+    format!("Hello from {CRATE_NAME} v{CRATE_VERSION}!")
 }
 
 #[http_get("/name")]
-pub fn show_crate_name() -> String {
-    CRATE_NAME.get_json()
+pub fn show_crate_name() -> impl Respond {
+    CRATE_NAME
 }
 
 #[http_get("/version")]
-pub fn show_crate_version() -> String {
-    CRATE_VERSION.get_json()
+pub fn show_crate_version() -> impl Respond {
+    CRATE_VERSION
 }
 
 #[http_get("/squared")]
-pub fn show_number_squared(number: String) -> String {
+pub fn show_number_squared(number: String) -> impl Respond {
     if number.parse::<i32>().is_ok() {
         let parsed_number: i32 = number.parse().unwrap();
         let squared_number = parsed_number * parsed_number;
-        format!("{parsed_number} squared is {squared_number}.").get_json()
+        format!("{parsed_number} squared is {squared_number}.")
     } else {
-        format!("{number} is not a number.").get_json()
+        format!("{number} is not a number.")
     }
 }
 
 #[http_get("/user/{id}")]
-pub fn show_user_by_id(id: String) -> String {
-    format!("Found user by id '{id}'").get_json()
+pub fn show_user_by_id(id: String) -> impl Respond {
+    format!("Found user by id '{id}'")
 }
 
 #[http_post("/sendname")]
-pub fn get_name(name: String) -> String {
-    format!("Received name '{name}' from POST route!").get_json()
+pub fn get_name(name: String) -> impl Respond {
+    format!("Received name '{name}' from POST route!")
 }
 
 #[http_put("/update")]
-pub fn get_update_id(id: String) -> String {
-    format!("Received id '{id}' from PUT route!").get_json()
+pub fn get_update_id(id: String) -> impl Respond {
+    format!("Received id '{id}' from PUT route!")
 }
 
 #[http_delete("/remove")]
-pub fn get_delete_id(id: String) -> String {
-    format!("Received id '{id}' from DELETE route!").get_json()
+pub fn get_delete_id(id: String) -> impl Respond {
+    format!("Received id '{id}' from DELETE route!")
+}
+
+#[http_get("/debug/state", cfg = "debug-endpoints")]
+pub fn show_debug_state() -> impl Respond {
+    crate::environment::server::ServerConfig::new().get_limits_summary()
 }
 
 /// [`get_endpoints`] will return a collection of [`Route`] for the
-/// server to listen for and respond to.
+/// server to listen for and respond to, which is every handler with a
+/// `#[http_*]` attribute, in any module.
 pub fn get_endpoints() -> Vec<Route> {
-    vec![
-        show_welcome_message(),
-        show_crate_name(),
-        show_crate_version(),
-        show_number_squared(),
-        show_user_by_id(),
-        get_name(),
-        get_update_id(),
-        get_delete_id(),
-        routes::mock::session::create_new_session_id(),
-    ]
+    http::collect_routes!()
+}
+
+/// [`get_router`] will return the [`Router`] for every [`Route`] from
+/// [`get_endpoints`], along with the response filters every [`Route`] response is
+/// passed through before it is sent, such as wrapping the `/users` responses in an
+/// envelope with `Router::with_filter_for("/users", Box::new(wrap_in_envelope))`.
+pub fn get_router() -> Router {
+    Router::new(get_endpoints())
+}
+
+/// [`get_static_files`] will return the [`StaticFiles`] for the server to serve,
+/// which are the files in the `static` directory under the `/assets` path.
+pub fn get_static_files() -> StaticFiles {
+    StaticFiles::new("/assets", "static")
+}
+
+/// [`get_dependency_checks`] will return a collection of [`DependencyCheck`] for
+/// the upstream dependencies the server needs, which routes can require with the
+/// `requires` option of the http attributes, such as
+/// `#[http_get("/users", requires = "db")]`.
+pub fn get_dependency_checks() -> Vec<DependencyCheck> {
+    Vec::new()
+}
+
+/// [`get_access_control`] will return the [`AccessControl`] for route groups, such
+/// as only allowing internal addresses under `/admin` with
+/// `AccessControl::new().with_group("/admin", AccessList::new().allow("10.0.0.0/8")?)`.
+/// Every request is also checked against the global `ALLOW_CIDRS` and
+/// `DENY_CIDRS` lists in the `.cargo` config files.
+pub fn get_access_control() -> AccessControl {
+    AccessControl::new()
+}
+
+/// [`get_chaos`] will return the [`Chaos`] faults to inject into requests when the
+/// `CHAOS` value in the `.cargo` config files is `"true"`, such as failing a tenth
+/// of `/users` requests with
+/// `Chaos::new().with_rule(ChaosRule::new("/users").with_errors(0.1))`.
+pub fn get_chaos() -> Chaos {
+    Chaos::new()
+}
+
+/// [`get_audit_log`] will return the [`AuditLog`] every `POST`, `PUT`, and `DELETE`
+/// request is audited by when the `AUDIT_LOG` value in the `.cargo` config files
+/// is a path, such as naming the user a gateway authenticated with
+/// `AuditLog::new().with_principal(Box::new(|request| request.header("X-User").map(str::to_string)))`.
+pub fn get_audit_log() -> AuditLog {
+    AuditLog::new()
+}
+
+/// [`get_warm_up`] will return the [`WarmUp`] every worker runs once before it takes
+/// its first request when the `WORKER_START` value in the `.cargo` config files is
+/// `"eager"`, such as filling a thread-local cache on every worker with
+/// `Some(Arc::new(fill_cache))`. `/ready` answers
+/// `503 SERVICE UNAVAILABLE` until every worker has warmed up.
+pub fn get_warm_up() -> Option<WarmUp> {
+    None
 }
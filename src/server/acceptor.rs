@@ -0,0 +1,216 @@
+use crate::server::shutdown::Shutdown;
+use logger::{log_debug, log_warning};
+#[cfg(feature = "socket-extras")]
+use socket2::{Domain, SockRef, Socket, Type};
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// [`WAKE_INTERVAL`] is how long [`stop_acceptors`] waits between connections that
+/// wake acceptor threads blocked on accepting.
+const WAKE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// [`WAKE_ATTEMPTS`] is how many times [`stop_acceptors`] wakes acceptor threads
+/// before it gives up on them.
+const WAKE_ATTEMPTS: usize = 500;
+
+/// [`SocketOptions`] represents the socket flags set on every connection the
+/// server accepts, before it is handed to a worker.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SocketOptions {
+    /// [`SocketOptions::nodelay`] is whether `TCP_NODELAY` is set, so small
+    /// responses are sent right away instead of being batched.
+    pub nodelay: bool,
+    /// [`SocketOptions::linger`] is how long closing a connection waits for unsent
+    /// data to be delivered (`SO_LINGER`), or [`None`] to close in the background,
+    /// which is only set with the `socket-extras` cargo feature.
+    pub linger: Option<Duration>,
+}
+
+impl SocketOptions {
+    /// [`SocketOptions::apply`] will set every [`SocketOptions`] flag on `stream`,
+    /// where [`SocketOptions::linger`] is left as-is without the `socket-extras`
+    /// cargo feature.
+    /// # Example
+    /// [`SocketOptions::apply`] can be used to disable Nagle's algorithm on a
+    /// connection:
+    /// ```rust
+    /// use minimal_api::server::acceptor::SocketOptions;
+    /// use std::net::TcpStream;
+    ///
+    /// fn tune_stream(stream: &TcpStream) -> std::io::Result<()> {
+    ///     SocketOptions { nodelay: true, linger: None }.apply(stream)
+    /// }
+    /// ```
+    pub fn apply(&self, stream: &TcpStream) -> io::Result<()> {
+        stream.set_nodelay(self.nodelay)?;
+        #[cfg(feature = "socket-extras")]
+        SockRef::from(stream).set_linger(self.linger)?;
+        Ok(())
+    }
+}
+
+/// [`LISTEN_BACKLOG`] is how many connections each listener queues before new
+/// connections are refused.
+#[cfg(feature = "socket-extras")]
+const LISTEN_BACKLOG: i32 = 1024;
+
+/// [`bind_reuse_port`] will bind `count` listeners to the same `address` with
+/// `SO_REUSEPORT`, so the kernel spreads incoming connections across them and each
+/// can be accepted on its own thread. Returns an [`Err`] on platforms without
+/// `SO_REUSEPORT`, or without the `socket-extras` cargo feature, when `count` is
+/// more than `1`.
+/// # Example
+/// [`bind_reuse_port`] can be used to bind one listener per core:
+/// ```rust
+/// use minimal_api::server::acceptor;
+/// use std::net::{SocketAddr, TcpListener};
+///
+/// fn bind_per_core(address: SocketAddr, cores: usize) -> std::io::Result<Vec<TcpListener>> {
+///     acceptor::bind_reuse_port(address, cores)
+/// }
+/// ```
+#[cfg(feature = "socket-extras")]
+pub fn bind_reuse_port(address: SocketAddr, count: usize) -> io::Result<Vec<TcpListener>> {
+    (0..count.max(1))
+        .map(|_| {
+            let socket = Socket::new(Domain::for_address(address), Type::STREAM, None)?;
+            #[cfg(unix)]
+            socket.set_reuse_address(true)?;
+            #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+            socket.set_reuse_port(true)?;
+            #[cfg(not(all(unix, not(any(target_os = "solaris", target_os = "illumos")))))]
+            if count > 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "SO_REUSEPORT is not supported on this platform",
+                ));
+            }
+            socket.bind(&address.into())?;
+            socket.listen(LISTEN_BACKLOG)?;
+            Ok(socket.into())
+        })
+        .collect()
+}
+
+/// [`bind_reuse_port`] will bind a single listener to `address`, since
+/// `SO_REUSEPORT` needs the `socket-extras` cargo feature, and returns an [`Err`]
+/// when `count` is more than `1`.
+#[cfg(not(feature = "socket-extras"))]
+pub fn bind_reuse_port(address: SocketAddr, count: usize) -> io::Result<Vec<TcpListener>> {
+    if count > 1 {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "more than one acceptor needs the `socket-extras` cargo feature",
+        ));
+    }
+    Ok(vec![TcpListener::bind(address)?])
+}
+
+/// [`spawn_acceptor`] will accept connections from `listener` on a dedicated
+/// thread, set the provided [`SocketOptions`] on each of them, and hand them to
+/// `handle_stream`. The thread runs until `shutdown` is triggered and it is woken
+/// by [`stop_acceptors`], then drops `handle_stream`.
+/// # Example
+/// [`spawn_acceptor`] can be used to hand every connection to a
+/// [`ThreadPool`](crate::server::thread_pool::ThreadPool):
+/// ```rust
+/// use minimal_api::server::{
+///     acceptor::{self, SocketOptions},
+///     shutdown::Shutdown,
+///     thread_pool::ThreadPool,
+/// };
+/// use std::{net::TcpListener, thread::JoinHandle};
+///
+/// fn accept_connections(
+///     listener: TcpListener,
+///     shutdown: Shutdown,
+///     pool: ThreadPool,
+/// ) -> std::io::Result<JoinHandle<()>> {
+///     acceptor::spawn_acceptor(listener, SocketOptions::default(), shutdown, move |stream| {
+///         pool.execute(move || drop(stream));
+///     })
+/// }
+/// ```
+pub fn spawn_acceptor<F>(
+    listener: TcpListener,
+    socket_options: SocketOptions,
+    shutdown: Shutdown,
+    handle_stream: F,
+) -> io::Result<JoinHandle<()>>
+where
+    F: Fn(TcpStream) + Send + 'static,
+{
+    thread::Builder::new()
+        .name("acceptor".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                if shutdown.is_triggered() {
+                    break;
+                }
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(error) => {
+                        log_warning!("can't accept connection: {error}");
+                        continue;
+                    }
+                };
+                if let Err(error) = socket_options.apply(&stream) {
+                    log_warning!("can't set socket options on connection: {error}");
+                }
+
+                log_debug!("accepted connection, handing it to a worker.");
+                handle_stream(stream);
+            }
+        })
+}
+
+/// [`stop_acceptors`] will wake every thread from [`spawn_acceptor`] that is blocked
+/// accepting on one of `addresses` once `shutdown` is triggered, and wait for each
+/// of them to stop. Acceptors are woken by connecting to their address, since an
+/// accept call can't be interrupted otherwise.
+/// # Example
+/// [`stop_acceptors`] can be used to stop the server after a [`Shutdown`]:
+/// ```rust
+/// use minimal_api::server::{acceptor, shutdown::Shutdown};
+/// use std::{net::SocketAddr, thread::JoinHandle};
+///
+/// fn stop_server(shutdown: &Shutdown, addresses: &[SocketAddr], acceptors: Vec<JoinHandle<()>>) {
+///     shutdown.trigger();
+///     acceptor::stop_acceptors(addresses, acceptors);
+/// }
+/// ```
+pub fn stop_acceptors(addresses: &[SocketAddr], acceptors: Vec<JoinHandle<()>>) {
+    for _ in 0..WAKE_ATTEMPTS {
+        if acceptors.iter().all(|acceptor| acceptor.is_finished()) {
+            break;
+        }
+        for address in addresses {
+            let _ = TcpStream::connect_timeout(&connectable(*address), WAKE_INTERVAL);
+        }
+        thread::sleep(WAKE_INTERVAL);
+    }
+
+    for acceptor in acceptors {
+        if acceptor.is_finished() && acceptor.join().is_err() {
+            log_warning!("an acceptor thread stopped unexpectedly.");
+        }
+    }
+}
+
+/// [`connectable`] will return `address` with an unspecified IP, such as `0.0.0.0`,
+/// replaced by the loopback IP of the same family, so it can be connected to.
+fn connectable(address: SocketAddr) -> SocketAddr {
+    match address {
+        SocketAddr::V4(v4) if v4.ip().is_unspecified() => {
+            SocketAddr::from((Ipv4Addr::LOCALHOST, v4.port()))
+        }
+        SocketAddr::V6(v6) if v6.ip().is_unspecified() => {
+            SocketAddr::from((Ipv6Addr::LOCALHOST, v6.port()))
+        }
+        address => address,
+    }
+}
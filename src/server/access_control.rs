@@ -0,0 +1,293 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    net::IpAddr,
+    str::FromStr,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// [`Cidr`] represents a range of IP addresses, such as `10.0.0.0/8` or
+/// `2001:db8::/32`. IPv4 addresses mapped into IPv6, such as `::ffff:10.0.0.1`,
+/// are matched as IPv4 addresses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cidr {
+    /// [`Cidr::network`] is the first address of the range.
+    pub network: IpAddr,
+    /// [`Cidr::prefix_len`] is how many leading bits of an address have to match
+    /// [`Cidr::network`].
+    pub prefix_len: u8,
+}
+
+/// [`AccessList`] decides which IP addresses are allowed, with a list of ranges to
+/// allow and a list of ranges to deny. Denied ranges always win, and an empty
+/// allow list allows every address that isn't denied.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessList {
+    /// [`AccessList::allow`] are the only ranges allowed, unless it is empty.
+    pub allow: Vec<Cidr>,
+    /// [`AccessList::deny`] are the ranges that are never allowed.
+    pub deny: Vec<Cidr>,
+}
+
+/// [`AccessControl`] checks the IP address of every request against a global
+/// [`AccessList`], and the [`AccessList`] of every route group the request path is
+/// in, before the request is routed, and denies addresses that were banned for a
+/// while. It counts every denied request.
+#[derive(Debug, Default)]
+pub struct AccessControl {
+    /// [`AccessControl::global`] is the [`AccessList`] every request is checked
+    /// against.
+    pub global: AccessList,
+    /// [`AccessControl::groups`] are the path prefixes of route groups, such as
+    /// `/admin`, along with the [`AccessList`] for requests under them.
+    pub groups: Vec<(String, AccessList)>,
+    /// [`AccessControl::denials`] is how many requests were denied.
+    denials: AtomicU64,
+    /// [`AccessControl::bans`] are the banned addresses, along with when their ban
+    /// ends.
+    bans: Mutex<HashMap<IpAddr, Instant>>,
+}
+
+impl Cidr {
+    /// [`Cidr::contains`] will return `true` if `address` is in the range, and
+    /// `false` otherwise.
+    pub fn contains(&self, address: IpAddr) -> bool {
+        match (self.network, unmap(address)) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                prefix_matches(&network.octets(), &address.octets(), self.prefix_len)
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                prefix_matches(&network.octets(), &address.octets(), self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Implement [`FromStr`] for [`Cidr`], which parses a range such as `10.0.0.0/8`,
+/// or a single address such as `10.0.0.1`.
+impl FromStr for Cidr {
+    type Err = String;
+
+    fn from_str(cidr: &str) -> Result<Self, Self::Err> {
+        let cidr = cidr.trim();
+        let (address, prefix_len) = match cidr.split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (cidr, None),
+        };
+        let network = unmap(
+            address
+                .parse::<IpAddr>()
+                .map_err(|_| format!("'{cidr}' is not an IP address or CIDR range"))?,
+        );
+        let max_prefix_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix_len| *prefix_len <= max_prefix_len)
+                .ok_or_else(|| format!("'{cidr}' has an invalid prefix length"))?,
+            None => max_prefix_len,
+        };
+        Ok(Cidr {
+            network,
+            prefix_len,
+        })
+    }
+}
+
+/// Implement [`fmt::Display`] for [`Cidr`].
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+impl AccessList {
+    /// [`AccessList::new`] creates an [`AccessList`] that allows every address.
+    /// # Example
+    /// [`AccessList::new`] can be used to only allow private networks:
+    /// ```rust
+    /// use minimal_api::server::access_control::AccessList;
+    ///
+    /// fn create_private_access_list() -> Result<AccessList, String> {
+    ///     AccessList::new().allow("10.0.0.0/8")?.allow("192.168.0.0/16")
+    /// }
+    /// ```
+    pub fn new() -> Self {
+        AccessList::default()
+    }
+
+    /// [`AccessList::parse`] creates an [`AccessList`] from comma separated lists
+    /// of ranges to `allow` and `deny`, which may be empty.
+    pub fn parse(allow: &str, deny: &str) -> Result<Self, String> {
+        Ok(AccessList {
+            allow: parse_cidrs(allow)?,
+            deny: parse_cidrs(deny)?,
+        })
+    }
+
+    /// [`AccessList::allow`] will add `cidr` to [`AccessList::allow`], and return
+    /// the [`AccessList`], or an [`Err`] if `cidr` isn't a valid range.
+    pub fn allow(mut self, cidr: &str) -> Result<Self, String> {
+        self.allow.push(cidr.parse()?);
+        Ok(self)
+    }
+
+    /// [`AccessList::deny`] will add `cidr` to [`AccessList::deny`], and return the
+    /// [`AccessList`], or an [`Err`] if `cidr` isn't a valid range.
+    pub fn deny(mut self, cidr: &str) -> Result<Self, String> {
+        self.deny.push(cidr.parse()?);
+        Ok(self)
+    }
+
+    /// [`AccessList::is_allowed`] will return `true` if `address` isn't in any
+    /// denied range, and is in an allowed range when there are any, and `false`
+    /// otherwise.
+    pub fn is_allowed(&self, address: IpAddr) -> bool {
+        !self.deny.iter().any(|cidr| cidr.contains(address))
+            && (self.allow.is_empty() || self.allow.iter().any(|cidr| cidr.contains(address)))
+    }
+
+    /// [`AccessList::is_empty`] will return `true` if the [`AccessList`] has no
+    /// ranges, and allows every address.
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+}
+
+impl AccessControl {
+    /// [`AccessControl::new`] creates an [`AccessControl`] that allows every
+    /// request.
+    pub fn new() -> Self {
+        AccessControl::default()
+    }
+
+    /// [`AccessControl::with_global`] will check every request against
+    /// `access_list`, and return the [`AccessControl`].
+    pub fn with_global(mut self, access_list: AccessList) -> Self {
+        self.global = access_list;
+        self
+    }
+
+    /// [`AccessControl::with_group`] will check requests under the path `prefix`
+    /// against `access_list`, on top of [`AccessControl::global`], and return the
+    /// [`AccessControl`].
+    /// # Example
+    /// [`AccessControl::with_group`] can be used to keep admin routes internal:
+    /// ```rust
+    /// use minimal_api::server::access_control::{AccessControl, AccessList};
+    ///
+    /// fn create_access_control() -> Result<AccessControl, String> {
+    ///     Ok(AccessControl::new().with_group("/admin", AccessList::new().allow("10.0.0.0/8")?))
+    /// }
+    /// ```
+    pub fn with_group(mut self, prefix: &str, access_list: AccessList) -> Self {
+        self.groups
+            .push((prefix.trim_end_matches('/').to_string(), access_list));
+        self
+    }
+
+    /// [`AccessControl::ban`] will deny every request from `address` for
+    /// `duration`, extending any ban it already has.
+    pub fn ban(&self, address: IpAddr, duration: Duration) {
+        let banned_until = Instant::now() + duration;
+        let mut bans = self.lock_bans();
+        let ban = bans.entry(unmap(address)).or_insert(banned_until);
+        *ban = (*ban).max(banned_until);
+    }
+
+    /// [`AccessControl::is_banned`] will return `true` if `address` is banned right
+    /// now, and `false` otherwise, forgetting bans that ended.
+    pub fn is_banned(&self, address: IpAddr) -> bool {
+        let mut bans = self.lock_bans();
+        let now = Instant::now();
+        bans.retain(|_, banned_until| *banned_until > now);
+        bans.contains_key(&unmap(address))
+    }
+
+    /// [`AccessControl::is_allowed`] will return `true` if a request from `address`
+    /// for `path` isn't banned, and is allowed by [`AccessControl::global`] and every
+    /// group `path` is in, and `false` otherwise, counting it in
+    /// [`AccessControl::denials`].
+    pub fn is_allowed(&self, address: IpAddr, path: &str) -> bool {
+        let is_allowed = !self.is_banned(address)
+            && self.global.is_allowed(address)
+            && self
+                .groups
+                .iter()
+                .filter(|(prefix, _)| is_in_group(path, prefix))
+                .all(|(_, access_list)| access_list.is_allowed(address));
+        if !is_allowed {
+            self.denials.fetch_add(1, Ordering::Relaxed);
+        }
+        is_allowed
+    }
+
+    /// [`AccessControl::denials`] will get how many requests were denied since the
+    /// server started.
+    pub fn denials(&self) -> u64 {
+        self.denials.load(Ordering::Relaxed)
+    }
+
+    /// [`AccessControl::is_empty`] will return `true` if no request can be denied.
+    pub fn is_empty(&self) -> bool {
+        self.lock_bans().is_empty()
+            && self.global.is_empty()
+            && self
+                .groups
+                .iter()
+                .all(|(_, access_list)| access_list.is_empty())
+    }
+
+    /// [`AccessControl::lock_bans`] will lock [`AccessControl::bans`], recovering
+    /// them if a caller panicked while holding the lock.
+    fn lock_bans(&self) -> std::sync::MutexGuard<'_, HashMap<IpAddr, Instant>> {
+        self.bans
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// [`parse_cidrs`] will parse a comma separated list of ranges, which may be
+/// empty.
+fn parse_cidrs(cidrs: &str) -> Result<Vec<Cidr>, String> {
+    cidrs
+        .split(',')
+        .filter(|cidr| !cidr.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// [`is_in_group`] will return `true` if `path` is `prefix` or under it, and
+/// `false` otherwise.
+fn is_in_group(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/') || prefix.is_empty())
+}
+
+/// [`unmap`] will turn an IPv4 address mapped into IPv6 back into IPv4.
+fn unmap(address: IpAddr) -> IpAddr {
+    match address {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(address, IpAddr::V4),
+        IpAddr::V4(_) => address,
+    }
+}
+
+/// [`prefix_matches`] will return `true` if the first `prefix_len` bits of
+/// `network` and `address` are the same, and `false` otherwise.
+fn prefix_matches(network: &[u8], address: &[u8], prefix_len: u8) -> bool {
+    let full_bytes = usize::from(prefix_len / 8);
+    let remaining_bits = prefix_len % 8;
+    if network[..full_bytes] != address[..full_bytes] {
+        return false;
+    }
+    remaining_bits == 0 || {
+        let mask = u8::MAX << (8 - remaining_bits);
+        network[full_bytes] & mask == address[full_bytes] & mask
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    hash::{BuildHasher, Hasher, RandomState},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// [`Fault`] is a fault that [`Chaos`] injects into a request.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fault {
+    /// [`Fault::Latency`] waits for the [`Duration`] before the request is routed.
+    Latency(Duration),
+    /// [`Fault::ServerError`] answers with a `500 INTERNAL SERVER ERROR` instead of
+    /// routing the request.
+    ServerError,
+    /// [`Fault::DropConnection`] closes the connection without answering.
+    DropConnection,
+}
+
+/// [`ChaosRule`] is the faults to inject into requests under a path prefix, and the
+/// rate of requests each one is injected into, from `0.0` for none to `1.0` for
+/// every request.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChaosRule {
+    /// [`ChaosRule::prefix`] is the path prefix of the routes the faults are
+    /// injected into, such as `/users`, where `/` matches every route.
+    pub prefix: String,
+    /// [`ChaosRule::latency`] is the rate of requests that are delayed, and how long
+    /// they are delayed for.
+    pub latency: Option<(f64, Duration)>,
+    /// [`ChaosRule::error_rate`] is the rate of requests answered with a
+    /// `500 INTERNAL SERVER ERROR`.
+    pub error_rate: f64,
+    /// [`ChaosRule::drop_rate`] is the rate of requests whose connection is closed
+    /// without an answer.
+    pub drop_rate: f64,
+}
+
+/// [`Chaos`] injects faults into requests based on every [`ChaosRule`] their path
+/// matches, so the resilience of clients can be tested against the server in
+/// staging. It counts every fault it injects.
+#[derive(Debug, Default)]
+pub struct Chaos {
+    /// [`Chaos::rules`] are the rules requests are matched against.
+    pub rules: Vec<ChaosRule>,
+    /// [`Chaos::injected`] is how many faults were injected.
+    injected: AtomicU64,
+}
+
+impl ChaosRule {
+    /// [`ChaosRule::new`] creates a [`ChaosRule`] for routes under the path
+    /// `prefix`, which injects no faults.
+    /// # Example
+    /// [`ChaosRule::new`] can be used to make a tenth of `/users` requests slow, and
+    /// fail one in twenty:
+    /// ```rust
+    /// use minimal_api::server::chaos::ChaosRule;
+    /// use std::time::Duration;
+    ///
+    /// fn create_chaos_rule() -> ChaosRule {
+    ///     ChaosRule::new("/users")
+    ///         .with_latency(0.1, Duration::from_secs(2))
+    ///         .with_errors(0.05)
+    /// }
+    /// ```
+    pub fn new(prefix: &str) -> Self {
+        ChaosRule {
+            prefix: prefix.trim_end_matches('/').to_string(),
+            latency: None,
+            error_rate: 0.0,
+            drop_rate: 0.0,
+        }
+    }
+
+    /// [`ChaosRule::with_latency`] will delay `rate` of requests by `delay`, and
+    /// return the [`ChaosRule`].
+    pub fn with_latency(mut self, rate: f64, delay: Duration) -> Self {
+        self.latency = Some((rate.clamp(0.0, 1.0), delay));
+        self
+    }
+
+    /// [`ChaosRule::with_errors`] will answer `rate` of requests with a
+    /// `500 INTERNAL SERVER ERROR`, and return the [`ChaosRule`].
+    pub fn with_errors(mut self, rate: f64) -> Self {
+        self.error_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// [`ChaosRule::with_drops`] will close the connection of `rate` of requests
+    /// without an answer, and return the [`ChaosRule`].
+    pub fn with_drops(mut self, rate: f64) -> Self {
+        self.drop_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// [`ChaosRule::matches`] will return `true` if `path` is
+    /// [`ChaosRule::prefix`] or under it, and `false` otherwise.
+    pub fn matches(&self, path: &str) -> bool {
+        path.strip_prefix(&self.prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/') || self.prefix.is_empty())
+    }
+}
+
+impl Chaos {
+    /// [`Chaos::new`] creates a [`Chaos`] that injects no faults.
+    pub fn new() -> Self {
+        Chaos::default()
+    }
+
+    /// [`Chaos::with_rule`] will inject the faults of `rule`, and return the
+    /// [`Chaos`].
+    pub fn with_rule(mut self, rule: ChaosRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// [`Chaos::faults`] will decide which faults to inject into a request for
+    /// `path`, where a [`Fault::Latency`] always comes first, and at most one
+    /// [`Fault::DropConnection`] or [`Fault::ServerError`] follows it.
+    pub fn faults(&self, path: &str) -> Vec<Fault> {
+        let mut faults = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.matches(path)) {
+            if let Some((rate, delay)) = rule.latency
+                && is_hit(rate)
+            {
+                faults.push(Fault::Latency(delay));
+            }
+            if is_hit(rule.drop_rate) {
+                faults.push(Fault::DropConnection);
+                break;
+            }
+            if is_hit(rule.error_rate) {
+                faults.push(Fault::ServerError);
+                break;
+            }
+        }
+        self.injected
+            .fetch_add(faults.len() as u64, Ordering::Relaxed);
+        faults
+    }
+
+    /// [`Chaos::injected`] will get how many faults were injected since the server
+    /// started.
+    pub fn injected(&self) -> u64 {
+        self.injected.load(Ordering::Relaxed)
+    }
+
+    /// [`Chaos::is_empty`] will return `true` if [`Chaos`] has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// [`is_hit`] will return `true` for `rate` of calls, at random.
+fn is_hit(rate: f64) -> bool {
+    rate > 0.0 && (rate >= 1.0 || random_fraction() < rate)
+}
+
+/// [`random_fraction`] will get a random number from `0.0` up to `1.0`, which is
+/// random enough to pick requests, without a dependency on a random number crate.
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1_u64 << 53) as f64
+}
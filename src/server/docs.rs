@@ -0,0 +1,72 @@
+use http::route::RouteSpec;
+
+/// [`DOCS_PATH`] is the path the server answers with the [`render_docs`] page on,
+/// unless a route of its own serves it.
+pub const DOCS_PATH: &str = "/docs";
+
+/// [`render_docs`] will render an HTML page that lists every [`RouteSpec`] in
+/// `routes`, with its description, its parameters, and a ready-to-run `curl` command
+/// from [`RouteSpec::example_curl`] for the server at `base_url`.
+/// # Example
+/// [`render_docs`] can be used to document the routes of the server:
+/// ```rust
+/// use minimal_api::{routes::index, server::docs};
+///
+/// fn get_docs_page() -> String {
+///     docs::render_docs(&index::get_router().specs(), "http://localhost:8080")
+/// }
+/// ```
+pub fn render_docs(routes: &[RouteSpec], base_url: &str) -> String {
+    let sections: String = routes
+        .iter()
+        .map(|route| {
+            let params: String = route
+                .params
+                .iter()
+                .map(|param| {
+                    let description = param
+                        .description
+                        .as_deref()
+                        .map(|description| format!(": {}", escape_html(description)))
+                        .unwrap_or_default();
+                    format!(
+                        "<li><code>{}</code> ({}, <code>{}</code>){description}</li>",
+                        escape_html(&param.name),
+                        param.location,
+                        escape_html(&param.type_name)
+                    )
+                })
+                .collect();
+            let params = if params.is_empty() {
+                "<p>No parameters.</p>".to_string()
+            } else {
+                format!("<ul>{params}</ul>")
+            };
+            let description = route
+                .description
+                .as_deref()
+                .map(|description| format!("<p>{}</p>", escape_html(description)))
+                .unwrap_or_default();
+            format!(
+                "<section><h2><code>{} {}</code></h2>{description}{params}<pre><code>{}</code></pre></section>",
+                escape_html(route.method.as_str()),
+                escape_html(&route.request_pattern),
+                escape_html(&route.example_curl(base_url))
+            )
+        })
+        .collect();
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>API docs</title></head>\
+         <body><h1>API docs</h1>{sections}</body></html>"
+    )
+}
+
+/// [`escape_html`] will escape the characters of `text` that HTML gives meaning
+/// to.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,184 @@
+use http::{
+    constants::{CACHE_CONTROL, CONTENT_EVENT_STREAM},
+    export::sse_event,
+    respond::Respond,
+    response::Response,
+    status::Status,
+};
+use logger::log_warning;
+use std::{
+    sync::{
+        Mutex,
+        mpsc::{self, RecvTimeoutError, TrySendError},
+    },
+    time::{Duration, Instant},
+};
+
+/// [`DEFAULT_EVENT_CAPACITY`] is how many events a subscriber of an [`EventBus`]
+/// can fall behind by before it's unsubscribed, unless
+/// [`EventBus::with_capacity`] is used.
+pub const DEFAULT_EVENT_CAPACITY: usize = 64;
+
+/// [`EVENT_STREAM_KEEP_ALIVE`] is how long an [`EventBus::event_stream`] waits
+/// without an event before sending a comment, so a client that went away is
+/// noticed.
+pub const EVENT_STREAM_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// [`EventBus`] is a typed, thread-safe publish/subscribe channel, which lets
+/// route handlers and background jobs share events of type `T`.
+///
+/// Every subscriber gets its own bounded [`mpsc::Receiver`], so a published event
+/// is delivered to every subscriber that is still listening, and a subscriber
+/// that stops reading is unsubscribed instead of holding its events forever.
+pub struct EventBus<T: Clone + Send + 'static> {
+    /// [`EventBus::subscribers`] is a collection of [`mpsc::SyncSender`], one per
+    /// subscriber.
+    subscribers: Mutex<Vec<mpsc::SyncSender<T>>>,
+    /// [`EventBus::capacity`] is how many events each subscriber can fall behind
+    /// by before it's unsubscribed.
+    capacity: usize,
+}
+
+impl<T: Clone + Send + 'static> EventBus<T> {
+    /// [`EventBus::new`] creates an [`EventBus`] without any subscribers, where
+    /// each subscriber can fall [`DEFAULT_EVENT_CAPACITY`] events behind.
+    /// # Example
+    /// [`EventBus::new`] can be used to share an [`EventBus`] across the server
+    /// with a `static`:
+    /// ```rust
+    /// use minimal_api::server::events::EventBus;
+    /// use std::sync::OnceLock;
+    ///
+    /// static USER_EVENTS: OnceLock<EventBus<String>> = OnceLock::new();
+    ///
+    /// fn get_user_events() -> &'static EventBus<String> {
+    ///     USER_EVENTS.get_or_init(EventBus::new)
+    /// }
+    /// ```
+    pub fn new() -> Self {
+        EventBus {
+            subscribers: Mutex::new(Vec::new()),
+            capacity: DEFAULT_EVENT_CAPACITY,
+        }
+    }
+
+    /// [`EventBus::with_capacity`] will set how many events each subscriber can
+    /// fall behind by before it's unsubscribed, which is at least one.
+    /// # Example
+    /// [`EventBus::with_capacity`] can be used to let slow subscribers catch up on
+    /// bursts of events:
+    /// ```rust
+    /// use minimal_api::server::events::EventBus;
+    ///
+    /// fn create_order_events() -> EventBus<String> {
+    ///     EventBus::new().with_capacity(1024)
+    /// }
+    /// ```
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// [`EventBus::subscribe`] will register a new subscriber, and return the
+    /// [`mpsc::Receiver`] that every future event is delivered to. Dropping the
+    /// [`mpsc::Receiver`] unsubscribes, and a subscriber that falls behind by the
+    /// capacity of the [`EventBus`] is unsubscribed, so its [`mpsc::Receiver`]
+    /// disconnects once the events it holds are read.
+    /// # Example
+    /// [`EventBus::subscribe`] can be used to wait for the next event:
+    /// ```rust
+    /// use minimal_api::server::events::EventBus;
+    ///
+    /// fn wait_for_event(events: &EventBus<String>) -> Option<String> {
+    ///     events.subscribe().recv().ok()
+    /// }
+    /// ```
+    pub fn subscribe(&self) -> mpsc::Receiver<T> {
+        let (sender, receiver) = mpsc::sync_channel(self.capacity);
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// [`EventBus::publish`] will send a copy of `event` to every subscriber,
+    /// drop any subscribers that have stopped listening or fallen behind by the
+    /// capacity of the [`EventBus`], and return how many subscribers received the
+    /// event. Publishing never waits on a subscriber.
+    /// # Example
+    /// [`EventBus::publish`] can be used to announce that a user was created:
+    /// ```rust
+    /// use minimal_api::server::events::EventBus;
+    ///
+    /// fn announce_user(events: &EventBus<String>, id: &str) -> usize {
+    ///     events.publish(format!("user {id} created"))
+    /// }
+    /// ```
+    pub fn publish(&self, event: T) -> usize {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|subscriber| match subscriber.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                log_warning!(
+                    "a subscriber fell {} events behind, unsubscribing it.",
+                    self.capacity
+                );
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+        subscribers.len()
+    }
+
+    /// [`EventBus::subscriber_count`] will return how many subscribers are
+    /// registered, including any that have stopped listening since the last
+    /// [`EventBus::publish`].
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.lock().unwrap().len()
+    }
+
+    /// [`EventBus::event_stream`] will subscribe to the [`EventBus`], and return a
+    /// [`Response::stream`] that sends every event to the client as a server-sent
+    /// event for `duration`, with a comment after each [`EVENT_STREAM_KEEP_ALIVE`]
+    /// without one. The stream ends early when the client goes away or falls
+    /// behind, and an `EventSource` reconnects once it ends. The stream holds a
+    /// worker while it's open, so `duration` should be short enough to share the
+    /// workers with other requests.
+    /// # Example
+    /// [`EventBus::event_stream`] can be used to push events to a browser:
+    /// ```rust
+    /// use http::response::Response;
+    /// use minimal_api::server::events::EventBus;
+    /// use std::time::Duration;
+    ///
+    /// fn stream_user_events(events: &EventBus<String>) -> Response {
+    ///     events.event_stream(Duration::from_secs(60))
+    /// }
+    /// ```
+    pub fn event_stream(&self, duration: Duration) -> Response
+    where
+        T: Respond,
+    {
+        let receiver = self.subscribe();
+        let deadline = Instant::now() + duration;
+        Response::stream(Status::Ok, CONTENT_EVENT_STREAM, move |writer| {
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(());
+                }
+                match receiver.recv_timeout(remaining.min(EVENT_STREAM_KEEP_ALIVE)) {
+                    Ok(event) => writer.write_chunk(sse_event(&event).as_bytes())?,
+                    Err(RecvTimeoutError::Timeout) => writer.write_chunk(b": keep-alive\n\n")?,
+                    Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            }
+        })
+        .with_header(CACHE_CONTROL, "no-cache")
+    }
+}
+
+/// Implement [`Default`] for [`EventBus`].
+impl<T: Clone + Send + 'static> Default for EventBus<T> {
+    fn default() -> Self {
+        EventBus::new()
+    }
+}
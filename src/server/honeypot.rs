@@ -0,0 +1,263 @@
+use std::{
+    io::{self, Write},
+    net::TcpStream,
+    str::FromStr,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+/// [`SCANNER_PATHS`] are paths that vulnerability scanners probe for, which no
+/// route of this server serves, so any request for them is from a scanner.
+pub const SCANNER_PATHS: &[&str] = &[
+    "/.env",
+    "/.git",
+    "/.aws",
+    "/wp-login.php",
+    "/wp-admin",
+    "/xmlrpc.php",
+    "/phpmyadmin",
+    "/admin.php",
+    "/config.php",
+    "/server-status",
+    "/cgi-bin",
+];
+
+/// [`DEFAULT_BAN_DURATION`] is how long the address of a scanner that hit a
+/// [`Honeypot`] is banned, unless [`Honeypot::with_ban_duration`] is used.
+pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// [`DEFAULT_TARPIT_DURATION`] is how long a [`HoneypotMode::Tarpit`] keeps a
+/// scanner waiting, unless [`Honeypot::with_tarpit`] is used.
+pub const DEFAULT_TARPIT_DURATION: Duration = Duration::from_secs(60);
+
+/// [`DEFAULT_TARPIT_INTERVAL`] is how long a [`HoneypotMode::Tarpit`] waits between
+/// each byte it sends, unless [`Honeypot::with_tarpit`] is used.
+pub const DEFAULT_TARPIT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// [`DEFAULT_JUNK_BYTES`] is how many bytes of junk a [`HoneypotMode::Junk`] sends,
+/// unless [`Honeypot::with_junk_bytes`] is used.
+pub const DEFAULT_JUNK_BYTES: usize = 4 * 1024 * 1024;
+
+/// [`MAX_TRAPPED_CONNECTIONS`] is how many scanner connections a [`Honeypot`]
+/// holds at once, so scanners can't use it to exhaust the server. Connections
+/// past it are closed right away.
+pub const MAX_TRAPPED_CONNECTIONS: usize = 32;
+
+/// [`HoneypotMode`] decides how a [`Honeypot`] answers scanners.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HoneypotMode {
+    /// [`HoneypotMode::Off`] serves scanner paths like any other path.
+    Off,
+    /// [`HoneypotMode::Tarpit`] answers extremely slowly, a byte at a time, to
+    /// waste the time of scanners.
+    Tarpit,
+    /// [`HoneypotMode::Junk`] answers with a large body of junk.
+    Junk,
+}
+
+/// Implement [`FromStr`] for [`HoneypotMode`], which parses `"off"`, `"tarpit"`,
+/// or `"junk"`.
+impl FromStr for HoneypotMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "off" => Ok(HoneypotMode::Off),
+            "tarpit" => Ok(HoneypotMode::Tarpit),
+            "junk" => Ok(HoneypotMode::Junk),
+            _ => Err(format!("unknown honeypot mode '{value}'")),
+        }
+    }
+}
+
+/// [`Honeypot`] catches requests for paths that only scanners ask for, before
+/// they are routed, so they never reach route handlers or their logs. Scanners
+/// are answered on a thread of their own, so they don't hold up workers, and their
+/// address is meant to be banned.
+#[derive(Debug)]
+pub struct Honeypot {
+    /// [`Honeypot::mode`] decides how scanners are answered.
+    pub mode: HoneypotMode,
+    /// [`Honeypot::paths`] are the paths that only scanners ask for, which also
+    /// catch every path under them.
+    pub paths: Vec<String>,
+    /// [`Honeypot::ban_duration`] is how long the address of a scanner is banned.
+    pub ban_duration: Duration,
+    /// [`Honeypot::tarpit_duration`] is how long a [`HoneypotMode::Tarpit`] keeps a
+    /// scanner waiting.
+    pub tarpit_duration: Duration,
+    /// [`Honeypot::tarpit_interval`] is how long a [`HoneypotMode::Tarpit`] waits
+    /// between each byte it sends.
+    pub tarpit_interval: Duration,
+    /// [`Honeypot::junk_bytes`] is how many bytes of junk a [`HoneypotMode::Junk`]
+    /// sends.
+    pub junk_bytes: usize,
+    /// [`Honeypot::trapped`] is how many scanner connections are held right now.
+    trapped: AtomicUsize,
+    /// [`Honeypot::caught`] is how many scanner requests were caught.
+    caught: AtomicU64,
+}
+
+impl Honeypot {
+    /// [`Honeypot::new`] creates a [`Honeypot`] for [`SCANNER_PATHS`] that answers
+    /// with `mode`.
+    /// # Example
+    /// [`Honeypot::new`] can be used to tarpit scanners for longer:
+    /// ```rust
+    /// use minimal_api::server::honeypot::{Honeypot, HoneypotMode};
+    /// use std::time::Duration;
+    ///
+    /// fn create_honeypot() -> Honeypot {
+    ///     Honeypot::new(HoneypotMode::Tarpit)
+    ///         .with_paths(&["/vendor/phpunit"])
+    ///         .with_tarpit(Duration::from_secs(300), Duration::from_secs(5))
+    /// }
+    /// ```
+    pub fn new(mode: HoneypotMode) -> Self {
+        Honeypot {
+            mode,
+            paths: SCANNER_PATHS.iter().map(|path| path.to_string()).collect(),
+            ban_duration: DEFAULT_BAN_DURATION,
+            tarpit_duration: DEFAULT_TARPIT_DURATION,
+            tarpit_interval: DEFAULT_TARPIT_INTERVAL,
+            junk_bytes: DEFAULT_JUNK_BYTES,
+            trapped: AtomicUsize::new(0),
+            caught: AtomicU64::new(0),
+        }
+    }
+
+    /// [`Honeypot::with_paths`] will add every path in `paths` to
+    /// [`Honeypot::paths`], and return the [`Honeypot`].
+    pub fn with_paths(mut self, paths: &[&str]) -> Self {
+        self.paths.extend(paths.iter().map(|path| path.to_string()));
+        self
+    }
+
+    /// [`Honeypot::with_ban_duration`] will set [`Honeypot::ban_duration`], and
+    /// return the [`Honeypot`].
+    pub fn with_ban_duration(mut self, ban_duration: Duration) -> Self {
+        self.ban_duration = ban_duration;
+        self
+    }
+
+    /// [`Honeypot::with_tarpit`] will set [`Honeypot::tarpit_duration`] and
+    /// [`Honeypot::tarpit_interval`], and return the [`Honeypot`].
+    pub fn with_tarpit(mut self, tarpit_duration: Duration, tarpit_interval: Duration) -> Self {
+        self.tarpit_duration = tarpit_duration;
+        self.tarpit_interval = tarpit_interval;
+        self
+    }
+
+    /// [`Honeypot::with_junk_bytes`] will set [`Honeypot::junk_bytes`], and return
+    /// the [`Honeypot`].
+    pub fn with_junk_bytes(mut self, junk_bytes: usize) -> Self {
+        self.junk_bytes = junk_bytes;
+        self
+    }
+
+    /// [`Honeypot::is_trap`] will return `true` if the [`Honeypot`] is on and `path`
+    /// is one of [`Honeypot::paths`] or under one of them, ignoring case, and
+    /// `false` otherwise.
+    pub fn is_trap(&self, path: &str) -> bool {
+        self.mode != HoneypotMode::Off
+            && self.paths.iter().any(|trap| {
+                path.get(..trap.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(trap))
+                    && matches!(path.as_bytes().get(trap.len()), None | Some(b'/'))
+            })
+    }
+
+    /// [`Honeypot::caught`] will get how many scanner requests were caught since
+    /// the server started.
+    pub fn caught(&self) -> u64 {
+        self.caught.load(Ordering::Relaxed)
+    }
+
+    /// [`Honeypot::trap`] will answer the scanner on the other end of `stream` on a
+    /// thread of its own, or close `stream` right away if
+    /// [`MAX_TRAPPED_CONNECTIONS`] are already held.
+    pub fn trap(&'static self, stream: TcpStream) -> io::Result<()> {
+        self.caught.fetch_add(1, Ordering::Relaxed);
+        if self.trapped.fetch_add(1, Ordering::Relaxed) >= MAX_TRAPPED_CONNECTIONS {
+            self.trapped.fetch_sub(1, Ordering::Relaxed);
+            return Ok(());
+        }
+        thread::Builder::new()
+            .name("honeypot".to_string())
+            .spawn(move || {
+                let mut stream = stream;
+                // scanners hanging up early is the expected outcome
+                let _ = self.respond(&mut stream);
+                self.trapped.fetch_sub(1, Ordering::Relaxed);
+            })
+            .map(|_| ())
+            .inspect_err(|_| {
+                self.trapped.fetch_sub(1, Ordering::Relaxed);
+            })
+    }
+
+    /// [`Honeypot::respond`] will write the answer for a scanner to `writer`, based
+    /// on [`Honeypot::mode`].
+    pub fn respond(&self, writer: &mut impl Write) -> io::Result<()> {
+        match self.mode {
+            HoneypotMode::Off => Ok(()),
+            HoneypotMode::Tarpit => {
+                // promise a large page, then send it too slowly to ever finish
+                writer.write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: 1048576\r\n\r\n",
+                )?;
+                writer.flush()?;
+                let ticks =
+                    self.tarpit_duration.as_nanos() / self.tarpit_interval.as_nanos().max(1);
+                for _ in 0..ticks {
+                    thread::sleep(self.tarpit_interval);
+                    writer.write_all(b" ")?;
+                    writer.flush()?;
+                }
+                Ok(())
+            }
+            HoneypotMode::Junk => {
+                writer.write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\n\r\n",
+                        self.junk_bytes
+                    )
+                    .as_bytes(),
+                )?;
+                let mut junk = Junk::new(self.junk_bytes as u64);
+                let mut chunk = [0; 8192];
+                let mut remaining = self.junk_bytes;
+                while remaining > 0 {
+                    let length = remaining.min(chunk.len());
+                    chunk[..length].fill_with(|| junk.next_byte());
+                    writer.write_all(&chunk[..length])?;
+                    remaining -= length;
+                }
+                writer.flush()
+            }
+        }
+    }
+}
+
+/// [`Junk`] generates lowercase letters that look random enough to keep a scanner
+/// parsing them.
+struct Junk {
+    /// [`Junk::state`] is the state of the xorshift generator.
+    state: u64,
+}
+
+impl Junk {
+    /// [`Junk::new`] creates a [`Junk`] generator from `seed`.
+    fn new(seed: u64) -> Self {
+        Junk { state: seed | 1 }
+    }
+
+    /// [`Junk::next_byte`] will get the next lowercase letter.
+    fn next_byte(&mut self) -> u8 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        b'a' + (self.state % 26) as u8
+    }
+}
@@ -3,26 +3,229 @@ use crate::{
         app::{CRATE_NAME, CRATE_VERSION},
         server::ServerConfig,
     },
-    server::thread_pool::ThreadPool,
+    server::{
+        acceptor,
+        access_control::AccessControl,
+        chaos::{Chaos, Fault},
+        docs::{self, DOCS_PATH},
+        honeypot::{Honeypot, HoneypotMode},
+        long_poll::{LongPolls, PARKED_WRITE_TIMEOUT},
+        metrics::{METRICS_PATH, Metrics, ResponseWarnings},
+        priority::{self, PRIORITY_PATHS, PRIORITY_PEEK_TIMEOUT},
+        profiler::{DEFAULT_SAMPLE_INTERVAL, PROFILE_PATH, Profiler},
+        readiness::{READINESS_PATH, Readiness},
+        service,
+        shutdown::Shutdown,
+        thread_pool::{ThreadPool, WorkerStart},
+    },
+};
+use http::{
+    arena,
+    audit::{AuditLog, AuditSink},
+    batch::{BATCH_PATH, Batch, BatchRequest},
+    body::BodyReader,
+    charset::Charset,
+    compression::{self, CompressionPolicy},
+    constants::{self, ALLOW},
+    context::{Deadline, SharedState},
+    correlation::Correlation,
+    extensions::TypeMap,
+    intern::headers::{ACCEPT_ENCODING, CONTENT_TYPE, COOKIE, HOST, IF_MODIFIED_SINCE},
+    methods::{self, Method},
+    multipart::{self, Multipart},
+    parser::{self, ParsingMode},
+    request::{ClientAddress, Request},
+    respond::Respond,
+    response::Response,
+    route::Route,
+    router::Router,
+    session::{SESSION_CLEANUP_INTERVAL, Session, Sessions},
+    static_files::StaticFiles,
+    status::Status,
+    tenant::{TenantId, TenantResolver},
 };
-use http::{request::Request, response::Response, route::Route};
 use logger::{log_debug, log_info, log_warning};
 use std::{
-    collections::HashMap,
-    io::{BufReader, prelude::*},
-    net::{TcpListener, TcpStream},
-    sync::OnceLock,
+    io::{BufReader, Cursor, prelude::*},
+    net::{IpAddr, Ipv4Addr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, OnceLock},
+    thread::JoinHandle,
+    time::{Duration, Instant},
 };
 
-/// [`ENDPOINTS`] is a `static` [`Vec`] of [`Route`] values that is initialized once
+/// [`ROUTER`] is a `static` [`Router`] of every [`Route`](http::route::Route) that is initialized once
 /// in a thread-safe manner.
-static ENDPOINTS: OnceLock<Vec<Route>> = OnceLock::new();
-fn get_endpoints() -> &'static Vec<Route> {
-    ENDPOINTS.get_or_init(crate::routes::index::get_endpoints)
+static ROUTER: OnceLock<Router> = OnceLock::new();
+fn get_router() -> &'static Router {
+    ROUTER.get_or_init(crate::routes::index::get_router)
+}
+
+/// [`STATIC_FILES`] is a `static` [`StaticFiles`] that is initialized once in a
+/// thread-safe manner, so assets are only fingerprinted when the server starts.
+static STATIC_FILES: OnceLock<StaticFiles> = OnceLock::new();
+fn get_static_files() -> &'static StaticFiles {
+    STATIC_FILES.get_or_init(crate::routes::index::get_static_files)
+}
+
+/// [`READINESS`] is a `static` [`Readiness`] that is initialized once in a
+/// thread-safe manner, so every worker sees the same dependency statuses.
+static READINESS: OnceLock<Readiness> = OnceLock::new();
+fn get_readiness() -> &'static Readiness {
+    READINESS.get_or_init(|| Readiness::new(crate::routes::index::get_dependency_checks()))
+}
+
+/// [`ACCESS_CONTROL`] is a `static` [`AccessControl`] that is initialized once in a
+/// thread-safe manner, combining the route group lists from
+/// [`get_access_control`](crate::routes::index::get_access_control) with the global
+/// list from [`ServerConfig::access_list`].
+static ACCESS_CONTROL: OnceLock<AccessControl> = OnceLock::new();
+fn get_access_control() -> &'static AccessControl {
+    ACCESS_CONTROL.get_or_init(|| {
+        crate::routes::index::get_access_control().with_global(ServerConfig::new().access_list)
+    })
+}
+
+/// [`HONEYPOT`] is a `static` [`Honeypot`] that is initialized once in a
+/// thread-safe manner, answering scanners with [`ServerConfig::honeypot_mode`].
+static HONEYPOT: OnceLock<Honeypot> = OnceLock::new();
+fn get_honeypot() -> &'static Honeypot {
+    HONEYPOT.get_or_init(|| Honeypot::new(ServerConfig::new().honeypot_mode))
+}
+
+/// [`AUDIT_LOG`] is a `static` [`AuditLog`] that is initialized once in a
+/// thread-safe manner, with the principal from
+/// [`get_audit_log`](crate::routes::index::get_audit_log) and a sink at
+/// [`ServerConfig::audit_log`], so nothing is audited when it is off.
+static AUDIT_LOG: OnceLock<AuditLog> = OnceLock::new();
+fn get_audit_log() -> &'static AuditLog {
+    AUDIT_LOG.get_or_init(|| {
+        let server_config = ServerConfig::new();
+        let audit_log = crate::routes::index::get_audit_log();
+        match server_config.audit_log {
+            Some(path) => audit_log.with_sink(
+                AuditSink::open(path, server_config.audit_rotation)
+                    .expect("cannot open AUDIT_LOG defined in .cargo/config.toml."),
+            ),
+            None => audit_log,
+        }
+    })
+}
+
+/// [`CHAOS`] is a `static` [`Chaos`] that is initialized once in a thread-safe
+/// manner, with the faults from [`get_chaos`](crate::routes::index::get_chaos) when
+/// [`ServerConfig::chaos_enabled`] is `true`, and no faults otherwise.
+static CHAOS: OnceLock<Chaos> = OnceLock::new();
+fn get_chaos() -> &'static Chaos {
+    CHAOS.get_or_init(|| {
+        if ServerConfig::new().chaos_enabled {
+            crate::routes::index::get_chaos()
+        } else {
+            Chaos::new()
+        }
+    })
+}
+
+/// [`SESSIONS`] is a `static` [`Sessions`] that is initialized once in a
+/// thread-safe manner, keeping sessions in the store of
+/// [`ServerConfig::session_backend`] for [`ServerConfig::session_ttl`].
+static SESSIONS: OnceLock<Sessions> = OnceLock::new();
+fn get_sessions() -> &'static Sessions {
+    SESSIONS.get_or_init(|| {
+        let server_config = ServerConfig::new();
+        let store = server_config
+            .session_backend
+            .open()
+            .unwrap_or_else(|error| {
+                panic!(
+                    "cannot open SESSION_STORE {} defined in .cargo/config.toml: {error}.",
+                    server_config.session_backend
+                )
+            });
+        let sessions = match server_config.session_ttl {
+            Some(ttl) => Sessions::new(store).with_ttl(ttl),
+            None => Sessions::new(store),
+        };
+        let sessions = if server_config.session_sliding {
+            sessions.with_sliding_renewal()
+        } else {
+            sessions
+        };
+        if server_config.session_secure {
+            sessions.with_secure_cookie()
+        } else {
+            sessions
+        }
+    })
+}
+
+/// [`PROPAGATED_HEADERS`] is a `static` collection of every header in
+/// [`ServerConfig::propagated_headers`], which is initialized once in a
+/// thread-safe manner.
+static PROPAGATED_HEADERS: OnceLock<Vec<String>> = OnceLock::new();
+fn get_propagated_headers() -> &'static [String] {
+    PROPAGATED_HEADERS.get_or_init(|| ServerConfig::new().propagated_headers)
+}
+
+/// [`TENANT_RESOLVER`] is a `static` [`TenantResolver`] from
+/// [`ServerConfig::tenant_resolver`], which is initialized once in a thread-safe
+/// manner, or [`None`] when tenants aren't resolved.
+static TENANT_RESOLVER: OnceLock<Option<TenantResolver>> = OnceLock::new();
+fn get_tenant_resolver() -> Option<&'static TenantResolver> {
+    TENANT_RESOLVER
+        .get_or_init(|| ServerConfig::new().tenant_resolver)
+        .as_ref()
+}
+
+/// [`RESPONSE_WARNINGS`] is a `static` [`ResponseWarnings`] that is initialized
+/// once in a thread-safe manner, with the thresholds from
+/// [`ServerConfig::slow_request_threshold`] and
+/// [`ServerConfig::large_response_threshold`].
+static RESPONSE_WARNINGS: OnceLock<ResponseWarnings> = OnceLock::new();
+fn get_response_warnings() -> &'static ResponseWarnings {
+    RESPONSE_WARNINGS.get_or_init(|| {
+        let server_config = ServerConfig::new();
+        ResponseWarnings::new(
+            server_config.slow_request_threshold,
+            server_config.large_response_threshold,
+        )
+    })
+}
+
+/// [`COMPRESSION_POLICY`] is a `static` [`CompressionPolicy`] that is initialized
+/// once in a thread-safe manner, with the exclusions from
+/// [`ServerConfig::get_compression_policy`].
+static COMPRESSION_POLICY: OnceLock<CompressionPolicy> = OnceLock::new();
+fn get_compression_policy() -> &'static CompressionPolicy {
+    COMPRESSION_POLICY.get_or_init(|| ServerConfig::new().get_compression_policy())
+}
+
+/// [`BATCH`] is a `static` [`Batch`] that is initialized once in a thread-safe
+/// manner, running [`ServerConfig::batch_concurrency`] sub-requests at a time, or
+/// [`None`] when batching is off.
+static BATCH: OnceLock<Option<Batch>> = OnceLock::new();
+fn get_batch() -> Option<&'static Batch> {
+    BATCH
+        .get_or_init(|| ServerConfig::new().batch_concurrency.map(Batch::new))
+        .as_ref()
+}
+
+/// [`PROFILER`] is a `static` [`Profiler`] that is initialized once in a
+/// thread-safe manner, so every worker records frames in the same place.
+static PROFILER: OnceLock<Profiler> = OnceLock::new();
+fn get_profiler() -> &'static Profiler {
+    PROFILER.get_or_init(|| Profiler::new(ServerConfig::new().profiler_enabled))
+}
+
+/// [`LONG_POLLS`] is a `static` [`LongPolls`] that is initialized once in a
+/// thread-safe manner, so every worker parks its long polls in the same place.
+static LONG_POLLS: OnceLock<LongPolls> = OnceLock::new();
+fn get_long_polls() -> &'static LongPolls {
+    LONG_POLLS.get_or_init(LongPolls::new)
 }
 
 /// [`listen`] will listen for requests to the server and dispatch responses in
-/// a thread-safe manner.
+/// a thread-safe manner, until the process is asked to stop by a `SIGTERM` or
+/// `SIGINT` signal, such as from launchd, systemd, Docker, or Ctrl+C.
 /// # Example
 /// ```rust
 /// use minimal_api::server::listener;
@@ -32,101 +235,919 @@ fn get_endpoints() -> &'static Vec<Route> {
 /// }
 /// ```
 pub fn listen() {
-    let server_config = ServerConfig::new();
-    let listener = TcpListener::bind(server_config.get_server_address()).unwrap();
-    let endpoints = get_endpoints();
-    let pool = ThreadPool::new(server_config.workers);
+    let shutdown = Shutdown::new();
+    if let Err(error) = service::handle_stop_signals(&shutdown) {
+        log_warning!(
+            "can't handle stop signals, the server will not shut down gracefully: {error}"
+        );
+    }
+    serve(shutdown);
+}
 
+/// [`serve`] will listen for requests to the server and dispatch responses in a
+/// thread-safe manner until the provided [`Shutdown`] is triggered. Once it is,
+/// no new connections are accepted, and [`serve`] returns after every connection
+/// that was already accepted has been responded to.
+/// # Example
+/// [`serve`] can be used to stop the server from another thread:
+/// ```rust
+/// use minimal_api::server::{listener, shutdown::Shutdown};
+///
+/// fn start_stoppable_server() -> Shutdown {
+///     let shutdown = Shutdown::new();
+///     let server_shutdown = shutdown.clone();
+///     std::thread::spawn(move || listener::serve(server_shutdown));
+///     shutdown
+/// }
+/// ```
+pub fn serve(shutdown: Shutdown) {
+    let server_config = ServerConfig::new();
+    let listeners = if server_config.acceptors > 1 {
+        let address = server_config
+            .get_server_address()
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .expect("cannot resolve IP_ADDR and PORT defined in .cargo/config.toml.");
+        acceptor::bind_reuse_port(address, server_config.acceptors)
+            .unwrap_or_else(|error| panic!("cannot bind the ACCEPTORS listeners: {error}"))
+    } else {
+        vec![TcpListener::bind(server_config.get_server_address()).unwrap()]
+    };
+    let addresses: Vec<_> = listeners
+        .iter()
+        .filter_map(|listener| listener.local_addr().ok())
+        .collect();
+    let router = get_router();
+    // fingerprint static assets and load access lists before accepting any connections
+    get_static_files();
+    let access_control = get_access_control();
+    if get_honeypot().mode != HoneypotMode::Off {
+        log_info!(
+            "honeypot enabled in {:?} mode for {} scanner path(s)",
+            get_honeypot().mode,
+            get_honeypot().paths.len()
+        );
+    }
+    #[cfg(not(feature = "socket-extras"))]
+    if server_config.socket_linger.is_some() {
+        log_warning!(
+            "SOCKET_LINGER needs the `socket-extras` cargo feature, closing connections in the background."
+        );
+    }
+    if let Some(sink) = get_audit_log().sink() {
+        log_info!(
+            "auditing POST, PUT, and DELETE requests to {}, {}.",
+            sink.path().display(),
+            sink.rotation()
+        );
+    }
+    if !get_chaos().is_empty() {
+        log_warning!(
+            "chaos enabled with {} rule(s), requests will fail on purpose.",
+            get_chaos().rules.len()
+        );
+    }
+    let sessions = get_sessions();
+    match sessions.ttl {
+        Some(ttl) => log_info!(
+            "sessions stored in {} for {ttl:?}{}.",
+            server_config.session_backend,
+            if sessions.sliding {
+                " since their last request"
+            } else {
+                ""
+            }
+        ),
+        None => log_info!("sessions stored in {}.", server_config.session_backend),
+    }
+    if !server_config.propagated_headers.is_empty() {
+        log_info!(
+            "propagating {} to upstream calls.",
+            server_config.propagated_headers.join(", ")
+        );
+    }
+    if let Some(tenant_resolver) = get_tenant_resolver() {
+        log_info!("resolving the tenant of every request by {tenant_resolver}.");
+    }
+    let response_warnings = get_response_warnings();
+    if let Some(threshold) = response_warnings.slow_request_threshold {
+        log_info!("warning about requests slower than {threshold:?}.");
+    }
+    if let Some(threshold) = response_warnings.large_response_threshold {
+        log_info!("warning about responses larger than {threshold} bytes.");
+    }
+    let compression_policy = get_compression_policy();
     log_info!(
-        "{CRATE_NAME} v{CRATE_VERSION} listening on http://{}",
-        server_config.get_server_address()
+        "compressing responses of at least {} bytes, except {} content type(s) and {} path(s).",
+        compression_policy.min_bytes,
+        compression_policy.excluded_content_types.len(),
+        compression_policy.excluded_paths.len()
     );
+    if server_config.metrics_enabled {
+        log_info!("metrics enabled at {METRICS_PATH}.");
+    }
+    if let Some(batch) = get_batch() {
+        log_info!(
+            "batching enabled at {BATCH_PATH}, running {} sub-request(s) at a time.",
+            batch.concurrency
+        );
+    }
+    if get_profiler().enabled {
+        log_info!("profiler enabled at {PROFILE_PATH}, keep it behind an access list.");
+    }
+    if !access_control.is_empty() {
+        log_info!(
+            "access lists enabled: {} global allow, {} global deny, {} route group(s)",
+            access_control.global.allow.len(),
+            access_control.global.deny.len(),
+            access_control.groups.len()
+        );
+    }
+    log_debug!("serving {} route(s):\n{router}", router.routes.len());
+    // the most specific of two conflicting routes is served, so only a shadowed
+    // route is never served
+    for conflict in router.conflicts() {
+        if conflict.shadowed {
+            log_warning!("route conflict: {conflict}.");
+        } else {
+            log_debug!("route conflict: {conflict}.");
+        }
+    }
+    // check dependencies before accepting any connections, then keep checking them
+    let readiness = get_readiness();
+    for route in &router.routes {
+        for dependency in route
+            .spec
+            .requires
+            .iter()
+            .filter(|name| !readiness.is_registered(name))
+        {
+            log_warning!(
+                "{} {} requires '{dependency}', which has no dependency check, so it will always return 503 SERVICE UNAVAILABLE.",
+                route.spec.method,
+                route.spec.request_pattern
+            );
+        }
+    }
+    readiness.run_checks();
+    if let Err(error) = readiness.spawn_checks(shutdown.clone()) {
+        log_warning!(
+            "can't spawn the readiness thread, dependencies will not be re-checked: {error}"
+        );
+    }
+    if sessions.ttl.is_some()
+        && let Err(error) = spawn_session_cleanup(sessions, shutdown.clone())
+    {
+        log_warning!(
+            "can't spawn the session cleanup thread, expired sessions will pile up: {error}"
+        );
+    }
+    if let Err(error) = get_long_polls().spawn_polls(shutdown.clone()) {
+        log_warning!(
+            "can't spawn the long poll thread, long polls will hold their worker: {error}"
+        );
+    }
+    let workers_per_acceptor = (server_config.workers / listeners.len()).max(1);
+    let validate_requests = server_config.validate_requests;
+    let parsing_mode = server_config.parsing_mode;
+    let max_body_bytes = server_config.max_body_bytes;
+    let metrics_enabled = server_config.metrics_enabled;
+    let request_timeout = server_config.request_timeout;
+    let priority_workers = server_config.priority_workers;
+    let warm_up = crate::routes::index::get_warm_up();
+    match (server_config.worker_start, &warm_up) {
+        (WorkerStart::Lazy, _) => {
+            log_info!("starting workers lazily, once every started worker is busy.")
+        }
+        (WorkerStart::Eager, Some(_)) => {
+            log_info!("warming up every worker, /ready will wait for them.")
+        }
+        (WorkerStart::Eager, None) => {}
+    }
+    if priority_workers > 0 {
+        log_info!(
+            "{priority_workers} priority worker(s) per acceptor for {} while the others are busy.",
+            PRIORITY_PATHS.join(" and ")
+        );
+    }
 
-    for stream in listener.incoming() {
-        let stream = stream.unwrap();
-        pool.execute(move || {
-            handle_connection(stream, endpoints);
-        });
+    log_info!("{CRATE_NAME} v{CRATE_VERSION} effective configuration:");
+    for (name, value) in server_config.get_summary() {
+        log_info!("  {name}: {value}");
     }
+    log_info!(
+        "{CRATE_NAME} v{CRATE_VERSION} listening on http://{} with {} acceptor(s)",
+        server_config.get_server_address(),
+        listeners.len()
+    );
+
+    // accept connections on dedicated threads, each owning its own group of workers
+    let acceptors: Vec<_> = listeners
+        .into_iter()
+        .map(|listener| {
+            let pool = Arc::new(match (server_config.worker_start, &warm_up) {
+                (WorkerStart::Lazy, _) => ThreadPool::lazy(workers_per_acceptor),
+                (WorkerStart::Eager, Some(warm_up)) => {
+                    ThreadPool::with_warm_up(workers_per_acceptor, warm_up.clone())
+                }
+                (WorkerStart::Eager, None) => ThreadPool::new(workers_per_acceptor),
+            });
+            readiness.wait_for(pool.ready());
+            let priority_pool =
+                (priority_workers > 0).then(|| Arc::new(ThreadPool::new(priority_workers)));
+            acceptor::spawn_acceptor(
+                listener,
+                server_config.get_socket_options(),
+                shutdown.clone(),
+                move |stream| {
+                    // a parked long poll is answered from the pool its request was on
+                    let serve_connection = move |stream, answer_pool| {
+                        handle_connection(
+                            stream,
+                            answer_pool,
+                            validate_requests,
+                            parsing_mode,
+                            max_body_bytes,
+                            metrics_enabled,
+                            request_timeout,
+                        )
+                    };
+                    // only a saturated pool is worth peeking at the request line for,
+                    // which a priority worker does, so a connection that sends
+                    // nothing never holds up the acceptor
+                    let Some(priority_pool) =
+                        priority_pool.as_ref().filter(|_| pool.is_saturated())
+                    else {
+                        let answer_pool = Arc::clone(&pool);
+                        pool.execute(move || serve_connection(stream, answer_pool));
+                        return;
+                    };
+                    let pool = Arc::clone(&pool);
+                    let answer_pool = Arc::clone(priority_pool);
+                    priority_pool.execute(move || {
+                        if priority::is_priority_request(
+                            &stream,
+                            &PRIORITY_PATHS,
+                            PRIORITY_PEEK_TIMEOUT,
+                        ) {
+                            serve_connection(stream, answer_pool);
+                        } else {
+                            let answer_pool = Arc::clone(&pool);
+                            pool.execute(move || serve_connection(stream, answer_pool));
+                        }
+                    });
+                },
+            )
+            .expect("cannot spawn an acceptor thread.")
+        })
+        .collect();
 
-    log_info!("{CRATE_NAME} shutting down.");
+    shutdown.wait();
+    log_info!("{CRATE_NAME} shutting down, finishing in-flight requests.");
+    // stopping an acceptor drops its workers, which finish every queued connection
+    acceptor::stop_acceptors(&addresses, acceptors);
+    log_info!("{CRATE_NAME} shut down.");
+}
+
+/// [`spawn_session_cleanup`] will remove expired sessions from `sessions` on a
+/// dedicated thread each [`SESSION_CLEANUP_INTERVAL`], until `shutdown` is triggered.
+fn spawn_session_cleanup(
+    sessions: &'static Sessions,
+    shutdown: Shutdown,
+) -> std::io::Result<JoinHandle<()>> {
+    std::thread::Builder::new()
+        .name("session-cleanup".to_string())
+        .spawn(move || {
+            while !shutdown.wait_timeout(SESSION_CLEANUP_INTERVAL) {
+                match sessions.remove_expired() {
+                    Ok(0) => {}
+                    Ok(removed) => log_debug!("removed {removed} expired session(s)."),
+                    Err(error) => log_warning!("can't remove expired sessions: {error}"),
+                }
+            }
+        })
 }
 
 /// [`handle_connection`] will respond to a server request by matching the request
-/// from the provided [`TcpStream`] to a [`Route`] in the provided `all_routes_vec`.
-fn handle_connection(mut stream: TcpStream, all_routes_vec: &[Route]) {
+/// from the provided [`TcpStream`] to a [`Route`](http::route::Route) in the
+/// provided [`Router`]. When `validate_requests` is `true`, the request is checked
+/// against the params of the matched [`Route`](http::route::Route) first, and any
+/// violations are returned as a `400 BAD REQUEST`.
+/// The request line and headers are read following the rules of `parsing_mode`,
+/// and requests with a body larger than `max_body_bytes`, or the body limit of the
+/// matched [`Route`](http::route::Route), get a `413 PAYLOAD TOO LARGE` without the
+/// body being read. A [`Route`](http::route::Route) that streams its body gets a
+/// [`BodyReader`] over the connection instead of the body. Every routed request has
+/// the [`ClientAddress`] it came from in its extensions, and every response from a
+/// route is passed through the response filters of the [`Router`].
+/// Requests from addresses that the access lists don't allow get a
+/// `403 FORBIDDEN` before they are routed, besides readiness probes. Requests for
+/// scanner paths are caught by the [`Honeypot`] when it is on, which bans their
+/// address.
+/// When `metrics_enabled` is `true`, `GET` requests for the [`METRICS_PATH`] answer
+/// with memory usage and the counts of denied and trapped requests.
+/// Requests slower than [`ServerConfig::slow_request_threshold`], or with a response
+/// larger than [`ServerConfig::large_response_threshold`], are logged as warnings
+/// and counted in those metrics.
+/// `GET` requests for the [`PROFILE_PATH`] run the [`Profiler`] when it is enabled,
+/// and answer with its report.
+/// Requests get the faults that [`Chaos`] injects before they are routed, such as
+/// latency, a `500 INTERNAL SERVER ERROR`, or a dropped connection.
+/// `GET` requests for the [`DOCS_PATH`] that no route serves answer with a page that
+/// documents every route.
+/// `GET` requests for static assets are served from disk before any route, and a
+/// route that requires a dependency which is down returns a
+/// `503 SERVICE UNAVAILABLE`.
+/// A [`Response::long_poll`] that is still waiting is parked in the [`LongPolls`],
+/// so the worker is free for other requests, and answered from `answer_pool` once
+/// it's ready, with a [`PARKED_WRITE_TIMEOUT`], and
+/// the body of a [`Response::stream`] is written to the connection as it's produced.
+fn handle_connection(
+    mut stream: TcpStream,
+    answer_pool: Arc<ThreadPool>,
+    validate_requests: bool,
+    parsing_mode: ParsingMode,
+    max_body_bytes: usize,
+    metrics_enabled: bool,
+    request_timeout: Option<Duration>,
+) {
     log_debug!("handling server connection.");
+    let router = get_router();
+    let started = Instant::now();
+    // the deadline of a request starts once its connection is picked up
+    let deadline = request_timeout.map(|timeout| Deadline(Instant::now() + timeout));
+    let profiler = get_profiler();
+    let _connection_frame = profiler.enter("handle_connection");
 
+    let read_head_frame = profiler.enter("read_request_head");
     let mut buf_reader = BufReader::new(&stream);
-    let mut request_line_str = String::new();
+    let request_head = match parser::read_request_head(&mut buf_reader, parsing_mode) {
+        Ok(request_head) => request_head,
+        Err(reason) => {
+            log_warning!("{reason}, returning 400 BAD REQUEST.");
+            stream
+                .write_all(Response::bad_request().to_string().as_bytes())
+                .unwrap();
+            return;
+        }
+    };
+
+    drop(read_head_frame);
+
+    // e.g., "GET", while a method no route can serve is answered right away
+    let method = match request_head.method.parse::<Method>() {
+        Ok(method) => method,
+        Err(reason) => {
+            log_warning!("{reason}, returning 501 NOT IMPLEMENTED.");
+            stream
+                .write_all(
+                    Response::with_status(Status::NotImplemented, &reason, false)
+                        .to_string()
+                        .as_bytes(),
+                )
+                .unwrap();
+            return;
+        }
+    };
+    let full_path_with_query = request_head.target.as_str(); // e.g., "/get/person/123?name=Alice"
+    let headers = request_head.headers;
+    let content_length = request_head.content_length;
+
+    // extract base path for matching (without query string)
+    let path_to_match = full_path_with_query
+        .split('?')
+        .next()
+        .unwrap_or(full_path_with_query)
+        .to_string();
 
-    // read the first line of the request (e.g., "GET /get/person/123?name=Alice HTTP/1.1")
-    if buf_reader.read_line(&mut request_line_str).is_err() || request_line_str.trim().is_empty() {
-        log_warning!("can't read request, returning 400 BAD REQUEST.");
+    // answer readiness probes before anything else
+    if method == http::methods::GET && path_to_match == READINESS_PATH {
+        let readiness = get_readiness();
+        let status = if readiness.is_ready() {
+            Status::Ok
+        } else {
+            Status::ServiceUnavailable
+        };
+        log_info!("{method} {full_path_with_query} -> {status}");
         stream
-            .write_all(Response::bad_request().to_string().as_bytes())
+            .write_all(
+                Response::with_status(status, &readiness.get_json(), true)
+                    .to_string()
+                    .as_bytes(),
+            )
             .unwrap();
         return;
     }
 
-    let request_line_str = request_line_str.trim();
-    let parts: Vec<&str> = request_line_str.splitn(3, ' ').collect();
-
-    if parts.len() != 3 {
-        log_warning!("request is malformed, returning 400 BAD REQUEST.");
+    // check the client address against the access lists before routing, where a
+    // connection that was reset before it could be checked is denied, unless the
+    // access lists only deny addresses
+    let access_control = get_access_control();
+    let client_address = stream
+        .peer_addr()
+        .map(|address| address.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    if !access_control.is_empty() && !access_control.is_allowed(client_address, &path_to_match) {
+        log_warning!(
+            "{client_address} is not allowed to {method} {full_path_with_query}, returning 403 FORBIDDEN ({} denied so far).",
+            access_control.denials()
+        );
         stream
-            .write_all(Response::bad_request().to_string().as_bytes())
+            .write_all(Response::forbidden().to_string().as_bytes())
             .unwrap();
         return;
     }
 
-    let method = parts[0].to_string(); // e.g., "GET"
-    let full_path_with_query = parts[1]; // e.g., "/get/person/123?name=Alice"
-    let _http_version = parts[2];
+    // keep scanners away from the routes, and out of their logs
+    let honeypot = get_honeypot();
+    if honeypot.is_trap(&path_to_match) {
+        log_debug!(
+            "{client_address} asked for scanner path {full_path_with_query}, banning it for {:?}.",
+            honeypot.ban_duration
+        );
+        access_control.ban(client_address, honeypot.ban_duration);
+        if let Err(error) = honeypot.trap(stream) {
+            log_warning!("can't spawn a honeypot thread: {error}");
+        }
+        return;
+    }
 
-    // extract base path for matching (without query string)
-    let path_to_match = full_path_with_query
-        .split('?')
-        .next()
-        .unwrap_or(full_path_with_query)
-        .to_string();
+    // answer with metrics from their admin path
+    if metrics_enabled && method == http::methods::GET && path_to_match == METRICS_PATH {
+        let arena_stats = arena::stats();
+        let metrics = Metrics::new()
+            .with_memory()
+            .with_counter(
+                "access_denials_total",
+                "Requests denied by the access lists.",
+                access_control.denials(),
+            )
+            .with_counter(
+                "honeypot_caught_total",
+                "Requests for scanner paths caught by the honeypot.",
+                honeypot.caught(),
+            )
+            .with_counter(
+                "chaos_faults_injected_total",
+                "Faults injected into requests on purpose.",
+                get_chaos().injected(),
+            )
+            .with_counter(
+                "slow_requests_total",
+                "Requests that took longer than the slow request threshold.",
+                get_response_warnings().slow_requests(),
+            )
+            .with_counter(
+                "large_responses_total",
+                "Responses larger than the large response threshold.",
+                get_response_warnings().large_responses(),
+            )
+            .with_counter(
+                "arena_requests_total",
+                "Request heads parsed in a worker's arena.",
+                arena_stats.requests,
+            )
+            .with_counter(
+                "arena_bytes_allocated_total",
+                "Bytes allocated in workers' arenas while parsing request heads.",
+                arena_stats.bytes_allocated,
+            )
+            .with_gauge(
+                "arena_peak_request_bytes",
+                "The most bytes a single request head allocated in an arena.",
+                arena_stats.peak_request_bytes,
+            )
+            .with_counter(
+                "arena_growths_total",
+                "Times a worker's arena had to grow past its capacity.",
+                arena_stats.growths,
+            );
+        log_info!("{method} {full_path_with_query} -> {}", Status::Ok);
+        stream
+            .write_all(
+                Response::with_status(Status::Ok, &metrics.to_string(), true)
+                    .with_header("Content-Type", "text/plain; version=0.0.4; charset=utf-8")
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        return;
+    }
 
-    // read headers and body
-    let mut headers = HashMap::new();
-    let mut content_length: usize = 0;
-    let mut current_line = String::new();
-    while buf_reader.read_line(&mut current_line).is_ok() && current_line.trim() != "" {
-        if let Some((key, value)) = current_line.split_once(':') {
-            headers.insert(key.trim().to_lowercase(), value.trim().to_string());
-            if key.trim().to_lowercase() == "content-length" {
-                content_length = value.trim().parse().unwrap_or(0);
+    // run the profiler from its admin path, holding this worker while it samples
+    if profiler.enabled && method == http::methods::GET && path_to_match == PROFILE_PATH {
+        let query = full_path_with_query
+            .split_once('?')
+            .map_or("", |(_, query)| query);
+        let response = match Profiler::duration_from_query(query)
+            .map(|duration| profiler.profile(duration, DEFAULT_SAMPLE_INTERVAL))
+        {
+            Ok(Ok(report)) => Response::with_status(Status::Ok, &report, true)
+                .with_header("Content-Type", "text/plain; charset=utf-8"),
+            Ok(Err(reason)) => {
+                log_warning!("can't run the profiler: {reason}.");
+                Response::with_status(Status::ServiceUnavailable, &reason, false)
             }
+            Err(reason) => {
+                log_warning!("can't run the profiler: {reason}.");
+                Response::with_status(Status::BadRequest, &reason, false)
+            }
+        };
+        log_info!("{method} {full_path_with_query} -> {}", response.status);
+        stream.write_all(response.to_string().as_bytes()).unwrap();
+        return;
+    }
+
+    // serve static assets straight from disk, without going through the routes
+    if method == http::methods::GET
+        && let Some(asset) = get_static_files().resolve(&path_to_match)
+    {
+        if let Err(error) = asset.send(
+            &mut stream,
+            headers.get(&IF_MODIFIED_SINCE).map(|v| v.as_str()),
+        ) {
+            log_warning!("can't send static asset '{full_path_with_query}': {error}");
         }
-        current_line.clear();
+        log_info!("{method} {full_path_with_query} -> static asset");
+        return;
     }
 
-    let mut body_bytes = vec![0; content_length];
-    let body_content = if content_length > 0 && buf_reader.read_exact(&mut body_bytes).is_ok() {
-        Some(String::from_utf8_lossy(&body_bytes).to_string())
-    } else {
+    // every request names its tenant when one deployment serves many customers,
+    // where a tenant in the path prefix is left out of the path that is routed
+    let (tenant, path_to_match, full_path_with_query) = match get_tenant_resolver() {
+        Some(resolver) => match resolver.resolve(&headers, &path_to_match) {
+            Some((tenant, tenant_path)) => {
+                let full_path = match full_path_with_query.split_once('?') {
+                    Some((_, query)) => format!("{tenant_path}?{query}"),
+                    None => tenant_path.clone(),
+                };
+                (Some(tenant), tenant_path, full_path)
+            }
+            None => {
+                log_warning!(
+                    "{method} {full_path_with_query} has no tenant, returning 404 NOT FOUND."
+                );
+                stream
+                    .write_all(Response::not_found().to_string().as_bytes())
+                    .unwrap();
+                return;
+            }
+        },
+        None => (None, path_to_match, full_path_with_query.to_string()),
+    };
+    let full_path_with_query = full_path_with_query.as_str();
+
+    // the groups of the access lists name the paths the routes see, so a path
+    // without its tenant prefix is checked again
+    if tenant.is_some()
+        && !access_control.is_empty()
+        && !access_control.is_allowed(client_address, &path_to_match)
+    {
+        log_warning!(
+            "{client_address} is not allowed to {method} {full_path_with_query} for its tenant, returning 403 FORBIDDEN ({} denied so far).",
+            access_control.denials()
+        );
+        stream
+            .write_all(Response::forbidden().to_string().as_bytes())
+            .unwrap();
+        return;
+    }
+
+    // a route can replace the body limit of the server, and leave its body on the
+    // connection to read it within bounded memory
+    let resolved = router.resolve(method, &path_to_match);
+    let body_limit = resolved
+        .as_ref()
+        .and_then(|(route, _)| route.spec.body_limit)
+        .unwrap_or(max_body_bytes);
+    if content_length > body_limit {
+        log_warning!(
+            "request body of {content_length} bytes is over the {body_limit} byte limit, returning 413 PAYLOAD TOO LARGE."
+        );
+        stream
+            .write_all(Response::payload_too_large().to_string().as_bytes())
+            .unwrap();
+        return;
+    }
+
+    // read body
+    let read_body_frame = profiler.enter("read_request_body");
+    let mut body_reader = None;
+    let mut multipart = None;
+    let body_content = if resolved
+        .as_ref()
+        .is_some_and(|(route, _)| route.spec.streams_body)
+    {
+        // the buffered reader may have read the start of the body with the headers
+        match stream.try_clone() {
+            Ok(connection) => {
+                let buffered = buf_reader.buffer().to_vec();
+                body_reader = Some(BodyReader::new(
+                    Box::new(Cursor::new(buffered).chain(connection)),
+                    content_length,
+                ));
+            }
+            Err(error) => log_warning!("can't stream the request body: {error}"),
+        }
         None
+    } else {
+        let mut body_bytes = vec![0; content_length];
+        let content_type = headers.get(&CONTENT_TYPE).map(String::as_str);
+        if content_length > 0
+            && multipart::is_multipart(content_type)
+            && buf_reader.read_exact(&mut body_bytes).is_ok()
+        {
+            // uploaded files are kept as bytes, since they are rarely text
+            match Multipart::parse(&body_bytes, content_type.unwrap_or_default()) {
+                Ok(parsed) => multipart = Some(parsed),
+                Err(error) => {
+                    log_warning!(
+                        "can't parse the multipart body of {method} {full_path_with_query}: {error}."
+                    );
+                    stream
+                        .write_all(Response::bad_request().to_string().as_bytes())
+                        .unwrap();
+                    return;
+                }
+            }
+            None
+        } else if content_length > 0 && buf_reader.read_exact(&mut body_bytes).is_ok() {
+            // bodies are decoded by the charset they name, rather than guessed at
+            let decoded = match Charset::from_content_type(content_type) {
+                Ok(charset) => charset
+                    .decode(&body_bytes)
+                    .map_err(|error| (Response::bad_request(), error)),
+                Err(error) => Err((Response::unsupported_media_type(), error)),
+            };
+            match decoded {
+                Ok(body) => Some(body),
+                Err((response, error)) => {
+                    log_warning!(
+                        "can't decode the body of {method} {full_path_with_query}: {error}."
+                    );
+                    stream.write_all(response.to_string().as_bytes()).unwrap();
+                    return;
+                }
+            }
+        } else {
+            None
+        }
     };
 
+    drop(read_body_frame);
+
+    // inject faults on purpose, to test how clients handle them
+    for fault in get_chaos().faults(&path_to_match) {
+        log_warning!("chaos: injecting {fault:?} into {method} {full_path_with_query}.");
+        match fault {
+            Fault::Latency(delay) => std::thread::sleep(delay),
+            Fault::ServerError => {
+                stream
+                    .write_all(Response::server_error().to_string().as_bytes())
+                    .unwrap();
+                return;
+            }
+            Fault::DropConnection => {
+                let _ = stream.shutdown(std::net::Shutdown::Both);
+                return;
+            }
+        }
+    }
+
+    // warnings name the route that served a request, rather than its full path
+    let route_name = resolved
+        .as_ref()
+        .map(|(route, _)| format!("{} {}", route.spec.method, route.spec.request_pattern))
+        .unwrap_or_else(|| format!("{method} {path_to_match}"));
+
     // iterate through ALL registered routes to find a match
-    let matched_response = all_routes_vec
-        .iter()
-        .filter(|&route| route.method == method)
-        .filter(|&route| route.matches_path(&path_to_match).is_some())
-        .take(1)
-        .next()
-        .map(|route| {
-            let incoming_request = Request {
+    let matched_response = resolved
+        .map(|(route, path_params)| {
+            // a client whose session can't be loaded gets a new one for this request
+            let session = get_sessions()
+                .load_for_tenant(headers.get(&COOKIE).map(String::as_str), tenant.as_ref())
+                .unwrap_or_else(|error| {
+                    log_warning!("{error}, starting a new session.");
+                    Session::new()
+                });
+            let mut incoming_request = Request {
                 path: full_path_with_query.to_string(),
-                method: method.clone(),
+                method,
                 body_content: body_content.clone(),
-                path_params: route.matches_path(&path_to_match).unwrap(),
+                path_params,
+                headers: headers.clone(),
+                body_reader: body_reader.take(),
+                extensions: TypeMap::new(),
+            }
+            .with_extension(ClientAddress(client_address))
+            .with_extension(SharedState(router.state.clone()))
+            .with_extension(session.clone());
+            if let Some(deadline) = deadline {
+                incoming_request = incoming_request.with_extension(deadline);
+            }
+            if let Some(tenant) = &tenant {
+                incoming_request = incoming_request.with_extension(tenant.clone());
+            }
+            if let Some(multipart) = &multipart {
+                incoming_request = incoming_request.with_extension(multipart.clone());
+            }
+            let response = serve_route(
+                router,
+                route,
+                incoming_request,
+                &path_to_match,
+                validate_requests,
+            );
+            get_sessions()
+                .persist_for_tenant(&session, tenant.as_ref(), response)
+                .unwrap_or_else(|error| {
+                    log_warning!("{error}, returning 500 INTERNAL SERVER ERROR.");
+                    Response::server_error()
+                })
+        })
+        .or_else(|| {
+            // document every route when no route serves the docs path itself
+            if method != http::methods::GET || path_to_match != DOCS_PATH {
+                return None;
+            }
+            let base_url = format!(
+                "http://{}",
+                headers
+                    .get(&HOST)
+                    .map(|host| host.as_str().to_string())
+                    .unwrap_or_else(|| ServerConfig::new().get_server_address())
+            );
+            Some(
+                Response::with_status(
+                    Status::Ok,
+                    &docs::render_docs(&router.specs(), &base_url),
+                    true,
+                )
+                .with_header("Content-Type", "text/html; charset=utf-8"),
+            )
+        })
+        .or_else(|| {
+            // route every sub-request of a batch when no route serves the batch path
+            // itself, where they share the headers and session of the batch, and go
+            // through the same checks and audit trail as a request of their own
+            if method != http::methods::POST || path_to_match != BATCH_PATH {
+                return None;
+            }
+            let batch = get_batch()?;
+            let session = get_sessions()
+                .load_for_tenant(headers.get(&COOKIE).map(String::as_str), tenant.as_ref())
+                .unwrap_or_else(|error| {
+                    log_warning!("{error}, starting a new session.");
+                    Session::new()
+                });
+            let dispatch = |sub_request: &BatchRequest| {
+                let sub_path = sub_request
+                    .path
+                    .split('?')
+                    .next()
+                    .unwrap_or(&sub_request.path);
+                let body_length = sub_request.body.as_ref().map_or(0, String::len);
+                let response = if !access_control.is_empty()
+                    && !access_control.is_allowed(client_address, sub_path)
+                {
+                    log_warning!(
+                        "{client_address} is not allowed to {} {} in a batch, returning 403 FORBIDDEN.",
+                        sub_request.method,
+                        sub_request.path
+                    );
+                    Response::forbidden()
+                } else {
+                    match router.resolve(sub_request.method, sub_path) {
+                        None => Response::not_found(),
+                        Some((route, _))
+                            if body_length > route.spec.body_limit.unwrap_or(max_body_bytes) =>
+                        {
+                            log_warning!(
+                                "batch request body of {body_length} bytes is over the body limit, returning 413 PAYLOAD TOO LARGE."
+                            );
+                            Response::payload_too_large()
+                        }
+                        Some((route, path_params)) => {
+                            // a route that streams its body reads the sub-request body
+                            // the same way it would read it from the connection
+                            let (body_content, body_reader) = match &sub_request.body {
+                                Some(body) if route.spec.streams_body => (
+                                    None,
+                                    Some(BodyReader::new(
+                                        Box::new(Cursor::new(body.clone().into_bytes())),
+                                        body_length,
+                                    )),
+                                ),
+                                body => (body.clone(), None),
+                            };
+                            let mut incoming_request = Request {
+                                path: sub_request.path.clone(),
+                                method: sub_request.method,
+                                body_content,
+                                path_params,
+                                headers: headers.clone(),
+                                body_reader,
+                                extensions: TypeMap::new(),
+                            }
+                            .with_extension(ClientAddress(client_address))
+                            .with_extension(SharedState(router.state.clone()))
+                            .with_extension(session.clone());
+                            if let Some(deadline) = deadline {
+                                incoming_request = incoming_request.with_extension(deadline);
+                            }
+                            if let Some(tenant) = &tenant {
+                                incoming_request = incoming_request.with_extension(tenant.clone());
+                            }
+                            // a batch is answered all at once, so a long poll in a
+                            // batch holds this worker until it is answered, and a
+                            // streamed body is collected whole
+                            serve_route(
+                                router,
+                                route,
+                                incoming_request,
+                                sub_path,
+                                validate_requests,
+                            )
+                            .wait()
+                            .buffered()
+                        }
+                    }
+                };
+                if get_audit_log().audits(sub_request.method) {
+                    audit(
+                        Request {
+                            path: sub_request.path.clone(),
+                            method: sub_request.method,
+                            body_content: sub_request.body.clone(),
+                            path_params: Default::default(),
+                            headers: headers.clone(),
+                            body_reader: None,
+                            extensions: TypeMap::new(),
+                        },
+                        client_address,
+                        tenant.as_ref(),
+                        &response,
+                    );
+                }
+                response
             };
-            route.get_response(incoming_request)
+            let response = batch.handle(body_content.as_deref().unwrap_or_default(), dispatch);
+            Some(
+                get_sessions()
+                    .persist_for_tenant(&session, tenant.as_ref(), response)
+                    .unwrap_or_else(|error| {
+                        log_warning!("{error}, returning 500 INTERNAL SERVER ERROR.");
+                        Response::server_error()
+                    }),
+            )
+        })
+        .or_else(|| {
+            // answer OPTIONS automatically when no route handles it explicitly
+            if method != http::methods::OPTIONS {
+                return None;
+            }
+            let allowed_methods = router.allowed_methods(&path_to_match);
+            (!allowed_methods.is_empty()).then(|| Response::options(&allowed_methods))
+        })
+        .or_else(|| {
+            // a fallback answers unmatched paths under its prefix instead of a 404, and
+            // gets the tenant, session, and path without its tenant prefix as a route
+            // would
+            let fallback = router.resolve_fallback(&path_to_match)?;
+            log_warning!("request did not match any existing routes, answering with a fallback");
+            let session = get_sessions()
+                .load_for_tenant(headers.get(&COOKIE).map(String::as_str), tenant.as_ref())
+                .unwrap_or_else(|error| {
+                    log_warning!("{error}, starting a new session.");
+                    Session::new()
+                });
+            let mut incoming_request = Request {
+                path: full_path_with_query.to_string(),
+                method,
+                body_content: body_content.clone(),
+                path_params: Default::default(),
+                headers: headers.clone(),
+                body_reader: None,
+                extensions: TypeMap::new(),
+            }
+            .with_extension(ClientAddress(client_address))
+            .with_extension(SharedState(router.state.clone()))
+            .with_extension(session.clone());
+            if let Some(deadline) = deadline {
+                incoming_request = incoming_request.with_extension(deadline);
+            }
+            if let Some(tenant) = &tenant {
+                incoming_request = incoming_request.with_extension(tenant.clone());
+            }
+            Some(
+                get_sessions()
+                    .persist_for_tenant(&session, tenant.as_ref(), fallback(incoming_request))
+                    .unwrap_or_else(|error| {
+                        log_warning!("{error}, returning 500 INTERNAL SERVER ERROR.");
+                        Response::server_error()
+                    }),
+            )
         });
 
     if matched_response.is_none() {
@@ -137,15 +1158,140 @@ fn handle_connection(mut stream: TcpStream, all_routes_vec: &[Route]) {
         return;
     }
 
-    // log the routing result and send it back to the stream
+    // log the routing result and send it back to the stream, where a HEAD
+    // response never has a body
     let final_response = matched_response.unwrap();
-    log_info!(
-        "{} {} -> {}",
-        method,
-        parts[1].to_string(),
-        final_response.status
-    );
-    stream
-        .write_all(final_response.to_string().as_bytes())
-        .unwrap();
+    let final_response = if method == http::methods::HEAD {
+        final_response.without_body()
+    } else {
+        final_response
+    };
+    let full_path_with_query = full_path_with_query.to_string();
+    // a parked answer is written from a worker, which a client that stops reading
+    // can only hold until the write times out
+    let is_pending = final_response.is_pending();
+    if is_pending && let Err(error) = stream.set_write_timeout(Some(PARKED_WRITE_TIMEOUT)) {
+        log_debug!(
+            "can't limit how long the answer to {method} {full_path_with_query} takes to write: {error}"
+        );
+    }
+    let answer = move |final_response: Response| {
+        log_info!(
+            "{} {} -> {}",
+            method,
+            full_path_with_query,
+            final_response.status
+        );
+        // the audit trail names who changed what once it is known how it was answered
+        if get_audit_log().audits(method) {
+            audit(
+                Request {
+                    path: full_path_with_query.clone(),
+                    method,
+                    body_content,
+                    path_params: Default::default(),
+                    headers: headers.clone(),
+                    body_reader: None,
+                    extensions: TypeMap::new(),
+                },
+                client_address,
+                tenant.as_ref(),
+                &final_response,
+            );
+        }
+        let _write_frame = get_profiler().enter("write_response");
+        let encoding = get_compression_policy().apply(
+            compression::negotiate(headers.get(&ACCEPT_ENCODING).map(|v| v.as_str())),
+            &path_to_match,
+            final_response.get_header(constants::CONTENT_TYPE),
+            final_response.content.len(),
+        );
+        let response_bytes = final_response.to_bytes(&encoding);
+        get_response_warnings().observe(&route_name, started.elapsed(), response_bytes.len());
+        // a long-polling client can give up before it is answered, and a streamed
+        // body is written once its status line and headers are sent
+        if let Err(error) = stream
+            .write_all(&response_bytes)
+            .and_then(|_| final_response.write_body_stream(&mut stream))
+        {
+            log_debug!("can't send the response to {method} {full_path_with_query}: {error}");
+        }
+    };
+    // a long poll that is still waiting is answered by the long poll thread, so this
+    // worker can serve other requests in the meantime
+    if is_pending {
+        get_long_polls().park(final_response, Box::new(answer), answer_pool);
+    } else {
+        answer(final_response);
+    }
+}
+
+/// [`serve_route`] will answer `request` with the matched `route`, once the request
+/// passed every check a route makes: its dependencies have to be available, it has
+/// to have the params of the route when `validate_requests` is `true`, and it has to
+/// match the current ETag of the route. Every top-level request and every
+/// sub-request of a batch goes through it, so a batch can't skip any of them.
+fn serve_route(
+    router: &Router,
+    route: &Route,
+    request: Request,
+    path_to_match: &str,
+    validate_requests: bool,
+) -> Response {
+    let unavailable = get_readiness().unavailable(&route.spec.requires);
+    if !unavailable.is_empty() {
+        log_warning!(
+            "required dependencies are down ({}), returning 503 SERVICE UNAVAILABLE.",
+            unavailable.join(", ")
+        );
+        return Response::service_unavailable().with_header(
+            "Retry-After",
+            &get_readiness().interval().as_secs().max(1).to_string(),
+        );
+    }
+    if validate_requests {
+        let violations = route.spec.validate(&request);
+        if !violations.is_empty() {
+            log_warning!("request failed validation, returning 400 BAD REQUEST.");
+            return Response::with_status(Status::BadRequest, &violations.get_json(), false);
+        }
+    }
+    if !route.check_precondition(&request) {
+        log_warning!("request did not match the current ETag, returning 412 PRECONDITION FAILED.");
+        return Response::precondition_failed();
+    }
+    let _route_frame = get_profiler().enter(format_args!(
+        "{} {}",
+        route.spec.method, route.spec.request_pattern
+    ));
+    // every upstream call the handler makes carries the request id and trace
+    // headers of the request
+    let propagated_headers: Vec<&str> = get_propagated_headers()
+        .iter()
+        .map(String::as_str)
+        .collect();
+    let correlation = Correlation::from_request(&request, &propagated_headers).enter();
+    let response = router.respond(route, request);
+    drop(correlation);
+    // an OPTIONS handler lists the allowed methods unless it chose them itself
+    if route.spec.method == http::methods::OPTIONS && response.get_header(ALLOW).is_none() {
+        response.with_header(
+            ALLOW,
+            &methods::join(&router.allowed_methods(path_to_match)),
+        )
+    } else {
+        response
+    }
+}
+
+/// [`audit`] will record `request`, made from `client_address` for `tenant`, in the
+/// [`AuditLog`] once it is known it was answered with `response`, where callers
+/// check [`AuditLog::audits`] first so nothing is copied for a request that isn't
+/// audited.
+fn audit(request: Request, client_address: IpAddr, tenant: Option<&TenantId>, response: &Response) {
+    let mut request = request.with_extension(ClientAddress(client_address));
+    if let Some(tenant) = tenant {
+        request = request.with_extension(tenant.clone());
+    }
+    get_audit_log().record(&request, response.status.clone());
 }
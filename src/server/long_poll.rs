@@ -0,0 +1,203 @@
+use crate::server::{shutdown::Shutdown, thread_pool::ThreadPool};
+use http::response::{LONG_POLL_INTERVAL, Response};
+use logger::log_warning;
+use std::{
+    io, mem,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// [`PARKED_WRITE_TIMEOUT`] is how long the answer of a parked request can take to
+/// write, so a client that stops reading gives up its worker.
+pub const PARKED_WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// [`AnswerFn`] is the dynamic function that sends the answer of a parked
+/// [`Response`] back to the client that is waiting on it.
+pub type AnswerFn = Box<dyn FnOnce(Response) + Send + 'static>;
+
+/// [`ParkedPoll`] is a pending [`Response::long_poll`], with the [`AnswerFn`] that
+/// sends its answer once it has one, and the [`ThreadPool`] it's sent from.
+struct ParkedPoll {
+    /// [`ParkedPoll::response`] is the pending [`Response`].
+    response: Response,
+    /// [`ParkedPoll::answer`] is the [`AnswerFn`] the answer is sent with.
+    answer: AnswerFn,
+    /// [`ParkedPoll::pool`] is the [`ThreadPool`] that runs [`ParkedPoll::answer`],
+    /// so a slow client only holds one of its workers.
+    pool: Arc<ThreadPool>,
+}
+
+impl ParkedPoll {
+    /// [`ParkedPoll::send`] will send `answer` from [`ParkedPoll::pool`].
+    fn send(self, answer: Response) {
+        let send_answer = self.answer;
+        self.pool.execute(move || send_answer(answer));
+    }
+
+    /// [`ParkedPoll::answer_without_content`] will answer a [`ParkedPoll`] with its
+    /// pending [`Response`], as if it timed out.
+    fn answer_without_content(mut self) {
+        let mut response = mem::replace(&mut self.response, Response::no_content());
+        response.pending = None;
+        self.send(response);
+    }
+}
+
+/// [`LongPolls`] keeps every pending [`Response::long_poll`] off the workers, and
+/// checks them all from a single thread, so a waiting client never holds a worker.
+/// Only the checks run on that thread, every answer is sent from a worker.
+#[derive(Default)]
+pub struct LongPolls {
+    /// [`LongPolls::parked`] is every [`ParkedPoll`] that is still waiting, which
+    /// is [`None`] while there is no thread checking them.
+    parked: Mutex<Option<Vec<ParkedPoll>>>,
+}
+
+impl LongPolls {
+    /// [`LongPolls::new`] creates a [`LongPolls`] without any parked requests,
+    /// which answers them right away until [`LongPolls::spawn_polls`] is called.
+    /// # Example
+    /// ```rust
+    /// use minimal_api::server::long_poll::LongPolls;
+    ///
+    /// fn create_long_polls() -> LongPolls {
+    ///     LongPolls::new()
+    /// }
+    /// ```
+    pub fn new() -> Self {
+        LongPolls::default()
+    }
+
+    /// [`LongPolls::park`] will keep a pending `response` until it's answered, and
+    /// send the answer with `answer` from a worker of `pool`. Without a thread
+    /// checking them, `response` is waited on and answered right away instead.
+    /// # Example
+    /// [`LongPolls::park`] can be used to free a worker from a long poll:
+    /// ```rust
+    /// use http::response::Response;
+    /// use minimal_api::server::{long_poll::LongPolls, thread_pool::ThreadPool};
+    /// use std::{io::Write, net::TcpStream, sync::Arc};
+    ///
+    /// fn park(
+    ///     long_polls: &LongPolls,
+    ///     response: Response,
+    ///     mut stream: TcpStream,
+    ///     pool: Arc<ThreadPool>,
+    /// ) {
+    ///     long_polls.park(
+    ///         response,
+    ///         Box::new(move |answer| {
+    ///             let _ = stream.write_all(answer.to_string().as_bytes());
+    ///         }),
+    ///         pool,
+    ///     );
+    /// }
+    /// ```
+    pub fn park(&self, response: Response, answer: AnswerFn, pool: Arc<ThreadPool>) {
+        let mut parked = self.parked.lock().unwrap();
+        match parked.as_mut() {
+            Some(parked) => parked.push(ParkedPoll {
+                response,
+                answer,
+                pool,
+            }),
+            None => {
+                drop(parked);
+                answer(response.wait());
+            }
+        }
+    }
+
+    /// [`LongPolls::len`] will return how many parked requests are still waiting.
+    pub fn len(&self) -> usize {
+        self.parked.lock().unwrap().as_ref().map_or(0, Vec::len)
+    }
+
+    /// [`LongPolls::is_empty`] will return `true` if no parked request is waiting,
+    /// and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// [`LongPolls::poll_parked`] will check every parked request once, hand the
+    /// answer of each one that has one to its pool, and return how many were
+    /// answered. A request whose [`PollFn`](http::response::PollFn) panics is
+    /// answered with a `500 INTERNAL SERVER ERROR`, so it can't stop the others from
+    /// being checked.
+    pub fn poll_parked(&self) -> usize {
+        // nothing is checked while holding the lock, so workers can keep parking
+        let parked = match self.parked.lock().unwrap().as_mut() {
+            Some(parked) => mem::take(parked),
+            None => return 0,
+        };
+
+        let mut waiting = Vec::new();
+        let mut answered = 0;
+        for parked_poll in parked {
+            let answer = match panic::catch_unwind(AssertUnwindSafe(|| parked_poll.response.poll()))
+            {
+                Ok(Some(answer)) => answer,
+                Ok(None) => {
+                    waiting.push(parked_poll);
+                    continue;
+                }
+                Err(_) => {
+                    log_warning!("a long poll panicked, returning 500 INTERNAL SERVER ERROR.");
+                    Response::server_error()
+                }
+            };
+            parked_poll.send(answer);
+            answered += 1;
+        }
+
+        let mut parked = self.parked.lock().unwrap();
+        match parked.as_mut() {
+            Some(parked) => parked.extend(waiting),
+            None => {
+                drop(parked);
+                waiting
+                    .into_iter()
+                    .for_each(ParkedPoll::answer_without_content);
+            }
+        }
+        answered
+    }
+
+    /// [`LongPolls::spawn_polls`] will check every parked request on a dedicated
+    /// thread each [`LONG_POLL_INTERVAL`], until `shutdown` is triggered, when every
+    /// request that is still waiting is answered with
+    /// [`Status::NoContent`](http::status::Status::NoContent).
+    /// # Example
+    /// [`LongPolls::spawn_polls`] can be used to answer long polls while the server
+    /// runs:
+    /// ```rust
+    /// use minimal_api::server::{long_poll::LongPolls, shutdown::Shutdown};
+    ///
+    /// fn answer_long_polls(long_polls: &'static LongPolls, shutdown: Shutdown) -> std::io::Result<()> {
+    ///     long_polls.spawn_polls(shutdown).map(|_| ())
+    /// }
+    /// ```
+    pub fn spawn_polls(&'static self, shutdown: Shutdown) -> io::Result<JoinHandle<()>> {
+        *self.parked.lock().unwrap() = Some(Vec::new());
+        thread::Builder::new()
+            .name("long-poll".to_string())
+            .spawn(move || {
+                while !shutdown.wait_timeout(LONG_POLL_INTERVAL) {
+                    self.poll_parked();
+                }
+                self.answer_waiting();
+            })
+            .inspect_err(|_| *self.parked.lock().unwrap() = None)
+    }
+
+    /// [`LongPolls::answer_waiting`] will stop parking requests, and answer every
+    /// request that is still waiting without its content.
+    fn answer_waiting(&self) {
+        let parked = self.parked.lock().unwrap().take().unwrap_or_default();
+        parked
+            .into_iter()
+            .for_each(ParkedPoll::answer_without_content);
+    }
+}
@@ -0,0 +1,318 @@
+use logger::log_warning;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// [`METRICS_PATH`] is the admin path that answers with [`Metrics`] in the
+/// Prometheus text format.
+pub const METRICS_PATH: &str = "/metrics";
+
+/// [`ALLOCATIONS`] is how many allocations [`CountingAllocator`] has made.
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+/// [`DEALLOCATIONS`] is how many allocations [`CountingAllocator`] has freed.
+static DEALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+/// [`ALLOCATED_BYTES`] is how many bytes [`CountingAllocator`] has allocated.
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+/// [`FREED_BYTES`] is how many bytes [`CountingAllocator`] has freed.
+static FREED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// [`CountingAllocator`] is a global allocator that counts every allocation before
+/// handing it to the [`System`] allocator, so memory that is never freed, such as
+/// from a response cache or session store that keeps growing, shows up in
+/// [`AllocationStats`]. It is installed by the server binary with the
+/// `alloc-metrics` cargo feature.
+/// # Example
+/// [`CountingAllocator`] can be installed by any binary:
+/// ```rust
+/// use minimal_api::server::metrics::CountingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+#[derive(Debug)]
+pub struct CountingAllocator;
+
+/// Implement [`GlobalAlloc`] for [`CountingAllocator`].
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::alloc`
+        let pointer = unsafe { System.alloc(layout) };
+        if !pointer.is_null() {
+            count_allocation(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::alloc_zeroed`
+        let pointer = unsafe { System.alloc_zeroed(layout) };
+        if !pointer.is_null() {
+            count_allocation(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::dealloc`
+        unsafe { System.dealloc(pointer, layout) };
+        count_deallocation(layout.size());
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::realloc`
+        let new_pointer = unsafe { System.realloc(pointer, layout, new_size) };
+        if !new_pointer.is_null() {
+            count_deallocation(layout.size());
+            count_allocation(new_size);
+        }
+        new_pointer
+    }
+}
+
+/// [`count_allocation`] will count an allocation of `size` bytes.
+fn count_allocation(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+/// [`count_deallocation`] will count a deallocation of `size` bytes.
+fn count_deallocation(size: usize) {
+    DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    FREED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+/// [`AllocationStats`] are the counters of [`CountingAllocator`], which are all `0`
+/// when it isn't installed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocationStats {
+    /// [`AllocationStats::allocations`] is how many allocations were made.
+    pub allocations: u64,
+    /// [`AllocationStats::deallocations`] is how many allocations were freed.
+    pub deallocations: u64,
+    /// [`AllocationStats::allocated_bytes`] is how many bytes were allocated.
+    pub allocated_bytes: u64,
+    /// [`AllocationStats::freed_bytes`] is how many bytes were freed.
+    pub freed_bytes: u64,
+}
+
+impl AllocationStats {
+    /// [`AllocationStats::current`] will get the counters of [`CountingAllocator`]
+    /// right now.
+    pub fn current() -> Self {
+        AllocationStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            deallocations: DEALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+            freed_bytes: FREED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// [`AllocationStats::is_counting`] will return `true` if [`CountingAllocator`]
+    /// is installed, and has counted anything.
+    pub fn is_counting(&self) -> bool {
+        self.allocations > 0
+    }
+
+    /// [`AllocationStats::in_use_bytes`] will get how many allocated bytes were not
+    /// freed yet.
+    pub fn in_use_bytes(&self) -> u64 {
+        self.allocated_bytes.saturating_sub(self.freed_bytes)
+    }
+}
+
+/// [`resident_memory_bytes`] will get the resident set size of the server, which is
+/// how much of its memory is in RAM, or [`None`] on platforms other than Linux.
+pub fn resident_memory_bytes() -> Option<u64> {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|kilobytes| kilobytes.trim().trim_end_matches("kB").trim().parse().ok())
+        .map(|kilobytes: u64| kilobytes * 1024)
+}
+
+/// [`ResponseWarnings`] logs a warning for every request that takes longer than its
+/// slow request threshold, or whose response is larger than its large response
+/// threshold, and counts them for [`Metrics`], so regressions show up before anyone
+/// complains about them.
+#[derive(Debug, Default)]
+pub struct ResponseWarnings {
+    /// [`ResponseWarnings::slow_request_threshold`] is how long a request can take
+    /// before it is slow, or [`None`] to never warn about slow requests.
+    pub slow_request_threshold: Option<Duration>,
+    /// [`ResponseWarnings::large_response_threshold`] is how many bytes a response
+    /// can have before it is large, or [`None`] to never warn about large
+    /// responses.
+    pub large_response_threshold: Option<usize>,
+    /// [`ResponseWarnings::slow_requests`] is how many slow requests were seen.
+    slow_requests: AtomicU64,
+    /// [`ResponseWarnings::large_responses`] is how many large responses were seen.
+    large_responses: AtomicU64,
+}
+
+impl ResponseWarnings {
+    /// [`ResponseWarnings::new`] creates [`ResponseWarnings`] that warn about
+    /// requests slower than `slow_request_threshold` and responses larger than
+    /// `large_response_threshold`.
+    pub fn new(
+        slow_request_threshold: Option<Duration>,
+        large_response_threshold: Option<usize>,
+    ) -> Self {
+        ResponseWarnings {
+            slow_request_threshold,
+            large_response_threshold,
+            ..Default::default()
+        }
+    }
+
+    /// [`ResponseWarnings::observe`] will log a warning with `route`, `elapsed`, and
+    /// `response_bytes` when the request was slow or its response was large, and
+    /// count it.
+    /// # Example
+    /// [`ResponseWarnings::observe`] can be used to watch how long a handler takes:
+    /// ```rust
+    /// use minimal_api::server::metrics::ResponseWarnings;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// fn get_report(warnings: &ResponseWarnings) -> String {
+    ///     let started = Instant::now();
+    ///     let report = "\"report\"".to_string();
+    ///     warnings.observe("GET /report", started.elapsed(), report.len());
+    ///     report
+    /// }
+    /// ```
+    pub fn observe(&self, route: &str, elapsed: Duration, response_bytes: usize) {
+        if let Some(threshold) = self.slow_request_threshold
+            && elapsed > threshold
+        {
+            self.slow_requests.fetch_add(1, Ordering::Relaxed);
+            log_warning!(
+                "slow request: {route} took {elapsed:?}, over the {threshold:?} threshold, with a {response_bytes} byte response."
+            );
+        }
+        if let Some(threshold) = self.large_response_threshold
+            && response_bytes > threshold
+        {
+            self.large_responses.fetch_add(1, Ordering::Relaxed);
+            log_warning!(
+                "large response: {route} answered with {response_bytes} bytes, over the {threshold} byte threshold, after {elapsed:?}."
+            );
+        }
+    }
+
+    /// [`ResponseWarnings::slow_requests`] will get how many slow requests were
+    /// seen.
+    pub fn slow_requests(&self) -> u64 {
+        self.slow_requests.load(Ordering::Relaxed)
+    }
+
+    /// [`ResponseWarnings::large_responses`] will get how many large responses were
+    /// seen.
+    pub fn large_responses(&self) -> u64 {
+        self.large_responses.load(Ordering::Relaxed)
+    }
+}
+
+/// [`Metrics`] is a set of counters and gauges, written in the Prometheus text
+/// format when displayed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metrics {
+    /// [`Metrics::samples`] are the name, help text, type, and value of every
+    /// metric, in the order they were added.
+    samples: Vec<(String, String, &'static str, u64)>,
+}
+
+impl Metrics {
+    /// [`Metrics::new`] creates an empty [`Metrics`].
+    pub fn new() -> Self {
+        Metrics::default()
+    }
+
+    /// [`Metrics::with_memory`] will add the resident set size, and the
+    /// [`AllocationStats`] when [`CountingAllocator`] is installed, and return the
+    /// [`Metrics`].
+    /// # Example
+    /// [`Metrics::with_memory`] can be used to report memory along with other
+    /// metrics:
+    /// ```rust
+    /// use minimal_api::server::metrics::Metrics;
+    ///
+    /// fn get_metrics(sessions: u64) -> String {
+    ///     Metrics::new()
+    ///         .with_memory()
+    ///         .with_gauge("sessions", "Sessions that are stored.", sessions)
+    ///         .to_string()
+    /// }
+    /// ```
+    pub fn with_memory(mut self) -> Self {
+        if let Some(resident_bytes) = resident_memory_bytes() {
+            self = self.with_gauge(
+                "process_resident_memory_bytes",
+                "Resident memory size in bytes.",
+                resident_bytes,
+            );
+        }
+        let stats = AllocationStats::current();
+        if stats.is_counting() {
+            self = self
+                .with_counter(
+                    "allocations_total",
+                    "Allocations made by the global allocator.",
+                    stats.allocations,
+                )
+                .with_counter(
+                    "deallocations_total",
+                    "Allocations freed by the global allocator.",
+                    stats.deallocations,
+                )
+                .with_counter(
+                    "allocated_bytes_total",
+                    "Bytes allocated by the global allocator.",
+                    stats.allocated_bytes,
+                )
+                .with_counter(
+                    "freed_bytes_total",
+                    "Bytes freed by the global allocator.",
+                    stats.freed_bytes,
+                )
+                .with_gauge(
+                    "allocated_bytes_in_use",
+                    "Bytes allocated by the global allocator that are not freed yet.",
+                    stats.in_use_bytes(),
+                );
+        }
+        self
+    }
+
+    /// [`Metrics::with_counter`] will add a counter, which only ever goes up, and
+    /// return the [`Metrics`].
+    pub fn with_counter(mut self, name: &str, help: &str, value: u64) -> Self {
+        self.samples
+            .push((name.to_string(), help.to_string(), "counter", value));
+        self
+    }
+
+    /// [`Metrics::with_gauge`] will add a gauge, which can go up and down, and
+    /// return the [`Metrics`].
+    pub fn with_gauge(mut self, name: &str, help: &str, value: u64) -> Self {
+        self.samples
+            .push((name.to_string(), help.to_string(), "gauge", value));
+        self
+    }
+}
+
+/// Implement [`fmt::Display`] for [`Metrics`], which writes the Prometheus text
+/// format.
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, help, kind, value) in &self.samples {
+            writeln!(f, "# HELP {name} {help}")?;
+            writeln!(f, "# TYPE {name} {kind}")?;
+            writeln!(f, "{name} {value}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::server::{metrics::METRICS_PATH, readiness::READINESS_PATH};
+use std::{net::TcpStream, time::Duration};
+
+/// [`PRIORITY_PATHS`] are the paths of the health and admin requests that are
+/// answered by the priority lane while every worker of the main pool is busy, so
+/// orchestrators don't restart a server that is busy but healthy.
+pub const PRIORITY_PATHS: [&str; 2] = [READINESS_PATH, METRICS_PATH];
+
+/// [`PRIORITY_PEEK_TIMEOUT`] is how long a priority worker waits for the request
+/// line of a connection to tell if it belongs in the priority lane, which it only
+/// does while every worker of the main pool is busy, before handing the connection
+/// to the main pool.
+pub const PRIORITY_PEEK_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// [`REQUEST_LINE_PEEK_BYTES`] is how much of a connection is peeked at for its
+/// request line, which is plenty for the short paths of health and admin requests.
+const REQUEST_LINE_PEEK_BYTES: usize = 256;
+
+/// [`is_priority_request`] will peek at the request line of `stream`, without
+/// reading it, and return `true` if it is a `GET` or `HEAD` request for one of
+/// `priority_paths`, such as a readiness probe. It waits up to `timeout` for the
+/// request line, and returns `false` if it doesn't arrive in time.
+/// # Example
+/// [`is_priority_request`] can be used to answer readiness probes on a reserved
+/// thread:
+/// ```rust
+/// use minimal_api::server::{
+///     priority::{self, PRIORITY_PEEK_TIMEOUT},
+///     readiness::READINESS_PATH,
+/// };
+/// use std::net::TcpStream;
+///
+/// fn is_readiness_probe(stream: &TcpStream) -> bool {
+///     priority::is_priority_request(stream, &[READINESS_PATH], PRIORITY_PEEK_TIMEOUT)
+/// }
+/// ```
+pub fn is_priority_request(stream: &TcpStream, priority_paths: &[&str], timeout: Duration) -> bool {
+    let previous_timeout = stream.read_timeout().unwrap_or(None);
+    if stream.set_read_timeout(Some(timeout)).is_err() {
+        return false;
+    }
+    let mut buffer = [0; REQUEST_LINE_PEEK_BYTES];
+    let peeked = stream.peek(&mut buffer).unwrap_or(0);
+    // the worker that handles the connection reads it with the usual timeout
+    let _ = stream.set_read_timeout(previous_timeout);
+
+    let request_line = String::from_utf8_lossy(&buffer[..peeked]);
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return false;
+    };
+    let path = target.split('?').next().unwrap_or(target);
+    matches!(method, "GET" | "HEAD") && priority_paths.contains(&path)
+}
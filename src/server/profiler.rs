@@ -0,0 +1,181 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Write},
+    sync::{
+        Mutex, MutexGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, ThreadId},
+    time::{Duration, Instant},
+};
+
+/// [`PROFILE_PATH`] is the admin path that runs a [`Profiler`] and answers with its
+/// report, such as `/__profile?seconds=10`.
+pub const PROFILE_PATH: &str = "/__profile";
+
+/// [`DEFAULT_PROFILE_DURATION`] is how long a profile runs when no `seconds` are
+/// asked for.
+pub const DEFAULT_PROFILE_DURATION: Duration = Duration::from_secs(10);
+
+/// [`MAX_PROFILE_DURATION`] is the longest a profile can run, since it holds a
+/// worker for as long as it runs.
+pub const MAX_PROFILE_DURATION: Duration = Duration::from_secs(60);
+
+/// [`DEFAULT_SAMPLE_INTERVAL`] is how long a [`Profiler`] waits between samples.
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// [`Profiler`] is a lightweight wall-time sampling profiler for worker threads.
+/// Threads mark what they are doing with [`Profiler::enter`], and while a profile
+/// runs, the stack of frames of every busy thread is sampled on an interval and
+/// reported as collapsed stacks, one `thread;frame;frame count` line per stack,
+/// which flamegraph tools read as-is. Frames are only recorded while a profile
+/// runs, so a [`Profiler`] costs a single atomic load per frame otherwise.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    /// [`Profiler::enabled`] is if profiles can be run at all.
+    pub enabled: bool,
+    /// [`Profiler::running`] is if a profile is running right now.
+    running: AtomicBool,
+    /// [`Profiler::stacks`] are the name and stack of frames of every thread that is
+    /// in a frame right now.
+    stacks: Mutex<HashMap<ThreadId, (String, Vec<String>)>>,
+}
+
+/// [`Frame`] is a frame a thread entered with [`Profiler::enter`], which the thread
+/// leaves when the [`Frame`] is dropped.
+#[must_use = "the frame is left as soon as it is dropped"]
+pub struct Frame<'a> {
+    /// [`Frame::profiler`] is the [`Profiler`] the frame was recorded in, if it was
+    /// recorded at all.
+    profiler: Option<&'a Profiler>,
+}
+
+impl Profiler {
+    /// [`Profiler::new`] creates a [`Profiler`], which only runs profiles when
+    /// `enabled`.
+    /// # Example
+    /// [`Profiler::new`] can be used to profile a slow piece of work:
+    /// ```rust
+    /// use minimal_api::server::profiler::Profiler;
+    ///
+    /// fn do_slow_work(profiler: &Profiler) {
+    ///     let _frame = profiler.enter("slow work");
+    ///     std::thread::sleep(std::time::Duration::from_millis(50));
+    /// }
+    /// ```
+    pub fn new(enabled: bool) -> Self {
+        Profiler {
+            enabled,
+            ..Profiler::default()
+        }
+    }
+
+    /// [`Profiler::is_running`] will return `true` if a profile is running right
+    /// now, and `false` otherwise.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+
+    /// [`Profiler::enter`] will push `frame` onto the stack of the current thread
+    /// while a profile runs, and return the [`Frame`] that pops it when dropped.
+    /// `frame` is only formatted while a profile runs, so a frame named with
+    /// [`format_args!`] costs nothing otherwise.
+    pub fn enter(&self, frame: impl fmt::Display) -> Frame<'_> {
+        if !self.is_running() {
+            return Frame { profiler: None };
+        }
+        let current = thread::current();
+        self.lock_stacks()
+            .entry(current.id())
+            .or_insert_with(|| (current.name().unwrap_or("worker").to_string(), Vec::new()))
+            .1
+            .push(frame.to_string().replace(';', ":"));
+        Frame {
+            profiler: Some(self),
+        }
+    }
+
+    /// [`Profiler::profile`] will sample every busy thread each `interval` for
+    /// `duration`, and return the collapsed stacks with how many samples saw each,
+    /// or an [`Err`] if the [`Profiler`] isn't enabled or a profile is already
+    /// running.
+    pub fn profile(&self, duration: Duration, interval: Duration) -> Result<String, String> {
+        if !self.enabled {
+            return Err("the profiler is not enabled".to_string());
+        }
+        if self
+            .running
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err("a profile is already running".to_string());
+        }
+
+        let mut samples: BTreeMap<String, u64> = BTreeMap::new();
+        let started = Instant::now();
+        while started.elapsed() < duration {
+            for (name, stack) in self.lock_stacks().values() {
+                if !stack.is_empty() {
+                    *samples
+                        .entry(format!("{name};{}", stack.join(";")))
+                        .or_default() += 1;
+                }
+            }
+            thread::sleep(interval);
+        }
+        self.running.store(false, Ordering::Release);
+
+        Ok(samples
+            .iter()
+            .fold(String::new(), |mut report, (stack, count)| {
+                let _ = writeln!(report, "{stack} {count}");
+                report
+            }))
+    }
+
+    /// [`Profiler::duration_from_query`] will get how long to profile for from the
+    /// `seconds` value of `query`, such as `seconds=30&other=1`, up to
+    /// [`MAX_PROFILE_DURATION`], or [`DEFAULT_PROFILE_DURATION`] if there is none.
+    pub fn duration_from_query(query: &str) -> Result<Duration, String> {
+        match query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("seconds="))
+        {
+            Some(seconds) => seconds
+                .parse::<u64>()
+                .map(|seconds| Duration::from_secs(seconds).min(MAX_PROFILE_DURATION))
+                .map_err(|_| format!("'{seconds}' is not a number of seconds")),
+            None => Ok(DEFAULT_PROFILE_DURATION),
+        }
+    }
+
+    /// [`Profiler::leave`] will pop the top frame off the stack of the current
+    /// thread, forgetting the thread once its stack is empty.
+    fn leave(&self) {
+        let mut stacks = self.lock_stacks();
+        let id = thread::current().id();
+        if let Some((_, stack)) = stacks.get_mut(&id) {
+            stack.pop();
+            if stack.is_empty() {
+                stacks.remove(&id);
+            }
+        }
+    }
+
+    /// [`Profiler::lock_stacks`] will lock [`Profiler::stacks`], recovering them if a
+    /// caller panicked while holding the lock.
+    fn lock_stacks(&self) -> MutexGuard<'_, HashMap<ThreadId, (String, Vec<String>)>> {
+        self.stacks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Implement [`Drop`] for [`Frame`], which leaves the frame.
+impl Drop for Frame<'_> {
+    fn drop(&mut self) {
+        if let Some(profiler) = self.profiler {
+            profiler.leave();
+        }
+    }
+}
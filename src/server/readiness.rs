@@ -0,0 +1,254 @@
+use crate::server::{shutdown::Shutdown, thread_pool::ReadySignal};
+use http::json::Json;
+use logger::{log_debug, log_info, log_warning};
+use std::{
+    io,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// [`READINESS_PATH`] is the path the server answers readiness probes on, such as
+/// a Kubernetes `readinessProbe`.
+pub const READINESS_PATH: &str = "/ready";
+
+/// [`DEFAULT_CHECK_INTERVAL`] is how often every [`DependencyCheck`] is run after
+/// the server starts, unless [`Readiness::with_interval`] is used.
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// [`DependencyCheckFn`] is a dynamic function for a [`DependencyCheck`], which
+/// gives back an [`Err`] describing the problem when the dependency is down.
+pub type DependencyCheckFn = Box<dyn Fn() -> Result<(), String> + Send + Sync + 'static>;
+
+/// [`DependencyCheck`] represents a check of an upstream dependency the server
+/// needs, such as "can reach the database".
+pub struct DependencyCheck {
+    /// [`DependencyCheck::name`] is the name routes use to require the dependency,
+    /// such as `"db"` for `#[http_get("/users", requires = "db")]`.
+    pub name: String,
+    /// [`DependencyCheck::check`] is the [`DependencyCheckFn`] that checks the
+    /// dependency.
+    pub check: DependencyCheckFn,
+}
+
+impl DependencyCheck {
+    /// [`DependencyCheck::new`] creates a [`DependencyCheck`] for the dependency
+    /// `name`, which is checked by `check`.
+    /// # Example
+    /// [`DependencyCheck::new`] can be used to check that a database is reachable:
+    /// ```rust
+    /// use minimal_api::server::readiness::DependencyCheck;
+    /// use std::net::TcpStream;
+    ///
+    /// fn check_database() -> DependencyCheck {
+    ///     DependencyCheck::new("db", Box::new(|| {
+    ///         TcpStream::connect("127.0.0.1:5432")
+    ///             .map(|_| ())
+    ///             .map_err(|error| error.to_string())
+    ///     }))
+    /// }
+    /// ```
+    pub fn new(name: &str, check: DependencyCheckFn) -> Self {
+        DependencyCheck {
+            name: name.to_string(),
+            check,
+        }
+    }
+}
+
+/// [`Readiness`] tracks whether every registered [`DependencyCheck`] is passing,
+/// which decides if the server is ready and which routes can be served.
+pub struct Readiness {
+    /// [`Readiness::checks`] is every registered [`DependencyCheck`].
+    checks: Vec<DependencyCheck>,
+    /// [`Readiness::statuses`] is whether each of [`Readiness::checks`] passed the
+    /// last time it was run, in the same order.
+    statuses: Vec<AtomicBool>,
+    /// [`Readiness::interval`] is how often [`Readiness::checks`] are run.
+    interval: Duration,
+    /// [`Readiness::has_run`] is whether [`Readiness::run_checks`] was called yet.
+    has_run: AtomicBool,
+    /// [`Readiness::signals`] is every [`ReadySignal`] from
+    /// [`Readiness::wait_for`], such as the warm-up of every worker.
+    signals: Mutex<Vec<ReadySignal>>,
+}
+
+impl Readiness {
+    /// [`Readiness::new`] creates a [`Readiness`] for the provided `checks`, which
+    /// are all considered down until [`Readiness::run_checks`] is called.
+    /// # Example
+    /// [`Readiness::new`] can be used to track a single dependency:
+    /// ```rust
+    /// use minimal_api::server::readiness::{DependencyCheck, Readiness};
+    ///
+    /// fn create_readiness() -> Readiness {
+    ///     Readiness::new(vec![DependencyCheck::new("cache", Box::new(|| Ok(())))])
+    /// }
+    /// ```
+    pub fn new(checks: Vec<DependencyCheck>) -> Self {
+        let statuses = checks.iter().map(|_| AtomicBool::new(false)).collect();
+        Readiness {
+            checks,
+            statuses,
+            interval: DEFAULT_CHECK_INTERVAL,
+            has_run: AtomicBool::new(false),
+            signals: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// [`Readiness::with_interval`] will set how often the checks are run by
+    /// [`Readiness::spawn_checks`], and return the [`Readiness`].
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// [`Readiness::interval`] will get how often the checks are run.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// [`Readiness::run_checks`] will run every [`DependencyCheck`] once, and log
+    /// every dependency that went down or came back up.
+    pub fn run_checks(&self) {
+        let is_first_run = !self.has_run.swap(true, Ordering::Relaxed);
+        for (dependency, status) in self.checks.iter().zip(&self.statuses) {
+            let result = (dependency.check)();
+            let was_up = status.swap(result.is_ok(), Ordering::Relaxed);
+            match result {
+                Err(reason) if was_up || is_first_run => {
+                    log_warning!("dependency '{}' is down: {reason}", dependency.name)
+                }
+                Err(reason) => {
+                    log_debug!("dependency '{}' is still down: {reason}", dependency.name)
+                }
+                Ok(()) if !was_up => log_info!("dependency '{}' is up.", dependency.name),
+                Ok(()) => {}
+            }
+        }
+    }
+
+    /// [`Readiness::wait_for`] will keep the [`Readiness`] from being ready until
+    /// `signal` is, such as until every worker of a
+    /// [`ThreadPool`](crate::server::thread_pool::ThreadPool) has warmed up.
+    /// # Example
+    /// [`Readiness::wait_for`] can be used to only be ready once a pool is warm:
+    /// ```rust
+    /// use minimal_api::server::{readiness::Readiness, thread_pool::ThreadPool};
+    ///
+    /// fn wait_for_pool(readiness: &Readiness, pool: &ThreadPool) {
+    ///     readiness.wait_for(pool.ready());
+    /// }
+    /// ```
+    pub fn wait_for(&self, signal: ReadySignal) {
+        self.signals
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(signal);
+    }
+
+    /// [`Readiness::is_warmed_up`] will return `true` if every [`ReadySignal`] from
+    /// [`Readiness::wait_for`] is ready, and `false` otherwise.
+    pub fn is_warmed_up(&self) -> bool {
+        self.signals
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .all(ReadySignal::is_ready)
+    }
+
+    /// [`Readiness::is_ready`] will return `true` if every [`DependencyCheck`] passed
+    /// the last time it was run and [`Readiness::is_warmed_up`], and `false`
+    /// otherwise.
+    pub fn is_ready(&self) -> bool {
+        self.is_warmed_up()
+            && self
+                .statuses
+                .iter()
+                .all(|status| status.load(Ordering::Relaxed))
+    }
+
+    /// [`Readiness::is_available`] will return `true` if the dependency `name`
+    /// passed its last check, and `false` if it failed or was never registered.
+    pub fn is_available(&self, name: &str) -> bool {
+        self.checks
+            .iter()
+            .zip(&self.statuses)
+            .any(|(dependency, status)| dependency.name == name && status.load(Ordering::Relaxed))
+    }
+
+    /// [`Readiness::is_registered`] will return `true` if a [`DependencyCheck`] for
+    /// the dependency `name` was registered, and `false` otherwise.
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.checks.iter().any(|dependency| dependency.name == name)
+    }
+
+    /// [`Readiness::unavailable`] will return every name in `requires` that is not
+    /// [`Readiness::is_available`].
+    /// # Example
+    /// [`Readiness::unavailable`] can be used to decide if a route can be served:
+    /// ```rust
+    /// use http::route::Route;
+    /// use minimal_api::server::readiness::Readiness;
+    ///
+    /// fn can_serve(readiness: &Readiness, route: &Route) -> bool {
+    ///     readiness.unavailable(&route.spec.requires).is_empty()
+    /// }
+    /// ```
+    pub fn unavailable<'a>(&self, requires: &'a [String]) -> Vec<&'a str> {
+        requires
+            .iter()
+            .filter(|name| !self.is_available(name))
+            .map(|name| name.as_str())
+            .collect()
+    }
+
+    /// [`Readiness::get_json`] will get a JSON summary of the [`Readiness`], such as
+    /// `{"ready":false,"dependencies":{"db":"down"}}`.
+    pub fn get_json(&self) -> String {
+        let dependencies = self
+            .checks
+            .iter()
+            .zip(&self.statuses)
+            .map(|(dependency, status)| {
+                let status = if status.load(Ordering::Relaxed) {
+                    "up"
+                } else {
+                    "down"
+                };
+                (dependency.name.clone(), Json::String(status.to_string()))
+            })
+            .collect();
+        Json::Object(vec![
+            ("ready".to_string(), Json::Bool(self.is_ready())),
+            ("dependencies".to_string(), Json::Object(dependencies)),
+        ])
+        .to_string()
+    }
+
+    /// [`Readiness::spawn_checks`] will run every [`DependencyCheck`] on a dedicated
+    /// thread each [`Readiness::interval`], until `shutdown` is triggered.
+    /// # Example
+    /// [`Readiness::spawn_checks`] can be used to keep checking dependencies while
+    /// the server runs:
+    /// ```rust
+    /// use minimal_api::server::{readiness::Readiness, shutdown::Shutdown};
+    ///
+    /// fn monitor(readiness: &'static Readiness, shutdown: Shutdown) -> std::io::Result<()> {
+    ///     readiness.run_checks();
+    ///     readiness.spawn_checks(shutdown).map(|_| ())
+    /// }
+    /// ```
+    pub fn spawn_checks(&'static self, shutdown: Shutdown) -> io::Result<JoinHandle<()>> {
+        thread::Builder::new()
+            .name("readiness".to_string())
+            .spawn(move || {
+                while !shutdown.wait_timeout(self.interval) {
+                    self.run_checks();
+                }
+            })
+    }
+}
@@ -0,0 +1,180 @@
+use crate::server::shutdown::Shutdown;
+use std::{io, path::Path};
+
+/// [`handle_stop_signals`] will trigger the provided [`Shutdown`] when the process
+/// receives `SIGTERM` or `SIGINT`, which is how launchd, systemd, and Docker stop a
+/// service, and what Ctrl+C sends. The signals are watched on a dedicated thread.
+/// # Example
+/// [`handle_stop_signals`] can be used to stop the server gracefully when it is
+/// asked to:
+/// ```rust
+/// use minimal_api::server::{listener, service, shutdown::Shutdown};
+///
+/// fn start_service() -> std::io::Result<()> {
+///     let shutdown = Shutdown::new();
+///     service::handle_stop_signals(&shutdown)?;
+///     listener::serve(shutdown);
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub fn handle_stop_signals(shutdown: &Shutdown) -> io::Result<()> {
+    use logger::log_info;
+    use signal_hook::{
+        consts::{SIGINT, SIGTERM},
+        iterator::Signals,
+    };
+
+    let mut signals = Signals::new([SIGTERM, SIGINT])?;
+    let shutdown = shutdown.clone();
+    std::thread::Builder::new()
+        .name("signals".to_string())
+        .spawn(move || {
+            if let Some(signal) = signals.forever().next() {
+                log_info!("received signal {signal}, shutting down gracefully.");
+                shutdown.trigger();
+            }
+        })?;
+    Ok(())
+}
+
+/// [`handle_stop_signals`] does nothing on platforms without Unix signals, where
+/// the server is stopped gracefully by the Windows service control manager instead
+/// (see [`run_as_windows_service`]).
+#[cfg(not(unix))]
+pub fn handle_stop_signals(_shutdown: &Shutdown) -> io::Result<()> {
+    Ok(())
+}
+
+/// [`launchd_plist`] will create a launchd property list that runs `program` as a
+/// macOS service under the provided `label`, starting it at load and restarting it
+/// if it exits. launchd stops the service with `SIGTERM`, which is handled by
+/// [`handle_stop_signals`].
+/// # Example
+/// [`launchd_plist`] can be used to install the server as a launch agent:
+/// ```rust
+/// use minimal_api::server::service;
+///
+/// fn install_launch_agent(plist_path: &std::path::Path) -> std::io::Result<()> {
+///     let program = std::env::current_exe()?;
+///     std::fs::write(plist_path, service::launchd_plist("com.example.minimal-api", &program))
+/// }
+/// ```
+pub fn launchd_plist(label: &str, program: &Path) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <true/>
+</dict>
+</plist>
+"#,
+        escape_xml(label),
+        escape_xml(&program.to_string_lossy())
+    )
+}
+
+/// [`escape_xml`] will escape every character in `value` that can't appear as-is
+/// in XML text.
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// [`WINDOWS_SERVICE_NAME`] is the name the server registers with the Windows
+/// service control manager, which is the crate name.
+#[cfg(windows)]
+const WINDOWS_SERVICE_NAME: &str = crate::environment::app::CRATE_NAME;
+
+#[cfg(windows)]
+windows_service::define_windows_service!(ffi_service_main, windows_service_main);
+
+/// [`run_as_windows_service`] will hand the current process to the Windows service
+/// control manager, which runs the server until the service is stopped. Stop and
+/// shutdown requests trigger a graceful [`Shutdown`], and the service is reported
+/// as stopped once every in-flight request is finished. This must be called from a
+/// process that was started by the service control manager.
+/// # Example
+/// [`run_as_windows_service`] can be used when the server is installed with
+/// `sc.exe create minimal_api binPath= "minimal-api.exe --windows-service"`:
+/// ```rust,ignore
+/// use minimal_api::server::service;
+///
+/// fn start_service() -> windows_service::Result<()> {
+///     service::run_as_windows_service()
+/// }
+/// ```
+#[cfg(windows)]
+pub fn run_as_windows_service() -> windows_service::Result<()> {
+    windows_service::service_dispatcher::start(WINDOWS_SERVICE_NAME, ffi_service_main)
+}
+
+/// [`windows_service_main`] is called by the Windows service control manager on its
+/// own thread, and serves requests until the service is stopped.
+#[cfg(windows)]
+fn windows_service_main(_arguments: Vec<std::ffi::OsString>) {
+    use logger::log_error;
+    use std::time::Duration;
+    use windows_service::{
+        service::{
+            ServiceControl, ServiceControlAccept, ServiceExitCode, ServiceState, ServiceStatus,
+            ServiceType,
+        },
+        service_control_handler::{self, ServiceControlHandlerResult},
+    };
+
+    let shutdown = Shutdown::new();
+    let control_shutdown = shutdown.clone();
+    let status_handle = match service_control_handler::register(
+        WINDOWS_SERVICE_NAME,
+        move |control| match control {
+            ServiceControl::Stop | ServiceControl::Shutdown => {
+                control_shutdown.trigger();
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            _ => ServiceControlHandlerResult::NotImplemented,
+        },
+    ) {
+        Ok(status_handle) => status_handle,
+        Err(error) => {
+            log_error!("can't register the Windows service control handler: {error}");
+            return;
+        }
+    };
+    let set_state = |current_state, controls_accepted| {
+        let status = ServiceStatus {
+            service_type: ServiceType::OWN_PROCESS,
+            current_state,
+            controls_accepted,
+            exit_code: ServiceExitCode::Win32(0),
+            checkpoint: 0,
+            wait_hint: Duration::default(),
+            process_id: None,
+        };
+        if let Err(error) = status_handle.set_service_status(status) {
+            log_error!("can't report the Windows service status: {error}");
+        }
+    };
+
+    set_state(
+        ServiceState::Running,
+        ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+    );
+    crate::server::listener::serve(shutdown);
+    set_state(ServiceState::Stopped, ServiceControlAccept::empty());
+}
@@ -0,0 +1,78 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+/// [`Shutdown`] is a signal shared between the server and whatever stops it, such
+/// as a signal handler or a service manager. Every clone of a [`Shutdown`] refers
+/// to the same signal.
+#[derive(Clone, Debug, Default)]
+pub struct Shutdown {
+    /// [`Shutdown::state`] is whether the [`Shutdown`] was triggered, along with a
+    /// [`Condvar`] to wake everything waiting on it.
+    state: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl Shutdown {
+    /// [`Shutdown::new`] creates a [`Shutdown`] that hasn't been triggered.
+    pub fn new() -> Self {
+        Shutdown::default()
+    }
+
+    /// [`Shutdown::trigger`] will ask the server to stop accepting connections and
+    /// finish the requests it already accepted. Triggering more than once does
+    /// nothing.
+    /// # Example
+    /// [`Shutdown::trigger`] can be used to stop the server from another thread:
+    /// ```rust
+    /// use minimal_api::server::shutdown::Shutdown;
+    ///
+    /// fn stop_server(shutdown: &Shutdown) {
+    ///     shutdown.trigger();
+    /// }
+    /// ```
+    pub fn trigger(&self) {
+        let (triggered, condvar) = &*self.state;
+        *triggered.lock().unwrap() = true;
+        condvar.notify_all();
+    }
+
+    /// [`Shutdown::is_triggered`] will return `true` if [`Shutdown::trigger`] was
+    /// called on the [`Shutdown`] or any of its clones, and `false` otherwise.
+    pub fn is_triggered(&self) -> bool {
+        *self.state.0.lock().unwrap()
+    }
+
+    /// [`Shutdown::wait`] will block the current thread until [`Shutdown::trigger`]
+    /// is called on the [`Shutdown`] or any of its clones.
+    pub fn wait(&self) {
+        let (triggered, condvar) = &*self.state;
+        let _triggered = condvar
+            .wait_while(triggered.lock().unwrap(), |triggered| !*triggered)
+            .unwrap();
+    }
+
+    /// [`Shutdown::wait_timeout`] will block the current thread until
+    /// [`Shutdown::trigger`] is called or `timeout` passes, and return `true` if the
+    /// [`Shutdown`] was triggered, and `false` otherwise.
+    /// # Example
+    /// [`Shutdown::wait_timeout`] can be used to run a background job periodically
+    /// until the server stops:
+    /// ```rust
+    /// use minimal_api::server::shutdown::Shutdown;
+    /// use std::time::Duration;
+    ///
+    /// fn run_every_second(shutdown: &Shutdown, job: impl Fn()) {
+    ///     while !shutdown.wait_timeout(Duration::from_secs(1)) {
+    ///         job();
+    ///     }
+    /// }
+    /// ```
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let (triggered, condvar) = &*self.state;
+        let (triggered, _) = condvar
+            .wait_timeout_while(triggered.lock().unwrap(), timeout, |triggered| !*triggered)
+            .unwrap();
+        *triggered
+    }
+}
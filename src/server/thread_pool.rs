@@ -1,13 +1,93 @@
 use crate::server::{job::Job, worker::Worker};
 use logger::log_debug;
-use std::sync::{Arc, Mutex, mpsc};
+use std::{
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+};
+
+/// [`WarmUp`] is a job every [`Worker`] of an eagerly started [`ThreadPool`] runs
+/// once before it takes its first [`Job`], such as to fill caches.
+pub type WarmUp = Arc<dyn Fn() + Send + Sync + 'static>;
+
+/// [`WorkerStart`] represents when the [`Worker`] threads of a [`ThreadPool`] are
+/// started.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WorkerStart {
+    /// [`WorkerStart::Eager`] starts every [`Worker`] with the [`ThreadPool`], and
+    /// runs the [`WarmUp`] on each of them.
+    Eager,
+    /// [`WorkerStart::Lazy`] starts a [`Worker`] the first time every started one is
+    /// busy, for a cheaper cold start, such as for a server embedded in a CLI.
+    Lazy,
+}
+
+/// Implement [`FromStr`] for [`WorkerStart`].
+impl FromStr for WorkerStart {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "eager" => Ok(WorkerStart::Eager),
+            "lazy" => Ok(WorkerStart::Lazy),
+            _ => Err(format!("unknown worker start '{value}'")),
+        }
+    }
+}
+
+/// [`ReadySignal`] tells when every [`Worker`] of a [`ThreadPool`] has finished its
+/// [`WarmUp`]. Clones of a [`ReadySignal`] share it.
+#[derive(Clone, Debug, Default)]
+pub struct ReadySignal(Arc<AtomicUsize>);
+
+impl ReadySignal {
+    /// [`ReadySignal::is_ready`] will return `true` once every [`Worker`] has
+    /// finished its [`WarmUp`].
+    pub fn is_ready(&self) -> bool {
+        self.0.load(Ordering::Acquire) == 0
+    }
+
+    /// [`ReadySignal::warmed_up`] will count one more [`Worker`] as warmed up.
+    pub fn warmed_up(&self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
 
 /// [`ThreadPool`] orchestrates one or many [`Worker`] to a [`Job`].
 pub struct ThreadPool {
-    /// [`ThreadPool::workers`] is a collection of [`Worker`].
-    pub workers: Vec<Worker>,
+    /// [`ThreadPool::workers`] is a collection of [`Worker`], which only holds the
+    /// started ones when they are started lazily.
+    workers: Mutex<Vec<Worker>>,
+    /// [`ThreadPool::size`] is how many [`Worker`] the [`ThreadPool`] can have.
+    size: usize,
+    /// [`ThreadPool::start`] is when the [`Worker`] threads are started.
+    start: WorkerStart,
     /// [`ThreadPool::sender`] is the [Sender](mpsc::Sender) of a [`Job`].
     pub sender: Option<mpsc::Sender<Job>>,
+    /// [`ThreadPool::receiver`] is the [Receiver](mpsc::Receiver) every [`Worker`]
+    /// takes a [`Job`] from, kept to start a [`Worker`] lazily.
+    receiver: Arc<Mutex<mpsc::Receiver<Job>>>,
+    /// [`ThreadPool::pending`] is how many [`Job`] were sent to the [`ThreadPool`]
+    /// and have not finished yet, whether they are running or waiting for a
+    /// [`Worker`].
+    pending: Arc<AtomicUsize>,
+    /// [`ThreadPool::ready`] is the [`ReadySignal`] of the [`WarmUp`] of every
+    /// [`Worker`].
+    ready: ReadySignal,
+}
+
+/// [`PendingJob`] counts a [`Job`] as pending in its [`ThreadPool`] until it is
+/// dropped, which happens once the [`Job`] finishes, even if it panics.
+struct PendingJob(Arc<AtomicUsize>);
+
+/// Implement [`Drop`] for [`PendingJob`].
+impl Drop for PendingJob {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 impl ThreadPool {
@@ -26,21 +106,81 @@ impl ThreadPool {
     /// # Panics
     /// [`ThreadPool::new`] will [`panic`] if the `size` is `0`.
     pub fn new(size: usize) -> ThreadPool {
+        ThreadPool::create(size, WorkerStart::Eager, None)
+    }
+
+    /// [`ThreadPool::lazy`] will create a new [`ThreadPool`] with capacity for
+    /// `size` [`Worker`] threads, which are only started once every started one is
+    /// busy.
+    /// # Example
+    /// [`ThreadPool::lazy`] can be used to start a server without starting threads
+    /// it might not need:
+    /// ```rust
+    /// use minimal_api::server::thread_pool::ThreadPool;
+    ///
+    /// fn create_idle_worker_threads() -> ThreadPool {
+    ///     ThreadPool::lazy(10)
+    /// }
+    /// ```
+    /// # Panics
+    /// [`ThreadPool::lazy`] will [`panic`] if the `size` is `0`.
+    pub fn lazy(size: usize) -> ThreadPool {
+        ThreadPool::create(size, WorkerStart::Lazy, None)
+    }
+
+    /// [`ThreadPool::with_warm_up`] will create a new [`ThreadPool`] of `size`
+    /// [`Worker`] threads that are all started right away, where each runs
+    /// `warm_up` before it takes its first [`Job`].
+    /// # Example
+    /// [`ThreadPool::with_warm_up`] can be used to fill a cache on every thread before
+    /// it serves anything:
+    /// ```rust
+    /// use minimal_api::server::thread_pool::ThreadPool;
+    /// use std::sync::Arc;
+    ///
+    /// fn create_warm_worker_threads() -> ThreadPool {
+    ///     ThreadPool::with_warm_up(10, Arc::new(|| println!("warming up!")))
+    /// }
+    /// ```
+    /// # Panics
+    /// [`ThreadPool::with_warm_up`] will [`panic`] if the `size` is `0`.
+    pub fn with_warm_up(size: usize, warm_up: WarmUp) -> ThreadPool {
+        ThreadPool::create(size, WorkerStart::Eager, Some(warm_up))
+    }
+
+    /// [`ThreadPool::create`] will create a [`ThreadPool`] of `size` [`Worker`]
+    /// threads that are started by `start`, where eagerly started ones run
+    /// `warm_up` first.
+    fn create(size: usize, start: WorkerStart, warm_up: Option<WarmUp>) -> ThreadPool {
         // panic if the size is 0 or less
         assert!(size > 0);
 
-        // create the worker threads
+        // create the worker threads, unless they are started on demand
         let (sender, receiver) = mpsc::channel();
         let receiver = Arc::new(Mutex::new(receiver));
+        let warming_up = if warm_up.is_some() { size } else { 0 };
+        let ready = ReadySignal(Arc::new(AtomicUsize::new(warming_up)));
         let mut workers = Vec::with_capacity(size);
-        for id in 0..size {
-            workers.push(Worker::new(id, Arc::clone(&receiver)));
+        if start == WorkerStart::Eager {
+            for id in 0..size {
+                workers.push(Worker::with_warm_up(
+                    id,
+                    Arc::clone(&receiver),
+                    warm_up.clone(),
+                    ready.clone(),
+                ));
+            }
         }
 
         // return the thread pool with the worker threads and a reference to the sender
         ThreadPool {
-            workers,
+            workers: Mutex::new(workers),
+            size,
+            start,
             sender: Some(sender),
+            receiver,
+            pending: Arc::new(AtomicUsize::new(0)),
+            ready,
         }
     }
 
@@ -65,9 +205,84 @@ impl ThreadPool {
     where
         F: FnOnce() + Send + 'static,
     {
-        let job = Box::new(f);
+        let pending = self.pending.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.start == WorkerStart::Lazy {
+            let mut workers = self
+                .workers
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            // a lazy worker skips the warm-up, which would hold up the job that
+            // started it
+            if pending > workers.len() && workers.len() < self.size {
+                let id = workers.len();
+                log_debug!("starting worker {id} on demand.");
+                workers.push(Worker::new(id, Arc::clone(&self.receiver)));
+            }
+        }
+        let pending_job = PendingJob(Arc::clone(&self.pending));
+        let job = Box::new(move || {
+            let _pending_job = pending_job;
+            f();
+        });
         self.sender.as_ref().unwrap().send(job).unwrap();
     }
+
+    /// [`ThreadPool::pending`] will get how many [`Job`] were sent to the
+    /// [`ThreadPool`] and have not finished yet.
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// [`ThreadPool::is_saturated`] will return `true` if every [`Worker`] is busy,
+    /// so a new [`Job`] would have to wait for one of them to finish.
+    /// # Example
+    /// [`ThreadPool::is_saturated`] can be used to send work elsewhere while every
+    /// [`Worker`] is busy:
+    /// ```rust
+    /// use minimal_api::server::thread_pool::ThreadPool;
+    ///
+    /// fn run_somewhere(pool: &ThreadPool, overflow: &ThreadPool) {
+    ///     let pool = if pool.is_saturated() { overflow } else { pool };
+    ///     pool.execute(|| println!("hello!"));
+    /// }
+    /// ```
+    pub fn is_saturated(&self) -> bool {
+        self.pending() >= self.size
+    }
+
+    /// [`ThreadPool::size`] will get how many [`Worker`] the [`ThreadPool`] can have.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// [`ThreadPool::started`] will get how many [`Worker`] threads were started,
+    /// which is less than [`ThreadPool::size`] until a lazy [`ThreadPool`] has been
+    /// busy enough to need all of them.
+    pub fn started(&self) -> usize {
+        self.workers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+
+    /// [`ThreadPool::ready`] will get the [`ReadySignal`] of the [`ThreadPool`],
+    /// which is ready once every [`Worker`] has run its [`WarmUp`], and right away
+    /// when there is none.
+    /// # Example
+    /// [`ThreadPool::ready`] can be used to wait for every [`Worker`] to warm up:
+    /// ```rust
+    /// use minimal_api::server::thread_pool::ThreadPool;
+    ///
+    /// fn wait_for_warm_up(pool: &ThreadPool) {
+    ///     let ready = pool.ready();
+    ///     while !ready.is_ready() {
+    ///         std::thread::yield_now();
+    ///     }
+    /// }
+    /// ```
+    pub fn ready(&self) -> ReadySignal {
+        self.ready.clone()
+    }
 }
 
 /// Implement [`Drop`] for [`ThreadPool`].
@@ -75,7 +290,11 @@ impl Drop for ThreadPool {
     fn drop(&mut self) {
         drop(self.sender.take());
 
-        for worker in &mut self.workers {
+        let workers = self
+            .workers
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for worker in workers {
             log_debug!("shutting down worker {}.", worker.id);
 
             if let Some(thread) = worker.thread.take() {
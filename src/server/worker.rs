@@ -1,4 +1,7 @@
-use crate::server::job::Job;
+use crate::server::{
+    job::Job,
+    thread_pool::{ReadySignal, WarmUp},
+};
 use logger::{log_debug, log_error};
 use std::{
     sync::{Arc, Mutex, mpsc},
@@ -37,9 +40,30 @@ impl Worker {
     /// }
     /// ```
     pub fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> Worker {
+        Worker::with_warm_up(id, receiver, None, ReadySignal::default())
+    }
+
+    /// [`Worker::with_warm_up`] will create a new [`Worker`] that runs `warm_up`,
+    /// then counts itself as warmed up on `ready`, before it operates on the
+    /// provided [`Job`] in a thread-safe manner. A [`Worker`] without a `warm_up`
+    /// leaves `ready` as it is.
+    pub fn with_warm_up(
+        id: usize,
+        receiver: Arc<Mutex<mpsc::Receiver<Job>>>,
+        warm_up: Option<WarmUp>,
+        ready: ReadySignal,
+    ) -> Worker {
         let thread = thread::spawn(move || {
+            if let Some(warm_up) = warm_up {
+                log_debug!("worker {id} warming up.");
+                warm_up();
+                ready.warmed_up();
+            }
             loop {
-                match receiver.lock().unwrap().recv() {
+                // the lock is released before the job runs, so the other workers can
+                // take the next jobs in the meantime
+                let job = receiver.lock().unwrap().recv();
+                match job {
                     Ok(job) => {
                         log_debug!("worker {id} got a job; executing.");
                         job();
@@ -0,0 +1,115 @@
+use minimal_api::server::{
+    acceptor::{self, SocketOptions},
+    shutdown::Shutdown,
+};
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    time::Duration,
+};
+
+#[test]
+fn spawn_acceptor_should_apply_socket_options_before_handing_off_streams() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let socket_options = SocketOptions {
+        nodelay: true,
+        linger: None,
+    };
+    let (sender, receiver) = mpsc::channel();
+
+    acceptor::spawn_acceptor(listener, socket_options, Shutdown::new(), move |stream| {
+        sender.send(stream.nodelay().unwrap()).unwrap();
+    })
+    .unwrap();
+    let _client = TcpStream::connect(address).unwrap();
+    let nodelay = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    assert!(nodelay);
+}
+
+#[cfg(feature = "socket-extras")]
+#[test]
+fn apply_should_set_linger_with_socket_extras_feature() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let socket_options = SocketOptions {
+        nodelay: false,
+        linger: Some(Duration::from_secs(1)),
+    };
+
+    socket_options.apply(&client).unwrap();
+
+    assert_eq!(
+        Some(Duration::from_secs(1)),
+        socket2::SockRef::from(&client).linger().unwrap()
+    );
+}
+
+#[test]
+fn apply_should_clear_socket_options_when_disabled() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    client.set_nodelay(true).unwrap();
+
+    SocketOptions::default().apply(&client).unwrap();
+
+    assert!(!client.nodelay().unwrap());
+    #[cfg(feature = "socket-extras")]
+    assert_eq!(None, socket2::SockRef::from(&client).linger().unwrap());
+}
+
+#[cfg(not(feature = "socket-extras"))]
+#[test]
+fn bind_reuse_port_should_bind_a_single_listener_without_socket_extras_feature() {
+    let address = "127.0.0.1:0".parse().unwrap();
+
+    let listeners = acceptor::bind_reuse_port(address, 1).unwrap();
+
+    assert_eq!(1, listeners.len());
+    assert!(acceptor::bind_reuse_port(address, 3).is_err());
+}
+
+#[cfg(all(target_os = "linux", feature = "socket-extras"))]
+#[test]
+fn bind_reuse_port_should_bind_every_listener_to_same_address() {
+    let address = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let listeners = acceptor::bind_reuse_port(address, 3).unwrap();
+
+    assert_eq!(3, listeners.len());
+    for listener in &listeners {
+        assert_eq!(address, listener.local_addr().unwrap());
+    }
+    assert!(TcpStream::connect(address).is_ok());
+}
+
+#[test]
+fn stop_acceptors_should_stop_acceptors_after_shutdown() {
+    let listener = TcpListener::bind("0.0.0.0:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let shutdown = Shutdown::new();
+    let (sender, receiver) = mpsc::channel();
+    let acceptor = acceptor::spawn_acceptor(
+        listener,
+        SocketOptions::default(),
+        shutdown.clone(),
+        move |stream| sender.send(stream).unwrap(),
+    )
+    .unwrap();
+
+    shutdown.trigger();
+    acceptor::stop_acceptors(&[address], vec![acceptor]);
+
+    assert!(receiver.recv_timeout(Duration::from_secs(1)).is_err());
+    assert!(
+        TcpStream::connect_timeout(
+            &([127, 0, 0, 1], address.port()).into(),
+            Duration::from_secs(1)
+        )
+        .is_err()
+    );
+}
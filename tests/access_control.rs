@@ -0,0 +1,57 @@
+use minimal_api::server::access_control::{AccessControl, AccessList, Cidr};
+use std::net::IpAddr;
+
+fn ip(address: &str) -> IpAddr {
+    address.parse().unwrap()
+}
+
+#[test]
+fn cidr_should_contain_addresses_in_range() {
+    let ipv4: Cidr = "10.1.0.0/15".parse().unwrap();
+    let ipv6: Cidr = "2001:db8::/32".parse().unwrap();
+
+    assert!(ipv4.contains(ip("10.0.255.255")));
+    assert!(ipv4.contains(ip("10.1.2.3")));
+    assert!(!ipv4.contains(ip("10.2.0.0")));
+    assert!(ipv4.contains(ip("::ffff:10.1.2.3")));
+    assert!(ipv6.contains(ip("2001:db8:1::1")));
+    assert!(!ipv6.contains(ip("2001:db9::1")));
+    assert!(!ipv6.contains(ip("10.1.2.3")));
+}
+
+#[test]
+fn cidr_should_parse_single_addresses_and_reject_invalid_ranges() {
+    let single: Cidr = "192.168.1.7".parse().unwrap();
+
+    assert_eq!("192.168.1.7/32", single.to_string());
+    assert!(single.contains(ip("192.168.1.7")));
+    assert!(!single.contains(ip("192.168.1.8")));
+    assert!("0.0.0.0/0".parse::<Cidr>().unwrap().contains(ip("8.8.8.8")));
+    assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+    assert!("not-an-ip/8".parse::<Cidr>().is_err());
+}
+
+#[test]
+fn access_list_should_let_deny_win_over_allow() {
+    let access_list = AccessList::parse("10.0.0.0/8", "10.0.0.13").unwrap();
+
+    assert!(access_list.is_allowed(ip("10.0.0.12")));
+    assert!(!access_list.is_allowed(ip("10.0.0.13")));
+    assert!(!access_list.is_allowed(ip("192.168.0.1")));
+    assert!(AccessList::parse("", " ").unwrap().is_empty());
+}
+
+#[test]
+fn access_control_should_check_route_groups_and_count_denials() {
+    let access_control = AccessControl::new()
+        .with_global(AccessList::new().deny("203.0.113.0/24").unwrap())
+        .with_group("/admin/", AccessList::new().allow("10.0.0.0/8").unwrap());
+
+    assert!(access_control.is_allowed(ip("198.51.100.1"), "/users"));
+    assert!(access_control.is_allowed(ip("198.51.100.1"), "/administrator"));
+    assert!(!access_control.is_allowed(ip("198.51.100.1"), "/admin"));
+    assert!(!access_control.is_allowed(ip("198.51.100.1"), "/admin/users"));
+    assert!(access_control.is_allowed(ip("10.4.4.4"), "/admin/users"));
+    assert!(!access_control.is_allowed(ip("203.0.113.9"), "/users"));
+    assert_eq!(3, access_control.denials());
+}
@@ -0,0 +1,78 @@
+use http::{
+    arena::{self, Arena},
+    parser::{self, ParsingMode},
+};
+use std::io::Cursor;
+
+#[test]
+fn alloc_should_return_handles_to_every_allocation() {
+    let mut arena = Arena::with_capacity(64);
+
+    let first = arena.alloc("content-type");
+    let second = arena.alloc("application/json");
+    let joined = arena.join(&first, ": ", &second);
+
+    assert_eq!("content-type", arena.get(&first));
+    assert_eq!("application/json", arena.get(&second));
+    assert_eq!("content-type: application/json", arena.get(&joined));
+}
+
+#[test]
+fn alloc_line_should_read_lines_into_arena() {
+    let mut arena = Arena::with_capacity(64);
+    let mut reader = Cursor::new("GET / HTTP/1.1\r\n  folded value \r\n");
+
+    let request_line = arena.alloc_line(&mut reader).unwrap();
+    let folded = arena.alloc_line(&mut reader).unwrap();
+    let end = arena.alloc_line(&mut reader).unwrap();
+
+    assert_eq!("GET / HTTP/1.1", arena.get(&request_line.shorten(2)));
+    assert_eq!("folded value", arena.get(&arena.trim(&folded)));
+    assert_eq!("", arena.get(&end));
+}
+
+#[test]
+fn reset_should_keep_capacity() {
+    let mut arena = Arena::with_capacity(16);
+    arena.alloc(&"x".repeat(1024));
+    let capacity = arena.capacity();
+
+    arena.reset();
+
+    assert!(arena.is_empty());
+    assert_eq!(capacity, arena.capacity());
+}
+
+#[test]
+fn read_request_head_should_record_arena_stats() {
+    let before = arena::stats();
+    let mut reader = Cursor::new("GET / HTTP/1.1\r\nHost: localhost\r\n\r\n");
+
+    parser::read_request_head(&mut reader, ParsingMode::Strict).unwrap();
+    let after = arena::stats();
+
+    assert!(after.requests > before.requests);
+    assert!(after.bytes_allocated >= before.bytes_allocated + 35);
+    assert!(after.peak_request_bytes >= 35);
+}
+
+#[test]
+fn alloc_line_within_should_stop_at_max_bytes() {
+    let mut arena = Arena::with_capacity(64);
+    let mut reader = Cursor::new("X-Long: value\r\nNext: line\r\n");
+
+    let line = arena.alloc_line_within(&mut reader, 6).unwrap();
+
+    assert_eq!("X-Long", arena.get(&line));
+}
+
+#[test]
+fn with_request_arena_should_shrink_an_arena_that_grew_too_large() {
+    arena::with_request_arena(|arena| {
+        arena.alloc(&"x".repeat(arena::REQUEST_ARENA_MAX_CAPACITY * 2));
+    });
+
+    let capacity = arena::with_request_arena(|arena| arena.capacity());
+
+    assert!(capacity <= arena::REQUEST_ARENA_MAX_CAPACITY);
+}
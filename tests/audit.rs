@@ -0,0 +1,153 @@
+use http::{
+    audit::{AuditLog, AuditRecord, AuditRotation, AuditSink},
+    json::Json,
+    methods::{DELETE, GET, POST, PUT},
+    request::Request,
+    status::Status,
+};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+fn create_path(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("minimal-api-audit-{name}"));
+    let _ = fs::remove_dir_all(&directory);
+    directory.join("audit.log")
+}
+
+fn create_request(method: http::methods::Method, body: Option<&str>) -> Request {
+    Request::new(
+        "/users?notify=true",
+        method,
+        body.map(str::to_string),
+        HashMap::new(),
+    )
+    .with_headers(HashMap::from([(
+        "X-Authenticated-User".to_string(),
+        "evan".to_string(),
+    )]))
+}
+
+fn read_lines(path: &PathBuf) -> Vec<Json> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(|line| Json::parse(line).unwrap())
+        .collect()
+}
+
+// ===========
+// audit tests
+// ===========
+#[test]
+fn to_json_should_record_who_what_and_when() {
+    let record = AuditRecord::new(
+        &create_request(POST, Some("test")),
+        Some("evan".to_string()),
+        Status::Created,
+    );
+    let json = Json::parse(&record.to_json()).unwrap();
+
+    assert_eq!(Some("evan"), json.get("principal").and_then(Json::as_str));
+    assert_eq!(Some("POST"), json.get("method").and_then(Json::as_str));
+    assert_eq!(
+        Some("/users?notify=true"),
+        json.get("path").and_then(Json::as_str)
+    );
+    assert_eq!(
+        Some("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"),
+        json.get("body_sha256").and_then(Json::as_str)
+    );
+    assert_eq!(Some(201.0), json.get("status").and_then(Json::as_f64));
+    assert!(json.get("time").and_then(Json::as_f64).unwrap() > 0.0);
+}
+
+#[test]
+fn to_json_should_record_null_without_a_principal_or_body() {
+    let record = AuditRecord::new(&create_request(DELETE, None), None, Status::NoContent);
+    let json = Json::parse(&record.to_json()).unwrap();
+
+    assert_eq!(Some(&Json::Null), json.get("principal"));
+    assert_eq!(Some(&Json::Null), json.get("body_sha256"));
+}
+
+#[test]
+fn audits_should_only_be_true_for_mutating_methods_with_a_sink() {
+    let path = create_path("audits");
+    let audit_log = AuditLog::new();
+    assert!(!audit_log.audits(POST));
+
+    let audit_log = audit_log.with_sink(AuditSink::open(&path, AuditRotation::default()).unwrap());
+    assert!(audit_log.audits(POST));
+    assert!(audit_log.audits(DELETE));
+    assert!(!audit_log.audits(GET));
+}
+
+#[test]
+fn record_should_append_mutating_requests_with_their_principal() {
+    let path = create_path("record");
+    let audit_log = AuditLog::new()
+        .with_principal(Box::new(|request| {
+            request.header("X-Authenticated-User").map(str::to_string)
+        }))
+        .with_sink(AuditSink::open(&path, AuditRotation::default()).unwrap());
+
+    audit_log.record(&create_request(GET, None), Status::Ok);
+    audit_log.record(&create_request(POST, Some("{}")), Status::Created);
+    audit_log.record(&create_request(DELETE, None), Status::NoContent);
+
+    let lines = read_lines(&path);
+    let methods: Vec<&str> = lines
+        .iter()
+        .filter_map(|line| line.get("method").and_then(Json::as_str))
+        .collect();
+    assert_eq!(vec!["POST", "DELETE"], methods);
+    assert!(
+        lines
+            .iter()
+            .all(|line| line.get("principal").and_then(Json::as_str) == Some("evan"))
+    );
+}
+
+#[test]
+fn open_should_append_to_an_existing_file() {
+    let path = create_path("reopen");
+    let record = AuditRecord::new(&create_request(PUT, None), None, Status::Ok);
+
+    AuditSink::open(&path, AuditRotation::default())
+        .unwrap()
+        .append(&record.to_json())
+        .unwrap();
+    AuditSink::open(&path, AuditRotation::default())
+        .unwrap()
+        .append(&record.to_json())
+        .unwrap();
+
+    assert_eq!(2, read_lines(&path).len());
+}
+
+#[test]
+fn append_should_rotate_and_keep_only_the_newest_files() {
+    let path = create_path("rotate");
+    let sink = AuditSink::open(
+        &path,
+        AuditRotation {
+            max_bytes: 8,
+            keep: 2,
+        },
+    )
+    .unwrap();
+
+    for line in ["\"one\"", "\"two\"", "\"three\"", "\"four\""] {
+        sink.append(line).unwrap();
+    }
+
+    assert_eq!("\"four\"\n", fs::read_to_string(&path).unwrap());
+    assert_eq!(
+        "\"three\"\n",
+        fs::read_to_string(sink.rotated_path(1)).unwrap()
+    );
+    assert_eq!(
+        "\"two\"\n",
+        fs::read_to_string(sink.rotated_path(2)).unwrap()
+    );
+    assert!(!sink.rotated_path(3).exists());
+}
@@ -0,0 +1,172 @@
+use http::{
+    batch::{Batch, BatchRequest, MAX_BATCH_REQUESTS},
+    json::Json,
+    methods::{GET, POST},
+    request::Request,
+    response::Response,
+    router::Router,
+    status::Status,
+};
+use http_attributes::{http_get, http_post};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/batch/users/{id}")]
+fn get_user(id: u32) -> String {
+    format!("\"user {id}\"")
+}
+
+#[http_post("/batch/echo")]
+fn echo(content: String) -> String {
+    content
+}
+
+fn dispatch(router: &Router, sub_request: &BatchRequest) -> Response {
+    match router.resolve(sub_request.method, &sub_request.path) {
+        Some((route, path_params)) => route.get_response(Request::new(
+            &sub_request.path,
+            sub_request.method,
+            sub_request.body.clone(),
+            path_params,
+        )),
+        None => Response::not_found(),
+    }
+}
+
+// ===========
+// batch tests
+// ===========
+#[test]
+fn parse_all_should_read_every_sub_request_in_order() {
+    let requests = BatchRequest::parse_all(
+        r#"[
+            {"method": "GET", "path": "/batch/users/1"},
+            {"method": "POST", "path": "/batch/echo", "body": {"name": "evan"}},
+            {"method": "POST", "path": "/batch/echo", "body": "plain"}
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            BatchRequest {
+                method: GET,
+                path: "/batch/users/1".to_string(),
+                body: None,
+            },
+            BatchRequest {
+                method: POST,
+                path: "/batch/echo".to_string(),
+                body: Some(r#"{"name":"evan"}"#.to_string()),
+            },
+            BatchRequest {
+                method: POST,
+                path: "/batch/echo".to_string(),
+                body: Some("plain".to_string()),
+            },
+        ],
+        requests
+    );
+}
+
+#[test]
+fn parse_all_should_reject_invalid_batches() {
+    assert!(BatchRequest::parse_all("{}").is_err());
+    assert!(BatchRequest::parse_all(r#"[{"path": "/batch/users/1"}]"#).is_err());
+    assert!(BatchRequest::parse_all(r#"[{"method": "GET", "path": "users"}]"#).is_err());
+    assert!(BatchRequest::parse_all(r#"[{"method": "FETCH", "path": "/"}]"#).is_err());
+
+    let too_many = vec![r#"{"method": "GET", "path": "/"}"#; MAX_BATCH_REQUESTS + 1].join(",");
+    assert!(BatchRequest::parse_all(&format!("[{too_many}]")).is_err());
+}
+
+#[test]
+fn run_should_keep_the_order_of_sub_requests_when_concurrent() {
+    let requests: Vec<BatchRequest> = (0..10)
+        .map(|index| BatchRequest {
+            method: GET,
+            path: format!("/{index}"),
+            body: None,
+        })
+        .collect();
+
+    let responses = Batch::new(4).run(&requests, |sub_request| {
+        // later sub-requests finish first
+        let index: u64 = sub_request.path[1..].parse().unwrap();
+        thread::sleep(Duration::from_millis(10 - index));
+        Response::ok(&sub_request.path, true)
+    });
+
+    let paths: Vec<&str> = responses
+        .iter()
+        .map(|response| response.content.as_str())
+        .collect();
+    assert_eq!(
+        vec!["/0", "/1", "/2", "/3", "/4", "/5", "/6", "/7", "/8", "/9"],
+        paths
+    );
+}
+
+#[test]
+fn run_should_limit_concurrent_sub_requests() {
+    let requests = vec![
+        BatchRequest {
+            method: GET,
+            path: "/".to_string(),
+            body: None,
+        };
+        8
+    ];
+    let running = AtomicUsize::new(0);
+    let most_running = AtomicUsize::new(0);
+
+    Batch::new(2).run(&requests, |_| {
+        let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+        most_running.fetch_max(now_running, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(5));
+        running.fetch_sub(1, Ordering::SeqCst);
+        Response::no_content()
+    });
+
+    assert!(most_running.load(Ordering::SeqCst) <= 2);
+}
+
+#[test]
+fn handle_should_answer_with_every_sub_response() {
+    let router = Router::new(vec![get_user(), echo()]);
+    let body = r#"[
+        {"method": "GET", "path": "/batch/users/7"},
+        {"method": "POST", "path": "/batch/echo", "body": "\"hi\""},
+        {"method": "GET", "path": "/batch/missing"}
+    ]"#;
+
+    let response = Batch::new(1).handle(body, |sub_request| dispatch(&router, sub_request));
+
+    assert_eq!(Status::Ok, response.status);
+    let responses = Json::parse(&response.content).unwrap();
+    let responses = responses.as_array().unwrap();
+    assert_eq!(3, responses.len());
+    let statuses: Vec<f64> = responses
+        .iter()
+        .filter_map(|response| response.get("status").and_then(Json::as_f64))
+        .collect();
+    assert_eq!(vec![200.0, 200.0, 404.0], statuses);
+    assert_eq!(
+        Some("user 7"),
+        responses[0].get("body").and_then(Json::as_str)
+    );
+    assert_eq!(Some("hi"), responses[1].get("body").and_then(Json::as_str));
+}
+
+#[test]
+fn handle_should_reject_a_batch_that_does_not_parse() {
+    let response = Batch::new(1).handle("not json", |_| Response::no_content());
+
+    assert_eq!(Status::BadRequest, response.status);
+}
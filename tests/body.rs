@@ -0,0 +1,117 @@
+use http::{
+    body::{self, BodyReader},
+    request::Request,
+    response::Response,
+    validation::ParamLocation,
+};
+use http_attributes::{http_post, http_put};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+};
+
+// ==================
+// common test values
+// ==================
+const TEST_UPLOAD: &str = "first line\nsecond line\nthird line\n";
+
+// =================
+// endpoints to test
+// =================
+#[http_put("/upload/{name}", generate_tests)]
+fn replace_upload(name: String, #[body(stream)] upload: BodyReader) -> String {
+    format!("{name}: {}", upload.len())
+}
+
+#[http_post("/upload", requires = "storage")]
+fn count_uploaded_lines(#[body(limit = "1MB", stream)] upload: BodyReader) -> String {
+    BufReader::new(upload).lines().count().to_string()
+}
+
+#[http_put("/notes/{id}")]
+fn update_note(id: String, #[body(limit = "16KB")] note: String) -> String {
+    format!("{id}: {note}")
+}
+
+// ==========
+// body tests
+// ==========
+#[test]
+fn parse_size_should_parse_every_unit() {
+    assert_eq!(Ok(512), body::parse_size("512"));
+    assert_eq!(Ok(512), body::parse_size("512B"));
+    assert_eq!(Ok(64 * 1024), body::parse_size("64KB"));
+    assert_eq!(Ok(1024 * 1024), body::parse_size("1 mb"));
+    assert_eq!(Ok(2 * 1024 * 1024 * 1024), body::parse_size("2GB"));
+    assert!(body::parse_size("MB").is_err());
+    assert!(body::parse_size("1TB").is_err());
+}
+
+#[test]
+fn take_body_should_read_body_content_when_body_was_not_streamed() {
+    let mut request = Request::new(
+        "/upload",
+        http::methods::POST,
+        Some(TEST_UPLOAD.to_string()),
+        HashMap::new(),
+    );
+
+    let lines = BufReader::new(request.take_body()).lines().count();
+
+    assert_eq!(3, lines);
+    assert_eq!(None, request.body_content);
+}
+
+#[test]
+fn body_reader_should_stop_at_the_end_of_the_body() {
+    let connection = format!("{TEST_UPLOAD}GET /next HTTP/1.1");
+    let reader = BodyReader::new(
+        Box::new(std::io::Cursor::new(connection.into_bytes())),
+        TEST_UPLOAD.len(),
+    );
+
+    let lines: Vec<String> = BufReader::new(reader).lines().map(Result::unwrap).collect();
+
+    assert_eq!(vec!["first line", "second line", "third line"], lines);
+}
+
+#[test]
+fn streamed_body_attribute_should_set_limit_and_stream_the_body() {
+    let route = count_uploaded_lines();
+    let mut request = Request::new("/upload", http::methods::POST, None, HashMap::new());
+    request.body_reader = Some(BodyReader::from_bytes(TEST_UPLOAD.as_bytes().to_vec()));
+
+    let response = route.get_response(request);
+
+    assert_eq!(Some(1024 * 1024), route.spec.body_limit);
+    assert!(route.spec.streams_body);
+    assert_eq!(vec!["storage".to_string()], route.spec.requires);
+    assert_eq!(Response::ok("3", false), response);
+}
+
+#[test]
+fn streamed_body_should_not_be_validated() {
+    let route = count_uploaded_lines();
+    let request = Request::new("/upload", http::methods::POST, None, HashMap::new());
+
+    assert!(route.spec.validate(&request).is_empty());
+}
+
+#[test]
+fn body_attribute_should_pick_the_body_argument() {
+    let route = update_note();
+    let request = Request::new(
+        "/notes/7",
+        http::methods::PUT,
+        Some("remember the milk".to_string()),
+        HashMap::from([("id".to_string(), "7".to_string())]),
+    );
+
+    let response = route.get_response(request);
+
+    assert_eq!(Some(16 * 1024), route.spec.body_limit);
+    assert!(!route.spec.streams_body);
+    assert_eq!(ParamLocation::Path, route.spec.params[0].location);
+    assert_eq!(ParamLocation::Body, route.spec.params[1].location);
+    assert_eq!(Response::ok("7: remember the milk", false), response);
+}
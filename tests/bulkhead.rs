@@ -0,0 +1,113 @@
+use http::{bulkhead::Bulkhead, methods::GET, request::Request, status::Status};
+use http_attributes::http_get;
+use std::{
+    collections::HashMap,
+    thread,
+    time::{Duration, Instant},
+};
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/reports/slow", concurrency = 1)]
+fn get_slow_report(millis: u64) -> String {
+    thread::sleep(Duration::from_millis(millis));
+    "\"report\"".to_string()
+}
+
+#[http_get("/reports/queued", concurrency = 1, queue_ms = 2000)]
+fn get_queued_report(millis: u64) -> String {
+    thread::sleep(Duration::from_millis(millis));
+    "\"report\"".to_string()
+}
+
+fn create_request(path: &str, millis: u64) -> Request {
+    Request::new(
+        &format!("{path}?millis={millis}"),
+        GET,
+        None,
+        HashMap::new(),
+    )
+}
+
+// ==============
+// bulkhead tests
+// ==============
+#[test]
+fn bulkhead_should_reject_calls_over_the_limit() {
+    let bulkhead = Bulkhead::new(2);
+
+    let first = bulkhead.try_enter();
+    let second = bulkhead.try_enter();
+    let third = bulkhead.try_enter();
+
+    assert!(first.is_some() && second.is_some());
+    assert!(third.is_none());
+    assert_eq!(2, bulkhead.running());
+    assert_eq!(1, bulkhead.rejected());
+}
+
+#[test]
+fn bulkhead_should_make_room_when_a_permit_is_dropped() {
+    let bulkhead = Bulkhead::new(1);
+
+    drop(bulkhead.try_enter());
+    let permit = bulkhead.try_enter();
+
+    assert!(permit.is_some());
+    assert_eq!(0, bulkhead.rejected());
+}
+
+#[test]
+fn bulkhead_should_queue_calls_until_one_finishes() {
+    let bulkhead = Bulkhead::new(1).with_queue_timeout(Duration::from_secs(2));
+    let permit = bulkhead.try_enter();
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(50));
+            drop(permit);
+        });
+        let started = Instant::now();
+        let queued = bulkhead.try_enter();
+
+        assert!(queued.is_some());
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    });
+    assert_eq!(0, bulkhead.rejected());
+}
+
+#[test]
+fn route_with_concurrency_should_answer_excess_requests_with_service_unavailable() {
+    let route = get_slow_report();
+
+    thread::scope(|scope| {
+        let running = scope.spawn(|| route.get_response(create_request("/reports/slow", 300)));
+        while route.bulkhead.as_ref().unwrap().running() == 0 {
+            thread::yield_now();
+        }
+
+        let rejected = route.get_response(create_request("/reports/slow", 0));
+
+        assert_eq!(Status::ServiceUnavailable, rejected.status);
+        assert_eq!(Some("1"), rejected.get_header("Retry-After"));
+        assert_eq!(Status::Ok, running.join().unwrap().status);
+    });
+}
+
+#[test]
+fn route_with_queue_should_serve_requests_once_there_is_room() {
+    let route = get_queued_report();
+
+    thread::scope(|scope| {
+        let running = scope.spawn(|| route.get_response(create_request("/reports/queued", 100)));
+        while route.bulkhead.as_ref().unwrap().running() == 0 {
+            thread::yield_now();
+        }
+
+        let queued = route.get_response(create_request("/reports/queued", 0));
+
+        assert_eq!(Status::Ok, queued.status);
+        assert_eq!(Status::Ok, running.join().unwrap().status);
+    });
+}
@@ -0,0 +1,60 @@
+use minimal_api::server::chaos::{Chaos, ChaosRule, Fault};
+use std::time::Duration;
+
+#[test]
+fn faults_should_inject_every_fault_at_full_rate() {
+    let chaos = Chaos::new().with_rule(
+        ChaosRule::new("/users")
+            .with_latency(1.0, Duration::from_millis(250))
+            .with_errors(1.0),
+    );
+
+    assert_eq!(
+        vec![
+            Fault::Latency(Duration::from_millis(250)),
+            Fault::ServerError
+        ],
+        chaos.faults("/users/1")
+    );
+    assert_eq!(2, chaos.injected());
+}
+
+#[test]
+fn faults_should_prefer_dropping_the_connection_over_an_error() {
+    let chaos = Chaos::new().with_rule(ChaosRule::new("/").with_errors(1.0).with_drops(1.0));
+
+    assert_eq!(vec![Fault::DropConnection], chaos.faults("/anything"));
+}
+
+#[test]
+fn faults_should_only_apply_to_matching_routes() {
+    let chaos = Chaos::new().with_rule(ChaosRule::new("/users/").with_errors(1.0));
+
+    assert!(chaos.faults("/usersettings").is_empty());
+    assert!(chaos.faults("/version").is_empty());
+    assert_eq!(vec![Fault::ServerError], chaos.faults("/users"));
+}
+
+#[test]
+fn faults_should_inject_nothing_at_zero_rate() {
+    let chaos = Chaos::new().with_rule(
+        ChaosRule::new("/")
+            .with_latency(0.0, Duration::from_secs(1))
+            .with_errors(-1.0)
+            .with_drops(0.0),
+    );
+
+    for _ in 0..100 {
+        assert!(chaos.faults("/user/1").is_empty());
+    }
+    assert_eq!(0, chaos.injected());
+}
+
+#[test]
+fn faults_should_inject_roughly_at_the_given_rate() {
+    let chaos = Chaos::new().with_rule(ChaosRule::new("/").with_errors(0.5));
+
+    let errors = (0..2000).filter(|_| !chaos.faults("/").is_empty()).count();
+
+    assert!((700..1300).contains(&errors), "{errors} errors out of 2000");
+}
@@ -0,0 +1,57 @@
+use http::{charset::Charset, response::Response, status::Status};
+
+// =============
+// charset tests
+// =============
+#[test]
+fn from_content_type_should_default_to_utf8() {
+    assert_eq!(Ok(Charset::Utf8), Charset::from_content_type(None));
+    assert_eq!(
+        Ok(Charset::Utf8),
+        Charset::from_content_type(Some("application/json"))
+    );
+}
+
+#[test]
+fn from_content_type_should_read_the_charset_parameter() {
+    assert_eq!(
+        Ok(Charset::Latin1),
+        Charset::from_content_type(Some("text/plain; charset=ISO-8859-1"))
+    );
+    assert_eq!(
+        Ok(Charset::Utf8),
+        Charset::from_content_type(Some("application/json;charset=\"UTF-8\""))
+    );
+    assert_eq!(
+        Ok(Charset::Ascii),
+        Charset::from_content_type(Some("text/plain; format=flowed; charset=us-ascii"))
+    );
+}
+
+#[test]
+fn from_content_type_should_reject_unknown_charsets() {
+    assert!(Charset::from_content_type(Some("text/plain; charset=shift_jis")).is_err());
+}
+
+#[test]
+fn decode_should_map_latin1_bytes_to_characters() {
+    assert_eq!(Ok("josé".to_string()), Charset::Latin1.decode(b"jos\xe9"));
+}
+
+#[test]
+fn decode_should_reject_invalid_bytes() {
+    assert!(Charset::Utf8.decode(b"jos\xe9").is_err());
+    assert!(Charset::Ascii.decode("josé".as_bytes()).is_err());
+    assert_eq!(
+        Ok("josé".to_string()),
+        Charset::Utf8.decode("josé".as_bytes())
+    );
+}
+
+#[test]
+fn unsupported_media_type_should_have_a_415_status() {
+    let response = Response::unsupported_media_type();
+
+    assert_eq!(Status::UnsupportedMediaType, response.status);
+    assert_eq!(415, response.status.code());
+}
@@ -0,0 +1,177 @@
+use http::{chunked::ChunkedWriter, compression::Encoding, response::Response, status::Status};
+
+#[test]
+fn chunked_writer_should_stream_chunks_and_trailers() {
+    let mut response = ChunkedWriter::new(Vec::new(), Status::Ok, &["X-Checksum"]).unwrap();
+
+    response.write_chunk(b"hello ").unwrap();
+    response.write_chunk(b"").unwrap();
+    response.write_chunk(b"world").unwrap();
+    let result = response.finish(&[("X-Checksum", "abc123")]).unwrap();
+
+    assert_eq!(
+        concat!(
+            "HTTP/1.1 200 OK\r\n",
+            "Transfer-Encoding: chunked\r\n",
+            "Content-Type: application/json; charset=utf-8\r\n",
+            "Trailer: X-Checksum\r\n",
+            "\r\n",
+            "6\r\nhello \r\n",
+            "5\r\nworld\r\n",
+            "0\r\n",
+            "X-Checksum: abc123\r\n",
+            "\r\n"
+        ),
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn chunked_writer_should_not_advertise_trailers_without_any() {
+    let response = ChunkedWriter::new(Vec::new(), Status::Ok, &[]).unwrap();
+
+    let result = String::from_utf8(response.finish(&[]).unwrap()).unwrap();
+
+    assert!(!result.contains("Trailer:"));
+    assert!(result.ends_with("\r\n\r\n0\r\n\r\n"));
+}
+
+#[test]
+fn chunked_writer_should_reject_headers_and_trailers_with_line_breaks() {
+    let injected_type = ChunkedWriter::with_content_type(
+        Vec::new(),
+        Status::Ok,
+        "text/plain\r\nSet-Cookie: session=stolen",
+        &[],
+    );
+    let injected_name = ChunkedWriter::new(Vec::new(), Status::Ok, &["X-Checksum\r\nX-Evil"]);
+    let response = ChunkedWriter::new(Vec::new(), Status::Ok, &["X-Checksum"]).unwrap();
+    let injected_trailer = response.finish(&[("X-Checksum", "abc\r\n\r\nHTTP/1.1 200 OK")]);
+
+    assert!(injected_type.is_err());
+    assert!(injected_name.is_err());
+    assert!(injected_trailer.is_err());
+}
+
+#[test]
+fn chunked_writer_should_reject_trailers_that_were_not_advertised() {
+    let response = ChunkedWriter::new(Vec::new(), Status::Ok, &["X-Checksum"]).unwrap();
+
+    let result = response.finish(&[("X-Elapsed-Ms", "12")]);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn chunked_writer_should_stream_csv_one_chunk_per_row() {
+    let mut response = ChunkedWriter::csv(Vec::new(), Status::Ok).unwrap();
+
+    response
+        .write_csv([["id", "name"], ["1", "evan, jr"]])
+        .unwrap();
+    let result = String::from_utf8(response.finish(&[]).unwrap()).unwrap();
+
+    assert!(result.contains("Content-Type: text/csv; charset=utf-8\r\n"));
+    assert!(result.ends_with("9\r\nid,name\r\n\r\nE\r\n1,\"evan, jr\"\r\n\r\n0\r\n\r\n"));
+}
+
+#[test]
+fn chunked_writer_should_stream_ndjson_one_chunk_per_item() {
+    let mut response = ChunkedWriter::ndjson(Vec::new(), Status::Ok).unwrap();
+
+    response.write_ndjson([true, false]).unwrap();
+    let result = String::from_utf8(response.finish(&[]).unwrap()).unwrap();
+
+    assert!(result.contains("Content-Type: application/x-ndjson; charset=utf-8\r\n"));
+    assert!(result.ends_with("5\r\ntrue\n\r\n6\r\nfalse\n\r\n0\r\n\r\n"));
+}
+
+fn send_streamed(response: &Response) -> String {
+    let mut sent = response.to_bytes(&Encoding::Brotli);
+    response.write_body_stream(&mut sent).unwrap();
+    String::from_utf8(sent).unwrap()
+}
+
+#[test]
+fn stream_response_should_send_its_body_as_chunks_after_the_head() {
+    let response = Response::stream(Status::Ok, "text/plain", |writer| {
+        writer.write_chunk(b"hello ")?;
+        writer.write_chunk(b"world")
+    });
+
+    let result = send_streamed(&response);
+
+    assert!(result.starts_with("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n"));
+    assert!(result.contains("Content-Type: text/plain\r\n"));
+    assert!(!result.contains("Content-Length"));
+    assert!(!result.contains("Content-Encoding"));
+    assert!(result.ends_with("\r\n\r\n6\r\nhello \r\n5\r\nworld\r\n0\r\n\r\n"));
+}
+
+#[test]
+fn csv_stream_response_should_send_one_row_per_chunk() {
+    let response = Response::csv_stream([["id", "total"], ["1", "9.99"]]);
+
+    let result = send_streamed(&response);
+
+    assert!(result.contains("Content-Type: text/csv"));
+    assert!(result.ends_with("\r\n\r\nA\r\nid,total\r\n\r\n8\r\n1,9.99\r\n\r\n0\r\n\r\n"));
+}
+
+#[test]
+fn ndjson_stream_response_should_send_one_item_per_chunk() {
+    let response = Response::ndjson_stream(vec!["a".to_string(), "b".to_string()]);
+
+    let result = send_streamed(&response);
+
+    assert!(result.contains("Content-Type: application/x-ndjson"));
+    assert!(result.ends_with("\r\n\r\n4\r\n\"a\"\n\r\n4\r\n\"b\"\n\r\n0\r\n\r\n"));
+}
+
+#[test]
+fn stream_response_should_only_write_its_body_once() {
+    let response = Response::stream(Status::Ok, "text/plain", |writer| {
+        writer.write_chunk(b"once")
+    });
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+
+    response.clone().write_body_stream(&mut first).unwrap();
+    response.write_body_stream(&mut second).unwrap();
+
+    assert_eq!(b"4\r\nonce\r\n0\r\n\r\n".to_vec(), first);
+    assert!(second.is_empty());
+}
+
+#[test]
+fn stream_response_without_body_should_not_write_chunks() {
+    let response = Response::stream(Status::Ok, "text/plain", |writer| {
+        writer.write_chunk(b"hidden")
+    })
+    .without_body();
+
+    let result = send_streamed(&response);
+
+    assert!(result.ends_with("\r\n\r\n"));
+    assert!(!result.contains("hidden"));
+}
+
+#[test]
+fn buffered_stream_response_should_hold_the_whole_body() {
+    let response = Response::csv_stream([["id"], ["1"]]).buffered();
+
+    assert!(!response.is_streamed());
+    assert_eq!("id\r\n1\r\n", response.content);
+    assert!(response.to_string().contains("Content-Length: 7\r\n"));
+    assert!(!response.to_string().contains("Transfer-Encoding"));
+}
+
+#[test]
+fn buffered_stream_response_should_be_server_error_when_its_body_fails() {
+    let response = Response::stream(Status::Ok, "text/plain", |_| {
+        Err(std::io::Error::other("lost the cursor"))
+    })
+    .buffered();
+
+    assert_eq!(Status::ServerError, response.status);
+}
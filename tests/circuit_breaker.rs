@@ -0,0 +1,89 @@
+use http::circuit_breaker::{CircuitBreaker, CircuitState};
+use std::time::Duration;
+
+fn create_tripping_breaker(open_duration: Duration) -> CircuitBreaker {
+    CircuitBreaker::new()
+        .with_failure_rate(0.5)
+        .with_minimum_calls(4)
+        .with_window_size(4)
+        .with_open_duration(open_duration)
+}
+
+#[test]
+fn record_failure_should_open_once_failure_rate_is_reached() {
+    let circuit_breaker = create_tripping_breaker(Duration::from_secs(60));
+
+    circuit_breaker.record_success();
+    circuit_breaker.record_failure();
+    circuit_breaker.record_success();
+    let state_below_threshold = circuit_breaker.state();
+    circuit_breaker.record_failure();
+
+    assert_eq!(CircuitState::Closed, state_below_threshold);
+    assert_eq!(CircuitState::Open, circuit_breaker.state());
+    assert!(!circuit_breaker.allow_request());
+    assert!(circuit_breaker.retry_after() > Duration::ZERO);
+}
+
+#[test]
+fn record_failure_should_stay_closed_below_minimum_calls() {
+    let circuit_breaker = create_tripping_breaker(Duration::from_secs(60));
+
+    circuit_breaker.record_failure();
+    circuit_breaker.record_failure();
+    circuit_breaker.record_failure();
+
+    assert_eq!(CircuitState::Closed, circuit_breaker.state());
+}
+
+#[test]
+fn allow_request_should_let_single_trial_through_after_open_duration() {
+    let circuit_breaker = create_tripping_breaker(Duration::from_millis(20));
+    for _ in 0..4 {
+        circuit_breaker.record_failure();
+    }
+
+    std::thread::sleep(Duration::from_millis(30));
+    let is_trial_allowed = circuit_breaker.allow_request();
+    let is_second_call_allowed = circuit_breaker.allow_request();
+
+    assert!(is_trial_allowed);
+    assert!(!is_second_call_allowed);
+    assert_eq!(CircuitState::HalfOpen, circuit_breaker.state());
+}
+
+#[test]
+fn half_open_trial_should_close_on_success_and_reopen_on_failure() {
+    let closing = create_tripping_breaker(Duration::ZERO);
+    let reopening = create_tripping_breaker(Duration::from_millis(20));
+    for _ in 0..4 {
+        closing.record_failure();
+        reopening.record_failure();
+    }
+    std::thread::sleep(Duration::from_millis(30));
+
+    closing.allow_request();
+    closing.record_success();
+    reopening.allow_request();
+    reopening.record_failure();
+
+    assert_eq!(CircuitState::Closed, closing.state());
+    assert_eq!(CircuitState::Open, reopening.state());
+}
+
+#[test]
+fn call_should_fail_fast_without_running_call_when_open() {
+    let circuit_breaker = create_tripping_breaker(Duration::from_secs(60));
+    for _ in 0..4 {
+        let _ = circuit_breaker.call(|| Err::<(), _>("refused".to_string()));
+    }
+    let mut was_called = false;
+
+    let result = circuit_breaker.call(|| {
+        was_called = true;
+        Ok(())
+    });
+
+    assert!(result.is_err());
+    assert!(!was_called);
+}
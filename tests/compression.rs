@@ -0,0 +1,175 @@
+use http::{
+    compression::{self, CompressionPolicy, Encoding},
+    response::Response,
+};
+
+#[test]
+fn negotiate_should_return_identity_without_accept_encoding() {
+    assert_eq!(Encoding::Identity, compression::negotiate(None));
+    assert_eq!(Encoding::Identity, compression::negotiate(Some("compress")));
+}
+
+#[test]
+fn negotiate_should_return_identity_when_every_coding_is_refused() {
+    let result = compression::negotiate(Some("br;q=0, gzip;q=0, *;q=1"));
+
+    assert_eq!(Encoding::Identity, result);
+}
+
+#[test]
+fn to_bytes_should_send_identity_response_as_is() {
+    let response = Response::ok("\"ok\"", false);
+
+    let result = response.to_bytes(&Encoding::Identity);
+
+    assert_eq!(response.to_string().into_bytes(), result);
+}
+
+#[test]
+fn compression_policy_should_skip_bodies_under_the_minimum_size() {
+    let policy = CompressionPolicy::new().with_min_bytes(1024);
+
+    assert_eq!(
+        Encoding::Identity,
+        policy.apply(Encoding::Brotli, "/users", None, 1023)
+    );
+    assert_eq!(
+        Encoding::Brotli,
+        policy.apply(Encoding::Brotli, "/users", None, 1024)
+    );
+}
+
+#[test]
+fn compression_policy_should_skip_excluded_content_types() {
+    let policy = CompressionPolicy::new().with_excluded_content_types(&["video/*", "Image/PNG"]);
+
+    assert_eq!(
+        Encoding::Identity,
+        policy.apply(Encoding::Brotli, "/logo", Some("image/png"), 2048)
+    );
+    assert_eq!(
+        Encoding::Identity,
+        policy.apply(
+            Encoding::Brotli,
+            "/clip",
+            Some("video/mp4; codecs=avc1"),
+            2048
+        )
+    );
+    assert_eq!(
+        Encoding::Brotli,
+        policy.apply(
+            Encoding::Brotli,
+            "/logo",
+            Some("image/svg+xml; charset=utf-8"),
+            2048
+        )
+    );
+}
+
+#[test]
+fn compression_policy_should_skip_excluded_paths_and_below() {
+    let policy = CompressionPolicy::new().with_excluded_paths(&["/events/"]);
+
+    assert_eq!(
+        Encoding::Identity,
+        policy.apply(Encoding::Brotli, "/events", None, 2048)
+    );
+    assert_eq!(
+        Encoding::Identity,
+        policy.apply(Encoding::Brotli, "/events/orders", None, 2048)
+    );
+    assert_eq!(
+        Encoding::Brotli,
+        policy.apply(Encoding::Brotli, "/eventsource", None, 2048)
+    );
+}
+
+#[cfg(not(any(feature = "brotli", feature = "gzip")))]
+#[test]
+fn negotiate_should_ignore_brotli_and_gzip_without_features() {
+    let result = compression::negotiate(Some("br, gzip;q=0.8"));
+
+    assert_eq!(Encoding::Identity, result);
+}
+
+#[cfg(all(feature = "brotli", not(feature = "gzip")))]
+#[test]
+fn negotiate_should_pick_brotli_by_q_value_with_brotli_feature() {
+    assert_eq!(
+        Encoding::Brotli,
+        compression::negotiate(Some("gzip, BR;q=0.5"))
+    );
+    assert_eq!(Encoding::Brotli, compression::negotiate(Some("*")));
+}
+
+#[cfg(feature = "brotli")]
+#[test]
+fn to_bytes_should_compress_body_and_add_headers_with_brotli_feature() {
+    let response = Response::ok(&"\"repeated\"".repeat(100), false);
+
+    let result = response.to_bytes(&Encoding::Brotli);
+
+    let head_end = result.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+    let head = String::from_utf8_lossy(&result[..head_end]);
+    assert!(head.contains("Content-Encoding: br\r\n"));
+    assert!(head.contains("Vary: Accept-Encoding\r\n"));
+    assert!(head.contains(&format!("Content-Length: {}\r\n", result.len() - head_end)));
+    assert!(result.len() < response.to_string().len());
+}
+
+#[cfg(feature = "brotli")]
+#[test]
+fn to_bytes_should_merge_accept_encoding_into_an_existing_vary_with_brotli_feature() {
+    let response = Response::ok(&"\"repeated\"".repeat(100), false).vary_on("Accept");
+
+    let result = response.to_bytes(&Encoding::Brotli);
+
+    let head = String::from_utf8_lossy(&result);
+    assert!(head.contains("Vary: Accept, Accept-Encoding\r\n"));
+    assert_eq!(1, head.matches("Vary:").count());
+    assert_eq!(Some("Accept"), response.get_header("Vary"));
+}
+
+#[test]
+fn negotiate_should_prefer_the_highest_q_value_over_server_preference() {
+    let result = compression::negotiate(Some("gzip;q=1, br;q=0.5"));
+
+    if cfg!(feature = "gzip") {
+        assert_eq!(Encoding::Gzip, result);
+    } else if cfg!(feature = "brotli") {
+        assert_eq!(Encoding::Brotli, result);
+    } else {
+        assert_eq!(Encoding::Identity, result);
+    }
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn negotiate_should_ignore_gzip_without_gzip_feature() {
+    let result = compression::negotiate(Some("gzip"));
+
+    assert_eq!(Encoding::Identity, result);
+}
+
+#[cfg(all(feature = "brotli", feature = "gzip"))]
+#[test]
+fn negotiate_should_prefer_brotli_when_accepted_equally_with_brotli_and_gzip_features() {
+    assert_eq!(Encoding::Brotli, compression::negotiate(Some("gzip, br")));
+    assert_eq!(Encoding::Brotli, compression::negotiate(Some("*")));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn to_bytes_should_compress_body_and_add_headers_with_gzip_feature() {
+    let response = Response::ok(&"\"repeated\"".repeat(100), false);
+
+    let result = response.to_bytes(&Encoding::Gzip);
+
+    let head_end = result.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+    let head = String::from_utf8_lossy(&result[..head_end]);
+    assert!(head.contains("Content-Encoding: gzip\r\n"));
+    assert!(head.contains(&format!("Content-Length: {}\r\n", result.len() - head_end)));
+    assert_eq!([0x1f, 0x8b], result[head_end..head_end + 2]);
+    assert!(result.len() < response.to_string().len());
+}
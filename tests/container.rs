@@ -0,0 +1,102 @@
+use minimal_api::environment::container::{
+    ContainerLimits, DEFAULT_MAX_BODY_BYTES, MIN_MAX_BODY_BYTES, WORKERS_PER_CPU,
+};
+use std::{fs, path::PathBuf};
+
+fn create_cgroup_root(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("minimal-api-cgroup-{name}"));
+    let _ = fs::remove_dir_all(&root);
+    for (file, contents) in files {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+    root
+}
+
+#[test]
+fn from_cgroup_root_should_read_cgroup_v2_limits() {
+    let root = create_cgroup_root(
+        "v2",
+        &[
+            ("cpu.max", "150000 100000\n"),
+            ("memory.max", "536870912\n"),
+        ],
+    );
+
+    let result = ContainerLimits::from_cgroup_root(root);
+
+    assert_eq!(Some(1.5), result.cpus);
+    assert_eq!(Some(536870912), result.memory_bytes);
+}
+
+#[test]
+fn from_cgroup_root_should_read_cgroup_v1_limits() {
+    let root = create_cgroup_root(
+        "v1",
+        &[
+            ("cpu/cpu.cfs_quota_us", "200000\n"),
+            ("cpu/cpu.cfs_period_us", "100000\n"),
+            ("memory/memory.limit_in_bytes", "9223372036854771712\n"),
+        ],
+    );
+
+    let result = ContainerLimits::from_cgroup_root(root);
+
+    assert_eq!(Some(2.0), result.cpus);
+    assert_eq!(None, result.memory_bytes);
+}
+
+#[test]
+fn from_cgroup_root_should_have_no_limits_when_unlimited_or_missing() {
+    let unlimited = create_cgroup_root(
+        "unlimited",
+        &[("cpu.max", "max 100000\n"), ("memory.max", "max\n")],
+    );
+    let missing = create_cgroup_root("missing", &[]);
+
+    assert_eq!(
+        ContainerLimits::default(),
+        ContainerLimits::from_cgroup_root(unlimited)
+    );
+    assert_eq!(
+        ContainerLimits::default(),
+        ContainerLimits::from_cgroup_root(missing)
+    );
+}
+
+#[test]
+fn default_workers_should_round_fractional_cpu_limit_up() {
+    let limits = ContainerLimits {
+        cpus: Some(0.5),
+        memory_bytes: None,
+    };
+
+    assert_eq!(1, limits.available_cpus());
+    assert_eq!(WORKERS_PER_CPU, limits.default_workers());
+}
+
+#[test]
+fn default_max_body_bytes_should_share_quarter_of_memory_between_workers() {
+    let limited = |memory_bytes| ContainerLimits {
+        cpus: None,
+        memory_bytes: Some(memory_bytes),
+    };
+
+    assert_eq!(
+        1024 * 1024,
+        limited(64 * 1024 * 1024).default_max_body_bytes(16)
+    );
+    assert_eq!(
+        MIN_MAX_BODY_BYTES,
+        limited(1024 * 1024).default_max_body_bytes(16)
+    );
+    assert_eq!(
+        DEFAULT_MAX_BODY_BYTES,
+        limited(1 << 40).default_max_body_bytes(4)
+    );
+    assert_eq!(
+        DEFAULT_MAX_BODY_BYTES,
+        ContainerLimits::default().default_max_body_bytes(4)
+    );
+}
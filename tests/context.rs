@@ -0,0 +1,132 @@
+use http::{
+    context::{Context, Deadline, SharedState},
+    extensions::TypeMap,
+    methods::{GET, POST},
+    request::Request,
+    router::Router,
+};
+use http_attributes::{http_get, http_post};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// ====================
+// common context types
+// ====================
+#[derive(Debug, PartialEq)]
+struct Greeting(&'static str);
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/greet/{name}", generate_tests)]
+fn greet(ctx: &Context) -> String {
+    let greeting = ctx
+        .state::<Greeting>()
+        .map_or("hello", |greeting| greeting.0);
+    format!(
+        "\"{greeting} {}{}\"",
+        ctx.path_param("name").unwrap_or_default(),
+        ctx.query_param("punctuation").unwrap_or_default()
+    )
+}
+
+#[http_post("/notes/{id}")]
+fn update_note(id: u32, note: String, ctx: &Context) -> String {
+    format!("\"{} {id}: {note}\"", ctx.scope)
+}
+
+fn create_request(path: &str, path_params: &[(&str, &str)]) -> Request {
+    Request::new(
+        path,
+        GET,
+        None,
+        path_params
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+// =============
+// context tests
+// =============
+#[test]
+fn context_should_get_path_and_query_params() {
+    let context = Context::new(
+        create_request("/greet/evan?punctuation=!", &[("name", "evan")]),
+        "GET /greet/{name}",
+    );
+
+    assert_eq!(Some("evan"), context.path_param("name"));
+    assert_eq!(Some("!"), context.query_param("punctuation"));
+    assert_eq!(None, context.path_param("missing"));
+}
+
+#[test]
+fn context_should_get_shared_state() {
+    let mut state = TypeMap::new();
+    state.insert(Greeting("howdy"));
+    let request = create_request("/", &[]).with_extension(SharedState(Arc::new(state)));
+
+    let context = Context::new(request, "GET /");
+
+    assert_eq!(Some(&Greeting("howdy")), context.state::<Greeting>());
+    assert_eq!(None, context.state::<u32>());
+}
+
+#[test]
+fn context_should_not_have_a_deadline_without_a_request_timeout() {
+    let context = Context::new(create_request("/", &[]), "GET /");
+
+    assert_eq!(None, context.deadline());
+    assert_eq!(None, context.time_remaining());
+}
+
+#[test]
+fn context_time_remaining_should_saturate_past_the_deadline() {
+    let deadline = Instant::now();
+    let request = create_request("/", &[]).with_extension(Deadline(deadline));
+
+    let context = Context::new(request, "GET /");
+
+    assert_eq!(Some(deadline), context.deadline());
+    assert_eq!(Some(Duration::ZERO), context.time_remaining());
+}
+
+#[test]
+fn router_with_state_should_share_state_with_handlers() {
+    let router = Router::new(vec![greet()]).with_state(Greeting("howdy"));
+    let (route, path_params) = router.resolve(GET, "/greet/evan").unwrap();
+    let request = Request::new("/greet/evan?punctuation=!", GET, None, path_params)
+        .with_extension(SharedState(router.state.clone()));
+
+    let response = (route.handler)(request);
+
+    assert!(response.content.contains("howdy evan!"));
+}
+
+#[test]
+fn handler_should_get_context_without_a_param() {
+    let route = greet();
+
+    assert!(route.spec.params.is_empty());
+}
+
+#[test]
+fn handler_should_get_context_after_other_arguments() {
+    let route = update_note();
+    let request = Request::new(
+        "/notes/7",
+        POST,
+        Some("buy milk".to_string()),
+        HashMap::from([("id".to_string(), "7".to_string())]),
+    );
+
+    let response = (route.handler)(request);
+
+    assert_eq!(2, route.spec.params.len());
+    assert!(response.content.contains("POST /notes/{id} 7: buy milk"));
+}
@@ -0,0 +1,136 @@
+use http::{
+    methods::{GET, Method, POST, PUT},
+    registry,
+    request::Request,
+    route::Route,
+    status::Status,
+};
+use http_attributes::{controller, http_get, http_post, http_route, require_header};
+use std::collections::HashMap;
+
+// =================
+// endpoints to test
+// =================
+struct Users;
+
+#[controller("/controller/users")]
+impl Users {
+    const GREETING: &str = "hello";
+
+    /// Gets a single user.
+    #[http_get("/{id}")]
+    fn get_user(id: u32) -> String {
+        format!("\"{} user {id}\"", Self::GREETING)
+    }
+
+    #[http_post("/")]
+    fn create_user(name: String) -> String {
+        format!("\"created {name}\"")
+    }
+
+    #[require_header("X-Internal", "true")]
+    #[http_route(path = "/{id}/role", methods = ["GET", "PUT"])]
+    fn role(id: u32) -> String {
+        format!("\"role of {id}\"")
+    }
+}
+
+struct Health;
+
+#[controller("/controller/")]
+impl Health {
+    #[http_get("/")]
+    fn get_health() -> String {
+        "\"healthy\"".to_string()
+    }
+}
+
+fn create_request(path: &str, method: Method, body: Option<&str>, id: Option<&str>) -> Request {
+    let path_params = id
+        .map(|id| HashMap::from([("id".to_string(), id.to_string())]))
+        .unwrap_or_default();
+    Request::new(path, method, body.map(str::to_string), path_params)
+}
+
+// ================
+// controller tests
+// ================
+#[test]
+fn routes_should_collect_every_route_of_the_controller_with_the_prefix() {
+    let routes = Users::routes();
+
+    let keys: Vec<(&str, &str)> = routes
+        .iter()
+        .map(|route| {
+            (
+                route.spec.method.as_str(),
+                route.spec.request_pattern.as_str(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        vec![
+            ("GET", "/controller/users/{id}"),
+            ("POST", "/controller/users"),
+            ("GET", "/controller/users/{id}/role"),
+            ("PUT", "/controller/users/{id}/role"),
+        ],
+        keys
+    );
+}
+
+#[test]
+fn controller_methods_should_return_their_route() {
+    let route: Route = Users::get_user();
+
+    let response = route.get_response(create_request("/controller/users/7", GET, None, Some("7")));
+
+    assert_eq!("/controller/users/{id}", route.spec.request_pattern);
+    assert_eq!(
+        Some("Gets a single user."),
+        route.spec.description.as_deref()
+    );
+    assert_eq!(Status::Ok, response.status);
+    assert!(response.content.contains("hello user 7"));
+}
+
+#[test]
+fn controller_routes_should_keep_their_arguments_and_guards() {
+    let routes = Users::routes();
+
+    let created = routes[1].get_response(create_request(
+        "/controller/users",
+        POST,
+        Some("evan"),
+        None,
+    ));
+    let forbidden = routes[3].get_response(create_request(
+        "/controller/users/7/role",
+        PUT,
+        None,
+        Some("7"),
+    ));
+
+    assert!(created.content.contains("created evan"));
+    assert_eq!(Status::Forbidden, forbidden.status);
+}
+
+#[test]
+fn controller_should_join_a_trailing_slash_prefix_with_a_root_path() {
+    let routes = Health::routes();
+
+    assert_eq!(1, routes.len());
+    assert_eq!("/controller", routes[0].spec.request_pattern);
+}
+
+#[test]
+fn controller_routes_should_be_registered() {
+    let routes = registry::collect_routes();
+
+    assert!(
+        routes
+            .iter()
+            .any(|route| route.spec.request_pattern == "/controller/users/{id}")
+    );
+}
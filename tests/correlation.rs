@@ -0,0 +1,138 @@
+use http::{
+    client::{Client, ClientRequest},
+    correlation::{Correlation, DEFAULT_PROPAGATED_HEADERS, REQUEST_ID_HEADER},
+    methods::GET,
+    request::Request,
+};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+};
+
+// ==================
+// common test values
+// ==================
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+fn create_request(headers: &[(&str, &str)]) -> Request {
+    Request::new("/orders", GET, None, HashMap::new()).with_headers(
+        headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+/// sends `request` to an upstream that answers `204 NO CONTENT`, and returns the
+/// head of the request the upstream received
+fn send_to_upstream(request: ClientRequest) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/upstream", listener.local_addr().unwrap());
+    let upstream = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut head = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+            head.push_str(&line);
+        }
+        stream
+            .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+            .unwrap();
+        head
+    });
+
+    Client::new()
+        .send(&ClientRequest { url, ..request })
+        .unwrap();
+    upstream.join().unwrap()
+}
+
+// =================
+// correlation tests
+// =================
+#[test]
+fn from_request_should_keep_allowlisted_headers() {
+    let request = create_request(&[
+        ("x-request-id", "abc123"),
+        ("Traceparent", TRACEPARENT),
+        ("Authorization", "Bearer secret"),
+    ]);
+
+    let correlation = Correlation::from_request(&request, &DEFAULT_PROPAGATED_HEADERS);
+
+    assert_eq!(
+        vec![
+            (REQUEST_ID_HEADER.to_string(), "abc123".to_string()),
+            ("traceparent".to_string(), TRACEPARENT.to_string()),
+        ],
+        correlation.headers
+    );
+}
+
+#[test]
+fn from_request_should_create_a_missing_request_id() {
+    let correlation = Correlation::from_request(&create_request(&[]), &DEFAULT_PROPAGATED_HEADERS);
+
+    let request_id = correlation.request_id().unwrap();
+
+    assert_eq!(32, request_id.len());
+    assert!(
+        request_id
+            .chars()
+            .all(|character| character.is_ascii_hexdigit())
+    );
+    assert_eq!(
+        None,
+        Correlation::from_request(&create_request(&[]), &["traceparent"]).request_id()
+    );
+}
+
+#[test]
+fn correlation_should_only_be_current_until_its_guard_is_dropped() {
+    let outer = Correlation::from_request(
+        &create_request(&[("X-Request-Id", "outer")]),
+        &[REQUEST_ID_HEADER],
+    );
+    let inner = Correlation::from_request(
+        &create_request(&[("X-Request-Id", "inner")]),
+        &[REQUEST_ID_HEADER],
+    );
+
+    let outer_guard = outer.clone().enter();
+    {
+        let _inner_guard = inner.clone().enter();
+        assert_eq!(Some(inner), Correlation::current());
+    }
+    assert_eq!(Some(outer), Correlation::current());
+    drop(outer_guard);
+
+    assert_eq!(None, Correlation::current());
+}
+
+#[test]
+fn client_should_propagate_the_current_correlation() {
+    let request = create_request(&[("X-Request-Id", "abc123"), ("traceparent", TRACEPARENT)]);
+    let _correlation = Correlation::from_request(&request, &DEFAULT_PROPAGATED_HEADERS).enter();
+
+    let head = send_to_upstream(
+        ClientRequest::new("GET", "http://unused").with_header("X-Request-Id", "explicit"),
+    );
+
+    assert!(head.contains(&format!("traceparent: {TRACEPARENT}\r\n")));
+    assert!(head.contains("X-Request-Id: explicit\r\n"));
+    assert!(!head.contains("abc123"));
+}
+
+#[test]
+fn client_should_not_add_headers_outside_of_a_request() {
+    let head = send_to_upstream(ClientRequest::new("GET", "http://unused"));
+
+    assert!(!head.to_lowercase().contains("x-request-id"));
+}
@@ -0,0 +1,107 @@
+use http::{
+    error::HttpError,
+    json::Json,
+    response::Response,
+    route::Route,
+    validation::{ParamLocation, RouteParam},
+};
+use http_attributes::{PathParams, http_get, http_post};
+use minimal_api::server::docs;
+
+// =================
+// endpoints to test
+// =================
+#[derive(PathParams)]
+struct OrgRepo {
+    /// The organization that owns the repository.
+    org: String,
+    repo: String,
+}
+
+/// Gets a single repository.
+///
+/// Archived repositories are included.
+#[http_get("/orgs/{org}/repos/{repo}")]
+fn get_repo(#[path] params: OrgRepo) -> String {
+    format!("{}/{}", params.org, params.repo)
+}
+
+/// Searches for users by name.
+#[http_get("/users")]
+fn search_users(
+    /// The name, or the start of the name, to search for.
+    name: String,
+    limit: u32,
+) -> String {
+    format!("{name} {limit}")
+}
+
+/// Creates a repository.
+#[http_post("/orgs/{org}/repos", status = 201)]
+fn create_repo(org: String, stars: u32) -> Result<Json, HttpError> {
+    Ok(Json::Array(vec![
+        Json::String(org),
+        Json::Number(f64::from(stars)),
+    ]))
+}
+
+#[test]
+fn render_docs_should_list_routes_with_escaped_curl_examples() {
+    let routes = vec![
+        Route::get("/user/{id}", Box::new(|_| Response::ok("\"user\"", false)))
+            .with_params(vec![RouteParam::new("id", ParamLocation::Path, "i32")])
+            .spec,
+        Route::get("/version", Box::new(|_| Response::ok("\"1\"", false))).spec,
+    ];
+
+    let page = docs::render_docs(&routes, "http://localhost:8080");
+
+    assert!(page.contains("<h2><code>GET /user/{id}</code></h2>"));
+    assert!(page.contains("<li><code>id</code> (path, <code>i32</code>)</li>"));
+    assert!(page.contains("curl &#39;http://localhost:8080/user/&lt;id&gt;&#39;"));
+    assert!(page.contains("<p>No parameters.</p>"));
+}
+
+#[test]
+fn http_attributes_should_capture_doc_comments() {
+    let repo_spec = get_repo().spec;
+    let users_spec = search_users().spec;
+
+    assert_eq!(
+        Some("Gets a single repository.\n\nArchived repositories are included."),
+        repo_spec.description.as_deref()
+    );
+    assert_eq!(
+        Some("The organization that owns the repository."),
+        repo_spec.params[0].description.as_deref()
+    );
+    assert_eq!(None, repo_spec.params[1].description);
+    assert_eq!(
+        Some("The name, or the start of the name, to search for."),
+        users_spec.params[0].description.as_deref()
+    );
+    assert_eq!(None, users_spec.params[1].description);
+}
+
+#[test]
+fn render_docs_should_show_route_and_param_descriptions() {
+    let page = docs::render_docs(&[search_users().spec], "http://localhost:8080");
+
+    assert!(page.contains("<p>Searches for users by name.</p>"));
+    assert!(page.contains(
+        "<li><code>name</code> (query, <code>String</code>): The name, or the start of the name, to search for.</li>"
+    ));
+}
+
+#[test]
+fn http_attributes_should_capture_body_and_response_types() {
+    let repo_spec = get_repo().spec;
+    let create_spec = create_repo().spec;
+
+    assert_eq!(None, repo_spec.body_type);
+    assert_eq!(Some("String".to_string()), repo_spec.response_type);
+    assert_eq!(Some(200), repo_spec.response_status);
+    assert_eq!(Some("u32".to_string()), create_spec.body_type);
+    assert_eq!(Some("Json".to_string()), create_spec.response_type);
+    assert_eq!(Some(201), create_spec.response_status);
+}
@@ -0,0 +1,140 @@
+use http::{
+    error::{ErrorMapper, HttpError, IntoResponse},
+    request::Request,
+    response::Response,
+    status::Status,
+};
+use http_attributes::{http_get, http_raw_get};
+use std::collections::HashMap;
+
+// =================
+// common error type
+// =================
+enum AppError {
+    UserNotFound(String),
+    DatabaseDown,
+}
+
+impl ErrorMapper for AppError {
+    fn status(&self) -> Status {
+        match self {
+            AppError::UserNotFound(_) => Status::NotFound,
+            AppError::DatabaseDown => Status::ServiceUnavailable,
+        }
+    }
+
+    fn body(&self) -> String {
+        match self {
+            AppError::UserNotFound(id) => format!("\"no user {id}\""),
+            AppError::DatabaseDown => "\"try again later\"".to_string(),
+        }
+    }
+}
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/user/{id}")]
+fn get_user(id: String) -> Result<String, AppError> {
+    match id.as_str() {
+        "1" => Ok("\"evan\"".to_string()),
+        "down" => Err(AppError::DatabaseDown),
+        _ => Err(AppError::UserNotFound(id)),
+    }
+}
+
+#[http_raw_get("/raw/user/{id}")]
+fn get_raw_user(id: String) -> Result<String, AppError> {
+    Err(AppError::UserNotFound(id))
+}
+
+#[http_get("/report/{id}")]
+fn get_report(id: u32) -> Result<String, http::error::HttpError> {
+    if id == 0 {
+        return Err(HttpError::bad_request("report ids start at 1"));
+    }
+    Err(HttpError::not_found(&format!("no report \"{id}\"")))
+}
+
+fn get_user_response(id: &str) -> Response {
+    let request = Request::new(
+        &format!("/user/{id}"),
+        http::methods::GET,
+        None,
+        HashMap::from([("id".to_string(), id.to_string())]),
+    );
+    get_user().get_response(request)
+}
+
+// ===========
+// error tests
+// ===========
+#[test]
+fn ok_result_should_be_200_ok() {
+    assert_eq!(Response::ok("\"evan\"", false), get_user_response("1"));
+}
+
+#[test]
+fn err_result_should_be_mapped_to_status_and_body() {
+    assert_eq!(
+        Response::with_status(Status::NotFound, "\"no user 7\"", false),
+        get_user_response("7")
+    );
+    assert_eq!(
+        Response::with_status(Status::ServiceUnavailable, "\"try again later\"", false),
+        get_user_response("down")
+    );
+}
+
+#[test]
+fn err_result_of_raw_route_should_be_raw() {
+    let request = Request::new(
+        "/raw/user/7",
+        http::methods::GET,
+        None,
+        HashMap::from([("id".to_string(), "7".to_string())]),
+    );
+
+    let response = get_raw_user().get_response(request);
+
+    assert_eq!(
+        Response::with_status(Status::NotFound, "\"no user 7\"", true),
+        response
+    );
+}
+
+#[test]
+fn response_error_should_be_sent_as_is() {
+    let result: Result<String, Response> = Err(Response::forbidden());
+
+    assert_eq!(Response::forbidden(), result.into_response(true));
+}
+
+#[test]
+fn http_error_should_be_mapped_to_its_status_and_escaped_message() {
+    let request = |id: &str| {
+        Request::new(
+            &format!("/report/{id}"),
+            http::methods::GET,
+            None,
+            HashMap::from([("id".to_string(), id.to_string())]),
+        )
+    };
+
+    assert_eq!(
+        Response::with_status(Status::BadRequest, "\"report ids start at 1\"", false),
+        get_report().get_response(request("0"))
+    );
+    assert_eq!(
+        Response::with_status(Status::NotFound, "\"no report \\\"7\\\"\"", false),
+        get_report().get_response(request("7"))
+    );
+}
+
+#[test]
+fn http_error_should_display_status_and_message() {
+    assert_eq!(
+        "503 SERVICE UNAVAILABLE: database is down",
+        HttpError::service_unavailable("database is down").to_string()
+    );
+}
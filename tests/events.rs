@@ -0,0 +1,90 @@
+use http::{compression::Encoding, constants::CONTENT_EVENT_STREAM};
+use minimal_api::server::events::EventBus;
+use std::{thread, time::Duration};
+
+#[test]
+fn publish_should_deliver_event_to_every_subscriber() {
+    let events = EventBus::new();
+    let first = events.subscribe();
+    let second = events.subscribe();
+
+    let result = events.publish("created".to_string());
+
+    assert_eq!(2, result);
+    assert_eq!("created", first.recv().unwrap());
+    assert_eq!("created", second.recv().unwrap());
+}
+
+#[test]
+fn publish_should_drop_subscribers_that_stopped_listening() {
+    let events = EventBus::new();
+    let listening = events.subscribe();
+    drop(events.subscribe());
+
+    let result = events.publish(1);
+
+    assert_eq!(1, result);
+    assert_eq!(1, events.subscriber_count());
+    assert_eq!(1, listening.recv().unwrap());
+}
+
+#[test]
+fn publish_should_deliver_events_across_threads() {
+    let events: &'static EventBus<u8> = Box::leak(Box::new(EventBus::new()));
+    let subscriber = events.subscribe();
+
+    thread::spawn(move || events.publish(42)).join().unwrap();
+
+    assert_eq!(42, subscriber.recv().unwrap());
+}
+
+#[test]
+fn publish_should_unsubscribe_subscribers_that_fell_behind() {
+    let events = EventBus::new().with_capacity(2);
+    let slow = events.subscribe();
+    let fast = events.subscribe();
+
+    events.publish(1);
+    fast.recv().unwrap();
+    events.publish(2);
+    fast.recv().unwrap();
+    let result = events.publish(3);
+
+    assert_eq!(1, result);
+    assert_eq!(3, fast.recv().unwrap());
+    assert_eq!(vec![1, 2], slow.iter().collect::<Vec<i32>>());
+}
+
+#[test]
+fn event_stream_should_send_published_events_as_server_sent_events() {
+    let events: &'static EventBus<String> = Box::leak(Box::new(EventBus::new()));
+    let response = events.event_stream(Duration::from_millis(200));
+    let mut sent = response.to_bytes(&Encoding::Identity);
+
+    events.publish("created".to_string());
+    events.publish("deleted".to_string());
+    response.write_body_stream(&mut sent).unwrap();
+    let result = String::from_utf8(sent).unwrap();
+
+    assert!(result.contains(&format!("Content-Type: {CONTENT_EVENT_STREAM}\r\n")));
+    assert!(result.contains("Cache-Control: no-cache\r\n"));
+    assert!(result.contains("data: \"created\"\n\n"));
+    assert!(result.contains("data: \"deleted\"\n\n"));
+    assert!(result.ends_with("0\r\n\r\n"));
+}
+
+#[test]
+fn event_stream_should_end_when_its_subscriber_falls_behind() {
+    let events: EventBus<String> = EventBus::new().with_capacity(1);
+    let response = events.event_stream(Duration::from_secs(60));
+    let mut sent = Vec::new();
+
+    events.publish("created".to_string());
+    events.publish("deleted".to_string());
+    response.write_body_stream(&mut sent).unwrap();
+    let result = String::from_utf8(sent).unwrap();
+
+    assert!(result.contains("data: \"created\"\n\n"));
+    assert!(!result.contains("deleted"));
+    assert!(result.ends_with("0\r\n\r\n"));
+}
@@ -0,0 +1,101 @@
+use http::{executor, request::Request, response::Response};
+use http_attributes::{http_get, http_post};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+// ==================
+// common test future
+// ==================
+// a future that is ready with its value once a thread it spawns wakes it
+struct Delayed {
+    value: u32,
+    state: Arc<Mutex<(bool, Option<Waker>)>>,
+}
+
+impl Delayed {
+    fn new(value: u32, delay: Duration) -> Self {
+        let state: Arc<Mutex<(bool, Option<Waker>)>> = Arc::new(Mutex::new((false, None)));
+        let thread_state = Arc::clone(&state);
+        thread::spawn(move || {
+            thread::sleep(delay);
+            let mut state = thread_state.lock().unwrap();
+            state.0 = true;
+            if let Some(waker) = state.1.take() {
+                waker.wake();
+            }
+        });
+        Delayed { value, state }
+    }
+}
+
+impl Future for Delayed {
+    type Output = u32;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<u32> {
+        let mut state = self.state.lock().unwrap();
+        if state.0 {
+            Poll::Ready(self.value)
+        } else {
+            state.1 = Some(context.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/async/{id}")]
+async fn get_async_user(id: u32) -> String {
+    let score = Delayed::new(id * 10, Duration::from_millis(10)).await;
+    format!("user {id} has {score} points")
+}
+
+#[http_post("/async/echo")]
+async fn echo(content: String) -> Result<String, http::error::HttpError> {
+    Ok(content)
+}
+
+// ==============
+// executor tests
+// ==============
+#[test]
+fn block_on_should_wait_for_future_to_be_woken() {
+    assert_eq!(
+        7,
+        executor::block_on(Delayed::new(7, Duration::from_millis(20)))
+    );
+}
+
+#[test]
+fn async_handler_should_be_driven_to_completion() {
+    let request = Request::new(
+        "/async/4",
+        http::methods::GET,
+        None,
+        HashMap::from([("id".to_string(), "4".to_string())]),
+    );
+
+    let response = get_async_user().get_response(request);
+
+    assert_eq!(Response::ok("user 4 has 40 points", false), response);
+}
+
+#[test]
+fn async_handler_should_support_result_return_types() {
+    let request = Request::new(
+        "/async/echo",
+        http::methods::POST,
+        Some("hi".to_string()),
+        HashMap::new(),
+    );
+
+    assert_eq!(Response::ok("hi", false), echo().get_response(request));
+}
@@ -0,0 +1,151 @@
+use http::{
+    form,
+    methods::{Method, POST},
+    request::Request,
+    status::Status,
+    validation::{ParamLocation, RouteParam},
+};
+use http_attributes::http_post;
+use std::collections::HashMap;
+
+// =================
+// endpoints to test
+// =================
+#[http_post("/form/signup")]
+fn signup(name: String, age: u32) -> String {
+    format!("\"{name} is {age}\"")
+}
+
+fn create_request(path: &str, method: Method, body: &str, content_type: Option<&str>) -> Request {
+    let headers = content_type
+        .map(|content_type| HashMap::from([("Content-Type".to_string(), content_type.to_string())]))
+        .unwrap_or_default();
+    Request::new(path, method, Some(body.to_string()), HashMap::new()).with_headers(headers)
+}
+
+// ==========
+// form tests
+// ==========
+#[test]
+fn parse_should_decode_every_field_in_order() {
+    let fields = form::parse("name=Evan+G&city=S%C3%A3o+Paulo&empty&=x&bad=%ZZ");
+
+    assert_eq!(
+        vec![
+            ("name".to_string(), "Evan G".to_string()),
+            ("city".to_string(), "São Paulo".to_string()),
+            ("empty".to_string(), String::new()),
+            (String::new(), "x".to_string()),
+        ],
+        fields
+    );
+}
+
+#[test]
+fn is_form_should_ignore_case_and_parameters() {
+    assert!(form::is_form(Some(
+        "Application/X-WWW-Form-Urlencoded; charset=utf-8"
+    )));
+    assert!(!form::is_form(Some("application/json")));
+    assert!(!form::is_form(None));
+}
+
+#[test]
+fn form_param_should_only_read_form_bodies() {
+    let form_request = create_request(
+        "/form/signup",
+        POST,
+        "name=evan&name=other",
+        Some("application/x-www-form-urlencoded"),
+    );
+    let json_request = create_request("/form/signup", POST, "name=evan", None);
+
+    assert_eq!(Some("evan".to_string()), form_request.form_param("name"));
+    assert_eq!(None, form_request.form_param("age"));
+    assert_eq!(None, json_request.form_param("name"));
+}
+
+#[test]
+fn handler_should_bind_form_fields_to_arguments() {
+    let request = create_request(
+        "/form/signup",
+        POST,
+        "name=Evan+Gipson&age=30",
+        Some("application/x-www-form-urlencoded"),
+    );
+
+    let response = signup().get_response(request);
+
+    assert_eq!(Status::Ok, response.status);
+    assert!(response.content.contains("Evan Gipson is 30"));
+}
+
+#[test]
+fn handler_should_fall_back_to_query_parameters_for_missing_form_fields() {
+    let request = create_request(
+        "/form/signup?age=41",
+        POST,
+        "name=evan",
+        Some("application/x-www-form-urlencoded"),
+    );
+
+    let response = signup().get_response(request);
+
+    assert!(response.content.contains("evan is 41"));
+}
+
+#[test]
+fn handler_should_reject_missing_or_invalid_form_fields() {
+    let missing = signup().get_response(create_request(
+        "/form/signup",
+        POST,
+        "age=30",
+        Some("application/x-www-form-urlencoded"),
+    ));
+    let invalid = signup().get_response(create_request(
+        "/form/signup",
+        POST,
+        "name=evan&age=old",
+        Some("application/x-www-form-urlencoded"),
+    ));
+
+    assert_eq!(Status::BadRequest, missing.status);
+    assert!(missing.content.contains("missing form field name"));
+    assert_eq!(Status::BadRequest, invalid.status);
+    assert!(
+        invalid
+            .content
+            .contains("invalid form field age: expected u32")
+    );
+}
+
+#[test]
+fn handler_should_bind_the_whole_body_without_a_form() {
+    let response = signup().get_response(create_request("/form/signup?age=30", POST, "evan", None));
+
+    assert!(response.content.contains("evan is 30"));
+}
+
+#[test]
+fn validate_should_read_form_fields() {
+    let request = create_request(
+        "/form/signup",
+        POST,
+        "name=evan&age=30",
+        Some("application/x-www-form-urlencoded"),
+    );
+
+    assert_eq!(
+        None,
+        RouteParam::new("name", ParamLocation::Body, "String").validate(&request)
+    );
+    assert_eq!(
+        None,
+        RouteParam::new("age", ParamLocation::Query, "u32").validate(&request)
+    );
+    assert!(
+        RouteParam::new("email", ParamLocation::Query, "String")
+            .validate(&request)
+            .is_some()
+    );
+}
@@ -0,0 +1,217 @@
+use http::{
+    client::{Client, ClientRequest},
+    header,
+    methods::{GET, HEAD, POST},
+    request::Request,
+    response::Response,
+    status::Status,
+    validation::{ParamLocation, RouteParam},
+};
+use http_attributes::{http_get, http_head, http_post};
+use std::collections::HashMap;
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/reports", generate_tests)]
+fn get_reports(#[header("X-Api-Key")] key: String, #[header("X-Page")] page: u32) -> String {
+    format!("\"{key} {page}\"")
+}
+
+#[http_post("/notes")]
+fn create_note(#[header("X-Author")] author: String, note: String) -> String {
+    format!("\"{author}: {note}\"")
+}
+
+#[http_head("/reports", headers(("Cache-Control", "no-store")))]
+fn head_reports(#[header("X-Api-Key")] key: String) -> String {
+    format!("\"{key}\"")
+}
+
+#[http_get("/theme")]
+fn get_theme(#[cookie("theme")] theme: String, #[cookie("visits")] visits: u32) -> String {
+    format!("\"{theme} {visits}\"")
+}
+
+fn create_request(method: http::methods::Method, headers: &[(&str, &str)]) -> Request {
+    Request::new("/reports", method, None, HashMap::new()).with_headers(
+        headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+// ============
+// header tests
+// ============
+#[test]
+fn header_argument_should_get_the_value_of_the_request_header() {
+    let request = create_request(GET, &[("x-api-key", "secret"), ("X-Page", "2")]);
+
+    let response = get_reports().get_response(request);
+
+    assert_eq!(Status::Ok, response.status);
+    assert_eq!("\"secret 2\"", response.content);
+}
+
+#[test]
+fn header_argument_should_answer_a_missing_header_with_a_bad_request() {
+    let request = create_request(GET, &[("X-Page", "2")]);
+
+    let response = get_reports().get_response(request);
+
+    assert_eq!(Status::BadRequest, response.status);
+    assert_eq!("\"missing header X-Api-Key\"", response.content);
+}
+
+#[test]
+fn header_argument_should_answer_an_invalid_header_with_a_bad_request() {
+    let request = create_request(GET, &[("X-Api-Key", "secret"), ("X-Page", "two")]);
+
+    let response = get_reports().get_response(request);
+
+    assert_eq!(Status::BadRequest, response.status);
+    assert_eq!("\"invalid header X-Page: expected u32\"", response.content);
+}
+
+#[test]
+fn header_argument_should_not_be_the_body_of_a_post() {
+    let request =
+        Request::new("/notes", POST, Some("buy milk".to_string()), HashMap::new()).with_headers(
+            HashMap::from([("X-Author".to_string(), "evan".to_string())]),
+        );
+
+    let response = create_note().get_response(request);
+
+    assert_eq!("\"evan: buy milk\"", response.content);
+    assert_eq!(
+        vec![
+            RouteParam::new("X-Author", ParamLocation::Header, "String"),
+            RouteParam::new("note", ParamLocation::Body, "String"),
+        ],
+        create_note().spec.params
+    );
+}
+
+#[test]
+fn header_argument_should_keep_static_headers_and_omit_the_body_when_missing() {
+    let response = head_reports().get_response(create_request(HEAD, &[]));
+
+    assert_eq!(Status::BadRequest, response.status);
+    assert!(response.omits_body);
+    assert_eq!(Some("no-store"), response.get_header("Cache-Control"));
+}
+
+#[test]
+fn header_param_should_be_validated_against_the_request_headers() {
+    let param = RouteParam::new("X-Page", ParamLocation::Header, "u32");
+
+    assert!(
+        param
+            .validate(&create_request(GET, &[("X-Page", "2")]))
+            .is_none()
+    );
+    assert!(param.validate(&create_request(GET, &[])).is_some());
+}
+
+#[test]
+fn cookie_argument_should_get_the_value_of_the_cookie() {
+    let request = create_request(
+        GET,
+        &[("Cookie", "session_id=abc; theme=\"dark\"; visits=3")],
+    );
+
+    let response = get_theme().get_response(request);
+
+    assert_eq!("\"dark 3\"", response.content);
+    assert_eq!(
+        vec![
+            RouteParam::new("theme", ParamLocation::Cookie, "String"),
+            RouteParam::new("visits", ParamLocation::Cookie, "u32"),
+        ],
+        get_theme().spec.params
+    );
+}
+
+#[test]
+fn cookie_argument_should_answer_a_missing_cookie_with_a_bad_request() {
+    let request = create_request(GET, &[("Cookie", "visits=3")]);
+
+    let response = get_theme().get_response(request);
+
+    assert_eq!(Status::BadRequest, response.status);
+    assert_eq!("\"missing cookie theme\"", response.content);
+}
+
+#[test]
+fn cookie_argument_should_answer_an_invalid_cookie_with_a_bad_request() {
+    let request = create_request(GET, &[("Cookie", "theme=dark; visits=many")]);
+
+    let response = get_theme().get_response(request);
+
+    assert_eq!(Status::BadRequest, response.status);
+    assert_eq!("\"invalid cookie visits: expected u32\"", response.content);
+}
+
+#[test]
+fn check_header_should_reject_line_breaks_in_names_and_values() {
+    assert!(header::check_header("X-Request-Id", "abc-123").is_ok());
+    assert!(header::check_header("X-Request-Id", "abc\r\nSet-Cookie: a=b").is_err());
+    assert!(header::check_header("X-Request-Id", "abc\nSet-Cookie: a=b").is_err());
+    assert!(header::check_header("X-Request-Id", "abc\0").is_err());
+    assert!(header::check_header("X-Evil\r\nSet-Cookie", "a=b").is_err());
+    assert!(header::check_header("X-Evil: a", "b").is_err());
+    assert!(header::check_header("", "b").is_err());
+}
+
+#[test]
+fn response_should_drop_headers_that_would_inject_another_header() {
+    let request_id = "abc\r\nSet-Cookie: session=stolen";
+
+    let response = Response::ok("\"ok\"", false)
+        .with_header("X-Request-Id", request_id)
+        .with_header("X-Evil\r\nSet-Cookie", "session=stolen")
+        .with_headers(&[("X-Trace", "1\n\nHTTP/1.1 200 OK"), ("X-Kept", "yes")]);
+
+    assert_eq!(None, response.get_header("X-Request-Id"));
+    assert_eq!(None, response.get_header("X-Trace"));
+    assert_eq!(Some("yes"), response.get_header("X-Kept"));
+    assert!(!response.to_string().contains("Set-Cookie"));
+    assert_eq!(1, response.to_string().matches("HTTP/1.1").count());
+}
+
+#[test]
+fn response_should_leave_out_unsafe_headers_pushed_directly() {
+    let mut response = Response::ok("\"ok\"", false);
+    response.headers.push((
+        "X-Request-Id".to_string(),
+        "abc\r\nSet-Cookie: a=b".to_string(),
+    ));
+
+    let response = response.with_header("X-Kept", "yes");
+
+    assert!(!response.to_string().contains("Set-Cookie"));
+    assert!(response.to_string().contains("X-Kept: yes\r\n"));
+}
+
+#[test]
+fn client_should_refuse_to_send_headers_or_urls_with_line_breaks() {
+    let client = Client::new();
+
+    let injected_header = client.send(
+        &ClientRequest::new("GET", "http://127.0.0.1:9/")
+            .with_header("X-Request-Id", "abc\r\nHost: internal"),
+    );
+    let injected_url = client.send(&ClientRequest::new(
+        "GET",
+        "http://127.0.0.1:9/ HTTP/1.1\r\nHost: internal\r\n\r\nGET /",
+    ));
+
+    assert!(
+        injected_header
+            .unwrap_err()
+            .contains("can't contain a CR, LF, or NUL")
+    );
+    assert!(injected_url.unwrap_err().contains("control characters"));
+}
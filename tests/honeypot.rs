@@ -0,0 +1,79 @@
+use minimal_api::server::{
+    access_control::AccessControl,
+    honeypot::{Honeypot, HoneypotMode},
+};
+use std::{
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn is_trap_should_catch_scanner_paths_and_paths_under_them() {
+    let honeypot = Honeypot::new(HoneypotMode::Junk).with_paths(&["/vendor/phpunit"]);
+
+    assert!(honeypot.is_trap("/.env"));
+    assert!(honeypot.is_trap("/WP-LOGIN.PHP"));
+    assert!(honeypot.is_trap("/.git/config"));
+    assert!(honeypot.is_trap("/vendor/phpunit/src/Util/PHP/eval-stdin.php"));
+    assert!(!honeypot.is_trap("/.environment"));
+    assert!(!honeypot.is_trap("/user/1"));
+}
+
+#[test]
+fn is_trap_should_catch_nothing_when_off() {
+    let honeypot = Honeypot::new(HoneypotMode::Off);
+
+    assert!(!honeypot.is_trap("/.env"));
+}
+
+#[test]
+fn respond_should_send_large_junk_body() {
+    let honeypot = Honeypot::new(HoneypotMode::Junk).with_junk_bytes(20_000);
+    let mut written = Vec::new();
+
+    honeypot.respond(&mut written).unwrap();
+    let written = String::from_utf8(written).unwrap();
+    let (head, body) = written.split_once("\r\n\r\n").unwrap();
+
+    assert!(head.contains("Content-Length: 20000"));
+    assert_eq!(20_000, body.len());
+    assert!(body.bytes().all(|byte| byte.is_ascii_lowercase()));
+}
+
+#[test]
+fn respond_should_tarpit_a_byte_at_a_time() {
+    let honeypot = Honeypot::new(HoneypotMode::Tarpit)
+        .with_tarpit(Duration::from_millis(60), Duration::from_millis(20));
+    let mut written = Vec::new();
+    let started = Instant::now();
+
+    honeypot.respond(&mut written).unwrap();
+    let written = String::from_utf8(written).unwrap();
+
+    assert!(started.elapsed() >= Duration::from_millis(60));
+    assert!(written.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert_eq!("   ", written.split_once("\r\n\r\n").unwrap().1);
+}
+
+#[test]
+fn honeypot_mode_should_parse_config_values() {
+    assert_eq!(Ok(HoneypotMode::Tarpit), "Tarpit".parse());
+    assert_eq!(Ok(HoneypotMode::Junk), "junk".parse());
+    assert_eq!(Ok(HoneypotMode::Off), "off".parse());
+    assert!("slow".parse::<HoneypotMode>().is_err());
+}
+
+#[test]
+fn ban_should_deny_address_until_it_ends() {
+    let access_control = AccessControl::new();
+    let scanner: IpAddr = "198.51.100.7".parse().unwrap();
+    let mapped_scanner: IpAddr = "::ffff:198.51.100.7".parse().unwrap();
+
+    access_control.ban(scanner, Duration::from_millis(50));
+    let is_denied_while_banned = !access_control.is_allowed(mapped_scanner, "/");
+    std::thread::sleep(Duration::from_millis(60));
+
+    assert!(is_denied_while_banned);
+    assert!(access_control.is_allowed(scanner, "/"));
+    assert!(access_control.is_empty());
+}
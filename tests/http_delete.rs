@@ -6,27 +6,27 @@ use std::collections::HashMap;
 // common test values
 // ==================
 const TEST_DELETE_ID: &str = "DELETE-ME";
-const TEST_DELETE_ENDPOINT: &str = "delete/test";
+const TEST_DELETE_ENDPOINT: &str = "/delete/test";
 
 // =================
 // endpoints to test
 // =================
-#[http_delete("delete/test")]
+#[http_delete("/delete/test")]
 fn test_delete(id: String) -> String {
     id.to_string()
 }
 
-#[http_delete("delete/test/{id}")]
+#[http_delete("/delete/test/{id}")]
 fn test_delete_dynamic(id: String) -> String {
     id.to_string()
 }
 
-#[http_raw_delete("delete/test")]
+#[http_raw_delete("/delete/test")]
 fn test_raw_delete(id: String) -> String {
     id.to_string()
 }
 
-#[http_raw_delete("delete/test/{id}")]
+#[http_raw_delete("/delete/test/{id}")]
 fn test_raw_delete_dynamic(id: String) -> String {
     id.to_string()
 }
@@ -36,7 +36,7 @@ fn test_raw_delete_dynamic(id: String) -> String {
 // =================
 #[test]
 fn http_delete_request_pattern_should_return_expected_endpoint() {
-    assert_eq!(TEST_DELETE_ENDPOINT, test_delete().request_pattern);
+    assert_eq!(TEST_DELETE_ENDPOINT, test_delete().spec.request_pattern);
 }
 
 #[test]
@@ -70,7 +70,7 @@ fn http_delete_handler_should_return_expected_dynamic_path_value() {
 // =====================
 #[test]
 fn http_raw_delete_request_pattern_should_return_expected_endpoint() {
-    assert_eq!(TEST_DELETE_ENDPOINT, test_raw_delete().request_pattern);
+    assert_eq!(TEST_DELETE_ENDPOINT, test_raw_delete().spec.request_pattern);
 }
 
 #[test]
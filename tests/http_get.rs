@@ -1,54 +1,120 @@
-use http::{request::Request, response::Response};
+use http::{
+    error::HttpError, json::Json, request::Request, respond::Respond, response::Response,
+    route::Route, status::Status,
+};
 use http_attributes::{http_get, http_raw_get};
 use std::collections::HashMap;
 
 // ==================
 // common test values
 // ==================
-const TEST_GET_ENDPOINT: &str = "get/test";
+const TEST_GET_ENDPOINT: &str = "/get/test";
 const TEST_GET_MESSAGE: &str = "Hello!";
-const TEST_GET_QUERY_ENDPOINT: &str = "get/test/query";
+const TEST_GET_QUERY_ENDPOINT: &str = "/get/test/query";
 const TEST_GET_ID: &str = "TEST-GET";
 
 // =================
 // endpoints to test
 // =================
-#[http_get("get/test")]
+#[http_get("/get/test")]
 fn test_get() -> String {
     TEST_GET_MESSAGE.to_string()
 }
 
-#[http_get("get/test/query")]
+#[http_get("/get/test/query")]
 fn test_get_query(id: String) -> String {
     id.to_string()
 }
 
-#[http_get("get/test/{id}")]
+#[http_get("/get/test/{id}")]
 fn test_get_dynamic(id: String) -> String {
     id.to_string()
 }
 
-#[http_raw_get("get/test")]
+#[http_get("/get/test/requires", requires = "db", requires = "cache")]
+fn test_get_requires() -> String {
+    TEST_GET_MESSAGE.to_string()
+}
+
+#[http_get("/get/test/debug", cfg = "debug-endpoints")]
+fn test_get_debug() -> String {
+    TEST_GET_MESSAGE.to_string()
+}
+
+#[http_get("/get/test/generated/{id}", generate_tests)]
+fn test_get_generated(id: String, verbose: bool) -> String {
+    format!("{id} {verbose}")
+}
+
+#[http_get("/get/test/respond")]
+fn test_get_respond() -> impl Respond {
+    TEST_GET_MESSAGE
+}
+
+#[http_get("/get/test/respond/{id}")]
+fn test_get_respond_result(id: u8) -> Result<Json, HttpError> {
+    match id {
+        0 => Err(HttpError::not_found("no such id")),
+        id => Ok(Json::Number(f64::from(id))),
+    }
+}
+
+#[http_get(
+    "/get/test/headers/{id}",
+    headers(("Cache-Control", "no-store"), ("X-Robots-Tag", "noindex"),)
+)]
+fn test_get_headers(id: u8) -> Result<String, HttpError> {
+    match id {
+        0 => Err(HttpError::not_found("no such id")),
+        id => Ok(id.to_string()),
+    }
+}
+
+#[http_get("/get/test/squared/{power}")]
+fn test_get_squared(power: u32, number: i32) -> String {
+    number.pow(power).to_string()
+}
+
+#[http_raw_get("/get/test")]
 fn test_raw_get() -> String {
     TEST_GET_MESSAGE.to_string()
 }
 
-#[http_raw_get("get/test/query")]
+#[http_raw_get("/get/test/query")]
 fn test_raw_get_query(id: String) -> String {
     id.to_string()
 }
 
-#[http_raw_get("get/test/{id}")]
+#[http_raw_get("/get/test/{id}")]
 fn test_raw_get_dynamic(id: String) -> String {
     id.to_string()
 }
 
+#[http_raw_get("/get/test/page/{id}", content_type = "text/html; charset=utf-8")]
+fn test_raw_get_page(id: u8) -> Result<String, HttpError> {
+    match id {
+        0 => Err(HttpError::not_found("no such page")),
+        id => Ok(format!("<h1>Page {id}</h1>")),
+    }
+}
+
 // ==============
 // http_get tests
 // ==============
 #[test]
 fn http_get_request_pattern_should_return_expected_endpoint() {
-    assert_eq!(TEST_GET_ENDPOINT, test_get().request_pattern);
+    assert_eq!(TEST_GET_ENDPOINT, test_get().spec.request_pattern);
+}
+
+#[test]
+fn http_get_segments_should_be_split_at_compile_time() {
+    let expected = Route::get("/get/test/{id}", Box::new(|_| Response::no_content()));
+
+    let result = test_get_dynamic();
+
+    assert_eq!(expected.spec.segments, result.spec.segments);
+    assert!(result.spec.matches_path("/get/test/5").is_some());
+    assert!(result.spec.matches_path("/get/other/5").is_none());
 }
 
 #[test]
@@ -87,12 +153,96 @@ fn http_get_handler_should_return_expected_dynamic_path_value() {
     assert_eq!(expected, result);
 }
 
+#[test]
+fn http_get_handler_should_send_the_json_of_a_respond_type() {
+    let expected = Response::ok(&TEST_GET_MESSAGE.get_json(), false);
+    let request = Request::new(
+        "/get/test/respond",
+        http::methods::GET,
+        None,
+        HashMap::new(),
+    );
+
+    let result = test_get_respond().get_response(request);
+
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn http_get_handler_should_send_the_json_of_an_ok_respond_type() {
+    let create_request = |id: &str| {
+        Request::new(
+            "/get/test/respond/{id}",
+            http::methods::GET,
+            None,
+            HashMap::from([("id".to_string(), id.to_string())]),
+        )
+    };
+
+    let found = test_get_respond_result().get_response(create_request("7"));
+    let missing = test_get_respond_result().get_response(create_request("0"));
+
+    assert_eq!(Response::ok("7", false), found);
+    assert_eq!(http::status::Status::NotFound, missing.status);
+}
+
+#[test]
+fn http_get_handler_should_answer_unparseable_arguments_with_a_bad_request() {
+    let create_request = |path: &str, power: &str| {
+        Request::new(
+            path,
+            http::methods::GET,
+            None,
+            HashMap::from([("power".to_string(), power.to_string())]),
+        )
+    };
+
+    let squared =
+        test_get_squared().get_response(create_request("/get/test/squared/2?number=3", "2"));
+    let invalid_query =
+        test_get_squared().get_response(create_request("/get/test/squared/2?number=AAA", "2"));
+    let missing_query = test_get_squared().get_response(create_request("/get/test/squared/2", "2"));
+
+    assert_eq!(Response::ok("9", false), squared);
+    assert_eq!(Status::BadRequest, invalid_query.status);
+    assert!(
+        invalid_query
+            .content
+            .contains("invalid query parameter number: expected i32")
+    );
+    assert_eq!(Status::BadRequest, missing_query.status);
+    assert!(
+        missing_query
+            .content
+            .contains("missing query parameter number")
+    );
+}
+
+#[test]
+fn http_get_handler_should_answer_an_unparseable_path_parameter_with_not_found() {
+    let request = Request::new(
+        "/get/test/squared/two?number=3",
+        http::methods::GET,
+        None,
+        HashMap::from([("power".to_string(), "two".to_string())]),
+    );
+
+    let response = test_get_squared().get_response(request);
+
+    assert_eq!(Status::NotFound, response.status);
+    assert!(
+        response
+            .content
+            .contains("invalid path parameter power: expected u32")
+    );
+}
+
 // ==================
 // http_raw_get tests
 // ==================
 #[test]
 fn http_raw_get_request_pattern_should_return_expected_endpoint() {
-    assert_eq!(TEST_GET_ENDPOINT, test_raw_get().request_pattern);
+    assert_eq!(TEST_GET_ENDPOINT, test_raw_get().spec.request_pattern);
 }
 
 #[test]
@@ -130,3 +280,87 @@ fn http_raw_get_handler_should_return_expected_dynamic_path_value() {
 
     assert_eq!(expected, result);
 }
+
+#[test]
+fn http_raw_get_should_send_successful_responses_as_the_content_type() {
+    let request = Request::new(
+        "/get/test/page/1",
+        http::methods::GET,
+        None,
+        HashMap::from([("id".to_string(), "1".to_string())]),
+    );
+
+    let result = test_raw_get_page().get_response(request);
+
+    assert_eq!(Status::Ok, result.status);
+    assert_eq!("<h1>Page 1</h1>", result.content);
+    assert_eq!(
+        Some("text/html; charset=utf-8"),
+        result.get_header("Content-Type")
+    );
+    assert_eq!(1, result.to_string().matches("Content-Type").count());
+}
+
+#[test]
+fn http_raw_get_should_send_errors_as_json_despite_the_content_type() {
+    let request = Request::new(
+        "/get/test/page/0",
+        http::methods::GET,
+        None,
+        HashMap::from([("id".to_string(), "0".to_string())]),
+    );
+
+    let result = test_raw_get_page().get_response(request);
+
+    assert_eq!(Status::NotFound, result.status);
+    assert_eq!(None, result.get_header("Content-Type"));
+}
+
+#[test]
+fn http_get_should_set_requires_from_attribute_options() {
+    let route = test_get_requires();
+
+    assert_eq!(
+        vec!["db".to_string(), "cache".to_string()],
+        route.spec.requires
+    );
+    assert!(test_get().spec.requires.is_empty());
+}
+
+#[test]
+#[cfg(feature = "debug-endpoints")]
+fn http_get_with_enabled_cfg_should_be_compiled() {
+    assert_eq!("/get/test/debug", test_get_debug().spec.request_pattern);
+}
+
+#[test]
+fn http_get_with_cfg_should_only_be_served_when_feature_is_enabled() {
+    let has_debug_route = minimal_api::routes::index::get_endpoints()
+        .iter()
+        .any(|route| route.spec.request_pattern == "/debug/state");
+
+    assert_eq!(cfg!(feature = "debug-endpoints"), has_debug_route);
+}
+
+#[test]
+fn http_get_with_headers_should_add_them_to_every_response() {
+    let route = test_get_headers();
+    let request = |id: &str| {
+        Request::new(
+            &format!("/get/test/headers/{id}"),
+            http::methods::GET,
+            None,
+            HashMap::from([("id".to_string(), id.to_string())]),
+        )
+    };
+
+    let found = route.get_response(request("1"));
+    let not_found = route.get_response(request("0"));
+
+    for response in [&found, &not_found] {
+        assert_eq!(Some("no-store"), response.get_header("Cache-Control"));
+        assert_eq!(Some("noindex"), response.get_header("X-Robots-Tag"));
+        assert!(response.to_string().contains("Cache-Control: no-store\r\n"));
+    }
+    assert_eq!(Status::NotFound, not_found.status);
+}
@@ -0,0 +1,122 @@
+use http::{request::Request, response::Response, route::Route};
+use http_attributes::{http_head, http_raw_head};
+use std::collections::HashMap;
+
+// ==================
+// common test values
+// ==================
+const TEST_HEAD_ENDPOINT: &str = "/head/test";
+const TEST_HEAD_MESSAGE: &str = "Hello!";
+const TEST_HEAD_ID: &str = "TEST-HEAD";
+
+// =================
+// endpoints to test
+// =================
+#[http_head("/head/test")]
+fn test_head() -> String {
+    TEST_HEAD_MESSAGE.to_string()
+}
+
+#[http_head("/head/test/{id}")]
+fn test_head_dynamic(id: String) -> String {
+    id.to_string()
+}
+
+#[http_raw_head("/head/test")]
+fn test_raw_head() -> String {
+    TEST_HEAD_MESSAGE.to_string()
+}
+
+// ===============
+// http_head tests
+// ===============
+#[test]
+fn http_head_should_set_head_method_and_pattern() {
+    let result = test_head();
+
+    assert_eq!(http::methods::HEAD, result.spec.method);
+    assert_eq!(TEST_HEAD_ENDPOINT, result.spec.request_pattern);
+}
+
+#[test]
+fn http_head_handler_should_keep_content_length_without_body() {
+    let full_response = Response::ok(TEST_HEAD_MESSAGE, false);
+    let request = Request::new(
+        TEST_HEAD_ENDPOINT,
+        http::methods::HEAD,
+        None,
+        HashMap::new(),
+    );
+
+    let result = test_head().get_response(request);
+
+    assert!(result.omits_body);
+    assert!(
+        result
+            .to_string()
+            .contains(&format!("Content-Length: {}\r\n", full_response.len(false)))
+    );
+    assert!(result.to_string().ends_with("\r\n\r\n"));
+    assert!(!result.to_string().contains(TEST_HEAD_MESSAGE));
+}
+
+#[test]
+fn http_head_handler_should_return_expected_dynamic_path_value_length() {
+    let request = Request::new(
+        TEST_HEAD_ENDPOINT,
+        http::methods::HEAD,
+        None,
+        HashMap::from([("id".to_string(), TEST_HEAD_ID.to_string())]),
+    );
+
+    let result = test_head_dynamic().get_response(request);
+
+    assert_eq!(Response::ok(TEST_HEAD_ID, false).without_body(), result);
+}
+
+// ===================
+// http_raw_head tests
+// ===================
+#[test]
+fn http_raw_head_handler_should_keep_raw_content_length_without_body() {
+    let request = Request::new(
+        TEST_HEAD_ENDPOINT,
+        http::methods::HEAD,
+        None,
+        HashMap::new(),
+    );
+
+    let result = test_raw_head().get_response(request);
+
+    assert!(
+        result
+            .to_string()
+            .contains(&format!("Content-Length: {}\r\n", TEST_HEAD_MESSAGE.len()))
+    );
+    assert!(result.to_string().ends_with("\r\n\r\n"));
+}
+
+// =================
+// Route::head tests
+// =================
+#[test]
+fn route_head_should_omit_body_of_handler_response() {
+    let route = Route::head(
+        TEST_HEAD_ENDPOINT,
+        Box::new(|_| Response::ok(TEST_HEAD_MESSAGE, true)),
+    );
+    let request = Request::new(
+        TEST_HEAD_ENDPOINT,
+        http::methods::HEAD,
+        None,
+        HashMap::new(),
+    );
+
+    let result = route.get_response(request);
+
+    assert!(result.omits_body);
+    assert_eq!(
+        result.to_string().as_bytes(),
+        result.to_bytes(&http::compression::Encoding::Identity)
+    );
+}
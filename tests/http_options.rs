@@ -0,0 +1,78 @@
+use http::{request::Request, response::Response, route::Route};
+use http_attributes::{http_options, http_raw_options};
+use std::collections::HashMap;
+
+// ==================
+// common test values
+// ==================
+const TEST_OPTIONS_ENDPOINT: &str = "/options/test";
+const TEST_OPTIONS_MESSAGE: &str = "[\"text/csv\"]";
+
+// =================
+// endpoints to test
+// =================
+#[http_options("/options/test")]
+fn test_options() -> String {
+    TEST_OPTIONS_MESSAGE.to_string()
+}
+
+#[http_raw_options("/options/test")]
+fn test_raw_options() -> String {
+    TEST_OPTIONS_MESSAGE.to_string()
+}
+
+// ==================
+// http_options tests
+// ==================
+#[test]
+fn http_options_should_set_options_method_and_pattern() {
+    let result = test_options();
+
+    assert_eq!(http::methods::OPTIONS, result.spec.method);
+    assert_eq!(TEST_OPTIONS_ENDPOINT, result.spec.request_pattern);
+}
+
+#[test]
+fn http_options_handler_should_return_expected_response() {
+    let expected = Response::ok(TEST_OPTIONS_MESSAGE, false);
+    let request = Request::new(
+        TEST_OPTIONS_ENDPOINT,
+        http::methods::OPTIONS,
+        None,
+        HashMap::new(),
+    );
+
+    let result = test_options().get_response(request);
+
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn http_options_route_should_be_listed_in_allowed_methods_once() {
+    let routes = vec![
+        Route::get(TEST_OPTIONS_ENDPOINT, Box::new(|_| Response::no_content())),
+        test_options(),
+    ];
+
+    let result = Route::allowed_methods(&routes, TEST_OPTIONS_ENDPOINT);
+
+    assert_eq!(vec![http::methods::GET, http::methods::OPTIONS], result);
+}
+
+// ======================
+// http_raw_options tests
+// ======================
+#[test]
+fn http_raw_options_handler_should_return_expected_raw_response() {
+    let expected = Response::ok(TEST_OPTIONS_MESSAGE, true);
+    let request = Request::new(
+        TEST_OPTIONS_ENDPOINT,
+        http::methods::OPTIONS,
+        None,
+        HashMap::new(),
+    );
+
+    let result = test_raw_options().get_response(request);
+
+    assert_eq!(expected, result);
+}
@@ -1,33 +1,62 @@
-use http::{request::Request, response::Response};
+use http::{error::HttpError, request::Request, response::Response, status::Status};
 use http_attributes::{http_post, http_raw_post};
 use std::collections::HashMap;
 
 // ==================
 // common test values
 // ==================
-const TEST_POST_ENDPOINT: &str = "post/test";
+const TEST_POST_ENDPOINT: &str = "/post/test";
 const TEST_POST_BODY_CONTENT: &str = "Hello!";
 const TEST_POST_ID: &str = "POST-ID";
 
 // =================
 // endpoints to test
 // =================
-#[http_post("post/test")]
+#[http_post("/post/test")]
 fn test_post(content: String) -> String {
     content
 }
 
-#[http_post("get/test/{id}")]
+#[http_post("/get/test/{id}")]
 fn test_post_dynamic(content: String, id: String) -> String {
     format!("{content} {id}")
 }
 
-#[http_raw_post("post/test")]
+#[http_post("/post/test/created", status = 201)]
+fn test_post_created(content: String) -> Result<String, HttpError> {
+    if content.is_empty() {
+        Err(HttpError::bad_request("empty item"))
+    } else {
+        Ok(content)
+    }
+}
+
+#[http_post("/post/test/raw/{id}", generate_tests)]
+fn test_post_request(id: u32, request: Request) -> String {
+    format!(
+        "{} {} {id} {}",
+        request.method,
+        request.path,
+        request.body_content.unwrap_or_default()
+    )
+}
+
+#[http_post("/post/test/raw")]
+fn test_post_request_reference(request: &Request, ctx: &http::context::Context) -> String {
+    format!("{} {}", ctx.scope, request.path)
+}
+
+#[http_raw_post("/post/test/number")]
+fn test_raw_post_number(number: i32) -> String {
+    (number * 2).to_string()
+}
+
+#[http_raw_post("/post/test")]
 fn test_raw_post(content: String) -> String {
     content
 }
 
-#[http_raw_post("get/test/{id}")]
+#[http_raw_post("/get/test/{id}")]
 fn test_raw_post_dynamic(content: String, id: String) -> String {
     format!("{content} {id}")
 }
@@ -37,7 +66,7 @@ fn test_raw_post_dynamic(content: String, id: String) -> String {
 // ===============
 #[test]
 fn http_post_request_pattern_should_return_expected_endpoint() {
-    assert_eq!(TEST_POST_ENDPOINT, test_post().request_pattern);
+    assert_eq!(TEST_POST_ENDPOINT, test_post().spec.request_pattern);
 }
 
 #[test]
@@ -70,12 +99,41 @@ fn http_post_handler_should_return_body_and_dynamic_path_value() {
     assert_eq!(expected, result);
 }
 
+#[test]
+fn http_post_with_status_should_answer_with_it_on_success() {
+    let expected = Response::with_status(Status::Created, TEST_POST_BODY_CONTENT, false);
+    let request = Request::new(
+        "/post/test/created",
+        http::methods::POST,
+        Some(TEST_POST_BODY_CONTENT.to_string()),
+        HashMap::new(),
+    );
+
+    let result = test_post_created().get_response(request);
+
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn http_post_with_status_should_keep_the_status_of_errors() {
+    let request = Request::new(
+        "/post/test/created",
+        http::methods::POST,
+        Some(String::new()),
+        HashMap::new(),
+    );
+
+    let result = test_post_created().get_response(request);
+
+    assert_eq!(Status::BadRequest, result.status);
+}
+
 // ===================
 // http_raw_post tests
 // ===================
 #[test]
 fn http_raw_post_request_pattern_should_return_expected_endpoint() {
-    assert_eq!(TEST_POST_ENDPOINT, test_raw_post().request_pattern);
+    assert_eq!(TEST_POST_ENDPOINT, test_raw_post().spec.request_pattern);
 }
 
 #[test]
@@ -107,3 +165,59 @@ fn http_raw_post_handler_should_return_body_and_dynamic_path_value() {
 
     assert_eq!(expected, result);
 }
+
+#[test]
+fn http_post_should_pass_the_request_through_without_extracting_it() {
+    let route = test_post_request();
+    let request = Request::new(
+        "/post/test/raw/7?verbose=true",
+        http::methods::POST,
+        Some("raw body".to_string()),
+        HashMap::from([("id".to_string(), "7".to_string())]),
+    );
+
+    let response = route.get_response(request);
+
+    assert_eq!(1, route.spec.params.len());
+    assert_eq!(
+        Response::ok("POST /post/test/raw/7?verbose=true 7 raw body", false).content,
+        response.content
+    );
+}
+
+#[test]
+fn http_post_should_pass_a_request_reference_alongside_the_context() {
+    let route = test_post_request_reference();
+    let request = Request::new("/post/test/raw", http::methods::POST, None, HashMap::new());
+
+    let response = route.get_response(request);
+
+    assert!(route.spec.params.is_empty());
+    assert_eq!("POST /post/test/raw /post/test/raw", response.content);
+}
+
+#[test]
+fn http_raw_post_should_answer_an_unparseable_body_with_an_unprocessable_entity() {
+    let create_request = |body: Option<&str>| {
+        Request::new(
+            "/post/test/number",
+            http::methods::POST,
+            body.map(str::to_string),
+            HashMap::new(),
+        )
+    };
+
+    let doubled = test_raw_post_number().get_response(create_request(Some("21")));
+    let invalid = test_raw_post_number().get_response(create_request(Some("AAA")));
+    let missing = test_raw_post_number().get_response(create_request(None));
+
+    assert_eq!("42", doubled.content);
+    assert_eq!(Status::UnprocessableEntity, invalid.status);
+    assert!(
+        invalid
+            .content
+            .contains("invalid request body: expected i32")
+    );
+    assert_eq!(Status::BadRequest, missing.status);
+    assert!(missing.content.contains("missing request body"));
+}
@@ -5,29 +5,29 @@ use std::collections::HashMap;
 // ==================
 // common test values
 // ==================
-const TEST_PUT_ENDPOINT: &str = "put/test";
+const TEST_PUT_ENDPOINT: &str = "/put/test";
 const TEST_PUT_BODY_CONTENT: &str = "Hello!";
 const TEST_PUT_ID: &str = "PUT-ID";
 
 // =================
 // endpoints to test
 // =================
-#[http_put("put/test")]
+#[http_put("/put/test")]
 fn test_put(content: String) -> String {
     content
 }
 
-#[http_put("put/test/{id}")]
+#[http_put("/put/test/{id}")]
 fn test_put_dynamic(content: String, id: String) -> String {
     format!("{content} {id}")
 }
 
-#[http_raw_put("put/test")]
+#[http_raw_put("/put/test")]
 fn test_raw_put(content: String) -> String {
     content
 }
 
-#[http_raw_put("put/test/{id}")]
+#[http_raw_put("/put/test/{id}")]
 fn test_raw_put_dynamic(content: String, id: String) -> String {
     format!("{content} {id}")
 }
@@ -37,7 +37,7 @@ fn test_raw_put_dynamic(content: String, id: String) -> String {
 // ==============
 #[test]
 fn http_put_request_pattern_should_return_expected_endpoint() {
-    assert_eq!(TEST_PUT_ENDPOINT, test_put().request_pattern);
+    assert_eq!(TEST_PUT_ENDPOINT, test_put().spec.request_pattern);
 }
 
 #[test]
@@ -75,7 +75,7 @@ fn http_put_handler_should_return_dynamic_route_value() {
 // ==================
 #[test]
 fn http_raw_put_request_pattern_should_return_expected_endpoint() {
-    assert_eq!(TEST_PUT_ENDPOINT, test_raw_put().request_pattern);
+    assert_eq!(TEST_PUT_ENDPOINT, test_raw_put().spec.request_pattern);
 }
 
 #[test]
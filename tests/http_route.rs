@@ -0,0 +1,110 @@
+use http::{methods::Method, request::Request, response::Response, route::Route};
+use http_attributes::{http_raw_route, http_route};
+use std::collections::HashMap;
+
+// ==================
+// common test values
+// ==================
+const TEST_ROUTE_ENDPOINT: &str = "/route/test";
+const TEST_ROUTE_NAME: &str = "TEST-ROUTE";
+
+// =================
+// endpoints to test
+// =================
+#[http_route(path = "/route/test", methods = ["GET", "POST"], generate_tests)]
+fn test_route(name: String) -> String {
+    name
+}
+
+#[http_raw_route(path = "/route/test/{id}", methods = ["GET", "HEAD", "DELETE"])]
+fn test_raw_route(id: String) -> String {
+    id
+}
+
+fn get_route(routes: Vec<Route>, method: Method) -> Route {
+    routes
+        .into_iter()
+        .find(|route| route.spec.method == method)
+        .unwrap()
+}
+
+// ================
+// http_route tests
+// ================
+#[test]
+fn http_route_should_return_a_route_for_every_method() {
+    let routes = test_route();
+
+    let methods: Vec<&str> = routes
+        .iter()
+        .map(|route| route.spec.method.as_str())
+        .collect();
+
+    assert_eq!(vec!["GET", "POST"], methods);
+    assert!(
+        routes
+            .iter()
+            .all(|route| route.spec.request_pattern == TEST_ROUTE_ENDPOINT)
+    );
+}
+
+#[test]
+fn http_route_get_handler_should_bind_query_parameter() {
+    let expected = Response::ok(TEST_ROUTE_NAME, false);
+    let get_path = &format!("{TEST_ROUTE_ENDPOINT}?name={TEST_ROUTE_NAME}");
+    let request = Request::new(get_path, http::methods::GET, None, HashMap::new());
+
+    let result = get_route(test_route(), http::methods::GET).get_response(request);
+
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn http_route_post_handler_should_bind_body() {
+    let expected = Response::ok(TEST_ROUTE_NAME, false);
+    let request = Request::new(
+        TEST_ROUTE_ENDPOINT,
+        http::methods::POST,
+        Some(TEST_ROUTE_NAME.to_string()),
+        HashMap::new(),
+    );
+
+    let result = get_route(test_route(), http::methods::POST).get_response(request);
+
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn http_route_should_register_every_method() {
+    let registered: Vec<Method> = http::collect_routes!()
+        .into_iter()
+        .filter(|route| route.spec.request_pattern == "/route/test/{id}")
+        .map(|route| route.spec.method)
+        .collect();
+
+    assert_eq!(vec![Method::Delete, Method::Get, Method::Head], registered);
+}
+
+// ====================
+// http_raw_route tests
+// ====================
+#[test]
+fn http_raw_route_handler_should_return_expected_raw_response() {
+    let path_params = HashMap::from([("id".to_string(), TEST_ROUTE_NAME.to_string())]);
+    let request = Request::new(
+        TEST_ROUTE_ENDPOINT,
+        http::methods::DELETE,
+        None,
+        path_params.clone(),
+    );
+    let head_request = Request::new(TEST_ROUTE_ENDPOINT, http::methods::HEAD, None, path_params);
+
+    let result = get_route(test_raw_route(), http::methods::DELETE).get_response(request);
+    let head_result = get_route(test_raw_route(), http::methods::HEAD).get_response(head_request);
+
+    assert_eq!(Response::ok(TEST_ROUTE_NAME, true), result);
+    assert_eq!(
+        Response::ok(TEST_ROUTE_NAME, true).without_body(),
+        head_result
+    );
+}
@@ -0,0 +1,52 @@
+use http::{instrument::HandlerSpan, request::Request, response::Response};
+use http_attributes::{http_get, http_post};
+use std::{collections::HashMap, thread, time::Duration};
+
+// =================
+// endpoints to test
+// =================
+#[http_post("/login", instrument, redact = "password")]
+fn login(password: String, user: String) -> String {
+    format!("{user} logged in with {} characters", password.len())
+}
+
+#[http_get("/ping", instrument)]
+fn ping() -> String {
+    "pong".to_string()
+}
+
+// ================
+// instrument tests
+// ================
+#[test]
+fn instrumented_handler_should_respond_as_usual() {
+    let request = Request::new(
+        "/login?user=evan",
+        http::methods::POST,
+        Some("hunter2".to_string()),
+        HashMap::new(),
+    );
+
+    let response = login().get_response(request);
+
+    assert_eq!(
+        Response::ok("evan logged in with 7 characters", false),
+        response
+    );
+}
+
+#[test]
+fn instrumented_handler_without_arguments_should_respond_as_usual() {
+    let request = Request::new("/ping", http::methods::GET, None, HashMap::new());
+
+    assert_eq!(Response::ok("pong", false), ping().get_response(request));
+}
+
+#[test]
+fn handler_span_should_measure_elapsed_time() {
+    let span = HandlerSpan::enter(http::methods::GET, "/slow", "");
+
+    thread::sleep(Duration::from_millis(20));
+
+    assert!(span.elapsed() >= Duration::from_millis(20));
+}
@@ -0,0 +1,49 @@
+use http::{
+    intern::{self, HeaderName, headers},
+    route::{Route, RouteSegment},
+};
+
+#[test]
+fn intern_should_return_same_symbol_for_same_string() {
+    let first = intern::intern("intern-test-segment");
+    let second = intern::intern("intern-test-segment");
+
+    assert_eq!(first, second);
+    assert_eq!("intern-test-segment", first.as_str());
+    assert_eq!(Some(first), intern::lookup("intern-test-segment"));
+    assert_eq!(None, intern::lookup("intern-test-never-interned"));
+}
+
+#[test]
+fn header_name_should_ignore_case_for_known_and_custom_headers() {
+    assert_eq!(headers::CONTENT_TYPE, HeaderName::new("Content-Type"));
+    assert_eq!(headers::CONTENT_TYPE, HeaderName::new("CONTENT-TYPE"));
+    assert_eq!(HeaderName::new("x-custom"), HeaderName::new("X-Custom"));
+    assert_eq!("x-custom", HeaderName::new("X-Custom").as_str());
+    assert_eq!("content-type", headers::CONTENT_TYPE.as_str());
+}
+
+#[test]
+fn header_name_should_lowercase_long_custom_headers() {
+    let long_name = format!("X-{}", "Long".repeat(40));
+
+    let result = HeaderName::new(&long_name);
+
+    assert_eq!(HeaderName::Custom(long_name.to_lowercase()), result);
+}
+
+#[test]
+fn route_should_intern_static_segments_and_keep_params() {
+    let route = Route::get("/intern/{id}", Box::new(|_| unreachable!()));
+
+    assert_eq!(
+        vec![
+            RouteSegment::Static(intern::intern("")),
+            RouteSegment::Static(intern::intern("intern")),
+            RouteSegment::Param("id".to_string()),
+        ],
+        route.spec.segments
+    );
+    assert!(route.spec.matches_path("/intern/5").is_some());
+    assert!(route.spec.matches_path("/interned/5").is_none());
+}
@@ -0,0 +1,105 @@
+use http::json::{Json, MAX_JSON_DEPTH};
+
+#[test]
+fn parse_should_read_nested_values() {
+    let result = Json::parse(r#"{"name":"minimal","tags":["a",1,true,null],"nested":{"pi":3.5}}"#);
+
+    assert!(result.is_ok());
+    let value = result.unwrap();
+    assert_eq!(Some("minimal"), value.get("name").and_then(Json::as_str));
+    assert_eq!(4, value.get("tags").and_then(Json::as_array).unwrap().len());
+    assert_eq!(
+        Some(3.5),
+        value
+            .get("nested")
+            .and_then(|n| n.get("pi"))
+            .and_then(Json::as_f64)
+    );
+}
+
+#[test]
+fn parse_should_resolve_string_escapes() {
+    let result = Json::parse(r#""line\nbreak \"quoted\" é 😀""#);
+
+    assert_eq!(
+        Ok(Json::String("line\nbreak \"quoted\" é 😀".to_string())),
+        result
+    );
+}
+
+#[test]
+fn parse_should_error_with_trailing_characters() {
+    let result = Json::parse("{} {}");
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn parse_should_error_with_unterminated_input() {
+    assert!(Json::parse(r#"{"key":"#).is_err());
+    assert!(Json::parse(r#"["value""#).is_err());
+    assert!(Json::parse(r#""value"#).is_err());
+}
+
+#[test]
+fn parse_should_read_numbers_of_the_json_grammar() {
+    for (number, expected) in [
+        ("0", 0.0),
+        ("-0", 0.0),
+        ("10", 10.0),
+        ("-1.5", -1.5),
+        ("0.25", 0.25),
+        ("1e3", 1000.0),
+        ("2E-2", 0.02),
+        ("1.5e+2", 150.0),
+    ] {
+        assert_eq!(Ok(Json::Number(expected)), Json::parse(number), "{number}");
+    }
+}
+
+#[test]
+fn parse_should_error_with_numbers_outside_the_json_grammar() {
+    for number in [
+        "01", "-01", "1.", ".5", "1e", "1e+", "+", "+1", "-", "1.e3", "[01]", "--1",
+    ] {
+        assert!(Json::parse(number).is_err(), "{number}");
+    }
+}
+
+#[test]
+fn parse_should_error_when_nested_too_deeply() {
+    let deepest = format!(
+        "{}{}",
+        "[".repeat(MAX_JSON_DEPTH),
+        "]".repeat(MAX_JSON_DEPTH)
+    );
+    let too_deep = format!(
+        "{}1{}",
+        "[{\"a\":".repeat(MAX_JSON_DEPTH / 2 + 1),
+        "}]".repeat(MAX_JSON_DEPTH / 2 + 1)
+    );
+
+    assert!(Json::parse(&deepest).is_ok());
+    assert!(Json::parse(&too_deep).is_err());
+    assert!(Json::parse(&"[".repeat(50_000)).is_err());
+}
+
+#[test]
+fn to_string_should_write_compact_json() {
+    let value = Json::Object(vec![
+        ("id".to_string(), Json::Number(1.0)),
+        ("ratio".to_string(), Json::Number(0.25)),
+        ("text".to_string(), Json::String("a \"b\"".to_string())),
+        (
+            "items".to_string(),
+            Json::Array(vec![Json::Null, Json::Bool(false)]),
+        ),
+    ]);
+
+    let result = value.to_string();
+
+    assert_eq!(
+        r#"{"id":1,"ratio":0.25,"text":"a \"b\"","items":[null,false]}"#,
+        result
+    );
+}
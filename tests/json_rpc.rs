@@ -0,0 +1,161 @@
+use http::{
+    json::Json,
+    json_rpc::{JsonRpcError, JsonRpcRegistry},
+    request::Request,
+    route::Route,
+    status::Status,
+};
+use std::collections::HashMap;
+
+// ==================
+// common test values
+// ==================
+const TEST_JSON_RPC_ENDPOINT: &str = "rpc/test";
+
+fn create_registry() -> JsonRpcRegistry {
+    let mut registry = JsonRpcRegistry::new();
+    registry.register(
+        "subtract",
+        Box::new(
+            |params: Option<Json>| match params.as_ref().and_then(Json::as_array) {
+                Some(numbers) if numbers.len() == 2 => {
+                    let minuend = numbers[0]
+                        .as_f64()
+                        .ok_or_else(JsonRpcError::invalid_params)?;
+                    let subtrahend = numbers[1]
+                        .as_f64()
+                        .ok_or_else(JsonRpcError::invalid_params)?;
+                    Ok(Json::Number(minuend - subtrahend))
+                }
+                _ => Err(JsonRpcError::invalid_params()),
+            },
+        ),
+    );
+    registry.register("notify", Box::new(|_| Ok(Json::Null)));
+    registry
+}
+
+// ==============
+// registry tests
+// ==============
+#[test]
+fn handle_should_return_result_for_valid_call() {
+    let body = r#"{"jsonrpc":"2.0","method":"subtract","params":[42,23],"id":1}"#;
+
+    let result = create_registry().handle(body);
+
+    assert_eq!(
+        Some(r#"{"jsonrpc":"2.0","result":19,"id":1}"#.to_string()),
+        result
+    );
+}
+
+#[test]
+fn handle_should_return_parse_error_for_invalid_json() {
+    let result = create_registry().handle(r#"{"jsonrpc":"2.0","method""#);
+
+    assert_eq!(
+        Some(
+            r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"Parse error"},"id":null}"#
+                .to_string()
+        ),
+        result
+    );
+}
+
+#[test]
+fn handle_should_return_method_not_found_for_unknown_method() {
+    let body = r#"{"jsonrpc":"2.0","method":"missing","id":"abc"}"#;
+
+    let result = create_registry().handle(body);
+
+    assert_eq!(
+        Some(
+            r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":"abc"}"#
+                .to_string()
+        ),
+        result
+    );
+}
+
+#[test]
+fn handle_should_return_invalid_request_for_empty_batch() {
+    let result = create_registry().handle("[]");
+
+    assert_eq!(
+        Some(
+            r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"Invalid Request"},"id":null}"#
+                .to_string()
+        ),
+        result
+    );
+}
+
+#[test]
+fn handle_should_not_respond_to_notifications() {
+    let body = r#"[{"jsonrpc":"2.0","method":"notify"},{"jsonrpc":"2.0","method":"missing"}]"#;
+
+    let result = create_registry().handle(body);
+
+    assert_eq!(None, result);
+}
+
+#[test]
+fn handle_should_respond_to_each_call_in_batch() {
+    let body = r#"[
+        {"jsonrpc":"2.0","method":"subtract","params":[1,1],"id":1},
+        {"jsonrpc":"2.0","method":"notify"},
+        {"foo":"bar"},
+        {"jsonrpc":"2.0","method":"subtract","params":"bad","id":2}
+    ]"#;
+
+    let result = create_registry().handle(body);
+
+    assert_eq!(
+        Some(
+            concat!(
+                r#"[{"jsonrpc":"2.0","result":0,"id":1},"#,
+                r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"Invalid Request"},"id":null},"#,
+                r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params"},"id":2}]"#
+            )
+            .to_string()
+        ),
+        result
+    );
+}
+
+// ===========
+// route tests
+// ===========
+#[test]
+fn json_rpc_route_should_be_post_and_return_raw_envelope() {
+    let route = Route::json_rpc(TEST_JSON_RPC_ENDPOINT, create_registry());
+    let request = Request::new(
+        TEST_JSON_RPC_ENDPOINT,
+        http::methods::POST,
+        Some(r#"{"jsonrpc":"2.0","method":"subtract","params":[5,3],"id":7}"#.to_string()),
+        HashMap::new(),
+    );
+
+    let result = route.get_response(request);
+
+    assert_eq!(http::methods::POST, route.spec.method);
+    assert_eq!(r#"{"jsonrpc":"2.0","result":2,"id":7}"#, result.content);
+    assert!(result.to_string().ends_with(&result.content));
+}
+
+#[test]
+fn json_rpc_route_should_answer_only_notifications_with_no_content() {
+    let route = Route::json_rpc(TEST_JSON_RPC_ENDPOINT, create_registry());
+    let request = Request::new(
+        TEST_JSON_RPC_ENDPOINT,
+        http::methods::POST,
+        Some(r#"[{"jsonrpc":"2.0","method":"notify"}]"#.to_string()),
+        HashMap::new(),
+    );
+
+    let result = route.get_response(request);
+
+    assert_eq!(Status::NoContent, result.status);
+    assert!(result.to_string().ends_with("\r\n\r\n"));
+}
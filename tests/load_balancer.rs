@@ -0,0 +1,214 @@
+use http::{
+    client::Client,
+    load_balancer::{BalanceStrategy, LoadBalancer},
+    proxy::Proxy,
+    request::Request,
+    status::Status,
+};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+const UPSTREAMS: &[&str] = &["http://10.0.0.1", "http://10.0.0.2", "http://10.0.0.3"];
+
+/// starts an upstream that answers `responses` in order
+fn start_upstream(responses: Vec<&'static str>) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    address
+}
+
+fn unused_address() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+fn create_request(user_id: Option<&str>) -> Request {
+    let request = Request::new("/api/users", http::methods::GET, None, HashMap::new());
+    match user_id {
+        Some(user_id) => request.with_headers(HashMap::from([(
+            "X-User-Id".to_string(),
+            user_id.to_string(),
+        )])),
+        None => request,
+    }
+}
+
+fn pick_url(load_balancer: &LoadBalancer, request: &Request) -> String {
+    load_balancer.pick(request).unwrap().backend.url.clone()
+}
+
+#[test]
+fn pick_should_round_robin_across_backends() {
+    let load_balancer = LoadBalancer::new(UPSTREAMS, BalanceStrategy::RoundRobin);
+    let request = create_request(None);
+
+    let picked: Vec<String> = (0..4).map(|_| pick_url(&load_balancer, &request)).collect();
+
+    assert_eq!(
+        vec![UPSTREAMS[0], UPSTREAMS[1], UPSTREAMS[2], UPSTREAMS[0]],
+        picked
+    );
+}
+
+#[test]
+fn pick_should_prefer_backend_with_least_connections() {
+    let load_balancer = LoadBalancer::new(&UPSTREAMS[..2], BalanceStrategy::LeastConnections);
+    let request = create_request(None);
+
+    let first = load_balancer.pick(&request).unwrap();
+    let second = pick_url(&load_balancer, &request);
+
+    assert_eq!(UPSTREAMS[0], first.backend.url);
+    assert_eq!(1, first.backend.active_requests());
+    assert_eq!(UPSTREAMS[1], second);
+    drop(first);
+    assert_eq!(0, load_balancer.backends[0].active_requests());
+}
+
+#[test]
+fn pick_should_keep_header_on_same_backend_until_it_is_ejected() {
+    let load_balancer = LoadBalancer::new(
+        UPSTREAMS,
+        BalanceStrategy::HashByHeader("X-User-Id".to_string()),
+    )
+    .with_ejection(1, Duration::from_secs(60));
+    let users: Vec<String> = (0..20).map(|user| format!("user-{user}")).collect();
+    let sticky: Vec<String> = users
+        .iter()
+        .map(|user| pick_url(&load_balancer, &create_request(Some(user))))
+        .collect();
+
+    let repeated: Vec<String> = users
+        .iter()
+        .map(|user| pick_url(&load_balancer, &create_request(Some(user))))
+        .collect();
+    load_balancer.record_failure(&load_balancer.backends[0]);
+    let after_ejection: Vec<String> = users
+        .iter()
+        .map(|user| pick_url(&load_balancer, &create_request(Some(user))))
+        .collect();
+
+    assert_eq!(sticky, repeated);
+    for (before, after) in sticky.iter().zip(&after_ejection) {
+        assert_ne!(UPSTREAMS[0], after);
+        if before != UPSTREAMS[0] {
+            assert_eq!(before, after);
+        }
+    }
+}
+
+#[test]
+fn record_failure_should_eject_backend_until_it_succeeds() {
+    let load_balancer = LoadBalancer::new(&UPSTREAMS[..2], BalanceStrategy::RoundRobin)
+        .with_ejection(2, Duration::from_secs(60));
+    let backend = &load_balancer.backends[0];
+
+    load_balancer.record_failure(backend);
+    let available_after_one_failure = backend.is_available();
+    load_balancer.record_failure(backend);
+    let picked_while_ejected: Vec<String> = (0..3)
+        .map(|_| pick_url(&load_balancer, &create_request(None)))
+        .collect();
+    load_balancer.record_success(backend);
+
+    assert!(available_after_one_failure);
+    assert!(picked_while_ejected.iter().all(|url| url == UPSTREAMS[1]));
+    assert!(backend.is_available());
+}
+
+#[test]
+fn pick_should_use_every_backend_when_all_are_ejected() {
+    let load_balancer = LoadBalancer::new(&UPSTREAMS[..1], BalanceStrategy::RoundRobin)
+        .with_ejection(1, Duration::from_secs(60));
+
+    load_balancer.record_failure(&load_balancer.backends[0]);
+
+    assert!(!load_balancer.backends[0].is_available());
+    assert_eq!(
+        UPSTREAMS[0],
+        pick_url(&load_balancer, &create_request(None))
+    );
+}
+
+#[test]
+fn check_health_should_eject_unhealthy_backends() {
+    let healthy = start_upstream(vec!["HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n"]);
+    let unhealthy = start_upstream(vec![
+        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n",
+    ]);
+    let load_balancer = LoadBalancer::new(
+        &[
+            &format!("http://{healthy}"),
+            &format!("http://{unhealthy}"),
+            &format!("http://{}", unused_address()),
+        ],
+        BalanceStrategy::RoundRobin,
+    )
+    .with_ejection(1, Duration::from_secs(60))
+    .with_health_check("/health", Duration::from_secs(5));
+
+    load_balancer.check_health(&Client::new().with_timeout(Duration::from_secs(1)));
+
+    assert!(load_balancer.backends[0].is_available());
+    assert!(!load_balancer.backends[1].is_available());
+    assert!(!load_balancer.backends[2].is_available());
+}
+
+#[test]
+fn balanced_proxy_should_eject_failing_upstream_and_use_the_rest() {
+    let live = start_upstream(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nlive",
+        "HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nlive",
+    ]);
+    let load_balancer = Arc::new(
+        LoadBalancer::new(
+            &[
+                &format!("http://{}", unused_address()),
+                &format!("http://{live}"),
+            ],
+            BalanceStrategy::RoundRobin,
+        )
+        .with_ejection(1, Duration::from_secs(60)),
+    );
+    let proxy = Proxy::balanced("/api", Arc::clone(&load_balancer))
+        .with_client(Client::new().with_timeout(Duration::from_secs(1)));
+    let forward = || {
+        proxy.forward(Request::new(
+            "/api/users",
+            http::methods::GET,
+            None,
+            HashMap::from([("proxy_path".to_string(), "users".to_string())]),
+        ))
+    };
+
+    let first = forward();
+    let second = forward();
+    let third = forward();
+
+    assert_eq!(Status::BadGateway, first.status);
+    assert_eq!("live", second.content);
+    assert_eq!("live", third.content);
+    assert!(!load_balancer.backends[0].is_available());
+}
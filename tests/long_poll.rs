@@ -0,0 +1,161 @@
+use http::{response::Response, status::Status};
+use minimal_api::server::{long_poll::LongPolls, shutdown::Shutdown, thread_pool::ThreadPool};
+use std::{
+    io::Write,
+    net::{TcpListener, TcpStream},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    time::{Duration, Instant},
+};
+
+fn create_long_polls() -> &'static LongPolls {
+    Box::leak(Box::new(LongPolls::new()))
+}
+
+fn create_pool() -> Arc<ThreadPool> {
+    Arc::new(ThreadPool::new(2))
+}
+
+#[test]
+fn park_should_return_right_away_while_the_long_poll_waits() {
+    let shutdown = Shutdown::new();
+    let long_polls = create_long_polls();
+    long_polls.spawn_polls(shutdown.clone()).unwrap();
+    let is_ready = Arc::new(AtomicBool::new(false));
+    let poll_ready = is_ready.clone();
+    let (sender, receiver) = mpsc::channel();
+    let started = Instant::now();
+
+    long_polls.park(
+        Response::long_poll(Duration::from_secs(5), move || {
+            poll_ready
+                .load(Ordering::SeqCst)
+                .then(|| "ready".to_string())
+        }),
+        Box::new(move |answer| sender.send(answer).unwrap()),
+        create_pool(),
+    );
+    let parked_for = started.elapsed();
+    let waiting = long_polls.len();
+    is_ready.store(true, Ordering::SeqCst);
+    let answer = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    shutdown.trigger();
+
+    assert!(parked_for < Duration::from_secs(1));
+    assert_eq!(1, waiting);
+    assert_eq!(Status::Ok, answer.status);
+    assert_eq!("ready", answer.content);
+    assert!(long_polls.is_empty());
+}
+
+#[test]
+fn poll_parked_should_answer_with_no_content_after_the_timeout() {
+    let shutdown = Shutdown::new();
+    let long_polls = create_long_polls();
+    long_polls.spawn_polls(shutdown.clone()).unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    long_polls.park(
+        Response::long_poll(Duration::from_millis(50), || None),
+        Box::new(move |answer| sender.send(answer).unwrap()),
+        create_pool(),
+    );
+    let answer = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    shutdown.trigger();
+
+    assert_eq!(Status::NoContent, answer.status);
+}
+
+#[test]
+fn poll_parked_should_answer_a_panicking_long_poll_with_server_error() {
+    let shutdown = Shutdown::new();
+    let long_polls = create_long_polls();
+    long_polls.spawn_polls(shutdown.clone()).unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let mut is_first_poll = true;
+
+    long_polls.park(
+        Response::long_poll(Duration::from_secs(5), move || {
+            if is_first_poll {
+                is_first_poll = false;
+                return None;
+            }
+            panic!("lost the message queue")
+        }),
+        Box::new(move |answer| sender.send(answer).unwrap()),
+        create_pool(),
+    );
+    let answer = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    shutdown.trigger();
+
+    assert_eq!(Status::ServerError, answer.status);
+}
+
+#[test]
+fn park_should_wait_for_the_answer_without_a_polling_thread() {
+    let long_polls = LongPolls::new();
+    let (sender, receiver) = mpsc::channel();
+
+    long_polls.park(
+        Response::long_poll(Duration::from_millis(50), || None),
+        Box::new(move |answer| sender.send(answer).unwrap()),
+        create_pool(),
+    );
+
+    assert_eq!(Status::NoContent, receiver.try_recv().unwrap().status);
+}
+
+#[test]
+fn spawn_polls_should_answer_every_waiting_long_poll_on_shutdown() {
+    let shutdown = Shutdown::new();
+    let long_polls = create_long_polls();
+    let polls = long_polls.spawn_polls(shutdown.clone()).unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    long_polls.park(
+        Response::long_poll(Duration::from_secs(60), || None),
+        Box::new(move |answer| sender.send(answer).unwrap()),
+        create_pool(),
+    );
+    shutdown.trigger();
+    polls.join().unwrap();
+
+    let answer = receiver.try_recv().unwrap();
+    assert_eq!(Status::NoContent, answer.status);
+    assert!(!answer.is_pending());
+}
+
+#[test]
+fn poll_parked_should_answer_other_long_polls_while_a_client_never_reads() {
+    let shutdown = Shutdown::new();
+    let long_polls = create_long_polls();
+    long_polls.spawn_polls(shutdown.clone()).unwrap();
+    let pool = create_pool();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let _client_that_never_reads = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut stalled_stream, _) = listener.accept().unwrap();
+    stalled_stream
+        .set_write_timeout(Some(Duration::from_secs(1)))
+        .unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    long_polls.park(
+        Response::long_poll(Duration::from_millis(50), || None),
+        Box::new(move |_| {
+            let _ = stalled_stream.write_all(&vec![b'a'; 64 * 1024 * 1024]);
+        }),
+        pool.clone(),
+    );
+    long_polls.park(
+        Response::long_poll(Duration::from_millis(100), || None),
+        Box::new(move |answer| sender.send(answer).unwrap()),
+        pool,
+    );
+    let answer = receiver.recv_timeout(Duration::from_millis(900));
+    shutdown.trigger();
+
+    assert_eq!(Status::NoContent, answer.unwrap().status);
+}
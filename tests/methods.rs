@@ -0,0 +1,77 @@
+use http::{
+    methods::{self, Method},
+    status::Status,
+};
+
+// ============
+// method tests
+// ============
+#[test]
+fn method_should_parse_from_its_name() {
+    let method = "DELETE".parse::<Method>();
+
+    assert_eq!(Ok(Method::Delete), method);
+}
+
+#[test]
+fn method_should_not_parse_unknown_or_lowercase_names() {
+    assert!("BREW".parse::<Method>().is_err());
+    assert!("get".parse::<Method>().is_err());
+}
+
+#[test]
+fn method_should_display_as_its_name() {
+    for method in [
+        Method::Get,
+        Method::Head,
+        Method::Post,
+        Method::Put,
+        Method::Patch,
+        Method::Delete,
+        Method::Options,
+    ] {
+        assert_eq!(method.as_str(), method.to_string());
+        assert_eq!(Ok(method), method.as_str().parse::<Method>());
+    }
+}
+
+#[test]
+fn join_should_list_methods_for_allow_header() {
+    let result = methods::join(&[methods::GET, methods::PUT, methods::OPTIONS]);
+
+    assert_eq!("GET, PUT, OPTIONS", result);
+}
+
+// ============
+// status tests
+// ============
+#[test]
+fn status_should_have_code_and_reason() {
+    assert_eq!(404, Status::NotFound.code());
+    assert_eq!("NOT FOUND", Status::NotFound.reason());
+    assert_eq!(501, Status::NotImplemented.code());
+    assert_eq!(201, Status::Other(201, "Created".to_string()).code());
+    assert_eq!(
+        "Created",
+        Status::Other(201, "Created".to_string()).reason()
+    );
+}
+
+#[test]
+fn status_should_display_as_code_and_reason() {
+    assert_eq!("500 INTERNAL SERVER ERROR", Status::ServerError.to_string());
+    assert_eq!("501 NOT IMPLEMENTED", Status::NotImplemented.to_string());
+}
+
+#[test]
+fn status_from_code_should_round_trip_known_codes() {
+    for status in [
+        Status::Ok,
+        Status::NotModified,
+        Status::Forbidden,
+        Status::NotImplemented,
+        Status::ServiceUnavailable,
+    ] {
+        assert_eq!(status, Status::from_code(status.code(), status.reason()));
+    }
+}
@@ -0,0 +1,67 @@
+use minimal_api::server::metrics::{AllocationStats, CountingAllocator, Metrics, ResponseWarnings};
+use std::time::Duration;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn counting_allocator_should_count_allocations_and_frees() {
+    let before = AllocationStats::current();
+
+    let buffer = std::hint::black_box(vec![0u8; 64 * 1024]);
+    let during = AllocationStats::current();
+    drop(buffer);
+    let after = AllocationStats::current();
+
+    assert!(during.allocations > before.allocations);
+    assert!(during.allocated_bytes >= before.allocated_bytes + 64 * 1024);
+    assert!(after.freed_bytes >= before.freed_bytes + 64 * 1024);
+    assert!(after.is_counting());
+}
+
+#[test]
+fn with_memory_should_report_allocations_when_counting() {
+    let metrics = Metrics::new().with_memory().to_string();
+
+    assert!(metrics.contains("# TYPE allocations_total counter\nallocations_total "));
+    assert!(metrics.contains("# TYPE allocated_bytes_in_use gauge\n"));
+    if cfg!(target_os = "linux") {
+        assert!(metrics.contains("process_resident_memory_bytes "));
+    }
+}
+
+#[test]
+fn to_string_should_write_prometheus_text_format() {
+    let metrics = Metrics::new()
+        .with_counter("denials_total", "Denied requests.", 3)
+        .with_gauge("sessions", "Stored sessions.", 7);
+
+    assert_eq!(
+        "# HELP denials_total Denied requests.\n# TYPE denials_total counter\ndenials_total 3\n\
+         # HELP sessions Stored sessions.\n# TYPE sessions gauge\nsessions 7\n",
+        metrics.to_string()
+    );
+}
+
+#[test]
+fn response_warnings_should_count_slow_requests_and_large_responses() {
+    let warnings = ResponseWarnings::new(Some(Duration::from_millis(100)), Some(1024));
+
+    warnings.observe("GET /fast", Duration::from_millis(5), 512);
+    warnings.observe("GET /slow", Duration::from_millis(250), 512);
+    warnings.observe("GET /export", Duration::from_millis(5), 4096);
+    warnings.observe("GET /slow/export", Duration::from_millis(250), 4096);
+
+    assert_eq!(2, warnings.slow_requests());
+    assert_eq!(2, warnings.large_responses());
+}
+
+#[test]
+fn response_warnings_should_not_count_without_thresholds() {
+    let warnings = ResponseWarnings::new(None, None);
+
+    warnings.observe("GET /slow/export", Duration::from_secs(60), usize::MAX);
+
+    assert_eq!(0, warnings.slow_requests());
+    assert_eq!(0, warnings.large_responses());
+}
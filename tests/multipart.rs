@@ -0,0 +1,146 @@
+use http::{
+    methods::POST,
+    multipart::{self, Multipart, Part},
+    request::Request,
+    status::Status,
+    validation::{ParamLocation, RouteParam},
+};
+use http_attributes::http_post;
+use std::collections::HashMap;
+
+const CONTENT_TYPE: &str = "multipart/form-data; boundary=XyZ";
+
+// =================
+// endpoints to test
+// =================
+#[http_post("/multipart/avatar")]
+fn upload_avatar(user: String, avatar: Part) -> String {
+    format!(
+        "\"{user} uploaded {} ({}, {} bytes)\"",
+        avatar.filename.unwrap_or_default(),
+        avatar.content_type.unwrap_or_default(),
+        avatar.bytes.len()
+    )
+}
+
+fn create_body() -> Vec<u8> {
+    let mut body = b"--XyZ\r\n\
+        Content-Disposition: form-data; name=\"user\"\r\n\r\n\
+        evan\r\n\
+        --XyZ\r\n\
+        Content-Disposition: form-data; name=\"avatar\"; filename=\"me.png\"\r\n\
+        Content-Type: image/png\r\n\r\n"
+        .to_vec();
+    body.extend_from_slice(&[0x89, b'P', b'N', b'G', 0xff, 0x00]);
+    body.extend_from_slice(b"\r\n--XyZ--\r\n");
+    body
+}
+
+fn create_request(multipart: Option<Multipart>) -> Request {
+    let request = Request::new("/multipart/avatar", POST, None, HashMap::new()).with_headers(
+        HashMap::from([("Content-Type".to_string(), CONTENT_TYPE.to_string())]),
+    );
+    match multipart {
+        Some(multipart) => request.with_extension(multipart),
+        None => request,
+    }
+}
+
+// ===============
+// multipart tests
+// ===============
+#[test]
+fn parse_should_read_every_part_in_order() {
+    let multipart = Multipart::parse(&create_body(), CONTENT_TYPE).unwrap();
+
+    let names: Vec<&str> = multipart
+        .parts()
+        .iter()
+        .map(|part| part.name.as_str())
+        .collect();
+    assert_eq!(vec!["user", "avatar"], names);
+    assert_eq!(
+        Some("evan".to_string()),
+        multipart.part("user").unwrap().text()
+    );
+    assert!(!multipart.part("user").unwrap().is_file());
+}
+
+#[test]
+fn parse_should_keep_file_bytes_as_sent() {
+    let multipart = Multipart::parse(&create_body(), CONTENT_TYPE).unwrap();
+    let avatar = multipart.part("avatar").unwrap();
+
+    assert_eq!(Some("me.png".to_string()), avatar.filename);
+    assert_eq!(Some("image/png".to_string()), avatar.content_type);
+    assert_eq!(vec![0x89, b'P', b'N', b'G', 0xff, 0x00], avatar.bytes);
+}
+
+#[test]
+fn parse_should_reject_bodies_without_their_boundary() {
+    assert!(Multipart::parse(&create_body(), "multipart/form-data").is_err());
+    assert!(Multipart::parse(&create_body(), "application/json").is_err());
+    assert!(Multipart::parse(b"--XyZ\r\nno end", CONTENT_TYPE).is_err());
+}
+
+#[test]
+fn is_multipart_should_ignore_case_and_parameters() {
+    assert!(multipart::is_multipart(Some(
+        "Multipart/Form-Data; boundary=a"
+    )));
+    assert!(!multipart::is_multipart(Some("application/json")));
+    assert!(!multipart::is_multipart(None));
+}
+
+#[test]
+fn handler_should_bind_files_and_text_parts_to_arguments() {
+    let multipart = Multipart::parse(&create_body(), CONTENT_TYPE).unwrap();
+
+    let response = upload_avatar().get_response(create_request(Some(multipart)));
+
+    assert_eq!(Status::Ok, response.status);
+    assert!(
+        response
+            .content
+            .contains("evan uploaded me.png (image/png, 6 bytes)")
+    );
+}
+
+#[test]
+fn handler_should_reject_a_missing_file_part() {
+    let response = upload_avatar().get_response(create_request(None));
+
+    assert_eq!(Status::BadRequest, response.status);
+}
+
+#[test]
+fn route_params_should_mark_part_arguments_as_files() {
+    let route = upload_avatar();
+
+    assert_eq!(
+        vec![
+            RouteParam::new("user", ParamLocation::Body, "String"),
+            RouteParam::new("avatar", ParamLocation::File, "Part"),
+        ],
+        route.spec.params
+    );
+}
+
+#[test]
+fn validate_should_require_file_parts() {
+    let route = upload_avatar();
+    let multipart = Multipart::parse(&create_body(), CONTENT_TYPE).unwrap();
+
+    assert!(
+        route
+            .spec
+            .validate(&create_request(Some(multipart)))
+            .is_empty()
+    );
+    let violations = route.spec.validate(&create_request(None));
+    assert!(
+        violations
+            .iter()
+            .any(|violation| violation.location == ParamLocation::File)
+    );
+}
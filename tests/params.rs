@@ -0,0 +1,273 @@
+use http::{
+    params::{FromQuery, PathParams, Uuid},
+    request::Request,
+    response::Response,
+    status::Status,
+    validation::{ParamLocation, RouteParam},
+};
+use http_attributes::{FromQuery, PathParams, http_get, http_post};
+use std::collections::HashMap;
+
+// ===================
+// common params types
+// ===================
+#[derive(Debug, PartialEq, PathParams)]
+struct OrgRepo {
+    org: String,
+    repo: String,
+}
+
+#[derive(Debug, PartialEq, PathParams)]
+struct IssuePath {
+    repo: String,
+    number: u32,
+}
+
+#[derive(Debug, PartialEq, FromQuery)]
+struct SearchParams {
+    /// The text to search for.
+    term: String,
+    page: u32,
+}
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/orgs/{org}/repos/{repo}")]
+fn get_repo(#[path] params: OrgRepo) -> String {
+    format!("{}/{}", params.org, params.repo)
+}
+
+#[http_post("/repos/{repo}/issues/{number}")]
+fn comment_on_issue(#[path] issue: IssuePath, comment: String) -> String {
+    format!("{}#{}: {comment}", issue.repo, issue.number)
+}
+
+#[http_get("/search")]
+fn search(#[query] params: SearchParams) -> String {
+    format!("{} on page {}", params.term, params.page)
+}
+
+#[http_get("/orgs/{org}/repos/{repo}/search", generate_tests)]
+fn search_repo(#[path] repo: OrgRepo, #[query] params: SearchParams) -> String {
+    format!("{}/{}: {}", repo.org, repo.repo, params.term)
+}
+
+#[http_get("/orders/{id}", generate_tests)]
+fn get_order(id: Uuid) -> String {
+    format!("\"order {id}\"")
+}
+
+fn create_path_params(params: &[(&str, &str)]) -> HashMap<String, String> {
+    params
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+// ============
+// params tests
+// ============
+#[test]
+fn from_path_params_should_bind_every_field() {
+    let path_params = create_path_params(&[("repo", "cargo"), ("number", "42")]);
+
+    assert_eq!(
+        Ok(IssuePath {
+            repo: "cargo".to_string(),
+            number: 42
+        }),
+        IssuePath::from_path_params(&path_params)
+    );
+}
+
+#[test]
+fn from_path_params_should_name_missing_or_invalid_parameter() {
+    assert_eq!(
+        Err("missing path parameter number".to_string()),
+        IssuePath::from_path_params(&create_path_params(&[("repo", "cargo")]))
+    );
+    assert_eq!(
+        Err("path parameter number must be of type u32".to_string()),
+        IssuePath::from_path_params(&create_path_params(&[("repo", "cargo"), ("number", "x")]))
+    );
+}
+
+#[test]
+fn path_attribute_should_bind_path_params_into_struct() {
+    let request = Request::new(
+        "/orgs/rust/repos/cargo",
+        http::methods::GET,
+        None,
+        create_path_params(&[("org", "rust"), ("repo", "cargo")]),
+    );
+
+    let response = get_repo().get_response(request);
+
+    assert_eq!(Response::ok("rust/cargo", false), response);
+}
+
+#[test]
+fn path_attribute_should_leave_body_for_next_argument() {
+    let route = comment_on_issue();
+    let request = Request::new(
+        "/repos/cargo/issues/42",
+        http::methods::POST,
+        Some("thanks!".to_string()),
+        create_path_params(&[("repo", "cargo"), ("number", "42")]),
+    );
+
+    let response = route.get_response(request);
+
+    assert_eq!(Response::ok("cargo#42: thanks!", false), response);
+    assert_eq!(
+        vec![
+            RouteParam::new("repo", ParamLocation::Path, "String"),
+            RouteParam::new("number", ParamLocation::Path, "u32"),
+            RouteParam::new("comment", ParamLocation::Body, "String"),
+        ],
+        route.spec.params
+    );
+}
+
+#[test]
+fn path_attribute_should_answer_an_unparseable_field_with_not_found() {
+    let request = Request::new(
+        "/repos/cargo/issues/latest",
+        http::methods::POST,
+        Some("thanks!".to_string()),
+        create_path_params(&[("repo", "cargo"), ("number", "latest")]),
+    );
+
+    let response = comment_on_issue().get_response(request);
+
+    assert_eq!(Status::NotFound, response.status);
+    assert!(
+        response
+            .content
+            .contains("path parameter number must be of type u32")
+    );
+}
+
+#[test]
+fn from_query_should_bind_every_field() {
+    let request = Request::new(
+        "/search?page=2&term=rust",
+        http::methods::GET,
+        None,
+        HashMap::new(),
+    );
+
+    assert_eq!(
+        Ok(SearchParams {
+            term: "rust".to_string(),
+            page: 2
+        }),
+        SearchParams::from_query(&request)
+    );
+}
+
+#[test]
+fn from_query_should_name_missing_or_invalid_parameter() {
+    let create_request = |path: &str| Request::new(path, http::methods::GET, None, HashMap::new());
+
+    assert_eq!(
+        Err("missing query parameter page".to_string()),
+        SearchParams::from_query(&create_request("/search?term=rust"))
+    );
+    assert_eq!(
+        Err("invalid query parameter page: expected u32".to_string()),
+        SearchParams::from_query(&create_request("/search?term=rust&page=last"))
+    );
+}
+
+#[test]
+fn query_attribute_should_bind_query_params_into_struct() {
+    let route = search();
+    let request = Request::new(
+        "/search?term=rust&page=2",
+        http::methods::GET,
+        None,
+        HashMap::new(),
+    );
+
+    let response = route.get_response(request);
+
+    assert_eq!(Response::ok("rust on page 2", false), response);
+    assert_eq!(
+        vec![
+            RouteParam::new("term", ParamLocation::Query, "String")
+                .with_description("The text to search for."),
+            RouteParam::new("page", ParamLocation::Query, "u32"),
+        ],
+        route.spec.params
+    );
+}
+
+#[test]
+fn query_attribute_should_answer_a_missing_or_invalid_field_with_bad_request() {
+    let create_request = |path: &str| Request::new(path, http::methods::GET, None, HashMap::new());
+
+    let missing = search().get_response(create_request("/search?page=2"));
+    let invalid = search().get_response(create_request("/search?term=rust&page=last"));
+
+    assert_eq!(Status::BadRequest, missing.status);
+    assert!(missing.content.contains("missing query parameter term"));
+    assert_eq!(Status::BadRequest, invalid.status);
+    assert!(
+        invalid
+            .content
+            .contains("invalid query parameter page: expected u32")
+    );
+}
+
+#[test]
+fn uuid_should_parse_and_display_the_hyphenated_form() {
+    let uuid: Uuid = "67E55044-10B1-426F-9247-BB680E5FE0C8".parse().unwrap();
+
+    assert_eq!("67e55044-10b1-426f-9247-bb680e5fe0c8", uuid.to_string());
+    assert_eq!(
+        "00000000-0000-0000-0000-000000000000",
+        Uuid::default().to_string()
+    );
+}
+
+#[test]
+fn uuid_should_not_parse_anything_else() {
+    for value in [
+        "",
+        "42",
+        "67e5504410b1426f9247bb680e5fe0c8",
+        "67e55044-10b1-426f-9247-bb680e5fe0c",
+        "67e55044-10b1-426f-9247-bb680e5fe0cg",
+        "+7e55044-10b1-426f-9247-bb680e5fe0c8",
+    ] {
+        assert!(value.parse::<Uuid>().is_err(), "{value} parsed");
+    }
+}
+
+#[test]
+fn uuid_path_parameter_should_answer_anything_else_with_not_found() {
+    let create_request = |id: &str| {
+        Request::new(
+            &format!("/orders/{id}"),
+            http::methods::GET,
+            None,
+            create_path_params(&[("id", id)]),
+        )
+    };
+
+    let found = get_order().get_response(create_request("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+    let missing = get_order().get_response(create_request("42"));
+
+    assert_eq!(
+        Response::ok("\"order 67e55044-10b1-426f-9247-bb680e5fe0c8\"", false),
+        found
+    );
+    assert_eq!(Status::NotFound, missing.status);
+    assert!(
+        missing
+            .content
+            .contains("invalid path parameter id: expected Uuid")
+    );
+}
@@ -0,0 +1,198 @@
+use http::{
+    intern::HeaderName,
+    parser::{self, ParsingMode},
+};
+use std::io::Cursor;
+
+// ==================
+// common test values
+// ==================
+const FOLDED_REQUEST: &str = "GET / HTTP/1.1\r\nX-Folded: first\r\n second\r\n\r\n";
+const DUPLICATE_LENGTH_REQUEST: &str =
+    "POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 0\r\n\r\nhello";
+const BARE_LF_REQUEST: &str = "GET / HTTP/1.1\nHost: localhost\n\n";
+
+fn read(request: &str, mode: ParsingMode) -> Result<parser::RequestHead, String> {
+    parser::read_request_head(&mut Cursor::new(request.as_bytes()), mode)
+}
+
+// =================
+// parser mode tests
+// =================
+#[test]
+fn parsing_mode_should_parse_from_config_value() {
+    assert_eq!(Ok(ParsingMode::Strict), "strict".parse());
+    assert_eq!(Ok(ParsingMode::Lenient), "Lenient".parse());
+    assert!("loose".parse::<ParsingMode>().is_err());
+}
+
+#[test]
+fn read_request_head_should_read_request_line_and_headers() {
+    let request = "POST /items?id=1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\nhello";
+
+    let result = read(request, ParsingMode::Strict).unwrap();
+
+    assert_eq!("POST", result.method);
+    assert_eq!("/items?id=1", result.target);
+    assert_eq!("HTTP/1.1", result.version);
+    assert_eq!(
+        Some(&"localhost".to_string()),
+        result.headers.get(&HeaderName::new("host"))
+    );
+    assert_eq!(5, result.content_length);
+}
+
+#[test]
+fn read_request_head_should_error_with_malformed_request_line() {
+    assert!(read("GET /\r\n\r\n", ParsingMode::Lenient).is_err());
+    assert!(read("", ParsingMode::Lenient).is_err());
+}
+
+#[test]
+fn read_request_head_should_join_repeated_headers() {
+    let request = "GET / HTTP/1.1\r\nAccept: text/html\r\naccept: application/json\r\n\r\n";
+
+    let result = read(request, ParsingMode::Strict).unwrap();
+
+    assert_eq!(
+        Some(&"text/html, application/json".to_string()),
+        result.headers.get(&HeaderName::new("accept"))
+    );
+}
+
+#[test]
+fn read_request_head_should_error_with_conflicting_content_length_in_any_mode() {
+    assert!(read(DUPLICATE_LENGTH_REQUEST, ParsingMode::Lenient).is_err());
+    assert!(read(DUPLICATE_LENGTH_REQUEST, ParsingMode::Strict).is_err());
+}
+
+#[test]
+fn read_request_head_should_error_with_content_length_and_transfer_encoding() {
+    let request =
+        "POST / HTTP/1.1\r\nContent-Length: 4\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n";
+
+    assert!(read(request, ParsingMode::Lenient).is_err());
+    assert!(read(request, ParsingMode::Strict).is_err());
+}
+
+#[test]
+fn read_request_head_should_error_with_non_numeric_content_length() {
+    let request = "POST / HTTP/1.1\r\nContent-Length: 5, 5\r\n\r\n";
+
+    assert!(read(request, ParsingMode::Lenient).is_err());
+}
+
+// =================
+// strict mode tests
+// =================
+#[test]
+fn strict_mode_should_reject_obsolete_line_folding() {
+    assert!(read(FOLDED_REQUEST, ParsingMode::Strict).is_err());
+}
+
+#[test]
+fn strict_mode_should_reject_duplicate_content_length() {
+    assert!(read(DUPLICATE_LENGTH_REQUEST, ParsingMode::Strict).is_err());
+}
+
+#[test]
+fn strict_mode_should_reject_bare_lf_line_endings() {
+    assert!(read(BARE_LF_REQUEST, ParsingMode::Strict).is_err());
+}
+
+#[test]
+fn strict_mode_should_reject_headers_without_terminating_blank_line() {
+    assert!(read("GET / HTTP/1.1\r\nHost: localhost\r\n", ParsingMode::Strict).is_err());
+}
+
+// ==================
+// lenient mode tests
+// ==================
+#[test]
+fn lenient_mode_should_unfold_obsolete_line_folding() {
+    let result = read(FOLDED_REQUEST, ParsingMode::Lenient).unwrap();
+
+    assert_eq!(
+        Some(&"first second".to_string()),
+        result.headers.get(&HeaderName::new("x-folded"))
+    );
+}
+
+#[test]
+fn lenient_mode_should_accept_duplicate_matching_content_length() {
+    let request = "POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\nhello";
+
+    let result = read(request, ParsingMode::Lenient).unwrap();
+
+    assert_eq!(5, result.content_length);
+    assert_eq!(
+        Some(&"5".to_string()),
+        result.headers.get(&HeaderName::new("content-length"))
+    );
+}
+
+#[test]
+fn lenient_mode_should_accept_bare_lf_line_endings() {
+    let result = read(BARE_LF_REQUEST, ParsingMode::Lenient).unwrap();
+
+    assert_eq!(
+        Some(&"localhost".to_string()),
+        result.headers.get(&HeaderName::new("host"))
+    );
+}
+
+#[test]
+fn lenient_mode_should_still_reject_folding_without_previous_header() {
+    assert!(read("GET / HTTP/1.1\r\n folded\r\n\r\n", ParsingMode::Lenient).is_err());
+}
+
+#[test]
+fn split_header_line_should_find_colon_at_any_position() {
+    for name_length in 1..20 {
+        let name = "x".repeat(name_length);
+        let line = format!("{name}:  value with: colon ");
+
+        let (result_name, result_value) = parser::split_header_line(line.as_bytes()).unwrap();
+
+        assert_eq!(name.as_bytes(), result_name);
+        assert_eq!(b"value with: colon", result_value);
+    }
+}
+
+#[test]
+fn split_header_line_should_return_none_without_colon() {
+    assert_eq!(
+        None,
+        parser::split_header_line(b"no colon in this long header line")
+    );
+    assert_eq!(None, parser::split_header_line(b""));
+}
+
+#[test]
+fn read_request_head_should_reject_header_values_that_are_not_utf8() {
+    let mut reader = Cursor::new(b"GET / HTTP/1.1\r\nX-Bad: \xff\xfe\r\n\r\n".to_vec());
+
+    let result = parser::read_request_head(&mut reader, ParsingMode::Strict);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn read_request_head_should_error_with_a_head_that_is_too_large() {
+    let long_header = format!(
+        "GET / HTTP/1.1\r\nX-Long: {}\r\n\r\n",
+        "x".repeat(parser::MAX_REQUEST_HEAD_BYTES)
+    );
+    let many_headers = format!(
+        "GET / HTTP/1.1\r\n{}\r\n",
+        "X-Many: 1\r\n".repeat(parser::MAX_REQUEST_HEADERS + 1)
+    );
+    let just_enough_headers = format!(
+        "GET / HTTP/1.1\r\n{}\r\n",
+        "X-Many: 1\r\n".repeat(parser::MAX_REQUEST_HEADERS)
+    );
+
+    assert!(read(&long_header, ParsingMode::Strict).is_err());
+    assert!(read(&many_headers, ParsingMode::Strict).is_err());
+    assert!(read(&just_enough_headers, ParsingMode::Strict).is_ok());
+}
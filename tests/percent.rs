@@ -0,0 +1,53 @@
+use http::{methods::GET, percent, request::Request, router::Router, status::Status};
+use http_attributes::http_get;
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/folders/{name}", encoded_slashes)]
+fn get_folder(name: String) -> String {
+    format!("\"{name}\"")
+}
+
+#[http_get("/users/{name}")]
+fn get_user(name: String) -> String {
+    format!("\"{name}\"")
+}
+
+// =============
+// percent tests
+// =============
+#[test]
+fn decode_should_decode_percent_escapes() {
+    assert_eq!(
+        Ok("hello world".to_string()),
+        percent::decode("hello%20world")
+    );
+    assert_eq!(Ok("josé".to_string()), percent::decode("jos%c3%A9"));
+    assert_eq!(Ok("a/b".to_string()), percent::decode("a%2Fb"));
+    assert_eq!(Ok("plain+text".to_string()), percent::decode("plain+text"));
+}
+
+#[test]
+fn decode_should_fail_on_invalid_escapes_or_utf8() {
+    assert!(percent::decode("%").is_err());
+    assert!(percent::decode("%4").is_err());
+    assert!(percent::decode("%+1").is_err());
+    assert!(percent::decode("%FF").is_err());
+}
+
+#[test]
+fn router_should_give_handlers_decoded_path_params() {
+    let router = Router::new(vec![get_user(), get_folder()]);
+
+    let (user_route, user_params) = router.resolve(GET, "/users/zo%C3%AB").unwrap();
+    let (folder_route, folder_params) = router.resolve(GET, "/folders/a%2Fb").unwrap();
+    let user = user_route.get_response(Request::new("/users/zo%C3%AB", GET, None, user_params));
+    let folder =
+        folder_route.get_response(Request::new("/folders/a%2Fb", GET, None, folder_params));
+
+    assert_eq!(Status::Ok, user.status);
+    assert!(user.content.contains("zoë"));
+    assert!(folder.content.contains("a/b"));
+    assert!(router.resolve(GET, "/users/a%2Fb").is_none());
+}
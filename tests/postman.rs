@@ -0,0 +1,66 @@
+use http::{
+    json::Json,
+    postman::{self, POSTMAN_SCHEMA},
+    response::Response,
+    route::Route,
+    validation::{ParamLocation, RouteParam},
+};
+
+#[test]
+fn export_collection_should_describe_every_route() {
+    let routes = vec![
+        Route::get("/user/{id}", Box::new(|_| Response::ok("\"user\"", false)))
+            .with_params(vec![
+                RouteParam::new("id", ParamLocation::Path, "i32"),
+                RouteParam::new("verbose", ParamLocation::Query, "bool"),
+            ])
+            .spec,
+        Route::post("/sendname", Box::new(|_| Response::ok("\"sent\"", false)))
+            .with_params(vec![RouteParam::new("name", ParamLocation::Body, "String")])
+            .spec,
+    ];
+
+    let collection = postman::export_collection(&routes, "Users", "http://localhost:8080/");
+
+    let info = collection.get("info").unwrap();
+    assert_eq!(Some("Users"), info.get("name").and_then(Json::as_str));
+    assert_eq!(
+        Some(POSTMAN_SCHEMA),
+        info.get("schema").and_then(Json::as_str)
+    );
+    let variable = &collection.get("variable").unwrap().as_array().unwrap()[0];
+    assert_eq!(
+        Some("http://localhost:8080"),
+        variable.get("value").and_then(Json::as_str)
+    );
+    let items = collection.get("item").unwrap().as_array().unwrap();
+    assert_eq!(2, items.len());
+    let user_request = items[0].get("request").unwrap();
+    let url = user_request.get("url").unwrap();
+    assert_eq!(
+        Some("{{baseUrl}}/user/:id?verbose="),
+        url.get("raw").and_then(Json::as_str)
+    );
+    assert_eq!(
+        Some("i32"),
+        url.get("variable").unwrap().as_array().unwrap()[0]
+            .get("description")
+            .and_then(Json::as_str)
+    );
+    assert!(user_request.get("body").is_none());
+    let send_request = items[1].get("request").unwrap();
+    assert_eq!(
+        Some("POST"),
+        send_request.get("method").and_then(Json::as_str)
+    );
+    assert!(send_request.get("body").is_some());
+}
+
+#[test]
+fn export_collection_should_be_valid_json() {
+    let routes = vec![Route::get("/", Box::new(|_| Response::ok("\"hi\"", false))).spec];
+
+    let collection = postman::export_collection(&routes, "Root", "http://localhost:8080");
+
+    assert_eq!(Ok(collection.clone()), Json::parse(&collection.to_string()));
+}
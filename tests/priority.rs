@@ -0,0 +1,166 @@
+use minimal_api::server::{
+    metrics::METRICS_PATH,
+    priority::{self, PRIORITY_PATHS, PRIORITY_PEEK_TIMEOUT},
+    readiness::READINESS_PATH,
+    thread_pool::ThreadPool,
+};
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex, mpsc},
+    time::{Duration, Instant},
+};
+
+// ==================
+// common test values
+// ==================
+/// connects to a local listener, sends `request`, and returns the accepted side of
+/// the connection
+fn accept_with(request: &[u8]) -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    client.write_all(request).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    (server, client)
+}
+
+// ==============
+// priority tests
+// ==============
+#[test]
+fn is_priority_request_should_match_health_and_admin_paths() {
+    for path in [READINESS_PATH, METRICS_PATH] {
+        let (server, _client) = accept_with(format!("GET {path} HTTP/1.1\r\n\r\n").as_bytes());
+
+        assert!(priority::is_priority_request(
+            &server,
+            &PRIORITY_PATHS,
+            PRIORITY_PEEK_TIMEOUT
+        ));
+    }
+}
+
+#[test]
+fn is_priority_request_should_not_match_other_requests() {
+    for request in [
+        "GET /reports HTTP/1.1\r\n\r\n".to_string(),
+        format!("POST {READINESS_PATH} HTTP/1.1\r\n\r\n"),
+        format!("GET {READINESS_PATH}extra HTTP/1.1\r\n\r\n"),
+    ] {
+        let (server, _client) = accept_with(request.as_bytes());
+
+        assert!(!priority::is_priority_request(
+            &server,
+            &PRIORITY_PATHS,
+            PRIORITY_PEEK_TIMEOUT
+        ));
+    }
+}
+
+#[test]
+fn is_priority_request_should_leave_the_request_on_the_connection() {
+    let request = format!("HEAD {READINESS_PATH}?verbose=true HTTP/1.1\r\n\r\n");
+    let (mut server, _client) = accept_with(request.as_bytes());
+
+    let is_priority =
+        priority::is_priority_request(&server, &PRIORITY_PATHS, PRIORITY_PEEK_TIMEOUT);
+    let mut received = vec![0; request.len()];
+    server.read_exact(&mut received).unwrap();
+
+    assert!(is_priority);
+    assert_eq!(request.as_bytes(), received);
+    assert_eq!(None, server.read_timeout().unwrap());
+}
+
+#[test]
+fn is_priority_request_should_give_up_on_a_silent_connection() {
+    let (server, _client) = accept_with(b"");
+
+    assert!(!priority::is_priority_request(
+        &server,
+        &PRIORITY_PATHS,
+        Duration::from_millis(10)
+    ));
+}
+
+#[test]
+fn thread_pool_should_be_saturated_while_every_worker_is_busy() {
+    let pool = ThreadPool::new(1);
+    let (release, released) = mpsc::channel::<()>();
+    let (finished, has_finished) = mpsc::channel();
+
+    pool.execute(move || {
+        released.recv().unwrap();
+        finished.send(()).unwrap();
+    });
+    let saturated = pool.is_saturated();
+    release.send(()).unwrap();
+    has_finished.recv().unwrap();
+    while pool.pending() > 0 {
+        std::thread::yield_now();
+    }
+
+    assert!(saturated);
+    assert!(!pool.is_saturated());
+}
+
+#[test]
+fn thread_pool_should_run_jobs_on_every_worker_at_the_same_time() {
+    let pool = ThreadPool::new(4);
+    let (finished, has_finished) = mpsc::channel();
+    let started = Instant::now();
+
+    for _ in 0..4 {
+        let finished = finished.clone();
+        pool.execute(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            finished.send(()).unwrap();
+        });
+    }
+    for _ in 0..4 {
+        has_finished.recv().unwrap();
+    }
+
+    // one after another, the jobs would take at least 1.2 seconds
+    assert!(started.elapsed() < Duration::from_millis(900));
+}
+
+#[test]
+fn lazy_thread_pool_should_only_start_workers_once_every_started_one_is_busy() {
+    let pool = ThreadPool::lazy(2);
+    let (release, released) = mpsc::channel::<()>();
+    let released = Arc::new(Mutex::new(released));
+
+    let started_before = pool.started();
+    for _ in 0..2 {
+        let released = Arc::clone(&released);
+        pool.execute(move || released.lock().unwrap().recv().unwrap());
+    }
+    let started_after = pool.started();
+    release.send(()).unwrap();
+    release.send(()).unwrap();
+
+    assert_eq!(0, started_before);
+    assert_eq!(2, started_after);
+    assert!(pool.ready().is_ready());
+}
+
+#[test]
+fn thread_pool_should_be_ready_once_every_worker_has_warmed_up() {
+    let (release, released) = mpsc::channel::<()>();
+    let released = Mutex::new(released);
+    let pool = ThreadPool::with_warm_up(
+        1,
+        Arc::new(move || released.lock().unwrap().recv().unwrap()),
+    );
+    let ready = pool.ready();
+
+    let was_ready = ready.is_ready();
+    release.send(()).unwrap();
+    while !ready.is_ready() {
+        std::thread::yield_now();
+    }
+
+    assert!(!was_ready);
+    assert_eq!(1, pool.started());
+}
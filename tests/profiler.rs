@@ -0,0 +1,100 @@
+use minimal_api::server::profiler::{DEFAULT_PROFILE_DURATION, MAX_PROFILE_DURATION, Profiler};
+use std::{
+    fmt,
+    sync::{Arc, Barrier},
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn profile_should_report_collapsed_stacks_of_busy_threads() {
+    let profiler = Arc::new(Profiler::new(true));
+    let started = Arc::new(Barrier::new(2));
+    let sampler = {
+        let profiler = Arc::clone(&profiler);
+        let started = Arc::clone(&started);
+        thread::spawn(move || {
+            started.wait();
+            profiler.profile(Duration::from_millis(200), Duration::from_millis(5))
+        })
+    };
+    started.wait();
+    while !profiler.is_running() {
+        thread::yield_now();
+    }
+
+    thread::Builder::new()
+        .name("worker-1".to_string())
+        .spawn({
+            let profiler = Arc::clone(&profiler);
+            move || {
+                let _connection = profiler.enter("handle_connection");
+                let _route = profiler.enter(format_args!("{} {}", "GET", "/user/{id}"));
+                thread::sleep(Duration::from_millis(100));
+            }
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    let report = sampler.join().unwrap().unwrap();
+
+    let line = report
+        .lines()
+        .find(|line| line.starts_with("worker-1;handle_connection;GET /user/{id} "))
+        .unwrap();
+    let count: u64 = line.rsplit(' ').next().unwrap().parse().unwrap();
+    assert!(count > 0);
+}
+
+#[test]
+fn enter_should_not_record_frames_when_no_profile_is_running() {
+    let profiler = Profiler::new(true);
+
+    let _frame = profiler.enter("handle_connection");
+    let report = profiler
+        .profile(Duration::from_millis(20), Duration::from_millis(5))
+        .unwrap();
+
+    assert!(report.is_empty());
+}
+
+#[test]
+fn enter_should_not_format_frames_when_no_profile_is_running() {
+    struct PanicsWhenFormatted;
+    impl fmt::Display for PanicsWhenFormatted {
+        fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+            panic!("a frame was formatted without a running profile")
+        }
+    }
+    let profiler = Profiler::new(true);
+
+    let _frame = profiler.enter(PanicsWhenFormatted);
+}
+
+#[test]
+fn profile_should_fail_when_not_enabled() {
+    let profiler = Profiler::new(false);
+
+    assert!(
+        profiler
+            .profile(Duration::from_millis(10), Duration::from_millis(5))
+            .is_err()
+    );
+}
+
+#[test]
+fn duration_from_query_should_clamp_and_default() {
+    assert_eq!(
+        Duration::from_secs(5),
+        Profiler::duration_from_query("seconds=5").unwrap()
+    );
+    assert_eq!(
+        MAX_PROFILE_DURATION,
+        Profiler::duration_from_query("other=1&seconds=3600").unwrap()
+    );
+    assert_eq!(
+        DEFAULT_PROFILE_DURATION,
+        Profiler::duration_from_query("").unwrap()
+    );
+    assert!(Profiler::duration_from_query("seconds=soon").is_err());
+}
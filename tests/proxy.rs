@@ -0,0 +1,247 @@
+use http::{
+    circuit_breaker::CircuitBreaker,
+    client::{Client, ClientRequest},
+    methods::{GET, Method, POST},
+    proxy::Proxy,
+    request::Request,
+    route::Route,
+    status::Status,
+};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener},
+    sync::{Arc, mpsc},
+    thread,
+    time::Duration,
+};
+
+/// starts an upstream that answers `responses` in order, and sends back the head of
+/// every request it receives
+fn start_upstream(responses: Vec<&'static str>) -> (SocketAddr, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            sender.send(head).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (address, receiver)
+}
+
+fn find_route<'a>(routes: &'a [Route], method: Method, path: &str) -> (&'a Route, Request) {
+    let route = routes
+        .iter()
+        .find(|route| route.spec.method == method && route.spec.matches_path(path).is_some())
+        .unwrap();
+    let request = Request::new(
+        path,
+        method,
+        None,
+        route
+            .spec
+            .matches_path(path.split('?').next().unwrap())
+            .unwrap(),
+    );
+    (route, request)
+}
+
+#[test]
+fn send_should_read_content_length_and_chunked_bodies() {
+    let (address, _requests) = start_upstream(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello",
+        "HTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2;ext=1\r\nde\r\n0\r\n\r\n",
+    ]);
+    let client = Client::new();
+
+    let first = client
+        .send(&ClientRequest::new(
+            "GET",
+            &format!("http://{address}/first"),
+        ))
+        .unwrap();
+    let second = client
+        .send(&ClientRequest::new("POST", &format!("http://{address}")).with_body(b"{}"))
+        .unwrap();
+
+    assert_eq!(200, first.status_code);
+    assert_eq!("hello", first.body_as_string());
+    assert_eq!(201, second.status_code);
+    assert_eq!("Created", second.reason);
+    assert_eq!("abcde", second.body_as_string());
+}
+
+#[test]
+fn send_should_error_for_bodies_over_the_max_response_size() {
+    let (address, _requests) = start_upstream(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 9999999999\r\n\r\nhello",
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\nffffffff\r\nde\r\n0\r\n\r\n",
+        "HTTP/1.1 200 OK\r\n\r\nhello world",
+        "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello",
+    ]);
+    let client = Client::new().with_max_response_bytes(5);
+    let url = format!("http://{address}");
+
+    let declared_too_large = client.send(&ClientRequest::new("GET", &url));
+    let chunked_too_large = client.send(&ClientRequest::new("GET", &url));
+    let unframed_too_large = client.send(&ClientRequest::new("GET", &url));
+    let within_limit = client.send(&ClientRequest::new("GET", &url));
+
+    assert!(
+        declared_too_large
+            .unwrap_err()
+            .contains("over the 5 byte limit")
+    );
+    assert!(
+        chunked_too_large
+            .unwrap_err()
+            .contains("over the 5 byte limit")
+    );
+    assert!(
+        unframed_too_large
+            .unwrap_err()
+            .contains("over the 5 byte limit")
+    );
+    assert_eq!("hello", within_limit.unwrap().body_as_string());
+}
+
+#[test]
+fn send_should_error_when_the_body_is_shorter_than_its_content_length() {
+    let (address, _requests) =
+        start_upstream(vec!["HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\nhello"]);
+
+    let result = Client::new().send(&ClientRequest::new("GET", &format!("http://{address}")));
+
+    assert!(result.unwrap_err().contains("5 of 100"));
+}
+
+#[test]
+fn send_should_error_for_non_http_urls() {
+    let result = Client::new().send(&ClientRequest::new("GET", "https://example.com/"));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn proxy_routes_should_forward_rest_of_path_and_query_to_upstream() {
+    let (address, requests) = start_upstream(vec![
+        "HTTP/1.1 201 Created\r\nContent-Type: text/plain\r\nX-Upstream: yes\r\nConnection: close\r\nContent-Length: 7\r\n\r\ncreated",
+    ]);
+    let routes = Proxy::new("/api", &format!("http://{address}")).routes();
+    let (route, request) = find_route(&routes, GET, "/api/users/42?verbose=true");
+
+    let response = route.get_response(request.with_headers(HashMap::from([
+        ("Accept".to_string(), "text/plain".to_string()),
+        ("Connection".to_string(), "keep-alive".to_string()),
+    ])));
+    let upstream_head = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    assert!(upstream_head.starts_with("GET /users/42?verbose=true HTTP/1.1\r\n"));
+    assert!(upstream_head.contains("accept: text/plain\r\n"));
+    assert!(!upstream_head.contains("keep-alive"));
+    assert_eq!(Status::Created, response.status);
+    assert_eq!("created", response.content);
+    assert_eq!(Some("text/plain"), response.get_header("content-type"));
+    assert_eq!(Some("yes"), response.get_header("x-upstream"));
+    assert_eq!(None, response.get_header("connection"));
+    assert!(response.to_string().starts_with("HTTP/1.1 201 CREATED\r\n"));
+    assert_eq!(1, response.to_string().matches("Content-Type").count());
+}
+
+#[test]
+fn proxy_should_return_bad_gateway_then_fail_fast_when_upstream_is_down() {
+    let address = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let circuit_breaker = Arc::new(
+        CircuitBreaker::new()
+            .with_minimum_calls(2)
+            .with_open_duration(Duration::from_secs(60)),
+    );
+    let client = Client::new()
+        .with_timeout(Duration::from_secs(1))
+        .with_circuit_breaker(Arc::clone(&circuit_breaker));
+    let routes = Proxy::new("/api", &format!("http://{address}"))
+        .with_client(client)
+        .routes();
+    let (route, _) = find_route(&routes, GET, "/api/users");
+
+    let first = route.get_response(find_route(&routes, GET, "/api/users").1);
+    let second = route.get_response(find_route(&routes, GET, "/api/users").1);
+
+    assert_eq!(Status::BadGateway, first.status);
+    assert_eq!(Status::ServiceUnavailable, second.status);
+    assert!(second.get_header("retry-after").is_some());
+}
+
+#[test]
+fn proxy_should_run_response_transforms_before_passing_response_back() {
+    let (address, _requests) = start_upstream(vec![
+        "HTTP/1.1 201 Created\r\nServer: internal/1.0\r\nX-Internal-Trace: abc\r\nLocation: http://127.0.0.1:1/users/7\r\nContent-Length: 15\r\n\r\n{\"legacy_id\":7}",
+    ]);
+    let upstream = format!("http://{address}");
+    let routes = Proxy::new("/api", &upstream)
+        .with_stripped_headers(&["server", "X-INTERNAL-TRACE"])
+        .with_location_rewrite()
+        .with_response_transform(|request, response| {
+            let body = response.body_as_string().replace("legacy_id", "id");
+            response.body = body.into_bytes();
+            response
+                .headers
+                .push(("X-Forwarded-Path".to_string(), request.path.clone()));
+        })
+        .routes();
+    let (route, request) = find_route(&routes, POST, "/api/users");
+
+    let response = route.get_response(request);
+
+    assert_eq!("{\"id\":7}", response.content);
+    assert_eq!(None, response.get_header("server"));
+    assert_eq!(None, response.get_header("x-internal-trace"));
+    assert_eq!(Some("/api/users"), response.get_header("x-forwarded-path"));
+    assert_eq!(
+        Some("http://127.0.0.1:1/users/7"),
+        response.get_header("location")
+    );
+}
+
+#[test]
+fn proxy_location_rewrite_should_point_at_prefix_on_requested_host() {
+    let (address, _requests) =
+        start_upstream(vec!["HTTP/1.1 302 Found\r\nContent-Length: 0\r\n\r\n"]);
+    let upstream = format!("http://{address}");
+    let location = format!("{upstream}/users/7?tab=1");
+    let routes = Proxy::new("/api", &upstream)
+        .with_response_transform(move |_, response| {
+            response
+                .headers
+                .push(("Location".to_string(), location.clone()));
+        })
+        .with_location_rewrite()
+        .routes();
+    let (route, request) = find_route(&routes, GET, "/api/users");
+
+    let response = route.get_response(request.with_headers(HashMap::from([(
+        "Host".to_string(),
+        "gateway.example.com".to_string(),
+    )])));
+
+    assert_eq!(
+        Some("http://gateway.example.com/api/users/7?tab=1"),
+        response.get_header("location")
+    );
+}
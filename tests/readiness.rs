@@ -0,0 +1,127 @@
+use minimal_api::server::{
+    readiness::{DependencyCheck, Readiness},
+    shutdown::Shutdown,
+    thread_pool::ThreadPool,
+};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+fn create_toggled_check(name: &str, is_up: &Arc<AtomicBool>) -> DependencyCheck {
+    let is_up = Arc::clone(is_up);
+    DependencyCheck::new(
+        name,
+        Box::new(move || {
+            if is_up.load(Ordering::Relaxed) {
+                Ok(())
+            } else {
+                Err("unreachable".to_string())
+            }
+        }),
+    )
+}
+
+#[test]
+fn run_checks_should_flip_readiness_when_dependency_goes_down() {
+    let database_up = Arc::new(AtomicBool::new(true));
+    let readiness = Readiness::new(vec![
+        create_toggled_check("db", &database_up),
+        DependencyCheck::new("cache", Box::new(|| Ok(()))),
+    ]);
+
+    readiness.run_checks();
+    let was_ready = readiness.is_ready();
+    database_up.store(false, Ordering::Relaxed);
+    readiness.run_checks();
+
+    assert!(was_ready);
+    assert!(!readiness.is_ready());
+    assert!(!readiness.is_available("db"));
+    assert!(readiness.is_available("cache"));
+}
+
+#[test]
+fn new_should_not_be_ready_until_checks_run() {
+    let readiness = Readiness::new(vec![DependencyCheck::new("db", Box::new(|| Ok(())))]);
+
+    assert!(!readiness.is_ready());
+}
+
+#[test]
+fn unavailable_should_return_down_and_unregistered_dependencies() {
+    let readiness = Readiness::new(vec![
+        DependencyCheck::new("db", Box::new(|| Err("refused".to_string()))),
+        DependencyCheck::new("cache", Box::new(|| Ok(()))),
+    ]);
+    readiness.run_checks();
+    let requires = vec!["cache".to_string(), "db".to_string(), "queue".to_string()];
+
+    let result = readiness.unavailable(&requires);
+
+    assert_eq!(vec!["db", "queue"], result);
+    assert!(!readiness.is_registered("queue"));
+}
+
+#[test]
+fn get_json_should_summarize_every_dependency() {
+    let readiness = Readiness::new(vec![
+        DependencyCheck::new("db", Box::new(|| Err("refused".to_string()))),
+        DependencyCheck::new("cache", Box::new(|| Ok(()))),
+    ]);
+    readiness.run_checks();
+
+    let result = readiness.get_json();
+
+    assert_eq!(
+        r#"{"ready":false,"dependencies":{"db":"down","cache":"up"}}"#,
+        result
+    );
+}
+
+#[test]
+fn spawn_checks_should_recheck_dependencies_until_shutdown() {
+    let database_up = Arc::new(AtomicBool::new(false));
+    let readiness: &'static Readiness = Box::leak(Box::new(
+        Readiness::new(vec![create_toggled_check("db", &database_up)])
+            .with_interval(Duration::from_millis(10)),
+    ));
+    let shutdown = Shutdown::new();
+
+    readiness.run_checks();
+    let checks = readiness.spawn_checks(shutdown.clone()).unwrap();
+    database_up.store(true, Ordering::Relaxed);
+    std::thread::sleep(Duration::from_millis(100));
+    shutdown.trigger();
+    checks.join().unwrap();
+
+    assert!(readiness.is_ready());
+}
+
+#[test]
+fn is_ready_should_wait_for_every_thread_pool_to_warm_up() {
+    let warmed_up = Arc::new(AtomicBool::new(false));
+    let is_warm = Arc::clone(&warmed_up);
+    let pool = ThreadPool::with_warm_up(
+        1,
+        Arc::new(move || {
+            while !is_warm.load(Ordering::Relaxed) {
+                std::thread::yield_now();
+            }
+        }),
+    );
+    let readiness = Readiness::new(Vec::new());
+    readiness.wait_for(pool.ready());
+
+    let was_ready = readiness.is_ready();
+    warmed_up.store(true, Ordering::Relaxed);
+    while !readiness.is_ready() {
+        std::thread::yield_now();
+    }
+
+    assert!(!was_ready);
+    assert!(readiness.is_warmed_up());
+}
@@ -0,0 +1,59 @@
+use http::{registry, route::Route};
+use http_attributes::{http_get, http_post};
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/registry/test")]
+fn test_registered_get() -> String {
+    "registered".to_string()
+}
+
+#[http_post("/registry/test")]
+fn test_registered_post(content: String) -> String {
+    content
+}
+
+// =====
+// tests
+// =====
+#[test]
+fn collect_routes_should_include_every_attributed_handler() {
+    let routes: Vec<Route> = http::collect_routes!();
+
+    let registered: Vec<&str> = routes
+        .iter()
+        .filter(|route| route.spec.request_pattern == "/registry/test")
+        .map(|route| route.spec.method.as_str())
+        .collect();
+
+    assert_eq!(vec!["GET", "POST"], registered);
+}
+
+#[test]
+fn collect_routes_should_order_routes_by_pattern_then_method() {
+    let routes = registry::collect_routes();
+
+    let keys: Vec<(&str, &str)> = routes
+        .iter()
+        .map(|route| {
+            (
+                route.spec.request_pattern.as_str(),
+                route.spec.method.as_str(),
+            )
+        })
+        .collect();
+    let mut sorted_keys = keys.clone();
+    sorted_keys.sort();
+
+    assert_eq!(sorted_keys, keys);
+}
+
+#[test]
+fn collect_routes_should_include_routes_from_linked_crates() {
+    let has_version_route = minimal_api::routes::index::get_endpoints()
+        .iter()
+        .any(|route| route.spec.request_pattern == "/version");
+
+    assert!(has_version_route);
+}
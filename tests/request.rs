@@ -1,10 +1,19 @@
-use http::request::Request;
+use http::{extensions::TypeMap, request::Request};
 use std::collections::HashMap;
 
+// ======================
+// common extension types
+// ======================
+#[derive(Debug, PartialEq)]
+struct AuthUser(String);
+
+#[derive(Debug, PartialEq)]
+struct Bucket(u8);
+
 #[test]
 fn new_request_should_assign_inputs_as_members() {
     let path = "path";
-    let method = "method";
+    let method = http::methods::PATCH;
 
     let request = Request::new(path, method, None, HashMap::new());
 
@@ -14,7 +23,7 @@ fn new_request_should_assign_inputs_as_members() {
 
 #[test]
 fn new_request_should_have_no_body_content() {
-    let request = Request::new("", "", None, HashMap::new());
+    let request = Request::new("", http::methods::GET, None, HashMap::new());
 
     assert_eq!(None, request.body_content);
 }
@@ -24,7 +33,7 @@ fn get_request_should_have_valid_http_get_header() {
     let path = "path";
     let get_request = Request::get(path);
 
-    assert!(get_request.contains(http::methods::GET));
+    assert!(get_request.contains(http::methods::GET.as_str()));
     assert!(get_request.contains(path));
     assert!(get_request.contains("HTTP/"));
 }
@@ -34,7 +43,7 @@ fn post_request_should_have_valid_http_post_header() {
     let path = "path";
     let post_request = Request::post(path);
 
-    assert!(post_request.contains(http::methods::POST));
+    assert!(post_request.contains(http::methods::POST.as_str()));
     assert!(post_request.contains(path));
     assert!(post_request.contains("HTTP/"));
 }
@@ -44,7 +53,7 @@ fn put_request_should_have_valid_http_put_header() {
     let path = "path";
     let put_request = Request::put(path);
 
-    assert!(put_request.contains(http::methods::PUT));
+    assert!(put_request.contains(http::methods::PUT.as_str()));
     assert!(put_request.contains(path));
     assert!(put_request.contains("HTTP/"));
 }
@@ -54,14 +63,14 @@ fn delete_request_should_have_valid_http_delete_header() {
     let path = "path";
     let delete_request = Request::delete(path);
 
-    assert!(delete_request.contains(http::methods::DELETE));
+    assert!(delete_request.contains(http::methods::DELETE.as_str()));
     assert!(delete_request.contains(path));
     assert!(delete_request.contains("HTTP/"));
 }
 
 #[test]
 fn query_param_should_return_none_without_any_query() {
-    let request = Request::new("", "", None, HashMap::new());
+    let request = Request::new("", http::methods::GET, None, HashMap::new());
 
     let result = request.query_param("fake-key");
 
@@ -70,7 +79,7 @@ fn query_param_should_return_none_without_any_query() {
 
 #[test]
 fn query_param_should_return_none_without_query_match() {
-    let request = Request::new("?key=value", "", None, HashMap::new());
+    let request = Request::new("?key=value", http::methods::GET, None, HashMap::new());
 
     let result = request.query_param("other-key");
 
@@ -81,7 +90,12 @@ fn query_param_should_return_none_without_query_match() {
 fn query_param_should_return_value_with_valid_query() {
     let key = "key";
     let expected = "value";
-    let request = Request::new(&format!("?{key}={expected}"), "", None, HashMap::new());
+    let request = Request::new(
+        &format!("?{key}={expected}"),
+        http::methods::GET,
+        None,
+        HashMap::new(),
+    );
 
     let result = request.query_param(key);
 
@@ -91,7 +105,7 @@ fn query_param_should_return_value_with_valid_query() {
 
 #[test]
 fn body_as_string_should_error_without_request_body() {
-    let request = Request::new("", "", None, HashMap::new());
+    let request = Request::new("", http::methods::GET, None, HashMap::new());
 
     let result = request.body_as_string();
 
@@ -101,7 +115,7 @@ fn body_as_string_should_error_without_request_body() {
 #[test]
 fn body_as_string_should_return_body_content_with_request_body() {
     let expected = "body content";
-    let mut request = Request::new("", "", None, HashMap::new());
+    let mut request = Request::new("", http::methods::GET, None, HashMap::new());
     request.body_content = Some(expected.to_string());
 
     let result = request.body_as_string();
@@ -109,3 +123,89 @@ fn body_as_string_should_return_body_content_with_request_body() {
     assert!(result.is_ok());
     assert_eq!(expected, result.unwrap());
 }
+
+#[test]
+fn header_should_return_none_without_any_headers() {
+    let request = Request::new("", http::methods::GET, None, HashMap::new());
+
+    let result = request.header("content-type");
+
+    assert_eq!(None, result);
+}
+
+#[test]
+fn header_should_return_value_ignoring_case() {
+    let expected = "application/json";
+    let request = Request::new("", http::methods::GET, None, HashMap::new()).with_headers(
+        HashMap::from([("Content-Type".to_string(), expected.to_string())]),
+    );
+
+    let result = request.header("CONTENT-TYPE");
+
+    assert_eq!(Some(expected), result);
+}
+
+#[test]
+fn header_all_should_return_every_comma_separated_value() {
+    let request =
+        Request::new("", http::methods::GET, None, HashMap::new()).with_headers(HashMap::from([(
+            "accept".to_string(),
+            "text/html, application/json".to_string(),
+        )]));
+
+    let result = request.header_all("Accept");
+
+    assert_eq!(vec!["text/html", "application/json"], result);
+}
+
+#[test]
+fn cookie_should_return_the_named_cookie_without_quotes() {
+    let request =
+        Request::new("", http::methods::GET, None, HashMap::new()).with_headers(HashMap::from([(
+            "Cookie".to_string(),
+            "session_id=abc; theme=\"dark\";visits=3".to_string(),
+        )]));
+
+    assert_eq!(Some("abc"), request.cookie("session_id"));
+    assert_eq!(Some("dark"), request.cookie("theme"));
+    assert_eq!(Some("3"), request.cookie("visits"));
+    assert_eq!(None, request.cookie("session"));
+}
+
+#[test]
+fn header_all_should_be_empty_without_header() {
+    let request = Request::new("", http::methods::GET, None, HashMap::new());
+
+    let result = request.header_all("accept");
+
+    assert!(result.is_empty());
+}
+
+#[test]
+fn extension_should_get_value_attached_by_type() {
+    let request = Request::new("/", http::methods::GET, None, HashMap::new())
+        .with_extension(AuthUser("evan".to_string()))
+        .with_extension(Bucket(2));
+
+    assert_eq!(
+        Some(&AuthUser("evan".to_string())),
+        request.extension::<AuthUser>()
+    );
+    assert_eq!(Some(&Bucket(2)), request.extension::<Bucket>());
+    assert_eq!(None, request.extension::<String>());
+}
+
+#[test]
+fn type_map_insert_should_replace_value_of_same_type() {
+    let mut extensions = TypeMap::new();
+
+    let first = extensions.insert(Bucket(1));
+    let replaced = extensions.insert(Bucket(2));
+    extensions.get_mut::<Bucket>().unwrap().0 += 1;
+
+    assert_eq!(None, first);
+    assert_eq!(Some(Bucket(1)), replaced);
+    assert_eq!(1, extensions.len());
+    assert_eq!(Some(Bucket(3)), extensions.remove::<Bucket>());
+    assert!(extensions.is_empty());
+}
@@ -0,0 +1,98 @@
+use http::{
+    methods::{GET, Method, POST},
+    request::Request,
+    status::Status,
+};
+use http_attributes::{http_get, http_post, http_raw_get, require_header};
+use std::collections::HashMap;
+
+// =================
+// endpoints to test
+// =================
+#[require_header("X-Internal", "true")]
+#[http_get("/internal/stats", generate_tests)]
+fn get_stats() -> String {
+    "\"ok\"".to_string()
+}
+
+#[http_raw_get("/internal/tenants")]
+#[require_header("X-Internal")]
+#[require_header("X-Tenant", "acme")]
+fn get_tenants(#[header("X-Internal")] caller: String) -> String {
+    format!("\"{caller}\"")
+}
+
+#[require_header("X-Internal", "true")]
+#[http_post("/internal/jobs")]
+fn create_job(job: String) -> String {
+    format!("\"queued {job}\"")
+}
+
+fn create_request(path: &str, method: Method, headers: &[(&str, &str)]) -> Request {
+    Request::new(path, method, None, HashMap::new()).with_headers(
+        headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+// ====================
+// require_header tests
+// ====================
+#[test]
+fn require_header_should_call_the_handler_when_the_header_matches() {
+    let request = create_request("/internal/stats", GET, &[("x-internal", "true")]);
+
+    let response = get_stats().get_response(request);
+
+    assert_eq!(Status::Ok, response.status);
+    assert!(response.content.contains("ok"));
+}
+
+#[test]
+fn require_header_should_forbid_a_missing_header() {
+    let request = create_request("/internal/stats", GET, &[]);
+
+    let response = get_stats().get_response(request);
+
+    assert_eq!(Status::Forbidden, response.status);
+    assert!(response.content.contains("missing header X-Internal"));
+}
+
+#[test]
+fn require_header_should_forbid_a_mismatched_header() {
+    let request = create_request("/internal/stats", GET, &[("X-Internal", "false")]);
+
+    let response = get_stats().get_response(request);
+
+    assert_eq!(Status::Forbidden, response.status);
+    assert!(response.content.contains("mismatched header X-Internal"));
+}
+
+#[test]
+fn require_header_should_check_every_stacked_header() {
+    let allowed = create_request(
+        "/internal/tenants",
+        GET,
+        &[("X-Internal", "billing"), ("X-Tenant", "acme")],
+    );
+    let forbidden = create_request("/internal/tenants", GET, &[("X-Internal", "billing")]);
+
+    let allowed_response = get_tenants().get_response(allowed);
+    let forbidden_response = get_tenants().get_response(forbidden);
+
+    assert_eq!(Status::Ok, allowed_response.status);
+    assert_eq!("\"billing\"", allowed_response.content);
+    assert_eq!(Status::Forbidden, forbidden_response.status);
+    assert_eq!("\"missing header X-Tenant\"", forbidden_response.content);
+}
+
+#[test]
+fn require_header_should_forbid_before_reading_the_body() {
+    let request = Request::new("/internal/jobs", POST, None, HashMap::new());
+
+    let response = create_job().get_response(request);
+
+    assert_eq!(Status::Forbidden, response.status);
+}
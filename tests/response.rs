@@ -1,5 +1,13 @@
+use http::compression::Encoding;
 use http::response::Response;
 use http::status::Status;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
 #[test]
 fn ok_response_should_assign_contents_and_have_ok_status() {
@@ -38,3 +46,266 @@ fn server_error_response_should_have_server_error_status() {
 
     assert_eq!(Status::ServerError, result.status);
 }
+
+#[test]
+fn no_content_response_should_have_no_content_status_and_empty_body() {
+    let result = Response::no_content();
+
+    assert_eq!(Status::NoContent, result.status);
+    assert!(result.is_empty(true));
+}
+
+#[test]
+fn long_poll_response_should_return_content_right_away_when_available() {
+    let result = Response::long_poll(Duration::from_secs(5), || Some("ready".to_string()));
+
+    assert!(!result.is_pending());
+    assert_eq!(Status::Ok, result.status);
+    assert_eq!("ready", result.content);
+}
+
+#[test]
+fn long_poll_response_should_be_pending_without_waiting() {
+    let started = Instant::now();
+
+    let result = Response::long_poll(Duration::from_secs(5), || None);
+
+    assert!(result.is_pending());
+    assert!(result.poll().is_none());
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn long_poll_response_should_return_content_once_available() {
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counted_attempts = attempts.clone();
+
+    let result = Response::long_poll(Duration::from_secs(5), move || {
+        let attempt = counted_attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        (attempt == 3).then(|| "ready".to_string())
+    })
+    .wait();
+
+    assert_eq!(Status::Ok, result.status);
+    assert_eq!("ready", result.content);
+    assert_eq!(3, attempts.load(Ordering::SeqCst));
+}
+
+#[test]
+fn long_poll_response_should_keep_its_headers_once_answered() {
+    let result = Response::long_poll(Duration::from_millis(10), || None)
+        .with_header("Cache-Control", "no-store")
+        .without_body()
+        .wait();
+
+    assert_eq!(Some("no-store"), result.get_header("Cache-Control"));
+    assert!(result.omits_body);
+}
+
+#[test]
+fn long_poll_response_should_return_no_content_after_timeout() {
+    let timeout = Duration::from_millis(60);
+    let started = Instant::now();
+
+    let result = Response::long_poll(timeout, || None).wait();
+
+    assert_eq!(Status::NoContent, result.status);
+    assert!(!result.is_pending());
+    assert!(started.elapsed() >= timeout);
+}
+
+#[test]
+fn precondition_failed_response_should_have_precondition_failed_status() {
+    let result = Response::precondition_failed();
+
+    assert_eq!(Status::PreconditionFailed, result.status);
+}
+
+#[test]
+fn options_response_should_have_allow_header_and_no_content() {
+    let result = Response::options(&[http::methods::GET, http::methods::OPTIONS]);
+
+    assert_eq!(Status::NoContent, result.status);
+    assert_eq!(Some("GET, OPTIONS"), result.get_header("allow"));
+    assert!(result.to_string().contains("Allow: GET, OPTIONS\r\n"));
+    assert!(!result.to_string().contains("Content-Length"));
+}
+
+#[test]
+fn no_content_response_should_not_send_content_length_or_content_type() {
+    let result = Response::no_content().with_header("Content-Type", "text/plain");
+
+    assert_eq!("HTTP/1.1 204 NO CONTENT\r\n\r\n", result.to_string());
+}
+
+#[test]
+fn not_modified_response_should_not_send_a_body() {
+    let result = Response::with_status(Status::NotModified, "\"stale\"", false);
+
+    assert_eq!("HTTP/1.1 304 NOT MODIFIED\r\n\r\n", result.to_string());
+    assert!(result.to_bytes(&Encoding::Brotli).ends_with(b"\r\n\r\n"));
+}
+
+#[test]
+fn with_header_should_keep_content_length_matching_body() {
+    let result = Response::ok("\"ok\"", false).with_header("X-Test", "value");
+
+    let sent = result.to_string();
+    let (head, body) = sent.split_once("\r\n\r\n").unwrap();
+
+    assert!(head.contains(&format!("Content-Length: {}", body.len())));
+    assert!(head.ends_with("X-Test: value"));
+}
+
+#[test]
+fn vary_on_should_merge_request_headers_without_duplicates() {
+    let result = Response::ok("ok", false)
+        .vary_on("Accept")
+        .vary_on("Accept-Encoding")
+        .vary_on("accept");
+
+    assert_eq!(Some("Accept, Accept-Encoding"), result.get_header("Vary"));
+    assert_eq!(1, result.headers.len());
+}
+
+#[test]
+fn vary_on_should_replace_every_header_with_wildcard() {
+    let result = Response::ok("ok", false)
+        .vary_on("Accept")
+        .vary_on("*")
+        .vary_on("Origin");
+
+    assert_eq!(Some("*"), result.get_header("Vary"));
+}
+
+fn assert_content_length_matches_body(response: &Response) {
+    let sent = response.to_string();
+    let (head, body) = sent.split_once("\r\n\r\n").unwrap();
+
+    assert!(head.contains(&format!("Content-Length: {}\r\n", body.len())));
+    assert_eq!(body.len(), response.len(response.raw_response));
+}
+
+#[test]
+fn content_length_should_match_body_for_every_response() {
+    let responses = [
+        Response::ok("\"ok\"", false),
+        Response::ok("{\"raw\":true}", true),
+        Response::not_found(),
+        Response::bad_request(),
+        Response::precondition_failed(),
+        Response::unprocessable_entity(),
+        Response::server_error(),
+        Response::with_status(Status::BadRequest, "\"héllo wörld\"", false),
+    ];
+
+    for response in &responses {
+        assert_content_length_matches_body(response);
+    }
+}
+
+#[test]
+fn content_length_should_match_body_after_adding_headers() {
+    let result = Response::ok("\"ünïcode\"", false)
+        .with_header("X-Test", "value")
+        .vary_on("Accept")
+        .vary_on("Origin");
+
+    assert_content_length_matches_body(&result);
+    assert_eq!(
+        result.to_string().into_bytes(),
+        result.to_bytes(&Encoding::Identity)
+    );
+}
+
+#[test]
+fn len_should_render_body_for_other_raw_setting() {
+    let result = Response::ok("\"ok\"", true);
+
+    assert_eq!(4, result.len(true));
+    assert!(result.len(false) > result.len(true));
+}
+
+#[test]
+fn csv_response_should_quote_fields_and_have_csv_content_type() {
+    let response = Response::csv([vec!["id", "quote"], vec!["1", "say \"hi\", then\nleave"]]);
+
+    assert_eq!(
+        "id,quote\r\n1,\"say \"\"hi\"\", then\nleave\"\r\n",
+        response.content
+    );
+    assert_eq!(
+        Some("text/csv; charset=utf-8"),
+        response.get_header("content-type")
+    );
+    assert!(!response.to_string().contains("application/json"));
+}
+
+#[test]
+fn ndjson_response_should_have_one_item_per_line() {
+    let response = Response::ndjson(["first", "line\nbreak"]);
+
+    assert_eq!("\"first\"\n\"line\\nbreak\"\n", response.content);
+    assert_eq!(
+        Some("application/x-ndjson; charset=utf-8"),
+        response.get_header("content-type")
+    );
+}
+
+#[test]
+fn with_headers_should_add_every_header_in_order() {
+    let response = Response::no_content()
+        .with_headers(&[("Cache-Control", "no-store"), ("X-Frame-Options", "DENY")]);
+
+    assert_eq!(
+        vec![
+            ("Cache-Control".to_string(), "no-store".to_string()),
+            ("X-Frame-Options".to_string(), "DENY".to_string())
+        ],
+        response.headers
+    );
+    assert!(
+        response
+            .to_string()
+            .contains("Cache-Control: no-store\r\nX-Frame-Options: DENY\r\n")
+    );
+}
+
+#[test]
+fn to_bytes_should_send_headers_changed_after_the_response_was_built() {
+    let mut response = Response::ok("\"ok\"", false).with_header("X-Internal-Trace", "abc");
+
+    response
+        .headers
+        .retain(|(name, _)| name != "X-Internal-Trace");
+    let result = String::from_utf8(response.to_bytes(&Encoding::Identity)).unwrap();
+
+    assert!(!result.contains("X-Internal-Trace"));
+    assert_eq!(response.to_string(), result);
+}
+
+#[test]
+fn response_should_send_content_and_status_changed_after_it_was_built() {
+    let mut response = Response::ok("\"short\"", true);
+
+    response.content = "\"a much longer body\"".to_string();
+    response.status = Status::Created;
+    let sent = response.header();
+    let (head, body) = sent.split_once("\r\n\r\n").unwrap();
+
+    assert!(head.starts_with("HTTP/1.1 201 CREATED\r\n"));
+    assert!(head.contains(&format!("Content-Length: {}\r\n", body.len())));
+    assert_eq!("\"a much longer body\"", body);
+}
+
+#[test]
+fn wrapped_response_should_send_status_changed_after_it_was_built() {
+    let mut response = Response::ok("\"ok\"", false);
+
+    response.status = Status::Accepted;
+    let sent = String::from_utf8(response.to_bytes(&Encoding::Identity)).unwrap();
+    let (head, body) = sent.split_once("\r\n\r\n").unwrap();
+
+    assert!(head.contains(&format!("Content-Length: {}\r\n", body.len())));
+    assert!(body.contains(&format!("\"status\":\"{}\"", Status::Accepted)));
+}
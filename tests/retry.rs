@@ -0,0 +1,131 @@
+use http::{
+    client::{Client, ClientRequest},
+    retry::{RetryBudget, RetryPolicy},
+};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener},
+    sync::{Arc, mpsc},
+    thread,
+    time::Duration,
+};
+
+const UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n";
+const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+
+/// starts an upstream that answers `responses` in order, and sends back the request
+/// line of every request it receives
+fn start_upstream(responses: Vec<&'static str>) -> (SocketAddr, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let _ = sender.send(request_line);
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (address, receiver)
+}
+
+fn create_fast_policy() -> RetryPolicy {
+    RetryPolicy::new()
+        .with_max_attempts(3)
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+}
+
+#[test]
+fn send_should_retry_idempotent_request_until_it_succeeds() {
+    let (address, requests) = start_upstream(vec![UNAVAILABLE, UNAVAILABLE, OK]);
+    let client = Client::new().with_retry_policy(create_fast_policy());
+
+    let response = client
+        .send(&ClientRequest::new(
+            "GET",
+            &format!("http://{address}/flaky"),
+        ))
+        .unwrap();
+
+    assert_eq!(200, response.status_code);
+    assert_eq!(3, requests.try_iter().count());
+}
+
+#[test]
+fn send_should_not_retry_non_idempotent_request() {
+    let (address, requests) = start_upstream(vec![UNAVAILABLE, OK]);
+    let client = Client::new().with_retry_policy(create_fast_policy());
+
+    let response = client
+        .send(&ClientRequest::new("POST", &format!("http://{address}/orders")).with_body(b"{}"))
+        .unwrap();
+
+    assert_eq!(503, response.status_code);
+    assert_eq!(1, requests.try_iter().count());
+}
+
+#[test]
+fn send_should_stop_retrying_once_budget_is_spent() {
+    let (address, requests) = start_upstream(vec![UNAVAILABLE, UNAVAILABLE, OK]);
+    let budget = Arc::new(RetryBudget::new(0.0, 1));
+    let client = Client::new().with_retry_policy(create_fast_policy().with_budget(budget.clone()));
+
+    let response = client
+        .send(&ClientRequest::new(
+            "GET",
+            &format!("http://{address}/flaky"),
+        ))
+        .unwrap();
+
+    assert_eq!(503, response.status_code);
+    assert_eq!(2, requests.try_iter().count());
+    assert_eq!(0, budget.remaining());
+}
+
+#[test]
+fn delay_for_should_back_off_exponentially_up_to_max_delay() {
+    let policy = RetryPolicy::new()
+        .with_backoff(Duration::from_millis(100), Duration::from_millis(300))
+        .with_jitter(0.0);
+
+    assert_eq!(Duration::from_millis(100), policy.delay_for(1));
+    assert_eq!(Duration::from_millis(200), policy.delay_for(2));
+    assert_eq!(Duration::from_millis(300), policy.delay_for(3));
+    assert_eq!(Duration::from_millis(300), policy.delay_for(30));
+}
+
+#[test]
+fn delay_for_should_stay_within_jittered_range() {
+    let policy = RetryPolicy::new()
+        .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+        .with_jitter(0.5);
+
+    for _ in 0..50 {
+        let delay = policy.delay_for(1);
+        assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+    }
+}
+
+#[test]
+fn retry_budget_should_earn_retries_from_requests() {
+    let budget = RetryBudget::new(0.5, 2);
+
+    assert!(budget.try_withdraw());
+    assert!(budget.try_withdraw());
+    assert!(!budget.try_withdraw());
+    budget.deposit();
+    budget.deposit();
+
+    assert!(budget.try_withdraw());
+    assert!(!budget.try_withdraw());
+}
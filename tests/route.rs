@@ -1,11 +1,18 @@
-use http::{request::Request, response::Response, route::Route};
+use http::{
+    request::Request,
+    respond::Respond,
+    response::Response,
+    route::Route,
+    router::Router,
+    validation::{ParamLocation, RouteParam},
+};
 use std::collections::HashMap;
 
 // ====================
 // common route handler
 // ====================
 fn route_handler(request: Request) -> Response {
-    Response::ok(&request.method, false)
+    Response::ok(request.method.as_str(), false)
 }
 
 #[test]
@@ -17,8 +24,8 @@ fn get_route_should_assign_contents_and_provide_get_response() {
 
     let result = route.get_response(request);
 
-    assert_eq!(expected, route.request_pattern);
-    assert_eq!(method, result.content);
+    assert_eq!(expected, route.spec.request_pattern);
+    assert_eq!(method.as_str(), result.content);
 }
 
 #[test]
@@ -30,8 +37,8 @@ fn post_route_should_assign_contents_and_provide_post_response() {
 
     let result = route.get_response(request);
 
-    assert_eq!(expected, route.request_pattern);
-    assert_eq!(method, result.content);
+    assert_eq!(expected, route.spec.request_pattern);
+    assert_eq!(method.as_str(), result.content);
 }
 
 #[test]
@@ -43,8 +50,8 @@ fn put_route_should_assign_contents_and_provide_put_response() {
 
     let result = route.get_response(request);
 
-    assert_eq!(expected, route.request_pattern);
-    assert_eq!(method, result.content);
+    assert_eq!(expected, route.spec.request_pattern);
+    assert_eq!(method.as_str(), result.content);
 }
 
 #[test]
@@ -56,6 +63,249 @@ fn delete_route_should_assign_contents_and_provide_delete_response() {
 
     let result = route.get_response(request);
 
-    assert_eq!(expected, route.request_pattern);
-    assert_eq!(method, result.content);
+    assert_eq!(expected, route.spec.request_pattern);
+    assert_eq!(method.as_str(), result.content);
+}
+
+// ===========================
+// if-match precondition tests
+// ===========================
+fn current_version(_request: &Request) -> Option<String> {
+    Some("\"v2\"".to_string())
+}
+
+fn create_if_match_request(if_match: Option<&str>) -> Request {
+    let headers = if_match
+        .map(|etag| HashMap::from([("If-Match".to_string(), etag.to_string())]))
+        .unwrap_or_default();
+    Request::new("put/path", http::methods::PUT, None, HashMap::new()).with_headers(headers)
+}
+
+#[test]
+fn check_precondition_should_pass_without_version_handler() {
+    let route = Route::put("put/path", Box::new(route_handler));
+
+    assert!(route.check_precondition(&create_if_match_request(None)));
+}
+
+#[test]
+fn check_precondition_should_pass_with_matching_etag() {
+    let route =
+        Route::put("put/path", Box::new(route_handler)).with_if_match(Box::new(current_version));
+
+    assert!(route.check_precondition(&create_if_match_request(Some("\"v1\", \"v2\""))));
+    assert!(route.check_precondition(&create_if_match_request(Some("*"))));
+}
+
+#[test]
+fn check_precondition_should_fail_with_mismatched_missing_or_weak_etag() {
+    let route =
+        Route::put("put/path", Box::new(route_handler)).with_if_match(Box::new(current_version));
+
+    assert!(!route.check_precondition(&create_if_match_request(Some("\"v1\""))));
+    assert!(!route.check_precondition(&create_if_match_request(Some("W/\"v2\""))));
+    assert!(!route.check_precondition(&create_if_match_request(None)));
+}
+
+// =====================
+// allowed methods tests
+// =====================
+#[test]
+fn allowed_methods_should_list_methods_matching_path_with_options() {
+    let routes = vec![
+        Route::get("item/{id}", Box::new(route_handler)),
+        Route::put("item/{id}", Box::new(route_handler)),
+        Route::get("item/{id}", Box::new(route_handler)),
+        Route::delete("other/{id}", Box::new(route_handler)),
+    ];
+
+    let result = Route::allowed_methods(&routes, "item/1");
+
+    assert_eq!(
+        vec![
+            http::methods::GET,
+            http::methods::PUT,
+            http::methods::OPTIONS
+        ],
+        result
+    );
+}
+
+#[test]
+fn allowed_methods_should_be_empty_without_matching_path() {
+    let routes = vec![Route::get("item/{id}", Box::new(route_handler))];
+
+    let result = Route::allowed_methods(&routes, "missing");
+
+    assert!(result.is_empty());
+}
+
+// =====================
+// default headers tests
+// =====================
+#[test]
+fn with_header_should_add_header_to_every_response() {
+    let route = Route::get("get/path", Box::new(route_handler))
+        .with_headers(&[("X-Service", "users"), ("X-Team", "identity")]);
+    let request = Request::new("get/path", http::methods::GET, None, HashMap::new());
+
+    let result = route.get_response(request);
+
+    assert_eq!(Some("users"), result.get_header("X-Service"));
+    assert_eq!(Some("identity"), result.get_header("X-Team"));
+}
+
+#[test]
+fn with_header_should_not_replace_header_set_by_handler() {
+    let route = Route::get(
+        "get/path",
+        Box::new(|_| Response::ok("ok", false).with_header("X-Service", "handler")),
+    )
+    .with_header("X-Service", "users");
+    let request = Request::new("get/path", http::methods::GET, None, HashMap::new());
+
+    let result = route.get_response(request);
+
+    assert_eq!(Some("handler"), result.get_header("X-Service"));
+    assert_eq!(1, result.headers.len());
+}
+
+#[test]
+fn with_header_should_merge_vary_header_with_response() {
+    let route = Route::get(
+        "get/path",
+        Box::new(|_| Response::ok("ok", false).vary_on("Accept")),
+    )
+    .with_header("Vary", "Origin, Accept");
+    let request = Request::new("get/path", http::methods::GET, None, HashMap::new());
+
+    let result = route.get_response(request);
+
+    assert_eq!(Some("Accept, Origin"), result.get_header("Vary"));
+}
+
+#[test]
+fn matches_path_should_give_rest_of_path_to_catch_all_segment() {
+    let route = Route::get("/files/{*path}", Box::new(route_handler));
+
+    let nested = route.spec.matches_path("/files/css/site.css").unwrap();
+    let empty = route.spec.matches_path("/files").unwrap();
+
+    assert_eq!("css/site.css", nested["path"]);
+    assert_eq!("", empty["path"]);
+    assert!(route.spec.matches_path("/other/css/site.css").is_none());
+}
+
+#[test]
+fn matches_path_should_percent_decode_path_params() {
+    let route = Route::get("/users/{name}/files/{*path}", Box::new(route_handler));
+
+    let path_params = route
+        .spec
+        .matches_path("/users/jos%C3%A9%20luis/files/caf%C3%A9/men%C3%BC.txt")
+        .unwrap();
+
+    assert_eq!("josé luis", path_params["name"]);
+    assert_eq!("café/menü.txt", path_params["path"]);
+}
+
+#[test]
+fn matches_path_should_match_unicode_path_params() {
+    let route = Route::get("/cafe/{name}", Box::new(route_handler));
+
+    let raw = route.spec.matches_path("/cafe/zoë").unwrap();
+    let encoded = route.spec.matches_path("/cafe/zo%C3%AB").unwrap();
+
+    assert_eq!("zoë", raw["name"]);
+    assert_eq!("zoë", encoded["name"]);
+}
+
+#[test]
+fn matches_path_should_match_static_segments_only_as_sent() {
+    let route = Route::get("/admin/stats", Box::new(route_handler));
+
+    assert!(route.spec.matches_path("/admin/stats").is_some());
+    assert!(route.spec.matches_path("/%61dmin/stats").is_none());
+    assert!(route.spec.matches_path("/admin/st%61ts").is_none());
+}
+
+#[test]
+fn matches_path_should_reject_invalid_percent_escapes() {
+    let route = Route::get("/users/{name}", Box::new(route_handler));
+
+    assert!(route.spec.matches_path("/users/100%").is_none());
+    assert!(route.spec.matches_path("/users/%zz").is_none());
+    assert!(route.spec.matches_path("/users/%C3").is_none());
+}
+
+#[test]
+fn matches_path_should_reject_encoded_slashes_unless_the_route_allows_them() {
+    let route = Route::get("/files/{name}", Box::new(route_handler));
+    let allowing_route =
+        Route::get("/files/{name}", Box::new(route_handler)).with_encoded_slashes();
+
+    assert!(route.spec.matches_path("/files/..%2Fsecrets").is_none());
+    assert!(route.spec.matches_path("/files/..%2fsecrets").is_none());
+    assert_eq!(
+        "docs/readme.md",
+        allowing_route
+            .spec
+            .matches_path("/files/docs%2Freadme.md")
+            .unwrap()["name"]
+    );
+}
+
+#[test]
+fn example_curl_should_use_placeholders_for_every_param() {
+    let route = Route::post("/user/{id}/notes", Box::new(route_handler)).with_params(vec![
+        RouteParam::new("id", ParamLocation::Path, "i32"),
+        RouteParam::new("pinned", ParamLocation::Query, "bool"),
+        RouteParam::new("content", ParamLocation::Body, "String"),
+    ]);
+
+    let result = route.spec.example_curl("http://localhost:8080/");
+
+    assert_eq!(
+        "curl -X POST 'http://localhost:8080/user/<id>/notes?pinned=<pinned>' -d '<content>'",
+        result
+    );
+}
+
+#[test]
+fn example_curl_should_leave_out_method_for_get() {
+    let route = Route::get("/files/{*path}", Box::new(route_handler));
+
+    let result = route.spec.example_curl("http://localhost:8080");
+
+    assert_eq!("curl 'http://localhost:8080/files/<path>'", result);
+}
+
+#[test]
+fn route_spec_should_serialize_without_handler() {
+    let route = Route::get("/user/{id}", Box::new(route_handler))
+        .with_params(vec![RouteParam::new("id", ParamLocation::Path, "i32")])
+        .with_requirements(&["db"])
+        .with_description("Gets a user.");
+
+    let result = route.spec.get_json();
+
+    assert_eq!(
+        r#"{"method":"GET","pattern":"/user/{id}","params":[{"name":"id","location":"path","type":"i32","description":null}],"requires":["db"],"description":"Gets a user.","body_type":null,"response":{"status":null,"type":null},"body_limit":null,"streams_body":false}"#,
+        result
+    );
+}
+
+#[test]
+fn router_specs_should_clone_every_spec_in_match_order() {
+    let router = Router::new(vec![
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::put("/user/me", Box::new(route_handler)).with_body_limit(1024),
+    ]);
+
+    let specs = router.specs();
+
+    assert_eq!(2, specs.len());
+    assert_eq!("/user/me", specs[0].request_pattern);
+    assert_eq!(Some(1024), specs[0].body_limit);
+    assert_eq!(router.routes[1].spec, specs[1]);
 }
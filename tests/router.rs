@@ -0,0 +1,358 @@
+use http::{
+    request::Request,
+    response::Response,
+    route::Route,
+    router::{RouteConflict, Router},
+};
+
+// ====================
+// common route handler
+// ====================
+fn route_handler(request: Request) -> Response {
+    Response::ok(&format!("\"{}\"", request.path), true)
+}
+
+fn get_response(router: &Router, path: &str) -> Response {
+    let (route, path_params) = router.resolve(http::methods::GET, path).unwrap();
+    let request = Request::new(path, http::methods::GET, None, path_params);
+    router.respond(route, request)
+}
+
+#[test]
+fn respond_should_apply_filters_in_order() {
+    let router = Router::new(vec![Route::get("/users", Box::new(route_handler))])
+        .with_filter(Box::new(|response| {
+            let content = format!("{{\"data\":{}}}", response.content);
+            Response::with_status(response.status, &content, true)
+        }))
+        .with_filter(Box::new(|response| {
+            let content = format!("{{\"envelope\":{}}}", response.content);
+            Response::with_status(response.status, &content, true)
+        }));
+
+    let response = get_response(&router, "/users");
+
+    assert_eq!("{\"envelope\":{\"data\":\"/users\"}}", response.content);
+}
+
+#[test]
+fn respond_should_only_apply_filters_for_matching_prefix() {
+    let router = Router::new(vec![
+        Route::get("/admin", Box::new(route_handler)),
+        Route::get("/admin/{page}", Box::new(route_handler)),
+        Route::get("/administrators", Box::new(route_handler)),
+    ])
+    .with_filter_for(
+        "/admin/",
+        Box::new(|response| response.with_header("Cache-Control", "no-store")),
+    );
+
+    assert_eq!(
+        Some("no-store"),
+        get_response(&router, "/admin").get_header("Cache-Control")
+    );
+    assert_eq!(
+        Some("no-store"),
+        get_response(&router, "/admin/users").get_header("Cache-Control")
+    );
+    assert_eq!(
+        None,
+        get_response(&router, "/administrators").get_header("Cache-Control")
+    );
+}
+
+#[test]
+fn respond_should_be_able_to_replace_the_body() {
+    let router = Router::new(vec![Route::get("/secret", Box::new(route_handler))]).with_filter(
+        Box::new(|response| Response::with_status(response.status, "\"[redacted]\"", true)),
+    );
+
+    let response = get_response(&router, "/secret");
+
+    assert_eq!("\"[redacted]\"", response.content);
+}
+
+#[test]
+fn respond_should_only_apply_filters_whose_predicate_matches() {
+    let router = Router::new(vec![
+        Route::get("/api/users", Box::new(route_handler)),
+        Route::get("/users", Box::new(route_handler)),
+    ])
+    .with_filter_only_for(
+        Box::new(|request| request.path.starts_with("/api")),
+        Box::new(|response| response.with_header("X-Api", "true")),
+    );
+    let respond = |path: &str| {
+        let (route, path_params) = router.resolve(http::methods::GET, path).unwrap();
+        router.respond(
+            route,
+            Request::new(path, http::methods::GET, None, path_params),
+        )
+    };
+
+    assert_eq!(Some("true"), respond("/api/users").get_header("X-Api"));
+    assert_eq!(None, respond("/users").get_header("X-Api"));
+}
+
+#[test]
+fn respond_should_apply_prefix_and_predicate_filters_in_order() {
+    let router = Router::new(vec![Route::get("/users", Box::new(route_handler))])
+        .with_filter_only_for(
+            Box::new(|request| request.header("Authorization").is_some()),
+            Box::new(|response| {
+                let content = format!("{{\"private\":{}}}", response.content);
+                Response::with_status(response.status, &content, true)
+            }),
+        )
+        .with_filter_for(
+            "/users",
+            Box::new(|response| {
+                let content = format!("{{\"envelope\":{}}}", response.content);
+                Response::with_status(response.status, &content, true)
+            }),
+        );
+    let (route, path_params) = router.resolve(http::methods::GET, "/users").unwrap();
+    let request = Request::new("/users", http::methods::GET, None, path_params).with_headers(
+        std::collections::HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer token".to_string(),
+        )]),
+    );
+
+    let response = router.respond(route, request);
+
+    assert_eq!("{\"envelope\":{\"private\":\"/users\"}}", response.content);
+    assert_eq!(
+        "{\"envelope\":\"/users\"}",
+        get_response(&router, "/users").content
+    );
+}
+
+#[test]
+fn conflicts_should_report_parameter_and_static_overlap() {
+    let router = Router::new(vec![
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::get("/user/new", Box::new(route_handler)),
+    ]);
+
+    let conflicts = router.conflicts();
+
+    assert_eq!(
+        vec![RouteConflict {
+            method: http::methods::GET,
+            first: "/user/new".to_string(),
+            second: "/user/{id}".to_string(),
+            shadowed: false,
+        }],
+        conflicts
+    );
+    assert_eq!(
+        "GET /user/new and GET /user/{id} both match some paths, which are served by /user/new",
+        conflicts[0].to_string()
+    );
+}
+
+#[test]
+fn conflicts_should_report_shadowed_duplicate_patterns() {
+    let router = Router::new(vec![
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::get("/user/{name}", Box::new(route_handler)),
+    ]);
+
+    let conflicts = router.conflicts();
+
+    assert_eq!(1, conflicts.len());
+    assert!(conflicts[0].shadowed);
+    assert_eq!(
+        "GET /user/{name} is shadowed by GET /user/{id}, so it is never served",
+        conflicts[0].to_string()
+    );
+}
+
+#[test]
+fn conflicts_should_report_partial_overlap_without_shadowing() {
+    let router = Router::new(vec![
+        Route::get("/user/new", Box::new(route_handler)),
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::get("/{kind}/1", Box::new(route_handler)),
+    ]);
+
+    let shadowed: Vec<(String, String, bool)> = router
+        .conflicts()
+        .into_iter()
+        .map(|conflict| (conflict.first, conflict.second, conflict.shadowed))
+        .collect();
+
+    assert_eq!(
+        vec![
+            ("/user/new".to_string(), "/user/{id}".to_string(), false),
+            ("/user/{id}".to_string(), "/{kind}/1".to_string(), false),
+        ],
+        shadowed
+    );
+}
+
+#[test]
+fn conflicts_should_report_catch_all_patterns() {
+    let router = Router::new(vec![
+        Route::get("/files/{*path}", Box::new(route_handler)),
+        Route::get("/files/readme/raw", Box::new(route_handler)),
+        Route::get("/files", Box::new(route_handler)),
+    ]);
+
+    let shadowed: Vec<(String, String, bool)> = router
+        .conflicts()
+        .into_iter()
+        .map(|conflict| (conflict.first, conflict.second, conflict.shadowed))
+        .collect();
+
+    // an empty catch-all segment matches the path without it
+    assert_eq!(
+        vec![
+            ("/files".to_string(), "/files/{*path}".to_string(), false),
+            (
+                "/files/readme/raw".to_string(),
+                "/files/{*path}".to_string(),
+                false
+            ),
+        ],
+        shadowed
+    );
+}
+
+#[test]
+fn conflicts_should_ignore_distinct_methods_and_static_segments() {
+    let router = Router::new(vec![
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::post("/user/new", Box::new(route_handler)),
+        Route::get("/users/{id}", Box::new(route_handler)),
+        Route::get("/user/{id}/posts", Box::new(route_handler)),
+    ]);
+
+    assert!(router.conflicts().is_empty());
+}
+
+#[test]
+fn resolve_should_prefer_static_segments_regardless_of_registration_order() {
+    let router = Router::new(vec![
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::get("/user/new", Box::new(route_handler)),
+    ]);
+
+    let (new_route, _) = router.resolve(http::methods::GET, "/user/new").unwrap();
+    let (id_route, path_params) = router.resolve(http::methods::GET, "/user/1").unwrap();
+
+    assert_eq!("/user/new", new_route.spec.request_pattern);
+    assert_eq!("/user/{id}", id_route.spec.request_pattern);
+    assert_eq!(Some(&"1".to_string()), path_params.get("id"));
+}
+
+#[test]
+fn resolve_should_prefer_parameters_over_catch_all_segments() {
+    let router = Router::new(vec![
+        Route::get("/files/{*path}", Box::new(route_handler)),
+        Route::get("/files/{name}", Box::new(route_handler)),
+        Route::get("/{section}/readme", Box::new(route_handler)),
+    ]);
+
+    let resolve_pattern = |path: &str| {
+        router
+            .resolve(http::methods::GET, path)
+            .map(|(route, _)| route.spec.request_pattern.clone())
+    };
+
+    assert_eq!(
+        Some("/files/{name}".to_string()),
+        resolve_pattern("/files/readme")
+    );
+    assert_eq!(
+        Some("/files/{*path}".to_string()),
+        resolve_pattern("/files/a/b")
+    );
+    assert_eq!(
+        Some("/{section}/readme".to_string()),
+        resolve_pattern("/docs/readme")
+    );
+}
+
+#[test]
+fn new_should_keep_registration_order_of_equally_specific_routes() {
+    let router = Router::new(vec![
+        Route::get("/b/{id}", Box::new(route_handler)),
+        Route::get("/a/{id}", Box::new(route_handler)),
+        Route::get("/{kind}", Box::new(route_handler)),
+        Route::get("/c", Box::new(route_handler)),
+    ]);
+
+    let patterns: Vec<&str> = router
+        .routes
+        .iter()
+        .map(|route| route.spec.request_pattern.as_str())
+        .collect();
+
+    assert_eq!(vec!["/c", "/b/{id}", "/a/{id}", "/{kind}"], patterns);
+}
+
+#[test]
+fn route_debug_should_show_method_and_pattern_without_handler() {
+    let route = Route::get("/user/{id}", Box::new(route_handler));
+
+    let result = format!("{route:?}");
+
+    assert_eq!(
+        "Route { method: Get, request_pattern: \"/user/{id}\", .. }",
+        result
+    );
+}
+
+#[test]
+fn router_display_should_list_routes_in_match_order() {
+    let router = Router::new(vec![
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::delete("/user/new", Box::new(route_handler))
+            .with_description("Drafts a user.\nNever saved."),
+    ]);
+
+    let result = router.to_string();
+
+    assert_eq!(
+        "METHOD   PATTERN     DESCRIPTION\n\
+         DELETE   /user/new   Drafts a user.\n\
+         GET      /user/{id}  -",
+        result
+    );
+}
+
+#[test]
+fn resolve_fallback_should_pick_the_longest_matching_prefix() {
+    let router = Router::new(Vec::new())
+        .with_fallback("/", Box::new(|_| Response::ok("\"html\"", true)))
+        .with_fallback("/api/", Box::new(|_| Response::not_found()));
+    let fallback_response = |path: &str| {
+        let fallback = router.resolve_fallback(path).unwrap();
+        fallback(Request::new(
+            path,
+            http::methods::GET,
+            None,
+            Default::default(),
+        ))
+    };
+
+    assert_eq!(
+        http::status::Status::NotFound,
+        fallback_response("/api/missing?page=2").status
+    );
+    assert_eq!(
+        http::status::Status::NotFound,
+        fallback_response("/api").status
+    );
+    assert_eq!("\"html\"", fallback_response("/apis").content);
+    assert_eq!("\"html\"", fallback_response("/about").content);
+}
+
+#[test]
+fn resolve_fallback_should_be_none_outside_every_prefix() {
+    let router = Router::new(Vec::new()).with_fallback("/api", Box::new(route_handler));
+
+    assert!(router.resolve_fallback("/about").is_none());
+}
@@ -0,0 +1,115 @@
+use http::{
+    client::Client,
+    response::Response,
+    route::{Route, RouteSpec},
+    sdk,
+    validation::{ParamLocation, RouteParam},
+};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener},
+    sync::mpsc,
+    thread,
+};
+
+#[allow(dead_code)]
+mod example_client {
+    include!("sdk/example_client.rs");
+}
+
+fn example_routes() -> Vec<RouteSpec> {
+    let ok = || Box::new(|_| Response::ok("\"ok\"", false));
+    vec![
+        Route::get("/", ok()),
+        Route::get("/user/{id}", ok()).with_params(vec![RouteParam::new(
+            "id",
+            ParamLocation::Path,
+            "i32",
+        )]),
+        Route::get("/squared", ok()).with_params(vec![RouteParam::new(
+            "number",
+            ParamLocation::Query,
+            "String",
+        )]),
+        Route::post("/send-name", ok()).with_params(vec![RouteParam::new(
+            "name",
+            ParamLocation::Body,
+            "String",
+        )]),
+        Route::get("/files/{*path}", ok()),
+    ]
+    .into_iter()
+    .map(|route| route.spec)
+    .collect()
+}
+
+/// starts a server that answers `requests` requests with `200 OK`, and sends back
+/// the request line and body of every request it receives
+fn start_server(requests: usize) -> (SocketAddr, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for _ in 0..requests {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some(length) = line.strip_prefix("Content-Length: ") {
+                    content_length = length.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            std::io::Read::read_exact(&mut reader, &mut body).unwrap();
+            sender
+                .send(format!(
+                    "{}{}",
+                    request_line.trim_end(),
+                    String::from_utf8(body).unwrap()
+                ))
+                .unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+        }
+    });
+    (address, receiver)
+}
+
+#[test]
+fn generate_client_should_match_checked_in_example() {
+    let source = sdk::generate_client(&example_routes(), "ExampleClient");
+
+    assert_eq!(include_str!("sdk/example_client.rs"), source);
+}
+
+#[test]
+fn generated_client_should_call_every_route_with_encoded_values() {
+    let (address, requests) = start_server(5);
+    let client = example_client::ExampleClient::new(&format!("http://{address}/"), Client::new());
+
+    client.get_root().unwrap();
+    client.get_user_by_id(7).unwrap();
+    client.get_squared("1 2").unwrap();
+    client.post_send_name("Ada").unwrap();
+    client.get_files_by_path("css/site main.css").unwrap();
+
+    assert_eq!("GET / HTTP/1.1", requests.recv().unwrap());
+    assert_eq!("GET /user/7 HTTP/1.1", requests.recv().unwrap());
+    assert_eq!(
+        "GET /squared?number=1%202 HTTP/1.1",
+        requests.recv().unwrap()
+    );
+    assert_eq!("POST /send-name HTTP/1.1Ada", requests.recv().unwrap());
+    assert_eq!(
+        "GET /files/css/site%20main.css HTTP/1.1",
+        requests.recv().unwrap()
+    );
+}
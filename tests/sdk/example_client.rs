@@ -0,0 +1,64 @@
+// Generated by `http::sdk::generate_client`, edits will be overwritten.
+use http::client::{Client, ClientRequest, ClientResponse};
+
+/// [`ExampleClient`] calls every route of the server at [`ExampleClient::base_url`].
+pub struct ExampleClient {
+    /// [`ExampleClient::base_url`] is where the server is, such as `http://localhost:8080`.
+    pub base_url: String,
+    /// [`ExampleClient::client`] is the [`Client`] every call is sent with.
+    pub client: Client,
+}
+
+impl ExampleClient {
+    /// [`ExampleClient::new`] creates a new [`ExampleClient`] for the server at `base_url`.
+    pub fn new(base_url: &str, client: Client) -> Self {
+        ExampleClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    /// [`Self::get_root`] calls `GET /`.
+    pub fn get_root(&self) -> Result<ClientResponse, String> {
+        let url = format!("{}/", self.base_url);
+        self.client.send(&ClientRequest::new("GET", &url))
+    }
+
+    /// [`Self::get_user_by_id`] calls `GET /user/{id}`.
+    pub fn get_user_by_id(&self, id: i32) -> Result<ClientResponse, String> {
+        let url = format!("{}/user/{}", self.base_url, encode(&id.to_string()));
+        self.client.send(&ClientRequest::new("GET", &url))
+    }
+
+    /// [`Self::get_squared`] calls `GET /squared`.
+    pub fn get_squared(&self, number: &str) -> Result<ClientResponse, String> {
+        let url = format!("{}/squared?number={}", self.base_url, encode(number));
+        self.client.send(&ClientRequest::new("GET", &url))
+    }
+
+    /// [`Self::post_send_name`] calls `POST /send-name`.
+    pub fn post_send_name(&self, name: &str) -> Result<ClientResponse, String> {
+        let url = format!("{}/send-name", self.base_url);
+        self.client.send(&ClientRequest::new("POST", &url).with_body(name.as_bytes()))
+    }
+
+    /// [`Self::get_files_by_path`] calls `GET /files/{*path}`.
+    pub fn get_files_by_path(&self, path: &str) -> Result<ClientResponse, String> {
+        let url = format!("{}/files/{}", self.base_url, path.split('/').map(encode).collect::<Vec<_>>().join("/"));
+        self.client.send(&ClientRequest::new("GET", &url))
+    }
+}
+
+/// [`encode`] will percent-encode every character of `value` that isn't allowed in a
+/// path segment or query value as-is.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
@@ -0,0 +1,35 @@
+use minimal_api::environment::server::{self, ServerConfig};
+
+#[test]
+fn get_summary_should_list_the_effective_configuration() {
+    let server_config = ServerConfig::new();
+
+    let summary = server_config.get_summary();
+
+    let value_of = |name: &str| {
+        summary
+            .iter()
+            .find(|(entry, _)| *entry == name)
+            .map(|(_, value)| value.clone())
+    };
+    assert_eq!(
+        Some(server_config.get_server_address()),
+        value_of("address")
+    );
+    assert_eq!(Some(server_config.workers.to_string()), value_of("workers"));
+    assert_eq!(
+        Some(server_config.max_body_bytes.to_string()),
+        value_of("max body bytes")
+    );
+    assert!(value_of("request timeout").is_some());
+    assert!(value_of("features").is_some());
+}
+
+#[test]
+fn redact_credentials_should_hide_everything_before_the_host() {
+    assert_eq!(
+        "<redacted>@cache:11211",
+        server::redact_credentials("user:secret@cache:11211")
+    );
+    assert_eq!("cache:11211", server::redact_credentials("cache:11211"));
+}
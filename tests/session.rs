@@ -0,0 +1,441 @@
+use http::{
+    response::Response,
+    session::{
+        FileStore, MemoryStore, SESSION_COOKIE, Session, SessionBackend, SessionData, SessionStore,
+        Sessions, StoredSession, TcpStore,
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener},
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// starts a key-value server that answers the `get`, `set`, and `delete` commands
+/// of the memcached text protocol for `connections` connections
+fn start_memcached(connections: usize) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let mut values: HashMap<String, String> = HashMap::new();
+        for _ in 0..connections {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let command: Vec<&str> = line.trim_end().split(' ').collect();
+            let reply = match command.as_slice() {
+                ["get", key] => match values.get(*key) {
+                    Some(value) => {
+                        format!("VALUE {key} 0 {}\r\n{value}\r\nEND\r\n", value.len())
+                    }
+                    None => "END\r\n".to_string(),
+                },
+                ["set", key, _, _, length] => {
+                    let mut value = vec![0; length.parse::<usize>().unwrap() + 2];
+                    reader.read_exact(&mut value).unwrap();
+                    value.truncate(value.len() - 2);
+                    values.insert(key.to_string(), String::from_utf8(value).unwrap());
+                    "STORED\r\n".to_string()
+                }
+                ["delete", key] => match values.remove(*key) {
+                    Some(_) => "DELETED\r\n".to_string(),
+                    None => "NOT_FOUND\r\n".to_string(),
+                },
+                _ => "ERROR\r\n".to_string(),
+            };
+            stream.write_all(reply.as_bytes()).unwrap();
+        }
+    });
+    address
+}
+
+fn create_session_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("minimal-api-sessions-{name}"));
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+fn create_data(values: &[(&str, &str)]) -> SessionData {
+    values
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn create_stored_session(values: &[(&str, &str)], expires_in: Option<i64>) -> StoredSession {
+    StoredSession {
+        data: create_data(values),
+        // whole seconds, as stores keep them
+        expires_at: expires_in.map(|seconds| {
+            let now = SystemTime::UNIX_EPOCH
+                + Duration::from_secs(
+                    SystemTime::now()
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs(),
+                );
+            if seconds < 0 {
+                now - Duration::from_secs(seconds.unsigned_abs())
+            } else {
+                now + Duration::from_secs(seconds as u64)
+            }
+        }),
+        flash: SessionData::new(),
+    }
+}
+
+/// saves, loads, and removes a session in `store`
+fn assert_store_round_trip(store: &dyn SessionStore) {
+    let id = "0123456789abcdef0123456789abcdef";
+    let session = create_stored_session(
+        &[("user", "evan"), ("note", "say \"hi\"; bye")],
+        Some(3_600),
+    );
+
+    assert_eq!(Ok(None), store.load(id));
+    store.save(id, &session).unwrap();
+    assert_eq!(Ok(Some(session)), store.load(id));
+    store.remove(id).unwrap();
+    assert_eq!(Ok(None), store.load(id));
+}
+
+/// saves an expired and a live session in `store`, and removes the expired one
+fn assert_store_removes_expired(store: &dyn SessionStore) {
+    let expired_id = "00000000000000000000000000000001";
+    let live_id = "00000000000000000000000000000002";
+    let expired = create_stored_session(&[("user", "evan")], Some(-1));
+    let live = create_stored_session(&[("user", "ada")], None);
+    store.save(expired_id, &expired).unwrap();
+    store.save(live_id, &live).unwrap();
+
+    assert_eq!(Ok(1), store.remove_expired());
+    assert_eq!(Ok(None), store.load(expired_id));
+    assert_eq!(Ok(Some(live)), store.load(live_id));
+}
+
+/// gets the `Set-Cookie` header of `response`
+fn get_cookie(response: &Response) -> Option<&str> {
+    response.get_header("Set-Cookie")
+}
+
+// =============
+// session tests
+// =============
+#[test]
+fn session_backend_should_parse_every_store() {
+    assert_eq!(Ok(SessionBackend::Memory), "memory".parse());
+    assert_eq!(
+        Ok(SessionBackend::File(PathBuf::from("/var/sessions"))),
+        "file:/var/sessions".parse()
+    );
+    assert_eq!(
+        Ok(SessionBackend::Tcp("memcached:11211".to_string())),
+        "tcp:memcached:11211".parse()
+    );
+    assert!("redis".parse::<SessionBackend>().is_err());
+    assert!("file:".parse::<SessionBackend>().is_err());
+    assert_eq!(
+        "tcp:memcached:11211",
+        SessionBackend::Tcp("memcached:11211".to_string()).to_string()
+    );
+}
+
+#[test]
+fn memory_store_should_save_load_and_remove_sessions() {
+    assert_store_round_trip(&MemoryStore::new());
+}
+
+#[test]
+fn file_store_should_save_load_and_remove_sessions() {
+    let directory = create_session_directory("round-trip");
+
+    assert_store_round_trip(&FileStore::new(directory.clone()).unwrap());
+    assert!(directory.is_dir());
+}
+
+#[test]
+fn file_store_should_keep_sessions_across_instances() {
+    let directory = create_session_directory("restart");
+    let id = "fedcba9876543210fedcba9876543210";
+    let session = create_stored_session(&[("user", "evan")], None);
+
+    FileStore::new(directory.clone())
+        .unwrap()
+        .save(id, &session)
+        .unwrap();
+
+    assert_eq!(
+        Ok(Some(session)),
+        FileStore::new(directory).unwrap().load(id)
+    );
+}
+
+#[test]
+fn memory_store_should_remove_expired_sessions() {
+    assert_store_removes_expired(&MemoryStore::new());
+}
+
+#[test]
+fn file_store_should_remove_expired_sessions() {
+    let directory = create_session_directory("expired");
+
+    assert_store_removes_expired(&FileStore::new(directory).unwrap());
+}
+
+#[test]
+fn tcp_store_should_not_load_expired_sessions() {
+    let address = start_memcached(2);
+    let store = TcpStore::new(&address.to_string());
+    let id = "0123456789abcdef0123456789abcdef";
+
+    store
+        .save(id, &create_stored_session(&[("user", "evan")], Some(-1)))
+        .unwrap();
+
+    assert_eq!(Ok(None), store.load(id));
+    assert_eq!(Ok(0), store.remove_expired());
+}
+
+#[test]
+fn tcp_store_should_save_load_and_remove_sessions() {
+    let address = start_memcached(5);
+
+    assert_store_round_trip(&TcpStore::new(&address.to_string()));
+}
+
+#[test]
+fn tcp_store_should_fail_without_a_server() {
+    let address = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    assert!(TcpStore::new(&address.to_string()).load("0123").is_err());
+}
+
+#[test]
+fn session_should_track_modifications() {
+    let session = Session::with_data("0123", create_data(&[("user", "evan")]));
+
+    assert!(!session.is_modified());
+    assert_eq!(Some("evan".to_string()), session.get("user"));
+    assert_eq!(None, session.remove("missing"));
+    assert!(!session.is_modified());
+    session.insert("theme", "dark");
+    assert!(session.is_modified());
+    assert_eq!(
+        create_data(&[("user", "evan"), ("theme", "dark")]),
+        session.data()
+    );
+}
+
+#[test]
+fn session_clones_should_share_values() {
+    let session = Session::new();
+    let handler_session = session.clone();
+
+    handler_session.insert("user", "evan");
+
+    assert_eq!(Some("evan".to_string()), session.get("user"));
+    assert_eq!(32, session.id().len());
+    assert_ne!(Session::new().id(), session.id());
+}
+
+#[test]
+fn sessions_should_set_a_cookie_for_a_new_session_and_load_it_back() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new()));
+    let session = sessions.load(None).unwrap();
+    session.insert("user", "evan");
+
+    let response = sessions.persist(&session, Response::no_content()).unwrap();
+    let cookie = format!("theme=dark; {SESSION_COOKIE}={}", session.id());
+    let loaded = sessions.load(Some(&cookie)).unwrap();
+
+    assert!(
+        get_cookie(&response)
+            .unwrap()
+            .starts_with(&format!("{SESSION_COOKIE}={};", session.id()))
+    );
+    assert!(!get_cookie(&response).unwrap().contains("Max-Age"));
+    assert!(!loaded.is_new());
+    assert_eq!(Some("evan".to_string()), loaded.get("user"));
+}
+
+#[test]
+fn sessions_should_only_mark_the_cookie_secure_with_secure_cookie() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new()));
+    let secure_sessions = Sessions::new(Box::new(MemoryStore::new()))
+        .with_ttl(Duration::from_secs(60))
+        .with_secure_cookie();
+    let session = sessions.load(None).unwrap();
+    session.insert("user", "evan");
+    let secure_session = secure_sessions.load(None).unwrap();
+    secure_session.insert("user", "evan");
+
+    let response = sessions.persist(&session, Response::no_content()).unwrap();
+    let secure_response = secure_sessions
+        .persist(&secure_session, Response::no_content())
+        .unwrap();
+
+    assert!(!get_cookie(&response).unwrap().contains("Secure"));
+    assert!(
+        get_cookie(&secure_response)
+            .unwrap()
+            .ends_with("; Max-Age=60; HttpOnly; SameSite=Lax; Secure")
+    );
+}
+
+#[test]
+fn session_ids_should_be_unique_hex() {
+    let ids: Vec<String> = (0..1000).map(|_| Session::new().id().to_string()).collect();
+
+    assert!(
+        ids.iter()
+            .all(|id| id.len() == 32 && id.bytes().all(|byte| byte.is_ascii_hexdigit()))
+    );
+    assert_eq!(ids.len(), ids.iter().collect::<HashSet<_>>().len());
+}
+
+#[test]
+fn sessions_should_not_store_unmodified_sessions() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new()));
+    let session = sessions.load(None).unwrap();
+
+    let response = sessions.persist(&session, Response::no_content()).unwrap();
+    let cookie = format!("{SESSION_COOKIE}={}", session.id());
+
+    assert_eq!(None, get_cookie(&response));
+    assert!(sessions.load(Some(&cookie)).unwrap().is_new());
+}
+
+#[test]
+fn sessions_should_ignore_ids_that_were_never_created() {
+    let directory = create_session_directory("traversal");
+    let sessions = Sessions::new(Box::new(FileStore::new(directory).unwrap()));
+
+    let session = sessions
+        .load(Some(&format!("{SESSION_COOKIE}=../../etc/passwd")))
+        .unwrap();
+
+    assert!(session.is_new());
+    assert_ne!("../../etc/passwd", session.id());
+}
+
+#[test]
+fn sessions_with_ttl_should_set_max_age_and_expiry() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new())).with_ttl(Duration::from_secs(60));
+    let session = sessions.load(None).unwrap();
+    session.insert("user", "evan");
+
+    let response = sessions.persist(&session, Response::no_content()).unwrap();
+    let loaded = sessions
+        .load(Some(&format!("{SESSION_COOKIE}={}", session.id())))
+        .unwrap();
+
+    assert!(get_cookie(&response).unwrap().contains("; Max-Age=60;"));
+    assert!(loaded.expires_at().unwrap() > SystemTime::now());
+}
+
+#[test]
+fn sessions_without_sliding_renewal_should_keep_their_expiry() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new())).with_ttl(Duration::from_secs(60));
+    let session = sessions.load(None).unwrap();
+    session.insert("user", "evan");
+    sessions.persist(&session, Response::no_content()).unwrap();
+    let cookie = format!("{SESSION_COOKIE}={}", session.id());
+    let loaded = sessions.load(Some(&cookie)).unwrap();
+
+    loaded.insert("theme", "dark");
+    let response = sessions.persist(&loaded, Response::no_content()).unwrap();
+    let reloaded = sessions.load(Some(&cookie)).unwrap();
+
+    assert_eq!(None, get_cookie(&response));
+    assert_eq!(loaded.expires_at(), reloaded.expires_at());
+}
+
+#[test]
+fn sessions_with_sliding_renewal_should_renew_on_every_request() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new()))
+        .with_ttl(Duration::from_secs(60))
+        .with_sliding_renewal();
+    let session = sessions.load(None).unwrap();
+    session.insert("user", "evan");
+    sessions.persist(&session, Response::no_content()).unwrap();
+    let cookie = format!("{SESSION_COOKIE}={}", session.id());
+    let loaded = sessions.load(Some(&cookie)).unwrap();
+
+    let response = sessions.persist(&loaded, Response::no_content()).unwrap();
+    let renewed = sessions.load(Some(&cookie)).unwrap();
+
+    assert!(get_cookie(&response).unwrap().contains("; Max-Age=60;"));
+    assert!(renewed.expires_at() > loaded.expires_at());
+    assert_eq!(Some("evan".to_string()), renewed.get("user"));
+}
+
+#[test]
+fn sessions_should_expire_the_cookie_of_a_cleared_session() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new()));
+    let session = sessions.load(None).unwrap();
+    session.insert("user", "evan");
+    sessions.persist(&session, Response::no_content()).unwrap();
+    let cookie = format!("{SESSION_COOKIE}={}", session.id());
+    let loaded = sessions.load(Some(&cookie)).unwrap();
+
+    loaded.clear();
+    let response = sessions.persist(&loaded, Response::no_content()).unwrap();
+
+    assert!(get_cookie(&response).unwrap().contains("; Max-Age=0;"));
+    assert!(sessions.load(Some(&cookie)).unwrap().is_new());
+}
+
+#[test]
+fn session_flash_should_be_readable_once_on_the_next_request() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new()));
+    let session = sessions.load(None).unwrap();
+    session.flash("notice", "profile saved");
+    sessions.persist(&session, Response::no_content()).unwrap();
+    let cookie = format!("{SESSION_COOKIE}={}", session.id());
+
+    let next = sessions.load(Some(&cookie)).unwrap();
+
+    assert_eq!(None, session.take_flash("notice"));
+    assert_eq!(Some("profile saved".to_string()), next.take_flash("notice"));
+    assert_eq!(None, next.take_flash("notice"));
+}
+
+#[test]
+fn session_flash_should_be_gone_after_the_next_request() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new()));
+    let session = sessions.load(None).unwrap();
+    session.insert("user", "evan");
+    session.flash("notice", "signed in");
+    sessions.persist(&session, Response::no_content()).unwrap();
+    let cookie = format!("{SESSION_COOKIE}={}", session.id());
+
+    // the next request doesn't read the flashed value
+    let next = sessions.load(Some(&cookie)).unwrap();
+    assert!(next.is_modified());
+    sessions.persist(&next, Response::no_content()).unwrap();
+    let after = sessions.load(Some(&cookie)).unwrap();
+
+    assert_eq!(None, after.take_flash("notice"));
+    assert_eq!(Some("evan".to_string()), after.get("user"));
+}
+
+#[test]
+fn file_store_should_keep_flashed_values() {
+    let directory = create_session_directory("flash");
+    let store = FileStore::new(directory).unwrap();
+    let id = "0123456789abcdef0123456789abcdef";
+    let mut session = create_stored_session(&[], None);
+    session.flash = create_data(&[("notice", "saved")]);
+
+    store.save(id, &session).unwrap();
+
+    assert_eq!(Ok(Some(session)), store.load(id));
+}
@@ -0,0 +1,50 @@
+use http::sha256;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[test]
+fn hash_should_match_the_fips_180_test_vectors() {
+    assert_eq!(
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        to_hex(&sha256::hash(b""))
+    );
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        to_hex(&sha256::hash(b"abc"))
+    );
+    assert_eq!(
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        to_hex(&sha256::hash(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        ))
+    );
+}
+
+#[test]
+fn hash_should_pad_messages_that_end_near_a_block_boundary() {
+    assert_eq!(
+        "9f4390f8d30c2dd92ec9f095b65e2b9ae9b0a925a5258e241c9f1e910f734318",
+        to_hex(&sha256::hash(&[b'a'; 55]))
+    );
+    assert_eq!(
+        "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb",
+        to_hex(&sha256::hash(&[b'a'; 64]))
+    );
+}
+
+#[test]
+fn hmac_should_match_the_rfc_4231_test_vectors() {
+    assert_eq!(
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        to_hex(&sha256::hmac(b"what do ya want for nothing?", b"Jefe"))
+    );
+    assert_eq!(
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        to_hex(&sha256::hmac(
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+            &[0xaa; 131]
+        ))
+    );
+}
@@ -0,0 +1,48 @@
+use minimal_api::server::{service, shutdown::Shutdown};
+use std::{path::Path, thread, time::Duration};
+
+#[test]
+fn wait_should_return_once_any_clone_is_triggered() {
+    let shutdown = Shutdown::new();
+    let trigger = shutdown.clone();
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        trigger.trigger();
+    });
+    shutdown.wait();
+    handle.join().unwrap();
+
+    assert!(shutdown.is_triggered());
+}
+
+#[test]
+fn is_triggered_should_be_false_for_new_shutdown() {
+    let shutdown = Shutdown::new();
+
+    assert!(!shutdown.is_triggered());
+}
+
+#[test]
+fn launchd_plist_should_run_program_with_escaped_label() {
+    let result = service::launchd_plist(
+        "com.example.api&co",
+        Path::new("/usr/local/bin/minimal-api"),
+    );
+
+    assert!(result.contains("<key>Label</key>\n    <string>com.example.api&amp;co</string>"));
+    assert!(result.contains("<string>/usr/local/bin/minimal-api</string>"));
+    assert!(result.contains("<key>KeepAlive</key>\n    <true/>"));
+    assert!(result.contains("<key>RunAtLoad</key>\n    <true/>"));
+}
+
+#[test]
+fn wait_timeout_should_return_false_when_not_triggered() {
+    let shutdown = Shutdown::new();
+
+    let timed_out = !shutdown.wait_timeout(Duration::from_millis(10));
+    shutdown.trigger();
+
+    assert!(timed_out);
+    assert!(shutdown.wait_timeout(Duration::from_millis(10)));
+}
@@ -0,0 +1,131 @@
+use http::{
+    client::{Client, ClientRequest},
+    signing::{HmacSigner, RequestSigner},
+};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, mpsc},
+    thread,
+};
+
+fn create_request() -> ClientRequest {
+    ClientRequest::new("POST", "http://example.com:8080/users?b=2&a=1")
+        .with_header("Content-Type", "application/json")
+        .with_body(br#"{"id":1}"#)
+}
+
+fn find_header<'a>(request: &'a ClientRequest, name: &str) -> Option<&'a str> {
+    request
+        .headers
+        .iter()
+        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn sign_at_should_add_sigv4_style_signature() {
+    let signer = HmacSigner::new("test", b"secret").with_signed_headers(&["Content-Type"]);
+    let mut request = create_request();
+
+    signer.sign_at(&mut request, 1_700_000_000).unwrap();
+
+    assert_eq!(
+        Some("1700000000"),
+        find_header(&request, "X-Signature-Timestamp")
+    );
+    assert_eq!(
+        Some(
+            "HMAC-SHA256 KeyId=test, SignedHeaders=content-type;host;x-content-sha256;x-signature-timestamp, Signature=a344f0593637ae8316f2e80be3bf2e424cf274a864359fbe753126f07973bcc6"
+        ),
+        find_header(&request, "Authorization")
+    );
+}
+
+#[test]
+fn sign_at_should_replace_previous_signature() {
+    let signer = HmacSigner::new("test", b"secret").with_signature_header("X-Signature");
+    let mut request = create_request();
+
+    signer.sign_at(&mut request, 1).unwrap();
+    let first = find_header(&request, "X-Signature").unwrap().to_string();
+    signer.sign_at(&mut request, 2).unwrap();
+
+    assert_eq!(
+        1,
+        request
+            .headers
+            .iter()
+            .filter(|(name, _)| name == "X-Signature")
+            .count()
+    );
+    assert_ne!(first, find_header(&request, "X-Signature").unwrap());
+    assert_eq!(None, find_header(&request, "Authorization"));
+}
+
+#[test]
+fn sign_at_should_change_signature_with_body() {
+    let signer = HmacSigner::new("test", b"secret");
+    let mut original = create_request();
+    let mut tampered = create_request().with_body(br#"{"id":2}"#);
+
+    signer.sign_at(&mut original, 1).unwrap();
+    signer.sign_at(&mut tampered, 1).unwrap();
+
+    assert_ne!(
+        find_header(&original, "Authorization"),
+        find_header(&tampered, "Authorization")
+    );
+}
+
+#[test]
+fn sign_should_reject_non_http_url() {
+    let signer = HmacSigner::new("test", b"secret");
+    let mut request = ClientRequest::new("GET", "ftp://example.com");
+
+    assert!(signer.sign(&mut request).is_err());
+}
+
+#[test]
+fn debug_should_not_show_secret() {
+    let signer = HmacSigner::new("test", b"hunter2");
+
+    assert!(!format!("{signer:?}").contains("hunter2"));
+}
+
+#[test]
+fn send_should_sign_request_with_signer() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut head = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+            head.push_str(&line);
+        }
+        sender.send(head).unwrap();
+        stream
+            .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+            .unwrap();
+    });
+    let client = Client::new().with_signer(Arc::new(HmacSigner::new("gateway", b"secret")));
+
+    let response = client
+        .send(&ClientRequest::new(
+            "GET",
+            &format!("http://{address}/hooks"),
+        ))
+        .unwrap();
+    let head = receiver.recv().unwrap();
+
+    assert_eq!(204, response.status_code);
+    assert!(head.contains("Authorization: HMAC-SHA256 KeyId=gateway, SignedHeaders=host;x-content-sha256;x-signature-timestamp, Signature="));
+    assert!(head.contains("X-Signature-Timestamp: "));
+}
@@ -0,0 +1,169 @@
+use http::static_files::{IMMUTABLE_CACHE_CONTROL, REVALIDATE_CACHE_CONTROL, StaticFiles};
+use std::{
+    fs,
+    io::Read,
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+};
+
+fn create_asset_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("minimal-api-{name}"));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(directory.join("css")).unwrap();
+    fs::write(directory.join("app.js"), "console.log('hello');").unwrap();
+    fs::write(directory.join("css/site.css"), "body { margin: 0; }").unwrap();
+    directory
+}
+
+#[test]
+fn asset_path_should_return_fingerprinted_path_from_manifest() {
+    let static_files = StaticFiles::new("/assets", create_asset_directory("asset-path"));
+
+    let result = static_files.asset_path("app.js").unwrap();
+
+    assert!(result.starts_with("/assets/app."));
+    assert!(result.ends_with(".js"));
+    assert_ne!("/assets/app.js", result);
+    assert!(static_files.asset_path("css/site.css").is_some());
+    assert!(static_files.asset_path("missing.js").is_none());
+}
+
+#[test]
+fn asset_path_should_change_with_file_contents() {
+    let directory = create_asset_directory("asset-path-changes");
+    let before = StaticFiles::new("/assets", &directory).asset_path("app.js");
+
+    fs::write(directory.join("app.js"), "console.log('goodbye');").unwrap();
+    let after = StaticFiles::new("/assets", &directory).asset_path("app.js");
+
+    assert_ne!(before, after);
+}
+
+#[test]
+fn resolve_should_only_resolve_known_assets_under_prefix() {
+    let static_files = StaticFiles::new("/assets", create_asset_directory("resolve"));
+
+    let plain = static_files.resolve("/assets/app.js").unwrap();
+    let fingerprinted = static_files
+        .resolve(&static_files.asset_path("app.js").unwrap())
+        .unwrap();
+
+    assert!(!plain.is_fingerprinted);
+    assert!(fingerprinted.is_fingerprinted);
+    assert_eq!(plain.file_path, fingerprinted.file_path);
+    assert_eq!("text/javascript; charset=utf-8", plain.content_type);
+    assert!(static_files.resolve("/app.js").is_none());
+    assert!(static_files.resolve("/assets/../Cargo.toml").is_none());
+}
+
+#[test]
+fn send_should_mark_only_fingerprinted_assets_immutable() {
+    let static_files = StaticFiles::new("/assets", create_asset_directory("send"));
+    let fingerprinted_path = static_files.asset_path("css/site.css").unwrap();
+
+    let mut plain = Vec::new();
+    static_files
+        .resolve("/assets/css/site.css")
+        .unwrap()
+        .send(&mut plain, None)
+        .unwrap();
+    let mut fingerprinted = Vec::new();
+    static_files
+        .resolve(&fingerprinted_path)
+        .unwrap()
+        .send(&mut fingerprinted, None)
+        .unwrap();
+    let plain = String::from_utf8(plain).unwrap();
+    let fingerprinted = String::from_utf8(fingerprinted).unwrap();
+
+    assert!(plain.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(plain.contains(&format!("Cache-Control: {REVALIDATE_CACHE_CONTROL}\r\n")));
+    assert!(fingerprinted.contains(&format!("Cache-Control: {IMMUTABLE_CACHE_CONTROL}\r\n")));
+    assert!(fingerprinted.contains("Content-Length: 19\r\n"));
+    assert!(fingerprinted.ends_with("\r\n\r\nbody { margin: 0; }"));
+}
+
+#[test]
+fn send_should_return_not_modified_when_file_is_unchanged_since_date() {
+    let static_files = StaticFiles::new("/assets", create_asset_directory("not-modified"));
+    let asset = static_files.resolve("/assets/app.js").unwrap();
+
+    let mut response = Vec::new();
+    asset
+        .send(&mut response, Some("Fri, 31 Dec 9999 23:59:59 GMT"))
+        .unwrap();
+    let response = String::from_utf8(response).unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 304 NOT MODIFIED\r\n"));
+    assert!(response.contains("Last-Modified: "));
+    assert!(!response.contains("Content-Length"));
+    assert!(response.ends_with("\r\n\r\n"));
+}
+
+#[test]
+fn send_should_return_file_when_modified_since_date() {
+    let static_files = StaticFiles::new("/assets", create_asset_directory("modified"));
+    let asset = static_files.resolve("/assets/app.js").unwrap();
+
+    let mut response = Vec::new();
+    asset
+        .send(&mut response, Some("Thu, 01 Jan 1970 00:00:00 GMT"))
+        .unwrap();
+    let response = String::from_utf8(response).unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with("console.log('hello');"));
+}
+
+#[test]
+fn send_should_return_not_modified_for_its_own_last_modified_date() {
+    let static_files = StaticFiles::new("/assets", create_asset_directory("last-modified"));
+    let asset = static_files.resolve("/assets/app.js").unwrap();
+    let mut first_response = Vec::new();
+    asset.send(&mut first_response, None).unwrap();
+    let first_response = String::from_utf8(first_response).unwrap();
+    let last_modified = first_response
+        .lines()
+        .find_map(|line| line.strip_prefix("Last-Modified: "))
+        .unwrap();
+
+    let mut response = Vec::new();
+    asset.send(&mut response, Some(last_modified)).unwrap();
+    let response = String::from_utf8(response).unwrap();
+
+    assert!(last_modified.ends_with(" GMT"));
+    assert!(response.starts_with("HTTP/1.1 304 NOT MODIFIED\r\n"));
+}
+
+#[test]
+fn send_should_stream_large_file_to_tcp_stream() {
+    let directory = create_asset_directory("large-file");
+    let contents: Vec<u8> = (0..4 * 1024 * 1024)
+        .map(|index| (index % 251) as u8)
+        .collect();
+    fs::write(directory.join("large.bin"), &contents).unwrap();
+    let static_files = StaticFiles::new("/assets", &directory);
+    let asset = static_files.resolve("/assets/large.bin").unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        asset.send(&mut stream, None).unwrap();
+    });
+    let mut response = Vec::new();
+    TcpStream::connect(address)
+        .unwrap()
+        .read_to_end(&mut response)
+        .unwrap();
+    server.join().unwrap();
+
+    let head_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .unwrap();
+    let head = String::from_utf8_lossy(&response[..head_end]);
+    assert!(head.contains(&format!("Content-Length: {}", contents.len())));
+    assert!(head.contains("Content-Type: application/octet-stream"));
+    assert_eq!(contents, &response[head_end + 4..]);
+}
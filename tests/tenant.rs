@@ -0,0 +1,160 @@
+use http::{
+    context::Context,
+    intern::HeaderName,
+    methods::GET,
+    request::Request,
+    response::Response,
+    session::{FileStore, MemoryStore, SESSION_COOKIE, Sessions},
+    tenant::{TenantId, TenantResolver},
+};
+use std::{collections::HashMap, fs};
+
+fn create_headers(headers: &[(&str, &str)]) -> HashMap<HeaderName, String> {
+    headers
+        .iter()
+        .map(|(name, value)| (HeaderName::new(name), value.to_string()))
+        .collect()
+}
+
+// ============
+// tenant tests
+// ============
+#[test]
+fn tenant_id_should_only_accept_safe_ids() {
+    assert_eq!(
+        Some("acme"),
+        TenantId::new("ACME").as_ref().map(TenantId::as_str)
+    );
+    assert!(TenantId::new("acme-west_2").is_some());
+    assert!(TenantId::new("").is_none());
+    assert!(TenantId::new("../acme").is_none());
+    assert!(TenantId::new("acme corp").is_none());
+    assert!(TenantId::new(&"a".repeat(64)).is_none());
+}
+
+#[test]
+fn tenant_id_should_scope_keys() {
+    let tenant = TenantId::new("acme").unwrap();
+
+    assert_eq!("acme.visits", tenant.scope("visits"));
+}
+
+#[test]
+fn subdomain_resolver_should_read_the_label_below_the_base_domain() {
+    let resolver = TenantResolver::Subdomain("example.com".to_string());
+
+    let resolved = resolver.resolve(
+        &create_headers(&[("Host", "acme.Example.com:8080")]),
+        "/users",
+    );
+    let nested = resolver.resolve(
+        &create_headers(&[("Host", "api.acme.example.com")]),
+        "/users",
+    );
+    let bare = resolver.resolve(&create_headers(&[("Host", "example.com")]), "/users");
+
+    assert_eq!(
+        Some((TenantId::new("acme").unwrap(), "/users".to_string())),
+        resolved
+    );
+    assert_eq!(
+        Some("acme"),
+        nested.as_ref().map(|(tenant, _)| tenant.as_str())
+    );
+    assert_eq!(None, bare);
+}
+
+#[test]
+fn header_resolver_should_read_the_header() {
+    let resolver = TenantResolver::Header("X-Tenant".to_string());
+
+    let resolved = resolver.resolve(&create_headers(&[("X-Tenant", " globex ")]), "/users");
+    let missing = resolver.resolve(&create_headers(&[]), "/users");
+
+    assert_eq!(
+        Some((TenantId::new("globex").unwrap(), "/users".to_string())),
+        resolved
+    );
+    assert_eq!(None, missing);
+}
+
+#[test]
+fn path_prefix_resolver_should_route_by_the_rest_of_the_path() {
+    let resolver = TenantResolver::PathPrefix;
+    let headers = create_headers(&[]);
+
+    assert_eq!(
+        Some((TenantId::new("acme").unwrap(), "/users/7".to_string())),
+        resolver.resolve(&headers, "/acme/users/7")
+    );
+    assert_eq!(
+        Some((TenantId::new("acme").unwrap(), "/".to_string())),
+        resolver.resolve(&headers, "/acme")
+    );
+    assert_eq!(None, resolver.resolve(&headers, "/"));
+}
+
+#[test]
+fn tenant_resolver_should_parse_every_resolver() {
+    for value in ["subdomain:example.com", "header:X-Tenant", "path"] {
+        assert_eq!(value, value.parse::<TenantResolver>().unwrap().to_string());
+    }
+    assert!("subdomain:".parse::<TenantResolver>().is_err());
+    assert!("cookie:tenant".parse::<TenantResolver>().is_err());
+}
+
+#[test]
+fn sessions_should_be_kept_apart_for_every_tenant() {
+    let sessions = Sessions::new(Box::new(MemoryStore::new()));
+    let acme = TenantId::new("acme").unwrap();
+    let globex = TenantId::new("globex").unwrap();
+    let session = sessions.load_for_tenant(None, Some(&acme)).unwrap();
+    session.insert("user", "evan");
+
+    sessions
+        .persist_for_tenant(&session, Some(&acme), Response::no_content())
+        .unwrap();
+    let cookie = format!("{SESSION_COOKIE}={}", session.id());
+
+    let acme_session = sessions
+        .load_for_tenant(Some(&cookie), Some(&acme))
+        .unwrap();
+    let globex_session = sessions
+        .load_for_tenant(Some(&cookie), Some(&globex))
+        .unwrap();
+    let untenanted_session = sessions.load(Some(&cookie)).unwrap();
+
+    assert_eq!(Some("evan".to_string()), acme_session.get("user"));
+    assert!(globex_session.is_new());
+    assert!(untenanted_session.is_new());
+}
+
+#[test]
+fn file_store_should_name_tenant_sessions_after_the_tenant() {
+    let directory = std::env::temp_dir().join("minimal-api-sessions-tenant");
+    let _ = fs::remove_dir_all(&directory);
+    let sessions = Sessions::new(Box::new(FileStore::new(directory.clone()).unwrap()));
+    let acme = TenantId::new("acme").unwrap();
+    let session = sessions.load_for_tenant(None, Some(&acme)).unwrap();
+    session.insert("user", "evan");
+
+    sessions
+        .persist_for_tenant(&session, Some(&acme), Response::no_content())
+        .unwrap();
+
+    assert!(
+        directory
+            .join(format!("acme.{}.json", session.id()))
+            .exists()
+    );
+}
+
+#[test]
+fn context_should_expose_the_tenant() {
+    let tenant = TenantId::new("acme").unwrap();
+    let request = Request::new("/users", GET, None, HashMap::new()).with_extension(tenant.clone());
+
+    let context = Context::new(request, "GET /users");
+
+    assert_eq!(Some(&tenant), context.tenant());
+}
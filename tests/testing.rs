@@ -0,0 +1,128 @@
+use http::{
+    methods::Method,
+    request::Request,
+    response::Response,
+    route::Route,
+    router::Router,
+    testing::{self, DEFAULT_CASES, Gen},
+};
+
+// ====================
+// common route handler
+// ====================
+fn route_handler(request: Request) -> Response {
+    Response::ok(request.method.as_str(), false)
+}
+
+#[test]
+fn resolve_should_get_first_matching_route_and_path_params() {
+    let router = Router::new(vec![
+        Route::post("/user/{id}", Box::new(route_handler)),
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::get("/user/{name}", Box::new(route_handler)),
+    ]);
+
+    let (route, path_params) = router.resolve(Method::Get, "/user/7").unwrap();
+
+    assert_eq!(Method::Get, route.spec.method);
+    assert_eq!(Some(&"7".to_string()), path_params.get("id"));
+    assert!(router.resolve(Method::Delete, "/user/7").is_none());
+    assert!(router.resolve(Method::Get, "/users").is_none());
+}
+
+#[test]
+fn gen_should_be_reproducible_from_its_seed() {
+    let mut first = Gen::new(99);
+    let mut second = Gen::new(99);
+
+    assert_eq!(first.path(5), second.path(5));
+    assert_eq!(first.segment(), second.segment());
+    assert_ne!(Gen::new(1).next_u64(), Gen::new(2).next_u64());
+}
+
+#[test]
+fn path_for_should_always_be_matched_by_its_route() {
+    let routes = [
+        Route::get("/", Box::new(route_handler)),
+        Route::get("/user/{id}/posts/{post}", Box::new(route_handler)),
+        Route::get("/files/{*path}", Box::new(route_handler)),
+    ];
+
+    let result = testing::check(DEFAULT_CASES, 3, |generator| {
+        routes.iter().try_for_each(|route| {
+            let path = generator.path_for(&route.spec);
+            route.spec.matches_path(&path).map(|_| ()).ok_or(format!(
+                "{path} did not match {}",
+                route.spec.request_pattern
+            ))
+        })
+    });
+
+    assert_eq!(Ok(()), result);
+}
+
+#[test]
+fn check_should_report_seed_of_failing_case() {
+    let result = testing::check(10, 100, |generator| {
+        if generator.seed() == 104 {
+            Err("unlucky".to_string())
+        } else {
+            Ok(())
+        }
+    });
+
+    assert_eq!(
+        Err("case 4 of 10 failed with seed 104: unlucky".to_string()),
+        result
+    );
+}
+
+#[test]
+fn find_ambiguities_should_find_overlapping_routes() {
+    let router = Router::new(vec![
+        Route::get("/user/{id}", Box::new(route_handler)),
+        Route::get("/user/me", Box::new(route_handler)),
+        Route::post("/user/me", Box::new(route_handler)),
+        Route::get("/files/{*path}", Box::new(route_handler)),
+    ]);
+
+    let ambiguities = testing::find_ambiguities(&router, 32, 0);
+
+    assert_eq!(1, ambiguities.len());
+    assert_eq!("/user/me", ambiguities[0].path);
+    assert_eq!(
+        "GET /user/me is matched by /user/me, then /user/{id}",
+        ambiguities[0].to_string()
+    );
+}
+
+#[test]
+fn render_snapshot_should_sort_headers_and_hide_time() {
+    let response = Response::ok("\"hi\"", false)
+        .with_header("X-Trace", "abc")
+        .with_header("Cache-Control", "no-store");
+
+    assert_eq!(
+        "200 OK\nCache-Control: no-store\nContent-Type: application/json; charset=utf-8\nX-Trace: abc\n\n\
+         {\"content\":\"hi\",\"status\":\"200 OK\",\"time\":\"[time]\"}\n",
+        testing::render_snapshot(&response)
+    );
+}
+
+#[test]
+fn snapshot_should_write_missing_golden_file_then_compare_against_it() {
+    let path = std::env::temp_dir()
+        .join(format!("minimal-api-snapshots-{}", std::process::id()))
+        .join("not_found.txt");
+
+    let written = testing::snapshot(&Response::not_found(), &path);
+    let matched = testing::snapshot(&Response::not_found(), &path);
+    let changed = testing::snapshot(&Response::bad_request(), &path);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    assert_eq!(Ok(()), written);
+    assert_eq!(Ok(()), matched);
+    let reason = changed.unwrap_err();
+    assert!(reason.contains("at line 1"), "{reason}");
+    assert!(reason.contains("expected: 404 NOT FOUND"), "{reason}");
+}
@@ -0,0 +1,203 @@
+use http::{
+    json::Json,
+    request::Request,
+    status::Status,
+    validation::{ParamLocation, RouteParam},
+};
+use http_attributes::{http_get, http_post};
+use std::{collections::HashMap, str::FromStr};
+
+// =================
+// endpoints to test
+// =================
+#[http_get("/validate/test/{id}")]
+fn test_validate_get(id: u64, number: i32) -> String {
+    format!("{id} {number}")
+}
+
+#[http_post("/validate/test")]
+fn test_validate_post(content: String) -> String {
+    content
+}
+
+#[http_get("/validate/rules")]
+fn test_validate_rules(
+    #[validate(range(min = 1, max = 100))] page: u32,
+    #[validate(range(min = -1.5))] offset: f64,
+    #[validate(length(min = 1, max = 8))] name: String,
+    #[header("X-Limit")]
+    #[validate(range(max = 10))]
+    limit: u8,
+) -> String {
+    format!("\"{name} {page} {offset} {limit}\"")
+}
+
+/// a body type that isn't a primitive, which is read from a body such as `21.5C`
+#[derive(Debug)]
+struct Temperature(f64);
+
+impl FromStr for Temperature {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value
+            .strip_suffix('C')
+            .and_then(|degrees| degrees.parse().ok())
+            .map(Temperature)
+            .ok_or_else(|| format!("{value} is not a temperature"))
+    }
+}
+
+#[http_post("/validate/temperature")]
+fn test_validate_temperature(temperature: Temperature) -> String {
+    format!("{}", temperature.0)
+}
+
+fn create_rules_request(query: &str, limit: &str) -> Request {
+    Request::new(
+        &format!("/validate/rules?{query}"),
+        http::methods::GET,
+        None,
+        HashMap::new(),
+    )
+    .with_headers(HashMap::from([("X-Limit".to_string(), limit.to_string())]))
+}
+
+// ================
+// validation tests
+// ================
+#[test]
+fn http_attributes_should_record_route_params() {
+    let expected = vec![
+        RouteParam::new("id", ParamLocation::Path, "u64"),
+        RouteParam::new("number", ParamLocation::Query, "i32"),
+    ];
+
+    assert_eq!(expected, test_validate_get().spec.params);
+}
+
+#[test]
+fn validate_should_return_no_violations_with_valid_request() {
+    let request = Request::new(
+        "/validate/test/1?number=2",
+        http::methods::GET,
+        None,
+        HashMap::from([("id".to_string(), "1".to_string())]),
+    );
+
+    let result = test_validate_get().spec.validate(&request);
+
+    assert!(result.is_empty());
+}
+
+#[test]
+fn validate_should_return_violation_for_each_invalid_param() {
+    let request = Request::new(
+        "/validate/test/abc?number=AAA",
+        http::methods::GET,
+        None,
+        HashMap::from([("id".to_string(), "abc".to_string())]),
+    );
+
+    let result = test_validate_get().spec.validate(&request);
+
+    assert_eq!(2, result.len());
+    assert_eq!("id", result[0].parameter);
+    assert_eq!("must be of type u64", result[0].message);
+    assert_eq!("number", result[1].parameter);
+    assert_eq!(ParamLocation::Query, result[1].location);
+}
+
+#[test]
+fn validate_should_return_violation_for_missing_body() {
+    let request = Request::new("/validate/test", http::methods::POST, None, HashMap::new());
+
+    let result = test_validate_post().spec.validate(&request);
+
+    assert_eq!(1, result.len());
+    assert_eq!(ParamLocation::Body, result[0].location);
+    assert_eq!("is required", result[0].message);
+}
+
+#[test]
+fn validate_should_check_a_body_against_its_declared_type() {
+    let route = test_validate_temperature();
+    let valid = Request::new(
+        "/validate/temperature",
+        http::methods::POST,
+        Some("21.5C".to_string()),
+        HashMap::new(),
+    );
+    let invalid = Request::new(
+        "/validate/temperature",
+        http::methods::POST,
+        Some("{\"degrees\":21.5}".to_string()),
+        HashMap::new(),
+    );
+
+    let valid_result = route.spec.validate(&valid);
+    let invalid_result = route.spec.validate(&invalid);
+
+    assert!(valid_result.is_empty());
+    assert_eq!(1, invalid_result.len());
+    assert_eq!(ParamLocation::Body, invalid_result[0].location);
+    assert_eq!("must be of type Temperature", invalid_result[0].message);
+}
+
+#[test]
+fn validate_should_only_check_primitive_types_without_a_type_check() {
+    let request = Request::new(
+        "/validate/temperature",
+        http::methods::POST,
+        Some("not a temperature".to_string()),
+        HashMap::new(),
+    );
+    let unchecked = RouteParam::new("temperature", ParamLocation::Body, "Temperature");
+    let checked = unchecked
+        .clone()
+        .with_type_check(|value| value.parse::<Temperature>().is_ok());
+
+    assert!(unchecked.validate(&request).is_none());
+    assert!(checked.validate(&request).is_some());
+}
+
+#[test]
+fn validate_attribute_should_run_the_handler_when_every_rule_holds() {
+    let request = create_rules_request("page=100&offset=-1.5&name=evan", "10");
+
+    let response = test_validate_rules().get_response(request);
+
+    assert_eq!(Status::Ok, response.status);
+    assert!(response.content.contains("evan 100 -1.5 10"));
+}
+
+#[test]
+fn validate_attribute_should_answer_every_broken_rule_with_unprocessable_entity() {
+    let request = create_rules_request("page=0&offset=-2&name=", "11");
+
+    let response = test_validate_rules().get_response(request);
+
+    assert_eq!(Status::UnprocessableEntity, response.status);
+    let body = Json::parse(&response.content).unwrap();
+    let messages: Vec<(&str, &str)> = body
+        .get("violations")
+        .and_then(Json::as_array)
+        .unwrap()
+        .iter()
+        .filter_map(|violation| {
+            Some((
+                violation.get("parameter")?.as_str()?,
+                violation.get("message")?.as_str()?,
+            ))
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("page", "must be between 1 and 100"),
+            ("offset", "must be at least -1.5"),
+            ("name", "must have a length between 1 and 8"),
+            ("X-Limit", "must be at most 10"),
+        ],
+        messages
+    );
+}
@@ -0,0 +1,76 @@
+use http::{json::Json, methods::GET, request::Request, respond::Respond, response::Response, xml};
+use std::collections::HashMap;
+
+// ==================
+// common test values
+// ==================
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+fn create_request(accept: Option<&str>) -> Request {
+    let headers = accept
+        .map(|accept| HashMap::from([("Accept".to_string(), accept.to_string())]))
+        .unwrap_or_default();
+    Request::new("/user", GET, None, HashMap::new()).with_headers(headers)
+}
+
+// =========
+// xml tests
+// =========
+#[test]
+fn from_json_should_write_objects_arrays_and_null_as_elements() {
+    let json = Json::parse(r#"{"name":"evan & co","tags":["a","b"],"age":30,"2fa":null}"#).unwrap();
+
+    let result = xml::from_json(&json);
+
+    assert_eq!(
+        format!(
+            "{XML_DECLARATION}<response><name>evan &amp; co</name><tags><item>a</item><item>b</item></tags><age>30</age><_2fa/></response>"
+        ),
+        result
+    );
+}
+
+#[test]
+fn get_xml_should_default_to_the_xml_of_get_json() {
+    assert_eq!(
+        format!("{XML_DECLARATION}<response>&lt;hi&gt;</response>"),
+        "<hi>".get_xml()
+    );
+    assert_eq!(
+        format!("{XML_DECLARATION}<response>true</response>"),
+        true.get_xml()
+    );
+}
+
+#[test]
+fn prefers_xml_should_rank_media_ranges_by_quality() {
+    assert!(!xml::prefers_xml(None));
+    assert!(!xml::prefers_xml(Some("*/*")));
+    assert!(!xml::prefers_xml(Some("application/json, application/xml")));
+    assert!(xml::prefers_xml(Some("application/xml")));
+    assert!(xml::prefers_xml(Some("text/xml, */*;q=0.1")));
+    assert!(!xml::prefers_xml(Some(
+        "application/xml;q=0.5, application/json"
+    )));
+}
+
+#[test]
+fn negotiate_should_answer_with_xml_when_the_client_prefers_it() {
+    let user = Json::Object(vec![("id".to_string(), Json::Number(7.0))]);
+
+    let xml_response = Response::negotiate(&user, &create_request(Some("application/xml")), false);
+    let json_response = Response::negotiate(&user, &create_request(None), true);
+
+    assert_eq!(
+        Some("application/xml; charset=utf-8"),
+        xml_response.get_header("Content-Type")
+    );
+    assert_eq!(
+        format!("{XML_DECLARATION}<response><id>7</id></response>"),
+        xml_response.content
+    );
+    assert_eq!(r#"{"id":7}"#, json_response.content);
+    assert_eq!(None, json_response.get_header("Content-Type"));
+    assert_eq!(Some("Accept"), json_response.get_header("Vary"));
+    assert_eq!(Some("Accept"), xml_response.get_header("Vary"));
+}